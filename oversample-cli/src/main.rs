@@ -0,0 +1,319 @@
+//! Headless analysis of recordings with the Oversample DSP core.
+//!
+//! Prints one JSON object per file (newline-delimited JSON) with the wSNR
+//! grade, bit-depth analysis, harmonics artifact findings and the measured
+//! call parameters, so a collection of recordings can be screened from a
+//! script or CI job without the GUI.
+
+use clap::{Parser, ValueEnum};
+use oversample_core::audio::loader::load_audio;
+use oversample_core::audio::source::ChannelView;
+use oversample_core::dsp::call_params::{self, CallMeasurement};
+use oversample_core::dsp::pulse_detect::PulseDetectionParams;
+use oversample_core::dsp::qc_report::{self, QcOptions};
+use oversample_core::dsp::smoothing::Smoothing;
+use oversample_core::dsp::{bit_analysis, harmonics};
+use serde_json::{json, Value};
+use std::path::{Path, PathBuf};
+
+/// File extensions picked up when a directory is given.
+const AUDIO_EXTENSIONS: &[&str] = &["wav", "w4v", "flac", "ogg", "mp3", "m4a", "m4b"];
+
+#[derive(Parser)]
+#[command(
+    name = "oversample-analyze",
+    about = "Analyse recordings and print one JSON object per file (NDJSON)"
+)]
+struct Cli {
+    /// Audio files or directories to analyse
+    #[arg(required = true)]
+    paths: Vec<PathBuf>,
+
+    /// Descend into subdirectories
+    #[arg(short, long)]
+    recursive: bool,
+
+    /// Analyse at most this many seconds from the start of each file (0 = whole file)
+    #[arg(long, default_value_t = 0.0)]
+    max_secs: f64,
+
+    /// Harmonics (including the fundamental) for the decay profile
+    #[arg(long, default_value_t = harmonics::DEFAULT_HARMONIC_COUNT)]
+    harmonics: usize,
+
+    /// Highest harmonic included in the decay-exponent fit
+    #[arg(long, default_value_t = harmonics::MAX_HARMONIC_COUNT)]
+    decay_fit_max: usize,
+
+    /// Decay-exponent fit method
+    #[arg(long, value_enum, default_value_t = DecayFitArg::Lsq)]
+    decay_fit_method: DecayFitArg,
+
+    /// Contour and flux smoothing
+    #[arg(long, value_enum, default_value_t = SmoothingArg::Off)]
+    smoothing: SmoothingArg,
+
+    /// Pretty-print each object (no longer one line per file)
+    #[arg(long)]
+    pretty: bool,
+}
+
+/// The smoothing choices the app offers.
+#[derive(Clone, Copy, ValueEnum)]
+enum SmoothingArg {
+    Off,
+    Median3,
+    Median5,
+    Median9,
+    Mean3,
+    Mean5,
+    Mean9,
+}
+
+impl From<SmoothingArg> for Smoothing {
+    fn from(arg: SmoothingArg) -> Self {
+        match arg {
+            SmoothingArg::Off => Smoothing::OFF,
+            SmoothingArg::Median3 => Smoothing::median(3),
+            SmoothingArg::Median5 => Smoothing::median(5),
+            SmoothingArg::Median9 => Smoothing::median(9),
+            SmoothingArg::Mean3 => Smoothing::moving_average(3),
+            SmoothingArg::Mean5 => Smoothing::moving_average(5),
+            SmoothingArg::Mean9 => Smoothing::moving_average(9),
+        }
+    }
+}
+
+/// Decay-exponent fit methods, by their settings keys.
+#[derive(Clone, Copy, ValueEnum)]
+enum DecayFitArg {
+    /// Least squares
+    Lsq,
+    /// Median of per-harmonic exponents
+    Robust,
+}
+
+impl From<DecayFitArg> for harmonics::DecayFitMethod {
+    fn from(arg: DecayFitArg) -> Self {
+        match arg {
+            DecayFitArg::Lsq => Self::LeastSquares,
+            DecayFitArg::Robust => Self::Robust,
+        }
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let smoothing = Smoothing::from(cli.smoothing);
+
+    let mut files = Vec::new();
+    for path in &cli.paths {
+        if path.is_dir() {
+            collect_audio_files(path, cli.recursive, &mut files);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut failures = 0;
+    for path in &files {
+        let record = match analyze_file(path, &cli, smoothing) {
+            Ok(v) => v,
+            Err(e) => {
+                failures += 1;
+                json!({ "file": path.display().to_string(), "error": e })
+            }
+        };
+        let line = if cli.pretty {
+            serde_json::to_string_pretty(&record)
+        } else {
+            serde_json::to_string(&record)
+        };
+        println!("{}", line.expect("JSON values always serialize"));
+    }
+
+    if failures > 0 {
+        eprintln!("{failures} of {} file(s) could not be analysed", files.len());
+        std::process::exit(1);
+    }
+}
+
+/// Audio files in `dir`, sorted by path so batch output is reproducible.
+fn collect_audio_files(dir: &Path, recursive: bool, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        eprintln!("Cannot read directory {}", dir.display());
+        return;
+    };
+    let mut paths: Vec<PathBuf> = entries.flatten().map(|e| e.path()).collect();
+    paths.sort();
+    for path in paths {
+        if path.is_dir() {
+            if recursive {
+                collect_audio_files(&path, recursive, out);
+            }
+        } else if is_audio_file(&path) {
+            out.push(path);
+        }
+    }
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| AUDIO_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+}
+
+fn analyze_file(path: &Path, cli: &Cli, smoothing: Smoothing) -> Result<Value, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Read failed: {e}"))?;
+    let audio = load_audio(&bytes)?;
+    let sr = audio.sample_rate;
+
+    let total = audio.source.total_samples() as usize;
+    let len = if cli.max_secs > 0.0 {
+        total.min((cli.max_secs * sr as f64) as usize)
+    } else {
+        total
+    };
+    let samples = audio.source.read_region(ChannelView::MonoMix, 0, len);
+    let analysed_secs = samples.len() as f64 / sr as f64;
+
+    let bits = bit_analysis::analyze_bits(
+        &samples,
+        audio.metadata.bits_per_sample,
+        audio.metadata.is_float,
+        analysed_secs,
+    );
+
+    let options = QcOptions {
+        harmonic_count: cli.harmonics,
+        smoothing,
+        decay_fit: harmonics::DecayFit {
+            last_harmonic: cli.decay_fit_max,
+            method: cli.decay_fit_method.into(),
+            ..harmonics::DecayFit::ALL_HARMONICS
+        },
+    };
+    let qc = qc_report::analyze(&samples, sr, &options);
+    let (w, h) = (&qc.wsnr, &qc.harmonics);
+
+    let params = PulseDetectionParams { contour_smoothing: smoothing, ..Default::default() };
+    let calls = call_params::measure_calls(&samples, sr, &params);
+
+    Ok(json!({
+        "file": path.display().to_string(),
+        "format": audio.metadata.format,
+        "sample_rate": sr,
+        "channels": audio.channels,
+        "bits_per_sample": audio.metadata.bits_per_sample,
+        "is_float": audio.metadata.is_float,
+        "duration_secs": audio.duration_secs,
+        "analysed_secs": analysed_secs,
+        "wsnr": {
+            "grade": w.grade.label(),
+            "snr_db": finite(w.snr_db),
+            "signal_db": finite(w.signal_db),
+            "noise_db": finite(w.noise_db),
+            "is_clipped": w.is_clipped,
+            "clipping_samples": w.clipping_samples,
+            "is_ultrasonic": w.is_ultrasonic,
+            "warnings": w.warnings,
+        },
+        "bits": {
+            "effective_bits": bits.effective_bits,
+            "effective_bits_estimate": finite(bits.effective_bits_f64),
+            "headroom_bits": bits.headroom_bits,
+            "noise_floor_db": finite(bits.noise_floor_db),
+            "summary": bits.summary,
+            "warnings": bits.warnings,
+        },
+        "harmonics": {
+            "phase_coherence_mean": h.phase_coherence_mean,
+            "coherence": h.coherence_verdict().label(),
+            "fundamental_hz": h.fundamental_freq,
+            "decay_exponent": h.decay_exponent,
+            "decay_fit_harmonics": h.decay_fit_indices.iter().map(|i| i + 1).collect::<Vec<_>>(),
+            "preringing_frames": h.preringing_count,
+            "staircasing_score": h.staircasing_score,
+            "findings": h.artifact_findings().collect::<Vec<_>>(),
+        },
+        "calls": calls.iter().map(call_json).collect::<Vec<_>>(),
+    }))
+}
+
+fn call_json(c: &CallMeasurement) -> Value {
+    let p = &c.pulse;
+    let mut v = json!({
+        "index": p.index,
+        "start_s": p.start_time,
+        "end_s": p.end_time,
+        "duration_ms": p.duration_ms(),
+        "snr_db": finite(p.local_snr_db),
+        "interval_ms": c.interval_ms,
+    });
+    if let Some(cp) = &c.params {
+        v["start_hz"] = json!(cp.start_freq_hz);
+        v["end_hz"] = json!(cp.end_freq_hz);
+        v["peak_hz"] = json!(cp.peak_freq_hz);
+        v["char_hz"] = json!(cp.char_freq_hz);
+        v["bandwidth_hz"] = json!(cp.bandwidth_hz);
+    }
+    v
+}
+
+/// JSON has no infinities; report them (e.g. the signal level of silence) as null.
+fn finite(x: f64) -> Option<f64> {
+    x.is_finite().then_some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Cli, clap::Error> {
+        Cli::try_parse_from(std::iter::once("oversample-analyze").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_defaults() {
+        let cli = parse(&["a.wav", "dir"]).unwrap();
+        assert_eq!(cli.paths, [PathBuf::from("a.wav"), PathBuf::from("dir")]);
+        assert!(!cli.recursive && !cli.pretty);
+        assert_eq!(cli.max_secs, 0.0);
+        assert_eq!(cli.harmonics, harmonics::DEFAULT_HARMONIC_COUNT);
+        assert_eq!(cli.decay_fit_max, harmonics::MAX_HARMONIC_COUNT);
+        assert!(matches!(cli.decay_fit_method, DecayFitArg::Lsq));
+        assert_eq!(Smoothing::from(cli.smoothing), Smoothing::OFF);
+    }
+
+    #[test]
+    fn test_options() {
+        let cli = parse(&[
+            "-r", "--max-secs", "2.5", "--harmonics", "6", "--decay-fit-max", "4",
+            "--decay-fit-method", "robust", "--smoothing", "median5", "--pretty", "x.flac",
+        ])
+        .unwrap();
+        assert!(cli.recursive && cli.pretty);
+        assert_eq!(cli.max_secs, 2.5);
+        assert_eq!((cli.harmonics, cli.decay_fit_max), (6, 4));
+        assert_eq!(harmonics::DecayFitMethod::from(cli.decay_fit_method), harmonics::DecayFitMethod::Robust);
+        assert_eq!(Smoothing::from(cli.smoothing), Smoothing::median(5));
+        assert_eq!(Smoothing::from(SmoothingArg::Mean9), Smoothing::moving_average(9));
+    }
+
+    #[test]
+    fn test_rejected_arguments() {
+        // At least one path is required
+        assert!(parse(&[]).is_err());
+        assert!(parse(&["--smoothing", "median4", "a.wav"]).is_err());
+        assert!(parse(&["--decay-fit-method", "lsqr", "a.wav"]).is_err());
+        assert!(parse(&["--max-secs", "soon", "a.wav"]).is_err());
+    }
+
+    #[test]
+    fn test_audio_extensions() {
+        assert!(is_audio_file(Path::new("night/XC1234.WAV")));
+        assert!(is_audio_file(Path::new("a.m4b")));
+        assert!(!is_audio_file(Path::new("XC1234.xc.json")));
+        assert!(!is_audio_file(Path::new("wav")));
+    }
+}
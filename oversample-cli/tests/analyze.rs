@@ -0,0 +1,105 @@
+//! Runs the `oversample-analyze` binary on generated WAV files and checks
+//! the NDJSON it prints.
+
+use oversample_core::audio::convert::{encode_wav, SampleFormat};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+#[path = "../../oversample-core/src/test_signals.rs"]
+mod test_signals;
+
+use test_signals::{len_of, mix_at, noise, tone_secs};
+
+const SR: u32 = 192_000;
+
+/// A fresh scratch directory for one test.
+fn scratch_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("oversample-cli-{}-{name}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Half a second of faint noise with three 5 ms, 45 kHz calls 100 ms apart.
+fn three_calls() -> Vec<f32> {
+    let mut samples = noise(len_of(SR, 0.5), 0.002, 7);
+    let call = tone_secs(45_000.0, SR, 0.005, 0.5);
+    for t in [0.1, 0.2, 0.3] {
+        mix_at(&mut samples, &call, len_of(SR, t));
+    }
+    samples
+}
+
+fn analyze(args: &[&std::ffi::OsStr]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_oversample-analyze")).args(args).output().unwrap()
+}
+
+fn records(output: &Output) -> Vec<Value> {
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn reports_generated_calls() {
+    let dir = scratch_dir("calls");
+    let wav = dir.join("calls.wav");
+    std::fs::write(&wav, encode_wav(&three_calls(), SR, SampleFormat::Int16).unwrap()).unwrap();
+
+    let output = analyze(&[wav.as_os_str()]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    let found = records(&output);
+    assert_eq!(found.len(), 1);
+    let r = &found[0];
+    assert_eq!(r["sample_rate"], SR);
+    assert_eq!(r["channels"], 1);
+    assert_eq!(r["bits_per_sample"], 16);
+    assert!((r["duration_secs"].as_f64().unwrap() - 0.5).abs() < 1e-3);
+    assert!(r["wsnr"]["grade"].is_string());
+
+    let calls = r["calls"].as_array().unwrap();
+    assert_eq!(calls.len(), 3, "{calls:?}");
+    for call in calls {
+        let peak = call["peak_hz"].as_f64().unwrap();
+        assert!((peak - 45_000.0).abs() < 1_000.0, "peak {peak}");
+    }
+    let ipi = calls[1]["interval_ms"].as_f64().unwrap();
+    assert!((ipi - 100.0).abs() < 2.0, "interval {ipi}");
+
+    // --max-secs stops before the last call
+    let output = analyze(&["--max-secs".as_ref(), "0.25".as_ref(), wav.as_os_str()]);
+    let r = &records(&output)[0];
+    assert!((r["analysed_secs"].as_f64().unwrap() - 0.25).abs() < 1e-3);
+    assert_eq!(r["calls"].as_array().unwrap().len(), 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn scans_directories_and_reports_failures() {
+    let dir = scratch_dir("scan");
+    let wav = encode_wav(&tone_secs(20_000.0, SR, 0.1, 0.5), SR, SampleFormat::Int16).unwrap();
+    std::fs::create_dir(dir.join("sub")).unwrap();
+    std::fs::write(dir.join("b.wav"), &wav).unwrap();
+    std::fs::write(dir.join("sub").join("a.wav"), &wav).unwrap();
+    std::fs::write(dir.join("notes.txt"), "not audio").unwrap();
+
+    // Only the top level without -r; sorted by path with it
+    let output = analyze(&[dir.as_os_str()]);
+    assert!(output.status.success());
+    assert_eq!(records(&output).len(), 1);
+    let output = analyze(&["-r".as_ref(), dir.as_os_str()]);
+    let files: Vec<String> = records(&output).iter().map(|r| r["file"].as_str().unwrap().to_string()).collect();
+    assert_eq!(files.len(), 2);
+    assert!(files[0].ends_with("b.wav") && files[1].ends_with("a.wav"), "{files:?}");
+
+    // An unreadable file gets an error record and a failing exit status
+    let missing = dir.join("missing.wav");
+    let output = analyze(&[missing.as_os_str()]);
+    assert!(!output.status.success());
+    assert!(records(&output)[0]["error"].is_string());
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,339 @@
+//! Normalize decoded audio to a uniform WAV format (mono, fixed sample rate
+//! and sample format), e.g. to batch-convert cached recordings into a dataset.
+
+use std::io::Cursor;
+
+use crate::audio::loader::load_audio;
+use crate::audio::source::ExactPcm;
+use crate::dsp::resample::resample_sinc;
+
+/// Sample encoding of the converted WAV.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SampleFormat {
+    Int16,
+    Int24,
+    Float32,
+}
+
+impl SampleFormat {
+    pub const ALL: [SampleFormat; 3] = [SampleFormat::Int16, SampleFormat::Int24, SampleFormat::Float32];
+
+    pub fn bits(self) -> u16 {
+        match self {
+            SampleFormat::Int16 => 16,
+            SampleFormat::Int24 => 24,
+            SampleFormat::Float32 => 32,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SampleFormat::Int16 => "16-bit PCM",
+            SampleFormat::Int24 => "24-bit PCM",
+            SampleFormat::Float32 => "32-bit float",
+        }
+    }
+
+    /// Short key used on the command line and in settings: "16", "24" or "f32".
+    pub fn key(self) -> &'static str {
+        match self {
+            SampleFormat::Int16 => "16",
+            SampleFormat::Int24 => "24",
+            SampleFormat::Float32 => "f32",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<SampleFormat> {
+        match key.trim().to_ascii_lowercase().as_str() {
+            "16" | "i16" => Some(SampleFormat::Int16),
+            "24" | "i24" => Some(SampleFormat::Int24),
+            "32" | "f32" | "float" => Some(SampleFormat::Float32),
+            _ => None,
+        }
+    }
+}
+
+/// Target of a conversion.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ConvertSpec {
+    pub sample_rate: u32,
+    pub format: SampleFormat,
+}
+
+/// Resample `samples` from `from_rate` to `to_rate` with the windowed-sinc
+/// resampler, which filters out content above the new Nyquist frequency when
+/// downsampling. The output has `len * to_rate / from_rate` samples, rounded.
+pub fn resample(samples: &[f32], from_rate: u32, to_rate: u32) -> Vec<f32> {
+    if from_rate == to_rate || from_rate == 0 || to_rate == 0 || samples.is_empty() {
+        return samples.to_vec();
+    }
+    let out_len = (samples.len() as f64 * to_rate as f64 / from_rate as f64).round().max(1.0) as usize;
+    let mut out = resample_sinc(samples, from_rate as f64, to_rate as f64);
+    out.resize(out_len, 0.0);
+    out
+}
+
+/// Encode mono samples as a WAV file. Integer formats are clamped to ±1.0.
+pub fn encode_wav(samples: &[f32], sample_rate: u32, format: SampleFormat) -> Result<Vec<u8>, String> {
+    encode_wav_dithered(samples, sample_rate, format, false)
+}
+
+/// Triangular (TPDF) dither source: the sum of two independent uniform
+/// values of ±½ LSB each, so ±1 LSB peak. Added before rounding it makes the
+/// quantisation error signal-independent white noise of 1/4 LSB² variance,
+/// where plain rounding leaves distortion correlated with quiet signals.
+struct TpdfDither {
+    state: u64,
+}
+
+impl TpdfDither {
+    fn new() -> Self {
+        Self { state: 0x9E37_79B9_7F4A_7C15 }
+    }
+
+    /// Uniform in [-0.5, 0.5) (xorshift64*).
+    fn uniform(&mut self) -> f64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        let bits = self.state.wrapping_mul(0x2545_F491_4F6C_DD1D) >> 11;
+        bits as f64 / (1u64 << 53) as f64 - 0.5
+    }
+
+    /// Next dither value in LSBs.
+    fn next(&mut self) -> f64 {
+        self.uniform() + self.uniform()
+    }
+}
+
+/// Scale samples to integers of `bits` width, rounding with optional TPDF
+/// dither. The scale is the loader's (2^(bits-1) per 1.0), so undithered
+/// integer samples read by the loader are written back unchanged. Input is
+/// clamped to ±1.0 and the result to the format's range.
+fn quantize(samples: &[f32], bits: u16, dither: bool) -> Vec<i32> {
+    let scale = (1i64 << (bits - 1)) as f64;
+    let mut tpdf = dither.then(TpdfDither::new);
+    samples
+        .iter()
+        .map(|&s| {
+            let scaled = s.clamp(-1.0, 1.0) as f64 * scale;
+            let d = tpdf.as_mut().map_or(0.0, |t| t.next());
+            (scaled + d).round().clamp(-scale, scale - 1.0) as i32
+        })
+        .collect()
+}
+
+/// Encode mono samples as a WAV file, optionally applying TPDF dither when
+/// reducing to an integer format. Float output is written as-is.
+pub fn encode_wav_dithered(samples: &[f32], sample_rate: u32, format: SampleFormat, dither: bool) -> Result<Vec<u8>, String> {
+    let spec = hound::WavSpec {
+        channels: 1,
+        sample_rate,
+        bits_per_sample: format.bits(),
+        sample_format: match format {
+            SampleFormat::Float32 => hound::SampleFormat::Float,
+            _ => hound::SampleFormat::Int,
+        },
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).map_err(|e| format!("WAV writer error: {e}"))?;
+    let write_err = |e: hound::Error| format!("WAV write error: {e}");
+    match format {
+        SampleFormat::Int16 => {
+            for v in quantize(samples, 16, dither) {
+                writer.write_sample(v as i16).map_err(write_err)?;
+            }
+        }
+        SampleFormat::Int24 => {
+            for v in quantize(samples, 24, dither) {
+                writer.write_sample(v).map_err(write_err)?;
+            }
+        }
+        SampleFormat::Float32 => {
+            for &s in samples {
+                writer.write_sample(s).map_err(write_err)?;
+            }
+        }
+    }
+    writer.finalize().map_err(|e| format!("WAV finalize error: {e}"))?;
+    Ok(cursor.into_inner())
+}
+
+/// Encode `len` frames of original integer samples, starting at frame `start`,
+/// as an integer WAV at the source's bit depth and channel count. No sample
+/// passes through f32, so the audio is bit-identical to that span of the source.
+pub fn encode_exact_wav(pcm: &ExactPcm, start: usize, len: usize, sample_rate: u32) -> Result<Vec<u8>, String> {
+    let spec = hound::WavSpec {
+        channels: pcm.channels as u16,
+        sample_rate,
+        bits_per_sample: pcm.bits_per_sample,
+        sample_format: hound::SampleFormat::Int,
+    };
+
+    let mut cursor = Cursor::new(Vec::new());
+    let mut writer = hound::WavWriter::new(&mut cursor, spec).map_err(|e| format!("WAV writer error: {e}"))?;
+    for &s in pcm.frames(start, len) {
+        writer.write_sample(s).map_err(|e| format!("WAV write error: {e}"))?;
+    }
+    writer.finalize().map_err(|e| format!("WAV finalize error: {e}"))?;
+    Ok(cursor.into_inner())
+}
+
+/// Decode any supported file, mix to mono, resample and re-encode as WAV.
+pub fn convert_bytes(bytes: &[u8], spec: ConvertSpec) -> Result<Vec<u8>, String> {
+    let audio = load_audio(bytes)?;
+    let samples = resample(&audio.samples, audio.sample_rate, spec.sample_rate);
+    encode_wav(&samples, spec.sample_rate, spec.format)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::{rms, tone_secs};
+
+    fn tone(freq: f64, sr: u32, secs: f64) -> Vec<f32> {
+        tone_secs(freq, sr, secs, 0.5)
+    }
+
+    #[test]
+    fn test_resample_length_and_passband() {
+        let src = tone(1_000.0, 48_000, 0.5);
+        let up = resample(&src, 48_000, 96_000);
+        assert_eq!(up.len(), src.len() * 2);
+        let down = resample(&src, 48_000, 44_100);
+        assert_eq!(down.len(), 22_050);
+        // A tone well below the new Nyquist keeps most of its level
+        assert!(rms(&down[1000..]) > 0.3);
+    }
+
+    #[test]
+    fn test_resample_attenuates_above_nyquist() {
+        // 40 kHz is above the 24 kHz Nyquist of the 48 kHz output
+        let src = tone(40_000.0, 192_000, 0.25);
+        let down = resample(&src, 192_000, 48_000);
+        assert!(rms(&down[500..down.len() - 500]) < 0.01 * rms(&src));
+    }
+
+    #[test]
+    fn test_round_trip_formats() {
+        let src = tone(5_000.0, 96_000, 0.1);
+        let wav = encode_wav(&src, 96_000, SampleFormat::Float32).unwrap();
+        for format in SampleFormat::ALL {
+            let out = convert_bytes(&wav, ConvertSpec { sample_rate: 48_000, format }).unwrap();
+            let audio = load_audio(&out).unwrap();
+            assert_eq!(audio.sample_rate, 48_000);
+            assert_eq!(audio.channels, 1);
+            assert_eq!(audio.metadata.bits_per_sample, format.bits());
+            assert_eq!(audio.samples.len(), src.len() / 2);
+        }
+    }
+
+    fn int_wav(values: &[i32], channels: u16, bits: u16) -> Vec<u8> {
+        let spec = hound::WavSpec { channels, sample_rate: 48_000, bits_per_sample: bits, sample_format: hound::SampleFormat::Int };
+        let mut cursor = Cursor::new(Vec::new());
+        let mut writer = hound::WavWriter::new(&mut cursor, spec).unwrap();
+        for &v in values {
+            writer.write_sample(v).unwrap();
+        }
+        writer.finalize().unwrap();
+        cursor.into_inner()
+    }
+
+    #[test]
+    fn test_exact_samples_round_trip_bit_identical() {
+        // Full-scale extremes and odd LSBs, which f32 scaling can nudge
+        let values_24 = [-(1 << 23), (1 << 23) - 1, 1, -1, 8_388_001, -4_194_303];
+        let values_16 = [i16::MIN as i32, i16::MAX as i32, 1, -1, 12_345, -777];
+        for (values, channels, bits) in [(&values_24[..], 1, 24), (&values_16[..], 2, 16)] {
+            let wav = int_wav(values, channels, bits);
+
+            let audio = crate::audio::loader::load_audio_exact(&wav).unwrap();
+            let pcm = audio.source.exact_pcm().expect("integer WAV keeps exact samples");
+            assert_eq!(pcm.samples.as_slice(), values);
+            let out = encode_exact_wav(pcm, 0, pcm.frame_count(), 48_000).unwrap();
+            assert_eq!(out, wav);
+
+            // A sub-range holds exactly those frames
+            let sub = encode_exact_wav(pcm, 1, 1, 48_000).unwrap();
+            let ch = channels as usize;
+            assert_eq!(exact_samples_of(&sub), &values[ch..2 * ch]);
+        }
+
+        // Only on request
+        let plain = load_audio(&int_wav(&values_16, 1, 16)).unwrap();
+        assert!(plain.source.exact_pcm().is_none());
+    }
+
+    #[test]
+    fn test_float_path_round_trips_unprocessed_samples() {
+        // The processed path writes the same integers back when nothing changed them
+        let values = [-(1 << 23), (1 << 23) - 1, 1, -1, 8_388_001, -4_194_303];
+        let audio = load_audio(&int_wav(&values, 1, 24)).unwrap();
+        let out = encode_wav(&audio.samples, 48_000, SampleFormat::Int24).unwrap();
+        assert_eq!(exact_samples_of(&out), values);
+        // Full scale clamps to the largest sample rather than wrapping
+        let out = encode_wav(&[1.0, -1.0], 48_000, SampleFormat::Int16).unwrap();
+        assert_eq!(exact_samples_of(&out), [i16::MAX as i32, i16::MIN as i32]);
+    }
+
+    fn exact_samples_of(wav: &[u8]) -> Vec<i32> {
+        let audio = crate::audio::loader::load_audio_exact(wav).unwrap();
+        audio.source.exact_pcm().unwrap().samples.to_vec()
+    }
+
+    #[test]
+    fn test_tpdf_dither_error_is_flat_white_noise() {
+        // Total error (dither + rounding) should be zero-mean with 1/4 LSB²
+        // variance whatever the input level, and uncorrelated sample to sample.
+        let lsb = 1.0 / i16::MAX as f64;
+        for offset in [0.0, 0.25, 0.5, 0.8] {
+            let samples = vec![(offset * lsb) as f32; 200_000];
+            let q = quantize(&samples, 16, true);
+            let err: Vec<f64> = q.iter().zip(&samples).map(|(&v, &s)| v as f64 - s as f64 / lsb).collect();
+            let n = err.len() as f64;
+            let mean = err.iter().sum::<f64>() / n;
+            let var = err.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / n;
+            let lag1 = err.windows(2).map(|w| (w[0] - mean) * (w[1] - mean)).sum::<f64>() / n / var;
+            assert!(mean.abs() < 0.01, "offset {offset}: mean {mean}");
+            assert!((var - 0.25).abs() < 0.01, "offset {offset}: variance {var}");
+            assert!(lag1.abs() < 0.02, "offset {offset}: lag-1 correlation {lag1}");
+        }
+        // The dither itself peaks at ±1 LSB
+        let mut tpdf = TpdfDither::new();
+        assert!((0..100_000).all(|_| tpdf.next().abs() <= 1.0));
+    }
+
+    #[test]
+    fn test_dither_preserves_sub_lsb_signal() {
+        // A tone of 0.4 LSB rounds to silence without dither, but its average
+        // survives the dithered quantisation.
+        let lsb = 1.0 / i16::MAX as f32;
+        let samples = crate::test_signals::tone(1_000.0, 96_000, 96_000, 0.4 * lsb);
+        assert!(quantize(&samples, 16, false).iter().all(|&v| v == 0));
+        let dithered = quantize(&samples, 16, true);
+        // Correlate against the tone to recover its amplitude (in LSBs)
+        let amp = 2.0 * dithered.iter().zip(&samples).map(|(&v, &s)| v as f64 * (s / lsb) as f64 / 0.4).sum::<f64>() / samples.len() as f64;
+        assert!((amp - 0.4).abs() < 0.05, "recovered amplitude {amp}");
+    }
+
+    #[test]
+    fn test_dithered_formats_round_trip() {
+        let src = tone(5_000.0, 48_000, 0.05);
+        for format in SampleFormat::ALL {
+            let wav = encode_wav_dithered(&src, 48_000, format, true).unwrap();
+            let audio = load_audio(&wav).unwrap();
+            assert_eq!(audio.metadata.bits_per_sample, format.bits());
+            let err: Vec<f32> = audio.samples.iter().zip(&src).map(|(a, b)| a - b).collect();
+            assert!(rms(&err) < 1e-4, "{format:?}: error rms {}", rms(&err));
+        }
+    }
+
+    #[test]
+    fn test_format_keys() {
+        for format in SampleFormat::ALL {
+            assert_eq!(SampleFormat::from_key(format.key()), Some(format));
+        }
+        assert_eq!(SampleFormat::from_key("8"), None);
+    }
+}
@@ -123,6 +123,10 @@ pub struct RecordingGuanoExtra {
     /// Pre-roll duration in seconds (listen buffer captured before user pressed record).
     /// None or 0.0 = no pre-roll.
     pub preroll_secs: Option<f64>,
+    /// Survey site name from the recording settings.
+    pub site: Option<String>,
+    /// Person making the recording, from the recording settings.
+    pub operator: Option<String>,
 }
 
 /// Build GUANO metadata for a recording.
@@ -228,6 +232,18 @@ pub fn build_recording_guano(
         }
     }
 
+    // Survey context from the recording settings
+    if let Some(ref site) = extra.site {
+        if !site.is_empty() {
+            g.add("Oversample|Survey|Site", site);
+        }
+    }
+    if let Some(ref operator) = extra.operator {
+        if !operator.is_empty() {
+            g.add("Oversample|Survey|Operator", operator);
+        }
+    }
+
     // Pre-roll: seconds of listen buffer captured before the user pressed record.
     if let Some(preroll) = extra.preroll_secs {
         if preroll > 0.0 {
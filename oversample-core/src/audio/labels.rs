@@ -0,0 +1,86 @@
+//! Label files for other annotation tools: Raven Pro selection tables and
+//! Audacity label tracks.
+//!
+//! Both are plain tab-separated text. A Raven selection table has a header
+//! row and one numbered row per box; every box needs a frequency range, so
+//! time-only labels span the whole band. Audacity writes one
+//! `start<TAB>end<TAB>label` line per label, optionally followed by a
+//! `\<TAB>low<TAB>high` line giving the label's spectral selection.
+
+/// One labelled box or point in time. A point has `end == start`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LabelBox {
+    pub start: f64,
+    pub end: f64,
+    pub freq_low: Option<f64>,
+    pub freq_high: Option<f64>,
+    pub label: String,
+}
+
+/// Tabs and newlines would break the row structure of either format.
+fn clean_label(label: &str) -> String {
+    label.replace(['\t', '\r', '\n'], " ")
+}
+
+/// Raven selection table (`*.selections.txt`). Boxes without frequency
+/// bounds span 0 Hz to `nyquist`.
+pub fn raven_selection_table(boxes: &[LabelBox], nyquist: f64) -> String {
+    let mut out = String::from(
+        "Selection\tView\tChannel\tBegin Time (s)\tEnd Time (s)\tLow Freq (Hz)\tHigh Freq (Hz)\tAnnotation\n",
+    );
+    for (i, b) in boxes.iter().enumerate() {
+        let low = b.freq_low.unwrap_or(0.0);
+        let high = b.freq_high.unwrap_or(nyquist);
+        out.push_str(&format!(
+            "{}\tSpectrogram 1\t1\t{:.6}\t{:.6}\t{:.1}\t{:.1}\t{}\n",
+            i + 1, b.start, b.end, low, high, clean_label(&b.label),
+        ));
+    }
+    out
+}
+
+/// Audacity label track export. The spectral line is only written when
+/// the box has both frequency bounds.
+pub fn audacity_labels(boxes: &[LabelBox]) -> String {
+    let mut out = String::new();
+    for b in boxes {
+        out.push_str(&format!("{:.6}\t{:.6}\t{}\n", b.start, b.end, clean_label(&b.label)));
+        if let (Some(low), Some(high)) = (b.freq_low, b.freq_high) {
+            out.push_str(&format!("\\\t{low:.1}\t{high:.1}\n"));
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boxes() -> Vec<LabelBox> {
+        vec![
+            LabelBox { start: 1.5, end: 2.25, freq_low: Some(20_000.0), freq_high: Some(45_000.0), label: "Myotis\tcall".into() },
+            LabelBox { start: 3.0, end: 3.0, freq_low: None, freq_high: None, label: "Bookmark".into() },
+        ]
+    }
+
+    #[test]
+    fn test_raven_table_rows() {
+        let table = raven_selection_table(&boxes(), 96_000.0);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("Selection\tView\tChannel\tBegin Time (s)"));
+        assert_eq!(lines[1], "1\tSpectrogram 1\t1\t1.500000\t2.250000\t20000.0\t45000.0\tMyotis call");
+        assert_eq!(lines[2], "2\tSpectrogram 1\t1\t3.000000\t3.000000\t0.0\t96000.0\tBookmark");
+        assert!(lines.iter().all(|l| l.split('\t').count() == 8));
+    }
+
+    #[test]
+    fn test_audacity_spectral_lines() {
+        let labels = audacity_labels(&boxes());
+        assert_eq!(
+            labels,
+            "1.500000\t2.250000\tMyotis call\n\\\t20000.0\t45000.0\n3.000000\t3.000000\tBookmark\n",
+        );
+        assert!(audacity_labels(&[]).is_empty());
+    }
+}
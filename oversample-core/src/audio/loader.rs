@@ -1,5 +1,6 @@
 use crate::audio::guano::{self, parse_guano, GuanoMetadata};
-use crate::audio::source::InMemorySource;
+use crate::audio::tags;
+use crate::audio::source::{ExactPcm, InMemorySource};
 use crate::types::{AudioData, FileMetadata, WavMarker};
 use std::io::Cursor;
 use std::sync::Arc;
@@ -9,7 +10,11 @@ use std::sync::Arc;
 pub struct WavHeader {
     pub sample_rate: u32,
     pub channels: u16,
+    /// Container size of each sample; use this for byte offsets and decoding.
     pub bits_per_sample: u16,
+    /// Significant bits per sample. Smaller than `bits_per_sample` when a
+    /// WAVE_FORMAT_EXTENSIBLE header pads samples (e.g. 24-bit audio in 32-bit slots).
+    pub valid_bits_per_sample: u16,
     pub is_float: bool,
     pub data_offset: u64,       // byte offset of PCM "data" chunk body within file
     pub data_size: u64,         // byte length of PCM data
@@ -19,6 +24,72 @@ pub struct WavHeader {
     pub wav_markers: Vec<WavMarker>,
 }
 
+const WAVE_FORMAT_PCM: u16 = 1;
+const WAVE_FORMAT_IEEE_FLOAT: u16 = 3;
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// Bytes 2..16 shared by all `KSDATAFORMAT_SUBTYPE_*` GUIDs; bytes 0..2 hold the
+/// plain format tag (1 = PCM, 3 = IEEE float).
+const KSDATAFORMAT_GUID_TAIL: [u8; 14] = [
+    0x00, 0x00, 0x00, 0x00, 0x10, 0x00, 0x80, 0x00, 0x00, 0xAA, 0x00, 0x38, 0x9B, 0x71,
+];
+
+/// Decoded `fmt ` chunk, with WAVE_FORMAT_EXTENSIBLE resolved to its subformat.
+#[derive(Clone, Copy, Debug)]
+struct WavFmt {
+    /// Effective format tag: the subformat for extensible headers.
+    format_tag: u16,
+    channels: u16,
+    sample_rate: u32,
+    bits_per_sample: u16,
+    valid_bits_per_sample: u16,
+    extensible: bool,
+}
+
+/// Parse a `fmt ` chunk body (at least 16 bytes).
+fn parse_fmt_chunk(fmt: &[u8]) -> Result<WavFmt, String> {
+    let raw_tag = u16::from_le_bytes([fmt[0], fmt[1]]);
+    let channels = u16::from_le_bytes([fmt[2], fmt[3]]);
+    let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
+    let bits_per_sample = u16::from_le_bytes([fmt[14], fmt[15]]);
+
+    if raw_tag != WAVE_FORMAT_EXTENSIBLE {
+        return Ok(WavFmt {
+            format_tag: raw_tag,
+            channels,
+            sample_rate,
+            bits_per_sample,
+            valid_bits_per_sample: bits_per_sample,
+            extensible: false,
+        });
+    }
+
+    // cbSize(2) + wValidBitsPerSample(2) + dwChannelMask(4) + SubFormat GUID(16)
+    if fmt.len() < 40 {
+        return Err("WAVE_FORMAT_EXTENSIBLE fmt chunk too small".into());
+    }
+    let valid_bits = u16::from_le_bytes([fmt[18], fmt[19]]);
+    let guid = &fmt[24..40];
+    if guid[2..] != KSDATAFORMAT_GUID_TAIL {
+        return Err("Unsupported WAVE_FORMAT_EXTENSIBLE subformat".into());
+    }
+    let subformat = u16::from_le_bytes([guid[0], guid[1]]);
+    // Some writers leave wValidBitsPerSample at 0; fall back to the container size
+    let valid_bits_per_sample = if valid_bits == 0 || valid_bits > bits_per_sample {
+        bits_per_sample
+    } else {
+        valid_bits
+    };
+    Ok(WavFmt {
+        format_tag: subformat,
+        channels,
+        sample_rate,
+        bits_per_sample,
+        valid_bits_per_sample,
+        extensible: true,
+    })
+}
+
 /// Parse only the WAV header from the given bytes (typically first 8-64KB of file).
 /// Returns enough metadata to open the file for streaming without decoding all samples.
 ///
@@ -46,7 +117,7 @@ pub fn parse_wav_header_with_file_size(header_bytes: &[u8], file_size: Option<u6
     }
 
     let mut pos = 12usize;
-    let mut fmt_chunk: Option<(u16, u32, u16, u16)> = None; // (format_tag, sample_rate, channels, bits)
+    let mut fmt_chunk: Option<WavFmt> = None;
     let mut data_offset: Option<u64> = None;
     let mut data_size: Option<u64> = None;
     let mut guano: Option<GuanoMetadata> = None;
@@ -85,12 +156,7 @@ pub fn parse_wav_header_with_file_size(header_bytes: &[u8], file_size: Option<u6
                     return Err("fmt chunk too small or truncated".into());
                 }
                 let body_end = body_end_u64 as usize;
-                let fmt = &header_bytes[body_start..body_end];
-                let format_tag = u16::from_le_bytes([fmt[0], fmt[1]]);
-                let channels = u16::from_le_bytes([fmt[2], fmt[3]]);
-                let sample_rate = u32::from_le_bytes([fmt[4], fmt[5], fmt[6], fmt[7]]);
-                let bits_per_sample = u16::from_le_bytes([fmt[14], fmt[15]]);
-                fmt_chunk = Some((format_tag, sample_rate, channels, bits_per_sample));
+                fmt_chunk = Some(parse_fmt_chunk(&header_bytes[body_start..body_end])?);
             }
             b"data" => {
                 data_offset = Some(body_start as u64);
@@ -153,14 +219,14 @@ pub fn parse_wav_header_with_file_size(header_bytes: &[u8], file_size: Option<u6
         }
     }
 
-    let (format_tag, sample_rate, channels, bits_per_sample) =
+    let WavFmt { format_tag, sample_rate, channels, bits_per_sample, valid_bits_per_sample, .. } =
         fmt_chunk.ok_or("No fmt chunk found in WAV header")?;
     let data_offset = data_offset.ok_or("No data chunk found in WAV header")?;
     let mut data_size = data_size.ok_or("No data chunk found in WAV header")?;
 
     // format_tag: 1 = PCM integer, 3 = IEEE float
-    let is_float = format_tag == 3;
-    if format_tag != 1 && format_tag != 3 {
+    let is_float = format_tag == WAVE_FORMAT_IEEE_FLOAT;
+    if format_tag != WAVE_FORMAT_PCM && format_tag != WAVE_FORMAT_IEEE_FLOAT {
         return Err(format!("Unsupported WAV format tag: {}", format_tag));
     }
 
@@ -202,6 +268,7 @@ pub fn parse_wav_header_with_file_size(header_bytes: &[u8], file_size: Option<u6
         sample_rate,
         channels,
         bits_per_sample,
+        valid_bits_per_sample,
         is_float,
         data_offset,
         data_size,
@@ -415,14 +482,25 @@ pub fn parse_wav_markers(bytes: &[u8]) -> Vec<WavMarker> {
 }
 
 pub fn load_audio(bytes: &[u8]) -> Result<AudioData, String> {
+    load_audio_with(bytes, false)
+}
+
+/// Like `load_audio`, but integer WAV and FLAC files also keep their original
+/// samples (`AudioSource::exact_pcm`) for lossless clip export, at the cost of
+/// another 4 bytes per sample in memory. Other formats load as usual.
+pub fn load_audio_exact(bytes: &[u8]) -> Result<AudioData, String> {
+    load_audio_with(bytes, true)
+}
+
+fn load_audio_with(bytes: &[u8], keep_exact: bool) -> Result<AudioData, String> {
     if bytes.len() < 4 {
         return Err("File too small".into());
     }
 
     match &bytes[0..4] {
         b"RIFF" | b"RF64" if is_w4v(bytes) => load_w4v(bytes),
-        b"RIFF" | b"RF64" => load_wav(bytes),
-        b"fLaC" => load_flac(bytes),
+        b"RIFF" | b"RF64" => load_wav(bytes, keep_exact),
+        b"fLaC" => load_flac(bytes, keep_exact),
         b"OggS" => load_ogg(bytes),
         _ if is_m4a(bytes) => load_m4a(bytes),
         _ if is_mp3(bytes) => load_mp3(bytes),
@@ -745,6 +823,10 @@ pub fn parse_ogg_header(header_bytes: &[u8], file_size: u64) -> Result<OggHeader
 /// Hound 3.5 doesn't handle RIFF word-alignment padding on odd-length chunks
 /// (e.g. a 651-byte `bext` chunk), so we strip extraneous chunks and produce
 /// a clean WAV that hound can always parse.
+///
+/// WAVE_FORMAT_EXTENSIBLE headers are rewritten as plain PCM/float at the
+/// container size. Hound would otherwise read e.g. 24-in-32-bit samples as
+/// right-justified 24-bit values, but the valid bits are left-justified.
 fn normalize_riff(bytes: &[u8]) -> Option<Vec<u8>> {
     if bytes.len() < 12 || &bytes[8..12] != b"WAVE" {
         return None;
@@ -785,6 +867,17 @@ fn normalize_riff(bytes: &[u8]) -> Option<Vec<u8>> {
     let fmt = fmt_data?;
     let data = audio_data?;
 
+    let basic_fmt;
+    let fmt = match (fmt.len() >= 16).then(|| parse_fmt_chunk(fmt)) {
+        Some(Ok(info)) if info.extensible => {
+            let mut f = fmt[..16].to_vec();
+            f[0..2].copy_from_slice(&info.format_tag.to_le_bytes());
+            basic_fmt = f;
+            &basic_fmt[..]
+        }
+        _ => fmt,
+    };
+
     // WAVE + fmt chunk header + fmt body + data chunk header + data body
     let riff_body_len = 4 + 8 + fmt.len() + 8 + data.len();
     let mut out = Vec::with_capacity(12 + riff_body_len - 4);
@@ -806,11 +899,11 @@ fn load_w4v(bytes: &[u8]) -> Result<AudioData, String> {
     let channels = header.channels as u32;
     let sample_rate = header.sample_rate;
 
-    let (samples, source) = build_source(all_samples, channels, sample_rate);
+    let (samples, source) = build_source(all_samples, channels, sample_rate, None);
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -827,11 +920,11 @@ fn load_w4v(bytes: &[u8]) -> Result<AudioData, String> {
     })
 }
 
-fn load_wav(bytes: &[u8]) -> Result<AudioData, String> {
+fn load_wav(bytes: &[u8], keep_exact: bool) -> Result<AudioData, String> {
     // Parse original header for data_offset/data_size before normalization
-    let (orig_data_offset, orig_data_size) = parse_wav_header_with_file_size(bytes, Some(bytes.len() as u64))
-        .map(|h| (Some(h.data_offset), Some(h.data_size)))
-        .unwrap_or((None, None));
+    let orig_header = parse_wav_header_with_file_size(bytes, Some(bytes.len() as u64)).ok();
+    let orig_data_offset = orig_header.as_ref().map(|h| h.data_offset);
+    let orig_data_size = orig_header.as_ref().map(|h| h.data_size);
 
     let normalized;
     let wav_bytes = match normalize_riff(bytes) {
@@ -844,8 +937,11 @@ fn load_wav(bytes: &[u8]) -> Result<AudioData, String> {
     let sample_rate = spec.sample_rate;
     let channels = spec.channels as u32;
     let bits_per_sample = spec.bits_per_sample;
+    // True depth for extensible headers (normalization reports the container size)
+    let valid_bits = orig_header.as_ref().map_or(bits_per_sample, |h| h.valid_bits_per_sample.min(bits_per_sample));
 
     let is_float = matches!(spec.sample_format, hound::SampleFormat::Float);
+    let mut exact = None;
     let all_samples: Vec<f32> = match spec.sample_format {
         hound::SampleFormat::Float => reader
             .into_samples::<f32>()
@@ -853,23 +949,25 @@ fn load_wav(bytes: &[u8]) -> Result<AudioData, String> {
             .map_err(|e| format!("WAV sample error: {e}"))?,
         hound::SampleFormat::Int => {
             let max_val = (1u32 << (bits_per_sample - 1)) as f32;
-            reader
+            let ints = reader
                 .into_samples::<i32>()
                 .collect::<Result<Vec<_>, _>>()
-                .map_err(|e| format!("WAV sample error: {e}"))?
-                .into_iter()
-                .map(|s| s as f32 / max_val)
-                .collect()
+                .map_err(|e| format!("WAV sample error: {e}"))?;
+            let floats = ints.iter().map(|&s| s as f32 / max_val).collect();
+            if keep_exact {
+                exact = Some(ExactPcm { samples: Arc::new(ints), channels, bits_per_sample });
+            }
+            floats
         }
     };
 
     let guano = parse_guano(bytes);
 
-    let (samples, source) = build_source(all_samples, channels, sample_rate);
+    let (samples, source) = build_source(all_samples, channels, sample_rate, exact);
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -877,7 +975,7 @@ fn load_wav(bytes: &[u8]) -> Result<AudioData, String> {
         metadata: FileMetadata {
             file_size: bytes.len(),
             format: "WAV",
-            bits_per_sample,
+            bits_per_sample: valid_bits,
             is_float,
             guano,
             data_offset: orig_data_offset,
@@ -886,7 +984,7 @@ fn load_wav(bytes: &[u8]) -> Result<AudioData, String> {
     })
 }
 
-fn load_flac(bytes: &[u8]) -> Result<AudioData, String> {
+fn load_flac(bytes: &[u8], keep_exact: bool) -> Result<AudioData, String> {
     // Parse header for data_offset before using claxon
     let (flac_data_offset, flac_data_size) = parse_flac_header(bytes)
         .map(|h| (Some(h.first_frame_offset), Some((bytes.len() as u64).saturating_sub(h.first_frame_offset))))
@@ -900,17 +998,18 @@ fn load_flac(bytes: &[u8]) -> Result<AudioData, String> {
     let bits = info.bits_per_sample;
     let max_val = (1u32 << (bits - 1)) as f32;
 
-    let all_samples: Vec<f32> = reader
+    let ints = reader
         .samples()
-        .map(|s| s.map(|v| v as f32 / max_val))
-        .collect::<Result<Vec<_>, _>>()
+        .collect::<Result<Vec<i32>, _>>()
         .map_err(|e| format!("FLAC sample error: {e}"))?;
+    let all_samples: Vec<f32> = ints.iter().map(|&v| v as f32 / max_val).collect();
+    let exact = keep_exact.then(|| ExactPcm { samples: Arc::new(ints), channels, bits_per_sample: bits as u16 });
 
-    let (samples, source) = build_source(all_samples, channels, sample_rate);
+    let (samples, source) = build_source(all_samples, channels, sample_rate, exact);
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -1004,11 +1103,11 @@ fn load_ogg(bytes: &[u8]) -> Result<AudioData, String> {
         }
     }
 
-    let (samples, source) = build_source(all_samples, channels, sample_rate);
+    let (samples, source) = build_source(all_samples, channels, sample_rate, None);
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -1018,7 +1117,7 @@ fn load_ogg(bytes: &[u8]) -> Result<AudioData, String> {
             format: "OGG",
             bits_per_sample: 16,
             is_float: false,
-            guano: None,
+            guano: tags::tags_to_guano(tags::parse_ogg_comments(bytes)),
             data_offset: ogg_page_region(bytes).0,
             data_size: ogg_page_region(bytes).1,
         },
@@ -1117,11 +1216,11 @@ fn load_mp3(bytes: &[u8]) -> Result<AudioData, String> {
         }
     }
 
-    let (samples, source) = build_source(all_samples, channels, sample_rate);
+    let (samples, source) = build_source(all_samples, channels, sample_rate, None);
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -1131,7 +1230,7 @@ fn load_mp3(bytes: &[u8]) -> Result<AudioData, String> {
             format: "MP3",
             bits_per_sample: 16,
             is_float: false,
-            guano: None,
+            guano: tags::tags_to_guano(tags::parse_id3v2_tags(bytes)),
             data_offset: Some(mp3_data_offset),
             data_size: Some(
                 (bytes.len() as u64)
@@ -1144,12 +1243,19 @@ fn load_mp3(bytes: &[u8]) -> Result<AudioData, String> {
 
 /// Build mono-mixed samples and an InMemorySource from decoded interleaved samples.
 /// For mono files, raw_samples is None (saves memory by sharing the Arc).
-fn build_source(all_samples: Vec<f32>, channels: u32, sample_rate: u32) -> (Arc<Vec<f32>>, Arc<InMemorySource>) {
+fn build_source(
+    all_samples: Vec<f32>,
+    channels: u32,
+    sample_rate: u32,
+    exact: Option<ExactPcm>,
+) -> (Arc<Vec<f32>>, Arc<InMemorySource>) {
+    let exact = exact.map(Arc::new);
     if channels == 1 {
         let samples = Arc::new(all_samples);
         let source = Arc::new(InMemorySource {
             samples: samples.clone(),
             raw_samples: None,
+            exact,
             sample_rate,
             channels: 1,
         });
@@ -1161,6 +1267,7 @@ fn build_source(all_samples: Vec<f32>, channels: u32, sample_rate: u32) -> (Arc<
         let source = Arc::new(InMemorySource {
             samples: samples.clone(),
             raw_samples: Some(raw),
+            exact,
             sample_rate,
             channels,
         });
@@ -1705,11 +1812,11 @@ fn load_m4a(bytes: &[u8]) -> Result<AudioData, String> {
 
     let sample_rate = actual_rate.unwrap_or(sample_rate);
     let channels = actual_channels.unwrap_or(channels);
-    let (samples, source) = build_source(all_samples, channels, sample_rate);
+    let (samples, source) = build_source(all_samples, channels, sample_rate, None);
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -1725,3 +1832,85 @@ fn load_m4a(bytes: &[u8]) -> Result<AudioData, String> {
         },
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mono WAVE_FORMAT_EXTENSIBLE file with the given container/valid bits and
+    /// subformat tag. Samples are written left-justified, as the format requires.
+    fn extensible_wav(container_bits: u16, valid_bits: u16, subformat: u16, samples: &[i32]) -> Vec<u8> {
+        let bytes_per_sample = container_bits / 8;
+        let mut data = Vec::new();
+        for &s in samples {
+            let shifted = s << (container_bits - valid_bits);
+            data.extend_from_slice(&shifted.to_le_bytes()[..bytes_per_sample as usize]);
+        }
+
+        let mut fmt = Vec::new();
+        fmt.extend_from_slice(&WAVE_FORMAT_EXTENSIBLE.to_le_bytes());
+        fmt.extend_from_slice(&1u16.to_le_bytes()); // channels
+        fmt.extend_from_slice(&384_000u32.to_le_bytes());
+        fmt.extend_from_slice(&(384_000 * bytes_per_sample as u32).to_le_bytes());
+        fmt.extend_from_slice(&bytes_per_sample.to_le_bytes()); // block align
+        fmt.extend_from_slice(&container_bits.to_le_bytes());
+        fmt.extend_from_slice(&22u16.to_le_bytes()); // cbSize
+        fmt.extend_from_slice(&valid_bits.to_le_bytes());
+        fmt.extend_from_slice(&4u32.to_le_bytes()); // channel mask: front centre
+        fmt.extend_from_slice(&subformat.to_le_bytes());
+        fmt.extend_from_slice(&KSDATAFORMAT_GUID_TAIL);
+
+        let mut out = Vec::new();
+        out.extend_from_slice(b"RIFF");
+        out.extend_from_slice(&((4 + 8 + fmt.len() + 8 + data.len()) as u32).to_le_bytes());
+        out.extend_from_slice(b"WAVE");
+        out.extend_from_slice(b"fmt ");
+        out.extend_from_slice(&(fmt.len() as u32).to_le_bytes());
+        out.extend_from_slice(&fmt);
+        out.extend_from_slice(b"data");
+        out.extend_from_slice(&(data.len() as u32).to_le_bytes());
+        out.extend_from_slice(&data);
+        out
+    }
+
+    #[test]
+    fn test_extensible_header_reports_valid_bits() {
+        let wav = extensible_wav(32, 24, WAVE_FORMAT_PCM, &[0, 1 << 22, -(1 << 22), 100]);
+        let h = parse_wav_header(&wav).unwrap();
+        assert_eq!(h.bits_per_sample, 32);
+        assert_eq!(h.valid_bits_per_sample, 24);
+        assert!(!h.is_float);
+        assert_eq!(h.total_frames, 4);
+    }
+
+    #[test]
+    fn test_extensible_pcm_loads_left_justified_samples() {
+        let wav = extensible_wav(32, 24, WAVE_FORMAT_PCM, &[0, 1 << 22, -(1 << 22), 100]);
+        let audio = load_audio(&wav).unwrap();
+        assert_eq!(audio.metadata.bits_per_sample, 24);
+        assert!(!audio.metadata.is_float);
+        let s = &audio.samples;
+        assert_eq!(s.len(), 4);
+        assert!((s[1] - 0.5).abs() < 1e-6, "got {}", s[1]);
+        assert!((s[2] + 0.5).abs() < 1e-6, "got {}", s[2]);
+    }
+
+    #[test]
+    fn test_extensible_float_subformat() {
+        let bits: Vec<i32> = [0.25f32, -0.5].iter().map(|f| f.to_bits() as i32).collect();
+        let wav = extensible_wav(32, 32, WAVE_FORMAT_IEEE_FLOAT, &bits);
+        assert!(parse_wav_header(&wav).unwrap().is_float);
+        let audio = load_audio(&wav).unwrap();
+        assert!(audio.metadata.is_float);
+        assert_eq!(audio.samples.as_slice(), &[0.25, -0.5]);
+    }
+
+    #[test]
+    fn test_extensible_unknown_subformat_rejected() {
+        let mut wav = extensible_wav(16, 16, WAVE_FORMAT_PCM, &[0, 1]);
+        // Corrupt the GUID tail (last byte of the fmt chunk body)
+        let guid_end = 12 + 8 + 40;
+        wav[guid_end - 1] ^= 0xFF;
+        assert!(parse_wav_header(&wav).is_err());
+    }
+}
@@ -0,0 +1,196 @@
+//! Fields shared by a xeno-canto record and the file's GUANO metadata.
+//!
+//! A recording downloaded from xeno-canto can also carry GUANO written by
+//! the detector, and the two often describe the same thing: species,
+//! position, elevation, date and time. They can disagree — a recording
+//! re-identified on xeno-canto keeps the detector's original species in its
+//! GUANO — and that difference is worth seeing, so each shared field is
+//! paired up and compared rather than one copy being silently dropped.
+
+/// Degrees within which two positions count as the same place. xeno-canto
+/// rounds coordinates, and may blur them for sensitive species.
+const POSITION_TOLERANCE_DEG: f64 = 0.01;
+/// Metres within which two elevations agree.
+const ELEVATION_TOLERANCE_M: f64 = 10.0;
+
+/// One field present in both sources.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Overlap {
+    /// Name of the shared field, e.g. "Species".
+    pub label: &'static str,
+    /// Label of the xeno-canto row it was read from.
+    pub xc_label: &'static str,
+    pub xc_value: String,
+    /// Raw GUANO keys it was read from.
+    pub guano_keys: Vec<&'static str>,
+    pub guano_value: String,
+    pub agrees: bool,
+}
+
+fn field<'a>(fields: &'a [(String, String)], key: &str) -> Option<&'a str> {
+    fields.iter().find(|(k, _)| k == key).map(|(_, v)| v.trim()).filter(|v| !v.is_empty())
+}
+
+fn normalize_name(s: &str) -> String {
+    s.replace('_', " ").split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Leading number of a value such as "123 m" or "-33.86".
+fn leading_number(s: &str) -> Option<f64> {
+    let end = s.find(|c: char| !(c.is_ascii_digit() || matches!(c, '.' | '-' | '+'))).unwrap_or(s.len());
+    s[..end].parse().ok()
+}
+
+/// "lat, lon" or "lat lon" as two numbers.
+fn parse_position(s: &str) -> Option<(f64, f64)> {
+    let mut parts = s.split(|c: char| c == ',' || c.is_whitespace()).filter(|p| !p.is_empty());
+    let lat = parts.next()?.parse().ok()?;
+    let lon = parts.next()?.parse().ok()?;
+    Some((lat, lon))
+}
+
+/// Pair up the fields both sources carry. `xc` is the labelled xeno-canto
+/// list ("Scientific name", "Coordinates", "Date", ...); `guano` the raw
+/// GUANO fields.
+pub fn find_overlaps(xc: &[(String, String)], guano: &[(String, String)]) -> Vec<Overlap> {
+    let mut out = Vec::new();
+
+    // Species: GUANO IDs may be scientific names, common names or a list;
+    // any listed name matching either xeno-canto name counts as agreement.
+    let xc_species = field(xc, "Scientific name").map(|v| ("Scientific name", v))
+        .or_else(|| field(xc, "Species").map(|v| ("Species", v)));
+    if let Some((xc_label, xc_value)) = xc_species {
+        let names: Vec<String> = ["Scientific name", "Species"].iter()
+            .filter_map(|k| field(xc, k))
+            .map(normalize_name)
+            .collect();
+        for key in ["Species Manual ID", "Species|Manual", "Species Auto ID", "Species|Auto"] {
+            let Some(value) = field(guano, key) else { continue };
+            let agrees = value.split([',', ';']).any(|id| names.contains(&normalize_name(id)));
+            out.push(Overlap {
+                label: "Species",
+                xc_label,
+                xc_value: xc_value.into(),
+                guano_keys: vec![key],
+                guano_value: value.into(),
+                agrees,
+            });
+        }
+    }
+
+    let guano_position = field(guano, "Loc Position").map(|v| (vec!["Loc Position"], v.to_string()))
+        .or_else(|| Some((vec!["Loc|Lat", "Loc|Lon"], format!("{} {}", field(guano, "Loc|Lat")?, field(guano, "Loc|Lon")?))));
+    if let (Some(xc_value), Some((keys, value))) = (field(xc, "Coordinates"), guano_position) {
+        if let (Some(a), Some(b)) = (parse_position(xc_value), parse_position(&value)) {
+            out.push(Overlap {
+                label: "Position",
+                xc_label: "Coordinates",
+                xc_value: xc_value.into(),
+                guano_keys: keys,
+                guano_value: value,
+                agrees: (a.0 - b.0).abs() <= POSITION_TOLERANCE_DEG && (a.1 - b.1).abs() <= POSITION_TOLERANCE_DEG,
+            });
+        }
+    }
+
+    let guano_elevation = ["Loc Elevation", "Loc|Elev"].into_iter().find_map(|k| Some((k, field(guano, k)?)));
+    if let (Some(xc_value), Some((key, value))) = (field(xc, "Altitude"), guano_elevation) {
+        if let (Some(a), Some(b)) = (leading_number(xc_value), leading_number(value)) {
+            out.push(Overlap {
+                label: "Elevation",
+                xc_label: "Altitude",
+                xc_value: xc_value.into(),
+                guano_keys: vec![key],
+                guano_value: value.into(),
+                agrees: (a - b).abs() <= ELEVATION_TOLERANCE_M,
+            });
+        }
+    }
+
+    // Timestamp is ISO 8601; compare its local date and hh:mm as written
+    if let Some(ts) = field(guano, "Timestamp") {
+        let (date, time) = ts.split_once(['T', ' ']).unwrap_or((ts, ""));
+        if let Some(xc_date) = field(xc, "Date") {
+            out.push(Overlap {
+                label: "Date",
+                xc_label: "Date",
+                xc_value: xc_date.into(),
+                guano_keys: vec!["Timestamp"],
+                guano_value: ts.into(),
+                agrees: xc_date == date,
+            });
+        }
+        if let Some(xc_time) = field(xc, "Time").filter(|t| t.contains(':')) {
+            out.push(Overlap {
+                label: "Time",
+                xc_label: "Time",
+                xc_value: xc_time.into(),
+                guano_keys: vec!["Timestamp"],
+                guano_value: ts.into(),
+                agrees: time.get(..5) == xc_time.get(..5),
+            });
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields(pairs: &[(&str, &str)]) -> Vec<(String, String)> {
+        pairs.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect()
+    }
+
+    fn xc() -> Vec<(String, String)> {
+        fields(&[
+            ("Species", "Gould's Wattled Bat"),
+            ("Scientific name", "Chalinolobus gouldii"),
+            ("Coordinates", "-33.86, 151.21"),
+            ("Altitude", "40 m"),
+            ("Date", "2024-03-15"),
+            ("Time", "21:30"),
+        ])
+    }
+
+    #[test]
+    fn test_matching_fields_agree() {
+        let guano = fields(&[
+            ("Species Manual ID", "Myotis_macropus, chalinolobus_gouldii"),
+            ("Loc Position", "-33.865 151.205"),
+            ("Loc Elevation", "45"),
+            ("Timestamp", "2024-03-15T21:30:12+10:00"),
+        ]);
+        let overlaps = find_overlaps(&xc(), &guano);
+        let labels: Vec<_> = overlaps.iter().map(|o| o.label).collect();
+        assert_eq!(labels, ["Species", "Position", "Elevation", "Date", "Time"]);
+        assert!(overlaps.iter().all(|o| o.agrees), "{overlaps:?}");
+        assert_eq!(overlaps[0].xc_label, "Scientific name");
+    }
+
+    #[test]
+    fn test_differences_are_kept() {
+        let guano = fields(&[
+            ("Species Auto ID", "Nyctophilus geoffroyi"),
+            ("Loc|Lat", "-34.5"),
+            ("Loc|Lon", "151.21"),
+            ("Loc|Elev", "120 m"),
+            ("Timestamp", "2024-03-16 02:10:00"),
+        ]);
+        let overlaps = find_overlaps(&xc(), &guano);
+        assert_eq!(overlaps.len(), 5);
+        assert!(overlaps.iter().all(|o| !o.agrees), "{overlaps:?}");
+        assert_eq!(overlaps[1].guano_keys, ["Loc|Lat", "Loc|Lon"]);
+        assert_eq!(overlaps[1].guano_value, "-34.5 151.21");
+    }
+
+    #[test]
+    fn test_fields_missing_from_either_side_are_skipped() {
+        let guano = fields(&[("Loc Position", "-33.86 151.21"), ("Species Manual ID", " ")]);
+        assert!(find_overlaps(&[], &guano).is_empty());
+        let xc = fields(&[("Time", "?"), ("Species", "Myotis macropus")]);
+        let guano = fields(&[("Timestamp", "2024-03-15T21:30:00")]);
+        assert!(find_overlaps(&xc, &guano).is_empty());
+    }
+}
@@ -1,3 +1,9 @@
 pub mod source;
 pub mod guano;
 pub mod loader;
+pub mod convert;
+pub mod tags;
+pub mod labels;
+pub mod recording_name;
+pub mod metadata_overlap;
+pub mod xc_sidecar;
@@ -0,0 +1,151 @@
+//! File names for new recordings, built from a user template.
+//!
+//! A template is plain text with `{field}` placeholders, e.g.
+//! `{site}_{date}_{time}`. The expanded name is sanitised the same way
+//! noise presets are: anything other than letters, digits, `-`, `_` and
+//! spaces becomes `_`, and spaces become `_`. Field values are sanitised
+//! too, so a site called "Creek/North" can't reach outside the recordings
+//! directory.
+
+/// The fixed scheme used before templates existed: `batcap_20240315_103000`.
+pub const DEFAULT_TEMPLATE: &str = "batcap_{date}_{time}";
+
+/// Placeholders a template may use.
+pub const FIELDS: [&str; 4] = ["date", "time", "site", "operator"];
+
+/// Values substituted into a template. `date` is `YYYYMMDD` and `time` is
+/// `HHMMSS`; empty fields collapse along with the separator next to them.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct NameFields {
+    pub date: String,
+    pub time: String,
+    pub site: String,
+    pub operator: String,
+}
+
+impl NameFields {
+    fn get(&self, field: &str) -> &str {
+        match field {
+            "date" => &self.date,
+            "time" => &self.time,
+            "site" => &self.site,
+            "operator" => &self.operator,
+            _ => "",
+        }
+    }
+}
+
+/// Replace characters that aren't safe in a file name. Shared with noise
+/// preset saving.
+pub fn sanitize_file_stem(name: &str) -> String {
+    let sanitized: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
+        .collect();
+    sanitized.trim().replace(' ', "_")
+}
+
+/// Split a template into literal text and placeholder names.
+fn parse(template: &str) -> Result<Vec<(bool, &str)>, String> {
+    let mut parts = Vec::new();
+    let mut rest = template;
+    while let Some(open) = rest.find(['{', '}']) {
+        if rest[open..].starts_with('}') {
+            return Err("Unmatched \u{201C}}\u{201D}".into());
+        }
+        let close = rest[open..].find('}').ok_or("Unclosed \u{201C}{\u{201D}")? + open;
+        let field = &rest[open + 1..close];
+        if !FIELDS.contains(&field) {
+            return Err(format!("Unknown field {{{field}}}; use {}", FIELDS.map(|f| format!("{{{f}}}")).join(", ")));
+        }
+        parts.push((false, &rest[..open]));
+        parts.push((true, field));
+        rest = &rest[close + 1..];
+    }
+    parts.push((false, rest));
+    Ok(parts)
+}
+
+/// Check a template before saving it. It must parse and include both
+/// `{date}` and `{time}`, or recordings made at the same time of day on
+/// different days would get the same name.
+pub fn validate_template(template: &str) -> Result<(), String> {
+    let parts = parse(template)?;
+    for field in ["date", "time"] {
+        if !parts.contains(&(true, field)) {
+            return Err(format!("Template must include {{{field}}}"));
+        }
+    }
+    Ok(())
+}
+
+/// Expand `template` into a sanitised `.wav` file name. Separators left
+/// doubled or dangling by empty fields are tidied away.
+pub fn expand_template(template: &str, fields: &NameFields) -> Result<String, String> {
+    let mut name = String::new();
+    for (is_field, text) in parse(template)? {
+        if is_field {
+            name.push_str(&sanitize_file_stem(fields.get(text)));
+        } else {
+            name.push_str(text);
+        }
+    }
+    let mut stem = String::new();
+    for c in sanitize_file_stem(&name).chars() {
+        let is_sep = c == '_' || c == '-';
+        if !(is_sep && stem.ends_with(['_', '-'])) {
+            stem.push(c);
+        }
+    }
+    let stem = stem.trim_matches(['_', '-']);
+    if stem.is_empty() {
+        return Err("Template gives an empty file name".into());
+    }
+    Ok(format!("{stem}.wav"))
+}
+
+/// Expand `template`, falling back to [`DEFAULT_TEMPLATE`] if it is invalid.
+pub fn recording_file_name(template: &str, fields: &NameFields) -> String {
+    expand_template(template, fields)
+        .or_else(|_| expand_template(DEFAULT_TEMPLATE, fields))
+        .unwrap_or_else(|_| "batcap.wav".into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fields() -> NameFields {
+        NameFields {
+            date: "20240315".into(),
+            time: "103000".into(),
+            site: "Creek/North bank".into(),
+            operator: "J. Smith".into(),
+        }
+    }
+
+    #[test]
+    fn test_default_matches_old_scheme() {
+        assert_eq!(expand_template(DEFAULT_TEMPLATE, &fields()).unwrap(), "batcap_20240315_103000.wav");
+    }
+
+    #[test]
+    fn test_fields_are_sanitized() {
+        let name = expand_template("{site}_{operator}_{date}_{time}", &fields()).unwrap();
+        assert_eq!(name, "Creek_North_bank_J_Smith_20240315_103000.wav");
+        let empty = NameFields { site: String::new(), ..fields() };
+        assert_eq!(expand_template("{site}_{date}_{time}", &empty).unwrap(), "20240315_103000.wav");
+        assert_eq!(expand_template("a {site} - {time}", &empty).unwrap(), "a_103000.wav");
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(validate_template("{site}_{date}_{time}").is_ok());
+        assert!(validate_template("{site}_{date}").unwrap_err().contains("{time}"));
+        assert!(validate_template("{site}_{time}").unwrap_err().contains("{date}"));
+        assert!(validate_template("{place}_{time}").unwrap_err().contains("{place}"));
+        assert!(validate_template("{site_{time}").is_err());
+        assert!(validate_template("site}_{time}").is_err());
+        assert_eq!(recording_file_name("{nope}", &fields()), "batcap_20240315_103000.wav");
+    }
+}
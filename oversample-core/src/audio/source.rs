@@ -124,6 +124,114 @@ impl AudioSource for TimelineAudioSource {
     }
 }
 
+/// Window onto part of another source, e.g. a recording with its silent
+/// lead-in and tail trimmed off. The inner source stays intact, so exports
+/// can still reach the original samples via [`TrimmedSource::original`].
+pub struct TrimmedSource {
+    inner: Arc<dyn AudioSource>,
+    start: u64,
+    len: u64,
+}
+
+impl std::fmt::Debug for TrimmedSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrimmedSource")
+            .field("start", &self.start)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl TrimmedSource {
+    /// Frames `start..start + len` of `inner`, clamped to its length.
+    pub fn new(inner: Arc<dyn AudioSource>, start: u64, len: u64) -> Self {
+        let start = start.min(inner.total_samples());
+        let len = len.min(inner.total_samples() - start);
+        Self { inner, start, len }
+    }
+
+    /// The untrimmed source.
+    pub fn original(&self) -> &Arc<dyn AudioSource> {
+        &self.inner
+    }
+
+    /// First original frame kept.
+    pub fn start_frame(&self) -> u64 {
+        self.start
+    }
+}
+
+impl AudioSource for TrimmedSource {
+    fn total_samples(&self) -> u64 {
+        self.len
+    }
+
+    fn sample_rate(&self) -> u32 {
+        self.inner.sample_rate()
+    }
+
+    fn channel_count(&self) -> u32 {
+        self.inner.channel_count()
+    }
+
+    fn read_samples(&self, channel: ChannelView, start: u64, buf: &mut [f32]) -> usize {
+        if start >= self.len {
+            return 0;
+        }
+        let n = buf.len().min((self.len - start) as usize);
+        self.inner.read_samples(channel, self.start + start, &mut buf[..n])
+    }
+
+    fn is_fully_loaded(&self) -> bool {
+        self.inner.is_fully_loaded()
+    }
+
+    fn as_contiguous(&self) -> Option<&[f32]> {
+        let all = self.inner.as_contiguous()?;
+        all.get(self.start as usize..(self.start + self.len) as usize)
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Whether every channel carries the same signal over `len` frames from `start`
+/// (a stereo file recorded from one mic and duplicated to both sides).
+///
+/// Channels count as identical when their difference from the first channel
+/// is at least 60 dB below its level, which tolerates dither and rounding in
+/// lossy re-encodes. Mono sources and all-silent regions are reported as
+/// identical.
+pub fn channels_identical(source: &dyn AudioSource, start: u64, len: usize) -> bool {
+    const CHUNK: usize = 65_536;
+    const MAX_DIFF_RATIO: f64 = 1e-6; // -60 dB in power
+    let channels = source.channel_count();
+    if channels < 2 {
+        return true;
+    }
+    let mut ref_energy = 0.0f64;
+    let mut diff_energy = vec![0.0f64; channels as usize];
+    let mut pos = start;
+    let end = start.saturating_add(len as u64).min(source.total_samples());
+    while pos < end {
+        let n = CHUNK.min((end - pos) as usize);
+        let first = source.read_region(ChannelView::Channel(0), pos, n);
+        ref_energy += first.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>();
+        for ch in 1..channels {
+            let other = source.read_region(ChannelView::Channel(ch), pos, n);
+            diff_energy[ch as usize] += first.iter().zip(&other)
+                .map(|(&a, &b)| {
+                    let d = (a - b) as f64;
+                    d * d
+                })
+                .sum::<f64>();
+        }
+        pos += n as u64;
+    }
+    diff_energy.iter().all(|&d| d <= ref_energy * MAX_DIFF_RATIO)
+}
+
 /// Default analysis window in seconds.
 /// Whole-file analysis operations (auto-gain, wSNR, bit analysis) should
 /// default to scanning only this many seconds from the start of the file,
@@ -143,6 +251,9 @@ pub enum ChannelView {
     Stereo,
     /// Mono downmix of all channels (single channel for both display and playback).
     MonoMix,
+    /// Sum of all channels: the downmix without dividing by the channel count,
+    /// so a call present in only one mic keeps its full level.
+    Sum,
     /// A specific channel by index (0 = first/left, 1 = second/right, etc.).
     Channel(u32),
     /// Left minus Right difference (stereo only).
@@ -198,6 +309,41 @@ pub trait AudioSource: Send + Sync {
 
     /// Downcast support for accessing implementation-specific methods (e.g. prefetch).
     fn as_any(&self) -> &dyn std::any::Any;
+
+    /// The file's original integer samples, when the loader was asked to keep them.
+    fn exact_pcm(&self) -> Option<&ExactPcm> {
+        None
+    }
+}
+
+/// Original integer PCM samples exactly as stored in the file.
+///
+/// The f32 buffers are fine for display and analysis, but converting back to
+/// integers can change the low bits. Clip export writes from this store instead
+/// so an unprocessed clip is bit-identical to the same span of the source.
+#[derive(Clone, Debug)]
+pub struct ExactPcm {
+    /// Interleaved samples, right-aligned (a 16-bit file holds -32768..=32767).
+    pub samples: Arc<Vec<i32>>,
+    pub channels: u32,
+    /// Container bit depth of the source (e.g. 16 or 24).
+    pub bits_per_sample: u16,
+}
+
+impl ExactPcm {
+    /// Number of per-channel frames.
+    pub fn frame_count(&self) -> usize {
+        self.samples.len() / self.channels.max(1) as usize
+    }
+
+    /// Interleaved samples for `len` frames starting at frame `start`,
+    /// truncated at the end of the file.
+    pub fn frames(&self, start: usize, len: usize) -> &[i32] {
+        let ch = self.channels.max(1) as usize;
+        let start = start.min(self.frame_count());
+        let end = start.saturating_add(len).min(self.frame_count());
+        &self.samples[start * ch..end * ch]
+    }
 }
 
 /// In-memory audio source wrapping `Arc<Vec<f32>>`.
@@ -212,6 +358,8 @@ pub struct InMemorySource {
     /// Original interleaved samples (for multi-channel access).
     /// `None` for mono files (where `samples` already contains the single channel).
     pub raw_samples: Option<Arc<Vec<f32>>>,
+    /// Original integer samples, kept only when requested (see `ExactPcm`).
+    pub exact: Option<Arc<ExactPcm>>,
     /// Sample rate in Hz.
     pub sample_rate: u32,
     /// Original channel count before mono mixing.
@@ -225,6 +373,7 @@ impl std::fmt::Debug for InMemorySource {
             .field("sample_rate", &self.sample_rate)
             .field("channels", &self.channels)
             .field("has_raw", &self.raw_samples.is_some())
+            .field("has_exact", &self.exact.is_some())
             .finish()
     }
 }
@@ -261,6 +410,29 @@ impl InMemorySource {
         n
     }
 
+    /// Collapse to a single channel, for files whose channels are identical.
+    ///
+    /// Keeps the existing mono mix and the first channel of the exact PCM;
+    /// the interleaved copy is dropped, roughly halving memory for stereo.
+    pub fn to_mono(&self) -> InMemorySource {
+        let exact = self.exact.as_ref().map(|e| {
+            let ch = e.channels.max(1) as usize;
+            let samples = if ch == 1 {
+                e.samples.clone()
+            } else {
+                Arc::new(e.samples.iter().step_by(ch).copied().collect())
+            };
+            Arc::new(ExactPcm { samples, channels: 1, bits_per_sample: e.bits_per_sample })
+        });
+        InMemorySource {
+            samples: self.samples.clone(),
+            raw_samples: None,
+            exact,
+            sample_rate: self.sample_rate,
+            channels: 1,
+        }
+    }
+
     /// Read from the mono-mixed buffer.
     fn read_mono(&self, start: u64, buf: &mut [f32]) -> usize {
         let start = start as usize;
@@ -296,6 +468,11 @@ impl AudioSource for InMemorySource {
     ) -> usize {
         match channel {
             ChannelView::Stereo | ChannelView::MonoMix => self.read_mono(start, buf),
+            ChannelView::Sum => {
+                let n = self.read_mono(start, buf);
+                scale_to_sum(&mut buf[..n], self.channels);
+                n
+            }
             ChannelView::Channel(ch) => {
                 if self.channels == 1 {
                     self.read_mono(start, buf)
@@ -332,6 +509,10 @@ impl AudioSource for InMemorySource {
     fn as_any(&self) -> &dyn std::any::Any {
         self
     }
+
+    fn exact_pcm(&self) -> Option<&ExactPcm> {
+        self.exact.as_deref()
+    }
 }
 
 impl ChannelView {
@@ -339,7 +520,8 @@ impl ChannelView {
     pub fn label(&self) -> &'static str {
         match self {
             ChannelView::Stereo => "Stereo",
-            ChannelView::MonoMix => "L+R",
+            ChannelView::MonoMix => "Avg",
+            ChannelView::Sum => "Sum",
             ChannelView::Channel(0) => "L",
             ChannelView::Channel(1) => "R",
             ChannelView::Channel(n) => {
@@ -354,4 +536,123 @@ impl ChannelView {
             ChannelView::Difference => "L-R",
         }
     }
+
+    /// How the channels are combined, for reporting the active mix.
+    pub fn description(&self) -> String {
+        match self {
+            ChannelView::Stereo => "Stereo playback; display and analysis use the average of all channels".into(),
+            ChannelView::MonoMix => "Average of all channels".into(),
+            ChannelView::Sum => "Sum of all channels".into(),
+            ChannelView::Channel(n) => format!("Channel {} only", n + 1),
+            ChannelView::Difference => "Difference of the first two channels (L\u{2212}R, the side signal)".into(),
+        }
+    }
+
+    /// Key used to persist the choice: "stereo", "mix", "sum", "diff" or "ch<n>".
+    pub fn key(&self) -> String {
+        match self {
+            ChannelView::Stereo => "stereo".into(),
+            ChannelView::MonoMix => "mix".into(),
+            ChannelView::Sum => "sum".into(),
+            ChannelView::Channel(n) => format!("ch{n}"),
+            ChannelView::Difference => "diff".into(),
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<ChannelView> {
+        match key {
+            "stereo" => Some(ChannelView::Stereo),
+            "mix" => Some(ChannelView::MonoMix),
+            "sum" => Some(ChannelView::Sum),
+            "diff" => Some(ChannelView::Difference),
+            _ => key.strip_prefix("ch")?.parse().ok().map(ChannelView::Channel),
+        }
+    }
+}
+
+/// Turn a channel average into a channel sum, in place.
+pub fn scale_to_sum(buf: &mut [f32], channels: u32) {
+    if channels > 1 {
+        let c = channels as f32;
+        for s in buf.iter_mut() {
+            *s *= c;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stereo(frames: &[(f32, f32)]) -> InMemorySource {
+        let raw: Vec<f32> = frames.iter().flat_map(|&(l, r)| [l, r]).collect();
+        let mono = frames.iter().map(|&(l, r)| (l + r) / 2.0).collect();
+        InMemorySource {
+            samples: Arc::new(mono),
+            raw_samples: Some(Arc::new(raw)),
+            exact: None,
+            sample_rate: 48_000,
+            channels: 2,
+        }
+    }
+
+    #[test]
+    fn test_mixdown_views() {
+        let src = stereo(&[(0.5, 0.5), (0.25, 0.0), (0.125, -0.375)]);
+        let read = |cv| src.read_region(cv, 0, 3);
+        assert_eq!(read(ChannelView::MonoMix), vec![0.5, 0.125, -0.125]);
+        assert_eq!(read(ChannelView::Sum), vec![1.0, 0.25, -0.25]);
+        assert_eq!(read(ChannelView::Channel(1)), vec![0.5, 0.0, -0.375]);
+        // Correlated content cancels; one-mic content survives
+        assert_eq!(read(ChannelView::Difference), vec![0.0, 0.25, 0.5]);
+    }
+
+    #[test]
+    fn test_channels_identical() {
+        let dup = stereo(&[(0.5, 0.5), (-0.25, -0.25), (0.125, 0.125)]);
+        assert!(channels_identical(&dup, 0, 3));
+        let silent = stereo(&[(0.0, 0.0); 4]);
+        assert!(channels_identical(&silent, 0, 4));
+        let distinct = stereo(&[(0.5, 0.5), (0.25, 0.0), (0.125, -0.375)]);
+        assert!(!channels_identical(&distinct, 0, 3));
+        // Only the scanned region counts
+        assert!(channels_identical(&distinct, 0, 1));
+
+        let mono = dup.to_mono();
+        assert_eq!(mono.channel_count(), 1);
+        assert!(mono.raw_samples.is_none());
+        assert_eq!(mono.read_region(ChannelView::Channel(1), 0, 3), dup.read_region(ChannelView::Channel(0), 0, 3));
+    }
+
+    #[test]
+    fn test_trimmed_source_offsets_reads() {
+        let inner: Arc<dyn AudioSource> = Arc::new(stereo(&[(0.0, 0.0), (0.1, 0.3), (0.2, 0.4), (0.3, 0.5), (0.4, 0.6)]));
+        let trimmed = TrimmedSource::new(inner, 1, 3);
+        assert_eq!(trimmed.total_samples(), 3);
+        assert_eq!(trimmed.read_region(ChannelView::Channel(1), 0, 10), vec![0.3, 0.4, 0.5]);
+        assert_eq!(trimmed.read_region(ChannelView::Channel(0), 2, 10), vec![0.3]);
+        assert!(trimmed.read_region(ChannelView::Channel(0), 3, 1).is_empty());
+        assert_eq!(trimmed.original().total_samples(), 5);
+    }
+
+    #[test]
+    fn test_trimmed_audio_shares_samples() {
+        let audio = crate::types::AudioData::from_mono((0..10).map(|i| i as f32).collect(), 4);
+        assert_eq!(audio.trim_offset_secs(), 0.0);
+        let trimmed = audio.trimmed(2, 7);
+        assert_eq!(&trimmed.samples[..], &[2.0, 3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(trimmed.samples.slice(1..99).as_slice(), &[3.0, 4.0, 5.0, 6.0]);
+        assert_eq!(trimmed.trim_offset_secs(), 0.5);
+        assert_eq!(trimmed.duration_secs, 1.25);
+        assert_eq!(trimmed.untrimmed_source().total_samples(), 10);
+        assert_eq!(trimmed.source.read_region(ChannelView::MonoMix, 0, 2), vec![2.0, 3.0]);
+    }
+
+    #[test]
+    fn test_channel_view_keys() {
+        for cv in [ChannelView::Stereo, ChannelView::MonoMix, ChannelView::Sum, ChannelView::Difference, ChannelView::Channel(0), ChannelView::Channel(3)] {
+            assert_eq!(ChannelView::from_key(&cv.key()), Some(cv));
+        }
+        assert_eq!(ChannelView::from_key("bogus"), None);
+    }
 }
@@ -0,0 +1,563 @@
+//! Text tags embedded in compressed audio: ID3v2 (MP3) and Vorbis comments
+//! (Ogg Vorbis / Opus).
+//!
+//! Keys are given friendly names matching the ones used for M4A tags
+//! ("Title", "Artist", "Comment", ...) so every format shows the same labels
+//! in the metadata panel. Binary frames (cover art etc.) are skipped.
+
+use crate::audio::guano::GuanoMetadata;
+
+/// Tags from the start of an MP3 or Ogg file, in file order. Empty if the
+/// format has no tags we read or none are present.
+pub fn parse_embedded_tags(bytes: &[u8]) -> Vec<(String, String)> {
+    if bytes.starts_with(b"ID3") {
+        parse_id3v2_tags(bytes)
+    } else if bytes.starts_with(b"OggS") {
+        parse_ogg_comments(bytes)
+    } else {
+        Vec::new()
+    }
+}
+
+/// Wrap tags for `FileMetadata::guano`, which is how non-GUANO formats
+/// surface their text metadata. None when there are no tags.
+pub fn tags_to_guano(tags: Vec<(String, String)>) -> Option<GuanoMetadata> {
+    (!tags.is_empty()).then_some(GuanoMetadata { fields: tags })
+}
+
+// ── ID3v2 ───────────────────────────────────────────────────────────────────
+
+fn synchsafe(b: &[u8]) -> usize {
+    b.iter().fold(0usize, |acc, &x| (acc << 7) | (x & 0x7F) as usize)
+}
+
+/// Undo ID3 unsynchronisation (0xFF 0x00 → 0xFF).
+fn unsynchronise(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut prev_ff = false;
+    for &b in data {
+        if !(prev_ff && b == 0) {
+            out.push(b);
+        }
+        prev_ff = b == 0xFF;
+    }
+    out
+}
+
+/// Parse the text frames of an ID3v2.2/2.3/2.4 tag at the start of `bytes`.
+pub fn parse_id3v2_tags(bytes: &[u8]) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    if bytes.len() < 10 || &bytes[0..3] != b"ID3" {
+        return out;
+    }
+    let version = bytes[3];
+    if !(2..=4).contains(&version) {
+        return out;
+    }
+    let flags = bytes[5];
+    let end = (10 + synchsafe(&bytes[6..10])).min(bytes.len());
+    let body = if flags & 0x80 != 0 && version < 4 {
+        unsynchronise(&bytes[10..end])
+    } else {
+        bytes[10..end].to_vec()
+    };
+
+    let mut pos = 0usize;
+    // Extended header (v2.3: size excludes itself; v2.4: synchsafe, includes itself)
+    if flags & 0x40 != 0 && version >= 3 && body.len() >= 4 {
+        pos = if version == 3 {
+            4 + u32::from_be_bytes([body[0], body[1], body[2], body[3]]) as usize
+        } else {
+            synchsafe(&body[0..4])
+        };
+    }
+
+    let header_len = if version == 2 { 6 } else { 10 };
+    while pos + header_len <= body.len() {
+        let h = &body[pos..pos + header_len];
+        if h[0] == 0 {
+            break; // padding
+        }
+        let (id, size, frame_flags) = match version {
+            2 => (
+                String::from_utf8_lossy(&h[0..3]).into_owned(),
+                ((h[3] as usize) << 16) | ((h[4] as usize) << 8) | h[5] as usize,
+                0u8,
+            ),
+            3 => (
+                String::from_utf8_lossy(&h[0..4]).into_owned(),
+                u32::from_be_bytes([h[4], h[5], h[6], h[7]]) as usize,
+                h[9],
+            ),
+            _ => (String::from_utf8_lossy(&h[0..4]).into_owned(), synchsafe(&h[4..8]), h[9]),
+        };
+        let start = pos + header_len;
+        let Some(frame_end) = start.checked_add(size).filter(|&e| e <= body.len()) else { break };
+        pos = frame_end;
+
+        // Compressed or encrypted frames can't be read as text
+        let (compressed, encrypted, unsync) = match version {
+            3 => (frame_flags & 0x80 != 0, frame_flags & 0x40 != 0, false),
+            4 => (frame_flags & 0x08 != 0, frame_flags & 0x04 != 0, frame_flags & 0x02 != 0),
+            _ => (false, false, false),
+        };
+        if compressed || encrypted {
+            continue;
+        }
+        let mut data = body[start..frame_end].to_vec();
+        if version == 4 && frame_flags & 0x01 != 0 && data.len() >= 4 {
+            data.drain(..4); // data length indicator
+        }
+        if unsync {
+            data = unsynchronise(&data);
+        }
+        let id = id3v22_to_v23(&id).unwrap_or(&id).to_string();
+        if let Some(entry) = parse_id3_frame(&id, &data) {
+            out.push(entry);
+        }
+    }
+    out
+}
+
+fn id3v22_to_v23(id: &str) -> Option<&'static str> {
+    Some(match id {
+        "TT1" => "TIT1",
+        "TT2" => "TIT2",
+        "TT3" => "TIT3",
+        "TP1" => "TPE1",
+        "TP2" => "TPE2",
+        "TAL" => "TALB",
+        "TYE" => "TYER",
+        "TCO" => "TCON",
+        "TCM" => "TCOM",
+        "TCR" => "TCOP",
+        "TEN" => "TENC",
+        "TSS" => "TSSE",
+        "TRK" => "TRCK",
+        "TPA" => "TPOS",
+        "TXX" => "TXXX",
+        "COM" => "COMM",
+        "WXX" => "WXXX",
+        _ => return None,
+    })
+}
+
+fn friendly_id3_key(id: &str) -> Option<&'static str> {
+    Some(match id {
+        "TIT2" => "Title",
+        "TIT1" => "Grouping",
+        "TIT3" => "Subtitle",
+        "TPE1" => "Artist",
+        "TPE2" => "Album Artist",
+        "TALB" => "Album",
+        "TYER" | "TDRC" => "Year",
+        "TCON" => "Genre",
+        "TCOM" => "Composer",
+        "TCOP" => "Copyright",
+        "TENC" => "Encoded by",
+        "TSSE" => "Encoder",
+        "TRCK" => "Track",
+        "TPOS" => "Disc",
+        "WOAF" => "File URL",
+        "WOAS" => "Source URL",
+        "WCOP" => "License URL",
+        _ => return None,
+    })
+}
+
+fn parse_id3_frame(id: &str, data: &[u8]) -> Option<(String, String)> {
+    let key = |id: &str| friendly_id3_key(id).map_or_else(|| id.to_string(), str::to_string);
+    match id {
+        "TXXX" | "WXXX" => {
+            let (&enc, rest) = data.split_first()?;
+            let (desc, value) = split_id3_text(enc, rest);
+            let value = if id == "WXXX" {
+                String::from_utf8_lossy(value.split(|&b| b == 0).next().unwrap_or(&[])).into_owned()
+            } else {
+                decode_id3_text(enc, value)
+            };
+            let desc = decode_id3_text(enc, desc);
+            let key = if desc.is_empty() { key(id) } else { desc };
+            non_empty(key, value)
+        }
+        "COMM" => {
+            let (&enc, rest) = data.split_first()?;
+            let rest = rest.get(3..)?; // language
+            let (desc, text) = split_id3_text(enc, rest);
+            let desc = decode_id3_text(enc, desc);
+            // iTunes stores normalisation and gapless data as comments
+            if desc.starts_with("iTun") {
+                return None;
+            }
+            let key = if desc.is_empty() { "Comment".to_string() } else { format!("Comment ({desc})") };
+            non_empty(key, decode_id3_text(enc, text))
+        }
+        _ if id.starts_with('T') => {
+            let (&enc, rest) = data.split_first()?;
+            non_empty(key(id), decode_id3_text(enc, rest))
+        }
+        _ if id.starts_with('W') => {
+            let url = data.split(|&b| b == 0).next().unwrap_or(&[]);
+            non_empty(key(id), String::from_utf8_lossy(url).into_owned())
+        }
+        _ => None,
+    }
+}
+
+fn non_empty(key: String, value: String) -> Option<(String, String)> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| (key, value.to_string()))
+}
+
+/// Split at the first string terminator for the encoding: one zero byte for
+/// Latin-1/UTF-8, an aligned zero pair for UTF-16.
+fn split_id3_text(enc: u8, data: &[u8]) -> (&[u8], &[u8]) {
+    let term = if enc == 1 || enc == 2 {
+        (0..data.len().saturating_sub(1)).step_by(2).find(|&i| data[i] == 0 && data[i + 1] == 0).map(|i| (i, 2))
+    } else {
+        data.iter().position(|&b| b == 0).map(|i| (i, 1))
+    };
+    match term {
+        Some((i, n)) => (&data[..i], &data[i + n..]),
+        None => (data, &[]),
+    }
+}
+
+/// Decode ID3 text. Multiple values (zero-separated) are joined with "; ".
+fn decode_id3_text(enc: u8, data: &[u8]) -> String {
+    let values: Vec<String> = match enc {
+        1 | 2 => {
+            let mut units: Vec<u16> = data.chunks_exact(2).map(|c| u16::from_be_bytes([c[0], c[1]])).collect();
+            let mut little_endian = false;
+            let mut parts = Vec::new();
+            for part in units.split_mut(|&u| u == 0) {
+                let mut part: &[u16] = part;
+                if enc == 1 {
+                    // Each value may carry its own BOM; bytes were read as big-endian
+                    match part.first() {
+                        Some(0xFFFE) => { little_endian = true; part = &part[1..]; }
+                        Some(0xFEFF) => { little_endian = false; part = &part[1..]; }
+                        _ => {}
+                    }
+                }
+                let decoded: Vec<u16> = if little_endian { part.iter().map(|u| u.swap_bytes()).collect() } else { part.to_vec() };
+                parts.push(String::from_utf16_lossy(&decoded));
+            }
+            parts
+        }
+        3 => data.split(|&b| b == 0).map(|p| String::from_utf8_lossy(p).into_owned()).collect(),
+        _ => data.split(|&b| b == 0).map(|p| p.iter().map(|&b| b as char).collect()).collect(),
+    };
+    values.iter().map(|v| v.trim()).filter(|v| !v.is_empty()).collect::<Vec<_>>().join("; ")
+}
+
+// ── Ogg Vorbis / Opus comments ──────────────────────────────────────────────
+
+/// Maximum packets examined; the comment header is the second packet.
+const OGG_MAX_PACKETS: usize = 3;
+
+/// Read the comment header from the first logical stream of an Ogg file.
+pub fn parse_ogg_comments(bytes: &[u8]) -> Vec<(String, String)> {
+    let mut packets: Vec<Vec<u8>> = Vec::new();
+    let mut current = Vec::new();
+    let mut serial = None;
+    let mut pos = 0usize;
+    while pos + 27 <= bytes.len() && &bytes[pos..pos + 4] == b"OggS" && packets.len() < OGG_MAX_PACKETS {
+        let page_serial = u32::from_le_bytes([bytes[pos + 14], bytes[pos + 15], bytes[pos + 16], bytes[pos + 17]]);
+        let n_segs = bytes[pos + 26] as usize;
+        let table_end = pos + 27 + n_segs;
+        if table_end > bytes.len() {
+            break;
+        }
+        let mut data_pos = table_end;
+        let same_stream = *serial.get_or_insert(page_serial) == page_serial;
+        for &lace in &bytes[pos + 27..table_end] {
+            if data_pos >= bytes.len() {
+                break;
+            }
+            let seg_end = (data_pos + lace as usize).min(bytes.len());
+            if same_stream {
+                current.extend_from_slice(&bytes[data_pos..seg_end]);
+                if lace < 255 {
+                    packets.push(std::mem::take(&mut current));
+                }
+            }
+            data_pos += lace as usize;
+        }
+        pos = data_pos;
+    }
+    // Data cut off mid-packet (a truncated file, or a comment header with
+    // cover art longer than the bytes read): keep what arrived
+    if !current.is_empty() && packets.len() < OGG_MAX_PACKETS {
+        packets.push(current);
+    }
+
+    packets
+        .iter()
+        .find_map(|p| {
+            p.strip_prefix(b"\x03vorbis".as_slice())
+                .or_else(|| p.strip_prefix(b"OpusTags".as_slice()))
+        })
+        .map(parse_vorbis_comment_block)
+        .unwrap_or_default()
+}
+
+/// Parse a Vorbis comment block (vendor string + `KEY=value` list), without
+/// the packet signature.
+pub fn parse_vorbis_comment_block(data: &[u8]) -> Vec<(String, String)> {
+    let mut out = Vec::new();
+    let read_u32 = |p: usize| data.get(p..p + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]) as usize);
+    let Some(vendor_len) = read_u32(0) else { return out };
+    let mut pos = 4 + vendor_len;
+    let Some(count) = read_u32(pos) else { return out };
+    pos += 4;
+    for _ in 0..count {
+        let Some(len) = read_u32(pos) else { break };
+        pos += 4;
+        let Some(entry) = data.get(pos..pos + len) else { break };
+        pos += len;
+        let entry = String::from_utf8_lossy(entry);
+        let Some((key, value)) = entry.split_once('=') else { continue };
+        let key = key.to_ascii_uppercase();
+        if matches!(key.as_str(), "METADATA_BLOCK_PICTURE" | "COVERART" | "COVERARTMIME") {
+            continue;
+        }
+        if let Some(e) = non_empty(friendly_vorbis_key(&key), value.to_string()) {
+            out.push(e);
+        }
+    }
+    out
+}
+
+fn friendly_vorbis_key(key: &str) -> String {
+    let name = match key {
+        "TITLE" => "Title",
+        "ARTIST" => "Artist",
+        "ALBUMARTIST" | "ALBUM ARTIST" => "Album Artist",
+        "ALBUM" => "Album",
+        "DATE" | "YEAR" => "Year",
+        "GENRE" => "Genre",
+        "COMMENT" => "Comment",
+        "DESCRIPTION" => "Description",
+        "COMPOSER" => "Composer",
+        "COPYRIGHT" => "Copyright",
+        "LICENSE" => "License",
+        "ENCODER" => "Encoder",
+        "TRACKNUMBER" => "Track",
+        "DISCNUMBER" => "Disc",
+        "LOCATION" => "Location",
+        _ => {
+            // Unknown keys: "SOME_KEY" → "Some key"
+            let lower = key.replace('_', " ").to_lowercase();
+            let mut chars = lower.chars();
+            return chars.next().map(|c| c.to_uppercase().collect::<String>() + chars.as_str()).unwrap_or_default();
+        }
+    };
+    name.to_string()
+}
+
+// ── Xeno-canto downloads ────────────────────────────────────────────────────
+
+/// Xeno-canto style fields derived from embedded tags, for files downloaded
+/// without a `.xc.json` sidecar: title → species (and scientific name when
+/// given in parentheses), artist → recordist, comment → remarks.
+///
+/// Only applies when the tags look like a xeno-canto download (a title
+/// starting with an `XC` catalogue number, or "xeno-canto" in any tag), so
+/// ordinary music files aren't given a species. Empty otherwise.
+pub fn xeno_canto_fields(tags: &[(String, String)]) -> Vec<(String, String)> {
+    let get = |key: &str| tags.iter().find(|(k, _)| k == key).map(|(_, v)| v.trim()).filter(|v| !v.is_empty());
+    let title = get("Title");
+    let is_xc = title.is_some_and(|t| split_xc_number(t).0.is_some())
+        || tags.iter().any(|(_, v)| v.to_ascii_lowercase().contains("xeno-canto"));
+    if !is_xc {
+        return Vec::new();
+    }
+
+    let mut fields = Vec::new();
+    if let Some(title) = title {
+        let (catalogue, rest) = split_xc_number(title);
+        let (species, scientific) = match rest.rsplit_once('(') {
+            Some((name, sci)) if rest.ends_with(')') => (name.trim(), Some(sci.trim_end_matches(')').trim())),
+            _ => (rest, None),
+        };
+        if !species.is_empty() {
+            fields.push(("Species".to_string(), species.to_string()));
+        }
+        if let Some(sci) = scientific.filter(|s| !s.is_empty()) {
+            fields.push(("Scientific name".to_string(), sci.to_string()));
+        }
+        if let Some(nr) = catalogue {
+            fields.push(("Catalogue no.".to_string(), nr.to_string()));
+        }
+    }
+    if let Some(artist) = get("Artist") {
+        fields.push(("Recordist".to_string(), artist.to_string()));
+    }
+    if let Some(comment) = get("Comment").or_else(|| get("Description")) {
+        fields.push(("Remarks".to_string(), comment.to_string()));
+    }
+    fields
+}
+
+/// Split a leading `XC123456` catalogue number (and any " - " or ":" after
+/// it) off a title.
+fn split_xc_number(title: &str) -> (Option<&str>, &str) {
+    let digits = title
+        .strip_prefix("XC")
+        .map(|rest| rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len())
+        .unwrap_or(0);
+    if digits == 0 {
+        return (None, title);
+    }
+    let (nr, rest) = title.split_at(2 + digits);
+    (Some(nr), rest.trim_start_matches(|c: char| c.is_whitespace() || matches!(c, '-' | ':' | '\u{2013}')))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn id3_frame(id: &[u8; 4], body: &[u8]) -> Vec<u8> {
+        let mut f = id.to_vec();
+        f.extend_from_slice(&(body.len() as u32).to_be_bytes());
+        f.extend_from_slice(&[0, 0]);
+        f.extend_from_slice(body);
+        f
+    }
+
+    fn id3v23(frames: &[Vec<u8>]) -> Vec<u8> {
+        let body: Vec<u8> = frames.concat();
+        let size = body.len() + 16; // trailing padding
+        let mut tag = b"ID3\x03\x00\x00".to_vec();
+        tag.extend([(size >> 21) as u8 & 0x7F, (size >> 14) as u8 & 0x7F, (size >> 7) as u8 & 0x7F, size as u8 & 0x7F]);
+        tag.extend(body);
+        tag.extend([0u8; 16]);
+        tag.extend([0xFF, 0xFB, 0x90, 0x00]); // first audio frame header
+        tag
+    }
+
+    #[test]
+    fn test_id3v23_text_and_comment() {
+        let mut utf16_artist = vec![1u8, 0xFF, 0xFE];
+        for u in "Jane Doe".encode_utf16() {
+            utf16_artist.extend(u.to_le_bytes());
+        }
+        let bytes = id3v23(&[
+            id3_frame(b"TIT2", b"\x00XC123456 Common Pipistrelle (Pipistrellus pipistrellus)"),
+            id3_frame(b"TPE1", &utf16_artist),
+            id3_frame(b"COMM", b"\x00eng\x00Feeding buzz near street light"),
+            id3_frame(b"COMM", b"\x00engiTunNORM\x00 0000"),
+            id3_frame(b"APIC", b"\x00image/jpeg\x00\x03\x00\xFF\xD8"),
+        ]);
+        let tags = parse_embedded_tags(&bytes);
+        assert_eq!(tags, vec![
+            ("Title".to_string(), "XC123456 Common Pipistrelle (Pipistrellus pipistrellus)".to_string()),
+            ("Artist".to_string(), "Jane Doe".to_string()),
+            ("Comment".to_string(), "Feeding buzz near street light".to_string()),
+        ]);
+
+        let xc = xeno_canto_fields(&tags);
+        assert_eq!(xc, vec![
+            ("Species".to_string(), "Common Pipistrelle".to_string()),
+            ("Scientific name".to_string(), "Pipistrellus pipistrellus".to_string()),
+            ("Catalogue no.".to_string(), "XC123456".to_string()),
+            ("Recordist".to_string(), "Jane Doe".to_string()),
+            ("Remarks".to_string(), "Feeding buzz near street light".to_string()),
+        ]);
+    }
+
+    /// A Vorbis comment packet holding three tags.
+    fn vorbis_comment() -> Vec<u8> {
+        let mut comment = b"\x03vorbis".to_vec();
+        let vendor = b"Xiph.Org libVorbis";
+        comment.extend((vendor.len() as u32).to_le_bytes());
+        comment.extend(vendor);
+        let entries: [&[u8]; 3] = [b"TITLE=Myotis daubentonii", b"artist=A. Recordist", b"ALBUM=xeno-canto"];
+        comment.extend((entries.len() as u32).to_le_bytes());
+        for e in entries {
+            comment.extend((e.len() as u32).to_le_bytes());
+            comment.extend(e);
+        }
+        comment.push(1); // framing bit
+        comment
+    }
+
+    /// One Ogg page holding `packets`.
+    fn ogg_page(packets: &[&[u8]]) -> Vec<u8> {
+        let mut lacing = Vec::new();
+        for p in packets {
+            lacing.extend(std::iter::repeat_n(255u8, p.len() / 255));
+            lacing.push((p.len() % 255) as u8);
+        }
+        let mut out = b"OggS\x00\x00".to_vec();
+        out.extend([0u8; 8]); // granule
+        out.extend(7u32.to_le_bytes()); // serial
+        out.extend([0u8; 8]); // sequence + CRC
+        out.push(lacing.len() as u8);
+        out.extend(lacing);
+        for p in packets {
+            out.extend_from_slice(p);
+        }
+        out
+    }
+
+    /// An ident page followed by a comment page.
+    fn vorbis_header_pages() -> Vec<u8> {
+        let mut bytes = ogg_page(&[b"\x01vorbis-ident"]);
+        bytes.extend(ogg_page(&[&vorbis_comment()]));
+        bytes
+    }
+
+    #[test]
+    fn test_ogg_vorbis_comments() {
+        let bytes = vorbis_header_pages();
+
+        let tags = parse_embedded_tags(&bytes);
+        assert_eq!(tags, vec![
+            ("Title".to_string(), "Myotis daubentonii".to_string()),
+            ("Artist".to_string(), "A. Recordist".to_string()),
+            ("Album".to_string(), "xeno-canto".to_string()),
+        ]);
+        let xc = xeno_canto_fields(&tags);
+        assert_eq!(xc[0], ("Species".to_string(), "Myotis daubentonii".to_string()));
+        assert_eq!(xc[1], ("Recordist".to_string(), "A. Recordist".to_string()));
+    }
+
+    #[test]
+    fn test_truncated_ogg_comments() {
+        let bytes = vorbis_header_pages();
+        // Cut inside the last tag: the ones before it survive
+        let cut = bytes.len() - 4;
+        assert_eq!(parse_ogg_comments(&bytes[..cut]), vec![
+            ("Title".to_string(), "Myotis daubentonii".to_string()),
+            ("Artist".to_string(), "A. Recordist".to_string()),
+        ]);
+        // Cut right after the comment page's lacing table, or inside the ident packet
+        let comment_page = ogg_page(&[&vorbis_comment()]);
+        let header_len = 27 + comment_page[26] as usize;
+        let ident_len = bytes.len() - comment_page.len();
+        assert!(parse_ogg_comments(&bytes[..ident_len + header_len]).is_empty());
+        assert!(parse_ogg_comments(&bytes[..ident_len - 3]).is_empty());
+
+        // A comment packet with cover art running past the bytes read
+        let mut big = b"\x03vorbis".to_vec();
+        big.extend(0u32.to_le_bytes());
+        big.extend(2u32.to_le_bytes());
+        for e in [b"TITLE=Pipistrellus".to_vec(), [b"COVERART=".as_slice(), &[b'x'; 60_000]].concat()] {
+            big.extend((e.len() as u32).to_le_bytes());
+            big.extend(e);
+        }
+        let mut bytes = ogg_page(&[b"\x01vorbis-ident"]);
+        bytes.extend(ogg_page(&[&big]));
+        assert_eq!(parse_ogg_comments(&bytes[..1000]), vec![("Title".to_string(), "Pipistrellus".to_string())]);
+    }
+
+    #[test]
+    fn test_untagged_and_non_xc_files() {
+        assert!(parse_embedded_tags(&[0xFF, 0xFB, 0x90, 0x00]).is_empty());
+        assert!(parse_embedded_tags(b"ID3\x03").is_empty());
+        let music = vec![("Title".to_string(), "Song".to_string()), ("Artist".to_string(), "Band".to_string())];
+        assert!(xeno_canto_fields(&music).is_empty());
+    }
+}
@@ -0,0 +1,61 @@
+//! Which JSON files next to a recording count as its xeno-canto sidecar.
+//!
+//! Shared by the browser's file picker and the desktop loader, so both pick
+//! the same sidecar for the same file.
+
+/// Sidecar names tried for an audio file, best first: `<stem>.xc.json`,
+/// `<name>.xc.json` (e.g. `XC1234.mp3.xc.json`), then `<stem>.json`. The last
+/// is only used if it holds a xeno-canto record (see [`accepts_sidecar`]).
+pub fn sidecar_names(audio_name: &str) -> [String; 3] {
+    let stem = audio_name.rfind('.').filter(|&dot| dot > 0).map_or(audio_name, |dot| &audio_name[..dot]);
+    [
+        format!("{stem}.xc.json"),
+        format!("{audio_name}.xc.json"),
+        format!("{stem}.json"),
+    ]
+}
+
+/// Whether `json` is a xeno-canto recording record rather than some other
+/// JSON file that happens to share the audio file's stem.
+pub fn is_xc_record(json: &serde_json::Value) -> bool {
+    !json["id"].is_null() && (json["gen"].is_string() || json["en"].is_string())
+}
+
+/// Whether the sidecar `name` holding `json` should be used. An `.xc.json`
+/// name is trusted; a plain `.json` has to look like a xeno-canto record.
+pub fn accepts_sidecar(name: &str, json: &serde_json::Value) -> bool {
+    name.to_ascii_lowercase().ends_with(".xc.json") || is_xc_record(json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_sidecar_names() {
+        assert_eq!(sidecar_names("XC1234.mp3"), ["XC1234.xc.json", "XC1234.mp3.xc.json", "XC1234.json"]);
+        assert_eq!(sidecar_names("night.2024.wav")[0], "night.2024.xc.json");
+        // No extension, or only a leading dot: the whole name is the stem
+        assert_eq!(sidecar_names("XC1234")[2], "XC1234.json");
+        assert_eq!(sidecar_names(".hidden")[0], ".hidden.xc.json");
+    }
+
+    #[test]
+    fn test_xc_records() {
+        assert!(is_xc_record(&json!({ "id": "1234", "gen": "Myotis", "sp": "daubentonii" })));
+        assert!(is_xc_record(&json!({ "id": 1234, "en": "Daubenton's Bat" })));
+        assert!(!is_xc_record(&json!({ "gen": "Myotis" })));
+        assert!(!is_xc_record(&json!({ "id": "1234", "gen": 5 })));
+        assert!(!is_xc_record(&json!(["id", "gen"])));
+    }
+
+    #[test]
+    fn test_accepts_sidecar() {
+        let other = json!({ "version": 2, "tracks": [] });
+        assert!(accepts_sidecar("XC1234.xc.json", &other));
+        assert!(accepts_sidecar("XC1234.XC.JSON", &other));
+        assert!(!accepts_sidecar("XC1234.json", &other));
+        assert!(accepts_sidecar("XC1234.json", &json!({ "id": "1234", "en": "Noctule" })));
+    }
+}
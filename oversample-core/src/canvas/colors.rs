@@ -13,6 +13,15 @@ pub fn magnitude_to_db(mag: f32) -> f32 {
     20.0 * mag.log10()
 }
 
+/// Convert a Hann-windowed STFT magnitude to dB relative to digital full scale.
+///
+/// A full-scale sine (amplitude 1.0) peaks at `fft_size / 4` after the Hann
+/// window (coherent gain 0.5, one-sided N/2), so that maps to 0 dBFS.
+#[inline]
+pub fn magnitude_to_dbfs(mag: f32, fft_size: usize) -> f32 {
+    magnitude_to_db(mag) - magnitude_to_db(fft_size as f32 / 4.0)
+}
+
 /// Map a spectrogram magnitude to a greyscale pixel value (0-255).
 /// Uses log scale (dB) for perceptual brightness.
 pub fn magnitude_to_greyscale(mag: f32, max_mag: f32) -> u8 {
@@ -50,19 +59,22 @@ pub const RESISTOR_BANDS: [[u8; 3]; 10] = [
     [255, 255, 255],   // 9 - white
 ];
 
-/// Resistor color band color for frequency markers (single digit based on 10 kHz).
-pub fn freq_marker_color(freq_hz: f64) -> [u8; 3] {
-    let digit = (freq_hz / 10_000.0).round() as u32 % 10;
-    RESISTOR_BANDS[digit as usize]
-}
-
-/// Solid shield color: picks the resistor band color for the digit that
-/// changes at the given scale. `division_hz` is the bar interval (e.g. 10000
-/// for major bars, 1000 for minor 1 kHz bars).
-pub fn freq_shield_color(freq_hz: f64, division_hz: f64) -> [u8; 3] {
-    let digit = (freq_hz / division_hz).round() as u32 % 10;
-    RESISTOR_BANDS[digit as usize]
-}
+/// Colour-blind safe alternative to the resistor bands, one colour per digit.
+/// Built from the Okabe–Ito palette (black swapped for greys and white so
+/// every entry reads on a dark spectrogram); neighbouring digits differ in
+/// lightness as well as hue.
+pub const COLORBLIND_BANDS: [[u8; 3]; 10] = [
+    [187, 187, 187],   // 0 - light grey
+    [230, 159, 0],     // 1 - orange
+    [86, 180, 233],    // 2 - sky blue
+    [0, 158, 115],     // 3 - bluish green
+    [240, 228, 66],    // 4 - yellow
+    [0, 114, 178],     // 5 - blue
+    [213, 94, 0],      // 6 - vermillion
+    [204, 121, 167],   // 7 - reddish purple
+    [255, 255, 255],   // 8 - white
+    [110, 110, 110],   // 9 - dark grey
+];
 
 /// Three-band resistor color encoding for a frequency in Hz.
 /// Returns [first_digit, second_digit, multiplier] colors.
@@ -98,6 +110,28 @@ fn smoothstep(x: f32, edge0: f32, edge1: f32) -> f32 {
     t * t * (3.0 - 2.0 * t)
 }
 
+/// Composite an overlay color onto a greyscale base with strength `effective`.
+///
+/// `Normal` mixes toward `color`. `Additive` adds the scaled color to the base,
+/// saturating at white. `Screen` only applies the part of the tint that is
+/// brighter than the base. Neither of the last two darkens, so underlying
+/// energy stays visible.
+#[inline]
+pub fn blend_overlay(grey: u8, color: [f32; 3], effective: f32, blend: FlowBlendMode) -> [u8; 3] {
+    let g = grey as f32;
+    color.map(|c| {
+        let v = match blend {
+            FlowBlendMode::Normal => g + effective * (c - g),
+            FlowBlendMode::Additive => g + effective * c,
+            FlowBlendMode::Screen => {
+                let layer = effective * (c - g).max(0.0);
+                255.0 - (255.0 - g) * (255.0 - layer) / 255.0
+            }
+        };
+        v.clamp(0.0, 255.0) as u8
+    })
+}
+
 /// Map a greyscale base value and a frequency-shift amount to an RGB triple.
 /// `shift` > 0 → energy moving upward in frequency → red tint.
 /// `shift` < 0 → energy moving downward in frequency → blue tint.
@@ -107,6 +141,17 @@ fn smoothstep(x: f32, edge0: f32, edge1: f32) -> f32 {
 /// - `flow_gate` (0.0–1.0): how large must the shift be to show color
 /// - `opacity` (0.0–1.0): overall color strength multiplier
 pub fn flow_rgb(grey: u8, shift: f32, intensity_gate: f32, flow_gate: f32, opacity: f32, shift_gain: f32, color_gamma: f32) -> [u8; 3] {
+    flow_rgb_blend(grey, shift, intensity_gate, flow_gate, opacity, shift_gain, color_gamma, FlowBlendMode::Normal)
+}
+
+/// `flow_rgb` with a selectable compositing mode.
+#[allow(clippy::too_many_arguments)]
+pub fn flow_rgb_blend(
+    grey: u8, shift: f32,
+    intensity_gate: f32, flow_gate: f32, opacity: f32,
+    shift_gain: f32, color_gamma: f32,
+    blend: FlowBlendMode,
+) -> [u8; 3] {
     let g_norm = grey as f32 / 255.0;
 
     // Smooth intensity gate: ramp from gate*0.6 to gate*1.4
@@ -143,16 +188,12 @@ pub fn flow_rgb(grey: u8, shift: f32, intensity_gate: f32, flow_gate: f32, opaci
         (rg, rg, b)
     };
     // Blend between grey and colored pixel using effective as alpha
-    [
-        (g + effective * (r - g)).clamp(0.0, 255.0) as u8,
-        (g + effective * (gv - g)).clamp(0.0, 255.0) as u8,
-        (g + effective * (b - g)).clamp(0.0, 255.0) as u8,
-    ]
+    blend_overlay(grey, [r, gv, b], effective, blend)
 }
 
 // ── Selectable flow color schemes ────────────────────────────────────────────
 
-use crate::types::FlowColorScheme;
+use crate::types::{FlowBlendMode, FlowColorScheme};
 
 /// Dispatch to the selected flow color scheme.
 /// Phase and PhaseCoherence algorithms ignore this and use their own color functions.
@@ -160,11 +201,11 @@ pub fn flow_rgb_scheme(
     grey: u8, shift: f32,
     intensity_gate: f32, flow_gate: f32, opacity: f32,
     shift_gain: f32, color_gamma: f32,
-    scheme: FlowColorScheme,
+    scheme: FlowColorScheme, blend: FlowBlendMode,
 ) -> [u8; 3] {
     match scheme {
-        FlowColorScheme::RedBlue => flow_rgb(grey, shift, intensity_gate, flow_gate, opacity, shift_gain, color_gamma),
-        _ => flow_rgb_diverging(grey, shift, intensity_gate, flow_gate, opacity, shift_gain, color_gamma, scheme),
+        FlowColorScheme::RedBlue => flow_rgb_blend(grey, shift, intensity_gate, flow_gate, opacity, shift_gain, color_gamma, blend),
+        _ => flow_rgb_diverging(grey, shift, intensity_gate, flow_gate, opacity, shift_gain, color_gamma, scheme, blend),
     }
 }
 
@@ -174,7 +215,7 @@ fn flow_rgb_diverging(
     grey: u8, shift: f32,
     intensity_gate: f32, flow_gate: f32, opacity: f32,
     shift_gain: f32, color_gamma: f32,
-    scheme: FlowColorScheme,
+    scheme: FlowColorScheme, blend: FlowBlendMode,
 ) -> [u8; 3] {
     let g_norm = grey as f32 / 255.0;
 
@@ -215,10 +256,26 @@ fn flow_rgb_diverging(
     let gv = g + t * (endpoint[1] - g);
     let b = g + t * (endpoint[2] - g);
     // Blend between grey and colored pixel using effective as alpha
+    blend_overlay(grey, [r, gv, b], effective, blend)
+}
+
+/// Colour for a difference spectrogram pixel. `diff` is the signed
+/// difference scaled to -1.0..=1.0 (positive = A louder, shown red;
+/// negative = B louder, shown blue); `grey` is the brightness of the signal
+/// there, so identical regions keep their spectrogram look in grey.
+pub fn diff_rgb(grey: u8, diff: f32) -> [u8; 3] {
+    const NEG: [f32; 3] = [59.0, 76.0, 192.0];
+    const POS: [f32; 3] = [220.0, 40.0, 38.0];
+    let t = diff.clamp(-1.0, 1.0);
+    let endpoint = if t >= 0.0 { POS } else { NEG };
+    let a = t.abs();
+    // Difference colours stay visible even where the signal is faint
+    let g = grey as f32 * (1.0 - 0.5 * a);
+    let lift = 0.35 + 0.65 * (grey as f32 / 255.0);
     [
-        (g + effective * (r - g)).clamp(0.0, 255.0) as u8,
-        (g + effective * (gv - g)).clamp(0.0, 255.0) as u8,
-        (g + effective * (b - g)).clamp(0.0, 255.0) as u8,
+        (g + a * endpoint[0] * lift).min(255.0) as u8,
+        (g + a * endpoint[1] * lift).min(255.0) as u8,
+        (g + a * endpoint[2] * lift).min(255.0) as u8,
     ]
 }
 
@@ -227,8 +284,10 @@ fn flow_rgb_diverging(
 ///
 /// Coherent pixels → bright blue-white. Forward deviation → red. Backward → blue.
 /// `intensity_gate` gates quiet pixels. `opacity` blends between grey and color.
-/// `shift_gain` amplifies the deviation before coloring.
-pub fn coherence_rgb(grey: u8, deviation: f32, intensity_gate: f32, flow_gate: f32, opacity: f32, shift_gain: f32, color_gamma: f32) -> [u8; 3] {
+/// `shift_gain` amplifies the deviation before coloring. `blend` selects how
+/// the color is composited onto the grey base.
+#[allow(clippy::too_many_arguments)]
+pub fn coherence_rgb(grey: u8, deviation: f32, intensity_gate: f32, flow_gate: f32, opacity: f32, shift_gain: f32, color_gamma: f32, blend: FlowBlendMode) -> [u8; 3] {
     let g_norm = grey as f32 / 255.0;
 
     // Smooth intensity gate
@@ -280,11 +339,29 @@ pub fn coherence_rgb(grey: u8, deviation: f32, intensity_gate: f32, flow_gate: f
     };
 
     // Blend with greyscale by effective opacity
-    let grey_f = grey as f32;
-    let final_r = (grey_f + effective * (r - grey_f)).clamp(0.0, 255.0) as u8;
-    let final_g = (grey_f + effective * (g - grey_f)).clamp(0.0, 255.0) as u8;
-    let final_b = (grey_f + effective * (b - grey_f)).clamp(0.0, 255.0) as u8;
-    [final_r, final_g, final_b]
+    blend_overlay(grey, [r, g, b], effective, blend)
+}
+
+/// Phase coherence mapped through a colormap LUT instead of the built-in
+/// deviation colors. The LUT index is the coherence (top of the map =
+/// coherent, bottom = full deviation in either direction), and the result is
+/// scaled by spectrogram brightness so quiet regions stay dark.
+#[allow(clippy::too_many_arguments)]
+pub fn coherence_lut_rgb(grey: u8, deviation: f32, intensity_gate: f32, opacity: f32, shift_gain: f32, color_gamma: f32, blend: FlowBlendMode, lut: impl Fn(u8) -> [u8; 3]) -> [u8; 3] {
+    let g_norm = grey as f32 / 255.0;
+    let ig_lo = intensity_gate * 0.6;
+    let ig_hi = (intensity_gate * 1.4).min(1.0);
+    let effective = smoothstep(g_norm, ig_lo, ig_hi) * opacity;
+    if effective < 0.001 {
+        return [grey, grey, grey];
+    }
+
+    let dev = (deviation.abs() * shift_gain).min(1.0);
+    let dev = if color_gamma == 1.0 { dev } else { dev.powf(color_gamma) };
+    let [r, g, b] = lut(((1.0 - dev) * 255.0).round() as u8);
+
+    let bright = g_norm.powf(0.75);
+    blend_overlay(grey, [r as f32 * bright, g as f32 * bright, b as f32 * bright], effective, blend)
 }
 
 // ── Oklch perceptually uniform phase colormap ────────────────────────────────
@@ -365,6 +442,26 @@ pub fn phase_rgb(grey: u8, phase: f32, intensity_gate: f32) -> [u8; 3] {
     ]
 }
 
+/// Format an RGB color as a CSS hex string (`#rrggbb`).
+pub fn rgb_to_hex(rgb: [u8; 3]) -> String {
+    format!("#{:02x}{:02x}{:02x}", rgb[0], rgb[1], rgb[2])
+}
+
+/// Parse a CSS hex color (`#rrggbb` or `rrggbb`). Returns None if malformed.
+pub fn hex_to_rgb(s: &str) -> Option<[u8; 3]> {
+    let s = s.trim().trim_start_matches('#');
+    if s.len() != 6 || !s.is_ascii() {
+        return None;
+    }
+    let channel = |i: usize| u8::from_str_radix(&s[i..i + 2], 16).ok();
+    Some([channel(0)?, channel(2)?, channel(4)?])
+}
+
+/// Format an RGB color with alpha as a CSS `rgba(...)` string.
+pub fn rgba_css(rgb: [u8; 3], alpha: f64) -> String {
+    format!("rgba({}, {}, {}, {})", rgb[0], rgb[1], rgb[2], alpha)
+}
+
 /// Label for a frequency marker (number only, e.g. "40").
 pub fn freq_marker_label(freq_hz: f64) -> String {
     format!("{}", (freq_hz / 1000.0).round() as u32)
@@ -622,3 +719,23 @@ pub const TURBO_LUT: [[u8; 3]; 256] = [
     [145,11,1],[142,10,1],[139,9,1],[135,8,1],[132,7,1],[129,6,2],[125,5,2],[122,4,2],
 ];
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blend_overlay_known_pixels() {
+        let color = [200.0, 50.0, 0.0];
+        assert_eq!(blend_overlay(100, color, 0.5, FlowBlendMode::Normal), [150, 75, 50]);
+        assert_eq!(blend_overlay(100, color, 0.5, FlowBlendMode::Additive), [200, 125, 100]);
+        assert_eq!(blend_overlay(100, color, 0.5, FlowBlendMode::Screen), [130, 100, 100]);
+
+        // Adding saturates at white, where a per-channel max would stay at 200
+        assert_eq!(blend_overlay(200, [200.0; 3], 1.0, FlowBlendMode::Additive), [255; 3]);
+        // No strength leaves the base untouched in every mode
+        for mode in [FlowBlendMode::Normal, FlowBlendMode::Additive, FlowBlendMode::Screen] {
+            assert_eq!(blend_overlay(80, color, 0.0, mode), [80; 3]);
+        }
+    }
+}
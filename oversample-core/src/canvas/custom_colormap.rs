@@ -0,0 +1,156 @@
+//! User-defined colormaps.
+//!
+//! A custom colormap is a list of evenly spaced control colors, low to high.
+//! It is expanded to a 256-entry LUT — the same resolution as the built-in
+//! maps — by linear interpolation between neighbouring stops, so a map
+//! imported with 256 stops (e.g. from matplotlib) is reproduced exactly.
+
+use serde::{Deserialize, Serialize};
+
+use super::colors::{hex_to_rgb, rgb_to_hex};
+
+/// Fewest control colors a custom map may have.
+pub const MIN_STOPS: usize = 2;
+/// Most control colors a custom map may have (one per LUT entry).
+pub const MAX_STOPS: usize = 256;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CustomColormap {
+    pub name: String,
+    /// Control colors from the lowest to the highest level.
+    pub stops: Vec<[u8; 3]>,
+}
+
+impl CustomColormap {
+    pub fn lut(&self) -> [[u8; 3]; 256] {
+        lut_from_stops(&self.stops)
+    }
+}
+
+/// Expand evenly spaced control colors into a 256-entry LUT.
+/// With no stops the result is plain greyscale.
+pub fn lut_from_stops(stops: &[[u8; 3]]) -> [[u8; 3]; 256] {
+    let mut lut = [[0u8; 3]; 256];
+    for (i, entry) in lut.iter_mut().enumerate() {
+        *entry = match stops.len() {
+            0 => [i as u8; 3],
+            1 => stops[0],
+            n => {
+                let pos = i as f64 / 255.0 * (n - 1) as f64;
+                let k = (pos.floor() as usize).min(n - 2);
+                let t = pos - k as f64;
+                let (a, b) = (stops[k], stops[k + 1]);
+                std::array::from_fn(|c| (a[c] as f64 + (b[c] as f64 - a[c] as f64) * t).round() as u8)
+            }
+        };
+    }
+    lut
+}
+
+/// Parse control colors from text, one color per line:
+///
+/// - `#rrggbb` hex (several per line are fine, separated by spaces or commas);
+/// - `r g b` or `r, g, b` triples, either floats in 0–1 as printed by
+///   matplotlib (`cmap(np.linspace(0, 1, n))`) or integers in 0–255.
+///   A fourth (alpha) value is ignored, and `[`, `]`, `(`, `)` are
+///   skipped so a pasted Python list works.
+///
+/// Lines starting with `//` are comments. Numeric triples are treated as
+/// 0–1 floats when no value in the whole input exceeds 1.
+pub fn parse_stops(text: &str) -> Result<Vec<[u8; 3]>, String> {
+    enum Stop {
+        Rgb([u8; 3]),
+        Numeric([f64; 3]),
+    }
+    let mut parsed = Vec::new();
+    // One bracketed group per line, so `[[r, g, b], [r, g, b]]` splits up
+    let text = text.replace(']', "\n");
+    for (line_no, line) in text.lines().enumerate() {
+        let line = line.split("//").next().unwrap_or("");
+        let tokens: Vec<&str> = line
+            .split(|c: char| c.is_whitespace() || matches!(c, ',' | '[' | '(' | ')'))
+            .filter(|t| !t.is_empty())
+            .collect();
+        if tokens.is_empty() {
+            continue;
+        }
+        if tokens.iter().all(|t| t.starts_with('#')) {
+            for t in tokens {
+                let rgb = hex_to_rgb(t).ok_or_else(|| format!("Line {}: bad color \"{t}\"", line_no + 1))?;
+                parsed.push(Stop::Rgb(rgb));
+            }
+            continue;
+        }
+        let values: Vec<f64> = tokens
+            .iter()
+            .map(|t| t.parse::<f64>().ok().filter(|v| v.is_finite() && *v >= 0.0))
+            .collect::<Option<_>>()
+            .ok_or_else(|| format!("Line {}: expected #rrggbb or r g b", line_no + 1))?;
+        if values.len() != 3 && values.len() != 4 {
+            return Err(format!("Line {}: expected 3 values, found {}", line_no + 1, values.len()));
+        }
+        parsed.push(Stop::Numeric([values[0], values[1], values[2]]));
+    }
+
+    let unit_floats = parsed.iter().all(|s| match s {
+        Stop::Numeric(v) => v.iter().all(|&x| x <= 1.0),
+        Stop::Rgb(_) => true,
+    });
+    let stops: Vec<[u8; 3]> = parsed
+        .into_iter()
+        .map(|s| match s {
+            Stop::Rgb(rgb) => rgb,
+            Stop::Numeric(v) => {
+                let scale = if unit_floats { 255.0 } else { 1.0 };
+                v.map(|x| (x * scale).round().min(255.0) as u8)
+            }
+        })
+        .collect();
+
+    if stops.len() < MIN_STOPS {
+        return Err(format!("Need at least {MIN_STOPS} colors"));
+    }
+    if stops.len() > MAX_STOPS {
+        return Err(format!("At most {MAX_STOPS} colors"));
+    }
+    Ok(stops)
+}
+
+/// Write stops as `#rrggbb` lines (readable by [`parse_stops`]).
+pub fn format_stops(stops: &[[u8; 3]]) -> String {
+    stops.iter().map(|&c| rgb_to_hex(c) + "\n").collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::canvas::colors::VIRIDIS_LUT;
+
+    #[test]
+    fn test_two_stops_interpolate_linearly() {
+        let lut = lut_from_stops(&[[0, 0, 0], [255, 100, 0]]);
+        assert_eq!(lut[0], [0, 0, 0]);
+        assert_eq!(lut[255], [255, 100, 0]);
+        assert_eq!(lut[51], [51, 20, 0]);
+    }
+
+    #[test]
+    fn test_full_lut_round_trips() {
+        assert_eq!(lut_from_stops(&VIRIDIS_LUT), VIRIDIS_LUT);
+        let text = format_stops(&VIRIDIS_LUT);
+        assert_eq!(parse_stops(&text).unwrap(), VIRIDIS_LUT.to_vec());
+    }
+
+    #[test]
+    fn test_parse_formats() {
+        let floats = "[[0.0, 0.0, 0.0, 1.0], [1.0, 0.5, 0.0, 1.0]]";
+        assert_eq!(parse_stops(floats).unwrap(), vec![[0, 0, 0], [255, 128, 0]]);
+        let ints = "// two stops\n0 0 0\n255 128 0\n";
+        assert_eq!(parse_stops(ints).unwrap(), vec![[0, 0, 0], [255, 128, 0]]);
+        let hex = "#000000, #ff8000";
+        assert_eq!(parse_stops(hex).unwrap(), vec![[0, 0, 0], [255, 128, 0]]);
+        assert!(parse_stops("#000000").is_err());
+        assert!(parse_stops("1 2\n3 4").is_err());
+        assert!(parse_stops("#00000g\n#ffffff").is_err());
+    }
+}
@@ -0,0 +1,146 @@
+//! Level-of-detail (LOD) table for the tiled spectrogram.
+//!
+//! Each LOD is an STFT hop size; zoom is measured in pixels per column of
+//! the baseline LOD (hop 512). A view picks the LOD whose zoom range it is
+//! in, with hysteresis so a zoom hovering at a boundary doesn't make it pop
+//! back and forth between coarse and fine tiles.
+
+pub struct LodConfig {
+    pub fft_size: usize,
+    pub hop_size: usize,
+}
+
+pub const NUM_LODS: usize = 8;
+
+/// The LOD level used as the spatial coordinate baseline (hop=512).
+/// All scroll positions, total_cols, etc. are expressed in this LOD's column space.
+pub const LOD_BASELINE: u8 = 2;
+
+/// Hop size of the baseline LOD — used for coordinate-space calculations.
+pub const BASELINE_HOP: usize = 512;
+
+pub const LOD_CONFIGS: [LodConfig; NUM_LODS] = [
+    LodConfig { fft_size: 256, hop_size: 8192 }, // LOD 0 — ultra-wide overview (quality downscaled)
+    LodConfig { fft_size: 256, hop_size: 2048 }, // LOD 1 — wide overview (quality downscaled)
+    LodConfig { fft_size: 256, hop_size: 512 },  // LOD 2 — normal resolution (baseline)
+    LodConfig { fft_size: 256, hop_size: 128 },  // LOD 3 — zoomed in
+    LodConfig { fft_size: 256, hop_size: 32 },   // LOD 4 — deep zoom
+    LodConfig { fft_size: 256, hop_size: 8 },    // LOD 5 — extreme zoom
+    LodConfig { fft_size: 256, hop_size: 2 },    // LOD 6 — sample-level zoom
+    LodConfig { fft_size: 128, hop_size: 1 },    // LOD 7 — per-sample zoom (finest possible)
+];
+
+/// Zoom (pixels per baseline column) at which each LOD above 0 takes over.
+/// LOD 7 is only 2× finer than LOD 6 (hop 1 vs 2), so its threshold is 2×.
+const LOD_THRESHOLDS: [f64; NUM_LODS - 1] = [0.125, 0.5, 2.0, 8.0, 32.0, 128.0, 256.0];
+
+/// How far (as a zoom factor) past its thresholds the current LOD is kept.
+const LOD_HYSTERESIS: f64 = 1.25;
+
+/// The LOD whose threshold range contains `zoom`, ignoring hysteresis.
+pub fn ideal_lod(zoom: f64) -> u8 {
+    LOD_THRESHOLDS.iter().take_while(|&&t| zoom >= t).count() as u8
+}
+
+/// Highest zoom (pixels per baseline column) at which one analysis window
+/// spans no more than `max_px` pixels, given the FFT size used at each LOD.
+/// Past that point the spectrogram only smears each window wider and shows
+/// no finer detail.
+pub fn window_zoom_limit(max_px: f64, fft_for_lod: impl Fn(u8) -> usize) -> f64 {
+    let mut limit = 0.0f64;
+    for lod in 0..NUM_LODS as u8 {
+        let lo = if lod == 0 { 0.0 } else { LOD_THRESHOLDS[lod as usize - 1] };
+        let hi = LOD_THRESHOLDS.get(lod as usize).copied().unwrap_or(f64::INFINITY);
+        let fits = max_px * BASELINE_HOP as f64 / fft_for_lod(lod).max(1) as f64;
+        if fits >= lo {
+            limit = limit.max(fits.min(hi));
+        }
+    }
+    limit
+}
+
+/// Select the LOD level for `zoom` (pixels per baseline (LOD2) column), given
+/// the level `prev` a view showed last.
+///
+/// Keeps `prev` while `zoom` stays within `LOD_HYSTERESIS` of that level's
+/// range, so zooming back and forth across a threshold doesn't flip levels.
+/// Selecting again at the same zoom with the result as `prev` returns it
+/// unchanged.
+pub fn select_lod(zoom: f64, prev: Option<u8>) -> u8 {
+    let ideal = ideal_lod(zoom);
+    match prev {
+        Some(prev) if prev != ideal && (prev as usize) < NUM_LODS => {
+            let lo = if prev == 0 { 0.0 } else { LOD_THRESHOLDS[prev as usize - 1] };
+            let hi = LOD_THRESHOLDS.get(prev as usize).copied().unwrap_or(f64::INFINITY);
+            if zoom >= lo / LOD_HYSTERESIS && zoom < hi * LOD_HYSTERESIS { prev } else { ideal }
+        }
+        _ => ideal,
+    }
+}
+
+/// Ratio of baseline (LOD2) columns to LOD_L columns (how many LOD_L cols per baseline col).
+/// LOD0: 0.0625, LOD1: 0.25, LOD2: 1.0, LOD3: 4.0, LOD4: 16.0, LOD5: 64.0, LOD6: 256.0, LOD7: 512.0
+pub fn lod_ratio(lod: u8) -> f64 {
+    BASELINE_HOP as f64 / LOD_CONFIGS[lod as usize].hop_size as f64
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ideal_lod_thresholds() {
+        assert_eq!(ideal_lod(0.01), 0);
+        for (i, &t) in LOD_THRESHOLDS.iter().enumerate() {
+            assert_eq!(ideal_lod(t * 0.99), i as u8);
+            assert_eq!(ideal_lod(t), i as u8 + 1);
+        }
+        assert_eq!(ideal_lod(1e6), NUM_LODS as u8 - 1);
+    }
+
+    #[test]
+    fn test_hysteresis_zooming_in_and_out() {
+        // Zooming in past the LOD 2 -> 3 threshold at 2.0: LOD 2 holds to 2.5
+        assert_eq!(select_lod(2.4, Some(2)), 2);
+        assert_eq!(select_lod(2.6, Some(2)), 3);
+        // Zooming back out: LOD 3 holds down to 1.6
+        assert_eq!(select_lod(1.7, Some(3)), 3);
+        assert_eq!(select_lod(1.5, Some(3)), 2);
+        // A jump well past the band goes straight to the ideal level
+        assert_eq!(select_lod(40.0, Some(2)), 5);
+        assert_eq!(select_lod(0.3, Some(5)), 1);
+        assert_eq!(select_lod(2.4, None), 3);
+    }
+
+    #[test]
+    fn test_window_zoom_limit_fixed_fft() {
+        // 2048-point windows: 200 px is reached at zoom 50, inside LOD 5's range
+        assert_eq!(window_zoom_limit(200.0, |_| 2048), 50.0);
+        assert_eq!(window_zoom_limit(400.0, |_| 2048), 100.0);
+        // Small windows never reach the width before the finest LOD runs out
+        assert_eq!(window_zoom_limit(200.0, |lod| LOD_CONFIGS[lod as usize].fft_size), 800.0);
+    }
+
+    #[test]
+    fn test_window_zoom_limit_keeps_windows_under_width() {
+        // Adaptive sizes: finer LODs use smaller windows
+        let fft = |lod: u8| [4096, 4096, 2048, 1024, 512, 256, 256, 128][lod as usize];
+        for max_px in [50.0, 100.0, 200.0, 400.0] {
+            let limit = window_zoom_limit(max_px, fft);
+            let at_limit = fft(ideal_lod(limit * 0.999)) as f64 * limit / BASELINE_HOP as f64;
+            assert!(at_limit <= max_px + 1e-9, "{max_px} px: {at_limit} px at zoom {limit}");
+            assert!(window_zoom_limit(max_px * 2.0, fft) > limit);
+        }
+    }
+
+    #[test]
+    fn test_reselecting_at_same_zoom_is_stable() {
+        let mut prev = None;
+        for zoom in [1.0, 2.2, 2.2, 1.8, 2.6, 2.6, 1.7, 1.5, 300.0, 240.0] {
+            let lod = select_lod(zoom, prev);
+            assert_eq!(select_lod(zoom, Some(lod)), lod, "zoom {zoom}");
+            prev = Some(lod);
+        }
+    }
+}
@@ -1,3 +1,5 @@
 pub mod colors;
 pub mod colormap_2d;
+pub mod custom_colormap;
+pub mod lod;
 pub mod spectral_store;
@@ -11,7 +11,7 @@
 //! with LRU-by-distance eviction.  Evicted columns can be recomputed from
 //! `AudioData.samples` via `tile_cache::schedule_tile_on_demand()`.
 
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 use crate::types::SpectrogramColumn;
 
@@ -30,12 +30,32 @@ struct SpectralColumnStore {
     max_magnitude: f32,
     /// Number of `Some` columns currently stored.
     present_count: usize,
+    /// `SpectrogramColumn::energy` of every column inserted while energy
+    /// tracking is on, NaN for the rest. Kept when a column is evicted.
+    /// Empty until the first tracked insert.
+    energies: Vec<f32>,
 }
 
 thread_local! {
     /// Keyed by file_idx so multiple files can load concurrently.
     static STORES: RefCell<HashMap<usize, SpectralColumnStore>> =
         RefCell::new(HashMap::new());
+    /// Whether inserted columns record their energy (see [`set_track_energies`]).
+    static TRACK_ENERGIES: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Record each inserted column's energy so [`column_energies`] still covers
+/// evicted columns. Only the amplitude envelope needs this; turning it off
+/// frees the energies of every store.
+pub fn set_track_energies(on: bool) {
+    let was_on = TRACK_ENERGIES.with(|t| t.replace(on));
+    if was_on && !on {
+        STORES.with(|s| {
+            for store in s.borrow_mut().values_mut() {
+                store.energies = Vec::new();
+            }
+        });
+    }
 }
 
 /// Total approximate bytes used across all stores.
@@ -51,6 +71,7 @@ pub fn init(file_idx: usize, total_cols: usize, fft_size: usize) {
             fft_size,
             max_magnitude: 0.0,
             present_count: 0,
+            energies: Vec::new(),
         });
     });
 }
@@ -59,9 +80,13 @@ pub fn init(file_idx: usize, total_cols: usize, fft_size: usize) {
 /// Updates the running max magnitude.  If the global memory budget is
 /// exceeded, evicts columns from the store furthest from `start_col`.
 pub fn insert_columns(file_idx: usize, start_col: usize, cols: &[SpectrogramColumn]) {
+    let track_energies = TRACK_ENERGIES.with(Cell::get);
     STORES.with(|s| {
         let mut stores = s.borrow_mut();
         let Some(store) = stores.get_mut(&file_idx) else { return };
+        if track_energies && store.energies.len() < store.columns.len() {
+            store.energies.resize(store.columns.len(), f32::NAN);
+        }
         for (i, col) in cols.iter().enumerate() {
             let idx = start_col + i;
             if idx < store.columns.len() {
@@ -73,6 +98,9 @@ pub fn insert_columns(file_idx: usize, start_col: usize, cols: &[SpectrogramColu
                 if store.columns[idx].is_none() {
                     store.present_count += 1;
                 }
+                if track_energies {
+                    store.energies[idx] = col.energy();
+                }
                 store.columns[idx] = Some(col.clone());
             }
         }
@@ -148,6 +176,9 @@ pub fn ensure_capacity(file_idx: usize, new_total: usize) {
             Some(store) => {
                 if new_total > store.columns.len() {
                     store.columns.resize_with(new_total, || None);
+                    if !store.energies.is_empty() {
+                        store.energies.resize(new_total, f32::NAN);
+                    }
                 }
             }
             None => {
@@ -156,6 +187,7 @@ pub fn ensure_capacity(file_idx: usize, new_total: usize) {
                     fft_size: 0,
                     max_magnitude: 0.0,
                     present_count: 0,
+                    energies: Vec::new(),
                 });
             }
         }
@@ -193,6 +225,82 @@ pub fn with_columns<R>(
     })
 }
 
+/// Energies of columns `start..end` (see `SpectrogramColumn::energy`), NaN
+/// for columns not available. Columns evicted while energy tracking was on
+/// keep their energy; otherwise only present columns count. Empty if the
+/// file has no store.
+pub fn column_energies(file_idx: usize, start: usize, end: usize) -> Vec<f32> {
+    STORES.with(|s| {
+        let stores = s.borrow();
+        let Some(store) = stores.get(&file_idx) else { return Vec::new() };
+        let end = end.min(store.columns.len());
+        (start.min(end)..end)
+            .map(|i| match store.energies.get(i) {
+                Some(&e) if !e.is_nan() => e,
+                _ => store.columns[i].as_ref().map_or(f32::NAN, SpectrogramColumn::energy),
+            })
+            .collect()
+    })
+}
+
+/// Level in dB (20·log10 of magnitude) that the loudest bin of a fraction
+/// `quantile` of the present columns in `start..end` stays below. Used for
+/// viewport auto-exposure; a high quantile rather than the maximum keeps a
+/// single click or spike from darkening the view. None if no column in the
+/// range is present or all are silent.
+pub fn visible_peak_db(file_idx: usize, start: usize, end: usize, quantile: f64) -> Option<f32> {
+    STORES.with(|s| {
+        let stores = s.borrow();
+        let store = stores.get(&file_idx)?;
+        let end = end.min(store.columns.len());
+        let mut peaks: Vec<f32> = store.columns.get(start..end)?
+            .iter()
+            .flatten()
+            .map(|c| c.magnitudes.iter().copied().fold(0.0, f32::max))
+            .collect();
+        if peaks.is_empty() {
+            return None;
+        }
+        peaks.sort_by(f32::total_cmp);
+        let q = peaks[((peaks.len() - 1) as f64 * quantile.clamp(0.0, 1.0)).round() as usize];
+        (q > 0.0).then(|| 20.0 * q.log10())
+    })
+}
+
+/// (present, total) column counts for a file's store.
+pub fn coverage(file_idx: usize) -> Option<(usize, usize)> {
+    STORES.with(|s| {
+        s.borrow().get(&file_idx).map(|store| (store.present_count, store.columns.len()))
+    })
+}
+
+/// Call `f` **by reference** on each present column whose `seen` flag is
+/// false, then mark it seen. `seen` is grown to the store's length. This is
+/// how incremental analyses pick up columns as tiles complete; see
+/// `dsp::incremental`. Returns the number of columns visited.
+pub fn visit_unseen(
+    file_idx: usize,
+    seen: &mut Vec<bool>,
+    mut f: impl FnMut(usize, &SpectrogramColumn),
+) -> usize {
+    STORES.with(|s| {
+        let stores = s.borrow();
+        let Some(store) = stores.get(&file_idx) else { return 0 };
+        if seen.len() < store.columns.len() {
+            seen.resize(store.columns.len(), false);
+        }
+        let mut visited = 0;
+        for (i, col) in store.columns.iter().enumerate() {
+            if let (Some(col), false) = (col, seen[i]) {
+                seen[i] = true;
+                f(i, col);
+                visited += 1;
+            }
+        }
+        visited
+    })
+}
+
 /// Get the current running max magnitude.
 pub fn get_max_magnitude(file_idx: usize) -> f32 {
     STORES.with(|s| {
@@ -241,6 +349,27 @@ pub fn fft_size(file_idx: usize) -> Option<usize> {
     STORES.with(|s| s.borrow().get(&file_idx).map(|store| store.fft_size))
 }
 
+/// Stored magnitude nearest to `time` (seconds) and `freq` (Hz), with the
+/// store's FFT size. Columns are `hop_secs` apart, as in the file's
+/// `SpectrogramData::time_resolution`; bins follow the store's own FFT size.
+pub fn magnitude_at(file_idx: usize, sample_rate: u32, hop_secs: f64, time: f64, freq: f64) -> Option<(f32, usize)> {
+    if sample_rate == 0 || hop_secs <= 0.0 || time < 0.0 || freq < 0.0 {
+        return None;
+    }
+    STORES.with(|s| {
+        let stores = s.borrow();
+        let store = stores.get(&file_idx)?;
+        let sr = sample_rate as f64;
+        // Column from the hop in samples, bin from the bin width in Hz
+        let hop_samples = hop_secs * sr;
+        let bin_hz = sr / store.fft_size.max(1) as f64;
+        let column = (time * sr / hop_samples).floor() as usize;
+        let bin = (freq / bin_hz).round() as usize;
+        let mag = store.columns.get(column)?.as_ref()?.magnitudes.get(bin).copied()?;
+        Some((mag, store.fft_size))
+    })
+}
+
 /// Check whether a file's stored columns were computed with the expected FFT size.
 pub fn fft_matches(file_idx: usize, expected_fft_size: usize) -> bool {
     fft_size(file_idx) == Some(expected_fft_size)
@@ -272,3 +401,77 @@ pub fn clear_file(file_idx: usize) {
 pub fn clear() {
     STORES.with(|s| s.borrow_mut().clear());
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(level: f32) -> SpectrogramColumn {
+        SpectrogramColumn { magnitudes: vec![level; 4], time_offset: 0.0 }
+    }
+
+    #[test]
+    fn test_column_energies() {
+        set_track_energies(true);
+        init(7, 5, 8);
+        insert_columns(7, 1, &[column(1.0), column(0.5)]);
+        let e = column_energies(7, 0, 10);
+        assert_eq!(e.len(), 5);
+        assert!(e[0].is_nan() && e[3].is_nan());
+        assert_eq!(e[1], 4.0);
+        assert_eq!(e[2], 1.0);
+        assert!(column_energies(8, 0, 5).is_empty());
+        clear_file(7);
+        set_track_energies(false);
+    }
+
+    #[test]
+    fn test_untracked_energies_come_from_present_columns() {
+        init(9, 3, 8);
+        insert_columns(9, 0, &[column(1.0), column(0.5)]);
+        STORES.with(|s| {
+            let mut stores = s.borrow_mut();
+            let store = stores.get_mut(&9).unwrap();
+            assert!(store.energies.is_empty());
+            store.columns[0] = None;
+        });
+        let e = column_energies(9, 0, 3);
+        assert!(e[0].is_nan() && e[2].is_nan());
+        assert_eq!(e[1], 1.0);
+        clear_file(9);
+    }
+
+    #[test]
+    fn test_magnitude_at() {
+        // 8 kHz with an 8-point FFT (1 kHz bins) and a 4-sample hop (0.5 ms)
+        init(11, 10, 8);
+        let cols: Vec<SpectrogramColumn> = (0..6)
+            .map(|c| SpectrogramColumn { magnitudes: (0..5).map(|b| (c * 10 + b) as f32).collect(), time_offset: 0.0 })
+            .collect();
+        insert_columns(11, 0, &cols);
+        let hop_secs = 4.0 / 8000.0;
+        assert_eq!(magnitude_at(11, 8000, hop_secs, 0.0021, 2000.0), Some((42.0, 8)));
+        assert_eq!(magnitude_at(11, 8000, hop_secs, 0.0, 3600.0), Some((4.0, 8)));
+        // Not yet computed, past the last bin, or no store
+        assert_eq!(magnitude_at(11, 8000, hop_secs, 0.004, 1000.0), None);
+        assert_eq!(magnitude_at(11, 8000, hop_secs, 0.001, 6000.0), None);
+        assert_eq!(magnitude_at(12, 8000, hop_secs, 0.001, 1000.0), None);
+        clear_file(11);
+    }
+
+    #[test]
+    fn test_visible_peak_db_ignores_outliers() {
+        init(9, 12, 8);
+        let mut cols = vec![column(0.1); 10];
+        cols[4] = column(100.0);
+        insert_columns(9, 0, &cols);
+        let db = visible_peak_db(9, 0, 12, 0.9).unwrap();
+        assert!((db - -20.0).abs() < 1e-4, "{db}");
+        assert!((visible_peak_db(9, 0, 12, 1.0).unwrap() - 40.0).abs() < 1e-4);
+        assert_eq!(visible_peak_db(9, 10, 12, 0.9), None);
+        insert_columns(9, 10, &[column(0.0)]);
+        assert_eq!(visible_peak_db(9, 10, 12, 0.9), None);
+        assert_eq!(visible_peak_db(10, 0, 12, 0.9), None);
+        clear_file(9);
+    }
+}
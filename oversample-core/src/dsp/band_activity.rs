@@ -0,0 +1,181 @@
+//! Energy over time in one frequency band, across a whole recording.
+//!
+//! Answers questions like "how much was going on between 40 and 50 kHz
+//! tonight?". The file is cut into fixed time steps; each step gets its own
+//! short STFT and the band power of its columns is averaged, so memory stays
+//! bounded however long the recording is. Callers reading the file in blocks
+//! should cut them on multiples of [`step_samples`]. Levels are in dBFS, with a
+//! full-scale sine inside the band reading 0 dB.
+
+use crate::canvas::colors::magnitude_to_dbfs;
+use crate::dsp::fft::compute_stft_columns;
+
+const ACTIVITY_FFT: usize = 1024;
+const ACTIVITY_HOP: usize = 512;
+/// Quantile of step levels taken as the noise floor.
+const NOISE_QUANTILE: f64 = 0.2;
+/// Equivalent noise bandwidth of the Hann window, in bins: a sine's power
+/// summed across the bins it leaks into is this much above its peak bin.
+const HANN_ENBW: f32 = 1.5;
+/// Level above the noise floor a step must reach to count as active.
+pub const ACTIVE_MARGIN_DB: f32 = 10.0;
+/// Shortest step, so short files still average over a few FFT frames.
+pub const MIN_STEP_SECS: f64 = 0.1;
+
+/// Step length in samples for a file of `total_samples`: long enough to give
+/// at most `max_points` steps, and at least [`MIN_STEP_SECS`] and one FFT frame.
+pub fn step_samples(total_samples: usize, sample_rate: u32, max_points: usize) -> usize {
+    let min_step = ((MIN_STEP_SECS * sample_rate as f64).round() as usize).max(ACTIVITY_FFT);
+    total_samples.div_ceil(max_points.max(1)).max(min_step)
+}
+
+/// Band level of one time step.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BandPoint {
+    /// Start of the step, in seconds.
+    pub time_secs: f64,
+    pub level_db: f32,
+}
+
+/// Whole-file figures for a [`band_activity`] track.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BandSummary {
+    /// Band energy summed over the file, in dB relative to one second of a
+    /// full-scale sine.
+    pub total_energy_db: f32,
+    /// Mean band power over the file, in dBFS.
+    pub mean_db: f32,
+    pub peak_db: f32,
+    pub peak_time_secs: f64,
+    pub noise_floor_db: f32,
+    /// Share of steps at least [`ACTIVE_MARGIN_DB`] above the noise floor.
+    pub active_fraction: f64,
+}
+
+/// Band level per `step` samples of `samples`, over `freq_low..freq_high` Hz.
+/// `offset` is added to every time. A step shorter than one FFT frame is
+/// zero-padded.
+pub fn band_activity(
+    samples: &[f32],
+    sample_rate: u32,
+    freq_low: f64,
+    freq_high: f64,
+    step: usize,
+    offset: f64,
+) -> Vec<BandPoint> {
+    if sample_rate == 0 || samples.is_empty() || step == 0 {
+        return Vec::new();
+    }
+    let sr = sample_rate as f64;
+    let bins = ACTIVITY_FFT / 2 + 1;
+    let freq_res = sr / ACTIVITY_FFT as f64;
+    let (lo, hi) = (freq_low.min(freq_high), freq_low.max(freq_high));
+    let bin_lo = ((lo / freq_res).floor() as usize).min(bins - 1);
+    let bin_hi = ((hi / freq_res).ceil() as usize).clamp(bin_lo, bins - 1);
+
+    samples
+        .chunks(step)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let columns = compute_stft_columns(chunk, sample_rate, ACTIVITY_FFT, ACTIVITY_HOP, 0, usize::MAX);
+            let power = columns
+                .iter()
+                .map(|c| c.magnitudes[bin_lo..=bin_hi].iter().map(|m| m * m).sum::<f32>())
+                .sum::<f32>()
+                / columns.len().max(1) as f32
+                / HANN_ENBW;
+            BandPoint {
+                time_secs: offset + (i * step) as f64 / sr,
+                level_db: magnitude_to_dbfs(power.sqrt(), ACTIVITY_FFT),
+            }
+        })
+        .collect()
+}
+
+/// Total, mean and peak of a band track. None for an empty track.
+pub fn summarize(points: &[BandPoint]) -> Option<BandSummary> {
+    let peak = points.iter().max_by(|a, b| a.level_db.total_cmp(&b.level_db))?;
+    let step_secs = match points {
+        [a, b, ..] => b.time_secs - a.time_secs,
+        _ => 0.0,
+    };
+    let power: Vec<f64> = points.iter().map(|p| 10f64.powf(p.level_db as f64 / 10.0)).collect();
+    let total = power.iter().sum::<f64>();
+
+    let mut sorted: Vec<f32> = points.iter().map(|p| p.level_db).collect();
+    sorted.sort_by(f32::total_cmp);
+    let noise = sorted[((sorted.len() - 1) as f64 * NOISE_QUANTILE).round() as usize];
+    let active = points.iter().filter(|p| p.level_db >= noise + ACTIVE_MARGIN_DB).count();
+
+    Some(BandSummary {
+        total_energy_db: (10.0 * (total * step_secs).max(1e-20).log10()) as f32,
+        mean_db: (10.0 * (total / points.len() as f64).max(1e-20).log10()) as f32,
+        peak_db: peak.level_db,
+        peak_time_secs: peak.time_secs,
+        noise_floor_db: noise,
+        active_fraction: active as f64 / points.len() as f64,
+    })
+}
+
+/// Header for [`csv_rows`].
+pub const CSV_HEADER: &str = "time_s,band_level_dbfs\n";
+
+/// CSV rows, one per step of a [`band_activity`] track.
+pub fn csv_rows(points: &[BandPoint]) -> String {
+    let mut out = String::new();
+    for p in points {
+        out.push_str(&format!("{:.3},{:.2}\n", p.time_secs, p.level_db));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::night;
+
+    #[test]
+    fn test_tone_shows_in_its_band_only() {
+        let samples = night(2.0, (0.5, 1.0));
+        let inside = band_activity(&samples, 192_000, 40_000.0, 50_000.0, 19_200, 0.0);
+        assert_eq!(inside.len(), 20);
+        assert!((inside[7].level_db - -6.0).abs() < 1.5, "tone level {}", inside[7].level_db);
+        assert!(inside[2].level_db < -40.0);
+
+        let outside = band_activity(&samples, 192_000, 60_000.0, 70_000.0, 19_200, 0.0);
+        assert!(outside.iter().all(|p| p.level_db < -40.0));
+    }
+
+    #[test]
+    fn test_summary_finds_peak_and_activity() {
+        let points = band_activity(&night(2.0, (0.5, 1.0)), 192_000, 40_000.0, 50_000.0, 19_200, 10.0);
+        let s = summarize(&points).unwrap();
+        assert!((10.5..11.0).contains(&s.peak_time_secs), "peak at {}", s.peak_time_secs);
+        assert!((s.active_fraction - 0.25).abs() < 0.06, "active {}", s.active_fraction);
+        // Half a second of a half-scale tone
+        assert!((s.total_energy_db - -9.0).abs() < 1.5, "total {}", s.total_energy_db);
+        assert!(s.mean_db < s.peak_db);
+        assert_eq!(summarize(&[]), None);
+    }
+
+    #[test]
+    fn test_step_times_follow_step() {
+        // At 8 kHz the minimum step is one FFT frame, not 0.1 s
+        let step = step_samples(8_000, 8_000, 2000);
+        assert_eq!(step, ACTIVITY_FFT);
+        let points = band_activity(&vec![0.1; 8_000], 8_000, 1_000.0, 2_000.0, step, 0.0);
+        assert_eq!(points.len(), 8);
+        assert!((points[1].time_secs - 0.128).abs() < 1e-9);
+
+        // Long files get longer steps so the point count stays bounded
+        assert_eq!(step_samples(192_000 * 3600, 192_000, 2000), 345_600);
+        assert_eq!(step_samples(192_000, 192_000, 2000), 19_200);
+    }
+
+    #[test]
+    fn test_csv_rows() {
+        let points = [BandPoint { time_secs: 0.5, level_db: -42.25 }];
+        assert_eq!(csv_rows(&points), "0.500,-42.25\n");
+        assert_eq!(CSV_HEADER.split(',').count(), 2);
+    }
+}
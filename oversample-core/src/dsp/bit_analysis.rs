@@ -59,13 +59,133 @@ pub struct BitAnalysis {
     /// For integer files: number of MSBs (below sign bit) that are always 0 in positive
     /// samples and always 1 in negative samples (sign-extension headroom). 0 for floats.
     pub headroom_bits: u16,
-    /// Estimated noise floor in dBFS: minimum RMS of 512-sample windows above -80 dBFS.
-    /// -120.0 if no active windows found.
+    /// Estimated noise floor in dBFS from 512-sample windows above the silence
+    /// threshold, using `noise_floor_method`. -120.0 if no active windows found.
     pub noise_floor_db: f64,
+    pub noise_floor_method: NoiseFloorMethod,
     /// Value space coverage (only for 16-bit and 24-bit integer files)
     pub value_coverage: Option<ValueCoverage>,
 }
 
+/// How the noise floor is estimated from the RMS levels of 512-sample
+/// windows. Windows at or below the silence threshold (digital silence,
+/// muted gaps) are ignored by every method.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum NoiseFloorMethod {
+    /// Quietest single window. Best when the recording has pauses with only
+    /// background noise.
+    #[default]
+    WindowedRms,
+    /// 10th percentile of window levels; ignores a few unusually quiet
+    /// windows such as dropouts.
+    Percentile,
+    /// Median of the local minima of the smoothed window power (after
+    /// Martin's minimum statistics). Follows the noise under continuous
+    /// signal, for recordings without true silence.
+    MinimumStatistics,
+}
+
+impl NoiseFloorMethod {
+    pub const ALL: [NoiseFloorMethod; 3] = [
+        NoiseFloorMethod::WindowedRms,
+        NoiseFloorMethod::Percentile,
+        NoiseFloorMethod::MinimumStatistics,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NoiseFloorMethod::WindowedRms => "Quietest window",
+            NoiseFloorMethod::Percentile => "10th percentile",
+            NoiseFloorMethod::MinimumStatistics => "Minimum statistics",
+        }
+    }
+
+    /// Short key used in settings.
+    pub fn key(self) -> &'static str {
+        match self {
+            NoiseFloorMethod::WindowedRms => "min",
+            NoiseFloorMethod::Percentile => "percentile",
+            NoiseFloorMethod::MinimumStatistics => "minstats",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Option<NoiseFloorMethod> {
+        match key {
+            "min" => Some(NoiseFloorMethod::WindowedRms),
+            "percentile" => Some(NoiseFloorMethod::Percentile),
+            "minstats" => Some(NoiseFloorMethod::MinimumStatistics),
+            _ => None,
+        }
+    }
+}
+
+/// Noise floor estimation settings for `analyze_bits_with`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NoiseFloorParams {
+    pub method: NoiseFloorMethod,
+    /// Windows at or below this level (dBFS) count as silence and are skipped.
+    pub silence_threshold_db: f64,
+}
+
+impl Default for NoiseFloorParams {
+    fn default() -> Self {
+        Self { method: NoiseFloorMethod::WindowedRms, silence_threshold_db: -80.0 }
+    }
+}
+
+const NOISE_WINDOW: usize = 512;
+/// Fraction of active windows below the Percentile estimate.
+const NOISE_PERCENTILE: f64 = 0.1;
+/// Minimum statistics: recursive smoothing of window power, and the number
+/// of windows per block whose minimum is taken. A block (~0.27 s at 192 kHz)
+/// must outlast the signal bursts for the minimum to land on noise.
+const MIN_STATS_SMOOTHING: f64 = 0.5;
+const MIN_STATS_BLOCK: usize = 100;
+
+/// Estimated noise floor in dBFS, or -120.0 if no window is above the
+/// silence threshold.
+pub fn estimate_noise_floor_db(samples: &[f32], params: &NoiseFloorParams) -> f64 {
+    let ws = NOISE_WINDOW.min(samples.len());
+    if ws == 0 {
+        return -120.0;
+    }
+    let threshold = 10f64.powf(params.silence_threshold_db / 20.0);
+    let active: Vec<f64> = samples
+        .chunks_exact(ws)
+        .map(|w| (w.iter().map(|&s| (s as f64) * (s as f64)).sum::<f64>() / ws as f64).sqrt())
+        .filter(|&rms| rms > threshold)
+        .collect();
+    if active.is_empty() {
+        return -120.0;
+    }
+
+    let rms = match params.method {
+        NoiseFloorMethod::WindowedRms => active.iter().copied().fold(f64::INFINITY, f64::min),
+        NoiseFloorMethod::Percentile => {
+            let mut sorted = active;
+            sorted.sort_by(f64::total_cmp);
+            sorted[((sorted.len() - 1) as f64 * NOISE_PERCENTILE).round() as usize]
+        }
+        NoiseFloorMethod::MinimumStatistics => {
+            // Smoothing cuts the spread of the window powers, so the block
+            // minima sit within a fraction of a dB of the true noise power
+            // and no bias compensation is applied.
+            let mut smoothed = active[0] * active[0];
+            let powers: Vec<f64> = active.iter().map(|&r| {
+                smoothed = MIN_STATS_SMOOTHING * smoothed + (1.0 - MIN_STATS_SMOOTHING) * r * r;
+                smoothed
+            }).collect();
+            let mut minima: Vec<f64> = powers
+                .chunks(MIN_STATS_BLOCK)
+                .map(|b| b.iter().copied().fold(f64::INFINITY, f64::min))
+                .collect();
+            minima.sort_by(f64::total_cmp);
+            minima[minima.len() / 2].sqrt()
+        }
+    };
+    if rms > 0.0 { 20.0 * rms.log10() } else { -120.0 }
+}
+
 /// Bit label for display in the grid.
 pub fn bit_label(bit_index: usize, bits_per_sample: u16, is_float: bool) -> String {
     let bit_pos = bits_per_sample as usize - 1 - bit_index;
@@ -82,12 +202,25 @@ pub fn bit_label(bit_index: usize, bits_per_sample: u16, is_float: bool) -> Stri
     }
 }
 
-/// Analyze bit usage across all samples.
+/// Analyze bit usage across all samples, with the default noise floor
+/// estimate.
 pub fn analyze_bits(
     samples: &[f32],
     bits_per_sample: u16,
     is_float: bool,
     duration_secs: f64,
+) -> BitAnalysis {
+    analyze_bits_with(samples, bits_per_sample, is_float, duration_secs, &NoiseFloorParams::default())
+}
+
+/// Analyze bit usage across all samples, estimating the noise floor with
+/// the given settings.
+pub fn analyze_bits_with(
+    samples: &[f32],
+    bits_per_sample: u16,
+    is_float: bool,
+    duration_secs: f64,
+    noise: &NoiseFloorParams,
 ) -> BitAnalysis {
     let n_bits = bits_per_sample as usize;
     let total = samples.len();
@@ -111,6 +244,7 @@ pub fn analyze_bits(
             pair_counts: Vec::new(),
             headroom_bits: 0,
             noise_floor_db: -120.0,
+            noise_floor_method: noise.method,
             value_coverage: None,
         };
     }
@@ -170,33 +304,7 @@ pub fn analyze_bits(
 
     let summary = make_summary(bits_per_sample, is_float, effective_bits, total);
 
-    // Noise floor: minimum RMS of 512-sample non-overlapping windows above -80 dBFS
-    let noise_floor_db = {
-        const WINDOW: usize = 512;
-        const GAP_THRESHOLD: f64 = 1e-4; // -80 dBFS
-        let ws = WINDOW.min(samples.len());
-        let mut min_rms: Option<f64> = None;
-        let mut pos = 0;
-        while pos + ws <= samples.len() {
-            let sum_sq: f64 = samples[pos..pos + ws]
-                .iter()
-                .map(|&s| (s as f64) * (s as f64))
-                .sum();
-            let rms = (sum_sq / ws as f64).sqrt();
-            if rms > GAP_THRESHOLD {
-                match min_rms {
-                    None => min_rms = Some(rms),
-                    Some(prev) if rms < prev => min_rms = Some(rms),
-                    _ => {}
-                }
-            }
-            pos += ws;
-        }
-        match min_rms {
-            Some(rms) if rms > 0.0 => 20.0 * rms.log10(),
-            _ => -120.0,
-        }
-    };
+    let noise_floor_db = estimate_noise_floor_db(samples, noise);
 
     let value_coverage = if !unique_seen.is_empty() {
         let unique_count: u32 = unique_seen.iter().map(|&b| b.count_ones()).sum();
@@ -236,6 +344,7 @@ pub fn analyze_bits(
         pair_counts,
         headroom_bits,
         noise_floor_db,
+        noise_floor_method: noise.method,
         value_coverage,
     }
 }
@@ -583,3 +692,55 @@ pub fn is_expected_used(
         bit_pos >= (bits_per_sample - effective_bits) as usize
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::noise_rms as noise;
+
+    fn params(method: NoiseFloorMethod) -> NoiseFloorParams {
+        NoiseFloorParams { method, ..Default::default() }
+    }
+
+    #[test]
+    fn test_noise_floor_under_continuous_signal() {
+        // -40 dBFS noise with a loud tone 70% of the time, in 0.19 s bursts
+        let mut s = noise(512 * 2_000, 0.01, 1);
+        for (w, chunk) in s.chunks_mut(512).enumerate() {
+            if w % 100 < 70 {
+                for (i, v) in chunk.iter_mut().enumerate() {
+                    *v += 0.3 * (i as f32 * 0.7).sin();
+                }
+            }
+        }
+        for method in NoiseFloorMethod::ALL {
+            let db = estimate_noise_floor_db(&s, &params(method));
+            assert!((db + 40.0).abs() < 1.0, "{method:?}: {db}");
+        }
+    }
+
+    #[test]
+    fn test_dropout_only_fools_quietest_window() {
+        // One window of -70 dBFS in -40 dBFS noise
+        let mut s = noise(512 * 1_000, 0.01, 2);
+        for v in &mut s[512 * 500..512 * 501] {
+            *v *= 0.03;
+        }
+        let min = estimate_noise_floor_db(&s, &params(NoiseFloorMethod::WindowedRms));
+        assert!(min < -65.0, "quietest window {min}");
+        for method in [NoiseFloorMethod::Percentile, NoiseFloorMethod::MinimumStatistics] {
+            let db = estimate_noise_floor_db(&s, &params(method));
+            assert!((db + 40.0).abs() < 1.0, "{method:?}: {db}");
+        }
+    }
+
+    #[test]
+    fn test_silence_threshold() {
+        // -90 dBFS noise is "silence" at the default -80 dBFS threshold
+        let s = noise(512 * 100, 10f32.powf(-90.0 / 20.0), 3);
+        assert_eq!(estimate_noise_floor_db(&s, &NoiseFloorParams::default()), -120.0);
+        let lower = NoiseFloorParams { silence_threshold_db: -100.0, ..Default::default() };
+        assert!((estimate_noise_floor_db(&s, &lower) + 90.0).abs() < 1.0);
+        assert_eq!(analyze_bits_with(&s, 24, false, 1.0, &lower).noise_floor_method, NoiseFloorMethod::WindowedRms);
+    }
+}
@@ -0,0 +1,309 @@
+//! Standard call parameters for detected pulses.
+//!
+//! Each pulse is split into short overlapping FFT frames and the dominant
+//! frequency of each frame traces the call's frequency contour. Frames more
+//! than `CONTOUR_FLOOR_DB` below the loudest frame are dropped so the weak
+//! onset and tail don't drag the start/end frequencies into the noise.
+//!
+//! Characteristic frequency follows the usual definition: the frequency at
+//! the flattest part of the contour within the final 40% of the call. A
+//! median smoothing of the contour first keeps one jittery frame from
+//! becoming the start, end or flattest point.
+
+use realfft::RealFftPlanner;
+use std::cell::RefCell;
+use crate::dsp::fft::hann_window;
+use crate::dsp::pulse_detect::{self, DetectedPulse, PulseDetectionParams};
+use crate::dsp::smoothing::Smoothing;
+
+thread_local! {
+    static CALL_FFT_PLANNER: RefCell<RealFftPlanner<f32>> = RefCell::new(RealFftPlanner::new());
+}
+
+/// FFT frame length for the frequency contour.
+const CONTOUR_FFT: usize = 256;
+
+/// Hop between contour frames (75% overlap).
+const CONTOUR_HOP: usize = CONTOUR_FFT / 4;
+
+/// Contour frames this far below the loudest frame are ignored.
+const CONTOUR_FLOOR_DB: f32 = 20.0;
+
+/// Fraction of the contour (from the end) searched for the characteristic frequency.
+const CHAR_FREQ_TAIL: f64 = 0.4;
+
+/// Frequency parameters of a single call. All values in Hz.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallParameters {
+    pub start_freq_hz: f64,
+    pub end_freq_hz: f64,
+    /// Frequency of maximum energy.
+    pub peak_freq_hz: f64,
+    /// Frequency at the flattest part of the final 40% of the call.
+    pub char_freq_hz: f64,
+    /// Span of the frequency contour (highest minus lowest frequency).
+    pub bandwidth_hz: f64,
+}
+
+/// A detected pulse with its measured call parameters.
+#[derive(Clone, Debug)]
+pub struct CallMeasurement {
+    pub pulse: DetectedPulse,
+    /// None if the pulse had no measurable energy in the search band.
+    pub params: Option<CallParameters>,
+    /// Start-to-start interval from the previous pulse (ms). None for the first.
+    pub interval_ms: Option<f64>,
+}
+
+/// Measure the frequency contour of one call.
+///
+/// `freq_range` limits the search band in Hz; a high edge of 0 means Nyquist.
+/// `smoothing` is applied to the contour before anything is read from it.
+pub fn measure_call(
+    samples: &[f32],
+    sample_rate: u32,
+    freq_range: (f64, f64),
+    smoothing: Smoothing,
+) -> Option<CallParameters> {
+    let contour = frequency_contour(samples, sample_rate, freq_range);
+    let raw: Vec<f64> = contour.iter().map(|&(_, f)| f).collect();
+    let freqs = smoothing.apply(&raw);
+    let peak_freq_hz = contour.iter().zip(&freqs).max_by(|a, b| a.0.0.total_cmp(&b.0.0)).map(|(_, &f)| f)?;
+
+    let start_freq_hz = freqs[0];
+    let end_freq_hz = freqs[freqs.len() - 1];
+    let lo = freqs.iter().copied().fold(f64::INFINITY, f64::min);
+    let hi = freqs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    Some(CallParameters {
+        start_freq_hz,
+        end_freq_hz,
+        peak_freq_hz,
+        char_freq_hz: characteristic_frequency(&freqs),
+        bandwidth_hz: hi - lo,
+    })
+}
+
+/// Detect pulses in a block of mono samples and measure each one.
+pub fn measure_calls(samples: &[f32], sample_rate: u32, params: &PulseDetectionParams) -> Vec<CallMeasurement> {
+    let freq_range = (params.bandpass_low_hz, params.bandpass_high_hz);
+    let sr = sample_rate as f64;
+    let mut prev_start: Option<f64> = None;
+
+    pulse_detect::detect_pulses_in_samples(samples, sample_rate, params)
+        .into_iter()
+        .map(|mut pulse| {
+            let start = ((pulse.start_time * sr) as usize).min(samples.len());
+            let end = ((pulse.end_time * sr) as usize).clamp(start, samples.len());
+            let call = measure_call(&samples[start..end], sample_rate, freq_range, params.contour_smoothing);
+            if let Some(c) = &call {
+                pulse.peak_freq = c.peak_freq_hz;
+            }
+            let interval_ms = prev_start.map(|p| (pulse.start_time - p) * 1000.0);
+            prev_start = Some(pulse.start_time);
+            CallMeasurement { pulse, params: call, interval_ms }
+        })
+        .collect()
+}
+
+/// Column header for [`csv_rows`].
+pub const CSV_HEADER: &str = "file,pulse,start_s,end_s,duration_ms,start_khz,end_khz,peak_khz,\
+char_khz,bandwidth_khz,interval_ms,snr_db,note\n";
+
+/// CSV rows (one per call) for one file, tagged with `file_name`.
+///
+/// A file with no calls gets a single row carrying only a note, so every
+/// input file is accounted for in the output.
+pub fn csv_rows(file_name: &str, calls: &[CallMeasurement]) -> String {
+    let name = csv_field(file_name);
+    if calls.is_empty() {
+        return format!("{name},,,,,,,,,,,,no pulses detected\n");
+    }
+
+    let khz = |hz: f64| format!("{:.2}", hz / 1000.0);
+    let mut out = String::new();
+    for c in calls {
+        let p = &c.pulse;
+        let (start, end, peak, char_f, bw) = match &c.params {
+            Some(cp) => (
+                khz(cp.start_freq_hz),
+                khz(cp.end_freq_hz),
+                khz(cp.peak_freq_hz),
+                khz(cp.char_freq_hz),
+                khz(cp.bandwidth_hz),
+            ),
+            None => Default::default(),
+        };
+        let interval = c.interval_ms.map(|ms| format!("{ms:.2}")).unwrap_or_default();
+        let note = if c.params.is_none() { "no energy in band" } else { "" };
+        out.push_str(&format!(
+            "{name},{},{:.4},{:.4},{:.2},{start},{end},{peak},{char_f},{bw},{interval},{:.1},{note}\n",
+            p.index,
+            p.start_time,
+            p.end_time,
+            p.duration_ms(),
+            p.local_snr_db,
+        ));
+    }
+    out
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or newline. Shared
+/// with the other CSV exports.
+pub(crate) fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Per-frame (power, dominant frequency) pairs, trimmed to frames within
+/// `CONTOUR_FLOOR_DB` of the loudest. Short calls get a single zero-padded frame.
+fn frequency_contour(samples: &[f32], sample_rate: u32, freq_range: (f64, f64)) -> Vec<(f32, f64)> {
+    if samples.is_empty() || sample_rate == 0 {
+        return Vec::new();
+    }
+    let bin_hz = sample_rate as f64 / CONTOUR_FFT as f64;
+    let nyquist = sample_rate as f64 / 2.0;
+    let hi_hz = if freq_range.1 > 0.0 { freq_range.1.min(nyquist) } else { nyquist };
+    let lo_bin = (freq_range.0.max(0.0) / bin_hz).ceil() as usize;
+    let hi_bin = ((hi_hz / bin_hz).floor() as usize).min(CONTOUR_FFT / 2);
+    if lo_bin > hi_bin {
+        return Vec::new();
+    }
+
+    let fft = CALL_FFT_PLANNER.with(|p| p.borrow_mut().plan_fft_forward(CONTOUR_FFT));
+    let window = hann_window(CONTOUR_FFT);
+    let mut input = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+
+    let mut frames = Vec::new();
+    let mut pos = 0usize;
+    loop {
+        let end = (pos + CONTOUR_FFT).min(samples.len());
+        input.fill(0.0);
+        for (inp, (&s, &w)) in input.iter_mut().zip(samples[pos..end].iter().zip(window.iter())) {
+            *inp = s * w;
+        }
+        fft.process(&mut input, &mut spectrum).expect("FFT failed");
+
+        let power: Vec<f32> = spectrum.iter().map(|c| c.norm_sqr()).collect();
+        let (bin, &p) = power[lo_bin..=hi_bin]
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(i, p)| (i + lo_bin, p))
+            .unwrap_or((lo_bin, &0.0));
+        if p > 0.0 {
+            frames.push((p, interpolated_freq(&power, bin, bin_hz)));
+        }
+
+        if end >= samples.len() {
+            break;
+        }
+        pos += CONTOUR_HOP;
+    }
+
+    let max_power = frames.iter().map(|&(p, _)| p).fold(0.0f32, f32::max);
+    let floor = max_power * 10f32.powf(-CONTOUR_FLOOR_DB / 10.0);
+    // Keep the span from the first to the last strong frame so the contour stays contiguous.
+    let first = frames.iter().position(|&(p, _)| p >= floor);
+    let last = frames.iter().rposition(|&(p, _)| p >= floor);
+    match (first, last) {
+        (Some(a), Some(b)) => frames[a..=b].to_vec(),
+        _ => Vec::new(),
+    }
+}
+
+/// Parabolic interpolation of a spectral peak on log power.
+fn interpolated_freq(power: &[f32], bin: usize, bin_hz: f64) -> f64 {
+    if bin == 0 || bin + 1 >= power.len() {
+        return bin as f64 * bin_hz;
+    }
+    let db = |i: usize| 10.0 * (power[i] as f64).max(1e-20).log10();
+    let (a, b, c) = (db(bin - 1), db(bin), db(bin + 1));
+    let denom = a - 2.0 * b + c;
+    let offset = if denom.abs() > 1e-12 { (0.5 * (a - c) / denom).clamp(-0.5, 0.5) } else { 0.0 };
+    (bin as f64 + offset) * bin_hz
+}
+
+/// Frequency at the lowest absolute slope within the final `CHAR_FREQ_TAIL`
+/// of the contour (mean of the two frames either side of that step).
+fn characteristic_frequency(freqs: &[f64]) -> f64 {
+    if freqs.len() < 2 {
+        return freqs.first().copied().unwrap_or(0.0);
+    }
+    let tail_start = ((freqs.len() as f64 * (1.0 - CHAR_FREQ_TAIL)).floor() as usize).min(freqs.len() - 2);
+    freqs[tail_start..]
+        .windows(2)
+        .min_by(|a, b| (a[1] - a[0]).abs().total_cmp(&(b[1] - b[0]).abs()))
+        .map(|w| (w[0] + w[1]) / 2.0)
+        .unwrap_or(freqs[freqs.len() - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals;
+
+    /// Linear FM sweep from `f0` to `f1` over `secs`, followed by a constant-frequency tail at `f1`.
+    fn sweep_with_tail(sr: u32, f0: f64, f1: f64, secs: f64, tail_secs: f64) -> Vec<f32> {
+        let n = (sr as f64 * secs) as usize;
+        let n_tail = (sr as f64 * tail_secs) as usize;
+        let mut phase = 0.0f64;
+        (0..n + n_tail)
+            .map(|i| {
+                let f = if i < n { f0 + (f1 - f0) * i as f64 / n as f64 } else { f1 };
+                phase += 2.0 * std::f64::consts::PI * f / sr as f64;
+                (0.5 * phase.sin()) as f32
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_measure_fm_call() {
+        let sr = 384_000;
+        let call = sweep_with_tail(sr, 80_000.0, 40_000.0, 0.004, 0.002);
+        let p = measure_call(&call, sr, (0.0, 0.0), Smoothing::OFF).expect("call should be measurable");
+        let tol = 3000.0;
+        // The first frame is centred ~0.33 ms in, by which point the sweep has dropped ~3 kHz
+        assert!((p.start_freq_hz - 80_000.0).abs() < 2.0 * tol, "start {}", p.start_freq_hz);
+        assert!((p.end_freq_hz - 40_000.0).abs() < tol, "end {}", p.end_freq_hz);
+        // The flat tail defines the characteristic frequency
+        assert!((p.char_freq_hz - 40_000.0).abs() < tol, "char {}", p.char_freq_hz);
+        assert!((p.bandwidth_hz - 40_000.0).abs() < 2.0 * tol, "bw {}", p.bandwidth_hz);
+
+        // A median keeps the sweep's shape and can only narrow the span
+        let s = measure_call(&call, sr, (0.0, 0.0), Smoothing::median(3)).unwrap();
+        assert!((s.char_freq_hz - 40_000.0).abs() < tol, "smoothed char {}", s.char_freq_hz);
+        assert!(s.bandwidth_hz <= p.bandwidth_hz + 1e-6);
+    }
+
+    #[test]
+    fn test_measure_calls_intervals_and_csv() {
+        let sr = 192_000;
+        let mut samples = test_signals::noise(sr as usize / 5, 0.002, 1);
+        let call = sweep_with_tail(sr, 60_000.0, 45_000.0, 0.003, 0.0);
+        for &t in &[0.02, 0.12] {
+            test_signals::mix_at(&mut samples, &call, (t * sr as f64) as usize);
+        }
+
+        let calls = measure_calls(&samples, sr, &PulseDetectionParams::default());
+        assert_eq!(calls.len(), 2, "expected two calls, got {calls:?}");
+        assert!(calls[0].interval_ms.is_none());
+        let ipi = calls[1].interval_ms.unwrap();
+        assert!((ipi - 100.0).abs() < 2.0, "interval {ipi}");
+        assert!(calls[0].pulse.peak_freq > 40_000.0);
+
+        let csv = csv_rows("a,b.wav", &calls);
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.starts_with("\"a,b.wav\",1,"));
+        let cols = CSV_HEADER.trim_end().split(',').count();
+        assert!(csv.lines().all(|l| l.split(',').count() == cols + 1)); // +1 for the quoted comma
+
+        let empty = csv_rows("quiet.wav", &[]);
+        assert_eq!(empty.trim_end().split(',').count(), cols);
+        assert!(empty.contains("no pulses detected"));
+    }
+}
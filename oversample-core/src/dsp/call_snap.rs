@@ -0,0 +1,163 @@
+//! Snap a hand-drawn selection to the edges of the call inside it.
+//!
+//! Where a user lets go of the mouse is subjective, so durations and
+//! bandwidths read off hand-drawn boxes differ between people. Snapping
+//! instead finds the loudest point inside the box and grows outwards while
+//! the level stays within `threshold_db` of that peak (and clear of the
+//! noise floor), giving edges that depend only on the recording and the
+//! threshold.
+
+use crate::canvas::colors::magnitude_to_dbfs;
+use crate::dsp::fft::compute_stft_columns;
+
+/// Short window for fine time edges on bat-rate recordings.
+const SNAP_FFT: usize = 256;
+const SNAP_HOP: usize = 32;
+/// Quantile of column levels taken as the noise floor.
+const NOISE_QUANTILE: f64 = 0.2;
+/// Level above the noise floor a column or bin must reach to count as call.
+const NOISE_MARGIN_DB: f32 = 6.0;
+/// Longest selection snapped, in seconds. Calls last well under this; a
+/// longer box is a sequence or a survey window and is left as drawn.
+pub const MAX_SNAP_SECS: f64 = 1.0;
+
+/// Edges found by [`snap_to_call`], in seconds and Hz.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SnappedCall {
+    pub time_start: f64,
+    pub time_end: f64,
+    pub freq_low: f64,
+    pub freq_high: f64,
+}
+
+fn quantile(values: &[f32], q: f64) -> f32 {
+    let mut sorted = values.to_vec();
+    sorted.sort_by(f32::total_cmp);
+    sorted[((sorted.len() - 1) as f64 * q).round() as usize]
+}
+
+/// Grow `peak` outwards while `keep(i)` holds; returns the inclusive range.
+fn grow(peak: usize, len: usize, keep: impl Fn(usize) -> bool) -> (usize, usize) {
+    let mut lo = peak;
+    while lo > 0 && keep(lo - 1) {
+        lo -= 1;
+    }
+    let mut hi = peak;
+    while hi + 1 < len && keep(hi + 1) {
+        hi += 1;
+    }
+    (lo, hi)
+}
+
+/// Snap the selection `time_start..time_end` (and `band`, if given) to the
+/// call it contains.
+///
+/// `samples` are mono and start at `offset` seconds; they should extend a
+/// little past the selection so edges drawn too tightly can grow out to the
+/// call. The peak is looked for only inside the selection. Returns None if
+/// nothing there stands above the noise floor, or if the selection is longer
+/// than [`MAX_SNAP_SECS`].
+pub fn snap_to_call(
+    samples: &[f32],
+    sample_rate: u32,
+    offset: f64,
+    time_start: f64,
+    time_end: f64,
+    band: Option<(f64, f64)>,
+    threshold_db: f64,
+) -> Option<SnappedCall> {
+    if sample_rate == 0 || time_end - time_start > MAX_SNAP_SECS {
+        return None;
+    }
+    let sr = sample_rate as f64;
+    let columns = compute_stft_columns(samples, sample_rate, SNAP_FFT, SNAP_HOP, 0, usize::MAX);
+    if columns.len() < 3 {
+        return None;
+    }
+    let bins = SNAP_FFT / 2 + 1;
+    let freq_res = sr / SNAP_FFT as f64;
+    let (band_lo, band_hi) = match band {
+        Some((lo, hi)) => (((lo / freq_res).floor() as usize).min(bins - 1), ((hi / freq_res).ceil() as usize).min(bins - 1)),
+        None => (0, bins - 1),
+    };
+    let db = |col: usize, bin: usize| magnitude_to_dbfs(columns[col].magnitudes[bin], SNAP_FFT);
+    let col_time = |col: usize| offset + (col * SNAP_HOP + SNAP_FFT / 2) as f64 / sr;
+
+    // Time: band energy per column
+    let level: Vec<f32> = (0..columns.len())
+        .map(|c| {
+            let power: f32 = columns[c].magnitudes[band_lo..=band_hi].iter().map(|m| m * m).sum();
+            magnitude_to_dbfs(power.sqrt(), SNAP_FFT)
+        })
+        .collect();
+    let noise = quantile(&level, NOISE_QUANTILE);
+    let peak_col = (0..columns.len())
+        .filter(|&c| (time_start..=time_end).contains(&col_time(c)))
+        .max_by(|&a, &b| level[a].total_cmp(&level[b]))?;
+    let peak = level[peak_col];
+    if peak < noise + NOISE_MARGIN_DB {
+        return None;
+    }
+    let floor = (peak - threshold_db as f32).max(noise + NOISE_MARGIN_DB);
+    let (first, last) = grow(peak_col, columns.len(), |c| level[c] >= floor);
+
+    // Frequency: loudest level each bin reaches during the call
+    let bin_level: Vec<f32> = (0..bins)
+        .map(|b| (first..=last).map(|c| db(c, b)).fold(f32::MIN, f32::max))
+        .collect();
+    let bin_noise: Vec<f32> = (0..bins)
+        .map(|b| quantile(&(0..columns.len()).map(|c| db(c, b)).collect::<Vec<_>>(), NOISE_QUANTILE))
+        .collect();
+    let peak_bin = (band_lo..=band_hi).max_by(|&a, &b| bin_level[a].total_cmp(&bin_level[b]))?;
+    let bin_floor = bin_level[peak_bin] - threshold_db as f32;
+    let (lo_bin, hi_bin) = grow(peak_bin, bins, |b| {
+        bin_level[b] >= bin_floor.max(bin_noise[b] + NOISE_MARGIN_DB)
+    });
+
+    let half_hop = SNAP_HOP as f64 / 2.0 / sr;
+    Some(SnappedCall {
+        time_start: col_time(first) - half_hop,
+        time_end: col_time(last) + half_hop,
+        freq_low: ((lo_bin as f64 - 0.5) * freq_res).max(0.0),
+        freq_high: ((hi_bin as f64 + 0.5) * freq_res).min(sr / 2.0),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::night;
+
+    /// 45 kHz burst from 10 to 20 ms over faint noise, 192 kHz, 40 ms long.
+    fn burst() -> Vec<f32> {
+        night(0.040, (0.010, 0.020))
+    }
+
+    #[test]
+    fn test_snaps_to_burst_edges() {
+        // Drawn too tight in time and too wide in frequency
+        let snap = snap_to_call(&burst(), 192_000, 0.0, 0.012, 0.018, Some((30_000.0, 70_000.0)), 20.0).unwrap();
+        assert!((snap.time_start - 0.010).abs() < 0.001, "start {}", snap.time_start);
+        assert!((snap.time_end - 0.020).abs() < 0.001, "end {}", snap.time_end);
+        assert!(snap.freq_low < 45_000.0 && snap.freq_high > 45_000.0);
+        assert!(snap.freq_high - snap.freq_low < 15_000.0, "band {:?}", snap);
+    }
+
+    #[test]
+    fn test_offset_shifts_times() {
+        let snap = snap_to_call(&burst(), 192_000, 1.0, 1.012, 1.018, None, 20.0).unwrap();
+        assert!((snap.time_start - 1.010).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_noise_only_finds_nothing() {
+        // The first 10 ms are noise alone
+        let noise = &burst()[..1_900];
+        assert_eq!(snap_to_call(noise, 192_000, 0.0, 0.002, 0.008, None, 20.0), None);
+    }
+
+    #[test]
+    fn test_long_selection_left_alone() {
+        assert_eq!(snap_to_call(&burst(), 192_000, 0.0, 0.0, MAX_SNAP_SECS + 0.001, None, 20.0), None);
+    }
+}
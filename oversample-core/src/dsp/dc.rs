@@ -0,0 +1,80 @@
+//! DC offset detection and removal.
+//!
+//! A constant offset wastes headroom and can bias level and quality
+//! measurements. How much offset is worth flagging depends on the recorder,
+//! so the warning thresholds are parameters rather than constants.
+
+/// Default warning threshold: |DC| as a fraction of full scale (1%).
+pub const DEFAULT_DC_WARN_FULL_SCALE: f64 = 0.01;
+
+/// Default warning threshold: |DC| relative to the RMS level (5%).
+pub const DEFAULT_DC_WARN_RMS_RATIO: f64 = 0.05;
+
+/// Below this many samples the mean is too noisy to call an offset.
+pub const DC_WARN_MIN_SAMPLES: usize = 10_000;
+
+/// Mean sample value (the DC component).
+pub fn dc_offset(samples: &[f32]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    samples.iter().map(|&s| s as f64).sum::<f64>() / samples.len() as f64
+}
+
+/// Whether a measured offset is large enough to warn about.
+///
+/// Flags when |dc| exceeds `max_full_scale` of full scale OR exceeds
+/// `max_rms_ratio` of the RMS level. A threshold of zero or less disables
+/// that criterion.
+pub fn dc_offset_notable(
+    dc: f64,
+    rms: f64,
+    n_samples: usize,
+    max_full_scale: f64,
+    max_rms_ratio: f64,
+) -> bool {
+    if n_samples <= DC_WARN_MIN_SAMPLES {
+        return false;
+    }
+    let ratio = if rms > 0.0 { dc.abs() / rms } else { 0.0 };
+    (max_full_scale > 0.0 && dc.abs() > max_full_scale)
+        || (max_rms_ratio > 0.0 && ratio > max_rms_ratio)
+}
+
+/// Subtract `offset` from every sample in place.
+pub fn remove_dc(samples: &mut [f32], offset: f32) {
+    if offset == 0.0 {
+        return;
+    }
+    for s in samples.iter_mut() {
+        *s -= offset;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dc_offset_and_removal() {
+        let mut buf: Vec<f32> = (0..1000).map(|i| 0.02 + 0.1 * (i as f32 * 0.3).sin()).collect();
+        let dc = dc_offset(&buf);
+        assert!((dc - 0.02).abs() < 0.002, "dc = {dc}");
+        remove_dc(&mut buf, dc as f32);
+        assert!(dc_offset(&buf).abs() < 1e-6);
+        assert_eq!(dc_offset(&[]), 0.0);
+    }
+
+    #[test]
+    fn test_notable_thresholds() {
+        let n = DC_WARN_MIN_SAMPLES + 1;
+        // Default thresholds: 2% FS offset is flagged, 0.1% on a quiet file by RMS ratio
+        assert!(dc_offset_notable(0.02, 0.5, n, DEFAULT_DC_WARN_FULL_SCALE, DEFAULT_DC_WARN_RMS_RATIO));
+        assert!(dc_offset_notable(0.001, 0.01, n, DEFAULT_DC_WARN_FULL_SCALE, DEFAULT_DC_WARN_RMS_RATIO));
+        assert!(!dc_offset_notable(0.001, 0.5, n, DEFAULT_DC_WARN_FULL_SCALE, DEFAULT_DC_WARN_RMS_RATIO));
+        // Disabling the RMS criterion silences the quiet-file case
+        assert!(!dc_offset_notable(0.001, 0.01, n, DEFAULT_DC_WARN_FULL_SCALE, 0.0));
+        // Too few samples never warns
+        assert!(!dc_offset_notable(0.5, 0.5, 100, DEFAULT_DC_WARN_FULL_SCALE, DEFAULT_DC_WARN_RMS_RATIO));
+    }
+}
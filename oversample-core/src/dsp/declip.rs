@@ -0,0 +1,158 @@
+//! Clip restoration: redraw runs of samples stuck at full scale.
+//!
+//! Clipping flattens the tops of loud waves into runs of full-scale
+//! samples. Restoration replaces each run with a cubic that joins the
+//! unclipped samples either side with matching slopes, so the peak rounds
+//! off past full scale instead of stopping flat. It can't recover what the
+//! recorder never captured; it only makes clipped calls easier to read and
+//! less harsh to listen to. Restored samples can exceed ±1.0, so playback
+//! limits them afterwards.
+
+/// Level at or above which a sample counts as clipped (as in the wSNR check).
+pub const CLIP_LEVEL: f32 = 0.999;
+
+/// Shortest run treated as clipping; one full-scale sample may be a real peak.
+pub const MIN_CLIP_RUN: usize = 2;
+
+/// Restored peaks are capped at this multiple of full scale.
+const MAX_RESTORED: f32 = 4.0;
+
+/// Samples read either side of a block before restoring it, so a run that
+/// crosses the block edge is restored whole. Runs longer than this stay flat
+/// where they meet the edge.
+pub const CONTEXT: usize = 1024;
+
+/// Runs of at least `MIN_CLIP_RUN` same-signed samples at or above
+/// `clip_level`, as `(start, len)`. Runs touching either end of the buffer
+/// are left out: there is nothing on the far side to interpolate from.
+pub fn clipped_runs(samples: &[f32], clip_level: f32) -> Vec<(usize, usize)> {
+    let mut runs = Vec::new();
+    let mut i = 0;
+    while i < samples.len() {
+        if samples[i].abs() < clip_level {
+            i += 1;
+            continue;
+        }
+        let sign = samples[i].signum();
+        let start = i;
+        while i < samples.len() && samples[i].abs() >= clip_level && samples[i].signum() == sign {
+            i += 1;
+        }
+        if i - start >= MIN_CLIP_RUN && start > 0 && i < samples.len() {
+            runs.push((start, i - start));
+        }
+    }
+    runs
+}
+
+/// Number of samples [`declip`] would reconstruct.
+pub fn count_clipped(samples: &[f32], clip_level: f32) -> usize {
+    clipped_runs(samples, clip_level).iter().map(|&(_, len)| len).sum()
+}
+
+/// Replace each clipped run with a cubic Hermite curve between the last
+/// good sample before it and the first one after, using the slopes just
+/// outside the run. A restored sample never falls below the clipped value.
+/// Returns the number of samples reconstructed.
+pub fn declip(samples: &mut [f32], clip_level: f32) -> usize {
+    let runs = clipped_runs(samples, clip_level);
+    for &(start, len) in &runs {
+        let a = start - 1;
+        let b = start + len;
+        let p0 = samples[a];
+        let p1 = samples[b];
+        let m0 = if a > 0 { p0 - samples[a - 1] } else { 0.0 };
+        let m1 = if b + 1 < samples.len() { samples[b + 1] - p1 } else { 0.0 };
+        let span = (b - a) as f32;
+        for (j, s) in samples[start..b].iter_mut().enumerate() {
+            let u = (j + 1) as f32 / span;
+            let (u2, u3) = (u * u, u * u * u);
+            let h = (2.0 * u3 - 3.0 * u2 + 1.0) * p0
+                + (u3 - 2.0 * u2 + u) * span * m0
+                + (-2.0 * u3 + 3.0 * u2) * p1
+                + (u3 - u2) * span * m1;
+            let clipped = *s;
+            let sign = clipped.signum();
+            *s = sign * (sign * h).max(clipped.abs()).min(MAX_RESTORED);
+        }
+    }
+    runs.iter().map(|&(_, len)| len).sum()
+}
+
+/// Read `start..end` of a `total`-sample signal through `read(start, len)`
+/// with [`CONTEXT`] samples either side, restore it, and return `start..end`
+/// alone. Blocks read this way join up as if the whole signal was restored.
+pub fn read_declipped(
+    read: impl FnOnce(usize, usize) -> Vec<f32>,
+    start: usize,
+    end: usize,
+    total: usize,
+    clip_level: f32,
+) -> Vec<f32> {
+    let lo = start.saturating_sub(CONTEXT);
+    let hi = (end + CONTEXT).min(total).max(end);
+    let mut buf = read(lo, hi - lo);
+    declip(&mut buf, clip_level);
+    let body_end = (end - lo).min(buf.len());
+    buf.truncate(body_end);
+    buf.drain(..(start - lo).min(body_end));
+    buf
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::tone;
+
+    /// 1 kHz sine at 1.5× full scale, sampled at 48 kHz, and its clipped copy.
+    fn clipped_sine() -> (Vec<f32>, Vec<f32>) {
+        let clean = tone(1_000.0, 48_000, 960, 1.5);
+        let clipped = clean.iter().map(|s| s.clamp(-1.0, 1.0)).collect();
+        (clean, clipped)
+    }
+
+    fn max_error(a: &[f32], b: &[f32]) -> f32 {
+        a.iter().zip(b).map(|(x, y)| (x - y).abs()).fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_restores_clipped_peaks() {
+        let (clean, mut samples) = clipped_sine();
+        let before = max_error(&clean, &samples);
+        let restored = declip(&mut samples, CLIP_LEVEL);
+        assert!(restored > 0);
+        assert!(samples.iter().any(|s| s.abs() > 1.2), "peaks stayed flat");
+        let after = max_error(&clean[48..912], &samples[48..912]);
+        assert!(after < before / 2.0, "error {before} -> {after}");
+    }
+
+    #[test]
+    fn test_counts_match_runs() {
+        let (_, samples) = clipped_sine();
+        let mut copy = samples.clone();
+        assert_eq!(count_clipped(&samples, CLIP_LEVEL), declip(&mut copy, CLIP_LEVEL));
+    }
+
+    #[test]
+    fn test_blocks_match_whole_signal() {
+        let (_, clipped) = clipped_sine();
+        let mut whole = clipped.clone();
+        declip(&mut whole, CLIP_LEVEL);
+        // 1 kHz at 48 kHz puts a clipped run across each of these edges
+        let mut joined = Vec::new();
+        for edges in [0, 12, 490, 636, 960].windows(2) {
+            let read = |s: usize, n: usize| clipped[s..s + n].to_vec();
+            joined.extend(read_declipped(read, edges[0], edges[1], clipped.len(), CLIP_LEVEL));
+        }
+        assert_eq!(joined, whole);
+    }
+
+    #[test]
+    fn test_leaves_unclipped_audio_alone() {
+        let mut samples = vec![0.0, 0.5, 1.0, 0.5, 0.0, -0.5, -0.9, -0.5];
+        let original = samples.clone();
+        // A lone full-scale sample is not a run
+        assert_eq!(declip(&mut samples, CLIP_LEVEL), 0);
+        assert_eq!(samples, original);
+    }
+}
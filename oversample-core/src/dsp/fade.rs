@@ -0,0 +1,88 @@
+//! Short raised-cosine fades at clip boundaries.
+//!
+//! Cutting audio at arbitrary sample positions leaves a step discontinuity
+//! that is heard as a click. A few milliseconds of fade-in/out at each edge
+//! removes it without audibly changing the clip.
+
+/// Default edge fade length for exported clips and selection playback.
+pub const DEFAULT_EDGE_FADE_MS: f64 = 2.0;
+
+/// Upper limit for the user-configurable edge fade.
+pub const MAX_EDGE_FADE_MS: f64 = 50.0;
+
+/// Convert a fade duration to a sample count at `sample_rate`.
+pub fn fade_samples(fade_ms: f64, sample_rate: u32) -> usize {
+    if fade_ms <= 0.0 {
+        return 0;
+    }
+    (fade_ms * sample_rate as f64 / 1000.0).round() as usize
+}
+
+/// Apply a raised-cosine fade-in over the first `fade_in` samples and a
+/// fade-out over the last `fade_out` samples, in place.
+///
+/// Each fade is limited to half the buffer so the two never overlap.
+/// Zero lengths leave the samples bit-exact.
+pub fn apply_edge_fades(samples: &mut [f32], fade_in: usize, fade_out: usize) {
+    let half = samples.len() / 2;
+    let fade_in = fade_in.min(half);
+    let fade_out = fade_out.min(half);
+
+    for (i, s) in samples.iter_mut().take(fade_in).enumerate() {
+        *s *= raised_cosine(i, fade_in);
+    }
+    let len = samples.len();
+    for (i, s) in samples[len - fade_out..].iter_mut().rev().enumerate() {
+        *s *= raised_cosine(i, fade_out);
+    }
+}
+
+/// Gain for position `i` of an `n`-sample fade-in (0 at the edge, rising to ~1).
+#[inline]
+fn raised_cosine(i: usize, n: usize) -> f32 {
+    let t = i as f32 / n as f32;
+    0.5 * (1.0 - (std::f32::consts::PI * t).cos())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fade_samples() {
+        assert_eq!(fade_samples(2.0, 48000), 96);
+        assert_eq!(fade_samples(0.0, 48000), 0);
+        assert_eq!(fade_samples(-1.0, 48000), 0);
+    }
+
+    #[test]
+    fn test_edge_fades_shape() {
+        let mut buf = vec![1.0f32; 100];
+        apply_edge_fades(&mut buf, 10, 20);
+        // Edges start from silence
+        assert_eq!(buf[0], 0.0);
+        assert_eq!(buf[99], 0.0);
+        // Monotonic rise / fall inside the fades
+        assert!(buf[..10].windows(2).all(|w| w[1] >= w[0]));
+        assert!(buf[80..].windows(2).all(|w| w[1] <= w[0]));
+        // Middle untouched
+        assert!(buf[10..80].iter().all(|&s| s == 1.0));
+    }
+
+    #[test]
+    fn test_zero_fade_is_bit_exact() {
+        let orig: Vec<f32> = (0..64).map(|i| (i as f32 * 0.37).sin()).collect();
+        let mut buf = orig.clone();
+        apply_edge_fades(&mut buf, 0, 0);
+        assert_eq!(buf, orig);
+    }
+
+    #[test]
+    fn test_fades_clamped_to_short_buffer() {
+        let mut buf = vec![1.0f32; 6];
+        apply_edge_fades(&mut buf, 100, 100);
+        assert_eq!(buf[0], 0.0);
+        assert_eq!(buf[5], 0.0);
+        assert!(buf.iter().all(|s| s.is_finite()));
+    }
+}
@@ -1,6 +1,7 @@
 use crate::canvas::colors::magnitude_to_greyscale;
 use crate::types::PreRendered;
-use crate::types::{AudioData, PreviewImage, SpectrogramColumn, SpectrogramData};
+use crate::types::{AudioData, ComplexSpectrogram, PreviewImage, SpectrogramColumn, SpectrogramData};
+use realfft::num_complex::Complex;
 use realfft::RealFftPlanner;
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -13,7 +14,8 @@ thread_local! {
     static DHANN_CACHE: RefCell<HashMap<usize, Vec<f32>>> = RefCell::new(HashMap::new());
 }
 
-fn hann_window(size: usize) -> Vec<f32> {
+/// Symmetric Hann window of `size` samples, cached per size.
+pub(crate) fn hann_window(size: usize) -> Vec<f32> {
     HANN_CACHE.with(|cache| {
         cache
             .borrow_mut()
@@ -196,6 +198,30 @@ pub fn compute_reassigned_tile(
     }
 }
 
+/// Number of STFT frames for a signal of `total_samples`. A signal shorter
+/// than one window (but not empty) still gets a single zero-padded frame, so
+/// a clip of a few milliseconds has something to show.
+pub fn stft_frame_count(total_samples: usize, fft_size: usize, hop_size: usize) -> usize {
+    if total_samples == 0 || hop_size == 0 {
+        0
+    } else if total_samples < fft_size {
+        1
+    } else {
+        (total_samples - fft_size) / hop_size + 1
+    }
+}
+
+/// A copy of `samples` zero-padded to one `fft_size` window, if it's
+/// non-empty but shorter than that; None otherwise.
+fn pad_to_one_frame(samples: &[f32], fft_size: usize) -> Option<Vec<f32>> {
+    if samples.is_empty() || samples.len() >= fft_size {
+        return None;
+    }
+    let mut padded = samples.to_vec();
+    padded.resize(fft_size, 0.0);
+    Some(padded)
+}
+
 /// Compute a spectrogram from audio data using a Short-Time Fourier Transform (STFT).
 ///
 /// Uses a Hann window for spectral leakage reduction.
@@ -204,15 +230,51 @@ pub fn compute_spectrogram(
     fft_size: usize,
     hop_size: usize,
 ) -> SpectrogramData {
+    compute_spectrogram_inner(audio, fft_size, hop_size, false).0
+}
+
+/// Like [`compute_spectrogram`], but also retains the complex STFT coefficients.
+///
+/// Roughly triples the memory of the magnitude-only result, so only use this
+/// where phase is actually needed (resynthesis, reassignment, coherence).
+pub fn compute_spectrogram_complex(
+    audio: &AudioData,
+    fft_size: usize,
+    hop_size: usize,
+) -> (SpectrogramData, ComplexSpectrogram) {
+    let (data, frames) = compute_spectrogram_inner(audio, fft_size, hop_size, true);
+    let complex = ComplexSpectrogram {
+        frames: Arc::new(frames),
+        fft_size,
+        hop_size,
+        sample_rate: audio.sample_rate,
+    };
+    (data, complex)
+}
+
+fn compute_spectrogram_inner(
+    audio: &AudioData,
+    fft_size: usize,
+    hop_size: usize,
+    keep_complex: bool,
+) -> (SpectrogramData, Vec<Vec<Complex<f32>>>) {
     let fft = FFT_PLANNER.with(|p| p.borrow_mut().plan_fft_forward(fft_size));
 
     // Use the in-memory mono-mixed samples directly — allocating a fresh Vec
     // via read_region would duplicate up to a gigabyte for long audiobooks
     // that already fit in audio.samples (and OOM the WASM heap). For streaming
     // sources audio.samples is the head (~30s), which is all we need anyway.
-    let samples: &[f32] = &audio.samples;
+    let padded;
+    let samples: &[f32] = match pad_to_one_frame(&audio.samples, fft_size) {
+        Some(p) => {
+            padded = p;
+            &padded
+        }
+        None => &audio.samples,
+    };
 
     let mut columns = Vec::new();
+    let mut complex_frames = Vec::new();
 
     let window = hann_window(fft_size);
 
@@ -233,6 +295,9 @@ pub fn compute_spectrogram(
         fft.process(&mut input, &mut spectrum).expect("FFT failed");
 
         let magnitudes: Vec<f32> = spectrum.iter().map(|c| c.norm()).collect();
+        if keep_complex {
+            complex_frames.push(spectrum.clone());
+        }
 
         let time_offset = pos as f64 / audio.sample_rate as f64;
         columns.push(SpectrogramColumn {
@@ -248,14 +313,52 @@ pub fn compute_spectrogram(
     let max_freq = audio.sample_rate as f64 / 2.0;
 
     let total_columns = columns.len();
-    SpectrogramData {
+    let data = SpectrogramData {
         columns: Arc::new(columns),
         total_columns,
         freq_resolution,
         time_resolution,
         max_freq,
         sample_rate: audio.sample_rate,
+    };
+    (data, complex_frames)
+}
+
+/// Compute complex (Hann-windowed) STFT frames directly from a sample slice.
+///
+/// Same framing as [`compute_stft_columns`] starting at column 0; used by
+/// phase-based analyses that only need a region rather than a whole file.
+pub fn compute_complex_frames(
+    samples: &[f32],
+    fft_size: usize,
+    hop_size: usize,
+) -> Vec<Vec<Complex<f32>>> {
+    let padded;
+    let samples = match pad_to_one_frame(samples, fft_size) {
+        Some(p) => {
+            padded = p;
+            &padded[..]
+        }
+        None => samples,
+    };
+    let fft = FFT_PLANNER.with(|p| p.borrow_mut().plan_fft_forward(fft_size));
+    let window = hann_window(fft_size);
+    let mut input = fft.make_input_vec();
+    let mut spectrum = fft.make_output_vec();
+    let mut frames = Vec::new();
+    let mut pos = 0;
+    while pos + fft_size <= samples.len() {
+        for (inp, (&s, &w)) in input
+            .iter_mut()
+            .zip(samples[pos..pos + fft_size].iter().zip(window.iter()))
+        {
+            *inp = s * w;
+        }
+        fft.process(&mut input, &mut spectrum).expect("FFT failed");
+        frames.push(spectrum.clone());
+        pos += hop_size;
     }
+    frames
 }
 
 /// Compute a partial spectrogram — only columns `col_start .. col_start + col_count`.
@@ -286,9 +389,19 @@ pub fn compute_stft_columns(
     col_start: usize,
     col_count: usize,
 ) -> Vec<SpectrogramColumn> {
-    if samples.len() < fft_size || col_count == 0 {
+    if col_count == 0 {
         return vec![];
     }
+    let padded;
+    let samples = match pad_to_one_frame(samples, fft_size) {
+        Some(p) if col_start == 0 => {
+            padded = p;
+            &padded[..]
+        }
+        Some(_) => return vec![],
+        None if samples.len() < fft_size => return vec![],
+        None => samples,
+    };
 
     let fft = FFT_PLANNER.with(|p| p.borrow_mut().plan_fft_forward(fft_size));
     let window = hann_window(fft_size);
@@ -319,7 +432,8 @@ pub fn compute_stft_columns(
 }
 
 /// Compute a fast low-resolution preview spectrogram as an RGBA pixel buffer.
-/// Uses FFT=256 with a dynamic hop to produce roughly `target_width` columns.
+/// Uses the smallest FFT (256 to 1024) with at least `target_height` bins and
+/// a dynamic hop to produce roughly `target_width` columns.
 pub fn compute_preview(audio: &AudioData, target_width: u32, target_height: u32) -> PreviewImage {
     // For streaming files, only the head samples are in memory — don't try to
     // read the entire multi-GB file. audio.samples contains the loaded portion.
@@ -337,8 +451,12 @@ pub fn compute_preview(audio: &AudioData, target_width: u32, target_height: u32)
         };
     }
 
-    let fft_size = 256;
-    let hop = (total / target_width as usize).max(fft_size);
+    let fft_size = (target_height as usize * 2)
+        .next_power_of_two()
+        .clamp(256, 1024)
+        .min(1 << total.ilog2());
+    // Frames may overlap for short files, but never more than 75%
+    let hop = (total / target_width.max(1) as usize).max(fft_size / 4);
     let spec = compute_spectrogram(audio, fft_size, hop);
 
     if spec.columns.is_empty() {
@@ -444,12 +562,13 @@ mod tests {
         let source = Arc::new(InMemorySource {
             samples: samples.clone(),
             raw_samples: None,
+            exact: None,
             sample_rate,
             channels: 1,
         });
         AudioData {
             duration_secs: samples.len() as f64 / sample_rate as f64,
-            samples,
+            samples: samples.into(),
             source,
             sample_rate,
             channels: 1,
@@ -500,4 +619,95 @@ mod tests {
             "Peak at {peak_freq} Hz, expected ~{freq} Hz"
         );
     }
+
+    #[test]
+    fn test_magnitude_at_full_scale_sine_is_near_0_dbfs() {
+        use crate::canvas::colors::magnitude_to_dbfs;
+        let sample_rate = 48000u32;
+        // Bin-centred tone so there's no scalloping loss
+        let freq = 48.0 * sample_rate as f64 / 1024.0;
+        let samples: Vec<f32> = (0..8192)
+            .map(|i| (2.0 * std::f64::consts::PI * freq * i as f64 / sample_rate as f64).sin() as f32)
+            .collect();
+        let result = compute_spectrogram(&AudioData::from_mono(samples, sample_rate), 1024, 512);
+        assert_eq!(result.fft_size(), 1024);
+
+        let mag = result.magnitude_at(0.05, freq).unwrap();
+        let dbfs = magnitude_to_dbfs(mag, result.fft_size());
+        assert!(dbfs.abs() < 0.5, "full-scale sine read {dbfs} dBFS");
+
+        // Half amplitude is ~6 dB down; out-of-range times have no reading
+        let half = magnitude_to_dbfs(mag / 2.0, 1024);
+        assert!((half + 6.02).abs() < 0.1);
+        assert!(result.magnitude_at(10.0, freq).is_none());
+    }
+
+    #[test]
+    fn test_complex_spectrogram_matches_magnitudes() {
+        let sample_rate = 48000u32;
+        let samples: Vec<f32> = (0..8192)
+            .map(|i| (2.0 * std::f32::consts::PI * 3000.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+        let audio = AudioData::from_mono(samples, sample_rate);
+
+        let (data, complex) = compute_spectrogram_complex(&audio, 512, 256);
+        assert_eq!(complex.frames.len(), data.columns.len());
+        for (frame, col) in complex.frames.iter().zip(data.columns.iter()) {
+            assert_eq!(frame.len(), col.magnitudes.len());
+            for (c, &m) in frame.iter().zip(col.magnitudes.iter()) {
+                assert!((c.norm() - m).abs() < 1e-4);
+            }
+        }
+        assert!(complex.phase(0, 32).is_some());
+        assert!(complex.phase(0, 10_000).is_none());
+
+        // The magnitude-only path must not change.
+        let plain = compute_spectrogram(&audio, 512, 256);
+        assert_eq!(plain.columns.len(), data.columns.len());
+    }
+
+    #[test]
+    fn test_preview_follows_target_size() {
+        let sample_rate = 192_000u32;
+        let samples: Vec<f32> = (0..sample_rate as usize * 2)
+            .map(|i| (2.0 * std::f32::consts::PI * 40_000.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+        let audio = AudioData::from_mono(samples, sample_rate);
+
+        // Width is approximate (whole hops), height exact
+        let small = compute_preview(&audio, 256, 128);
+        assert!((240..=256).contains(&small.width), "{}", small.width);
+        assert_eq!(small.height, 128);
+        // Taller than a 256-point FFT's 129 bins
+        let large = compute_preview(&audio, 640, 320);
+        assert!((600..=640).contains(&large.width), "{}", large.width);
+        assert_eq!(large.height, 320);
+        assert_eq!(large.pixels.len(), (large.width * large.height * 4) as usize);
+
+        // Shorter than the chosen FFT still gives a preview
+        let short = AudioData::from_mono(vec![0.1; 600], sample_rate);
+        assert!(compute_preview(&short, 256, 256).width >= 1);
+    }
+
+    #[test]
+    fn test_clip_shorter_than_window_gives_one_column() {
+        let sample_rate = 192_000u32;
+        // 2 ms of a 40 kHz tone, well under a 2048-point window
+        let samples: Vec<f32> = (0..384)
+            .map(|i| (2.0 * std::f32::consts::PI * 40_000.0 * i as f32 / sample_rate as f32).sin())
+            .collect();
+        assert_eq!(stft_frame_count(samples.len(), 2048, 512), 1);
+        assert_eq!(stft_frame_count(0, 2048, 512), 0);
+        assert_eq!(stft_frame_count(4096, 2048, 512), 5);
+
+        let audio = AudioData::from_mono(samples.clone(), sample_rate);
+        let spec = compute_spectrogram(&audio, 2048, 512);
+        assert_eq!(spec.columns.len(), 1);
+        assert!(spec.columns[0].magnitudes.iter().any(|&m| m > 0.0));
+
+        let cols = compute_stft_columns(&samples, sample_rate, 2048, 512, 0, 4);
+        assert_eq!(cols.len(), 1);
+        assert!(compute_stft_columns(&samples, sample_rate, 2048, 512, 1, 4).is_empty());
+        assert!(compute_stft_columns(&[], sample_rate, 2048, 512, 0, 4).is_empty());
+    }
 }
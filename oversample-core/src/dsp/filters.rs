@@ -38,7 +38,31 @@ fn hann_window(size: usize) -> Vec<f32> {
     })
 }
 
-/// Apply a multi-band EQ filter in the frequency domain using overlap-add FFT processing.
+/// FFT size for the spectral EQ's block convolution.
+const EQ_FFT_SIZE: usize = 4096;
+/// Length of the spectral EQ's FIR kernel (odd, so it has an integer delay).
+/// Input blocks are `EQ_FFT_SIZE - EQ_KERNEL_LEN + 1` samples, so each block's
+/// linear convolution fits in one FFT frame without wrapping.
+const EQ_KERNEL_LEN: usize = EQ_FFT_SIZE / 2 + 1;
+/// Samples of context the spectral EQ needs on each side of a region for the
+/// output there to match filtering the whole file (half the kernel).
+pub const EQ_FILTER_REACH: usize = EQ_KERNEL_LEN / 2;
+
+/// Where to stop reading for a chunk that ends at `end` of a `total`-sample
+/// source: [`EQ_FILTER_REACH`] further on, where the source has it. Chunked
+/// readers drop the extra samples after filtering; without them each chunk's
+/// tail is filtered blind to what follows and the joins are audible.
+pub fn eq_lookahead_end(end: usize, total: usize) -> usize {
+    (end + EQ_FILTER_REACH).min(total.max(end))
+}
+
+/// Apply a multi-band EQ filter in the frequency domain.
+///
+/// The per-band gains are turned into a Blackman-windowed, zero-phase FIR
+/// kernel, which is applied by overlap-add FFT convolution. Because this is a
+/// true linear convolution there are no block seams: filtering a chunk with at
+/// least [`EQ_FILTER_REACH`] samples of context on each side gives the same
+/// samples as filtering the whole file.
 ///
 /// Bands are defined relative to the "selected" frequency range [freq_low, freq_high]:
 /// - Below: 0 to freq_low
@@ -62,12 +86,10 @@ pub fn apply_eq_filter(
         return Vec::new();
     }
 
-    let fft_size = 4096;
-    let hop_size = fft_size / 2;
+    let fft_size = EQ_FFT_SIZE;
+    let block_len = fft_size - EQ_KERNEL_LEN + 1;
     let len = samples.len();
 
-    let window = hann_window(fft_size);
-
     // Build per-bin gain table
     let num_bins = fft_size / 2 + 1;
     let freq_per_bin = sample_rate as f64 / fft_size as f64;
@@ -101,60 +123,71 @@ pub fn apply_eq_filter(
         (p.plan_fft_forward(fft_size), p.plan_fft_inverse(fft_size))
     });
 
-    // Overlap-add output buffer
-    let mut output = vec![0.0f32; len];
-    let mut window_sum = vec![0.0f32; len];
+    let kernel_spectrum = eq_kernel_spectrum(&gains, fft_size, &fft_fwd, &fft_inv);
+
+    // Overlap-add: each block's full convolution (block_len + kernel - 1
+    // samples) is accumulated; the kernel's delay is removed at the end.
+    let delay = EQ_FILTER_REACH;
+    let mut acc = vec![0.0f32; len + EQ_KERNEL_LEN];
 
-    // Pre-allocate FFT buffers once and reuse across frames
+    // Pre-allocate FFT buffers once and reuse across blocks
     let mut frame = fft_fwd.make_input_vec();
     let mut spectrum = fft_fwd.make_output_vec();
     let mut time_out = fft_inv.make_output_vec();
+    let norm = 1.0 / fft_size as f32;
 
     let mut pos = 0;
     while pos < len {
-        // Zero and fill frame in-place (no allocation)
+        let n = block_len.min(len - pos);
         frame.fill(0.0);
-        for (i, &w) in window.iter().enumerate() {
-            if pos + i < len {
-                frame[i] = samples[pos + i] * w;
-            }
-        }
+        frame[..n].copy_from_slice(&samples[pos..pos + n]);
 
-        // Forward FFT
         fft_fwd.process(&mut frame, &mut spectrum).expect("FFT forward failed");
-
-        // Apply per-bin gains
-        for (bin, gain) in gains.iter().enumerate() {
-            if bin < spectrum.len() {
-                spectrum[bin] *= *gain;
-            }
+        for (s, k) in spectrum.iter_mut().zip(kernel_spectrum.iter()) {
+            *s *= *k;
         }
-
-        // Inverse FFT
         fft_inv.process(&mut spectrum, &mut time_out).expect("FFT inverse failed");
 
-        // Normalize (realfft inverse doesn't normalize)
-        let norm = 1.0 / fft_size as f32;
-
-        // Overlap-add with window
-        for i in 0..fft_size {
-            if pos + i < len {
-                output[pos + i] += time_out[i] * norm * window[i];
-                window_sum[pos + i] += window[i] * window[i];
-            }
+        let out_len = (n + EQ_KERNEL_LEN - 1).min(acc.len() - pos);
+        for (a, &t) in acc[pos..pos + out_len].iter_mut().zip(time_out.iter()) {
+            *a += t * norm;
         }
 
-        pos += hop_size;
+        pos += block_len;
     }
 
-    // Normalize by window sum to avoid amplitude changes
-    for i in 0..len {
-        if window_sum[i] > 1e-6 {
-            output[i] /= window_sum[i];
-        }
+    acc[delay..delay + len].to_vec()
+}
+
+/// Spectrum of the EQ's FIR kernel: the zero-phase impulse response of
+/// `gains`, centred in `EQ_KERNEL_LEN` taps and Blackman-windowed so the
+/// brick-wall band edges don't ring across block boundaries.
+fn eq_kernel_spectrum(
+    gains: &[f32],
+    fft_size: usize,
+    fft_fwd: &std::sync::Arc<dyn realfft::RealToComplex<f32>>,
+    fft_inv: &std::sync::Arc<dyn realfft::ComplexToReal<f32>>,
+) -> Vec<realfft::num_complex::Complex<f32>> {
+    let mut spectrum = fft_fwd.make_output_vec();
+    for (s, &g) in spectrum.iter_mut().zip(gains.iter()) {
+        *s = realfft::num_complex::Complex::new(g, 0.0);
+    }
+    let mut impulse = fft_inv.make_output_vec();
+    fft_inv.process(&mut spectrum, &mut impulse).expect("FFT inverse failed");
+
+    let half = EQ_KERNEL_LEN / 2;
+    let m = (EQ_KERNEL_LEN - 1) as f32;
+    let mut kernel = fft_fwd.make_input_vec();
+    for (j, k) in kernel.iter_mut().take(EQ_KERNEL_LEN).enumerate() {
+        let w = 0.42 - 0.5 * (2.0 * std::f32::consts::PI * j as f32 / m).cos()
+            + 0.08 * (4.0 * std::f32::consts::PI * j as f32 / m).cos();
+        let idx = (j + fft_size - half) % fft_size;
+        *k = impulse[idx] / fft_size as f32 * w;
     }
 
-    output
+    let mut kernel_spectrum = fft_fwd.make_output_vec();
+    fft_fwd.process(&mut kernel, &mut kernel_spectrum).expect("FFT forward failed");
+    kernel_spectrum
 }
 
 /// Fast IIR-based multi-band EQ using cascaded lowpass band-splitting.
@@ -409,6 +442,7 @@ pub fn decimated_rate(source_rate: u32, target_rate: u32) -> u32 {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_signals::{mix_at, tone};
 
     #[test]
     fn harmonics_band_uses_doubled_low_when_it_exceeds_focus_high() {
@@ -485,4 +519,62 @@ mod tests {
         let result = lowpass_filter(&[], 1000.0, 44100);
         assert!(result.is_empty());
     }
+
+    fn two_tones(sample_rate: u32, len: usize) -> Vec<f32> {
+        let mut s = tone(10_000.0, sample_rate, len, 0.4);
+        mix_at(&mut s, &tone(40_000.0, sample_rate, len, 0.4), 0);
+        s
+    }
+
+    #[test]
+    fn test_spectral_eq_unity_is_identity() {
+        let input = two_tones(192_000, 10_000);
+        let out = apply_eq_filter(&input, 192_000, 20_000.0, 60_000.0, 0.0, 0.0, 0.0, 0.0, 3);
+        assert_eq!(out.len(), input.len());
+        let max_err = input.iter().zip(&out).map(|(a, b)| (a - b).abs()).fold(0.0f32, f32::max);
+        assert!(max_err < 1e-3, "unity EQ changed the signal by {max_err}");
+    }
+
+    #[test]
+    fn test_spectral_eq_cuts_below_band() {
+        let sr = 192_000;
+        let input = two_tones(sr, 19_200);
+        // Cut the 10 kHz tone, keep the 40 kHz one
+        let out = apply_eq_filter(&input, sr, 20_000.0, 60_000.0, -60.0, 0.0, 0.0, 0.0, 3);
+        let keep = tone(40_000.0, sr, input.len(), 0.4);
+        let mid = 4096..input.len() - 4096;
+        let max_err = out[mid.clone()].iter().zip(&keep[mid]).map(|(a, b)| (a - b).abs()).fold(0.0f32, f32::max);
+        assert!(max_err < 0.01, "residual of cut tone {max_err}");
+    }
+
+    #[test]
+    fn test_spectral_eq_chunks_have_no_seams() {
+        let sr = 192_000;
+        let input = two_tones(sr, 30_000);
+        let eq = |s: &[f32]| apply_eq_filter(s, sr, 20_000.0, 30_000.0, -30.0, 0.0, -12.0, -40.0, 4);
+        let whole = eq(&input);
+
+        // Filter in chunks the way streaming playback and export read them:
+        // a fixed warm-up before each chunk, and whatever lookahead `read_end`
+        // gives after it
+        let chunked = |read_end: &dyn Fn(usize) -> usize| {
+            let (chunk, warmup) = (7_000, 4_096);
+            let mut stitched = Vec::with_capacity(input.len());
+            let mut pos = 0;
+            while pos < input.len() {
+                let end = (pos + chunk).min(input.len());
+                let ctx_start = pos.saturating_sub(warmup);
+                let out = eq(&input[ctx_start..read_end(end)]);
+                stitched.extend_from_slice(&out[pos - ctx_start..end - ctx_start]);
+                pos = end;
+            }
+            whole.iter().zip(&stitched).map(|(a, b)| (a - b).abs()).fold(0.0f32, f32::max)
+        };
+
+        let max_err = chunked(&|end| eq_lookahead_end(end, input.len()));
+        assert!(max_err < 1e-4, "chunked output differs from whole-file output by {max_err}");
+        // Warm-up alone leaves each chunk's tail without its future samples
+        let blind = chunked(&|end| end);
+        assert!(blind > 1e-2, "seam went undetected: {blind}");
+    }
 }
@@ -1,6 +1,7 @@
 use realfft::num_complex::Complex;
 use realfft::RealFftPlanner;
 use crate::audio::source::ChannelView;
+use crate::dsp::smoothing::Smoothing;
 use crate::types::{AudioData, SpectrogramData, SpectrogramColumn};
 use std::cell::RefCell;
 use std::collections::HashMap;
@@ -8,6 +9,113 @@ use std::f32::consts::PI;
 
 type Complex32 = Complex<f32>;
 
+/// Default number of harmonics extracted for the decay profile.
+pub const DEFAULT_HARMONIC_COUNT: usize = 8;
+
+/// Upper limit for the user-selectable harmonic count.
+pub const MAX_HARMONIC_COUNT: usize = 12;
+
+/// How the power-law decay exponent is fitted to the harmonic amplitudes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum DecayFitMethod {
+    /// Least squares on log amplitude against log harmonic number.
+    #[default]
+    LeastSquares,
+    /// Median of the exponents each harmonic implies on its own, so one
+    /// aberrant harmonic can't drag the fit.
+    Robust,
+}
+
+impl DecayFitMethod {
+    pub const ALL: [DecayFitMethod; 2] = [Self::LeastSquares, Self::Robust];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::LeastSquares => "Least squares",
+            Self::Robust => "Robust (median)",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::LeastSquares => "lsq",
+            Self::Robust => "robust",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "robust" => Self::Robust,
+            _ => Self::LeastSquares,
+        }
+    }
+}
+
+/// Which harmonics the decay exponent is fitted over, and how. Harmonic
+/// numbers count the fundamental as 1; the fundamental is the reference
+/// every amplitude is normalised to, so the fit starts at 2 at the earliest.
+/// Harmonics outside the range are still extracted and charted, but don't
+/// affect the exponent or the monotonicity check.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DecayFit {
+    pub first_harmonic: usize,
+    pub last_harmonic: usize,
+    pub method: DecayFitMethod,
+}
+
+impl DecayFit {
+    /// Every extracted harmonic, least-squares fit.
+    pub const ALL_HARMONICS: DecayFit = DecayFit {
+        first_harmonic: 2,
+        last_harmonic: MAX_HARMONIC_COUNT,
+        method: DecayFitMethod::LeastSquares,
+    };
+
+    /// Whether harmonic index `i` (0-based, 0 = fundamental) is fitted.
+    pub fn includes(&self, i: usize) -> bool {
+        (self.first_harmonic.max(2)..=self.last_harmonic).contains(&(i + 1))
+    }
+}
+
+impl Default for DecayFit {
+    fn default() -> Self {
+        Self::ALL_HARMONICS
+    }
+}
+
+/// Sole entry in `artifact_indicators` when nothing suspicious was found.
+pub const NO_ARTIFACTS: &str = "No significant artifacts detected";
+
+/// Overall assessment of `phase_coherence_mean`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoherenceVerdict {
+    /// ≥ 65%: consistent with an unprocessed recording.
+    Natural,
+    Moderate,
+    /// < 45%: heavy phase drift, typical of pitch shifting or resynthesis.
+    Processed,
+}
+
+impl CoherenceVerdict {
+    pub fn from_mean(mean: f32) -> Self {
+        if mean >= 0.65 {
+            Self::Natural
+        } else if mean >= 0.45 {
+            Self::Moderate
+        } else {
+            Self::Processed
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Natural => "High (natural)",
+            Self::Moderate => "Moderate",
+            Self::Processed => "Low (processed)",
+        }
+    }
+}
+
 thread_local! {
     static HARM_FFT_PLANNER: RefCell<RealFftPlanner<f32>> = RefCell::new(RealFftPlanner::new());
     static HARM_HANN_CACHE: RefCell<HashMap<usize, Vec<f32>>> = RefCell::new(HashMap::new());
@@ -51,9 +159,18 @@ pub struct HarmonicsAnalysis {
     pub decay_is_monotonic: bool,
     /// Harmonic indices (0-based) where amplitude anomalously exceeds the prior harmonic.
     pub decay_anomaly_indices: Vec<usize>,
+    /// Harmonic indices (0-based) the decay exponent was fitted over.
+    pub decay_fit_indices: Vec<usize>,
+    /// Number of harmonics that were asked for.
+    pub harmonics_requested: usize,
+    /// True if fewer than `harmonics_requested` were extracted because the
+    /// higher ones would lie above Nyquist.
+    pub harmonics_nyquist_limited: bool,
 
     // --- Spectral Flux ---
-    /// Half-wave-rectified onset flux, one value per spectrogram frame transition.
+    /// Half-wave-rectified onset flux, one value per spectrogram frame
+    /// transition, after the requested smoothing. The flux statistics below
+    /// are derived from this smoothed track.
     pub flux_per_frame: Vec<f32>,
     pub flux_mean: f32,
     pub flux_peak: f32,
@@ -71,48 +188,188 @@ impl PartialEq for HarmonicsAnalysis {
         // Cheap comparison — if the same file produced both, these will be identical.
         self.phase_coherence_mean == other.phase_coherence_mean
             && self.fundamental_freq == other.fundamental_freq
+            && self.decay_exponent == other.decay_exponent
+            && self.decay_fit_indices == other.decay_fit_indices
             && self.flux_per_frame.len() == other.flux_per_frame.len()
             && self.phase_coherence.len() == other.phase_coherence.len()
     }
 }
 
+impl HarmonicsAnalysis {
+    pub fn coherence_verdict(&self) -> CoherenceVerdict {
+        CoherenceVerdict::from_mean(self.phase_coherence_mean)
+    }
+
+    /// Artifact indicators, excluding the "nothing found" placeholder.
+    pub fn artifact_findings(&self) -> impl Iterator<Item = &str> {
+        self.artifact_indicators.iter().map(String::as_str).filter(|s| *s != NO_ARTIFACTS)
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Public entry points
 // ---------------------------------------------------------------------------
 
 /// Compute the harmonics analysis summary for sidebar display.
 /// Does not return 2-D per-frame coherence (call `compute_coherence_frames` for the heatmap).
-pub fn analyze_harmonics(audio: &AudioData, spectrogram: &SpectrogramData) -> HarmonicsAnalysis {
+///
+/// `num_harmonics` (including the fundamental) is clamped to
+/// `1..=MAX_HARMONIC_COUNT` and to the harmonics that fit below Nyquist.
+/// `flux_smoothing` is applied to the per-frame flux before its statistics
+/// and artifact checks, so single-frame transients don't read as anomalies.
+/// `decay_fit` picks the harmonics and method for the decay exponent.
+pub fn analyze_harmonics(
+    audio: &AudioData,
+    spectrogram: &SpectrogramData,
+    num_harmonics: usize,
+    flux_smoothing: Smoothing,
+    decay_fit: DecayFit,
+) -> HarmonicsAnalysis {
+    analyze_harmonics_range(audio, spectrogram, 0.0, audio.duration_secs, num_harmonics, flux_smoothing, decay_fit)
+}
+
+/// The fundamental and harmonic decay profile: the parts of
+/// [`HarmonicsAnalysis`] that need only an average magnitude spectrum.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HarmonicDecay {
+    /// FFT bin of the detected fundamental.
+    pub fundamental_bin: Option<usize>,
+    pub fundamental_freq: Option<f32>,
+    pub harmonic_amplitudes: Vec<f32>,
+    pub decay_exponent: f32,
+    pub decay_is_monotonic: bool,
+    pub decay_anomaly_indices: Vec<usize>,
+    pub decay_fit_indices: Vec<usize>,
+    pub harmonics_nyquist_limited: bool,
+}
+
+/// Detect the fundamental in `avg_spectrum` and measure how its harmonics
+/// decay. Works on a spectrum averaged a column at a time (see
+/// `incremental::AverageSpectrum`) as well as on a whole spectrogram.
+pub fn harmonic_decay_from_spectrum(
+    avg_spectrum: &[f32],
+    freq_resolution: f64,
+    max_freq: f64,
+    num_harmonics: usize,
+    decay_fit: DecayFit,
+) -> HarmonicDecay {
+    let harmonics_requested = num_harmonics.clamp(1, MAX_HARMONIC_COUNT);
+    let fundamental_bin = detect_fundamental_hps(avg_spectrum);
+    let fundamental_freq = fundamental_bin.map(|b| b as f32 * freq_resolution as f32);
+    let (harmonic_amplitudes, decay_exponent, decay_is_monotonic, decay_anomaly_indices, decay_fit_indices) =
+        if let Some(f_bin) = fundamental_bin {
+            compute_harmonic_decay(avg_spectrum, f_bin, harmonics_requested, max_freq, freq_resolution, decay_fit)
+        } else {
+            (vec![], 1.0, true, vec![], vec![])
+        };
+    let harmonics_nyquist_limited = fundamental_freq.is_some_and(|f0| {
+        harmonics_below_nyquist(f0 as f64, max_freq) < harmonics_requested
+    });
+    HarmonicDecay {
+        fundamental_bin,
+        fundamental_freq,
+        harmonic_amplitudes,
+        decay_exponent,
+        decay_is_monotonic,
+        decay_anomaly_indices,
+        decay_fit_indices,
+        harmonics_nyquist_limited,
+    }
+}
+
+/// How many harmonics of `fundamental_hz` (including the fundamental itself)
+/// lie at or below `max_freq`.
+pub fn harmonics_below_nyquist(fundamental_hz: f64, max_freq: f64) -> usize {
+    if fundamental_hz <= 0.0 {
+        return 0;
+    }
+    (max_freq / fundamental_hz).floor() as usize
+}
+
+/// A frequency expressed relative to a fundamental.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HarmonicRatio {
+    /// `freq / fundamental`, e.g. 2.01.
+    pub ratio: f64,
+    /// Nearest harmonic number (1 = the fundamental itself).
+    pub nearest: usize,
+    /// Relative deviation from that harmonic, e.g. 0.005 = 0.5 % sharp.
+    pub deviation: f64,
+}
+
+/// Express `freq_hz` as a multiple of `fundamental_hz`, to judge whether a
+/// band sits on a true harmonic. None for non-positive inputs.
+pub fn harmonic_ratio(freq_hz: f64, fundamental_hz: f64) -> Option<HarmonicRatio> {
+    if !(freq_hz > 0.0 && fundamental_hz > 0.0) {
+        return None;
+    }
+    let ratio = freq_hz / fundamental_hz;
+    let nearest = (ratio.round() as usize).max(1);
+    Some(HarmonicRatio {
+        ratio,
+        nearest,
+        deviation: ratio / nearest as f64 - 1.0,
+    })
+}
+
+/// Like [`analyze_harmonics`], but restricted to `time_start..time_end` (seconds).
+///
+/// Spectrogram columns are re-sliced by their `time_offset` and the complex
+/// STFT only reads the matching sample region, so metrics for a single call
+/// aren't diluted by the silence and other pulses around it.
+pub fn analyze_harmonics_range(
+    audio: &AudioData,
+    spectrogram: &SpectrogramData,
+    time_start: f64,
+    time_end: f64,
+    num_harmonics: usize,
+    flux_smoothing: Smoothing,
+    decay_fit: DecayFit,
+) -> HarmonicsAnalysis {
+    let harmonics_requested = num_harmonics.clamp(1, MAX_HARMONIC_COUNT);
     let fft_size = derive_fft_size(audio.sample_rate, spectrogram.freq_resolution);
     let hop_size = derive_hop_size(audio.sample_rate, spectrogram.time_resolution);
 
+    let (t0, t1) = (time_start.min(time_end).max(0.0), time_start.max(time_end));
+    let col_start = spectrogram.columns.partition_point(|c| c.time_offset < t0);
+    let col_end = spectrogram.columns.partition_point(|c| c.time_offset < t1).max(col_start);
+    let columns = &spectrogram.columns[col_start..col_end];
+
+    let sr = audio.sample_rate as f64;
+    let total = audio.source.total_samples();
+    let start_sample = ((t0 * sr) as u64).min(total);
+    let end_sample = ((t1 * sr).ceil() as u64).min(total).max(start_sample);
+    let region_len = (end_sample - start_sample) as usize;
+
     // Spectral flux (cheap — reuses existing SpectrogramData).
-    let flux_per_frame = compute_spectral_flux_frames(&spectrogram.columns);
+    let flux_per_frame = flux_smoothing.apply_f32(&compute_spectral_flux_frames(columns));
     let flux_mean = mean_f32(&flux_per_frame);
     let flux_peak = flux_per_frame.iter().copied().fold(0.0f32, f32::max);
     let preringing_count = count_preringing(&flux_per_frame, flux_peak);
-    let staircasing_score = compute_staircasing_score(&spectrogram.columns, &flux_per_frame, flux_peak);
+    let staircasing_score = compute_staircasing_score(columns, &flux_per_frame, flux_peak);
 
     // Harmonic decay (cheap — uses existing SpectrogramData).
-    let avg_spectrum = compute_avg_spectrum(&spectrogram.columns);
-    let fundamental_bin = detect_fundamental_hps(&avg_spectrum);
-    let fundamental_freq = fundamental_bin
-        .map(|b| b as f32 * spectrogram.freq_resolution as f32);
-    let (harmonic_amplitudes, decay_exponent, decay_is_monotonic, decay_anomaly_indices) =
-        if let Some(f_bin) = fundamental_bin {
-            compute_harmonic_decay(
-                &avg_spectrum,
-                f_bin,
-                spectrogram.max_freq,
-                spectrogram.freq_resolution,
-            )
-        } else {
-            (vec![], 1.0, true, vec![])
-        };
+    let avg_spectrum = compute_avg_spectrum(columns);
+    let HarmonicDecay {
+        fundamental_bin,
+        fundamental_freq,
+        harmonic_amplitudes,
+        decay_exponent,
+        decay_is_monotonic,
+        decay_anomaly_indices,
+        decay_fit_indices,
+        harmonics_nyquist_limited,
+    } = harmonic_decay_from_spectrum(
+        &avg_spectrum,
+        spectrogram.freq_resolution,
+        spectrogram.max_freq,
+        harmonics_requested,
+        decay_fit,
+    );
 
     // Phase coherence (requires a new STFT pass to keep complex output).
-    let (phase_coherence, _) = if audio.source.total_samples() as usize >= fft_size {
-        let frames = compute_complex_stft(audio, fft_size, hop_size);
+    let (phase_coherence, _) = if region_len >= fft_size {
+        let frames = compute_complex_stft(audio, start_sample, region_len, fft_size, hop_size);
         compute_phase_coherence_summary(&frames, fft_size, hop_size)
     } else {
         let n = fft_size / 2 + 1;
@@ -162,7 +419,7 @@ pub fn analyze_harmonics(audio: &AudioData, spectrogram: &SpectrogramData) -> Ha
         ));
     }
     if indicators.is_empty() {
-        indicators.push("No significant artifacts detected".to_string());
+        indicators.push(NO_ARTIFACTS.to_string());
     }
 
     HarmonicsAnalysis {
@@ -174,6 +431,9 @@ pub fn analyze_harmonics(audio: &AudioData, spectrogram: &SpectrogramData) -> Ha
         decay_exponent,
         decay_is_monotonic,
         decay_anomaly_indices,
+        decay_fit_indices,
+        harmonics_requested,
+        harmonics_nyquist_limited,
         flux_per_frame,
         flux_mean,
         flux_peak,
@@ -194,7 +454,8 @@ pub fn compute_coherence_frames(
     if (audio.source.total_samples() as usize) < fft_size {
         return Vec::new();
     }
-    let frames = compute_complex_stft(audio, fft_size, hop_size);
+    let total = audio.source.total_samples() as usize;
+    let frames = compute_complex_stft(audio, 0, total, fft_size, hop_size);
     let (_, coherence_frames) = compute_phase_coherence_summary(&frames, fft_size, hop_size);
     coherence_frames
 }
@@ -215,29 +476,13 @@ fn derive_hop_size(sample_rate: u32, time_resolution: f64) -> usize {
 
 fn compute_complex_stft(
     audio: &AudioData,
+    start_sample: u64,
+    len: usize,
     fft_size: usize,
     hop_size: usize,
 ) -> Vec<Vec<Complex32>> {
-    let total = audio.source.total_samples() as usize;
-    let samples = audio.source.read_region(ChannelView::MonoMix, 0, total);
-    let fft = HARM_FFT_PLANNER.with(|p| p.borrow_mut().plan_fft_forward(fft_size));
-    let window = hann_window(fft_size);
-    let mut frames = Vec::new();
-    let mut input = fft.make_input_vec();
-    let mut spectrum = fft.make_output_vec();
-    let mut pos = 0;
-    while pos + fft_size <= samples.len() {
-        for (inp, (&s, &w)) in input
-            .iter_mut()
-            .zip(samples[pos..pos + fft_size].iter().zip(window.iter()))
-        {
-            *inp = s * w;
-        }
-        fft.process(&mut input, &mut spectrum).expect("FFT failed");
-        frames.push(spectrum.to_vec());
-        pos += hop_size;
-    }
-    frames
+    let samples = audio.source.read_region(ChannelView::MonoMix, start_sample, len);
+    crate::dsp::fft::compute_complex_frames(&samples, fft_size, hop_size)
 }
 
 /// Returns `(per_bin_coherence, per_frame_coherence_map)`.
@@ -365,17 +610,21 @@ fn detect_fundamental_hps(avg_spectrum: &[f32]) -> Option<usize> {
     }
 }
 
-/// Extract normalised harmonic amplitudes and fit a power-law decay exponent.
+/// Extract up to `num_harmonics` normalised harmonic amplitudes and fit a
+/// power-law decay exponent over the harmonics `fit` selects. Stops early at
+/// `max_freq` (Nyquist). Also returns the fitted harmonic indices.
 fn compute_harmonic_decay(
     avg_spectrum: &[f32],
     fundamental_bin: usize,
+    num_harmonics: usize,
     max_freq: f64,
     freq_resolution: f64,
-) -> (Vec<f32>, f32, bool, Vec<usize>) {
+    fit: DecayFit,
+) -> (Vec<f32>, f32, bool, Vec<usize>, Vec<usize>) {
     let n = avg_spectrum.len();
     let mut amplitudes = Vec::new();
 
-    for h in 1..=8usize {
+    for h in 1..=num_harmonics {
         let bin = fundamental_bin * h;
         if bin >= n {
             break;
@@ -395,35 +644,46 @@ fn compute_harmonic_decay(
     }
 
     if amplitudes.is_empty() {
-        return (vec![], 1.0, true, vec![]);
+        return (vec![], 1.0, true, vec![], vec![]);
     }
 
     let a1 = amplitudes[0].max(1e-10);
     let normalised: Vec<f32> = amplitudes.iter().map(|&a| a / a1).collect();
+    let fit_indices: Vec<usize> = (1..normalised.len()).filter(|&i| fit.includes(i)).collect();
+    // (log n, log(A_n / A1)) for each fitted harmonic
+    let points: Vec<(f32, f32)> = fit_indices
+        .iter()
+        .map(|&i| (((i + 1) as f32).ln(), normalised[i].max(1e-10).ln()))
+        .collect();
 
-    // Least-squares fit of log(A_n / A1) = -α * log(n).
-    let decay_exponent = if normalised.len() >= 2 {
-        let mut num = 0.0f32;
-        let mut den = 0.0f32;
-        for (i, &amp) in normalised.iter().enumerate().skip(1) {
-            let log_n = ((i + 1) as f32).ln();
-            let log_a = amp.max(1e-10).ln();
-            num += -log_a * log_n;
-            den += log_n * log_n;
+    // Fit log(A_n / A1) = -α * log(n).
+    let decay_exponent = match fit.method {
+        _ if points.is_empty() => 1.0,
+        DecayFitMethod::LeastSquares => {
+            let num: f32 = points.iter().map(|&(log_n, log_a)| -log_a * log_n).sum();
+            let den: f32 = points.iter().map(|&(log_n, _)| log_n * log_n).sum();
+            if den > 0.0 {
+                num / den
+            } else {
+                1.0
+            }
         }
-        if den > 0.0 {
-            num / den
-        } else {
-            1.0
+        DecayFitMethod::Robust => {
+            let mut alphas: Vec<f32> = points.iter().map(|&(log_n, log_a)| -log_a / log_n).collect();
+            alphas.sort_by(f32::total_cmp);
+            let mid = alphas.len() / 2;
+            if alphas.len().is_multiple_of(2) {
+                (alphas[mid - 1] + alphas[mid]) / 2.0
+            } else {
+                alphas[mid]
+            }
         }
-    } else {
-        1.0
     };
 
-    // Detect monotonicity anomalies.
+    // Detect monotonicity anomalies among the fitted harmonics.
     let mut decay_is_monotonic = true;
     let mut anomaly_indices = Vec::new();
-    for i in 1..normalised.len() {
+    for &i in &fit_indices {
         // Flag if this harmonic has ≥ 95 % of the previous one's energy.
         if normalised[i] >= normalised[i - 1] * 0.95 {
             decay_is_monotonic = false;
@@ -431,7 +691,7 @@ fn compute_harmonic_decay(
         }
     }
 
-    (normalised, decay_exponent, decay_is_monotonic, anomaly_indices)
+    (normalised, decay_exponent, decay_is_monotonic, anomaly_indices, fit_indices)
 }
 
 /// Coherence ratio: mean coherence at harmonic bins divided by overall mean.
@@ -743,3 +1003,115 @@ pub fn compute_tile_phase_angle_data(
         flow_shifts,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat-topped spectrum with peaks at every multiple of `f_bin`, decaying as 1/n.
+    fn harmonic_spectrum(len: usize, f_bin: usize) -> Vec<f32> {
+        let mut spec = vec![1e-4f32; len];
+        for h in 1..len / f_bin {
+            spec[h * f_bin] = 1.0 / h as f32;
+        }
+        spec
+    }
+
+    #[test]
+    fn test_decay_respects_requested_count() {
+        let spec = harmonic_spectrum(513, 20);
+        for count in [2, 8, 12] {
+            let (amps, alpha, monotonic, anomalies, fitted) =
+                compute_harmonic_decay(&spec, 20, count, 96_000.0, 187.5, DecayFit::ALL_HARMONICS);
+            assert_eq!(amps.len(), count);
+            assert!(monotonic, "1/n decay should be monotonic: {anomalies:?}");
+            assert!((alpha - 1.0).abs() < 0.05, "alpha = {alpha}");
+            assert_eq!(fitted, (1..count).collect::<Vec<_>>());
+        }
+    }
+
+    #[test]
+    fn test_decay_fit_range_and_robust_method() {
+        // 1/n decay with a spurious loud 8th harmonic
+        let mut spec = harmonic_spectrum(513, 20);
+        spec[160] = 0.5;
+        let fit = |first, last, method| {
+            let (_, alpha, monotonic, _, fitted) =
+                compute_harmonic_decay(&spec, 20, 8, 96_000.0, 187.5, DecayFit { first_harmonic: first, last_harmonic: last, method });
+            (alpha, monotonic, fitted)
+        };
+        let (skewed, monotonic, _) = fit(2, 8, DecayFitMethod::LeastSquares);
+        assert!(!monotonic);
+        assert!(skewed < 0.9, "alpha = {skewed}");
+        // Leaving H8 out of the fit
+        let (alpha, monotonic, fitted) = fit(2, 7, DecayFitMethod::LeastSquares);
+        assert!(monotonic);
+        assert_eq!(fitted, vec![1, 2, 3, 4, 5, 6]);
+        assert!((alpha - 1.0).abs() < 0.05, "alpha = {alpha}");
+        // Or outvoting it
+        let (alpha, ..) = fit(2, 8, DecayFitMethod::Robust);
+        assert!((alpha - 1.0).abs() < 0.05, "alpha = {alpha}");
+        // The fundamental is the reference, never a fitted point
+        assert_eq!(fit(1, 3, DecayFitMethod::LeastSquares).2, vec![1, 2]);
+    }
+
+    #[test]
+    fn test_decay_stops_at_nyquist() {
+        // Fundamental at bin 100 of 513: only 5 harmonics fit below bin 512.
+        let spec = harmonic_spectrum(513, 100);
+        let max_freq = 512.0 * 187.5;
+        let (amps, ..) = compute_harmonic_decay(&spec, 100, 12, max_freq, 187.5, DecayFit::ALL_HARMONICS);
+        assert_eq!(amps.len(), 5);
+        assert_eq!(harmonics_below_nyquist(100.0 * 187.5, max_freq), 5);
+        assert_eq!(harmonics_below_nyquist(0.0, max_freq), 0);
+    }
+
+    #[test]
+    fn test_analyze_harmonics_range_on_harmonic_tone() {
+        use crate::dsp::fft::compute_spectrogram;
+        use crate::test_signals::{len_of, mix_at, tone_secs};
+        use crate::types::AudioData;
+
+        // Bin-centred fundamental (1024-point FFT at 192 kHz: 187.5 Hz bins)
+        // with 1/n harmonics over 0.4..0.6 s, and a loud tone on the 2nd
+        // harmonic later on that would skew a whole-file decay profile.
+        let sr = 192_000;
+        let f0 = 100.0 * 187.5;
+        let mut samples = vec![0.0f32; len_of(sr, 1.0)];
+        for n in 1..=3 {
+            mix_at(&mut samples, &tone_secs(f0 * n as f64, sr, 0.2, 0.3 / n as f32), len_of(sr, 0.4));
+        }
+        mix_at(&mut samples, &tone_secs(2.0 * f0, sr, 0.3, 0.8), len_of(sr, 0.65));
+        let audio = AudioData::from_mono(samples, sr);
+        let spec = compute_spectrogram(&audio, 1024, 256);
+
+        let r = analyze_harmonics_range(&audio, &spec, 0.42, 0.58, 3, Smoothing::OFF, DecayFit::ALL_HARMONICS);
+        let found = r.fundamental_freq.expect("fundamental");
+        assert!((found as f64 - f0).abs() <= 187.5, "fundamental {found}");
+        assert_eq!(r.harmonic_amplitudes.len(), 3);
+        assert!((r.harmonic_amplitudes[1] - 0.5).abs() < 0.05, "{:?}", r.harmonic_amplitudes);
+        assert!((r.harmonic_amplitudes[2] - 1.0 / 3.0).abs() < 0.05, "{:?}", r.harmonic_amplitudes);
+        assert!(r.decay_is_monotonic);
+        assert!((r.decay_exponent - 1.0).abs() < 0.15, "alpha = {}", r.decay_exponent);
+        assert!(!r.harmonics_nyquist_limited);
+
+        // The range may be given backwards
+        let swapped = analyze_harmonics_range(&audio, &spec, 0.58, 0.42, 3, Smoothing::OFF, DecayFit::ALL_HARMONICS);
+        assert_eq!(swapped.fundamental_freq, r.fundamental_freq);
+        // Over the whole file the later tone breaks the decay
+        let whole = analyze_harmonics_range(&audio, &spec, 0.0, 1.0, 3, Smoothing::OFF, DecayFit::ALL_HARMONICS);
+        assert!(!whole.decay_is_monotonic, "{:?}", whole.harmonic_amplitudes);
+    }
+
+    #[test]
+    fn test_harmonic_ratio() {
+        let r = harmonic_ratio(50_250.0, 25_000.0).unwrap();
+        assert!((r.ratio - 2.01).abs() < 1e-9);
+        assert_eq!(r.nearest, 2);
+        assert!((r.deviation - 0.005).abs() < 1e-9);
+        // Below the fundamental the nearest harmonic is still the first
+        assert_eq!(harmonic_ratio(10_000.0, 25_000.0).unwrap().nearest, 1);
+        assert_eq!(harmonic_ratio(50_000.0, 0.0), None);
+        assert_eq!(harmonic_ratio(0.0, 25_000.0), None);
+    }
+}
@@ -1,6 +1,12 @@
 use crate::dsp::filters::lowpass_filter;
 use std::f64::consts::PI;
 
+/// Default number of cascaded single-pole low-pass stages after mixing
+/// (-24 dB/octave, roughly a 4th-order Butterworth).
+pub const DEFAULT_LP_ORDER: usize = 4;
+/// Steepest selectable low-pass (-48 dB/octave).
+pub const MAX_LP_ORDER: usize = 8;
+
 /// Simulate a heterodyne bat detector by mixing (multiplying) the input signal
 /// with a local oscillator, then low-pass filtering to extract the difference
 /// frequency. This shifts ultrasonic frequencies down into the audible range.
@@ -9,7 +15,11 @@ use std::f64::consts::PI;
 /// local oscillator has frequency f_lo, multiplication produces two components:
 ///   - f_in + f_lo  (sum, removed by low-pass filter)
 ///   - |f_in - f_lo| (difference, the audible output)
-pub fn heterodyne_mix(samples: &[f32], sample_rate: u32, lo_freq: f64, cutoff_hz: f64) -> Vec<f32> {
+///
+/// `order` is the number of low-pass stages (each -6 dB/octave), clamped to
+/// `1..=MAX_LP_ORDER`. Fewer stages give a gentle, "open" rolloff that lets
+/// neighbouring energy bleed through; more isolate a narrower beat band.
+pub fn heterodyne_mix(samples: &[f32], sample_rate: u32, lo_freq: f64, cutoff_hz: f64, order: usize) -> Vec<f32> {
     let sr = sample_rate as f64;
     let angular_freq = 2.0 * PI * lo_freq;
 
@@ -25,10 +35,9 @@ pub fn heterodyne_mix(samples: &[f32], sample_rate: u32, lo_freq: f64, cutoff_hz
         .collect();
 
     // Step 2: Cascaded low-pass filter to remove the sum frequency component.
-    // 4 passes of a single-pole IIR gives -24 dB/octave rolloff
-    // (equivalent to a 4th-order Butterworth).
+    // Each pass of a single-pole IIR adds -6 dB/octave of rolloff.
     let mut filtered = mixed;
-    for _ in 0..4 {
+    for _ in 0..order.clamp(1, MAX_LP_ORDER) {
         filtered = lowpass_filter(&filtered, cutoff_hz, sample_rate);
     }
     filtered
@@ -39,7 +48,7 @@ pub fn heterodyne_mix(samples: &[f32], sample_rate: u32, lo_freq: f64, cutoff_hz
 /// consecutive audio buffers to avoid clicks and transients.
 pub struct RealtimeHet {
     phase: f64,
-    lp_state: [f32; 4],
+    lp_state: [f32; MAX_LP_ORDER],
 }
 
 impl Default for RealtimeHet {
@@ -52,12 +61,13 @@ impl RealtimeHet {
     pub fn new() -> Self {
         Self {
             phase: 0.0,
-            lp_state: [0.0; 4],
+            lp_state: [0.0; MAX_LP_ORDER],
         }
     }
 
-    /// Process `input` through heterodyne (ring modulation + 4-pass LP) and
-    /// write result into `output`. Both slices must have the same length.
+    /// Process `input` through heterodyne (ring modulation + `order`-pass LP,
+    /// as in `heterodyne_mix`) and write result into `output`. Both slices
+    /// must have the same length.
     pub fn process(
         &mut self,
         input: &[f32],
@@ -65,20 +75,22 @@ impl RealtimeHet {
         sample_rate: u32,
         lo_freq: f64,
         cutoff_hz: f64,
+        order: usize,
     ) {
         let sr = sample_rate as f64;
         let phase_inc = 2.0 * PI * lo_freq / sr;
         let dt = 1.0 / sr;
         let rc = 1.0 / (2.0 * PI * cutoff_hz);
         let alpha = (dt / (rc + dt)) as f32;
+        let order = order.clamp(1, MAX_LP_ORDER);
 
         for (i, &sample) in input.iter().enumerate() {
             // Ring modulation with continuous phase
             let lo = (self.phase + phase_inc * i as f64).cos() as f32;
             let mut val = sample * lo;
 
-            // 4-pass cascaded single-pole LP filter
-            for s in self.lp_state.iter_mut() {
+            // Cascaded single-pole LP filter
+            for s in self.lp_state[..order].iter_mut() {
                 val = alpha * val + (1.0 - alpha) * *s;
                 *s = val;
             }
@@ -93,7 +105,7 @@ impl RealtimeHet {
     /// Reset state (call when HET params change significantly or mic restarts)
     pub fn reset(&mut self) {
         self.phase = 0.0;
-        self.lp_state = [0.0; 4];
+        self.lp_state = [0.0; MAX_LP_ORDER];
     }
 }
 
@@ -118,7 +130,7 @@ mod tests {
             })
             .collect();
 
-        let output = heterodyne_mix(&input, sample_rate, lo_freq, 15_000.0);
+        let output = heterodyne_mix(&input, sample_rate, lo_freq, 15_000.0, DEFAULT_LP_ORDER);
         assert_eq!(output.len(), input.len());
 
         // Verify the output has energy (is not all zeros)
@@ -142,7 +154,22 @@ mod tests {
 
     #[test]
     fn test_heterodyne_empty_input() {
-        let output = heterodyne_mix(&[], 192_000, 45_000.0, 15_000.0);
+        let output = heterodyne_mix(&[], 192_000, 45_000.0, 15_000.0, DEFAULT_LP_ORDER);
         assert!(output.is_empty());
     }
+
+    #[test]
+    fn test_steeper_order_attenuates_more() {
+        // A 20 kHz beat note sits above a 5 kHz cutoff; more stages cut it further.
+        let sample_rate = 192_000u32;
+        let input: Vec<f32> = (0..19_200)
+            .map(|i| (2.0 * PI * 60_000.0 * i as f64 / sample_rate as f64).sin() as f32)
+            .collect();
+        let rms = |order| {
+            let out = heterodyne_mix(&input, sample_rate, 40_000.0, 5_000.0, order);
+            (out.iter().map(|s| (*s as f64).powi(2)).sum::<f64>() / out.len() as f64).sqrt()
+        };
+        let (gentle, default, steep) = (rms(1), rms(DEFAULT_LP_ORDER), rms(MAX_LP_ORDER));
+        assert!(gentle > default && default > steep, "{gentle} {default} {steep}");
+    }
 }
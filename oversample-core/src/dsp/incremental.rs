@@ -0,0 +1,159 @@
+//! Analyses that consume spectrogram columns as they become available.
+//!
+//! Large files never materialize a full `SpectrogramData.columns`; their
+//! columns arrive in `spectral_store` a tile at a time as the viewport and
+//! prefetcher compute them. An analysis implementing [`ColumnAnalysis`] can
+//! be wrapped in [`Incremental`] and fed whatever new columns the store holds
+//! each time a tile lands, giving a partial result that firms up as coverage
+//! grows. The same analysis can also be fed an in-memory spectrogram in one go.
+
+use crate::canvas::spectral_store;
+use crate::types::SpectrogramColumn;
+
+/// An analysis that accumulates over spectrogram columns in any order.
+pub trait ColumnAnalysis {
+    fn add_column(&mut self, index: usize, column: &SpectrogramColumn);
+}
+
+/// Tracks which columns an analysis has already seen, so each is added once.
+pub struct Incremental<A> {
+    analysis: A,
+    seen: Vec<bool>,
+    consumed: usize,
+    total: usize,
+}
+
+impl<A: ColumnAnalysis> Incremental<A> {
+    pub fn new(analysis: A) -> Self {
+        Self { analysis, seen: Vec::new(), consumed: 0, total: 0 }
+    }
+
+    pub fn analysis(&self) -> &A {
+        &self.analysis
+    }
+
+    /// Add any columns for `file_idx` in the spectral store that haven't been
+    /// added yet. Returns how many were new.
+    pub fn feed_from_store(&mut self, file_idx: usize) -> usize {
+        if let Some((_, total)) = spectral_store::coverage(file_idx) {
+            self.total = total;
+        }
+        let analysis = &mut self.analysis;
+        let added = spectral_store::visit_unseen(file_idx, &mut self.seen, |i, col| {
+            analysis.add_column(i, col);
+        });
+        self.consumed += added;
+        added
+    }
+
+    /// Add in-memory columns starting at column `start`, skipping any already
+    /// seen. Returns how many were new.
+    pub fn feed_columns(&mut self, start: usize, columns: &[SpectrogramColumn]) -> usize {
+        let end = start + columns.len();
+        if self.seen.len() < end {
+            self.seen.resize(end, false);
+        }
+        self.total = self.total.max(end);
+        let mut added = 0;
+        for (i, col) in columns.iter().enumerate() {
+            if !std::mem::replace(&mut self.seen[start + i], true) {
+                self.analysis.add_column(start + i, col);
+                added += 1;
+            }
+        }
+        self.consumed += added;
+        added
+    }
+
+    /// (columns added, columns in the file).
+    pub fn progress(&self) -> (usize, usize) {
+        (self.consumed, self.total)
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.total > 0 && self.consumed >= self.total
+    }
+}
+
+/// Mean magnitude spectrum over the columns added so far.
+#[derive(Clone, Debug, Default)]
+pub struct AverageSpectrum {
+    sum: Vec<f64>,
+    count: usize,
+}
+
+impl AverageSpectrum {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn mean(&self) -> Vec<f32> {
+        if self.count == 0 {
+            return Vec::new();
+        }
+        let n = self.count as f64;
+        self.sum.iter().map(|&s| (s / n) as f32).collect()
+    }
+}
+
+impl ColumnAnalysis for AverageSpectrum {
+    fn add_column(&mut self, _index: usize, column: &SpectrogramColumn) {
+        // Evicted placeholder columns are empty; skip them
+        if column.magnitudes.is_empty() {
+            return;
+        }
+        if self.sum.len() < column.magnitudes.len() {
+            self.sum.resize(column.magnitudes.len(), 0.0);
+        }
+        for (s, &m) in self.sum.iter_mut().zip(&column.magnitudes) {
+            *s += m as f64;
+        }
+        self.count += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn column(peak_bin: usize, bins: usize) -> SpectrogramColumn {
+        let mut magnitudes = vec![0.01; bins];
+        magnitudes[peak_bin] = 1.0;
+        SpectrogramColumn { magnitudes, time_offset: 0.0 }
+    }
+
+    #[test]
+    fn test_store_columns_are_added_once_as_they_arrive() {
+        let file_idx = 9001;
+        spectral_store::init(file_idx, 8, 64);
+        let mut run = Incremental::new(AverageSpectrum::default());
+        assert_eq!(run.feed_from_store(file_idx), 0);
+        assert_eq!(run.progress(), (0, 8));
+
+        let tile: Vec<_> = (0..4).map(|_| column(10, 33)).collect();
+        spectral_store::insert_columns(file_idx, 4, &tile);
+        assert_eq!(run.feed_from_store(file_idx), 4);
+        assert_eq!(run.feed_from_store(file_idx), 0);
+        assert!(!run.is_complete());
+
+        spectral_store::insert_columns(file_idx, 0, &tile);
+        assert_eq!(run.feed_from_store(file_idx), 4);
+        assert!(run.is_complete());
+        assert_eq!(run.analysis().count(), 8);
+        let mean = run.analysis().mean();
+        assert!((mean[10] - 1.0).abs() < 1e-6);
+        spectral_store::clear_file(file_idx);
+    }
+
+    #[test]
+    fn test_in_memory_columns_are_added_once() {
+        let mut run = Incremental::new(AverageSpectrum::default());
+        let cols = vec![column(5, 33), column(7, 33)];
+        assert_eq!(run.feed_columns(2, &cols), 2);
+        assert_eq!(run.feed_columns(2, &cols), 0);
+        assert_eq!(run.feed_columns(0, &cols), 2);
+        assert_eq!(run.progress(), (4, 4));
+        let mean = run.analysis().mean();
+        assert!((mean[5] - 0.505).abs() < 1e-6);
+    }
+}
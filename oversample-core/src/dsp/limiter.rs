@@ -0,0 +1,138 @@
+//! Output limiter for synthesized audio (heterodyne, zero-crossing), in both
+//! playback and export.
+//!
+//! Those modes have no level normalization of their own, so after the gain
+//! slider a loud input can drive them past full scale. The limiter reduces
+//! gain instantly on a peak over the ceiling (so nothing clips) and recovers
+//! smoothly over the release time, which is far gentler than hard clipping.
+//! It keeps state across chunks so gain doesn't jump at chunk joins.
+
+/// Default ceiling, -1 dBFS.
+pub const DEFAULT_CEILING: f32 = 0.891;
+/// Default release time in milliseconds.
+pub const DEFAULT_RELEASE_MS: f64 = 80.0;
+
+/// Gain below this counts as limiting when reporting.
+const LIMITING_GAIN: f32 = 0.999;
+
+pub struct SoftLimiter {
+    ceiling: f32,
+    release_coeff: f32,
+    gain: f32,
+}
+
+impl SoftLimiter {
+    pub fn new(sample_rate: u32) -> Self {
+        Self::with_params(sample_rate, DEFAULT_CEILING, DEFAULT_RELEASE_MS)
+    }
+
+    pub fn with_params(sample_rate: u32, ceiling: f32, release_ms: f64) -> Self {
+        let release_coeff = (-1.0 / (release_ms * 0.001 * sample_rate.max(1) as f64)).exp() as f32;
+        Self { ceiling, release_coeff, gain: 1.0 }
+    }
+
+    /// Limit `samples` in place. Returns how many samples were gain-reduced.
+    pub fn process(&mut self, samples: &mut [f32]) -> usize {
+        let mut limited = 0;
+        for s in samples.iter_mut() {
+            let g = self.next_gain(s.abs());
+            *s *= g;
+            if g < LIMITING_GAIN {
+                limited += 1;
+            }
+        }
+        limited
+    }
+
+    /// Limit two channels with one shared gain, driven by the louder channel,
+    /// so the stereo image doesn't shift. Returns how many frames were reduced.
+    pub fn process_stereo(&mut self, left: &mut [f32], right: &mut [f32]) -> usize {
+        let mut limited = 0;
+        for (l, r) in left.iter_mut().zip(right.iter_mut()) {
+            let g = self.next_gain(l.abs().max(r.abs()));
+            *l *= g;
+            *r *= g;
+            if g < LIMITING_GAIN {
+                limited += 1;
+            }
+        }
+        limited
+    }
+
+    /// Release toward unity, but never above the gain that keeps `level`
+    /// under the ceiling.
+    fn next_gain(&mut self, level: f32) -> f32 {
+        let released = 1.0 - (1.0 - self.gain) * self.release_coeff;
+        let needed = if level > self.ceiling { self.ceiling / level } else { 1.0 };
+        self.gain = released.min(needed);
+        self.gain
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals;
+
+    fn tone(amp: f32, n: usize) -> Vec<f32> {
+        test_signals::tone(400.0, 48_000, n, amp)
+    }
+
+    #[test]
+    fn test_quiet_signal_untouched() {
+        let mut lim = SoftLimiter::new(48_000);
+        let orig = tone(0.5, 4800);
+        let mut s = orig.clone();
+        assert_eq!(lim.process(&mut s), 0);
+        assert_eq!(s, orig);
+    }
+
+    #[test]
+    fn test_loud_signal_stays_under_ceiling() {
+        let mut lim = SoftLimiter::new(48_000);
+        let mut s = tone(4.0, 4800);
+        assert!(lim.process(&mut s) > 0);
+        let peak = s.iter().fold(0.0f32, |m, x| m.max(x.abs()));
+        assert!(peak <= DEFAULT_CEILING + 1e-6, "peak {peak}");
+    }
+
+    #[test]
+    fn test_loud_het_and_zc_output_stays_in_range() {
+        // A 45 kHz call, synthesised, then pushed 12 dB over full scale by the gain slider
+        let sr = 384_000;
+        let call = test_signals::tone(45_000.0, sr, 38_400, 1.0);
+        let outputs = [
+            crate::dsp::heterodyne::heterodyne_mix(&call, sr, 40_000.0, 15_000.0, 4),
+            crate::dsp::zc_divide::zc_divide(&call, sr, 8, false),
+        ];
+        for mut out in outputs {
+            let peak = out.iter().fold(0.0f32, |m, s| m.max(s.abs()));
+            assert!(peak > 0.0);
+            out.iter_mut().for_each(|s| *s *= 4.0 / peak);
+            SoftLimiter::new(sr).process(&mut out);
+            assert!(out.iter().all(|s| s.abs() <= 1.0));
+        }
+    }
+
+    #[test]
+    fn test_gain_recovers_after_peak() {
+        let mut lim = SoftLimiter::new(48_000);
+        let mut burst = tone(4.0, 480);
+        lim.process(&mut burst);
+        // 0.5 s of quiet signal: well past the release time
+        let mut quiet = tone(0.1, 24_000);
+        lim.process(&mut quiet);
+        assert!(lim.gain > 0.99, "gain {}", lim.gain);
+    }
+
+    #[test]
+    fn test_stereo_shares_gain() {
+        let mut lim = SoftLimiter::new(48_000);
+        let mut l = tone(3.0, 2000);
+        let mut r = tone(0.3, 2000);
+        lim.process_stereo(&mut l, &mut r);
+        for (a, b) in l.iter().zip(&r) {
+            assert!((a - b * 10.0).abs() < 1e-4);
+        }
+    }
+}
@@ -1,12 +1,21 @@
 pub mod agc;
+pub mod band_activity;
 pub mod bit_analysis;
+pub mod call_params;
+pub mod call_snap;
+pub mod dc;
+pub mod declip;
+pub mod fade;
 pub mod fft;
 pub mod filters;
 pub mod harmonics;
 pub mod heterodyne;
+pub mod incremental;
+pub mod limiter;
 pub mod notch;
 pub mod phase_vocoder;
 pub mod pitch_shift;
+pub mod spectral_diff;
 pub mod spectral_sub;
 pub mod zc_divide;
 pub mod wsnr;
@@ -14,4 +23,9 @@ pub mod zero_crossing;
 pub mod chromagram;
 pub mod psd;
 pub mod pulse_detect;
+pub mod qc_report;
+pub mod resample;
 pub mod resonators;
+pub mod silence_trim;
+pub mod smoothing;
+pub mod species_hint;
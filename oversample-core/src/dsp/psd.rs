@@ -242,6 +242,56 @@ where
     })
 }
 
+// ── Energy band ─────────────────────────────────────────────────────────────
+
+/// Frequency band holding `fraction` (e.g. 0.9) of the energy that stands
+/// above the noise floor, considering only bins at or above `min_freq`.
+///
+/// The floor is the median bin power, which sits at the background level
+/// whenever calls occupy less than half the spectrum. Energy less than 3 dB
+/// over it is ignored, and equal tails are trimmed from both ends. Returns
+/// `None` if nothing rises above the floor.
+pub fn energy_band(psd: &PsdResult, fraction: f64, min_freq: f64) -> Option<(f64, f64)> {
+    let res = psd.freq_resolution;
+    let first = ((min_freq / res).ceil() as usize).max(1);
+    if first >= psd.power_db.len() {
+        return None;
+    }
+    let power: Vec<f64> = psd.power_db[first..].iter().map(|db| 10f64.powf(db / 10.0)).collect();
+    let mut sorted = power.clone();
+    sorted.sort_by(|a, b| a.total_cmp(b));
+    let floor = sorted[sorted.len() / 2];
+    let excess: Vec<f64> = power.iter().map(|&p| (p - 2.0 * floor).max(0.0)).collect();
+    let total: f64 = excess.iter().sum();
+    if total <= 0.0 {
+        return None;
+    }
+
+    let tail = total * (1.0 - fraction.clamp(0.0, 1.0)) / 2.0;
+    let mut acc = 0.0;
+    let mut lo_bin = 0;
+    for (i, &e) in excess.iter().enumerate() {
+        acc += e;
+        if acc > tail {
+            lo_bin = i;
+            break;
+        }
+    }
+    acc = 0.0;
+    let mut hi_bin = excess.len() - 1;
+    for (i, &e) in excess.iter().enumerate().rev() {
+        acc += e;
+        if acc > tail {
+            hi_bin = i;
+            break;
+        }
+    }
+    let nyquist = psd.sample_rate as f64 / 2.0;
+    let lo = ((first + lo_bin) as f64 - 0.5) * res;
+    let hi = ((first + hi_bin) as f64 + 0.5) * res;
+    Some((lo.max(min_freq), hi.min(nyquist)))
+}
+
 // ── Peak detection ──────────────────────────────────────────────────────────
 
 /// Maximum number of peaks to return.
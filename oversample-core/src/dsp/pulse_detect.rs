@@ -1,6 +1,7 @@
 use crate::audio::source::ChannelView;
 use crate::types::{AudioData, SpectrogramData};
 use crate::dsp::zc_divide::{cascaded_lp, smooth_envelope};
+use crate::dsp::smoothing::Smoothing;
 
 #[derive(Clone, Debug)]
 pub struct DetectedPulse {
@@ -11,12 +12,27 @@ pub struct DetectedPulse {
     pub peak_freq: f64,      // dominant frequency (Hz) from spectrogram
     pub snr_db: f64,         // signal-to-noise ratio relative to noise floor
     pub peak_amplitude: f64, // peak envelope level (linear)
+    /// Peak level relative to the noise in the gaps immediately around this
+    /// pulse (dB). Falls back to the whole-file noise floor when the
+    /// neighbouring gaps are too short to measure.
+    pub local_snr_db: f64,
 }
 
+/// Local SNR at or above which a pulse is considered clean enough for ID work.
+pub const USABLE_PULSE_SNR_DB: f64 = 10.0;
+
+/// How far either side of a pulse to look for inter-pulse noise (ms).
+const LOCAL_NOISE_WINDOW_MS: f64 = 20.0;
+
 impl DetectedPulse {
     pub fn duration_ms(&self) -> f64 {
         (self.end_time - self.start_time) * 1000.0
     }
+
+    /// True if the local SNR is high enough for the call to be useful for ID.
+    pub fn is_usable(&self) -> bool {
+        self.local_snr_db >= USABLE_PULSE_SNR_DB
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -29,6 +45,13 @@ pub struct PulseDetectionParams {
     pub bandpass_low_hz: f64,
     /// Bandpass high frequency (Hz). 0 = no lowpass (use Nyquist).
     pub bandpass_high_hz: f64,
+    /// Drop pulses whose local SNR is below this (dB). Keeps wind and
+    /// handling noise that pokes over the trigger threshold out of the
+    /// results. 0 (the default) = keep everything the trigger finds.
+    pub min_snr_db: f64,
+    /// Smoothing of each call's frequency contour before start, end and
+    /// characteristic frequencies are read from it (see `call_params`).
+    pub contour_smoothing: Smoothing,
 }
 
 impl Default for PulseDetectionParams {
@@ -40,6 +63,8 @@ impl Default for PulseDetectionParams {
             threshold_db: 6.0,
             bandpass_low_hz: 0.0,
             bandpass_high_hz: 0.0,
+            min_snr_db: 0.0,
+            contour_smoothing: Smoothing::OFF,
         }
     }
 }
@@ -56,45 +81,47 @@ pub fn detect_pulses(
 ) -> Vec<DetectedPulse> {
     let total = audio.source.total_samples() as usize;
     let samples = audio.source.read_region(ChannelView::MonoMix, 0, total);
-    let sr = audio.sample_rate;
-    if samples.len() < 2 {
-        return Vec::new();
-    }
-
-    // Step 1: Bandpass filter to focus frequency range
-    let filtered = bandpass(&samples, sr, params.bandpass_low_hz, params.bandpass_high_hz);
+    detect_pulses_with(&samples, audio.sample_rate, params, |start, end| {
+        find_peak_frequency(spectrogram, start, end)
+    })
+}
 
-    // Step 2: Compute energy envelope (~0.25ms window for bat calls)
-    let env_window = ((sr as f64 * 0.00025) as usize).max(1);
-    let envelope = smooth_envelope(&filtered, env_window);
+/// Detect pulses in a block of mono samples without a spectrogram.
+///
+/// `peak_freq` is left at 0; callers that need frequencies measure each
+/// pulse themselves (see `call_params::measure_calls`).
+pub fn detect_pulses_in_samples(
+    samples: &[f32],
+    sample_rate: u32,
+    params: &PulseDetectionParams,
+) -> Vec<DetectedPulse> {
+    detect_pulses_with(samples, sample_rate, params, |_, _| 0.0)
+}
 
-    // Step 3: Estimate noise floor (10th percentile of envelope)
-    let noise_floor = estimate_noise_floor(&envelope);
-    if noise_floor <= 0.0 {
+/// Shared body of the detectors; `peak_freq_of(start_time, end_time)`
+/// supplies each pulse's dominant frequency.
+fn detect_pulses_with(
+    samples: &[f32],
+    sr: u32,
+    params: &PulseDetectionParams,
+    peak_freq_of: impl Fn(f64, f64) -> f64,
+) -> Vec<DetectedPulse> {
+    let Some((envelope, noise_floor, raw_pulses)) = envelope_and_raw_pulses(samples, sr, params) else {
         return Vec::new();
-    }
-
-    // Step 4: Schmitt trigger pulse detection
-    let threshold_high = noise_floor * 10f64.powf(params.threshold_db / 20.0) as f32;
-    let hysteresis_db = params.threshold_db - 3.0;
-    let threshold_low = noise_floor * 10f64.powf(hysteresis_db.max(0.0) / 20.0) as f32;
+    };
 
-    let min_gap_samples = ((sr as f64 * params.min_gap_ms / 1000.0) as usize).max(1);
     let min_dur_samples = ((sr as f64 * params.min_pulse_duration_ms / 1000.0) as usize).max(1);
     let max_dur_samples = ((sr as f64 * params.max_pulse_duration_ms / 1000.0) as usize).max(1);
-
-    let raw_pulses = detect_raw_pulses(
-        &envelope,
-        threshold_high,
-        threshold_low,
-        min_gap_samples,
-    );
+    // All raw spans (including ones about to be rejected by duration) are
+    // excluded when measuring local noise.
+    let spans: Vec<(usize, usize)> = raw_pulses.iter().map(|&(s, e, _, _)| (s, e)).collect();
+    let noise_window = ((sr as f64 * LOCAL_NOISE_WINDOW_MS / 1000.0) as usize).max(1);
 
     // Step 5: Filter by duration and build results
     let mut pulses = Vec::new();
     let mut index = 1usize;
 
-    for (start_sample, end_sample, peak_sample, peak_amp) in raw_pulses {
+    for (span_idx, (start_sample, end_sample, peak_sample, peak_amp)) in raw_pulses.into_iter().enumerate() {
         let dur = end_sample - start_sample;
         if dur < min_dur_samples || dur > max_dur_samples {
             continue;
@@ -104,15 +131,21 @@ pub fn detect_pulses(
         let end_time = end_sample as f64 / sr as f64;
         let peak_time = peak_sample as f64 / sr as f64;
 
-        // Step 6: Find peak frequency from spectrogram
-        let peak_freq = find_peak_frequency(spectrogram, start_time, end_time);
-
-        // Step 7: Compute SNR
+        // Step 6: Compute SNR
         let snr_db = if noise_floor > 0.0 {
             20.0 * (peak_amp as f64 / noise_floor as f64).log10()
         } else {
             0.0
         };
+        let local_noise = local_noise_level(&envelope, &spans, span_idx, noise_window)
+            .unwrap_or(noise_floor);
+        let local_snr_db = 20.0 * (peak_amp as f64 / local_noise.max(1e-10) as f64).log10();
+        if params.min_snr_db > 0.0 && local_snr_db < params.min_snr_db {
+            continue;
+        }
+
+        // Step 7: Find peak frequency from spectrogram
+        let peak_freq = peak_freq_of(start_time, end_time);
 
         pulses.push(DetectedPulse {
             index,
@@ -122,6 +155,7 @@ pub fn detect_pulses(
             peak_freq,
             snr_db,
             peak_amplitude: peak_amp as f64,
+            local_snr_db,
         });
         index += 1;
     }
@@ -129,6 +163,57 @@ pub fn detect_pulses(
     pulses
 }
 
+/// Count pulses in a block of mono samples without measuring their frequency.
+///
+/// Uses the same envelope, thresholds and SNR gate as [`detect_pulses`] but
+/// needs no spectrogram, so it is cheap enough to run over a whole folder of files.
+pub fn count_pulses(samples: &[f32], sample_rate: u32, params: &PulseDetectionParams) -> usize {
+    detect_pulses_in_samples(samples, sample_rate, params).len()
+}
+
+/// (start_sample, end_sample, peak_sample, peak_amplitude) from the Schmitt trigger.
+type RawPulse = (usize, usize, usize, f32);
+
+/// Steps 1–4 of pulse detection: bandpass, envelope, noise floor and
+/// Schmitt-trigger spans. Returns None for silent or too-short input.
+fn envelope_and_raw_pulses(
+    samples: &[f32],
+    sr: u32,
+    params: &PulseDetectionParams,
+) -> Option<(Vec<f32>, f32, Vec<RawPulse>)> {
+    if samples.len() < 2 {
+        return None;
+    }
+
+    // Step 1: Bandpass filter to focus frequency range
+    let filtered = bandpass(samples, sr, params.bandpass_low_hz, params.bandpass_high_hz);
+
+    // Step 2: Compute energy envelope (~0.25ms window for bat calls)
+    let env_window = ((sr as f64 * 0.00025) as usize).max(1);
+    let envelope = smooth_envelope(&filtered, env_window);
+
+    // Step 3: Estimate noise floor (10th percentile of envelope)
+    let noise_floor = estimate_noise_floor(&envelope);
+    if noise_floor <= 0.0 {
+        return None;
+    }
+
+    // Step 4: Schmitt trigger pulse detection
+    let threshold_high = noise_floor * 10f64.powf(params.threshold_db / 20.0) as f32;
+    let hysteresis_db = params.threshold_db - 3.0;
+    let threshold_low = noise_floor * 10f64.powf(hysteresis_db.max(0.0) / 20.0) as f32;
+
+    let min_gap_samples = ((sr as f64 * params.min_gap_ms / 1000.0) as usize).max(1);
+
+    let raw_pulses = detect_raw_pulses(
+        &envelope,
+        threshold_high,
+        threshold_low,
+        min_gap_samples,
+    );
+    Some((envelope, noise_floor, raw_pulses))
+}
+
 /// Bandpass filter samples to the given frequency range.
 fn bandpass(samples: &[f32], sample_rate: u32, low_hz: f64, high_hz: f64) -> Vec<f32> {
     let nyquist = sample_rate as f64 / 2.0;
@@ -164,6 +249,32 @@ fn estimate_noise_floor(envelope: &[f32]) -> f32 {
     sampled[idx].max(1e-10) // tiny minimum to avoid division by zero
 }
 
+/// Median envelope level in the inter-pulse gaps within `window` samples
+/// before and after `spans[i]`. Returns `None` if there are too few gap
+/// samples to give a meaningful estimate.
+fn local_noise_level(
+    envelope: &[f32],
+    spans: &[(usize, usize)],
+    i: usize,
+    window: usize,
+) -> Option<f32> {
+    let (start, end) = spans[i];
+    let prev_end = if i > 0 { spans[i - 1].1 } else { 0 };
+    let next_start = spans.get(i + 1).map(|s| s.0).unwrap_or(envelope.len());
+
+    let before = start.saturating_sub(window).max(prev_end)..start;
+    let after = end.min(envelope.len())..(end + window).min(next_start).min(envelope.len());
+    let mut gap: Vec<f32> = before
+        .chain(after)
+        .filter_map(|j| envelope.get(j).copied())
+        .collect();
+    if gap.len() < (window / 8).max(4) {
+        return None;
+    }
+    gap.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    Some(gap[gap.len() / 2].max(1e-10))
+}
+
 /// Raw pulse detection using Schmitt trigger on envelope.
 /// Returns Vec of (start_sample, end_sample, peak_sample, peak_amplitude).
 fn detect_raw_pulses(
@@ -251,3 +362,66 @@ fn find_peak_frequency(
 
     best_bin as f64 * spectrogram.freq_resolution
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::{self, len_of, mix_at, tone_secs};
+
+    /// Low-level pseudo-random noise plus 5 ms tone bursts at the given
+    /// (start time, amplitude) pairs.
+    fn bursts(sample_rate: u32, secs: f64, calls: &[(f64, f32)], noise: f32) -> Vec<f32> {
+        let mut samples = test_signals::noise(len_of(sample_rate, secs), 2.0 * noise, 12345);
+        for &(t, amp) in calls {
+            let call = tone_secs(40_000.0, sample_rate, 0.005, amp);
+            mix_at(&mut samples, &call, len_of(sample_rate, t));
+        }
+        samples
+    }
+
+    #[test]
+    fn test_local_snr_per_pulse() {
+        let sr = 192_000;
+        let samples = bursts(sr, 0.3, &[(0.05, 0.5), (0.15, 0.02)], 0.001);
+        let audio = AudioData::from_mono(samples, sr);
+        let spec = crate::dsp::fft::compute_spectrogram(&audio, 256, 128);
+        let pulses = detect_pulses(&audio, &spec, &PulseDetectionParams::default());
+
+        assert_eq!(pulses.len(), 2, "expected two pulses, got {pulses:?}");
+        let (loud, faint) = (&pulses[0], &pulses[1]);
+        assert!(loud.local_snr_db > faint.local_snr_db + 20.0);
+        assert!(loud.is_usable());
+        // Uniform background: local and whole-file estimates should roughly agree.
+        assert!((loud.local_snr_db - loud.snr_db).abs() < 6.0);
+    }
+
+    #[test]
+    fn test_count_pulses_matches_detect() {
+        let sr = 192_000;
+        let samples = bursts(sr, 0.3, &[(0.05, 0.5), (0.15, 0.02)], 0.001);
+        let params = PulseDetectionParams::default();
+        assert_eq!(count_pulses(&samples, sr, &params), 2);
+        assert_eq!(count_pulses(&[0.0; 4096], sr, &params), 0);
+    }
+
+    #[test]
+    fn test_min_snr_gate() {
+        let sr = 192_000;
+        let samples = bursts(sr, 0.3, &[(0.05, 0.5), (0.15, 0.02)], 0.001);
+        let audio = AudioData::from_mono(samples, sr);
+        let spec = crate::dsp::fft::compute_spectrogram(&audio, 256, 128);
+
+        // Off by default, so existing results don't change
+        let all = detect_pulses(&audio, &spec, &PulseDetectionParams::default());
+        assert_eq!(all.len(), 2);
+
+        // Gate between the two pulses' local SNRs: only the loud one survives, renumbered from 1
+        let cut = (all[0].local_snr_db + all[1].local_snr_db) / 2.0;
+        let params = PulseDetectionParams { min_snr_db: cut, ..Default::default() };
+        let gated = detect_pulses(&audio, &spec, &params);
+        assert_eq!(gated.len(), 1);
+        assert_eq!(gated[0].index, 1);
+        assert!((gated[0].start_time - all[0].start_time).abs() < 1e-9);
+        assert_eq!(count_pulses(&audio.samples, sr, &params), 1);
+    }
+}
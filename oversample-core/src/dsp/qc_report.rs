@@ -0,0 +1,201 @@
+//! Batch quality-control report.
+//!
+//! Combines the wSNR grade with the harmonics module's phase-coherence verdict
+//! and artifact indicators into one row per file, so a curator can screen a
+//! folder of submitted recordings for low quality or signs of processing
+//! (e.g. pitch-shifted audio passed off as a bat call).
+
+use crate::dsp::call_params::csv_field;
+use crate::dsp::harmonics::{self, CoherenceVerdict, DecayFit, HarmonicsAnalysis};
+use crate::dsp::wsnr::{self, WsnrGrade, WsnrResult};
+use crate::dsp::smoothing::Smoothing;
+use crate::dsp::fft::compute_spectrogram;
+use crate::types::AudioData;
+
+/// STFT used for the harmonics pass.
+const QC_FFT_SIZE: usize = 512;
+const QC_HOP_SIZE: usize = 128;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct QcEntry {
+    pub file_name: String,
+    pub grade: WsnrGrade,
+    pub snr_db: f64,
+    pub phase_coherence_mean: f32,
+    pub coherence: CoherenceVerdict,
+    /// wSNR warnings followed by harmonics artifact indicators.
+    pub findings: Vec<String>,
+}
+
+impl QcEntry {
+    /// True if the file needs a closer look: poor grade, low phase
+    /// coherence, or any artifact finding.
+    pub fn flagged(&self) -> bool {
+        matches!(self.grade, WsnrGrade::D | WsnrGrade::E)
+            || self.coherence == CoherenceVerdict::Processed
+            || !self.findings.is_empty()
+    }
+}
+
+/// Settings for the harmonics pass. The batch report uses the defaults.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct QcOptions {
+    pub harmonic_count: usize,
+    pub smoothing: Smoothing,
+    pub decay_fit: DecayFit,
+}
+
+impl Default for QcOptions {
+    fn default() -> Self {
+        Self {
+            harmonic_count: harmonics::DEFAULT_HARMONIC_COUNT,
+            smoothing: Smoothing::OFF,
+            decay_fit: DecayFit::ALL_HARMONICS,
+        }
+    }
+}
+
+/// The full wSNR and harmonics results a [`QcEntry`] is drawn from.
+#[derive(Clone)]
+pub struct QcAnalysis {
+    pub wsnr: WsnrResult,
+    pub harmonics: HarmonicsAnalysis,
+}
+
+impl QcAnalysis {
+    pub fn entry(&self, file_name: &str) -> QcEntry {
+        let mut findings = self.wsnr.warnings.clone();
+        findings.extend(self.harmonics.artifact_findings().map(str::to_string));
+
+        QcEntry {
+            file_name: file_name.to_string(),
+            grade: self.wsnr.grade.clone(),
+            snr_db: self.wsnr.snr_db,
+            phase_coherence_mean: self.harmonics.phase_coherence_mean,
+            coherence: self.harmonics.coherence_verdict(),
+            findings,
+        }
+    }
+}
+
+/// Run the wSNR and harmonics passes over one file's mono samples.
+pub fn analyze(samples: &[f32], sample_rate: u32, options: &QcOptions) -> QcAnalysis {
+    let wsnr = wsnr::analyze_wsnr(samples, sample_rate);
+
+    let audio = AudioData::from_mono(samples.to_vec(), sample_rate);
+    let spectrogram = compute_spectrogram(&audio, QC_FFT_SIZE, QC_HOP_SIZE);
+    let harmonics = harmonics::analyze_harmonics(&audio, &spectrogram, options.harmonic_count, options.smoothing, options.decay_fit);
+
+    QcAnalysis { wsnr, harmonics }
+}
+
+/// Analyse one file's mono samples with the default options.
+pub fn analyze_file(file_name: &str, samples: &[f32], sample_rate: u32) -> QcEntry {
+    analyze(samples, sample_rate, &QcOptions::default()).entry(file_name)
+}
+
+pub const CSV_HEADER: &str = "file,flagged,grade,snr_db,phase_coherence_pct,coherence,findings\n";
+
+/// One CSV row per entry. Findings are joined with "; ".
+pub fn csv_rows(entries: &[QcEntry]) -> String {
+    let mut out = String::new();
+    for e in entries {
+        out.push_str(&format!(
+            "{},{},{},{:.1},{:.0},{},{}\n",
+            csv_field(&e.file_name),
+            if e.flagged() { "yes" } else { "no" },
+            e.grade.label(),
+            e.snr_db,
+            e.phase_coherence_mean * 100.0,
+            csv_field(e.coherence.label()),
+            csv_field(&e.findings.join("; ")),
+        ));
+    }
+    out
+}
+
+/// The report as a Markdown table, flagged files first.
+pub fn markdown_table(entries: &[QcEntry]) -> String {
+    let mut sorted: Vec<&QcEntry> = entries.iter().collect();
+    sorted.sort_by_key(|e| !e.flagged());
+
+    let flagged = entries.iter().filter(|e| e.flagged()).count();
+    let mut out = format!("# Quality-control report\n\n{} of {} files flagged.\n\n", flagged, entries.len());
+    out.push_str("| File | Flag | Grade | SNR (dB) | Phase coherence | Findings |\n");
+    out.push_str("|---|---|---|---|---|---|\n");
+    for e in sorted {
+        let findings = if e.findings.is_empty() { "\u{2014}".to_string() } else { e.findings.join("<br>") };
+        out.push_str(&format!(
+            "| {} | {} | {} | {:.1} | {:.0}% {} | {} |\n",
+            md_cell(&e.file_name),
+            if e.flagged() { "\u{26a0}" } else { "" },
+            e.grade.label(),
+            e.snr_db,
+            e.phase_coherence_mean * 100.0,
+            e.coherence.label(),
+            md_cell(&findings),
+        ));
+    }
+    out
+}
+
+/// Escape pipes so a value can't break out of its Markdown table cell.
+fn md_cell(s: &str) -> String {
+    s.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::tone_secs;
+
+    fn entry(name: &str, grade: WsnrGrade, coherence: CoherenceVerdict, findings: &[&str]) -> QcEntry {
+        QcEntry {
+            file_name: name.to_string(),
+            grade,
+            snr_db: 40.0,
+            phase_coherence_mean: 0.7,
+            coherence,
+            findings: findings.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_flagging() {
+        assert!(!entry("a", WsnrGrade::B, CoherenceVerdict::Natural, &[]).flagged());
+        assert!(entry("a", WsnrGrade::E, CoherenceVerdict::Natural, &[]).flagged());
+        assert!(entry("a", WsnrGrade::A, CoherenceVerdict::Processed, &[]).flagged());
+        assert!(entry("a", WsnrGrade::A, CoherenceVerdict::Natural, &["clipping"]).flagged());
+    }
+
+    #[test]
+    fn test_csv_and_markdown() {
+        let entries = [
+            entry("good.wav", WsnrGrade::A, CoherenceVerdict::Natural, &[]),
+            entry("odd, name.wav", WsnrGrade::C, CoherenceVerdict::Processed, &["x", "y|z"]),
+        ];
+        let csv = csv_rows(&entries);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].starts_with("good.wav,no,A,40.0,70,"));
+        assert!(lines[1].starts_with("\"odd, name.wav\",yes,C,"));
+        assert!(lines[1].ends_with(",x; y|z"));
+
+        let md = markdown_table(&entries);
+        assert!(md.contains("1 of 2 files flagged"));
+        // Flagged file is listed first, and its pipe is escaped
+        let odd = md.find("odd, name.wav").unwrap();
+        assert!(odd < md.find("good.wav").unwrap());
+        assert!(md.contains("y\\|z"));
+    }
+
+    #[test]
+    fn test_analyze_tone() {
+        let sr = 96_000;
+        let samples = tone_secs(20_000.0, sr, 0.5, 0.3);
+        let e = analyze_file("tone.wav", &samples, sr);
+        assert_eq!(e.file_name, "tone.wav");
+        assert!(e.snr_db.is_finite());
+        assert!((0.0..=1.0).contains(&e.phase_coherence_mean));
+    }
+}
@@ -0,0 +1,291 @@
+//! Band-limited sample-rate conversion.
+//!
+//! Windowed-sinc interpolation for paths where linear interpolation would be
+//! heard as buzz or aliasing, chiefly time-expansion playback: a 384 kHz
+//! recording slowed 10x runs at 38.4 kHz and has to be brought to the output
+//! device's rate. When downsampling, the kernel is widened so content above
+//! the new Nyquist frequency is filtered out rather than folded back.
+
+use std::f64::consts::PI;
+
+/// Zero crossings of the sinc kernel on each side of the centre tap.
+const HALF_ZEROS: f64 = 16.0;
+/// Passband edge as a fraction of the lower of the two Nyquist frequencies,
+/// leaving room for the kernel's transition band.
+const PASSBAND: f64 = 0.95;
+/// Kernel phases tabulated per input sample. Positions between two phases
+/// interpolate their taps linearly.
+const PHASES: usize = 512;
+
+/// Streaming windowed-sinc resampler.
+///
+/// Input arrives in chunks via `process`; output is continuous across chunk
+/// boundaries. Call `finish` after the last chunk to flush the tail.
+///
+/// The kernel is tabulated once at construction, so each output sample costs
+/// one or two multiply-adds per tap rather than a sinc and a window per tap.
+/// A whole-number step (integer decimation) only ever uses the first phase.
+pub struct SincResampler {
+    /// Input samples per output sample.
+    step: f64,
+    /// Taps either side of the output position, in whole input samples.
+    half_taps: usize,
+    /// `PHASES + 1` rows of `2 * half_taps` weights, each row summing to one.
+    /// Row `p` is for an output position `p / PHASES` past an input sample.
+    table: Vec<f32>,
+    /// Buffered input; `buf[0]` is absolute input index `buf_start`.
+    buf: Vec<f32>,
+    buf_start: i64,
+    /// Output samples produced so far; the next one sits at input position
+    /// `produced * step` (computed fresh each time so rounding can't drift).
+    produced: u64,
+    /// Total input samples received.
+    received: i64,
+}
+
+impl SincResampler {
+    pub fn new(in_rate: f64, out_rate: f64) -> Self {
+        let cutoff = (out_rate / in_rate).min(1.0) * PASSBAND;
+        let reach = HALF_ZEROS / cutoff;
+        let half_taps = reach.ceil() as usize;
+        let width = 2 * half_taps;
+        let mut table = Vec::with_capacity((PHASES + 1) * width);
+        for p in 0..=PHASES {
+            let frac = p as f64 / PHASES as f64;
+            // Tap j is input sample `floor(t) + 1 - half_taps + j`
+            let row: Vec<f64> = (0..width)
+                .map(|j| {
+                    let x = frac + half_taps as f64 - 1.0 - j as f64;
+                    sinc(cutoff * x) * blackman(x / reach)
+                })
+                .collect();
+            // Normalising by the tap sum keeps unity gain at DC for every phase.
+            let sum: f64 = row.iter().sum();
+            let scale = if sum.abs() > 1e-9 { 1.0 / sum } else { 0.0 };
+            table.extend(row.iter().map(|w| (w * scale) as f32));
+        }
+        // Silence before the first sample, so the opening taps have data.
+        let pad = half_taps;
+        Self {
+            step: in_rate / out_rate,
+            half_taps,
+            table,
+            buf: vec![0.0; pad],
+            buf_start: -(pad as i64),
+            produced: 0,
+            received: 0,
+        }
+    }
+
+    /// Feed the next chunk of input and return every output sample whose
+    /// kernel is now fully covered.
+    pub fn process(&mut self, input: &[f32]) -> Vec<f32> {
+        self.buf.extend_from_slice(input);
+        self.received += input.len() as i64;
+        let end = (self.buf_start + self.buf.len() as i64) as f64;
+        let mut out = Vec::with_capacity((input.len() as f64 / self.step) as usize + 1);
+        while self.pos().floor() + (self.half_taps as f64) < end {
+            out.push(self.interpolate(self.pos()));
+            self.produced += 1;
+        }
+        self.discard_consumed();
+        out
+    }
+
+    /// Flush the remaining output, treating the input as silent past its end.
+    pub fn finish(&mut self) -> Vec<f32> {
+        let pad = self.half_taps + 1;
+        self.buf.resize(self.buf.len() + pad, 0.0);
+        let mut out = Vec::new();
+        while self.pos() < self.received as f64 {
+            out.push(self.interpolate(self.pos()));
+            self.produced += 1;
+        }
+        self.discard_consumed();
+        out
+    }
+
+    fn pos(&self) -> f64 {
+        self.produced as f64 * self.step
+    }
+
+    fn interpolate(&self, t: f64) -> f32 {
+        let base = t.floor();
+        let width = 2 * self.half_taps;
+        let first = (base as i64 + 1 - self.half_taps as i64 - self.buf_start) as usize;
+        let input = &self.buf[first..first + width];
+        let phase = (t - base) * PHASES as f64;
+        let p = (phase as usize).min(PHASES - 1);
+        let mix = (phase - p as f64) as f32;
+        let dot = |row: usize| -> f32 {
+            let weights = &self.table[row * width..(row + 1) * width];
+            weights.iter().zip(input).map(|(w, x)| w * x).sum()
+        };
+        if mix == 0.0 {
+            dot(p)
+        } else {
+            dot(p) * (1.0 - mix) + dot(p + 1) * mix
+        }
+    }
+
+    /// Drop buffered input that no future output sample can reach.
+    fn discard_consumed(&mut self) {
+        let keep_from = self.pos().floor() as i64 + 1 - self.half_taps as i64;
+        let drop = (keep_from - self.buf_start).clamp(0, self.buf.len() as i64) as usize;
+        if drop > 0 {
+            self.buf.drain(..drop);
+            self.buf_start += drop as i64;
+        }
+    }
+}
+
+fn sinc(x: f64) -> f64 {
+    if x.abs() < 1e-12 { 1.0 } else { (PI * x).sin() / (PI * x) }
+}
+
+/// Blackman window over `v` in -1..=1.
+fn blackman(v: f64) -> f64 {
+    if v.abs() >= 1.0 {
+        return 0.0;
+    }
+    0.42 + 0.5 * (PI * v).cos() + 0.08 * (2.0 * PI * v).cos()
+}
+
+/// Resample a whole buffer from `in_rate` to `out_rate`.
+pub fn resample_sinc(samples: &[f32], in_rate: f64, out_rate: f64) -> Vec<f32> {
+    let mut resampler = SincResampler::new(in_rate, out_rate);
+    let mut out = resampler.process(samples);
+    out.extend(resampler.finish());
+    out
+}
+
+/// Decimation factor that brings a live stream at `sample_rate` to at most
+/// `max_rate` (0 = no limit, stream at full rate).
+pub fn display_factor_for(sample_rate: u32, max_rate: u32) -> usize {
+    if max_rate == 0 || max_rate >= sample_rate {
+        1
+    } else {
+        sample_rate.div_ceil(max_rate) as usize
+    }
+}
+
+/// Reduces a live display stream by an integer factor. A windowed-sinc
+/// low-pass ([`SincResampler`]) removes everything above the new Nyquist
+/// frequency first, so ultrasonic calls don't fold down into the displayed
+/// band. Filter state carries over between chunks.
+pub struct DisplayDecimator {
+    factor: usize,
+    resampler: Option<SincResampler>,
+}
+
+impl DisplayDecimator {
+    pub fn new(sample_rate: u32, factor: usize) -> Self {
+        let factor = factor.max(1);
+        let resampler = (factor > 1)
+            .then(|| SincResampler::new(sample_rate as f64, sample_rate as f64 / factor as f64));
+        Self { factor, resampler }
+    }
+
+    pub fn factor(&self) -> usize {
+        self.factor
+    }
+
+    pub fn process(&mut self, input: Vec<f32>) -> Vec<f32> {
+        match &mut self.resampler {
+            Some(r) => r.process(&input),
+            None => input,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::{self, rms};
+
+    fn tone(freq: f64, rate: f64, len: usize) -> Vec<f32> {
+        test_signals::tone(freq, rate as u32, len, 1.0)
+    }
+
+    #[test]
+    fn test_output_length_follows_ratio() {
+        let input = vec![0.0f32; 38_400];
+        assert_eq!(resample_sinc(&input, 38_400.0, 48_000.0).len(), 48_000);
+        assert_eq!(resample_sinc(&input, 38_400.0, 19_200.0).len(), 19_200);
+        assert!(resample_sinc(&[], 38_400.0, 48_000.0).is_empty());
+    }
+
+    #[test]
+    fn test_tone_passes_cleanly() {
+        // A 2 kHz tone upsampled 19.2k -> 48k keeps its level and shape
+        let out = resample_sinc(&tone(2_000.0, 19_200.0, 19_200), 19_200.0, 48_000.0);
+        let expected = tone(2_000.0, 48_000.0, out.len());
+        let mid = 1_000..out.len() - 1_000;
+        let err: Vec<f32> = out[mid.clone()].iter().zip(&expected[mid]).map(|(a, b)| a - b).collect();
+        assert!(rms(&err) < 1e-3, "error rms {}", rms(&err));
+    }
+
+    #[test]
+    fn test_downsampling_removes_content_above_new_nyquist() {
+        // 30 kHz can't be represented at 48 kHz and must not alias to 18 kHz
+        let out = resample_sinc(&tone(30_000.0, 96_000.0, 96_000), 96_000.0, 48_000.0);
+        let level = rms(&out[1_000..out.len() - 1_000]);
+        assert!(level < 0.01, "alias level {level}");
+    }
+
+    #[test]
+    fn test_integer_decimation_keeps_passband() {
+        // A step of 4 lands on whole input samples, so only phase 0 is used
+        let out = resample_sinc(&tone(5_000.0, 192_000.0, 192_000), 192_000.0, 48_000.0);
+        let expected = tone(5_000.0, 48_000.0, out.len());
+        let mid = 1_000..out.len() - 1_000;
+        let err: Vec<f32> = out[mid.clone()].iter().zip(&expected[mid]).map(|(a, b)| a - b).collect();
+        assert!(rms(&err) < 1e-3, "error rms {}", rms(&err));
+    }
+
+    #[test]
+    fn test_chunked_matches_one_shot() {
+        let input = tone(3_000.0, 25_000.0, 10_000);
+        let whole = resample_sinc(&input, 25_000.0, 48_000.0);
+        let mut r = SincResampler::new(25_000.0, 48_000.0);
+        let mut chunked = Vec::new();
+        for chunk in input.chunks(777) {
+            chunked.extend(r.process(chunk));
+        }
+        chunked.extend(r.finish());
+        assert_eq!(chunked.len(), whole.len());
+        assert!(chunked.iter().zip(&whole).all(|(a, b)| (a - b).abs() < 1e-6));
+    }
+
+    #[test]
+    fn test_display_factor_for() {
+        assert_eq!(display_factor_for(384_000, 0), 1);
+        assert_eq!(display_factor_for(96_000, 192_000), 1);
+        assert_eq!(display_factor_for(192_000, 192_000), 1);
+        assert_eq!(display_factor_for(384_000, 192_000), 2);
+        assert_eq!(display_factor_for(384_000, 96_000), 4);
+        // Rounds up so the rate never exceeds the limit
+        assert_eq!(display_factor_for(250_000, 96_000), 3);
+    }
+
+    #[test]
+    fn test_display_decimator() {
+        let mut passthrough = DisplayDecimator::new(384_000, 0);
+        assert_eq!(passthrough.factor(), 1);
+        assert_eq!(passthrough.process(vec![0.5, -0.5]), vec![0.5, -0.5]);
+
+        // 384 kHz down to 96 kHz: a 20 kHz tone passes, 100 kHz doesn't fold down
+        let mut d = DisplayDecimator::new(384_000, 4);
+        assert_eq!(d.factor(), 4);
+        let input = tone(20_000.0, 384_000.0, 38_400);
+        let out: Vec<f32> = input.chunks(3_000).flat_map(|c| d.process(c.to_vec())).collect();
+        assert!((out.len() as i64 - 9_600).unsigned_abs() < 100, "{} samples", out.len());
+        let level = rms(&out[1_000..]);
+        assert!((level - 0.5f32.sqrt()).abs() < 0.01, "passband level {level}");
+
+        let mut d = DisplayDecimator::new(384_000, 4);
+        let out = d.process(tone(100_000.0, 384_000.0, 38_400));
+        let level = rms(&out[1_000..]);
+        assert!(level < 0.01, "alias level {level}");
+    }
+}
@@ -0,0 +1,81 @@
+//! Find the active part of a recording so silent lead-ins and tails can be
+//! trimmed on import.
+//!
+//! "Silence" is judged against the recording's own noise floor rather than
+//! an absolute level: field recorders rarely capture true digital silence,
+//! and a quiet site at high gain sits well above it. The signal is cut into
+//! short frames, the floor is taken as a low quantile of frame levels, and
+//! anything `threshold_db` above it counts as activity.
+
+/// Frame length for level measurement.
+const FRAME_SECS: f64 = 0.010;
+/// Quantile of frame levels taken as the noise floor.
+const NOISE_QUANTILE: f64 = 0.1;
+
+/// RMS level of each `frame`-sample chunk, in dB.
+fn frame_levels(samples: &[f32], frame: usize) -> Vec<f32> {
+    samples
+        .chunks(frame)
+        .map(|c| {
+            let power = c.iter().map(|s| s * s).sum::<f32>() / c.len() as f32;
+            10.0 * power.max(1e-20).log10()
+        })
+        .collect()
+}
+
+/// Sample range `start..end` from the first to the last frame more than
+/// `threshold_db` above the noise floor, widened by `pad_secs` either side.
+/// Returns None when nothing stands above the floor, so a recording that is
+/// all noise is left alone rather than trimmed to nothing.
+pub fn active_region(samples: &[f32], sample_rate: u32, threshold_db: f32, pad_secs: f64) -> Option<(usize, usize)> {
+    if sample_rate == 0 {
+        return None;
+    }
+    let frame = ((sample_rate as f64 * FRAME_SECS) as usize).max(1);
+    let levels = frame_levels(samples, frame);
+    if levels.len() < 2 {
+        return None;
+    }
+    let mut sorted = levels.clone();
+    sorted.sort_by(f32::total_cmp);
+    let floor = sorted[((sorted.len() - 1) as f64 * NOISE_QUANTILE).round() as usize];
+    let active = |l: &f32| *l > floor + threshold_db;
+    let first = levels.iter().position(active)?;
+    let last = levels.iter().rposition(active)?;
+
+    let pad = (pad_secs.max(0.0) * sample_rate as f64) as usize;
+    let start = (first * frame).saturating_sub(pad);
+    let end = ((last + 1) * frame + pad).min(samples.len());
+    Some((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_signals::burst_over_noise;
+
+    /// 1 s of faint noise at 48 kHz with a loud tone from 0.4 to 0.6 s.
+    fn quiet_ends() -> Vec<f32> {
+        burst_over_noise(48_000, 1.0, 5_000.0, (0.4, 0.6), 0.5, 0.001, 7)
+    }
+
+    #[test]
+    fn test_finds_active_span() {
+        let (start, end) = active_region(&quiet_ends(), 48_000, 12.0, 0.0).unwrap();
+        assert!((start as f64 / 48_000.0 - 0.4).abs() < 0.011, "start {start}");
+        assert!((end as f64 / 48_000.0 - 0.6).abs() < 0.011, "end {end}");
+    }
+
+    #[test]
+    fn test_padding_is_clamped() {
+        let (start, end) = active_region(&quiet_ends(), 48_000, 12.0, 0.5).unwrap();
+        assert_eq!((start, end), (0, 48_000));
+        let (start, _) = active_region(&quiet_ends(), 48_000, 12.0, 0.1).unwrap();
+        assert!((start as f64 / 48_000.0 - 0.3).abs() < 0.011);
+    }
+
+    #[test]
+    fn test_noise_only_is_left_alone() {
+        assert_eq!(active_region(&quiet_ends()[..15_000], 48_000, 12.0, 0.0), None);
+    }
+}
@@ -0,0 +1,231 @@
+//! Smoothing for per-frame metric tracks.
+//!
+//! Frame-by-frame measurements such as the dominant-frequency contour and
+//! spectral flux jitter from frame to frame, and a single transient frame
+//! can look like an anomaly. A moving average evens out the jitter; a
+//! median removes single-frame spikes while keeping the edges of a sweep.
+//! Windows are centred and shrink at the ends, so the output has the same
+//! length as the input.
+//!
+//! Streaming spectrogram columns can't wait for a centred window, so they
+//! use a one-sided exponential average instead ([`ColumnAverager`]).
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum SmoothingKind {
+    #[default]
+    Off,
+    MovingAverage,
+    Median,
+}
+
+/// A smoothing filter: a kind and a window length in frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Smoothing {
+    pub kind: SmoothingKind,
+    pub window: usize,
+}
+
+impl Smoothing {
+    pub const OFF: Smoothing = Smoothing { kind: SmoothingKind::Off, window: 1 };
+
+    /// Choices offered in the UI, from none to strongest.
+    pub const PRESETS: [Smoothing; 7] = [
+        Self::OFF,
+        Smoothing { kind: SmoothingKind::Median, window: 3 },
+        Smoothing { kind: SmoothingKind::Median, window: 5 },
+        Smoothing { kind: SmoothingKind::Median, window: 9 },
+        Smoothing { kind: SmoothingKind::MovingAverage, window: 3 },
+        Smoothing { kind: SmoothingKind::MovingAverage, window: 5 },
+        Smoothing { kind: SmoothingKind::MovingAverage, window: 9 },
+    ];
+
+    pub fn median(window: usize) -> Self {
+        Self { kind: SmoothingKind::Median, window }
+    }
+
+    pub fn moving_average(window: usize) -> Self {
+        Self { kind: SmoothingKind::MovingAverage, window }
+    }
+
+    /// True if [`apply`](Self::apply) would return its input unchanged.
+    pub fn is_off(self) -> bool {
+        self.kind == SmoothingKind::Off || self.window < 2
+    }
+
+    /// Frames either side of the centre frame (an even window is rounded up).
+    fn half_width(self) -> usize {
+        self.window / 2
+    }
+
+    pub fn label(self) -> String {
+        if self.is_off() {
+            return "Off".to_string();
+        }
+        match self.kind {
+            SmoothingKind::Median => format!("Median, {} frames", self.window),
+            _ => format!("Average, {} frames", self.window),
+        }
+    }
+
+    /// e.g. "off", "median5", "mean3".
+    pub fn key(self) -> String {
+        if self.is_off() {
+            return "off".to_string();
+        }
+        match self.kind {
+            SmoothingKind::Median => format!("median{}", self.window),
+            _ => format!("mean{}", self.window),
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        let parse = |n: &str| n.parse::<usize>().ok().filter(|&w| w >= 2);
+        if let Some(w) = s.strip_prefix("median").and_then(parse) {
+            Self::median(w)
+        } else if let Some(w) = s.strip_prefix("mean").and_then(parse) {
+            Self::moving_average(w)
+        } else {
+            Self::OFF
+        }
+    }
+
+    /// Smooth `values` with a centred window.
+    pub fn apply(self, values: &[f64]) -> Vec<f64> {
+        if self.is_off() || values.len() < 2 {
+            return values.to_vec();
+        }
+        let half = self.half_width();
+        let mut scratch = Vec::with_capacity(2 * half + 1);
+        (0..values.len())
+            .map(|i| {
+                let window = &values[i.saturating_sub(half)..(i + half + 1).min(values.len())];
+                match self.kind {
+                    SmoothingKind::Median => {
+                        scratch.clear();
+                        scratch.extend_from_slice(window);
+                        median(&mut scratch)
+                    }
+                    _ => window.iter().sum::<f64>() / window.len() as f64,
+                }
+            })
+            .collect()
+    }
+
+    /// [`apply`](Self::apply) for `f32` tracks such as spectral flux.
+    pub fn apply_f32(self, values: &[f32]) -> Vec<f32> {
+        if self.is_off() {
+            return values.to_vec();
+        }
+        let wide: Vec<f64> = values.iter().map(|&v| v as f64).collect();
+        self.apply(&wide).into_iter().map(|v| v as f32).collect()
+    }
+}
+
+impl Default for Smoothing {
+    fn default() -> Self {
+        Self::OFF
+    }
+}
+
+/// Exponential average of spectrogram columns as they arrive, for a
+/// steadier live display. Averaging is done on power, so a persistent tone
+/// keeps its level while noise flicker evens out towards its mean.
+#[derive(Clone, Debug, Default)]
+pub struct ColumnAverager {
+    power: Vec<f32>,
+}
+
+impl ColumnAverager {
+    /// Share of the running average kept at each step of `step_secs` for a
+    /// time constant of `time_constant_secs`. 0 = no smoothing.
+    pub fn retain(time_constant_secs: f64, step_secs: f64) -> f32 {
+        if time_constant_secs <= 0.0 || step_secs <= 0.0 {
+            return 0.0;
+        }
+        (-step_secs / time_constant_secs).exp() as f32
+    }
+
+    /// Fold `magnitudes` into the average and replace them with it. The
+    /// first column (or one of a new size) starts the average afresh.
+    pub fn apply(&mut self, magnitudes: &mut [f32], retain: f32) {
+        if self.power.len() != magnitudes.len() {
+            self.power = magnitudes.iter().map(|m| m * m).collect();
+            return;
+        }
+        for (p, m) in self.power.iter_mut().zip(magnitudes.iter_mut()) {
+            *p = retain * *p + (1.0 - retain) * *m * *m;
+            *m = p.sqrt();
+        }
+    }
+
+    /// Forget the running average.
+    pub fn reset(&mut self) {
+        self.power.clear();
+    }
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len().is_multiple_of(2) {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_median_removes_single_frame_spike() {
+        let sweep = [50.0, 49.0, 48.0, 90.0, 46.0, 45.0, 44.0];
+        let smoothed = Smoothing::median(3).apply(&sweep);
+        assert_eq!(smoothed.len(), sweep.len());
+        assert_eq!(smoothed[3], 48.0);
+        // Neighbouring frames stay on the sweep
+        assert_eq!(&smoothed[..3], &[49.5, 49.0, 49.0]);
+        assert_eq!(&smoothed[4..], &[46.0, 45.0, 44.5]);
+    }
+
+    #[test]
+    fn test_moving_average_and_off() {
+        let values = [0.0, 3.0, 0.0, 3.0];
+        assert_eq!(Smoothing::moving_average(3).apply(&values), vec![1.5, 1.0, 2.0, 1.5]);
+        assert_eq!(Smoothing::OFF.apply(&values), values.to_vec());
+        assert_eq!(Smoothing::median(1).apply(&values), values.to_vec());
+    }
+
+    #[test]
+    fn test_keys_round_trip() {
+        for s in Smoothing::PRESETS {
+            assert_eq!(Smoothing::from_key(&s.key()), s);
+        }
+        assert_eq!(Smoothing::from_key("median1"), Smoothing::OFF);
+        assert_eq!(Smoothing::from_key("bogus"), Smoothing::OFF);
+    }
+
+    #[test]
+    fn test_column_averager_settles_on_mean_power() {
+        assert_eq!(ColumnAverager::retain(0.0, 0.005), 0.0);
+        let retain = ColumnAverager::retain(0.05, 0.005);
+        assert!((retain - (-0.1f32).exp()).abs() < 1e-6);
+
+        // Bin 0 flickers between 0 and 2; bin 1 holds steady at 1
+        let mut avg = ColumnAverager::default();
+        let mut last = Vec::new();
+        for i in 0..200 {
+            let mut col = vec![if i % 2 == 0 { 2.0 } else { 0.0 }, 1.0];
+            avg.apply(&mut col, retain);
+            last = col;
+        }
+        assert!((last[0] - 2.0f32.sqrt()).abs() < 0.15, "flicker {}", last[0]);
+        assert!((last[1] - 1.0).abs() < 1e-4);
+
+        // No smoothing passes columns through
+        let mut col = vec![3.0, 4.0];
+        avg.apply(&mut col, 0.0);
+        assert_eq!(col, vec![3.0, 4.0]);
+    }
+}
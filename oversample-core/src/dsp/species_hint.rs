@@ -0,0 +1,330 @@
+//! Rule-based species hint from measured call parameters.
+//!
+//! The median call parameters of a recording (characteristic frequency,
+//! bandwidth, duration and pulse interval) are compared against a table of
+//! typical ranges per species. This is a simple heuristic, not a classifier:
+//! many species overlap, and social calls, feeding buzzes or a distant bat
+//! will all mislead it. Results are a starting point for identification only.
+//!
+//! The table is plain JSON so it can be edited or replaced per region.
+//! Frequencies are in kHz and times in ms to keep it readable by hand.
+
+use serde::{Deserialize, Serialize};
+use crate::dsp::call_params::CallMeasurement;
+
+/// Built-in table: common species of north-west Europe.
+pub const DEFAULT_TABLE_JSON: &str = r#"{
+  "region": "North-west Europe",
+  "species": [
+    { "name": "Common pipistrelle", "scientific_name": "Pipistrellus pipistrellus",
+      "char_freq_khz": [42, 51], "bandwidth_khz": [5, 40], "duration_ms": [3, 9], "interval_ms": [60, 130] },
+    { "name": "Soprano pipistrelle", "scientific_name": "Pipistrellus pygmaeus",
+      "char_freq_khz": [51, 60], "bandwidth_khz": [5, 40], "duration_ms": [3, 8], "interval_ms": [50, 110] },
+    { "name": "Nathusius' pipistrelle", "scientific_name": "Pipistrellus nathusii",
+      "char_freq_khz": [36, 42], "bandwidth_khz": [3, 30], "duration_ms": [4, 11], "interval_ms": [70, 150] },
+    { "name": "Common noctule", "scientific_name": "Nyctalus noctula",
+      "char_freq_khz": [17, 24], "bandwidth_khz": [1, 20], "duration_ms": [8, 28], "interval_ms": [150, 500] },
+    { "name": "Leisler's bat", "scientific_name": "Nyctalus leisleri",
+      "char_freq_khz": [22, 29], "bandwidth_khz": [2, 25], "duration_ms": [7, 20], "interval_ms": [100, 350] },
+    { "name": "Serotine", "scientific_name": "Eptesicus serotinus",
+      "char_freq_khz": [24, 32], "bandwidth_khz": [5, 35], "duration_ms": [6, 16], "interval_ms": [90, 250] },
+    { "name": "Barbastelle", "scientific_name": "Barbastella barbastellus",
+      "char_freq_khz": [29, 38], "bandwidth_khz": [5, 20], "duration_ms": [2, 6], "interval_ms": [40, 120] },
+    { "name": "Brown long-eared bat", "scientific_name": "Plecotus auritus",
+      "char_freq_khz": [23, 40], "bandwidth_khz": [15, 45], "duration_ms": [1, 4], "interval_ms": [30, 100] },
+    { "name": "Daubenton's bat", "scientific_name": "Myotis daubentonii",
+      "char_freq_khz": [35, 50], "bandwidth_khz": [30, 60], "duration_ms": [2, 6], "interval_ms": [40, 100] },
+    { "name": "Natterer's bat", "scientific_name": "Myotis nattereri",
+      "char_freq_khz": [30, 50], "bandwidth_khz": [60, 110], "duration_ms": [1, 5], "interval_ms": [30, 90] },
+    { "name": "Greater horseshoe bat", "scientific_name": "Rhinolophus ferrumequinum",
+      "char_freq_khz": [77, 85], "bandwidth_khz": [0, 15], "duration_ms": [30, 80], "interval_ms": [60, 200] },
+    { "name": "Lesser horseshoe bat", "scientific_name": "Rhinolophus hipposideros",
+      "char_freq_khz": [105, 116], "bandwidth_khz": [0, 15], "duration_ms": [20, 50], "interval_ms": [50, 150] }
+  ]
+}"#;
+
+/// Weight of the characteristic frequency relative to the other criteria.
+const CHAR_FREQ_WEIGHT: f64 = 2.0;
+
+/// Candidates below this confidence are not reported.
+const MIN_CONFIDENCE: f64 = 0.4;
+
+/// A species table: one set of typical call ranges per species.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesTable {
+    #[serde(default)]
+    pub region: String,
+    pub species: Vec<SpeciesRule>,
+}
+
+/// Typical call ranges for one species. Each range is `[min, max]`.
+/// Only the characteristic frequency is required.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpeciesRule {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub scientific_name: Option<String>,
+    pub char_freq_khz: [f64; 2],
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bandwidth_khz: Option<[f64; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duration_ms: Option<[f64; 2]>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interval_ms: Option<[f64; 2]>,
+}
+
+impl SpeciesTable {
+    /// Parse a table from JSON, rejecting empty tables and inverted ranges.
+    pub fn from_json(json: &str) -> Result<Self, String> {
+        let table: SpeciesTable = serde_json::from_str(json).map_err(|e| e.to_string())?;
+        if table.species.is_empty() {
+            return Err("Species table is empty".to_string());
+        }
+        for rule in &table.species {
+            let ranges = [Some(rule.char_freq_khz), rule.bandwidth_khz, rule.duration_ms, rule.interval_ms];
+            if ranges.iter().flatten().any(|[lo, hi]| lo > hi) {
+                return Err(format!("{}: range minimum is above its maximum", rule.name));
+            }
+        }
+        Ok(table)
+    }
+
+    /// The built-in table.
+    pub fn builtin() -> Self {
+        Self::from_json(DEFAULT_TABLE_JSON).expect("built-in species table is valid")
+    }
+}
+
+/// Median parameters of the measured calls in a recording.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallSummary {
+    pub char_freq_khz: f64,
+    pub bandwidth_khz: f64,
+    pub duration_ms: f64,
+    /// None if there were too few calls to measure an interval.
+    pub interval_ms: Option<f64>,
+    /// Number of calls with measurable parameters.
+    pub call_count: usize,
+}
+
+/// Summarise a set of measured calls. None if no call could be measured.
+pub fn summarize(calls: &[CallMeasurement]) -> Option<CallSummary> {
+    let measured: Vec<_> = calls.iter().filter_map(|c| c.params.as_ref().map(|p| (c, p))).collect();
+    if measured.is_empty() {
+        return None;
+    }
+    let char_freq: Vec<f64> = measured.iter().map(|(_, p)| p.char_freq_hz / 1000.0).collect();
+    let bandwidth: Vec<f64> = measured.iter().map(|(_, p)| p.bandwidth_hz / 1000.0).collect();
+    let duration: Vec<f64> = measured.iter().map(|(c, _)| c.pulse.duration_ms()).collect();
+    let intervals: Vec<f64> = calls.iter().filter_map(|c| c.interval_ms).collect();
+    Some(CallSummary {
+        char_freq_khz: median(char_freq)?,
+        bandwidth_khz: median(bandwidth)?,
+        duration_ms: median(duration)?,
+        interval_ms: median(intervals),
+        call_count: measured.len(),
+    })
+}
+
+fn median(mut v: Vec<f64>) -> Option<f64> {
+    if v.is_empty() {
+        return None;
+    }
+    v.sort_by(f64::total_cmp);
+    let mid = v.len() / 2;
+    Some(if v.len().is_multiple_of(2) { (v[mid - 1] + v[mid]) / 2.0 } else { v[mid] })
+}
+
+/// How one measured parameter compares with a species' range.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CriterionMatch {
+    pub label: &'static str,
+    pub unit: &'static str,
+    pub measured: f64,
+    pub range: [f64; 2],
+    pub matched: bool,
+}
+
+impl CriterionMatch {
+    /// e.g. "Fc 45.2 kHz (42–51)".
+    pub fn describe(&self) -> String {
+        format!(
+            "{} {:.1} {} ({}\u{2013}{})",
+            self.label, self.measured, self.unit, self.range[0], self.range[1],
+        )
+    }
+}
+
+/// A candidate species with its confidence and per-criterion results.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SpeciesCandidate {
+    pub name: String,
+    pub scientific_name: Option<String>,
+    /// 0–1: weighted share of criteria that fall inside the species' ranges,
+    /// with partial credit for values just outside.
+    pub confidence: f64,
+    pub criteria: Vec<CriterionMatch>,
+}
+
+impl SpeciesCandidate {
+    pub fn confidence_label(&self) -> &'static str {
+        if self.confidence >= 0.8 {
+            "High"
+        } else if self.confidence >= 0.6 {
+            "Medium"
+        } else {
+            "Low"
+        }
+    }
+}
+
+/// Score `summary` against every species in `table`, best match first.
+///
+/// A species is only considered if the characteristic frequency falls inside
+/// its range, since that is the most diagnostic parameter.
+pub fn match_species(summary: &CallSummary, table: &SpeciesTable) -> Vec<SpeciesCandidate> {
+    let mut out: Vec<SpeciesCandidate> = table.species.iter().filter_map(|rule| {
+        let fc = criterion("Fc", "kHz", summary.char_freq_khz, rule.char_freq_khz);
+        if !fc.matched {
+            return None;
+        }
+        let mut criteria = vec![fc];
+        if let Some(r) = rule.bandwidth_khz {
+            criteria.push(criterion("Bandwidth", "kHz", summary.bandwidth_khz, r));
+        }
+        if let Some(r) = rule.duration_ms {
+            criteria.push(criterion("Duration", "ms", summary.duration_ms, r));
+        }
+        if let (Some(r), Some(iv)) = (rule.interval_ms, summary.interval_ms) {
+            criteria.push(criterion("Interval", "ms", iv, r));
+        }
+
+        let (mut score, mut weight) = (0.0, 0.0);
+        for (i, c) in criteria.iter().enumerate() {
+            let w = if i == 0 { CHAR_FREQ_WEIGHT } else { 1.0 };
+            score += w * range_score(c.measured, c.range);
+            weight += w;
+        }
+        let confidence = score / weight;
+        (confidence >= MIN_CONFIDENCE).then(|| SpeciesCandidate {
+            name: rule.name.clone(),
+            scientific_name: rule.scientific_name.clone(),
+            confidence,
+            criteria,
+        })
+    }).collect();
+    out.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+    out
+}
+
+/// Species whose characteristic frequency range lies within `tolerance_khz`
+/// of `char_freq_khz`, nearest first (ranges containing it come first, then
+/// by distance from the middle of the range). Used to pick reference
+/// recordings to compare against when the other parameters aren't trusted.
+pub fn species_near_char_freq(table: &SpeciesTable, char_freq_khz: f64, tolerance_khz: f64) -> Vec<&SpeciesRule> {
+    let distance = |[lo, hi]: [f64; 2]| (lo - char_freq_khz).max(char_freq_khz - hi).max(0.0);
+    let mut out: Vec<&SpeciesRule> = table.species.iter()
+        .filter(|rule| distance(rule.char_freq_khz) <= tolerance_khz)
+        .collect();
+    let key = |rule: &SpeciesRule| {
+        let [lo, hi] = rule.char_freq_khz;
+        (distance(rule.char_freq_khz), ((lo + hi) / 2.0 - char_freq_khz).abs())
+    };
+    out.sort_by(|a, b| {
+        let (ka, kb) = (key(a), key(b));
+        ka.0.total_cmp(&kb.0).then(ka.1.total_cmp(&kb.1))
+    });
+    out
+}
+
+fn criterion(label: &'static str, unit: &'static str, measured: f64, range: [f64; 2]) -> CriterionMatch {
+    CriterionMatch { label, unit, measured, range, matched: (range[0]..=range[1]).contains(&measured) }
+}
+
+/// 1 inside the range, falling linearly to 0 at one range-width outside it.
+fn range_score(value: f64, [lo, hi]: [f64; 2]) -> f64 {
+    let dist = if value < lo { lo - value } else if value > hi { value - hi } else { return 1.0 };
+    let width = (hi - lo).max(1.0);
+    (1.0 - dist / width).max(0.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(fc: f64, bw: f64, dur: f64, iv: Option<f64>) -> CallSummary {
+        CallSummary { char_freq_khz: fc, bandwidth_khz: bw, duration_ms: dur, interval_ms: iv, call_count: 10 }
+    }
+
+    #[test]
+    fn test_builtin_table_parses() {
+        let t = SpeciesTable::builtin();
+        assert!(!t.species.is_empty());
+        assert!(t.species.iter().all(|s| s.char_freq_khz[0] <= s.char_freq_khz[1]));
+    }
+
+    #[test]
+    fn test_table_validation() {
+        assert!(SpeciesTable::from_json("{\"species\": []}").is_err());
+        assert!(SpeciesTable::from_json("not json").is_err());
+        let inverted = r#"{"species": [{"name": "x", "char_freq_khz": [50, 40]}]}"#;
+        assert!(SpeciesTable::from_json(inverted).is_err());
+        let minimal = r#"{"species": [{"name": "x", "char_freq_khz": [40, 50]}]}"#;
+        let t = SpeciesTable::from_json(minimal).unwrap();
+        assert_eq!(t.region, "");
+        assert_eq!(t.species[0].bandwidth_khz, None);
+    }
+
+    #[test]
+    fn test_match_pipistrelle() {
+        let t = SpeciesTable::builtin();
+        let c = match_species(&summary(46.0, 20.0, 5.0, Some(90.0)), &t);
+        assert_eq!(c[0].name, "Common pipistrelle");
+        assert_eq!(c[0].confidence, 1.0);
+        assert_eq!(c[0].confidence_label(), "High");
+        assert!(c[0].criteria.iter().all(|m| m.matched));
+        // Species whose Fc range excludes 46 kHz are never suggested
+        assert!(c.iter().all(|s| s.name != "Soprano pipistrelle"));
+    }
+
+    #[test]
+    fn test_partial_match_and_ordering() {
+        let t = SpeciesTable::builtin();
+        // Horseshoe-like Fc but an FM-style short call
+        let c = match_species(&summary(81.0, 40.0, 4.0, None), &t);
+        let horseshoe = c.iter().find(|s| s.name == "Greater horseshoe bat");
+        assert!(horseshoe.is_none_or(|s| s.confidence < 0.8));
+        // Criteria without a measurement are skipped, not failed
+        let c = match_species(&summary(46.0, 20.0, 5.0, None), &t);
+        assert_eq!(c[0].criteria.len(), 3);
+        assert!(c.windows(2).all(|w| w[0].confidence >= w[1].confidence));
+    }
+
+    #[test]
+    fn test_species_near_char_freq() {
+        let t = SpeciesTable::builtin();
+        let near: Vec<&str> = species_near_char_freq(&t, 46.0, 0.0).iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(near[0], "Common pipistrelle");
+        assert!(near.contains(&"Daubenton's bat"));
+        assert!(!near.contains(&"Soprano pipistrelle"));
+        // Tolerance pulls in neighbouring ranges, after the containing ones
+        let wide = species_near_char_freq(&t, 46.0, 6.0);
+        let soprano = wide.iter().position(|s| s.name == "Soprano pipistrelle").unwrap();
+        assert!(soprano >= near.len());
+        assert!(species_near_char_freq(&t, 150.0, 5.0).is_empty());
+    }
+
+    #[test]
+    fn test_range_score() {
+        assert_eq!(range_score(45.0, [40.0, 50.0]), 1.0);
+        assert!((range_score(55.0, [40.0, 50.0]) - 0.5).abs() < 1e-9);
+        assert_eq!(range_score(70.0, [40.0, 50.0]), 0.0);
+    }
+
+    #[test]
+    fn test_median() {
+        assert_eq!(median(vec![]), None);
+        assert_eq!(median(vec![3.0, 1.0, 2.0]), Some(2.0));
+        assert_eq!(median(vec![4.0, 1.0, 2.0, 3.0]), Some(2.5));
+    }
+}
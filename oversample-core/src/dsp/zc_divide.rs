@@ -165,6 +165,80 @@ pub fn zc_rate_per_bin(
     bins
 }
 
+/// Anabat-style zero-crossing dots: one (time, frequency) point for every
+/// `division` cycles of the signal while the Schmitt trigger is armed.
+///
+/// Unlike `zc_rate_per_bin`, each dot's frequency comes from the interval
+/// between two interpolated positive-going crossings, so a sweep shows up
+/// as a continuous trail rather than a staircase of bin averages.
+/// `division` = 1 gives one dot per cycle; 8 matches Anabat's usual ratio.
+/// Counting restarts whenever the trigger disarms, so no dot spans a gap.
+pub fn zc_dots(
+    samples: &[f32],
+    sample_rate: u32,
+    division: usize,
+    skip_bandpass: bool,
+) -> Vec<(f64, f64)> {
+    if samples.len() < 2 {
+        return Vec::new();
+    }
+
+    let division = division.max(1);
+    let filtered = if skip_bandpass {
+        samples.to_vec()
+    } else {
+        bandpass_ultrasonic(samples, sample_rate)
+    };
+    let env_samples = ((sample_rate as f64 * 0.001) as usize).max(1);
+    let envelope = smooth_envelope(&filtered, env_samples);
+    let (threshold_high, threshold_low) = adaptive_threshold(&filtered);
+    let sr = sample_rate as f64;
+
+    let mut dots = Vec::new();
+    let mut armed = false;
+    // Time of the crossing that opens the current group of cycles
+    let mut group_start: Option<f64> = None;
+    let mut cycles = 0usize;
+
+    for i in 1..filtered.len() {
+        let env = envelope[i];
+        if env > threshold_high {
+            armed = true;
+        } else if env < threshold_low {
+            armed = false;
+            group_start = None;
+        }
+        if !armed {
+            continue;
+        }
+
+        let (prev, curr) = (filtered[i - 1], filtered[i]);
+        if prev < 0.0 && curr >= 0.0 {
+            // Linear interpolation between the two samples for sub-sample timing
+            let frac = (-prev / (curr - prev)) as f64;
+            let t = ((i - 1) as f64 + frac) / sr;
+            match group_start {
+                None => {
+                    group_start = Some(t);
+                    cycles = 0;
+                }
+                Some(t0) => {
+                    cycles += 1;
+                    if cycles == division {
+                        if t > t0 {
+                            dots.push((t, division as f64 / (t - t0)));
+                        }
+                        group_start = Some(t);
+                        cycles = 0;
+                    }
+                }
+            }
+        }
+    }
+
+    dots
+}
+
 pub(crate) fn cascaded_lp(samples: &[f32], cutoff: f64, sample_rate: u32, passes: usize) -> Vec<f32> {
     let mut result = samples.to_vec();
     for _ in 0..passes {
@@ -272,4 +346,29 @@ mod tests {
             assert!(error < 0.15, "Rate should be ~45kHz, got {rate:.0}");
         }
     }
+
+    #[test]
+    fn test_zc_dots_track_frequency() {
+        let sr = 192_000;
+        let mut input = vec![0.0f32; 1920];
+        input.extend(make_sine(45_000.0, sr, 0.01).iter().map(|s| s * 0.5));
+        input.extend(vec![0.0f32; 1920]);
+
+        let every_cycle = zc_dots(&input, sr, 1, false);
+        let divided = zc_dots(&input, sr, 8, false);
+        // ~450 cycles in the burst; division thins the dots accordingly
+        assert!(every_cycle.len() > 300, "got {} dots", every_cycle.len());
+        assert!(divided.len() * 8 <= every_cycle.len() + 8);
+        for &(t, f) in &divided {
+            assert!((0.01..=0.021).contains(&t), "dot outside the burst at {t}");
+            let error = (f - 45_000.0).abs() / 45_000.0;
+            assert!(error < 0.02, "Dot should be ~45kHz, got {f:.0}");
+        }
+    }
+
+    #[test]
+    fn test_zc_dots_silence() {
+        assert!(zc_dots(&[0.0; 19200], 192_000, 8, false).is_empty());
+        assert!(zc_dots(&[], 192_000, 8, false).is_empty());
+    }
 }
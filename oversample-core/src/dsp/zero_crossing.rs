@@ -43,11 +43,123 @@ pub fn zero_crossing_frequency(samples: &[f32], sample_rate: u32) -> ZeroCrossin
     }
 }
 
+/// Default window length for [`zero_crossing_track`].
+pub const DEFAULT_TRACK_WINDOW_MS: f64 = 1.0;
+
+/// One window of a zero-crossing frequency track.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ZcTrackPoint {
+    /// Start of the window in seconds.
+    pub time_secs: f64,
+    pub estimated_frequency_hz: f64,
+    pub crossing_count: usize,
+}
+
+/// Window length in samples used by [`zero_crossing_track`].
+pub fn track_window_samples(window_secs: f64, sample_rate: u32) -> usize {
+    (window_secs * sample_rate as f64).round().max(2.0) as usize
+}
+
+/// Zero-crossing frequency over consecutive `window_secs` windows: a
+/// frequency-vs-time track, complementary to the FFT dominant-frequency contour.
+///
+/// Each window also sees the first sample of the next, so a crossing on a
+/// window boundary is counted exactly once. Times are offset by `start_time`
+/// (e.g. the start of the selection `samples` came from). A trailing window
+/// shorter than half the length is dropped as too short to estimate.
+///
+/// Callers reading a file in blocks get the same track by starting blocks on
+/// multiples of [`track_window_samples`] and reading one sample past each.
+pub fn zero_crossing_track(
+    samples: &[f32],
+    sample_rate: u32,
+    window_secs: f64,
+    start_time: f64,
+) -> Vec<ZcTrackPoint> {
+    let window = track_window_samples(window_secs, sample_rate);
+    let mut points = Vec::with_capacity(samples.len() / window + 1);
+    let mut start = 0;
+    while start + window / 2 < samples.len() {
+        let end = (start + window + 1).min(samples.len());
+        let zc = zero_crossing_frequency(&samples[start..end], sample_rate);
+        points.push(ZcTrackPoint {
+            time_secs: start_time + start as f64 / sample_rate as f64,
+            estimated_frequency_hz: zc.estimated_frequency_hz,
+            crossing_count: zc.crossing_count,
+        });
+        start += window;
+    }
+    points
+}
+
+/// Column header for [`track_csv_rows`].
+pub const TRACK_CSV_HEADER: &str = "time_s,estimated_frequency_hz,crossing_count\n";
+
+/// CSV rows, one per window of a [`zero_crossing_track`].
+pub fn track_csv_rows(points: &[ZcTrackPoint]) -> String {
+    let mut out = String::new();
+    for p in points {
+        out.push_str(&format!("{:.6},{:.1},{}\n", p.time_secs, p.estimated_frequency_hz, p.crossing_count));
+    }
+    out
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_signals::tone_secs;
     use std::f64::consts::PI;
 
+    fn sine(freq: f64, sample_rate: u32, secs: f64) -> Vec<f32> {
+        tone_secs(freq, sample_rate, secs, 1.0)
+    }
+
+    #[test]
+    fn test_track_follows_frequency_step() {
+        let sr = 192_000;
+        let mut samples = sine(30_000.0, sr, 0.01);
+        samples.extend(sine(60_000.0, sr, 0.01));
+        let track = zero_crossing_track(&samples, sr, 0.001, 2.0);
+        assert_eq!(track.len(), 20);
+        assert!((track[0].time_secs - 2.0).abs() < 1e-9);
+        assert!((track[1].time_secs - 2.001).abs() < 1e-9);
+        assert!((track[3].estimated_frequency_hz - 30_000.0).abs() < 1_500.0, "{:?}", track[3]);
+        assert!((track[15].estimated_frequency_hz - 60_000.0).abs() < 1_500.0, "{:?}", track[15]);
+
+        let csv = track_csv_rows(&track[..2]);
+        assert_eq!(csv.lines().count(), 2);
+        assert!(csv.starts_with("2.000000,"));
+        assert_eq!(TRACK_CSV_HEADER.split(',').count(), 3);
+    }
+
+    #[test]
+    fn test_track_counts_every_crossing_once() {
+        let sr = 48_000;
+        let samples = sine(1_000.0, sr, 0.1);
+        let whole = zero_crossing_frequency(&samples, sr).crossing_count;
+        let track = zero_crossing_track(&samples, sr, 0.005, 0.0);
+        let summed: usize = track.iter().map(|p| p.crossing_count).sum();
+        assert_eq!(summed, whole);
+    }
+
+    #[test]
+    fn test_track_in_blocks_matches_whole() {
+        let sr = 48_000;
+        let samples = sine(1_000.0, sr, 0.1);
+        let whole = zero_crossing_track(&samples, sr, 0.0015, 0.0);
+        let block = track_window_samples(0.0015, sr) * 7;
+        let mut blocks = Vec::new();
+        for start in (0..samples.len()).step_by(block) {
+            let end = (start + block + 1).min(samples.len());
+            blocks.extend(zero_crossing_track(&samples[start..end], sr, 0.0015, start as f64 / sr as f64));
+        }
+        assert_eq!(blocks.len(), whole.len());
+        for (a, b) in blocks.iter().zip(&whole) {
+            assert_eq!(a.crossing_count, b.crossing_count);
+            assert!((a.time_secs - b.time_secs).abs() < 1e-9);
+        }
+    }
+
     #[test]
     fn test_known_sine_wave() {
         let sample_rate = 192_000u32;
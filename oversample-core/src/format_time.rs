@@ -0,0 +1,278 @@
+//! Centralized time formatting for the entire app.
+//!
+//! All time values are in seconds. Two main entry points:
+//!
+//! - **`format_time_label`** — adaptive precision for canvas timeline labels (compact)
+//! - **`format_time_display`** — fixed precision for UI text (annotations, metadata, etc.)
+
+// ── Adaptive-precision (canvas timeline) ────────────────────────────────
+
+/// Format a time value as a compact label for canvas timeline ticks.
+///
+/// Precision adapts to the tick `interval` (in seconds) so labels aren't
+/// over- or under-specified.  `use_ms` forces millisecond notation for
+/// sub-second values (should be true only when the max visible time ≤ 100 ms).
+pub fn format_time_label(seconds: f64, interval: f64, use_ms: bool) -> String {
+    let abs = seconds.abs();
+
+    if abs < 1.0 {
+        if use_ms {
+            let ms = seconds * 1000.0;
+            return if interval < 0.01 {
+                format!("{:.1}ms", ms)
+            } else {
+                format!("{:.0}ms", ms)
+            };
+        }
+        return format_seconds_adaptive(seconds, interval);
+    }
+
+    if abs < 60.0 {
+        return format_seconds_adaptive(seconds, interval);
+    }
+
+    if abs < 3600.0 {
+        return format_minutes_seconds_adaptive(seconds, interval);
+    }
+
+    format_hours_minutes_seconds_adaptive(seconds, interval)
+}
+
+/// Format a relative time offset as "+50ms", "−0.2s", etc.
+pub fn format_relative_label(offset: f64, interval: f64) -> String {
+    let abs = offset.abs();
+    if abs < 0.0005 {
+        return String::new();
+    }
+    let sign = if offset >= 0.0 { "+" } else { "\u{2212}" }; // − (Unicode minus)
+
+    if abs < 1.0 && interval < 0.1 {
+        let ms = abs * 1000.0;
+        if interval < 0.001 {
+            format!("{}{:.1}ms", sign, ms)
+        } else {
+            format!("{}{:.0}ms", sign, ms)
+        }
+    } else if interval >= 1.0 {
+        format!("{}{:.0}s", sign, abs)
+    } else if interval >= 0.1 {
+        format!("{}{:.1}s", sign, abs)
+    } else {
+        format!("{}{:.2}s", sign, abs)
+    }
+}
+
+// ── Fixed-precision (UI display) ────────────────────────────────────────
+
+/// Format a time position with fixed decimal precision for UI display.
+///
+/// Examples (precision=3): `5.250s`, `1m30.500s`, `1h05m30.000s`
+pub fn format_time_display(seconds: f64, precision: u8) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let abs = seconds.abs();
+
+    if abs < 60.0 {
+        format!("{}{:.prec$}s", sign, abs, prec = precision as usize)
+    } else if abs < 3600.0 {
+        let mins = (abs / 60.0).floor() as u32;
+        let secs = abs - mins as f64 * 60.0;
+        format!("{}{}m{:0>width$.prec$}s", sign, mins, secs,
+            width = 3 + precision as usize, // "00." = 3 chars + decimals
+            prec = precision as usize)
+    } else {
+        let hours = (abs / 3600.0).floor() as u32;
+        let rem = abs - hours as f64 * 3600.0;
+        let mins = (rem / 60.0).floor() as u32;
+        let secs = rem - mins as f64 * 60.0;
+        format!("{}{}h{:02}m{:0>width$.prec$}s", sign, hours, mins, secs,
+            width = 3 + precision as usize,
+            prec = precision as usize)
+    }
+}
+
+/// Format a duration (always positive) with fixed precision.
+///
+/// Same as `format_time_display` but takes the absolute value.
+pub fn format_duration(seconds: f64, precision: u8) -> String {
+    format_time_display(seconds.abs(), precision)
+}
+
+/// Format a duration compactly for UI lists (file lengths, gaps, etc.).
+///
+/// - Under 90s: `45.1s`
+/// - 90s–1h: `5m30s`
+/// - Over 1h: `9h44m22s`
+pub fn format_duration_compact(seconds: f64) -> String {
+    let abs = seconds.abs();
+    if abs < 90.0 {
+        format!("{abs:.1}s")
+    } else if abs < 3600.0 {
+        let mins = (abs / 60.0).floor() as u32;
+        let secs = (abs - mins as f64 * 60.0).round() as u32;
+        if secs == 60 {
+            format!("{}m00s", mins + 1)
+        } else {
+            format!("{mins}m{secs:02}s")
+        }
+    } else {
+        let hours = (abs / 3600.0).floor() as u32;
+        let rem = abs - hours as f64 * 3600.0;
+        let mins = (rem / 60.0).floor() as u32;
+        let secs = (rem - mins as f64 * 60.0).round() as u32;
+        if secs == 60 {
+            format!("{hours}h{:02}m00s", mins + 1)
+        } else {
+            format!("{hours}h{mins:02}m{secs:02}s")
+        }
+    }
+}
+
+/// Format a time range as "start–end" (en-dash separated).
+///
+/// Example: `5.000–10.500s`
+pub fn format_time_range(start: f64, end: f64, precision: u8) -> String {
+    format!("{}–{}", format_time_display(start, precision), format_time_display(end, precision))
+}
+
+// ── Parsing (user input) ────────────────────────────────────────────────
+
+/// Parse a typed time position into seconds.
+///
+/// Accepts clock form (`83.5`, `1:23.456`, `1:02:03.5`), the display form
+/// produced by `format_time_display` (`1m23.456s`, `1h02m03s`), and
+/// milliseconds (`250ms`). Returns `None` for anything else, for negatives,
+/// and for minutes or seconds of 60 or more after a larger unit (`1:75`).
+pub fn parse_time_input(text: &str) -> Option<f64> {
+    let text = text.trim();
+    if text.is_empty() {
+        return None;
+    }
+    let secs = if let Some(ms) = text.strip_suffix("ms") {
+        ms.trim().parse::<f64>().ok()? / 1000.0
+    } else if text.contains(':') {
+        let parts: Vec<&str> = text.split(':').collect();
+        if parts.len() > 3 {
+            return None;
+        }
+        let mut total = 0.0;
+        for (i, part) in parts.iter().enumerate() {
+            let value = part.trim().parse::<f64>().ok()?;
+            if value.is_sign_negative() || (i > 0 && value >= 60.0) {
+                return None;
+            }
+            total = total * 60.0 + value;
+        }
+        total
+    } else if text.ends_with(['h', 'm', 's']) {
+        let mut total = 0.0;
+        let mut num = String::new();
+        let mut prev_scale = f64::INFINITY;
+        for c in text.chars() {
+            let scale = match c {
+                'h' => 3600.0,
+                'm' => 60.0,
+                's' => 1.0,
+                c if c.is_ascii_digit() || c == '.' => { num.push(c); continue; }
+                c if c.is_whitespace() => continue,
+                _ => return None,
+            };
+            // Units run largest first; those after the first stay under 60
+            let value = num.parse::<f64>().ok()?;
+            if scale >= prev_scale || (prev_scale.is_finite() && value >= 60.0) {
+                return None;
+            }
+            total += value * scale;
+            prev_scale = scale;
+            num.clear();
+        }
+        total
+    } else {
+        text.parse::<f64>().ok()?
+    };
+    (secs.is_finite() && secs >= 0.0).then_some(secs)
+}
+
+// ── Private adaptive helpers ────────────────────────────────────────────
+
+fn format_seconds_adaptive(seconds: f64, interval: f64) -> String {
+    if interval >= 1.0 {
+        format!("{:.0}s", seconds)
+    } else if interval >= 0.1 {
+        // Drop ".0" when seconds are whole
+        let rounded = (seconds * 10.0).round() / 10.0;
+        if (rounded - rounded.round()).abs() < 0.01 {
+            format!("{:.0}s", rounded)
+        } else {
+            format!("{:.1}s", seconds)
+        }
+    } else if interval >= 0.01 {
+        format!("{:.2}s", seconds)
+    } else {
+        format!("{:.3}s", seconds)
+    }
+}
+
+fn format_minutes_seconds_adaptive(seconds: f64, interval: f64) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let abs = seconds.abs();
+    let mins = (abs / 60.0).floor() as u32;
+    let secs = abs - mins as f64 * 60.0;
+    if interval >= 1.0 {
+        format!("{}{}m{:02.0}s", sign, mins, secs)
+    } else if interval >= 0.1 {
+        // Drop ".0" when seconds are whole
+        let rounded = (secs * 10.0).round() / 10.0;
+        if (rounded - rounded.round()).abs() < 0.01 {
+            format!("{}{}m{:02.0}s", sign, mins, rounded)
+        } else {
+            format!("{}{}m{:04.1}s", sign, mins, secs)
+        }
+    } else {
+        format!("{}{}m{:06.3}s", sign, mins, secs)
+    }
+}
+
+fn format_hours_minutes_seconds_adaptive(seconds: f64, interval: f64) -> String {
+    let sign = if seconds < 0.0 { "-" } else { "" };
+    let abs = seconds.abs();
+    let hours = (abs / 3600.0).floor() as u32;
+    let rem = abs - hours as f64 * 3600.0;
+    let mins = (rem / 60.0).floor() as u32;
+    let secs = rem - mins as f64 * 60.0;
+    if interval >= 1.0 {
+        format!("{}{}h{:02}m{:02.0}s", sign, hours, mins, secs)
+    } else {
+        format!("{}{}h{:02}m{:04.1}s", sign, hours, mins, secs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_input_forms() {
+        assert_eq!(parse_time_input("83.5"), Some(83.5));
+        assert_eq!(parse_time_input(" 1:23.5 "), Some(83.5));
+        assert_eq!(parse_time_input("1:02:03.5"), Some(3723.5));
+        assert_eq!(parse_time_input("90:00"), Some(5400.0));
+        assert_eq!(parse_time_input("250ms"), Some(0.25));
+        assert_eq!(parse_time_input("1m23.5s"), Some(83.5));
+        assert_eq!(parse_time_input("1h02m03s"), Some(3723.0));
+        assert_eq!(parse_time_input("45s"), Some(45.0));
+    }
+
+    #[test]
+    fn test_parse_time_input_round_trips_display_form() {
+        for secs in [5.25, 90.5, 3723.125] {
+            assert_eq!(parse_time_input(&format_time_display(secs, 3)), Some(secs));
+        }
+    }
+
+    #[test]
+    fn test_parse_time_input_rejects_bad_components() {
+        for text in ["", "abc", "-5", "1:75", "1:-5", "-1:05", "1:60", "1:2:3:4", "1::5", "1m75s", "5s1m", "1h1h", "-250ms"] {
+            assert_eq!(parse_time_input(text), None, "{text:?}");
+        }
+    }
+}
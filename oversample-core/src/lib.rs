@@ -1,4 +1,46 @@
+//! DSP, audio decoding and spectrogram rendering for bat call analysis.
+//!
+//! This crate has no UI or platform dependencies, so the analyses behind the
+//! Oversample app can be scripted from other Rust tools:
+//!
+//! - [`audio::loader::load_audio`] decodes WAV, FLAC, MP3, OGG and M4A bytes
+//!   into [`types::AudioData`]; [`types::AudioData::from_mono`] wraps samples
+//!   you already have.
+//! - [`dsp::fft::compute_spectrogram`] computes an STFT magnitude spectrogram.
+//! - [`dsp::zero_crossing`] and [`dsp::zc_divide`] estimate frequency and do
+//!   frequency division by zero crossings.
+//! - [`dsp::wsnr::analyze_wsnr`] grades recording quality by weighted SNR.
+//! - [`dsp::harmonics::analyze_harmonics`] measures phase coherence and
+//!   harmonic decay, and flags signs of processing.
+//! - [`dsp::bit_analysis::analyze_bits`] checks the bit depth actually used.
+//! - [`dsp::heterodyne`], [`dsp::pitch_shift`] and [`dsp::filters`] provide
+//!   the playback transforms and filters.
+//!
+//! ```
+//! use oversample_core::dsp::{fft, wsnr, zero_crossing};
+//! use oversample_core::types::AudioData;
+//!
+//! let sr = 192_000;
+//! let tone: Vec<f32> = (0..sr / 10)
+//!     .map(|i| 0.5 * (2.0 * std::f32::consts::PI * 40_000.0 * i as f32 / sr as f32).sin())
+//!     .collect();
+//!
+//! let zc = zero_crossing::zero_crossing_frequency(&tone, sr);
+//! assert!((zc.estimated_frequency_hz - 40_000.0).abs() < 500.0);
+//!
+//! let audio = AudioData::from_mono(tone, sr);
+//! let spec = fft::compute_spectrogram(&audio, 1024, 256);
+//! assert!(!spec.columns.is_empty());
+//!
+//! let quality = wsnr::analyze_wsnr(&audio.samples, sr);
+//! println!("wSNR grade {:?}", quality.grade);
+//! ```
+
 pub mod types;
 pub mod dsp;
 pub mod audio;
 pub mod canvas;
+pub mod format_time;
+
+#[cfg(test)]
+mod test_signals;
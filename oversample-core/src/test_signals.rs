@@ -0,0 +1,77 @@
+//! Synthetic signals shared by the unit tests and `tests/dsp_api.rs`.
+//!
+//! Noise comes from a fixed-seed LCG so every run sees the same samples.
+
+#![allow(dead_code)]
+
+use std::f64::consts::PI;
+
+/// Number of samples in `secs` at `sample_rate`.
+pub fn len_of(sample_rate: u32, secs: f64) -> usize {
+    (sample_rate as f64 * secs) as usize
+}
+
+/// `len` samples of a sine at `freq` Hz and peak amplitude `amp`.
+pub fn tone(freq: f64, sample_rate: u32, len: usize, amp: f32) -> Vec<f32> {
+    (0..len)
+        .map(|i| amp * (2.0 * PI * freq * i as f64 / sample_rate as f64).sin() as f32)
+        .collect()
+}
+
+/// [`tone`] lasting `secs`.
+pub fn tone_secs(freq: f64, sample_rate: u32, secs: f64, amp: f32) -> Vec<f32> {
+    tone(freq, sample_rate, len_of(sample_rate, secs), amp)
+}
+
+/// Uniform noise in `-amp / 2..amp / 2`.
+pub fn noise(len: usize, amp: f32, seed: u32) -> Vec<f32> {
+    let mut seed = seed;
+    (0..len)
+        .map(|_| {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            amp * ((seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5)
+        })
+        .collect()
+}
+
+/// Uniform noise with the given RMS level.
+pub fn noise_rms(len: usize, rms: f32, seed: u32) -> Vec<f32> {
+    noise(len, rms * 12f32.sqrt(), seed)
+}
+
+/// `secs` of [`noise`] at `noise_amp`, with a `freq` Hz tone of peak
+/// `amp` switched on over `on.0..on.1` seconds.
+pub fn burst_over_noise(sample_rate: u32, secs: f64, freq: f64, on: (f64, f64), amp: f32, noise_amp: f32, seed: u32) -> Vec<f32> {
+    let mut samples = noise(len_of(sample_rate, secs), noise_amp, seed);
+    let sr = sample_rate as f64;
+    for (i, s) in samples.iter_mut().enumerate() {
+        let t = i as f64 / sr;
+        if (on.0..on.1).contains(&t) {
+            *s += amp * (2.0 * PI * freq * t).sin() as f32;
+        }
+    }
+    samples
+}
+
+/// Sample rate of [`night`].
+pub const NIGHT_RATE: u32 = 192_000;
+
+/// Frequency of the call in [`night`].
+pub const NIGHT_CALL_HZ: f64 = 45_000.0;
+
+/// `secs` of faint noise at [`NIGHT_RATE`] with a half-scale
+/// [`NIGHT_CALL_HZ`] tone over `call.0..call.1` seconds.
+pub fn night(secs: f64, call: (f64, f64)) -> Vec<f32> {
+    burst_over_noise(NIGHT_RATE, secs, NIGHT_CALL_HZ, call, 0.5, 0.001, 3)
+}
+
+/// Add `signal` into `samples` starting at sample `at`.
+pub fn mix_at(samples: &mut [f32], signal: &[f32], at: usize) {
+    for (s, x) in samples[at..].iter_mut().zip(signal) {
+        *s += x;
+    }
+}
+
+pub fn rms(samples: &[f32]) -> f32 {
+    (samples.iter().map(|&x| x as f64 * x as f64).sum::<f64>() / samples.len().max(1) as f64).sqrt() as f32
+}
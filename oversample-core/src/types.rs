@@ -1,5 +1,6 @@
 use crate::audio::guano::GuanoMetadata;
 use crate::audio::source::AudioSource;
+use realfft::num_complex::Complex;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -15,10 +16,59 @@ pub struct FileMetadata {
     pub data_size: Option<u64>,
 }
 
+/// Shared mono samples: all of a buffer, or a window onto one. Cloning and
+/// windowing never copy the samples. Derefs to `[f32]`.
+#[derive(Clone, Default)]
+pub struct Samples {
+    buf: Arc<Vec<f32>>,
+    start: usize,
+    end: usize,
+}
+
+impl Samples {
+    /// Samples `range` of this view, clamped to its length.
+    pub fn slice(&self, range: std::ops::Range<usize>) -> Self {
+        let end = range.end.min(self.len());
+        let start = range.start.min(end);
+        Samples { buf: self.buf.clone(), start: self.start + start, end: self.start + end }
+    }
+
+    pub fn as_slice(&self) -> &[f32] {
+        &self.buf[self.start..self.end]
+    }
+}
+
+impl std::ops::Deref for Samples {
+    type Target = [f32];
+
+    fn deref(&self) -> &[f32] {
+        self.as_slice()
+    }
+}
+
+impl From<Arc<Vec<f32>>> for Samples {
+    fn from(buf: Arc<Vec<f32>>) -> Self {
+        let end = buf.len();
+        Samples { buf, start: 0, end }
+    }
+}
+
+impl From<Vec<f32>> for Samples {
+    fn from(buf: Vec<f32>) -> Self {
+        Arc::new(buf).into()
+    }
+}
+
+impl std::fmt::Debug for Samples {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Samples").field("len", &self.len()).finish()
+    }
+}
+
 #[derive(Clone)]
 pub struct AudioData {
     /// Mono-mixed samples. Kept during migration; new code should use `source`.
-    pub samples: Arc<Vec<f32>>,
+    pub samples: Samples,
     /// AudioSource abstraction for on-demand sample access.
     pub source: Arc<dyn AudioSource>,
     pub sample_rate: u32,
@@ -28,6 +78,72 @@ pub struct AudioData {
     pub metadata: FileMetadata,
 }
 
+impl AudioData {
+    /// Wrap mono samples already in memory, e.g. for analysing audio that
+    /// didn't come from the loader.
+    pub fn from_mono(samples: Vec<f32>, sample_rate: u32) -> Self {
+        let samples = Arc::new(samples);
+        AudioData {
+            duration_secs: samples.len() as f64 / sample_rate.max(1) as f64,
+            source: Arc::new(crate::audio::source::InMemorySource {
+                samples: samples.clone(),
+                raw_samples: None,
+                exact: None,
+                sample_rate,
+                channels: 1,
+            }),
+            samples: samples.into(),
+            sample_rate,
+            channels: 1,
+            metadata: FileMetadata {
+                file_size: 0,
+                format: "",
+                bits_per_sample: 32,
+                is_float: true,
+                guano: None,
+                data_offset: None,
+                data_size: None,
+            },
+        }
+    }
+
+    /// View keeping only samples `start..end`, without copying them. The new
+    /// source is a [`TrimmedSource`](crate::audio::source::TrimmedSource) over
+    /// this one, so the untrimmed audio stays reachable for export.
+    pub fn trimmed(&self, start: usize, end: usize) -> Self {
+        let end = end.min(self.samples.len());
+        let start = start.min(end);
+        let samples = self.samples.slice(start..end);
+        AudioData {
+            duration_secs: samples.len() as f64 / self.sample_rate.max(1) as f64,
+            source: Arc::new(crate::audio::source::TrimmedSource::new(
+                self.source.clone(),
+                start as u64,
+                (end - start) as u64,
+            )),
+            samples,
+            sample_rate: self.sample_rate,
+            channels: self.channels,
+            metadata: self.metadata.clone(),
+        }
+    }
+
+    fn trim(&self) -> Option<&crate::audio::source::TrimmedSource> {
+        self.source.as_any().downcast_ref()
+    }
+
+    /// Seconds trimmed off the start on import, 0 if untrimmed. Add it to a
+    /// time in this audio to get the time in the original file.
+    pub fn trim_offset_secs(&self) -> f64 {
+        self.trim().map_or(0.0, |t| t.start_frame() as f64 / self.sample_rate.max(1) as f64)
+    }
+
+    /// The source as loaded, before any import trim.
+    pub fn untrimmed_source(&self) -> &Arc<dyn crate::audio::source::AudioSource> {
+        self.trim().map_or(&self.source, |t| t.original())
+    }
+}
+
 impl std::fmt::Debug for AudioData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AudioData")
@@ -46,6 +162,13 @@ pub struct SpectrogramColumn {
     pub time_offset: f64,
 }
 
+impl SpectrogramColumn {
+    /// Total power in the column: the sum of squared bin magnitudes.
+    pub fn energy(&self) -> f32 {
+        self.magnitudes.iter().map(|m| m * m).sum()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SpectrogramData {
     pub columns: Arc<Vec<SpectrogramColumn>>,
@@ -59,6 +182,67 @@ pub struct SpectrogramData {
     pub sample_rate: u32,
 }
 
+impl SpectrogramData {
+    /// FFT size the columns were computed with.
+    pub fn fft_size(&self) -> usize {
+        if self.freq_resolution > 0.0 {
+            (self.sample_rate as f64 / self.freq_resolution).round() as usize
+        } else {
+            0
+        }
+    }
+
+    /// STFT (column, bin) nearest to a time (seconds) and frequency (Hz).
+    /// Column indices follow `time_offset = column * time_resolution`.
+    pub fn column_bin_at(&self, time: f64, freq: f64) -> Option<(usize, usize)> {
+        if self.time_resolution <= 0.0 || self.freq_resolution <= 0.0 || time < 0.0 || freq < 0.0 {
+            return None;
+        }
+        let column = (time / self.time_resolution).floor() as usize;
+        let bin = (freq / self.freq_resolution).round() as usize;
+        (column < self.total_columns.max(self.columns.len())).then_some((column, bin))
+    }
+
+    /// Raw STFT magnitude at a time/frequency, if that column is held in `columns`.
+    /// Large files keep columns in the spectral store instead; callers fall back to it.
+    pub fn magnitude_at(&self, time: f64, freq: f64) -> Option<f32> {
+        let (column, bin) = self.column_bin_at(time, freq)?;
+        self.columns.get(column)?.magnitudes.get(bin).copied()
+    }
+}
+
+/// Complex STFT coefficients for phase-based analysis.
+///
+/// Kept in a structure parallel to [`SpectrogramData`] rather than inside it,
+/// so the normal rendering path stays magnitude-only. Produced on request by
+/// `dsp::fft::compute_spectrogram_complex`; frame `i` matches column `i` of
+/// the magnitude spectrogram computed with the same FFT/hop sizes.
+#[derive(Clone, Debug)]
+pub struct ComplexSpectrogram {
+    /// One frame per STFT column, `fft_size / 2 + 1` bins each.
+    pub frames: Arc<Vec<Vec<Complex<f32>>>>,
+    pub fft_size: usize,
+    pub hop_size: usize,
+    pub sample_rate: u32,
+}
+
+impl ComplexSpectrogram {
+    /// Phase in radians (−π..π) at `(column, bin)`, if in range.
+    pub fn phase(&self, column: usize, bin: usize) -> Option<f32> {
+        self.frames.get(column)?.get(bin).map(|c| c.arg())
+    }
+
+    /// Magnitude at `(column, bin)`, if in range.
+    pub fn magnitude(&self, column: usize, bin: usize) -> Option<f32> {
+        self.frames.get(column)?.get(bin).map(|c| c.norm())
+    }
+
+    /// Approximate heap footprint in bytes.
+    pub fn byte_len(&self) -> usize {
+        self.frames.iter().map(|f| f.len() * std::mem::size_of::<Complex<f32>>()).sum()
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct PreviewImage {
     pub width: u32,
@@ -104,7 +288,7 @@ impl PreRendered {
 }
 
 /// Display settings for converting dB tile data to pixels at render time.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct SpectDisplaySettings {
     /// dB floor (e.g. -80.0).  Values below this map to black.
     pub floor_db: f32,
@@ -144,3 +328,15 @@ pub enum FlowColorScheme {
     PurpleGreen,
     Spectral,
 }
+
+/// How the flow overlay color combines with the greyscale spectrogram.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum FlowBlendMode {
+    /// Mix toward the overlay color (can darken the base).
+    #[default]
+    Normal,
+    /// Add the overlay color to the base, saturating at white; never darkens.
+    Additive,
+    /// Screen the overlay tint over the base; brightens, softening near white.
+    Screen,
+}
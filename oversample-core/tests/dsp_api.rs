@@ -0,0 +1,166 @@
+//! Exercises the public DSP surface the way an outside tool would: only
+//! through `oversample_core`'s exported paths, starting from plain samples
+//! or encoded WAV bytes.
+
+use oversample_core::audio::convert::{encode_wav, SampleFormat};
+use oversample_core::audio::loader::load_audio;
+use oversample_core::audio::source::ChannelView;
+use oversample_core::dsp::smoothing::Smoothing;
+use oversample_core::dsp::{bit_analysis, fft, filters, harmonics, heterodyne, pitch_shift, psd, wsnr, zc_divide, zero_crossing};
+use oversample_core::types::AudioData;
+
+#[path = "../src/test_signals.rs"]
+mod test_signals;
+
+use test_signals::{mix_at, noise, rms, tone_secs};
+
+const SR: u32 = 192_000;
+
+fn tone(freq: f64, secs: f64, amp: f32) -> Vec<f32> {
+    tone_secs(freq, SR, secs, amp)
+}
+
+/// Frequency of the loudest bin in the middle column.
+fn peak_freq(spec: &oversample_core::types::SpectrogramData) -> f64 {
+    let col = &spec.columns[spec.columns.len() / 2];
+    let (bin, _) = col.magnitudes.iter().enumerate()
+        .fold((0, 0.0f32), |best, (i, &m)| if m > best.1 { (i, m) } else { best });
+    bin as f64 * spec.freq_resolution
+}
+
+#[test]
+fn from_mono_wraps_samples_for_analysis() {
+    let samples = tone(45_000.0, 0.1, 0.5);
+    let audio = AudioData::from_mono(samples.clone(), SR);
+    assert_eq!(audio.channels, 1);
+    assert!((audio.duration_secs - 0.1).abs() < 1e-9);
+
+    // The source reads back the same samples, from any offset
+    let mut buf = vec![0.0; 100];
+    assert_eq!(audio.source.read_samples(ChannelView::MonoMix, 500, &mut buf), 100);
+    assert_eq!(buf, samples[500..600]);
+    assert_eq!(audio.source.total_samples(), samples.len() as u64);
+
+    let spec = fft::compute_spectrogram(&audio, 1024, 256);
+    assert_eq!(spec.total_columns, spec.columns.len());
+    assert!((peak_freq(&spec) - 45_000.0).abs() <= spec.freq_resolution);
+}
+
+#[test]
+fn spectrogram_from_loaded_wav() {
+    let wav = encode_wav(&tone(45_000.0, 0.2, 0.5), SR, SampleFormat::Int16).unwrap();
+    let audio = load_audio(&wav).unwrap();
+    assert_eq!(audio.sample_rate, SR);
+    assert!((audio.duration_secs - 0.2).abs() < 1e-3);
+
+    let spec = fft::compute_spectrogram(&audio, 1024, 256);
+    assert_eq!(spec.sample_rate, SR);
+    assert!((spec.max_freq - SR as f64 / 2.0).abs() < 1.0);
+    assert!((peak_freq(&spec) - 45_000.0).abs() <= spec.freq_resolution);
+}
+
+#[test]
+fn zero_crossing_frequency_and_division() {
+    let samples = tone(40_000.0, 0.1, 0.5);
+    let zc = zero_crossing::zero_crossing_frequency(&samples, SR);
+    assert!((zc.estimated_frequency_hz - 40_000.0).abs() < 500.0);
+
+    // One click per 10 crossings of the 40 kHz tone: 8,000 clicks a second.
+    // The clicks are one-sided, so remove their DC before timing them
+    let divided = zc_divide::zc_divide(&samples, SR, 10, false);
+    assert_eq!(divided.len(), samples.len());
+    let settled = &divided[divided.len() / 4..];
+    let mean = settled.iter().sum::<f32>() / settled.len() as f32;
+    let centred: Vec<f32> = settled.iter().map(|s| s - mean).collect();
+    let rate = zero_crossing::zero_crossing_frequency(&centred, SR).estimated_frequency_hz;
+    assert!((rate - 8_000.0).abs() < 200.0, "click rate {rate}");
+}
+
+#[test]
+fn wsnr_grades_louder_signal_higher() {
+    // A 5 kHz call in a quiet or loud hiss floor
+    let grade = |level: f32| {
+        let mut samples = noise(SR as usize, level, 1);
+        mix_at(&mut samples, &tone(5_000.0, 0.1, 0.5), SR as usize / 5);
+        wsnr::analyze_wsnr(&samples, SR)
+    };
+    let clean = grade(0.001);
+    let noisy = grade(0.2);
+    assert!(!clean.is_clipped);
+    assert!(clean.snr_db > noisy.snr_db, "{} vs {}", clean.snr_db, noisy.snr_db);
+}
+
+#[test]
+fn harmonics_on_synthetic_audio() {
+    // A 12 kHz call with harmonics falling off as 1/n
+    let mut samples = tone(12_000.0, 0.2, 0.4);
+    for n in 2..=4 {
+        mix_at(&mut samples, &tone(12_000.0 * n as f64, 0.2, 0.4 / n as f32), 0);
+    }
+    let audio = AudioData::from_mono(samples, SR);
+    let spec = fft::compute_spectrogram(&audio, 512, 128);
+    let h = harmonics::analyze_harmonics(&audio, &spec, harmonics::DEFAULT_HARMONIC_COUNT, Smoothing::OFF, harmonics::DecayFit::ALL_HARMONICS);
+    let f0 = h.fundamental_freq.expect("fundamental found");
+    assert!((f0 as f64 - 12_000.0).abs() <= spec.freq_resolution, "f0 {f0}");
+    assert!(h.decay_is_monotonic, "{:?}", h.harmonic_amplitudes);
+    assert!((h.harmonic_amplitudes[1] - 0.5).abs() < 0.1, "{:?}", h.harmonic_amplitudes);
+    assert!((0.0..=1.0).contains(&h.phase_coherence_mean));
+    assert!(!h.artifact_indicators.is_empty());
+}
+
+#[test]
+fn bit_analysis_sees_16_bit_content() {
+    let wav = encode_wav(&tone(20_000.0, 0.1, 0.5), SR, SampleFormat::Int16).unwrap();
+    let audio = load_audio(&wav).unwrap();
+    let bits = bit_analysis::analyze_bits(
+        &audio.samples,
+        audio.metadata.bits_per_sample,
+        audio.metadata.is_float,
+        audio.duration_secs,
+    );
+    assert_eq!(bits.bits_per_sample, 16);
+    assert_eq!(bits.total_samples, audio.samples.len());
+    assert!(bits.effective_bits > 8);
+}
+
+#[test]
+fn transforms_bring_ultrasound_into_hearing_range() {
+    let samples = tone(45_000.0, 0.2, 0.5);
+
+    // Heterodyne at 40 kHz leaves a 5 kHz difference tone
+    let het = heterodyne::heterodyne_mix(&samples, SR, 40_000.0, 15_000.0, heterodyne::DEFAULT_LP_ORDER);
+    assert_eq!(het.len(), samples.len());
+    let spec = fft::compute_spectrogram(&AudioData::from_mono(het, SR), 4096, 1024);
+    assert!((peak_freq(&spec) - 5_000.0).abs() <= 2.0 * spec.freq_resolution);
+
+    // Pitch shift keeps length, time expansion stretches it
+    let shifted = pitch_shift::pitch_shift_realtime(&samples, 10.0);
+    assert_eq!(shifted.len(), samples.len());
+    let stretched = pitch_shift::resample_stretch(&samples, 10.0);
+    assert_eq!(stretched.len(), samples.len() * 10);
+}
+
+#[test]
+fn lowpass_removes_ultrasound() {
+    let samples = tone(60_000.0, 0.1, 0.5);
+    let filtered = filters::cascaded_lowpass(&samples, 10_000.0, SR, 4);
+    assert!(rms(&filtered[1000..]) < 0.05 * rms(&samples));
+
+    let decimated = filters::decimate(&tone(5_000.0, 0.1, 0.5), SR, 48_000);
+    let rate = filters::decimated_rate(SR, 48_000);
+    assert_eq!(decimated.len(), (SR as usize / 10) * rate as usize / SR as usize);
+}
+
+#[test]
+fn energy_band_brackets_call_over_noise() {
+    // A 45 kHz call over faint broadband noise
+    let mut samples = noise(SR as usize / 2, 0.01, 12345);
+    mix_at(&mut samples, &tone(45_000.0, 0.5, 0.5), 0);
+    let result = psd::compute_psd(&samples, SR, 1024, None);
+    let (lo, hi) = psd::energy_band(&result, 0.9, 1_000.0).unwrap();
+    assert!(lo < 45_000.0 && hi > 45_000.0, "{lo}..{hi}");
+    assert!(hi - lo < 5_000.0, "band too wide: {lo}..{hi}");
+
+    let silence = psd::compute_psd(&vec![0.0; 4096], SR, 1024, None);
+    assert_eq!(psd::energy_band(&silence, 0.9, 1_000.0), None);
+}
@@ -0,0 +1,285 @@
+//! Fast, header-only audio metadata for folder scanning.
+//!
+//! Unlike `audio_decode::file_info` (which reads the whole file and, for
+//! OGG/MP3/M4A, fully decodes it), this only touches the container header,
+//! metadata chunks and — for OGG — the last page. Sample data is skipped
+//! with seeks, so scanning hundreds of files stays responsive.
+
+use oversample_core::audio::xc_sidecar;
+use serde::Serialize;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::path::Path;
+
+/// Wildlife Acoustics W4V format tag (see `audio_decode`).
+const W4V_FORMAT_TAG: u16 = 0x5741;
+const W4V_BLOCK_SAMPLES: u64 = 512;
+
+const WAVE_FORMAT_EXTENSIBLE: u16 = 0xFFFE;
+
+/// How far from the end of an OGG file to search for the last page.
+const OGG_TAIL_BYTES: u64 = 64 * 1024;
+
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct AudioFileMetadata {
+    pub format: String,
+    pub file_size: u64,
+    pub sample_rate: u32,
+    pub channels: u32,
+    pub bits_per_sample: u16,
+    /// None when the container doesn't record a length (e.g. VBR MP3 without a Xing header).
+    pub duration_secs: Option<f64>,
+    /// GUANO fields from a WAV `guan` chunk.
+    pub guano: Vec<(String, String)>,
+    /// Fields from a `<stem>.xc.json` sidecar (xeno-canto downloads).
+    pub xc_metadata: Vec<(String, String)>,
+    /// Species from GUANO (manual ID preferred) or the XC sidecar.
+    pub species: Option<String>,
+    /// xeno-canto quality rating (A–E) from the XC sidecar.
+    pub xc_quality: Option<String>,
+}
+
+/// Read format, duration and metadata without decoding any samples.
+pub fn quick_metadata(path: &str) -> Result<AudioFileMetadata, String> {
+    let path = Path::new(path);
+    let mut file = File::open(path).map_err(|e| format!("Failed to open file: {e}"))?;
+    let file_size = file.metadata().map_err(|e| format!("Failed to stat file: {e}"))?.len();
+
+    let mut magic = [0u8; 12];
+    let n = read_up_to(&mut file, &mut magic)?;
+    if n < 4 {
+        return Err("File too small".into());
+    }
+    file.seek(SeekFrom::Start(0)).map_err(|e| format!("Seek failed: {e}"))?;
+
+    let mut meta = match &magic[0..4] {
+        b"RIFF" | b"RF64" if n >= 12 && &magic[8..12] == b"WAVE" => riff_metadata(&mut file, file_size)?,
+        b"fLaC" => flac_metadata(file)?,
+        b"OggS" => ogg_metadata(&mut file, file_size)?,
+        _ if n >= 8 && &magic[4..8] == b"ftyp" => symphonia_metadata(file, "M4A")?,
+        _ if &magic[0..3] == b"ID3" || (magic[0] == 0xFF && (magic[1] & 0xE0) == 0xE0) => {
+            symphonia_metadata(file, "MP3")?
+        }
+        _ => return Err("Unknown audio format (expected WAV, W4V, FLAC, OGG, MP3, or M4A)".into()),
+    };
+    meta.file_size = file_size;
+
+    meta.xc_metadata = read_xc_sidecar(path);
+    meta.xc_quality = field(&meta.xc_metadata, "Quality");
+    meta.species = field(&meta.guano, "Species Manual ID")
+        .or_else(|| field(&meta.guano, "Species Auto ID"))
+        .or_else(|| field(&meta.xc_metadata, "Species"));
+    Ok(meta)
+}
+
+fn field(fields: &[(String, String)], key: &str) -> Option<String> {
+    fields.iter()
+        .find(|(k, v)| k == key && !v.trim().is_empty())
+        .map(|(_, v)| v.trim().to_string())
+}
+
+fn read_up_to(r: &mut impl Read, buf: &mut [u8]) -> Result<usize, String> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match r.read(&mut buf[filled..]) {
+            Ok(0) => break,
+            Ok(n) => filled += n,
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(format!("Read failed: {e}")),
+        }
+    }
+    Ok(filled)
+}
+
+fn read_chunk_body(file: &mut File, size: u64) -> Result<Vec<u8>, String> {
+    let mut body = vec![0u8; size as usize];
+    file.read_exact(&mut body).map_err(|e| format!("Read failed: {e}"))?;
+    Ok(body)
+}
+
+// ── WAV / W4V / RF64 ────────────────────────────────────────────────
+
+/// Walk RIFF chunks, reading `fmt `, `ds64` and `guan` and seeking past the rest.
+fn riff_metadata(file: &mut File, file_size: u64) -> Result<AudioFileMetadata, String> {
+    let mut header = [0u8; 12];
+    file.read_exact(&mut header).map_err(|e| format!("Read failed: {e}"))?;
+    let is_rf64 = &header[0..4] == b"RF64";
+
+    let mut meta = AudioFileMetadata::default();
+    let mut format_tag = 0u16;
+    let mut block_align = 0u16;
+    let mut ds64_data_size: Option<u64> = None;
+    let mut data_size: Option<u64> = None;
+
+    let mut pos = 12u64;
+    while pos + 8 <= file_size {
+        file.seek(SeekFrom::Start(pos)).map_err(|e| format!("Seek failed: {e}"))?;
+        let mut ch = [0u8; 8];
+        if file.read_exact(&mut ch).is_err() {
+            break;
+        }
+        let id = [ch[0], ch[1], ch[2], ch[3]];
+        let mut size = u32::from_le_bytes([ch[4], ch[5], ch[6], ch[7]]) as u64;
+
+        match &id {
+            b"fmt " if size >= 16 => {
+                let body = read_chunk_body(file, size.min(64))?;
+                format_tag = u16::from_le_bytes([body[0], body[1]]);
+                meta.channels = u16::from_le_bytes([body[2], body[3]]) as u32;
+                meta.sample_rate = u32::from_le_bytes([body[4], body[5], body[6], body[7]]);
+                block_align = u16::from_le_bytes([body[12], body[13]]);
+                meta.bits_per_sample = u16::from_le_bytes([body[14], body[15]]);
+                // WAVE_FORMAT_EXTENSIBLE: report the valid bits, not the container size
+                if format_tag == WAVE_FORMAT_EXTENSIBLE && body.len() >= 20 {
+                    let valid = u16::from_le_bytes([body[18], body[19]]);
+                    if valid > 0 && valid < meta.bits_per_sample {
+                        meta.bits_per_sample = valid;
+                    }
+                }
+            }
+            b"ds64" if size >= 16 => {
+                let body = read_chunk_body(file, 16)?;
+                ds64_data_size = Some(u64::from_le_bytes(body[8..16].try_into().unwrap_or([0; 8])));
+            }
+            b"data" => {
+                if is_rf64 && size == 0xFFFF_FFFF {
+                    size = ds64_data_size.unwrap_or(file_size.saturating_sub(pos + 8));
+                }
+                // Truncated files: count only the bytes actually present
+                data_size = Some(size.min(file_size.saturating_sub(pos + 8)));
+            }
+            b"guan" => {
+                let body = read_chunk_body(file, size.min(file_size.saturating_sub(pos + 8)))?;
+                if let Some(g) = oversample_core::audio::guano::parse_guano_chunk(&body) {
+                    meta.guano = g.fields;
+                }
+            }
+            _ => {}
+        }
+        // Chunks are word-aligned (padded to even size)
+        pos += 8 + ((size + 1) & !1);
+    }
+
+    if meta.sample_rate == 0 {
+        return Err("WAV missing fmt chunk".into());
+    }
+    let is_w4v = format_tag == W4V_FORMAT_TAG;
+    meta.format = if is_w4v { "W4V" } else if is_rf64 { "RF64" } else { "WAV" }.into();
+    meta.duration_secs = data_size.filter(|_| block_align > 0).map(|bytes| {
+        let frames = if is_w4v {
+            (bytes / block_align as u64) * W4V_BLOCK_SAMPLES
+        } else {
+            bytes / block_align as u64
+        };
+        frames as f64 / meta.sample_rate as f64
+    });
+    Ok(meta)
+}
+
+// ── FLAC ────────────────────────────────────────────────────────────
+
+fn flac_metadata(file: File) -> Result<AudioFileMetadata, String> {
+    // claxon reads only the metadata blocks on construction; frames are decoded lazily.
+    let reader = claxon::FlacReader::new(BufReader::new(file)).map_err(|e| format!("FLAC error: {e}"))?;
+    let info = reader.streaminfo();
+    Ok(AudioFileMetadata {
+        format: "FLAC".into(),
+        sample_rate: info.sample_rate,
+        channels: info.channels,
+        bits_per_sample: info.bits_per_sample as u16,
+        duration_secs: info.samples
+            .filter(|_| info.sample_rate > 0)
+            .map(|frames| frames as f64 / info.sample_rate as f64),
+        ..Default::default()
+    })
+}
+
+// ── OGG ─────────────────────────────────────────────────────────────
+
+/// Read the Vorbis identification header, then take the duration from the
+/// granule position of the last page.
+fn ogg_metadata(file: &mut File, file_size: u64) -> Result<AudioFileMetadata, String> {
+    let (sample_rate, channels) = {
+        let reader = lewton::inside_ogg::OggStreamReader::new(BufReader::new(&mut *file))
+            .map_err(|e| format!("OGG error: {e}"))?;
+        (reader.ident_hdr.audio_sample_rate, reader.ident_hdr.audio_channels as u32)
+    };
+
+    let tail_start = file_size.saturating_sub(OGG_TAIL_BYTES);
+    file.seek(SeekFrom::Start(tail_start)).map_err(|e| format!("Seek failed: {e}"))?;
+    let mut tail = Vec::new();
+    file.read_to_end(&mut tail).map_err(|e| format!("Read failed: {e}"))?;
+    let last_granule = (0..tail.len().saturating_sub(13))
+        .rev()
+        .filter(|&i| &tail[i..i + 4] == b"OggS")
+        .find_map(|i| {
+            let g = u64::from_le_bytes(tail[i + 6..i + 14].try_into().ok()?);
+            // -1 marks a page with no completed packet
+            (g != u64::MAX).then_some(g)
+        });
+
+    Ok(AudioFileMetadata {
+        format: "OGG".into(),
+        sample_rate,
+        channels,
+        bits_per_sample: 16,
+        duration_secs: last_granule
+            .filter(|_| sample_rate > 0)
+            .map(|g| g as f64 / sample_rate as f64),
+        ..Default::default()
+    })
+}
+
+// ── MP3 / M4A ───────────────────────────────────────────────────────
+
+/// Probe the container with symphonia and read the track's codec parameters.
+/// Duration comes from the frame count the demuxer reports (Xing/Info header
+/// for MP3, `mdhd`/`stts` for MP4) without decoding any packets.
+fn symphonia_metadata(file: File, format: &str) -> Result<AudioFileMetadata, String> {
+    use symphonia::core::codecs::CODEC_TYPE_NULL;
+    use symphonia::core::formats::FormatOptions;
+    use symphonia::core::io::MediaSourceStream;
+    use symphonia::core::meta::MetadataOptions;
+    use symphonia::core::probe::Hint;
+
+    let mss = MediaSourceStream::new(Box::new(file), Default::default());
+    let probed = symphonia::default::get_probe()
+        .format(&Hint::new(), mss, &FormatOptions::default(), &MetadataOptions::default())
+        .map_err(|e| format!("{format} probe error: {e}"))?;
+    let track = probed.format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| format!("No audio track found in {format}"))?;
+    let params = &track.codec_params;
+    let sample_rate = params.sample_rate.ok_or_else(|| format!("{format} missing sample rate"))?;
+
+    Ok(AudioFileMetadata {
+        format: format.into(),
+        sample_rate,
+        channels: params.channels.map(|c| c.count() as u32).unwrap_or(0),
+        bits_per_sample: params.bits_per_sample.unwrap_or(16) as u16,
+        duration_secs: params.n_frames.map(|n| n as f64 / sample_rate as f64),
+        ..Default::default()
+    })
+}
+
+// ── Sidecars ────────────────────────────────────────────────────────
+
+/// Find and parse the xeno-canto sidecar next to an audio file, trying the
+/// names from [`xc_sidecar::sidecar_names`] in order.
+pub(crate) fn find_xc_sidecar(path: &Path) -> Option<serde_json::Value> {
+    let audio_name = path.file_name()?.to_str()?;
+    xc_sidecar::sidecar_names(audio_name).iter().find_map(|candidate| {
+        let text = std::fs::read_to_string(path.with_file_name(candidate)).ok()?;
+        let json = serde_json::from_str::<serde_json::Value>(&text).ok()?;
+        xc_sidecar::accepts_sidecar(candidate, &json).then_some(json)
+    })
+}
+
+/// Metadata fields from the audio file's xeno-canto sidecar, if it has one.
+fn read_xc_sidecar(path: &Path) -> Vec<(String, String)> {
+    find_xc_sidecar(path)
+        .map(|json| crate::xc::parse_xc_json_metadata(&json))
+        .unwrap_or_default()
+}
@@ -22,6 +22,34 @@ pub fn write_sidecar(path: String, yaml: String) -> Result<(), String> {
     Ok(())
 }
 
+#[tauri::command]
+pub fn read_notes_sidecar(path: String) -> Result<Option<String>, String> {
+    let sidecar = format!("{}.notes.json", path);
+    if std::path::Path::new(&sidecar).exists() {
+        std::fs::read_to_string(&sidecar)
+            .map(Some)
+            .map_err(|e| format!("Failed to read notes: {e}"))
+    } else {
+        Ok(None)
+    }
+}
+
+/// Write (or, when `json` is empty, delete) the `.notes.json` sidecar.
+#[tauri::command]
+pub fn write_notes_sidecar(path: String, json: String) -> Result<(), String> {
+    let sidecar = format!("{}.notes.json", path);
+    if json.is_empty() {
+        if std::path::Path::new(&sidecar).exists() {
+            std::fs::remove_file(&sidecar).map_err(|e| format!("Failed to remove notes: {e}"))?;
+        }
+        return Ok(());
+    }
+    let tmp = format!("{}.notes.json.tmp", path);
+    std::fs::write(&tmp, &json).map_err(|e| format!("Failed to write notes: {e}"))?;
+    std::fs::rename(&tmp, &sidecar).map_err(|e| format!("Failed to rename notes: {e}"))?;
+    Ok(())
+}
+
 #[tauri::command]
 pub fn read_central_annotations(app: tauri::AppHandle, file_key: String) -> Result<Option<String>, String> {
     let dir = app
@@ -1,10 +1,24 @@
 use crate::audio_decode::{self, AudioFileInfo, FullDecodeResult};
+use crate::audio_meta::{self, AudioFileMetadata};
 
 #[tauri::command]
 pub fn audio_file_info(path: String) -> Result<AudioFileInfo, String> {
     audio_decode::file_info(&path)
 }
 
+/// Header-only metadata (format, duration, GUANO, XC sidecar) for fast folder scanning.
+/// Async so scanning many files doesn't block the main thread.
+#[tauri::command]
+pub async fn audio_file_metadata(path: String) -> Result<AudioFileMetadata, String> {
+    audio_meta::quick_metadata(&path)
+}
+
+/// The xeno-canto sidecar next to an audio file, as JSON text (None if absent).
+#[tauri::command]
+pub fn read_xc_sidecar(path: String) -> Option<String> {
+    audio_meta::find_xc_sidecar(std::path::Path::new(&path)).map(|json| json.to_string())
+}
+
 #[tauri::command]
 pub fn audio_decode_full(path: String) -> Result<FullDecodeResult, String> {
     audio_decode::decode_full(&path)
@@ -31,3 +45,35 @@ pub fn read_file_range(path: String, offset: u64, length: u64) -> Result<tauri::
         .map_err(|e| format!("Read failed: {}", e))?;
     Ok(tauri::ipc::Response::new(buf))
 }
+
+/// Files the app was asked to open — on its command line or by the OS via a
+/// file association — that the frontend hasn't taken yet. The frontend
+/// drains this at startup, since it isn't listening when the args arrive.
+#[derive(Default)]
+pub struct PendingOpenFiles(pub std::sync::Mutex<Vec<String>>);
+
+/// Existing files among the process arguments.
+pub fn startup_file_args() -> Vec<String> {
+    std::env::args()
+        .skip(1)
+        .filter(|a| !a.starts_with('-') && std::path::Path::new(a).is_file())
+        .collect()
+}
+
+/// Queue files opened while running and tell the frontend to fetch them.
+#[cfg_attr(not(any(target_os = "macos", target_os = "ios")), allow(dead_code))]
+pub fn queue_open_files(app: &tauri::AppHandle, paths: Vec<String>) {
+    use tauri::{Emitter, Manager};
+    if paths.is_empty() {
+        return;
+    }
+    if let Some(pending) = app.try_state::<PendingOpenFiles>() {
+        pending.0.lock().unwrap().extend(paths);
+    }
+    let _ = app.emit("open-files", ());
+}
+
+#[tauri::command]
+pub fn take_pending_open_files(pending: tauri::State<'_, PendingOpenFiles>) -> Vec<String> {
+    std::mem::take(&mut *pending.0.lock().unwrap())
+}
@@ -50,14 +50,10 @@ pub fn save_recording(
     app: tauri::AppHandle,
     filename: String,
     data: Vec<u8>,
+    save_dir: Option<String>,
 ) -> Result<String, String> {
-    let dir = app
-        .path()
-        .app_data_dir()
-        .map_err(|e| e.to_string())?
-        .join("recordings");
-    std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-    let path = dir.join(&filename);
+    let dir = recording::recordings_dir(&app, save_dir.as_deref())?;
+    let path = recording::unique_path(&dir, &recording::recording_filename(Some(&filename), &chrono::Local::now()));
     std::fs::write(&path, &data).map_err(|e| e.to_string())?;
     Ok(path.to_string_lossy().to_string())
 }
@@ -105,7 +101,14 @@ pub fn mic_open(
 
     // Start the emitter thread for streaming audio chunks to the frontend
     // (also does best-effort disk flushing for crash-recovery).
-    recording::start_emitter(app, m.buffer.clone(), m.emitter_stop.clone(), m.recovery.clone());
+    recording::start_emitter(
+        app,
+        m.buffer.clone(),
+        m.emitter_stop.clone(),
+        m.recovery.clone(),
+        m.display_factor.clone(),
+        m.is_streaming.clone(),
+    );
 
     *mic = Some(m);
     Ok(info)
@@ -155,6 +158,8 @@ pub fn mic_start_recording(
     loc_longitude: Option<f64>,
     loc_elevation: Option<f64>,
     loc_accuracy: Option<f64>,
+    site: Option<String>,
+    operator: Option<String>,
     enable_recovery: Option<bool>,
 ) -> Result<(), String> {
     let mic = state.lock().map_err(|e| e.to_string())?;
@@ -177,6 +182,8 @@ pub fn mic_start_recording(
         loc_longitude,
         loc_elevation,
         loc_accuracy,
+        site,
+        operator,
         enable_recovery,
     };
     if let Some(writer) = recovery::start_writer(&app, &args, m.format, m.sample_rate, m.channels as u16, "batcap") {
@@ -201,6 +208,10 @@ pub fn mic_stop_recording(
     device_model: Option<String>,
     app_version: Option<String>,
     skip_native_save: Option<bool>,
+    filename: Option<String>,
+    save_dir: Option<String>,
+    site: Option<String>,
+    operator: Option<String>,
 ) -> Result<RecordingResult, String> {
     let mic = state.lock().map_err(|e| e.to_string())?;
     let m = mic.as_ref().ok_or("Microphone not open")?;
@@ -250,7 +261,15 @@ pub fn mic_stop_recording(
 
     // Build the GUANO chunk for either path below.
     let now = chrono::Local::now();
-    let filename_ts = now.format("batcap_%Y%m%d_%H%M%S.wav").to_string();
+    let mut filename_ts = recording::recording_filename(filename.as_deref(), &now);
+    // Pick the destination now, so GUANO names the file actually written
+    let target = match shared_fd {
+        Some(_) => None,
+        None => Some(recording::unique_path(&recording::recordings_dir(&app, save_dir.as_deref())?, &filename_ts)),
+    };
+    if let Some(name) = target.as_ref().and_then(|t| t.file_name()) {
+        filename_ts = name.to_string_lossy().into_owned();
+    }
     let location = match (loc_latitude, loc_longitude) {
         (Some(lat), Some(lon)) => Some(recording::RecordingLocation {
             latitude: lat,
@@ -271,6 +290,8 @@ pub fn mic_stop_recording(
         mic_make: None,
         app_version: app_version.unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
         is_mobile,
+        site,
+        operator,
     };
     let guano = recording::build_tauri_guano(
         sample_rate, num_samples, &filename_ts, &now, &guano_params,
@@ -303,16 +324,9 @@ pub fn mic_stop_recording(
             let _ = std::fs::remove_file(&finalized_path);
             "shared://recording".to_string()
         } else {
-            // Move .wav.part → recordings/<name>.wav
-            let dir = app
-                .path()
-                .app_data_dir()
-                .map_err(|e| e.to_string())?
-                .join("recordings");
-            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-            let target = dir.join(&filename_ts);
-            std::fs::rename(&finalized_path, &target)
-                .map_err(|e| format!("recovery: rename to final path: {}", e))?;
+            // Move .wav.part → <save dir>/<name>.wav
+            let target = target.expect("target is set without a shared fd");
+            recording::move_recording(&finalized_path, &target)?;
             target.to_string_lossy().to_string()
         };
         (saved_path, final_size as usize, Vec::new())
@@ -331,13 +345,7 @@ pub fn mic_stop_recording(
             recording::write_wav_to_fd(fd, &wav_data)?;
             "shared://recording".to_string()
         } else {
-            let dir = app
-                .path()
-                .app_data_dir()
-                .map_err(|e| e.to_string())?
-                .join("recordings");
-            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-            let full_path = dir.join(&filename_ts);
+            let full_path = target.expect("target is set without a shared fd");
             std::fs::write(&full_path, &wav_data).map_err(|e| e.to_string())?;
             full_path.to_string_lossy().to_string()
         };
@@ -377,6 +385,17 @@ pub fn mic_set_listening(state: tauri::State<MicMutex>, listening: bool) -> Resu
     Ok(())
 }
 
+/// Limit the rate of the samples streamed for display to `max_rate` Hz
+/// (0 = full rate). Returns the resulting stream rate. Recording is unaffected.
+#[tauri::command]
+pub fn mic_set_display_rate(state: tauri::State<MicMutex>, max_rate: u32) -> Result<u32, String> {
+    let mic = state.lock().map_err(|e| e.to_string())?;
+    let m = mic.as_ref().ok_or("Microphone not open")?;
+    let factor = oversample_core::dsp::resample::display_factor_for(m.sample_rate, max_rate);
+    m.display_factor.store(factor, Ordering::Relaxed);
+    Ok(m.sample_rate / factor as u32)
+}
+
 #[tauri::command]
 pub fn mic_get_status(state: tauri::State<MicMutex>) -> MicStatus {
     let mic = state.lock().unwrap_or_else(|e| e.into_inner());
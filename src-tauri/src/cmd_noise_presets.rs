@@ -8,15 +8,11 @@ pub fn save_noise_preset(app: tauri::AppHandle, name: String, json: String) -> R
         .map_err(|e| e.to_string())?
         .join("noise-presets");
     std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-    let sanitized: String = name
-        .chars()
-        .map(|c| if c.is_alphanumeric() || c == '-' || c == '_' || c == ' ' { c } else { '_' })
-        .collect();
-    let sanitized = sanitized.trim().to_string();
+    let sanitized = oversample_core::audio::recording_name::sanitize_file_stem(&name);
     let filename = if sanitized.is_empty() {
         "noise_profile.batm".to_string()
     } else {
-        format!("{}.batm", sanitized.replace(' ', "_").to_lowercase())
+        format!("{}.batm", sanitized.to_lowercase())
     };
     let path = dir.join(&filename);
     std::fs::write(&path, &json).map_err(|e| e.to_string())?;
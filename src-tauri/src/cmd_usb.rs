@@ -3,7 +3,6 @@ use crate::recovery;
 use crate::usb_audio::{self, UsbStreamInfo, UsbStreamStatus};
 use crate::UsbStreamMutex;
 use std::sync::atomic::Ordering;
-use tauri::Manager;
 
 #[tauri::command]
 pub fn usb_start_stream(
@@ -81,6 +80,8 @@ pub fn usb_start_recording(
     loc_longitude: Option<f64>,
     loc_elevation: Option<f64>,
     loc_accuracy: Option<f64>,
+    site: Option<String>,
+    operator: Option<String>,
     enable_recovery: Option<bool>,
 ) -> Result<(), String> {
     let usb = state.lock().map_err(|e| e.to_string())?;
@@ -106,6 +107,8 @@ pub fn usb_start_recording(
         loc_longitude,
         loc_elevation,
         loc_accuracy,
+        site,
+        operator,
         enable_recovery,
     };
     // USB streams are always 16-bit mono in this implementation.
@@ -133,6 +136,10 @@ pub fn usb_stop_recording(
     device_model: Option<String>,
     app_version: Option<String>,
     skip_native_save: Option<bool>,
+    filename: Option<String>,
+    save_dir: Option<String>,
+    site: Option<String>,
+    operator: Option<String>,
 ) -> Result<RecordingResult, String> {
     let usb = state.lock().map_err(|e| e.to_string())?;
     let s = usb.as_ref().ok_or("USB stream not open")?;
@@ -182,7 +189,15 @@ pub fn usb_stop_recording(
     }
 
     let now = chrono::Local::now();
-    let filename = now.format("batcap_%Y%m%d_%H%M%S.wav").to_string();
+    let mut filename = recording::recording_filename(filename.as_deref(), &now);
+    // Pick the destination now, so GUANO names the file actually written
+    let target = match shared_fd {
+        Some(_) => None,
+        None => Some(recording::unique_path(&recording::recordings_dir(&app, save_dir.as_deref())?, &filename)),
+    };
+    if let Some(name) = target.as_ref().and_then(|t| t.file_name()) {
+        filename = name.to_string_lossy().into_owned();
+    }
     let location = match (loc_latitude, loc_longitude) {
         (Some(lat), Some(lon)) => Some(recording::RecordingLocation {
             latitude: lat,
@@ -212,6 +227,8 @@ pub fn usb_stop_recording(
         mic_make: None,
         app_version: app_version.unwrap_or_else(|| env!("CARGO_PKG_VERSION").to_string()),
         is_mobile,
+        site,
+        operator,
     };
     let guano = recording::build_tauri_guano(
         sample_rate, num_samples, &filename, &now, &guano_params,
@@ -235,15 +252,8 @@ pub fn usb_stop_recording(
             let _ = std::fs::remove_file(&finalized_path);
             "shared://recording".to_string()
         } else {
-            let dir = app
-                .path()
-                .app_data_dir()
-                .map_err(|e| e.to_string())?
-                .join("recordings");
-            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-            let target = dir.join(&filename);
-            std::fs::rename(&finalized_path, &target)
-                .map_err(|e| format!("recovery: rename to final path: {}", e))?;
+            let target = target.expect("target is set without a shared fd");
+            recording::move_recording(&finalized_path, &target)?;
             target.to_string_lossy().to_string()
         };
         (saved_path, final_size, Vec::new())
@@ -257,13 +267,7 @@ pub fn usb_stop_recording(
             recording::write_wav_to_fd(fd, &wav_data)?;
             "shared://recording".to_string()
         } else {
-            let dir = app
-                .path()
-                .app_data_dir()
-                .map_err(|e| e.to_string())?
-                .join("recordings");
-            std::fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
-            let full_path = dir.join(&filename);
+            let full_path = target.expect("target is set without a shared fd");
             std::fs::write(&full_path, &wav_data).map_err(|e| e.to_string())?;
             full_path.to_string_lossy().to_string()
         };
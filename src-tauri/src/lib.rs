@@ -1,4 +1,5 @@
 mod audio_decode;
+mod audio_meta;
 mod cmd_annotations;
 mod cmd_audio_files;
 mod cmd_mic;
@@ -30,6 +31,7 @@ pub fn run() {
         .manage(Mutex::new(None::<MicState>))
         .manage(Mutex::new(None::<PlaybackState>))
         .manage(Mutex::new(None::<UsbStreamState>))
+        .manage(cmd_audio_files::PendingOpenFiles(Mutex::new(cmd_audio_files::startup_file_args())))
         .setup(|app| {
             let cache_root = app
                 .path()
@@ -40,6 +42,7 @@ pub fn run() {
             app.manage(Mutex::new(xc::XcState {
                 client: reqwest::Client::new(),
                 cache_root,
+                offline: false,
             }));
             Ok(())
         })
@@ -50,13 +53,17 @@ pub fn run() {
             cmd_mic::mic_start_recording,
             cmd_mic::mic_stop_recording,
             cmd_mic::mic_set_listening,
+            cmd_mic::mic_set_display_rate,
             cmd_mic::mic_get_status,
             cmd_mic::mic_list_devices,
             cmd_mic::mic_recover_recordings,
             cmd_audio_files::audio_file_info,
+            cmd_audio_files::audio_file_metadata,
             cmd_audio_files::audio_decode_full,
+            cmd_audio_files::read_xc_sidecar,
             cmd_audio_files::read_file_bytes,
             cmd_audio_files::read_file_range,
+            cmd_audio_files::take_pending_open_files,
             cmd_playback::native_play,
             cmd_playback::native_stop,
             cmd_playback::native_playback_status,
@@ -69,6 +76,8 @@ pub fn run() {
             xc::xc_species_recordings,
             xc::xc_download,
             xc::xc_is_cached,
+            xc::xc_set_offline,
+            xc::xc_convert_cache,
             cmd_usb::usb_start_stream,
             cmd_usb::usb_stop_stream,
             cmd_usb::usb_start_recording,
@@ -80,11 +89,24 @@ pub fn run() {
             cmd_noise_presets::delete_noise_preset,
             cmd_annotations::read_sidecar,
             cmd_annotations::write_sidecar,
+            cmd_annotations::read_notes_sidecar,
+            cmd_annotations::write_notes_sidecar,
             cmd_annotations::read_central_annotations,
             cmd_annotations::write_central_annotations,
             cmd_annotations::export_annotations_file,
             cmd_annotations::open_file_dialog,
         ])
-        .run(tauri::generate_context!())
-        .expect("error while running tauri application");
+        .build(tauri::generate_context!())
+        .expect("error while building tauri application")
+        .run(|_app, _event| {
+            // macOS delivers file-association opens as an event, not as args
+            #[cfg(any(target_os = "macos", target_os = "ios"))]
+            if let tauri::RunEvent::Opened { urls } = _event {
+                let paths = urls.iter()
+                    .filter_map(|u| u.to_file_path().ok())
+                    .map(|p| p.to_string_lossy().into_owned())
+                    .collect();
+                cmd_audio_files::queue_open_files(_app, paths);
+            }
+        });
 }
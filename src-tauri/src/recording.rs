@@ -1,7 +1,8 @@
 use cpal::traits::{DeviceTrait, HostTrait, StreamTrait};
+use oversample_core::dsp::resample::DisplayDecimator;
 use serde::Serialize;
 use std::io::Cursor;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex};
 
 #[derive(Clone, Copy, Debug, Serialize)]
@@ -97,6 +98,9 @@ pub struct MicState {
     /// Crash-recovery writer + shared state. Active between
     /// `mic_start_recording` and `mic_stop_recording` on Android.
     pub recovery: crate::recovery::RecoveryHandle,
+    /// Decimation factor for the samples streamed to the frontend for display
+    /// (1 = full rate). The recording buffer always keeps every sample.
+    pub display_factor: Arc<AtomicUsize>,
 }
 
 #[derive(Serialize)]
@@ -560,6 +564,7 @@ pub fn open_mic(
         device_name,
         supported_sample_rates: supported_rates,
         recovery: crate::recovery::RecoveryHandle::default(),
+        display_factor: Arc::new(AtomicUsize::new(1)),
     })
 }
 
@@ -638,6 +643,68 @@ pub struct TauriGuanoParams {
     pub mic_make: Option<String>,
     pub app_version: String,
     pub is_mobile: bool,
+    pub site: Option<String>,
+    pub operator: Option<String>,
+}
+
+/// Directory a finished recording goes to: `save_dir` when the user chose
+/// one, otherwise `recordings/` under the app data directory. Created if
+/// missing.
+pub fn recordings_dir(app: &tauri::AppHandle, save_dir: Option<&str>) -> Result<std::path::PathBuf, String> {
+    use tauri::Manager;
+    let dir = match save_dir.map(str::trim).filter(|d| !d.is_empty()) {
+        Some(d) => std::path::PathBuf::from(d),
+        None => app.path().app_data_dir().map_err(|e| e.to_string())?.join("recordings"),
+    };
+    std::fs::create_dir_all(&dir).map_err(|e| format!("Can't create {}: {}", dir.display(), e))?;
+    Ok(dir)
+}
+
+/// File name for a finished recording: the name the frontend built from
+/// its template, sanitised again here, or `batcap_<timestamp>.wav` if none
+/// was sent.
+pub fn recording_filename(requested: Option<&str>, now: &chrono::DateTime<chrono::Local>) -> String {
+    use oversample_core::audio::recording_name::sanitize_file_stem;
+    let stem = requested
+        .map(|name| name.trim().trim_end_matches(".wav"))
+        .map(sanitize_file_stem)
+        .filter(|s| !s.is_empty());
+    match stem {
+        Some(stem) => format!("{stem}.wav"),
+        None => now.format("batcap_%Y%m%d_%H%M%S.wav").to_string(),
+    }
+}
+
+/// `dir/name`, or `dir/<stem>_2.wav`, `_3`, … if that is already taken, so
+/// a new recording never replaces an earlier one in the save directory.
+pub fn unique_path(dir: &std::path::Path, name: &str) -> std::path::PathBuf {
+    let path = dir.join(name);
+    if !path.exists() {
+        return path;
+    }
+    let (stem, ext) = name.rsplit_once('.').unwrap_or((name, ""));
+    (2u32..)
+        .map(|n| match ext {
+            "" => dir.join(format!("{stem}_{n}")),
+            ext => dir.join(format!("{stem}_{n}.{ext}")),
+        })
+        .find(|p| !p.exists())
+        .expect("some suffix is free")
+}
+
+/// Move a finalized recording into place. `rename` can't cross filesystems,
+/// which a user-chosen save directory may be on, so fall back to copying.
+/// Refuses to replace an existing file; pick `to` with [`unique_path`].
+pub fn move_recording(from: &std::path::Path, to: &std::path::Path) -> Result<(), String> {
+    if to.exists() {
+        return Err(format!("{} already exists", to.display()));
+    }
+    if std::fs::rename(from, to).is_ok() {
+        return Ok(());
+    }
+    std::fs::copy(from, to).map_err(|e| format!("Can't move recording to {}: {}", to.display(), e))?;
+    let _ = std::fs::remove_file(from);
+    Ok(())
 }
 
 /// Build GUANO metadata for a Tauri-side recording using the shared builder.
@@ -678,6 +745,8 @@ pub fn build_tauri_guano(
         device_make: if params.is_mobile { params.device_make.clone() } else { None },
         device_model: if params.is_mobile { params.device_model.clone() } else { None },
         preroll_secs: None, // Pre-roll handled on the WASM side
+        site: params.site.clone(),
+        operator: params.operator.clone(),
     };
 
     guano::build_recording_guano(
@@ -731,23 +800,50 @@ pub fn write_wav_to_fd(_fd: i32, _wav_data: &[u8]) -> Result<(), String> {
 /// `RecoveryWriter` is installed (by `mic_start_recording`), any native-format
 /// samples appended since the last tick are written to the `.wav.part` file.
 /// Disk I/O happens outside the buffer lock to avoid stalling the audio callback.
+///
+/// Streamed chunks are decimated by `display_factor` (see `DisplayDecimator`)
+/// so high-rate devices don't flood the IPC channel; disk flushing and the
+/// recording buffer are unaffected. While decimating and `listening`, the
+/// chunks go out at full rate as `mic-listen-chunk` instead, since heterodyne
+/// and time-expansion listening need the ultrasonic band the display drops;
+/// the page decimates them for display itself. Only one stream is sent either way.
 pub fn start_emitter(
     app: tauri::AppHandle,
     buffer: Arc<Mutex<RecordingBuffer>>,
     stop_flag: Arc<AtomicBool>,
     recovery: crate::recovery::RecoveryHandle,
+    display_factor: Arc<AtomicUsize>,
+    listening: Arc<AtomicBool>,
 ) {
     std::thread::spawn(move || {
         use tauri::Emitter;
         let mut tick: u32 = 0;
+        let sample_rate = buffer.lock().unwrap().sample_rate;
+        let mut decimator = DisplayDecimator::new(sample_rate, display_factor.load(Ordering::Relaxed));
+        let mut sent_full_rate = false;
         while !stop_flag.load(Ordering::Relaxed) {
             std::thread::sleep(std::time::Duration::from_millis(80));
+            let factor = display_factor.load(Ordering::Relaxed);
+            let full_rate = factor > 1 && listening.load(Ordering::Relaxed);
+            // Restart the filter when the factor changes or after a full-rate
+            // stretch, so stale input doesn't leak into the display stream
+            if factor != decimator.factor() || (sent_full_rate && !full_rate) {
+                decimator = DisplayDecimator::new(sample_rate, factor);
+            }
+            sent_full_rate = full_rate;
             let chunks = {
                 let mut buf = buffer.lock().unwrap();
                 buf.drain_pending()
             };
-            if !chunks.is_empty() {
-                let _ = app.emit("mic-audio-chunk", &chunks);
+            if full_rate {
+                if !chunks.is_empty() {
+                    let _ = app.emit("mic-listen-chunk", &chunks);
+                }
+            } else {
+                let chunks = decimator.process(chunks);
+                if !chunks.is_empty() {
+                    let _ = app.emit("mic-audio-chunk", &chunks);
+                }
             }
 
             // Flush new samples to disk every ~240 ms (every 3rd tick) to
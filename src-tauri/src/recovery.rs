@@ -44,6 +44,11 @@ pub struct RecoveryMeta {
     pub longitude: Option<f64>,
     pub elevation: Option<f64>,
     pub accuracy: Option<f64>,
+    /// Survey fields; absent from sidecars written before they were added.
+    #[serde(default)]
+    pub site: Option<String>,
+    #[serde(default)]
+    pub operator: Option<String>,
 }
 
 impl RecoveryMeta {
@@ -68,6 +73,8 @@ impl RecoveryMeta {
             mic_make: self.mic_make.clone(),
             app_version: self.app_version.clone(),
             is_mobile: self.is_mobile,
+            site: self.site.clone(),
+            operator: self.operator.clone(),
         };
         let start = chrono::DateTime::parse_from_rfc3339(&self.start_time_iso)
             .ok()
@@ -117,6 +124,8 @@ pub struct StartArgs {
     pub loc_longitude: Option<f64>,
     pub loc_elevation: Option<f64>,
     pub loc_accuracy: Option<f64>,
+    pub site: Option<String>,
+    pub operator: Option<String>,
     pub enable_recovery: Option<bool>,
 }
 
@@ -152,6 +161,8 @@ pub fn build_meta(
         longitude: args.loc_longitude,
         elevation: args.loc_elevation,
         accuracy: args.loc_accuracy,
+        site: args.site.clone(),
+        operator: args.operator.clone(),
     }
 }
 
@@ -629,8 +640,8 @@ fn recover_one(
 
     // Write to recordings dir with a "recovered_" prefix so the user can tell.
     std::fs::create_dir_all(target_dir)?;
-    let final_name = format!("recovered_{}", wav_name);
-    let final_path = target_dir.join(&final_name);
+    let final_path = crate::recording::unique_path(target_dir, &format!("recovered_{}", wav_name));
+    let final_name = final_path.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
     std::fs::write(&final_path, &wav_data)?;
 
     // Remove the partial + sidecar now that we have a good final file.
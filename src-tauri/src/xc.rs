@@ -1,12 +1,33 @@
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 use std::sync::Mutex;
-use xc_lib::{api, cache, key_store, taxonomy, XcGroupTaxonomy, XcRecording, XcSearchResult};
+use xc_lib::{api, cache, key_store, taxonomy, XcError, XcGroupTaxonomy, XcRecording, XcSearchResult};
 
 /// Shared state for XC operations.
 pub struct XcState {
     pub client: reqwest::Client,
     pub cache_root: PathBuf,
+    /// Offline mode: only cached taxonomy and recordings are served.
+    pub offline: bool,
+}
+
+const OFFLINE_ERROR: &str = "Offline mode is on \u{2014} xeno-canto is unavailable";
+
+// ── Offline mode ──────────────────────────────────────────────────────
+
+#[tauri::command]
+pub fn xc_set_offline(state: tauri::State<'_, Mutex<XcState>>, offline: bool) -> Result<(), String> {
+    state.lock().map_err(|e| e.to_string())?.offline = offline;
+    Ok(())
+}
+
+/// Client for a network request, or an error if offline mode is on.
+fn online_client(state: &tauri::State<'_, Mutex<XcState>>) -> Result<reqwest::Client, XcError> {
+    let s = state.lock().map_err(|e| e.to_string())?;
+    if s.offline {
+        return Err(XcError::Offline(OFFLINE_ERROR.into()));
+    }
+    Ok(s.client.clone())
 }
 
 // ── API Key management ────────────────────────────────────────────────
@@ -22,23 +43,24 @@ pub fn xc_get_api_key() -> Result<Option<String>, String> {
     Ok(key_store::load_key())
 }
 
-fn require_api_key() -> Result<String, String> {
+fn require_api_key() -> Result<String, XcError> {
     key_store::resolve_key(&None)
-        .ok_or_else(|| "No XC API key configured. Please set your key first.".into())
+        .ok_or_else(|| XcError::Auth("No XC API key configured. Please set your key first.".into()))
 }
 
 // ── Taxonomy browsing ─────────────────────────────────────────────────
 
 #[tauri::command]
 pub async fn xc_browse_group(
+    app: tauri::AppHandle,
     state: tauri::State<'_, Mutex<XcState>>,
     group: String,
     country: Option<String>,
-) -> Result<XcGroupTaxonomy, String> {
+) -> Result<XcGroupTaxonomy, XcError> {
     let api_key = require_api_key()?;
-    let (client, cache_root) = {
+    let cache_root = {
         let s = state.lock().map_err(|e| e.to_string())?;
-        (s.client.clone(), s.cache_root.clone())
+        s.cache_root.clone()
     };
 
     let country_ref = country.as_deref();
@@ -49,12 +71,13 @@ pub async fn xc_browse_group(
     }
 
     // Fetch from API
+    let client = online_client(&state)?;
     let result = taxonomy::build_species_list(
         &client,
         &api_key,
         &group,
         country_ref,
-        |_, _| {},
+        emit_taxonomy_progress(&app, &group),
     )
     .await?;
 
@@ -64,16 +87,37 @@ pub async fn xc_browse_group(
     Ok(result)
 }
 
+/// Progress event emitted as "xc-taxonomy-progress" after each result page,
+/// carrying the species first seen on that page.
+#[derive(Clone, Serialize)]
+struct XcTaxonomyProgress<'a> {
+    group: &'a str,
+    #[serde(flatten)]
+    progress: &'a taxonomy::TaxonomyProgress,
+}
+
+fn emit_taxonomy_progress<'a>(
+    app: &'a tauri::AppHandle,
+    group: &'a str,
+) -> impl FnMut(&taxonomy::TaxonomyProgress) + 'a {
+    use tauri::Emitter;
+    move |progress| {
+        let _ = app.emit("xc-taxonomy-progress", XcTaxonomyProgress { group, progress });
+    }
+}
+
 #[tauri::command]
 pub async fn xc_refresh_taxonomy(
+    app: tauri::AppHandle,
     state: tauri::State<'_, Mutex<XcState>>,
     group: String,
     country: Option<String>,
-) -> Result<XcGroupTaxonomy, String> {
+) -> Result<XcGroupTaxonomy, XcError> {
     let api_key = require_api_key()?;
-    let (client, cache_root) = {
+    let client = online_client(&state)?;
+    let cache_root = {
         let s = state.lock().map_err(|e| e.to_string())?;
-        (s.client.clone(), s.cache_root.clone())
+        s.cache_root.clone()
     };
 
     let country_ref = country.as_deref();
@@ -83,7 +127,7 @@ pub async fn xc_refresh_taxonomy(
         &api_key,
         &group,
         country_ref,
-        |_, _| {},
+        emit_taxonomy_progress(&app, &group),
     )
     .await?;
 
@@ -97,12 +141,12 @@ pub fn xc_taxonomy_age(
     state: tauri::State<'_, Mutex<XcState>>,
     group: String,
     country: Option<String>,
-) -> Result<Option<String>, String> {
+) -> Result<Option<cache::TaxonomyAge>, String> {
     let cache_root = {
         let s = state.lock().map_err(|e| e.to_string())?;
         s.cache_root.clone()
     };
-    Ok(cache::taxonomy_age_string(&cache_root, &group, country.as_deref()))
+    Ok(cache::taxonomy_age(&cache_root, &group, country.as_deref()))
 }
 
 // ── Search ────────────────────────────────────────────────────────────
@@ -112,12 +156,9 @@ pub async fn xc_search(
     state: tauri::State<'_, Mutex<XcState>>,
     query: String,
     page: Option<u32>,
-) -> Result<XcSearchResult, String> {
+) -> Result<XcSearchResult, XcError> {
     let api_key = require_api_key()?;
-    let client = {
-        let s = state.lock().map_err(|e| e.to_string())?;
-        s.client.clone()
-    };
+    let client = online_client(&state)?;
 
     api::search(&client, &api_key, &query, page.unwrap_or(1), 100).await
 }
@@ -128,12 +169,9 @@ pub async fn xc_species_recordings(
     genus: String,
     species: String,
     page: Option<u32>,
-) -> Result<XcSearchResult, String> {
+) -> Result<XcSearchResult, XcError> {
     let api_key = require_api_key()?;
-    let client = {
-        let s = state.lock().map_err(|e| e.to_string())?;
-        s.client.clone()
-    };
+    let client = online_client(&state)?;
 
     let query = format!("gen:{genus} sp:{species}");
     api::search(&client, &api_key, &query, page.unwrap_or(1), 100).await
@@ -156,11 +194,11 @@ pub struct XcCachedFile {
 pub async fn xc_download(
     state: tauri::State<'_, Mutex<XcState>>,
     id: u64,
-) -> Result<XcCachedFile, String> {
+) -> Result<XcCachedFile, XcError> {
     let api_key = require_api_key()?;
-    let (client, cache_root) = {
+    let cache_root = {
         let s = state.lock().map_err(|e| e.to_string())?;
-        (s.client.clone(), s.cache_root.clone())
+        s.cache_root.clone()
     };
 
     // Check if already cached
@@ -181,14 +219,15 @@ pub async fn xc_download(
     }
 
     // Fetch recording info
+    let client = online_client(&state)?;
     let rec = api::fetch_recording(&client, &api_key, id)
         .await
-        .map_err(|e| format!("XC{id}: {e}"))?;
+        .map_err(|e| e.context(&format!("XC{id}")))?;
 
     // Download audio
     let audio_bytes = api::download_audio(&client, &rec.file_url)
         .await
-        .map_err(|e| format!("XC{id}: {e}"))?;
+        .map_err(|e| e.context(&format!("XC{id}")))?;
 
     // Compute hashes before saving (save_recording also computes them internally)
     let file_hashes = cache::compute_file_hashes(&audio_bytes);
@@ -233,6 +272,90 @@ pub fn xc_is_cached(
     cache::is_recording_cached(&cache_root, id)
 }
 
+// ── Batch conversion ──────────────────────────────────────────────────
+
+/// Progress event emitted as "xc-convert-progress" after each recording.
+#[derive(Clone, Serialize)]
+struct XcConvertProgress {
+    done: usize,
+    total: usize,
+    name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// Ask for an output folder, then convert every cached recording into it as
+/// mono WAV at `sample_rate` Hz in `format` ("16", "24" or "f32"), copying the
+/// `.xc.json` sidecars. Files already in the folder are skipped unless `force`.
+/// Returns `None` if the folder dialog was cancelled.
+#[cfg(not(target_os = "android"))]
+#[tauri::command]
+pub async fn xc_convert_cache(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, Mutex<XcState>>,
+    sample_rate: u32,
+    format: String,
+    force: bool,
+) -> Result<Option<cache::ConvertSummary>, String> {
+    use oversample_core::audio::convert::{convert_bytes, ConvertSpec, SampleFormat};
+    use tauri::Emitter;
+
+    let format = SampleFormat::from_key(&format).ok_or_else(|| format!("Unknown sample format: {format}"))?;
+    if sample_rate == 0 {
+        return Err("Sample rate must be positive".into());
+    }
+    let spec = ConvertSpec { sample_rate, format };
+    let cache_root = {
+        let s = state.lock().map_err(|e| e.to_string())?;
+        s.cache_root.clone()
+    };
+
+    let Some(folder) = rfd::AsyncFileDialog::new()
+        .set_title("Convert cached recordings into folder")
+        .pick_folder()
+        .await
+    else {
+        return Ok(None); // cancelled
+    };
+    let out_dir = folder.path().to_path_buf();
+
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        cache::convert_cached_recordings(
+            &cache_root,
+            &out_dir,
+            "wav",
+            force,
+            |bytes| convert_bytes(bytes, spec),
+            |done, total, rec, outcome| {
+                let error = match outcome {
+                    cache::ConvertOutcome::Failed(e) => Some(e.clone()),
+                    _ => None,
+                };
+                let _ = app.emit("xc-convert-progress", XcConvertProgress {
+                    done,
+                    total,
+                    name: rec.stem.clone(),
+                    error,
+                });
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Conversion task failed: {e}"))??;
+
+    Ok(Some(summary))
+}
+
+#[cfg(target_os = "android")]
+#[tauri::command]
+pub async fn xc_convert_cache(
+    _sample_rate: u32,
+    _format: String,
+    _force: bool,
+) -> Result<Option<cache::ConvertSummary>, String> {
+    Err("Batch conversion is not supported on Android".into())
+}
+
 // ── Helpers ───────────────────────────────────────────────────────────
 
 fn recording_to_metadata(rec: &XcRecording) -> Vec<(String, String)> {
@@ -261,8 +384,13 @@ fn recording_to_metadata(rec: &XcRecording) -> Vec<(String, String)> {
     if !rec.lat.is_empty() && !rec.lon.is_empty() {
         fields.push(("Coordinates".into(), format!("{}, {}", rec.lat, rec.lon)));
     }
+    if !rec.alt.is_empty() {
+        fields.push(("Altitude".into(), format!("{} m", rec.alt)));
+    }
     for (val, label) in [
         (&rec.date, "Date"),
+        (&rec.time, "Time"),
+        (&rec.length, "Length"),
         (&rec.sound_type, "Sound type"),
         (&rec.q, "Quality"),
         (&rec.dvc, "Device"),
@@ -273,11 +401,31 @@ fn recording_to_metadata(rec: &XcRecording) -> Vec<(String, String)> {
             fields.push((label.into(), val.clone()));
         }
     }
+    let also: Vec<&str> = rec.also.iter().map(|s| s.as_str()).filter(|s| !s.is_empty()).collect();
+    if !also.is_empty() {
+        fields.push(("Also heard".into(), also.join(", ")));
+    }
+    for (val, label) in [
+        (&rec.rmk, "Remarks"),
+        (&rec.regnr, "Registration no."),
+    ] {
+        if !val.is_empty() {
+            fields.push((label.into(), val.clone()));
+        }
+    }
     if !rec.url.is_empty() {
         fields.push(("URL".into(), rec.url.clone()));
     } else {
         fields.push(("URL".into(), format!("https://www.xeno-canto.org/{}", rec.id)));
     }
+    for (val, label) in [
+        (&rec.sono_url, "Sonogram"),
+        (&rec.osci_url, "Oscillogram"),
+    ] {
+        if !val.is_empty() {
+            fields.push((label.into(), val.clone()));
+        }
+    }
     fields
 }
 
@@ -301,7 +449,7 @@ fn load_sidecar_metadata_and_hashes(cache_root: &std::path::Path, id: u64) -> (V
     (Vec::new(), None)
 }
 
-fn parse_xc_json_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
+pub(crate) fn parse_xc_json_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
     let mut fields = Vec::new();
     let s = |key: &str| json[key].as_str().unwrap_or("").to_string();
 
@@ -331,13 +479,36 @@ fn parse_xc_json_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
     if !lat.is_empty() && !lon.is_empty() {
         fields.push(("Coordinates".into(), format!("{lat}, {lon}")));
     }
+    let alt = s("alt");
+    if !alt.is_empty() {
+        fields.push(("Altitude".into(), format!("{alt} m")));
+    }
     for (key, label) in [
         ("date", "Date"),
+        ("time", "Time"),
+        ("length", "Length"),
         ("type", "Sound type"),
         ("q", "Quality"),
         ("dvc", "Device"),
         ("mic", "Microphone"),
         ("method", "Method"),
+    ] {
+        let v = s(key);
+        if !v.is_empty() {
+            fields.push((label.into(), v));
+        }
+    }
+    // "also" (background species) is an array in both the API response and the sidecar
+    let also: Vec<&str> = json["also"]
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).filter(|v| !v.is_empty()).collect())
+        .unwrap_or_default();
+    if !also.is_empty() {
+        fields.push(("Also heard".into(), also.join(", ")));
+    }
+    for (key, label) in [
+        ("rmk", "Remarks"),
+        ("regnr", "Registration no."),
         ("url", "URL"),
     ] {
         let v = s(key);
@@ -345,5 +516,12 @@ fn parse_xc_json_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
             fields.push((label.into(), v));
         }
     }
+    // Sidecars store a single URL; raw API JSON has a map of sizes
+    for (key, label) in [("sono", "Sonogram"), ("osci", "Oscillogram")] {
+        let v = api::image_url(&json[key]);
+        if !v.is_empty() {
+            fields.push((label.into(), v));
+        }
+    }
     fields
 }
@@ -341,9 +341,9 @@ impl AnnotationSet {
         let mut set = Self::new(file_identity);
         set.audio_metadata = Some(AudioFileMetadata {
             sample_rate: audio.sample_rate,
-            total_samples: audio.source.total_samples(),
+            total_samples: audio.untrimmed_source().total_samples(),
             channels: audio.channels,
-            duration_secs: audio.duration_secs,
+            duration_secs: audio.untrimmed_source().duration_secs(),
             format: audio.metadata.format.to_string(),
             bits_per_sample: Some(audio.metadata.bits_per_sample),
             data_offset: audio.metadata.data_offset,
@@ -359,6 +359,28 @@ impl AnnotationSet {
         self.modified_at = Some(now_iso8601());
         self.app_version = env!("CARGO_PKG_VERSION").to_string();
     }
+
+    /// Move every annotation time by `secs`. Saved sets are in original-file
+    /// time; a file trimmed on import holds them shifted back by its lead trim.
+    pub fn shift_times(&mut self, secs: f64) {
+        if secs == 0.0 {
+            return;
+        }
+        for a in &mut self.annotations {
+            match &mut a.kind {
+                AnnotationKind::Region(r) => {
+                    r.time_start += secs;
+                    r.time_end += secs;
+                }
+                AnnotationKind::Marker(m) => m.time += secs,
+                AnnotationKind::Measurement(m) => {
+                    m.start_time += secs;
+                    m.end_time += secs;
+                }
+                AnnotationKind::Group(_) => {}
+            }
+        }
+    }
 }
 
 /// In-memory annotation store, indexed parallel to AppState::files.
@@ -76,7 +76,7 @@ pub async fn decode_via_audio_context(
     let duration_secs = samples.len() as f64 / sample_rate as f64;
 
     Ok(AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels,
@@ -99,6 +99,7 @@ fn build_source(all_samples: Vec<f32>, channels: u32, sample_rate: u32) -> (Arc<
         let source = Arc::new(InMemorySource {
             samples: samples.clone(),
             raw_samples: None,
+            exact: None,
             sample_rate,
             channels: 1,
         });
@@ -110,6 +111,7 @@ fn build_source(all_samples: Vec<f32>, channels: u32, sample_rate: u32) -> (Arc<
         let source = Arc::new(InMemorySource {
             samples: samples.clone(),
             raw_samples: Some(raw),
+            exact: None,
             sample_rate,
             channels,
         });
@@ -4,12 +4,15 @@ use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 
 use crate::annotations::{Annotation, AnnotationKind, Region};
-use crate::audio::microphone::encode_wav;
+use crate::audio::convert::{encode_wav_dithered, SampleFormat};
+use crate::audio::labels::{audacity_labels, raven_selection_table, LabelBox};
 use crate::audio::playback::snapshot_params;
 use crate::audio::source::{AudioSource, ChannelView};
-use crate::audio::streaming_playback::{apply_dsp_mode, apply_filters, PlaybackParams, PV_MODE_BOOST_DB};
+use crate::audio::streaming_playback::{apply_dsp_mode, is_passthrough, output_limiter, read_filtered, PlaybackParams, PV_MODE_BOOST_DB};
 use crate::audio::playback::apply_gain;
-use crate::state::{AppState, PlaybackMode, Selection};
+use crate::dsp::fade::{apply_edge_fades, fade_samples};
+use crate::state::{AppState, Bookmark, PlaybackMode, Selection};
+use crate::types::WavMarker;
 
 /// Number of source samples per export chunk (same as streaming playback).
 const CHUNK_SAMPLES: usize = 96_000;
@@ -44,7 +47,30 @@ pub(crate) fn build_export_params(
     } else {
         state.selection.get_untracked()
     };
-    snapshot_params(state, selection, sample_rate)
+    let mut params = snapshot_params(state, selection, sample_rate);
+    // Exports keep the original samples; restored peaks are only a listening aid
+    params.declip = false;
+    // "Band-limit audio" narrows the audio to the cropped band unless a
+    // frequency selection already does
+    if let Some((lo, hi)) = export_focus_band(state).filter(|_| state.export_crop_audio_to_ff.get_untracked()) {
+        let nyquist = sample_rate as f64 / 2.0;
+        let sel_band = params.has_selection
+            && (params.sel_freq_low > 0.0 || params.sel_freq_high < nyquist);
+        if !sel_band && !params.filter_enabled {
+            params.has_selection = true;
+            params.sel_freq_low = lo;
+            params.sel_freq_high = hi;
+        }
+    }
+    params
+}
+
+/// The HFR focus band (Hz) when "Crop to focus band" applies to exports.
+pub(crate) fn export_focus_band(state: &AppState) -> Option<(f64, f64)> {
+    let lo = state.band_ff_freq_lo.get_untracked();
+    let hi = state.band_ff_freq_hi.get_untracked();
+    (state.export_crop_to_ff.get_untracked() && state.hfr_enabled.get_untracked() && hi > lo)
+        .then_some((lo, hi))
 }
 
 /// Process a time range through the DSP pipeline and return processed f32 samples.
@@ -80,12 +106,7 @@ pub(crate) fn process_region(
         };
         let trailing_len = trailing_end - chunk_end;
 
-        let chunk_with_warmup = source.read_region(
-            ChannelView::MonoMix,
-            warmup_start as u64,
-            trailing_end - warmup_start,
-        );
-        let filtered = apply_filters(&chunk_with_warmup, sample_rate, params);
+        let filtered = read_filtered(source, ChannelView::MonoMix, warmup_start, trailing_end, sample_rate, params);
         let processed = apply_dsp_mode(&filtered, sample_rate, params);
 
         if crossfade_mode {
@@ -158,6 +179,15 @@ pub(crate) fn process_region(
     let gain_db = params.gain_db + pv_boost;
     apply_gain(&mut all_samples, gain_db);
 
+    // Same headroom management as playback, so exported het/ZC audio doesn't clip
+    if let Some(mut limiter) = output_limiter(params, sample_rate) {
+        limiter.process(&mut all_samples);
+    }
+
+    // Fade the clip edges so cutting at arbitrary samples doesn't click
+    let edge_fade = fade_samples(params.edge_fade_ms, sample_rate);
+    apply_edge_fades(&mut all_samples, edge_fade, edge_fade);
+
     all_samples
 }
 
@@ -199,19 +229,83 @@ pub(crate) fn trigger_browser_download(data: &[u8], filename: &str) {
     web_sys::Url::revoke_object_url(&url).ok();
 }
 
+/// Cue markers for an exported region: the source file's own cue points that
+/// fall inside it (labels and notes preserved), plus any bookmarks that don't
+/// already coincide with one. Positions are rebased to the region start.
+pub(crate) fn export_markers(
+    source_markers: &[WavMarker],
+    bookmarks: &[Bookmark],
+    sample_rate: u32,
+    start_time: f64,
+    end_time: f64,
+) -> Vec<WavMarker> {
+    let start_sample = (start_time * sample_rate as f64) as u64;
+    let end_sample = (end_time * sample_rate as f64) as u64;
+    // Bookmarks within 1 ms of an existing cue are treated as that cue
+    let tolerance = (sample_rate as u64 / 1000).max(1);
+
+    let mut positions: Vec<(u64, Option<String>, Option<String>)> = source_markers.iter()
+        .filter(|m| m.position >= start_sample && m.position < end_sample)
+        .map(|m| (m.position, m.label.clone(), m.note.clone()))
+        .collect();
+    for bm in bookmarks {
+        let pos = (bm.time * sample_rate as f64).round() as u64;
+        if pos < start_sample || pos >= end_sample {
+            continue;
+        }
+        if positions.iter().any(|(p, _, _)| p.abs_diff(pos) <= tolerance) {
+            continue;
+        }
+        positions.push((pos, None, None));
+    }
+    positions.sort_by_key(|(p, _, _)| *p);
+
+    positions.into_iter()
+        .enumerate()
+        .map(|(i, (pos, label, note))| WavMarker {
+            id: i as u32 + 1,
+            position: pos - start_sample,
+            label,
+            note,
+        })
+        .collect()
+}
+
+/// Sample encoding of processed (non bit-exact) WAV exports.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WavEncoding {
+    pub format: SampleFormat,
+    pub dither: bool,
+}
+
+impl WavEncoding {
+    pub fn from_state(state: AppState) -> Self {
+        Self {
+            format: state.export_wav_format.get_untracked(),
+            dither: state.export_wav_dither.get_untracked(),
+        }
+    }
+}
+
 /// Export a single region as a WAV file and trigger browser download.
+///
+/// When the source is mono, kept its original integer samples at the bit
+/// depth of `encoding`, and nothing in `params` would change them, the region
+/// is written from those, bit-identical to the file. The edge fade is skipped
+/// there, as it would change the samples. Otherwise the mono mix goes through
+/// `process_region`. Either way the WAV is mono in `encoding`'s format.
 pub(crate) fn export_one_region(
     source: &dyn AudioSource,
     sample_rate: u32,
     start_time: f64,
     end_time: f64,
     params: &PlaybackParams,
+    encoding: WavEncoding,
     filename: &str,
     source_filename: &str,
     source_guano: Option<&crate::audio::guano::GuanoMetadata>,
+    markers: &[WavMarker],
 ) {
-    let samples = process_region(source, sample_rate, start_time, end_time, params);
-
     // Determine output sample rate — TimeExpansion slows playback by changing rate
     let output_rate = match params.mode {
         PlaybackMode::TimeExpansion => {
@@ -221,11 +315,50 @@ pub(crate) fn export_one_region(
         _ => sample_rate,
     };
 
-    let mut wav_data = encode_wav(&samples, output_rate);
+    let exact_wav = source.exact_pcm()
+        .filter(|pcm| {
+            pcm.channels == 1
+                && encoding.format != SampleFormat::Float32
+                && pcm.bits_per_sample == encoding.format.bits()
+                && is_passthrough(sample_rate, params)
+        })
+        .and_then(|pcm| {
+            let start = (start_time * sample_rate as f64) as usize;
+            let end = ((end_time * sample_rate as f64) as usize).min(pcm.frame_count());
+            let len = end.saturating_sub(start);
+            match crate::audio::convert::encode_exact_wav(pcm, start, len, output_rate) {
+                Ok(wav) => Some((wav, len)),
+                Err(e) => {
+                    log::warn!("Exact export failed, falling back to f32: {e}");
+                    None
+                }
+            }
+        });
+    let (mut wav_data, frame_count) = match exact_wav {
+        Some(exact) => exact,
+        None => {
+            let samples = process_region(source, sample_rate, start_time, end_time, params);
+            match encode_wav_dithered(&samples, output_rate, encoding.format, encoding.dither) {
+                Ok(wav) => (wav, samples.len()),
+                Err(e) => {
+                    log::error!("WAV export failed: {e}");
+                    return;
+                }
+            }
+        }
+    };
+
+    // Cue markers (sample positions are unchanged by TE, which only relabels the rate)
+    let markers: Vec<WavMarker> = markers.iter()
+        .filter(|m| (m.position as usize) < frame_count)
+        .cloned()
+        .collect();
+    let cue_bytes = crate::audio::wav_encoder::encode_wav_cue_chunks(&markers);
+    crate::audio::wav_encoder::insert_cue_chunks(&mut wav_data, &cue_bytes);
 
     // Build GUANO metadata for the export
     let guano = build_export_guano(
-        output_rate, &samples, params, filename, source_filename, source_guano,
+        output_rate, frame_count, params, filename, source_filename, source_guano,
     );
     crate::audio::guano::append_guano_chunk(&mut wav_data, &guano.to_text());
 
@@ -238,7 +371,7 @@ pub(crate) fn export_one_region(
 /// For TE exports, Length is the "actual length" (WAV length / TE factor) per GUANO spec.
 fn build_export_guano(
     output_rate: u32,
-    samples: &[f32],
+    frame_count: usize,
     params: &PlaybackParams,
     _filename: &str,
     source_filename: &str,
@@ -246,7 +379,7 @@ fn build_export_guano(
 ) -> crate::audio::guano::GuanoMetadata {
     use crate::audio::guano::GuanoMetadata;
     let version = env!("CARGO_PKG_VERSION");
-    let wav_duration_secs = samples.len() as f64 / output_rate as f64;
+    let wav_duration_secs = frame_count as f64 / output_rate as f64;
 
     let mut g = GuanoMetadata::new();
 
@@ -451,11 +584,18 @@ pub fn export_selected(state: &AppState) {
         Some(f) => f,
         None => return,
     };
-    let source = &file.audio.source;
     let sample_rate = file.audio.sample_rate;
+    // A file trimmed on import exports from its untrimmed original; times on
+    // screen are shifted by the trimmed lead-in.
+    let source = file.audio.untrimmed_source();
+    let trim_offset = file.audio.trim_offset_secs();
     let use_region_focus = state.export_use_region_focus.get_untracked();
     let source_filename = &file.name;
     let source_guano = file.audio.metadata.guano.as_ref();
+    let bookmarks = state.bookmarks.get_untracked();
+    let markers_for = |start: f64, end: f64| {
+        export_markers(&file.wav_markers, &bookmarks, sample_rate, start, end)
+    };
 
     // Strip extension from source filename for export naming
     let base_name = file.name.trim_end_matches(".wav")
@@ -474,6 +614,7 @@ pub fn export_selected(state: &AppState) {
         .trim_end_matches(".M4B");
 
     let regions = get_selected_regions(state);
+    let encoding = WavEncoding::from_state(state);
 
     if !regions.is_empty() {
         // Export selected annotation regions
@@ -494,9 +635,10 @@ pub fn export_selected(state: &AppState) {
             let filename = format!("{base_name}{suffix}.wav");
             export_one_region(
                 source.as_ref(), sample_rate,
-                region.time_start, region.time_end,
-                &params, &filename,
+                region.time_start + trim_offset, region.time_end + trim_offset,
+                &params, encoding, &filename,
                 source_filename, source_guano,
+                &markers_for(region.time_start, region.time_end),
             );
         }
     } else if let Some(sel) = state.selection.get_untracked() {
@@ -505,20 +647,225 @@ pub fn export_selected(state: &AppState) {
         let filename = format!("{base_name}_selection.wav");
         export_one_region(
             source.as_ref(), sample_rate,
-            sel.time_start, sel.time_end,
-            &params, &filename,
+            sel.time_start + trim_offset, sel.time_end + trim_offset,
+            &params, encoding, &filename,
             source_filename, source_guano,
+            &markers_for(sel.time_start, sel.time_end),
         );
     } else {
-        // No selection — export the whole file
+        // No selection — export the whole (untrimmed) file
         let params = build_export_params(state, None, false, sample_rate);
-        let duration = file.audio.source.duration_secs();
+        let duration = source.duration_secs();
         let filename = format!("{base_name}_export.wav");
+        let mut markers = markers_for(0.0, file.audio.source.duration_secs());
+        let trim_frames = (trim_offset * sample_rate as f64).round() as u64;
+        for m in &mut markers {
+            m.position += trim_frames;
+        }
         export_one_region(
             source.as_ref(), sample_rate,
             0.0, duration,
-            &params, &filename,
+            &params, encoding, &filename,
             source_filename, source_guano,
+            &markers,
         );
     }
 }
+
+/// Label file formats for other annotation tools.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LabelFormat {
+    /// Raven Pro selection table.
+    Raven,
+    /// Audacity label track.
+    Audacity,
+}
+
+/// Regions, markers, the current selection and bookmarks of the current
+/// file as label boxes, in time order.
+fn label_boxes(state: &AppState, file_idx: usize) -> Vec<LabelBox> {
+    let mut boxes = Vec::new();
+    let store = state.annotation_store.get_untracked();
+    if let Some(Some(set)) = store.sets.get(file_idx) {
+        for a in &set.annotations {
+            match &a.kind {
+                AnnotationKind::Region(r) => boxes.push(LabelBox {
+                    start: r.time_start,
+                    end: r.time_end,
+                    freq_low: r.freq_low,
+                    freq_high: r.freq_high,
+                    label: r.label.clone().unwrap_or_default(),
+                }),
+                AnnotationKind::Marker(m) => boxes.push(LabelBox {
+                    start: m.time,
+                    end: m.time,
+                    freq_low: None,
+                    freq_high: None,
+                    label: m.label.clone().unwrap_or_default(),
+                }),
+                _ => {}
+            }
+        }
+    }
+    if let Some(sel) = state.selection.get_untracked() {
+        // Skip the selection when it is just an annotation region selected
+        let duplicate = boxes.iter().any(|b| {
+            b.start == sel.time_start && b.end == sel.time_end
+                && b.freq_low == sel.freq_low && b.freq_high == sel.freq_high
+        });
+        if !duplicate {
+            boxes.push(LabelBox {
+                start: sel.time_start,
+                end: sel.time_end,
+                freq_low: sel.freq_low,
+                freq_high: sel.freq_high,
+                label: "Selection".into(),
+            });
+        }
+    }
+    for b in state.bookmarks.get_untracked() {
+        boxes.push(LabelBox { start: b.time, end: b.time, freq_low: None, freq_high: None, label: "Bookmark".into() });
+    }
+    boxes.sort_by(|a, b| a.start.total_cmp(&b.start));
+    boxes
+}
+
+/// Download the current file's selection, annotations and bookmarks as a
+/// Raven selection table or Audacity label file.
+pub fn export_labels(state: &AppState, format: LabelFormat) {
+    let Some(idx) = state.current_file_index.get_untracked() else { return };
+    let Some(file) = state.current_file() else { return };
+    let mut boxes = label_boxes(state, idx);
+    // Label files refer to the original recording, not the trimmed view
+    let trim_offset = file.audio.trim_offset_secs();
+    for b in &mut boxes {
+        b.start += trim_offset;
+        b.end += trim_offset;
+    }
+    if boxes.is_empty() {
+        state.show_info_toast("Nothing to export: no selection, annotations or bookmarks");
+        return;
+    }
+    let base_name = file.name.rsplit_once('.').map_or(file.name.as_str(), |(stem, _)| stem);
+    let (text, filename) = match format {
+        LabelFormat::Raven => (
+            raven_selection_table(&boxes, file.audio.sample_rate as f64 / 2.0),
+            format!("{base_name}.Table.1.selections.txt"),
+        ),
+        LabelFormat::Audacity => (audacity_labels(&boxes), format!("{base_name}_labels.txt")),
+    };
+    trigger_browser_download(text.as_bytes(), &filename);
+}
+
+/// Longest flow field export, in STFT columns.
+const MAX_FLOW_EXPORT_COLS: usize = 20_000;
+
+/// Download the shift field behind the flow view as CSV, for the selection
+/// (or the visible view) of the current file. Uses the selected flow
+/// algorithm at the resolution currently drawn, and records both in the
+/// file header so the numbers can be interpreted on their own.
+pub fn export_flow_field(state: &AppState) {
+    use crate::canvas::flow::{compute_flow_data, flow_field_csv, FlowAlgo, FlowShiftParams};
+    use crate::state::SpectParams;
+
+    let Some(file) = state.current_file() else { return };
+    let Some(spect) = state.spect_params() else { return };
+    let algo = state.spectrogram_display.get_untracked().flow_algo();
+    let shift_params = FlowShiftParams::new(
+        state.flow_radius.get_untracked(),
+        state.flow_max_displacement.get_untracked(),
+    );
+    let sr = file.audio.sample_rate;
+    let duration = file.audio.duration_secs;
+    let trim_offset = file.audio.trim_offset_secs();
+
+    let (t0, t1, band) = match state.selection.get_untracked() {
+        Some(sel) if sel.time_end > sel.time_start => (sel.time_start, sel.time_end, sel.freq_low.zip(sel.freq_high)),
+        _ => {
+            let scroll = state.scroll_offset.get_untracked();
+            let visible = crate::viewport::visible_time(
+                state.spectrogram_canvas_width.get_untracked(),
+                state.zoom_level.get_untracked(),
+                file.spectrogram.time_resolution,
+            );
+            (scroll, scroll + visible, None)
+        }
+    };
+    let (t0, t1) = (t0.clamp(0.0, duration), t1.clamp(0.0, duration));
+    let SpectParams { fft_size, hop, .. } = spect;
+    let cols = ((t1 - t0) * sr as f64 / hop as f64) as usize;
+    if cols < 2 {
+        state.show_info_toast("Nothing to export: the range is shorter than two columns");
+        return;
+    }
+    if cols > MAX_FLOW_EXPORT_COLS {
+        state.show_error_toast(format!(
+            "Too long to export at this zoom ({cols} columns); select a shorter range or zoom out",
+        ));
+        return;
+    }
+
+    // Phase algorithms need one extra frame to difference against
+    let start = (t0 * sr as f64) as u64;
+    let len = (cols + 1) * hop + fft_size;
+    let cv = state.channel_view.get_untracked();
+    let samples = file.audio.source.read_region(cv, start, len);
+
+    let (shifts, width, height) = match algo {
+        FlowAlgo::PhaseCoherence | FlowAlgo::Phase => {
+            let data = if algo == FlowAlgo::Phase {
+                crate::dsp::harmonics::compute_tile_phase_angle_data(&samples, cols, fft_size, hop)
+            } else {
+                crate::dsp::harmonics::compute_tile_phase_data(&samples, cols, fft_size, hop)
+            };
+            (data.flow_shifts, data.width as usize, data.height as usize)
+        }
+        FlowAlgo::Optical | FlowAlgo::Centroid | FlowAlgo::Gradient => {
+            let audio = crate::types::AudioData::from_mono(samples, sr);
+            let spectrogram = crate::dsp::fft::compute_spectrogram(&audio, fft_size, hop);
+            let data = compute_flow_data(&spectrogram, algo, shift_params);
+            (data.shifts, data.width as usize, data.height as usize)
+        }
+    };
+    if width == 0 || height == 0 {
+        state.show_info_toast("Nothing to export: no complete columns in range");
+        return;
+    }
+
+    let freq_res = spect.freq_resolution();
+    let bins = match band {
+        Some((lo, hi)) => {
+            let lo_bin = ((lo / freq_res).floor() as usize).min(height - 1);
+            let hi_bin = ((hi / freq_res).ceil() as usize).min(height - 1);
+            lo_bin..hi_bin + 1
+        }
+        None => 0..height,
+    };
+    let mut metadata = vec![
+        ("file", file.name.clone()),
+        ("algorithm", algo.label().to_string()),
+        ("shift", algo.shift_units().to_string()),
+        ("spectrogram", spect.summary()),
+        ("sample_rate_hz", sr.to_string()),
+        ("channel", format!("{cv:?}")),
+    ];
+    if matches!(algo, FlowAlgo::Optical | FlowAlgo::Centroid) {
+        metadata.push(("radius_bins", shift_params.radius.to_string()));
+    }
+    if algo == FlowAlgo::Optical {
+        metadata.push(("max_displacement_bins", shift_params.max_displacement.to_string()));
+    }
+    let time_res = spect.time_resolution();
+    // Phase deviation columns sit between frames c and c + 1
+    let col_offset = if algo == FlowAlgo::PhaseCoherence { 1.0 } else { 0.0 };
+    let csv = flow_field_csv(
+        &shifts, width, height, bins,
+        |c| trim_offset + t0 + (c as f64 + col_offset) * time_res,
+        |b| b as f64 * freq_res,
+        &metadata,
+    );
+
+    let base_name = file.name.rsplit_once('.').map_or(file.name.as_str(), |(stem, _)| stem);
+    let algo_key = algo.label().to_lowercase().replace(' ', "_");
+    trigger_browser_download(csv.as_bytes(), &format!("{base_name}_{algo_key}.csv"));
+}
@@ -0,0 +1,98 @@
+//! Lightweight per-file metrics for sorting the Files panel.
+//!
+//! Each metric is computed from the first analysis window of the file
+//! (`DEFAULT_ANALYSIS_WINDOW_SECS`), so scanning a folder of long recordings
+//! stays cheap. Files are processed one at a time, yielding to the browser
+//! between steps, and each result is written back to `LoadedFile::metrics`
+//! as soon as it is ready so the list can re-sort progressively.
+
+use std::cell::Cell;
+use leptos::prelude::*;
+use crate::audio::source::{ChannelView, DEFAULT_ANALYSIS_WINDOW_SECS};
+use crate::canvas::tile_cache::yield_to_browser;
+use crate::dsp::{psd, pulse_detect, wsnr};
+use crate::state::AppState;
+
+/// FFT size for the peak-frequency PSD.
+const PSD_NFFT: usize = 1024;
+
+/// Ignore DC and low-frequency rumble when picking the peak frequency.
+const MIN_PEAK_FREQ_HZ: f64 = 1000.0;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct FileMetrics {
+    pub wsnr_db: f64,
+    pub wsnr_grade: wsnr::WsnrGrade,
+    /// Strongest PSD peak above `MIN_PEAK_FREQ_HZ`. None if nothing stands out.
+    pub peak_freq_hz: Option<f64>,
+    /// Number of pulses found with the default detection parameters.
+    pub call_count: usize,
+}
+
+thread_local! {
+    static SCAN_RUNNING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Compute metrics for a block of mono samples.
+pub fn compute_metrics(samples: &[f32], sample_rate: u32) -> FileMetrics {
+    let w = wsnr::analyze_wsnr(samples, sample_rate);
+    let nyquist = sample_rate as f64 / 2.0;
+    let peak_freq_hz = if samples.len() >= PSD_NFFT && nyquist > MIN_PEAK_FREQ_HZ {
+        psd::compute_psd(samples, sample_rate, PSD_NFFT, Some((MIN_PEAK_FREQ_HZ, nyquist)))
+            .peaks
+            .first()
+            .map(|p| p.freq_hz)
+    } else {
+        None
+    };
+    let call_count = pulse_detect::count_pulses(
+        samples,
+        sample_rate,
+        &pulse_detect::PulseDetectionParams::default(),
+    );
+    FileMetrics {
+        wsnr_db: w.snr_db,
+        wsnr_grade: w.grade,
+        peak_freq_hz,
+        call_count,
+    }
+}
+
+/// Compute metrics for every loaded file that doesn't have them yet.
+///
+/// Runs in the background, one file at a time. Calling this while a scan is
+/// already running does nothing — the running scan picks up files added in
+/// the meantime before it finishes.
+pub fn start_metrics_scan(state: AppState) {
+    if SCAN_RUNNING.with(|r| r.replace(true)) {
+        return;
+    }
+
+    leptos::task::spawn_local(async move {
+        loop {
+            yield_to_browser().await;
+
+            // Next file that has finished loading but has no metrics yet
+            let next = state.files.with_untracked(|files| {
+                files.iter().find(|f| f.metrics.is_none() && f.loading_id.is_none()).map(|f| {
+                    let sr = f.audio.sample_rate;
+                    let total = f.audio.source.total_samples() as usize;
+                    let window = ((DEFAULT_ANALYSIS_WINDOW_SECS * sr as f64) as usize).min(total);
+                    let samples = f.audio.source.read_region(ChannelView::MonoMix, 0, window);
+                    (f.name.clone(), f.add_order, samples, sr)
+                })
+            });
+            let Some((name, add_order, samples, sample_rate)) = next else { break };
+
+            let metrics = compute_metrics(&samples, sample_rate);
+
+            // Match by identity rather than index: files may have been closed meanwhile.
+            state.files.update(|files| {
+                if let Some(f) = files.iter_mut().find(|f| f.add_order == add_order && f.name == name) {
+                    f.metrics = Some(metrics);
+                }
+            });
+        }
+        SCAN_RUNNING.with(|r| r.set(false));
+    });
+}
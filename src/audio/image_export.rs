@@ -0,0 +1,346 @@
+//! PNG export: render the spectrogram of the selected region (or selection,
+//! or whole file) to a still image, optionally cropped to the HFR focus band.
+//! The visible view can also be copied to the clipboard as an image, and the
+//! whole recording can be exported at a fixed pixels-per-second scale, split
+//! into numbered parts when it is wider than a browser canvas allows.
+
+use leptos::prelude::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+
+use crate::audio::export::{export_focus_band, get_selected_regions};
+use crate::audio::video_export::{render_frame, snapshot_render_params, RenderParams};
+use crate::canvas::tile_cache;
+use crate::state::{AppState, SpectParams};
+
+/// Widest canvas browsers reliably allocate; wider whole-recording exports
+/// are split into parts of this width.
+pub const MAX_PART_WIDTH: u32 = 16_384;
+/// Whole-recording exports above this many pixels get a size warning.
+pub const LARGE_EXPORT_PIXELS: u64 = 100_000_000;
+/// Longest wait for one part's tiles before rendering it with what is cached.
+const TILE_WAIT_MS: f64 = 30_000.0;
+
+/// Export the spectrogram as a PNG and trigger a browser download.
+pub fn export_png(state: &AppState) {
+    if let Err(e) = export_png_impl(state) {
+        let msg = format!("{:?}", e);
+        log::error!("PNG export failed: {msg}");
+        state.video_export_status.set(Some(format!("Export failed: {msg}")));
+    }
+}
+
+fn export_png_impl(state: &AppState) -> Result<(), JsValue> {
+    let file = state.current_file().ok_or_else(|| JsValue::from_str("No file loaded"))?;
+
+    // Same time range as WAV/MP4 export
+    let regions = get_selected_regions(state);
+    let (start_time, end_time) = if !regions.is_empty() {
+        let r = &regions[0].1;
+        (r.time_start, r.time_end)
+    } else if let Some(sel) = state.selection.get_untracked() {
+        (sel.time_start, sel.time_end)
+    } else {
+        (0.0, file.audio.source.duration_secs())
+    };
+    let (canvas, filename) = render_png_canvas(state, start_time, end_time)?;
+    download_canvas_png(&canvas, &filename)
+}
+
+/// Copy the visible part of the spectrogram to the clipboard as a PNG.
+/// Where the browser can't write images to the clipboard, the image is
+/// downloaded instead.
+pub fn copy_view_to_clipboard(state: &AppState) {
+    let state = *state;
+    let rendered = view_time_range(&state).and_then(|(start, end)| render_png_canvas(&state, start, end));
+    let (canvas, filename) = match rendered {
+        Ok(r) => r,
+        Err(e) => {
+            let msg = format!("{:?}", e);
+            log::error!("Copy image failed: {msg}");
+            state.show_error_toast(format!("Copy image failed: {msg}"));
+            return;
+        }
+    };
+    // Start the write now, while still inside the user gesture (Safari requires it)
+    let write = write_canvas_to_clipboard(&canvas);
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = match write {
+            Ok(promise) => wasm_bindgen_futures::JsFuture::from(promise).await.map(|_| ()),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => state.show_info_toast("Spectrogram image copied"),
+            Err(e) => {
+                log::warn!("Image clipboard write unavailable ({e:?}); downloading instead");
+                match download_canvas_png(&canvas, &filename) {
+                    Ok(()) => state.show_info_toast("Can't copy images here \u{2014} downloaded the PNG instead"),
+                    Err(e) => state.show_error_toast(format!("Copy image failed: {e:?}")),
+                }
+            }
+        }
+    });
+}
+
+/// Time range currently shown in the main spectrogram view.
+fn view_time_range(state: &AppState) -> Result<(f64, f64), JsValue> {
+    let file = state.current_file().ok_or_else(|| JsValue::from_str("No file loaded"))?;
+    let visible_time = crate::viewport::visible_time(
+        state.spectrogram_canvas_width.get_untracked(),
+        state.zoom_level.get_untracked(),
+        file.spectrogram.time_resolution,
+    );
+    let scroll = state.scroll_offset.get_untracked();
+    Ok((scroll.max(0.0), (scroll + visible_time).min(file.audio.source.duration_secs())))
+}
+
+/// `navigator.clipboard.write([new ClipboardItem({"image/png": blob})])`,
+/// with the blob supplied as a promise so the item is created synchronously.
+fn write_canvas_to_clipboard(canvas: &HtmlCanvasElement) -> Result<js_sys::Promise, JsValue> {
+    let window = web_sys::window().ok_or_else(|| JsValue::from_str("No window"))?;
+    let item_ctor: js_sys::Function = js_sys::Reflect::get(&window, &"ClipboardItem".into())?
+        .dyn_into()
+        .map_err(|_| JsValue::from_str("ClipboardItem not supported"))?;
+    let canvas = canvas.clone();
+    let blob = js_sys::Promise::new(&mut |resolve, reject| {
+        let reject_null = reject.clone();
+        let on_blob = Closure::once_into_js(move |blob: JsValue| {
+            if blob.is_null() {
+                let _ = reject_null.call1(&JsValue::NULL, &"Canvas could not be encoded".into());
+            } else {
+                let _ = resolve.call1(&JsValue::NULL, &blob);
+            }
+        });
+        if let Err(e) = canvas.to_blob_with_type(on_blob.unchecked_ref(), "image/png") {
+            let _ = reject.call1(&JsValue::NULL, &e);
+        }
+    });
+    let data = js_sys::Object::new();
+    js_sys::Reflect::set(&data, &"image/png".into(), &blob)?;
+    let item = js_sys::Reflect::construct(&item_ctor, &js_sys::Array::of1(&data))?;
+    let clipboard = window.navigator().clipboard();
+    let write: js_sys::Function = js_sys::Reflect::get(&clipboard, &"write".into())?
+        .dyn_into()
+        .map_err(|_| JsValue::from_str("Clipboard image writes not supported"))?;
+    write.call1(&clipboard, &js_sys::Array::of1(&item))?.dyn_into()
+}
+
+/// Render `start_time..end_time` of the current file at the export resolution.
+/// Returns the canvas and a suggested file name.
+fn render_png_canvas(state: &AppState, start_time: f64, end_time: f64) -> Result<(HtmlCanvasElement, String), JsValue> {
+    let file = state.current_file().ok_or_else(|| JsValue::from_str("No file loaded"))?;
+    let file_idx = state.current_file_index.get_untracked().unwrap();
+    if end_time <= start_time {
+        return Err(JsValue::from_str("Invalid time range"));
+    }
+
+    let canvas_w_hint = state.spectrogram_canvas_width.get_untracked().max(320.0) as u32;
+    let (img_w, img_h) = state.video_resolution.get_untracked().dimensions(canvas_w_hint, 400);
+
+    let mut render = snapshot_render_params(state, &file, file_idx, start_time, end_time, img_w, img_h);
+    let band = apply_focus_band(state, &mut render);
+    let canvas = draw_export_canvas(state, &render, band, start_time, end_time - start_time, file.spectrogram.sample_rate)?;
+
+    let base_name = export_base_name(&file.name);
+    let filename = format!("{base_name}{}.png", band_suffix(band));
+
+    Ok((canvas, filename))
+}
+
+/// Crop the render to the HFR focus band when that export option is on.
+/// Returns the band actually used.
+fn apply_focus_band(state: &AppState, render: &mut RenderParams) -> Option<(f64, f64)> {
+    let band = export_focus_band(state).map(|(lo, hi)| (lo, hi.min(render.file_max_freq)));
+    if let Some((lo, hi)) = band {
+        render.min_freq = lo;
+        render.max_freq = hi;
+        render.freq_crop_lo = lo / render.file_max_freq;
+        render.freq_crop_hi = hi / render.file_max_freq;
+    }
+    band
+}
+
+/// Draw `visible_time` seconds from `start_time` across a new canvas of the
+/// render's size, with band labels and the parameter caption as configured.
+fn draw_export_canvas(
+    state: &AppState,
+    render: &RenderParams,
+    band: Option<(f64, f64)>,
+    start_time: f64,
+    visible_time: f64,
+    sample_rate: u32,
+) -> Result<HtmlCanvasElement, JsValue> {
+    let (img_w, img_h) = (render.canvas_w, render.canvas_h);
+    let document = web_sys::window().unwrap().document().unwrap();
+    let canvas: HtmlCanvasElement = document.create_element("canvas")?.dyn_into()?;
+    canvas.set_width(img_w);
+    canvas.set_height(img_h);
+    let ctx: CanvasRenderingContext2d = canvas.get_context("2d")?.unwrap().dyn_into()?;
+
+    let zoom = img_w as f64 / (visible_time / render.time_res);
+    render_frame(&ctx, render, start_time / render.time_res, zoom, visible_time, start_time);
+    if let Some((lo, hi)) = band {
+        draw_band_limits(&ctx, lo, hi, img_w as f64, img_h as f64);
+    }
+    if state.export_params_caption.get_untracked() {
+        let lod = tile_cache::ideal_lod(zoom);
+        let params = SpectParams::for_lod(state.spect_fft_mode.get_untracked(), lod, sample_rate);
+        draw_caption(&ctx, &params.summary(), img_h as f64);
+    }
+    Ok(canvas)
+}
+
+fn export_base_name(name: &str) -> &str {
+    name
+        .trim_end_matches(".wav").trim_end_matches(".WAV")
+        .trim_end_matches(".w4v").trim_end_matches(".W4V")
+        .trim_end_matches(".flac").trim_end_matches(".FLAC")
+        .trim_end_matches(".ogg").trim_end_matches(".OGG")
+        .trim_end_matches(".mp3").trim_end_matches(".MP3")
+        .trim_end_matches(".m4a").trim_end_matches(".M4A")
+        .trim_end_matches(".m4b").trim_end_matches(".M4B")
+}
+
+fn band_suffix(band: Option<(f64, f64)>) -> String {
+    match band {
+        Some((lo, hi)) => format!("_{:.0}-{:.0}kHz", lo / 1000.0, hi / 1000.0),
+        None => String::new(),
+    }
+}
+
+/// Size of a whole-recording export of the current file at the chosen
+/// pixels-per-second: (total width, height, number of parts). Reads its
+/// signals tracked so the export panel can show it live.
+pub fn full_export_size(state: &AppState) -> Option<(u64, u32, usize)> {
+    let idx = state.current_file_index.get()?;
+    let duration = state.files.with(|files| files.get(idx).map(|f| f.audio.source.duration_secs()))?;
+    let canvas_w_hint = state.spectrogram_canvas_width.get_untracked().max(320.0) as u32;
+    let (_, img_h) = state.video_resolution.get().dimensions(canvas_w_hint, 400);
+    let width = (duration * state.export_full_px_per_sec.get()).ceil().max(1.0) as u64;
+    Some((width, img_h, width.div_ceil(MAX_PART_WIDTH as u64) as usize))
+}
+
+/// Export the whole current recording at the chosen pixels-per-second,
+/// downloading one PNG or, when too wide for one canvas, numbered parts.
+pub fn export_full_png(state: &AppState) {
+    let state = *state;
+    if state.export_full_progress.get_untracked().is_some() {
+        return;
+    }
+    wasm_bindgen_futures::spawn_local(async move {
+        let result = export_full_png_impl(&state).await;
+        state.export_full_progress.set(None);
+        match result {
+            Ok(1) => state.show_info_toast("Exported the whole recording"),
+            Ok(parts) => state.show_info_toast(format!("Exported the whole recording as {parts} images")),
+            Err(e) => {
+                let msg = format!("{:?}", e);
+                log::error!("Whole-recording PNG export failed: {msg}");
+                state.show_error_toast(format!("Export failed: {msg}"));
+            }
+        }
+    });
+}
+
+async fn export_full_png_impl(state: &AppState) -> Result<usize, JsValue> {
+    let file = state.current_file().ok_or_else(|| JsValue::from_str("No file loaded"))?;
+    let file_idx = state.current_file_index.get_untracked().unwrap();
+    let (total_w, img_h, parts) = full_export_size(state).ok_or_else(|| JsValue::from_str("No file loaded"))?;
+    let total_samples = file.audio.source.total_samples() as usize;
+    let secs_per_px = file.audio.source.duration_secs() / total_w as f64;
+    let base_name = export_base_name(&file.name);
+
+    for part in 0..parts {
+        state.export_full_progress.set(Some((part, parts)));
+        let x0 = part as u64 * MAX_PART_WIDTH as u64;
+        let part_w = (total_w - x0).min(MAX_PART_WIDTH as u64) as u32;
+        let start_time = x0 as f64 * secs_per_px;
+        let visible_time = part_w as f64 * secs_per_px;
+
+        let mut render = snapshot_render_params(state, &file, file_idx, start_time, start_time + visible_time, part_w, img_h);
+        let band = apply_focus_band(state, &mut render);
+        let zoom = part_w as f64 / (visible_time / render.time_res);
+        wait_for_tiles(state, file_idx, total_samples, file.audio.sample_rate, zoom, start_time, start_time + visible_time).await;
+        let canvas = draw_export_canvas(state, &render, band, start_time, visible_time, file.spectrogram.sample_rate)?;
+
+        let filename = if parts == 1 {
+            format!("{base_name}_full{}.png", band_suffix(band))
+        } else {
+            format!("{base_name}_full{}_part{:02}of{:02}.png", band_suffix(band), part + 1, parts)
+        };
+        download_canvas_png(&canvas, &filename)?;
+        tile_cache::yield_to_browser().await;
+    }
+    Ok(parts)
+}
+
+/// Schedule the magnitude tiles covering `start..end` at the level the
+/// renderer will pick for `zoom`, and wait until they are all cached. Gives
+/// up after `TILE_WAIT_MS` so a stuck tile can't stall the export; anything
+/// still missing is drawn from a coarser level.
+async fn wait_for_tiles(state: &AppState, file_idx: usize, total_samples: usize, sample_rate: u32, zoom: f64, start: f64, end: f64) {
+    if sample_rate == 0 {
+        return;
+    }
+    let started = js_sys::Date::now();
+    loop {
+        let lod = tile_cache::ideal_lod(zoom);
+        let tile_count = tile_cache::tile_count_for_samples(total_samples, lod);
+        if tile_count == 0 {
+            return;
+        }
+        let tile_secs = (tile_cache::TILE_COLS * tile_cache::LOD_CONFIGS[lod as usize].hop_size) as f64 / sample_rate as f64;
+        let first = ((start / tile_secs).floor() as usize).min(tile_count - 1);
+        let last = ((end / tile_secs).floor() as usize).min(tile_count - 1);
+        let missing: Vec<usize> = (first..=last)
+            .filter(|&t| tile_cache::get_tile(file_idx, lod, t).is_none())
+            .collect();
+        if missing.is_empty() || js_sys::Date::now() - started > TILE_WAIT_MS {
+            return;
+        }
+        for tile_idx in missing {
+            tile_cache::schedule_tile_lod(*state, file_idx, lod, tile_idx);
+        }
+        tile_cache::yield_to_browser().await;
+    }
+}
+
+fn download_canvas_png(canvas: &HtmlCanvasElement, filename: &str) -> Result<(), JsValue> {
+    let document = web_sys::window().unwrap().document().unwrap();
+    let url = canvas.to_data_url_with_type("image/png")?;
+    let a: web_sys::HtmlAnchorElement = document.create_element("a")?.dyn_into()?;
+    a.set_href(&url);
+    a.set_download(filename);
+    a.set_attribute("style", "display:none").ok();
+    let body = document.body().ok_or_else(|| JsValue::from_str("No document body"))?;
+    body.append_child(&a)?;
+    a.click();
+    body.remove_child(&a).ok();
+    Ok(())
+}
+
+/// Write the analysis parameters in the bottom-left corner, so a figure
+/// carries the settings a paper needs to state.
+fn draw_caption(ctx: &CanvasRenderingContext2d, text: &str, h: f64) {
+    ctx.set_font("11px sans-serif");
+    ctx.set_text_align("left");
+    ctx.set_text_baseline("bottom");
+    ctx.set_fill_style_str("rgba(0, 0, 0, 0.6)");
+    let width = ctx.measure_text(text).map(|m| m.width()).unwrap_or(0.0);
+    ctx.fill_rect(0.0, h - 18.0, width + 8.0, 18.0);
+    ctx.set_fill_style_str("rgba(255, 255, 255, 0.9)");
+    let _ = ctx.fill_text(text, 4.0, h - 4.0);
+}
+
+/// Label the top and bottom edges of a band-cropped image with the band limits,
+/// so the figure's frequency extent is explicit even between tick marks.
+fn draw_band_limits(ctx: &CanvasRenderingContext2d, lo: f64, hi: f64, w: f64, h: f64) {
+    ctx.set_font("bold 12px sans-serif");
+    ctx.set_text_align("right");
+    ctx.set_fill_style_str("rgba(255, 255, 255, 0.9)");
+    ctx.set_text_baseline("top");
+    let _ = ctx.fill_text(&format!("{:.1} kHz", hi / 1000.0), w - 4.0, 4.0);
+    ctx.set_text_baseline("bottom");
+    let _ = ctx.fill_text(&format!("{:.1} kHz", lo / 1000.0), w - 4.0, h - 4.0);
+}
@@ -17,6 +17,10 @@ use std::sync::Arc;
 const LIVE_FFT: usize = 1024;
 const LIVE_HOP: usize = 256;
 
+/// Minimum gap between level-triggered bookmarks, so one burst of calls
+/// gives one marker rather than dozens.
+const TRIGGER_HOLDOFF_SECS: f64 = 1.0;
+
 /// Clean up the live recording file when finalization fails (empty samples,
 /// command error, etc.).  If the file has no audio data and no preview,
 /// removes it entirely and fixes `current_file_index`.  Otherwise marks it
@@ -62,26 +66,18 @@ pub(crate) fn cleanup_failed_recording(state: &AppState) {
 /// Create a live LoadedFile at recording start for real-time visualization.
 /// Returns the file index where the live file was inserted.
 pub(crate) fn start_live_recording(state: &AppState, sample_rate: u32) -> usize {
-    let now = js_sys::Date::new_0();
-    let name = format!(
-        "batcap_{:04}{:02}{:02}_{:02}{:02}{:02}.wav",
-        now.get_full_year(),
-        now.get_month() + 1,
-        now.get_date(),
-        now.get_hours(),
-        now.get_minutes(),
-        now.get_seconds(),
-    );
+    let name = generate_recording_name(state);
 
     let samples: Arc<Vec<f32>> = Arc::new(Vec::new());
     let source = Arc::new(InMemorySource {
         samples: samples.clone(),
         raw_samples: None,
+        exact: None,
         sample_rate,
         channels: 1,
     });
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels: 1,
@@ -135,6 +131,9 @@ pub(crate) fn start_live_recording(state: &AppState, sample_rate: u32) -> usize
             loading_id: None,
             min_display_freq: None,
             max_display_freq: None,
+            notes: String::new(),
+            metrics: None,
+            identical_channels: false,
         });
     });
 
@@ -159,11 +158,12 @@ pub(crate) fn start_live_listening(state: &AppState, sample_rate: u32) -> usize
     let source = Arc::new(InMemorySource {
         samples: samples.clone(),
         raw_samples: None,
+        exact: None,
         sample_rate,
         channels: 1,
     });
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels: 1,
@@ -217,6 +217,9 @@ pub(crate) fn start_live_listening(state: &AppState, sample_rate: u32) -> usize
             loading_id: None,
             min_display_freq: None,
             max_display_freq: None,
+            notes: String::new(),
+            metrics: None,
+            identical_channels: false,
         });
     });
 
@@ -282,15 +285,7 @@ pub(crate) fn convert_listen_to_recording(state: &AppState, sample_rate: u32) ->
     let ts = js_sys::Date::new(&wasm_bindgen::JsValue::from_f64(
         js_sys::Date::now() - preroll_ms,
     ));
-    let name = format!(
-        "batcap_{:04}{:02}{:02}_{:02}{:02}{:02}.wav",
-        ts.get_full_year(),
-        ts.get_month() + 1,
-        ts.get_date(),
-        ts.get_hours(),
-        ts.get_minutes(),
-        ts.get_seconds(),
-    );
+    let name = recording_file_name(state, &ts);
 
     state.files.update(|files| {
         if let Some(f) = files.get_mut(file_index) {
@@ -332,6 +327,16 @@ pub(crate) fn spawn_live_processing_loop(state: AppState, file_index: usize, sam
         let mut last_snapshot_len: usize = 0;
         let is_tauri = state.is_tauri;
 
+        // Level-trigger bookkeeping. Buffer positions shift when the front of
+        // the buffer is trimmed, so track how much was trimmed since recording
+        // began and where the saved file's first sample sits in the buffer.
+        let mut was_listening = false;
+        let mut was_recording = false;
+        let mut record_origin: usize = 0;
+        let mut trimmed_while_recording: usize = 0;
+        let mut trigger_scan_len: usize = 0;
+        let mut last_trigger_pos: Option<usize> = None;
+
         loop {
             let p = js_sys::Promise::new(&mut |resolve, _| {
                 if let Some(w) = web_sys::window() {
@@ -422,7 +427,7 @@ pub(crate) fn spawn_live_processing_loop(state: AppState, file_index: usize, sam
                 }
 
                 // Push to waterfall for direct rendering
-                live_waterfall::push_columns(&new_cols);
+                live_waterfall::push_columns(&new_cols, state.mic_live_smoothing_secs.get_untracked());
 
                 // Update file metadata (recording OR listening with a live file)
                 let has_live_file = state.mic_live_file_idx.get_untracked() == Some(file_index);
@@ -452,7 +457,7 @@ pub(crate) fn spawn_live_processing_loop(state: AppState, file_index: usize, sam
                         let snapshot = Arc::new(samples.to_vec());
                         state.files.update(|files| {
                             if let Some(f) = files.get_mut(file_index) {
-                                f.audio.samples = snapshot;
+                                f.audio.samples = snapshot.into();
                             }
                         });
                         last_snapshot_len = samples.len();
@@ -471,6 +476,55 @@ pub(crate) fn spawn_live_processing_loop(state: AppState, file_index: usize, sam
                 (true, normalized)
             });
 
+            // Level-triggered bookmarks: mark where the input first crosses the
+            // trigger level, at most once per TRIGGER_HOLDOFF_SECS. Times are
+            // on the recording's own timeline so they line up with the saved file.
+            if is_recording {
+                let starting = !was_recording;
+                if starting {
+                    trimmed_while_recording = 0;
+                    last_trigger_pos = None;
+                    state.mic_trigger_times.set(Vec::new());
+                }
+                let threshold = state.mic_trigger_level_db.get_untracked()
+                    .map(|db| 10f32.powf(db as f32 / 20.0));
+                let has_preroll = state.mic_preroll_samples.get_untracked() > 0;
+                let holdoff = (TRIGGER_HOLDOFF_SECS * sample_rate as f64) as usize;
+                let hits = with_live_samples(is_tauri, |samples| -> Vec<f64> {
+                    if starting {
+                        // Native listen → record without pre-roll: the backend
+                        // writes only what arrives from now on, so the saved
+                        // file starts here rather than at the listen buffer's start.
+                        let native_fresh = is_tauri && was_listening && !has_preroll;
+                        record_origin = if native_fresh { samples.len() } else { 0 };
+                        trigger_scan_len = record_origin;
+                    }
+                    let start = trigger_scan_len.min(samples.len());
+                    trigger_scan_len = samples.len();
+                    let Some(threshold) = threshold else { return Vec::new() };
+                    let mut hits = Vec::new();
+                    for (i, &s) in samples[start..].iter().enumerate() {
+                        if s.abs() < threshold {
+                            continue;
+                        }
+                        let pos = (start + i + trimmed_while_recording).saturating_sub(record_origin);
+                        if last_trigger_pos.is_none_or(|last| pos >= last + holdoff) {
+                            last_trigger_pos = Some(pos);
+                            hits.push(pos as f64 / sample_rate as f64);
+                        }
+                    }
+                    hits
+                });
+                if !hits.is_empty() {
+                    state.bookmarks.update(|bm| {
+                        bm.extend(hits.iter().map(|&time| crate::state::Bookmark { time }));
+                    });
+                    state.mic_trigger_times.update(|t| t.extend(hits));
+                }
+            }
+            was_recording = is_recording;
+            was_listening |= is_listening;
+
             // Trim the WASM-side circular buffer.
             //
             // We do this during listen AND during recording (whenever the
@@ -514,6 +568,10 @@ pub(crate) fn spawn_live_processing_loop(state: AppState, file_index: usize, sam
                         let trimmed_cols = trim / hop_size;
                         last_processed_col = last_processed_col.saturating_sub(trimmed_cols);
                         last_snapshot_len = last_snapshot_len.saturating_sub(trim);
+                        trigger_scan_len = trigger_scan_len.saturating_sub(trim);
+                        if is_recording {
+                            trimmed_while_recording += trim;
+                        }
                     }
                 });
             }
@@ -679,6 +737,7 @@ fn build_recording_meta(
     } else {
         None
     };
+    let embed_survey = state.recording_embed_survey.get_untracked();
 
     let guano_extra = crate::audio::guano::RecordingGuanoExtra {
         mic_interface: conn_type,
@@ -691,6 +750,8 @@ fn build_recording_meta(
         device_make: dev_make,
         device_model: dev_model,
         preroll_secs,
+        site: embed_survey.then(|| state.recording_site.get_untracked()),
+        operator: embed_survey.then(|| state.recording_operator.get_untracked()),
     };
     let guano = crate::audio::guano::build_recording_guano(
         sample_rate, duration_secs, filename,
@@ -700,7 +761,7 @@ fn build_recording_meta(
         env!("CARGO_PKG_VERSION"),
     );
 
-    let wav_markers = if preroll > 0 {
+    let mut wav_markers = if preroll > 0 {
         vec![crate::types::WavMarker {
             id: 1,
             position: preroll as u64,
@@ -710,10 +771,25 @@ fn build_recording_meta(
     } else {
         Vec::new()
     };
+    let first_id = wav_markers.len() as u32 + 1;
+    wav_markers.extend(trigger_markers(state, sample_rate, first_id));
 
     RecordingMeta { guano, wav_markers, preroll_samples: preroll }
 }
 
+/// WAV cue points for the level-triggered bookmarks of the current recording,
+/// numbered from `first_id`.
+fn trigger_markers(state: &AppState, sample_rate: u32, first_id: u32) -> Vec<crate::types::WavMarker> {
+    state.mic_trigger_times.with_untracked(|times| {
+        times.iter().zip(first_id..).map(|(&t, id)| crate::types::WavMarker {
+            id,
+            position: (t * sample_rate as f64).round() as u64,
+            label: Some("Trigger".to_string()),
+            note: None,
+        }).collect()
+    })
+}
+
 /// Create or update the LoadedFile in state. Returns (file_index, filename).
 fn update_or_create_file(
     state: AppState,
@@ -742,7 +818,7 @@ fn update_or_create_file(
 
         (idx, name)
     } else {
-        let name = generate_recording_name();
+        let name = generate_recording_name(&state);
         let total_cols = if audio.samples.len() >= 2048 {
             (audio.samples.len() - 2048) / 512 + 1
         } else { 0 };
@@ -785,13 +861,16 @@ fn update_or_create_file(
                 loading_id: None,
                 min_display_freq: None,
                 max_display_freq: None,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
         });
         state.current_file_index.set(Some(idx));
         (idx, name)
     };
 
-    // Store WAV markers (preroll cue point) on the file
+    // Store WAV markers (preroll cue point, trigger bookmarks) on the file
     if !wav_markers.is_empty() {
         state.files.update(|files| {
             if let Some(f) = files.get_mut(file_index) {
@@ -825,10 +904,11 @@ fn persist_and_identify(
     );
 
     if let Some(wav_data) = wav_bytes_for_save {
+        let save_dir = state.recording_save_dir.get_untracked();
         wasm_bindgen_futures::spawn_local(async move {
             if is_mobile {
                 crate::audio::wav_encoder::save_wav_to_shared(&wav_data, &filename).await;
-            } else if try_tauri_save(&wav_data, &filename).await.is_some() {
+            } else if try_tauri_save(&wav_data, &filename, &save_dir).await.is_some() {
                 // Desktop WASM save succeeded
             }
             state.files.update(|files| {
@@ -911,7 +991,7 @@ pub(crate) fn finalize_recording(params: FinalizeParams, state: AppState) {
     // ── Phase 1: Build metadata (GUANO + WAV markers) from state ────────
     let recording_name = live_idx
         .and_then(|idx| state.files.with_untracked(|f| f.get(idx).map(|f| f.name.clone())))
-        .unwrap_or_else(generate_recording_name);
+        .unwrap_or_else(|| generate_recording_name(&state));
     let meta = build_recording_meta(&state, sample_rate, duration_secs, &recording_name);
 
     // ── Phase 2: Encode WAV bytes (single pass for size, hash, and save) ─
@@ -919,6 +999,7 @@ pub(crate) fn finalize_recording(params: FinalizeParams, state: AppState) {
     let source = Arc::new(InMemorySource {
         samples: samples.clone(),
         raw_samples: None,
+        exact: None,
         sample_rate,
         channels: 1,
     });
@@ -930,7 +1011,7 @@ pub(crate) fn finalize_recording(params: FinalizeParams, state: AppState) {
     let audio_data_size = num_samples * (bits_per_sample as u64 / 8);
 
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels: 1,
@@ -946,7 +1027,8 @@ pub(crate) fn finalize_recording(params: FinalizeParams, state: AppState) {
         },
     };
 
-    let preview = compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = compute_preview(&audio, preview_w, preview_h);
     let audio_for_stft = audio.clone();
 
     // ── Phase 3: Update or create the file in state ─────────────────────
@@ -997,7 +1079,7 @@ pub(crate) fn finalize_recording(params: FinalizeParams, state: AppState) {
 
     let canvas_w = state.spectrogram_canvas_width.get_untracked();
     let final_time_res = 512.0 / sample_rate as f64;
-    state.zoom_level.set(crate::viewport::fit_zoom(canvas_w, final_time_res, duration_secs));
+    state.zoom_level.set(crate::viewport::fit_zoom(canvas_w, final_time_res, duration_secs, state.max_zoom()));
     state.scroll_offset.set(0.0);
 
     spawn_spectrogram_computation(audio_for_stft, name_check, file_index, state);
@@ -1084,7 +1166,7 @@ async fn finalize_streaming_tauri_recording(
     let duration_secs = header.total_frames as f64 / header.sample_rate as f64;
     let samples_arc = Arc::new(head_mono);
     let audio = AudioData {
-        samples: samples_arc,
+        samples: samples_arc.into(),
         source,
         sample_rate: header.sample_rate,
         channels: header.channels as u32,
@@ -1101,13 +1183,17 @@ async fn finalize_streaming_tauri_recording(
     };
     let audio_for_stft = audio.clone();
 
-    let preview = crate::dsp::fft::compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = crate::dsp::fft::compute_preview(&audio, preview_w, preview_h);
 
     // Build the recording name from the saved filename.
     let name = path.rsplit(['/', '\\']).next().unwrap_or(&path).to_string();
 
+    // The file is already on disk, so trigger bookmarks can't be written into
+    // it; keep them on the loaded file so they export with any clip.
+    let markers = trigger_markers(&state, header.sample_rate, 1);
     let (file_index, name_check) = update_or_create_file(
-        state, live_idx, audio, preview, Vec::new(), header.sample_rate,
+        state, live_idx, audio, preview, markers, header.sample_rate,
     );
 
     // Rename the live entry to match the on-disk filename + wire up the handle.
@@ -1122,7 +1208,7 @@ async fn finalize_streaming_tauri_recording(
 
     let canvas_w = state.spectrogram_canvas_width.get_untracked();
     let final_time_res = 512.0 / header.sample_rate as f64;
-    state.zoom_level.set(crate::viewport::fit_zoom(canvas_w, final_time_res, duration_secs));
+    state.zoom_level.set(crate::viewport::fit_zoom(canvas_w, final_time_res, duration_secs, state.max_zoom()));
     state.scroll_offset.set(0.0);
     if state.mic_preroll_samples.get_untracked() > 0 {
         state.mic_preroll_samples.set(0);
@@ -1143,17 +1229,21 @@ async fn finalize_streaming_tauri_recording(
     Ok(())
 }
 
-fn generate_recording_name() -> String {
-    let now = js_sys::Date::new_0();
-    format!(
-        "batcap_{:04}{:02}{:02}_{:02}{:02}{:02}.wav",
-        now.get_full_year(),
-        now.get_month() + 1,
-        now.get_date(),
-        now.get_hours(),
-        now.get_minutes(),
-        now.get_seconds(),
-    )
+/// File name for a recording that started at `at`, built from the user's
+/// name template, site and operator.
+pub(crate) fn recording_file_name(state: &AppState, at: &js_sys::Date) -> String {
+    use crate::audio::recording_name::{self, NameFields};
+    let fields = NameFields {
+        date: format!("{:04}{:02}{:02}", at.get_full_year(), at.get_month() + 1, at.get_date()),
+        time: format!("{:02}{:02}{:02}", at.get_hours(), at.get_minutes(), at.get_seconds()),
+        site: state.recording_site.get_untracked(),
+        operator: state.recording_operator.get_untracked(),
+    };
+    recording_name::recording_file_name(&state.recording_name_template.get_untracked(), &fields)
+}
+
+fn generate_recording_name(state: &AppState) -> String {
+    recording_file_name(state, &js_sys::Date::new_0())
 }
 
 /// Shared async spectrogram computation (used by both web and Tauri modes).
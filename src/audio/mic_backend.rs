@@ -14,6 +14,7 @@ use crate::dsp::heterodyne::RealtimeHet;
 use crate::dsp::pitch_shift::pitch_shift_realtime;
 use crate::dsp::phase_vocoder::phase_vocoder_pitch_shift;
 use crate::dsp::zc_divide::zc_divide;
+use crate::dsp::resample::DisplayDecimator;
 use crate::tauri_bridge::{get_tauri_internals, tauri_invoke, tauri_invoke_no_args};
 use std::cell::RefCell;
 
@@ -54,6 +55,8 @@ fn build_start_recording_args(state: &AppState, shared_fd: Option<i32>) -> JsVal
     if let Some(name) = filename {
         let _ = js_sys::Reflect::set(&args, &JsValue::from_str("filename"), &JsValue::from_str(&name));
     }
+    // Kept in the recovery sidecar too, so a recovered file has them
+    set_survey_args(state, &args);
     if let Some(c) = state.mic_connection_type.get_untracked() {
         let _ = js_sys::Reflect::set(&args, &JsValue::from_str("connectionType"), &JsValue::from_str(&c));
     }
@@ -85,10 +88,34 @@ fn build_start_recording_args(state: &AppState, shared_fd: Option<i32>) -> JsVal
     args.into()
 }
 
+/// Add the site and operator to start/stop IPC args when embedding is on.
+fn set_survey_args(state: &AppState, args: &js_sys::Object) {
+    if !state.recording_embed_survey.get_untracked() {
+        return;
+    }
+    for (key, value) in [("site", state.recording_site.get_untracked()), ("operator", state.recording_operator.get_untracked())] {
+        if !value.trim().is_empty() {
+            let _ = js_sys::Reflect::set(args, &JsValue::from_str(key), &JsValue::from_str(value.trim()));
+        }
+    }
+}
+
 /// Build IPC args for mic_stop_recording / usb_stop_recording,
-/// including optional GPS location and device model fields from state.
+/// including optional GPS location and device model fields from state,
+/// and the file name, save folder and survey fields from the recording settings.
 fn build_stop_recording_args(state: &AppState) -> JsValue {
     let args = js_sys::Object::new();
+    // Name the saved file after the live file so both sides agree
+    let filename = state.mic_live_file_idx.get_untracked()
+        .and_then(|idx| state.files.with_untracked(|f| f.get(idx).map(|f| f.name.clone())));
+    if let Some(name) = filename {
+        let _ = js_sys::Reflect::set(&args, &JsValue::from_str("filename"), &JsValue::from_str(&name));
+    }
+    let save_dir = state.recording_save_dir.get_untracked();
+    if !save_dir.trim().is_empty() && !state.is_mobile.get_untracked() {
+        let _ = js_sys::Reflect::set(&args, &JsValue::from_str("saveDir"), &JsValue::from_str(&save_dir));
+    }
+    set_survey_args(state, &args);
     if let Some(loc) = state.recording_location.get_untracked() {
         let _ = js_sys::Reflect::set(&args, &JsValue::from_str("locLatitude"), &JsValue::from_f64(loc.latitude));
         let _ = js_sys::Reflect::set(&args, &JsValue::from_str("locLongitude"), &JsValue::from_f64(loc.longitude));
@@ -132,6 +159,14 @@ thread_local! {
 
 // ── Thread-local state: Native mode (shared by cpal AND USB) ────────────
 
+/// Most audio (seconds) that live listening may queue ahead of the output clock.
+///
+/// Native chunks arrive over IPC on the mic's clock, which can run slightly
+/// fast relative to the output device or arrive in bursts after a stall.
+/// Without a cap the schedule creeps ahead and monitoring lags further and
+/// further behind the bat; chunks beyond this are dropped instead.
+const MAX_LISTEN_LATENCY_SECS: f64 = 0.25;
+
 thread_local! {
     /// Whether a native mic (cpal or USB) is currently open.
     static NATIVE_MIC_OPEN: RefCell<Option<NativeMode>> = const { RefCell::new(None) };
@@ -141,6 +176,8 @@ thread_local! {
     static HET_NEXT_TIME: RefCell<f64> = const { RefCell::new(0.0) };
     /// Keep the event listener closure alive.
     static TAURI_EVENT_CLOSURE: RefCell<Option<Closure<dyn FnMut(JsValue)>>> = RefCell::new(None);
+    /// Keep the full-rate listen chunk listener closure alive.
+    static TAURI_LISTEN_CLOSURE: RefCell<Option<Closure<dyn FnMut(JsValue)>>> = RefCell::new(None);
     /// Unlisten function returned by Tauri event subscription.
     static TAURI_UNLISTEN: RefCell<Option<js_sys::Function>> = const { RefCell::new(None) };
     /// Accumulated recording samples on the frontend for native modes (cpal/USB).
@@ -149,6 +186,9 @@ thread_local! {
     static NATIVE_RT_HET: RefCell<RealtimeHet> = RefCell::new(RealtimeHet::new());
     /// Overlap context state for PS/PV live listening (native).
     static NATIVE_LISTEN_STATE: RefCell<ListenDspState> = RefCell::new(ListenDspState::new());
+    /// Decimates the full-rate listen stream for display while the cpal
+    /// backend sends only that stream.
+    static NATIVE_DISPLAY_DECIMATOR: RefCell<Option<DisplayDecimator>> = const { RefCell::new(None) };
 }
 
 // ── Thread-local state: USB-specific ────────────────────────────────────
@@ -242,7 +282,12 @@ impl TauriRecordingResult {
             .map(|i| samples_array.get(i).as_f64().unwrap_or(0.0) as f32)
             .collect();
 
-        if samples.is_empty() {
+        // Only to-memory recordings carry their samples. Streaming-to-disk
+        // recordings return just the finished file's path (loaded from disk
+        // by `finalize_recording`), and pre-roll recordings return neither
+        // (the WASM-side buffer is used). Either way the recording still
+        // belongs in the file list, so only give up when nothing was captured.
+        if samples.is_empty() && saved_path.is_empty() && duration_secs <= 0.0 {
             return None;
         }
 
@@ -300,6 +345,7 @@ fn process_listen_audio(
     context_samples: usize,
     het_freq: f64,
     het_cutoff: f64,
+    het_lp_order: usize,
     ps_factor: f64,
     pv_factor: f64,
     zc_factor: f64,
@@ -308,7 +354,7 @@ fn process_listen_audio(
         ListenMode::Heterodyne => {
             dsp_state.clear();
             let mut out = vec![0.0f32; input.len()];
-            rt_het.process(input, &mut out, sample_rate, het_freq, het_cutoff);
+            rt_het.process(input, &mut out, sample_rate, het_freq, het_cutoff, het_lp_order);
             out
         }
         ListenMode::PitchShift | ListenMode::PhaseVocoder => {
@@ -575,14 +621,7 @@ async fn try_create_shared_fd(state: &AppState) -> Option<i32> {
     // Build filename from the live file if available, otherwise generate one
     let filename = state.mic_live_file_idx.get_untracked()
         .and_then(|idx| state.files.with_untracked(|f| f.get(idx).map(|f| f.name.clone())))
-        .unwrap_or_else(|| {
-            let now = js_sys::Date::new_0();
-            format!(
-                "batcap_{:04}{:02}{:02}_{:02}{:02}{:02}.wav",
-                now.get_full_year(), now.get_month() + 1, now.get_date(),
-                now.get_hours(), now.get_minutes(), now.get_seconds(),
-            )
-        });
+        .unwrap_or_else(|| crate::audio::live_recording::recording_file_name(state, &js_sys::Date::new_0()));
 
     let args = js_sys::Object::new();
     js_sys::Reflect::set(&args, &JsValue::from_str("filename"), &JsValue::from_str(&filename)).ok();
@@ -635,33 +674,32 @@ fn tauri_listen(event_name: &str, callback: Closure<dyn FnMut(JsValue)>) -> Opti
     if TAURI_EVENT_CLOSURE.with(|c| c.borrow().is_some()) {
         return Some(());
     }
-
-    let tauri = get_tauri_internals()?;
-
-    let transform_fn = js_sys::Reflect::get(&tauri, &JsValue::from_str("transformCallback")).ok()?;
-    let transform_fn = js_sys::Function::from(transform_fn);
-    let handler_id = transform_fn.call1(&tauri, callback.as_ref().unchecked_ref()).ok()?;
-
-    let invoke_fn = js_sys::Reflect::get(&tauri, &JsValue::from_str("invoke")).ok()?;
-    let invoke_fn = js_sys::Function::from(invoke_fn);
-
-    let args = js_sys::Object::new();
-    js_sys::Reflect::set(&args, &"event".into(), &JsValue::from_str(event_name)).ok();
-    let target = js_sys::Object::new();
-    js_sys::Reflect::set(&target, &"kind".into(), &JsValue::from_str("Any")).ok();
-    js_sys::Reflect::set(&args, &"target".into(), &target).ok();
-    js_sys::Reflect::set(&args, &"handler".into(), &handler_id).ok();
-
-    invoke_fn
-        .call2(&tauri, &JsValue::from_str("plugin:event|listen"), &args)
-        .ok();
-
+    register_tauri_event(event_name, &callback)?;
     TAURI_EVENT_CLOSURE.with(|c| *c.borrow_mut() = Some(callback));
     Some(())
 }
 
+/// Subscribe to the full-rate `mic-listen-chunk` stream the cpal backend
+/// sends while its display stream is decimated. Registered once, like
+/// [`tauri_listen`].
+fn tauri_listen_full_rate(callback: Closure<dyn FnMut(JsValue)>) -> Option<()> {
+    if TAURI_LISTEN_CLOSURE.with(|c| c.borrow().is_some()) {
+        return Some(());
+    }
+    register_tauri_event("mic-listen-chunk", &callback)?;
+    TAURI_LISTEN_CLOSURE.with(|c| *c.borrow_mut() = Some(callback));
+    Some(())
+}
+
 /// Subscribe to a USB stream error event (separate thread-local from tauri_listen).
 fn tauri_listen_usb_error(event_name: &str, callback: Closure<dyn FnMut(JsValue)>) -> Option<()> {
+    register_tauri_event(event_name, &callback)?;
+    USB_ERROR_CLOSURE.with(|c| *c.borrow_mut() = Some(callback));
+    Some(())
+}
+
+/// Register `callback` for a Tauri event. The caller keeps the closure alive.
+fn register_tauri_event(event_name: &str, callback: &Closure<dyn FnMut(JsValue)>) -> Option<()> {
     let tauri = get_tauri_internals()?;
 
     let transform_fn = js_sys::Reflect::get(&tauri, &JsValue::from_str("transformCallback")).ok()?;
@@ -681,8 +719,6 @@ fn tauri_listen_usb_error(event_name: &str, callback: Closure<dyn FnMut(JsValue)
     invoke_fn
         .call2(&tauri, &JsValue::from_str("plugin:event|listen"), &args)
         .ok();
-
-    USB_ERROR_CLOSURE.with(|c| *c.borrow_mut() = Some(callback));
     Some(())
 }
 
@@ -713,72 +749,112 @@ async fn setup_het_context(state: &AppState) -> bool {
 fn create_native_chunk_handler(state: AppState) -> Closure<dyn FnMut(JsValue)> {
     let state_cb = state;
     Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
-        let payload = match js_sys::Reflect::get(&event, &JsValue::from_str("payload")) {
-            Ok(p) => p,
-            Err(_) => return,
-        };
+        let Some(input_data) = chunk_payload(&event) else { return };
+        // Back on the display stream: the next full-rate stretch starts a fresh filter
+        NATIVE_DISPLAY_DECIMATOR.with(|d| d.borrow_mut().take());
+        accumulate_display_chunk(state_cb, &input_data);
+
+        // Listen mode: a decimated display stream has lost the ultrasonic
+        // band, so listening then uses the full-rate `mic-listen-chunk` stream
+        if state_cb.mic_listening.get_untracked() && state_cb.mic_display_factor.get_untracked() <= 1 {
+            play_listen_chunk(state_cb, &input_data, state_cb.mic_stream_rate());
+        }
+    })
+}
 
-        let array = js_sys::Array::from(&payload);
-        let len = array.length() as usize;
-        if len == 0 {
+/// Accumulate display-rate samples for the live waterfall during recording
+/// or listening.
+fn accumulate_display_chunk(state: AppState, input_data: &[f32]) {
+    if state.mic_recording.get_untracked() || state.mic_listening.get_untracked() {
+        NATIVE_REC_BUFFER.with(|buf| buf.borrow_mut().extend_from_slice(input_data));
+        if state.mic_recording.get_untracked() {
+            state.mic_samples_recorded.update(|n| *n += input_data.len());
+        }
+    }
+}
+
+/// Handler for the full-rate chunks the cpal backend sends instead of the
+/// decimated display stream while listening. They are played, then
+/// decimated here for the display.
+fn create_native_listen_handler(state: AppState) -> Closure<dyn FnMut(JsValue)> {
+    Closure::<dyn FnMut(JsValue)>::new(move |event: JsValue| {
+        let factor = state.mic_display_factor.get_untracked() as usize;
+        if factor <= 1 {
             return;
         }
+        let Some(input_data) = chunk_payload(&event) else { return };
+        let sample_rate = state.mic_sample_rate.get_untracked();
+        if state.mic_listening.get_untracked() {
+            play_listen_chunk(state, &input_data, sample_rate);
+        }
+        let display = NATIVE_DISPLAY_DECIMATOR.with(|d| {
+            let mut d = d.borrow_mut();
+            if d.as_ref().is_some_and(|dec| dec.factor() != factor) {
+                *d = None;
+            }
+            d.get_or_insert_with(|| DisplayDecimator::new(sample_rate, factor)).process(input_data)
+        });
+        accumulate_display_chunk(state, &display);
+    })
+}
 
-        let input_data: Vec<f32> = (0..len)
-            .map(|i| array.get(i as u32).as_f64().unwrap_or(0.0) as f32)
-            .collect();
+/// Samples of a native chunk event, or None if it is empty.
+fn chunk_payload(event: &JsValue) -> Option<Vec<f32>> {
+    let payload = js_sys::Reflect::get(event, &JsValue::from_str("payload")).ok()?;
+    let array = js_sys::Array::from(&payload);
+    let len = array.length();
+    if len == 0 {
+        return None;
+    }
+    Some((0..len).map(|i| array.get(i).as_f64().unwrap_or(0.0) as f32).collect())
+}
 
-        // Accumulate samples for live waterfall display during recording OR listening
-        if state_cb.mic_recording.get_untracked() || state_cb.mic_listening.get_untracked() {
-            NATIVE_REC_BUFFER.with(|buf| buf.borrow_mut().extend_from_slice(&input_data));
-            if state_cb.mic_recording.get_untracked() {
-                state_cb.mic_samples_recorded.update(|n| *n += len);
-            }
+/// Process `input_data` (at `sr` Hz) through the selected listen DSP and
+/// schedule it on the speakers.
+fn play_listen_chunk(state: AppState, input_data: &[f32], sr: u32) {
+    let mode = state.listen_mode.get_untracked();
+    let out_data = NATIVE_RT_HET.with(|h| {
+        NATIVE_LISTEN_STATE.with(|s| {
+            process_listen_audio(
+                input_data,
+                mode,
+                sr,
+                &mut h.borrow_mut(),
+                &mut s.borrow_mut(),
+                state.listen_context_samples.get_untracked(),
+                state.listen_het_frequency.get_untracked(),
+                state.listen_het_cutoff.get_untracked(),
+                state.het_lp_order.get_untracked(),
+                state.ps_factor.get_untracked(),
+                state.pv_factor.get_untracked(),
+                state.zc_factor.get_untracked(),
+            )
+        })
+    });
+
+    // Schedule playback via AudioBuffer
+    let out_len = out_data.len();
+    HET_CTX.with(|ctx_cell| {
+        let ctx_ref = ctx_cell.borrow();
+        let Some(ctx) = ctx_ref.as_ref() else { return };
+        let current_time = ctx.current_time();
+        let next_time = HET_NEXT_TIME.with(|t| *t.borrow());
+        if next_time - current_time > MAX_LISTEN_LATENCY_SECS {
+            // Let the queue drain so the monitor stays close to real time
+            return;
         }
+        let Ok(buffer) = ctx.create_buffer(1, out_len as u32, sr as f32) else { return };
+        let _ = buffer.copy_to_channel(&out_data, 0);
+        let Ok(source) = ctx.create_buffer_source() else { return };
+        source.set_buffer(Some(&buffer));
+        let _ = source.connect_with_audio_node(&ctx.destination());
 
-        // Listen mode: process input through selected DSP and play through speakers
-        if state_cb.mic_listening.get_untracked() {
-            let sr = state_cb.mic_sample_rate.get_untracked();
-            let mode = state_cb.listen_mode.get_untracked();
-            let out_data = NATIVE_RT_HET.with(|h| {
-                NATIVE_LISTEN_STATE.with(|s| {
-                    process_listen_audio(
-                        &input_data,
-                        mode,
-                        sr,
-                        &mut h.borrow_mut(),
-                        &mut s.borrow_mut(),
-                        state_cb.listen_context_samples.get_untracked(),
-                        state_cb.listen_het_frequency.get_untracked(),
-                        state_cb.listen_het_cutoff.get_untracked(),
-                        state_cb.ps_factor.get_untracked(),
-                        state_cb.pv_factor.get_untracked(),
-                        state_cb.zc_factor.get_untracked(),
-                    )
-                })
-            });
+        let start = if next_time > current_time { next_time } else { current_time };
+        let _ = source.start_with_when(start);
 
-            // Schedule playback via AudioBuffer
-            let out_len = out_data.len();
-            HET_CTX.with(|ctx_cell| {
-                let ctx_ref = ctx_cell.borrow();
-                let Some(ctx) = ctx_ref.as_ref() else { return };
-                let Ok(buffer) = ctx.create_buffer(1, out_len as u32, sr as f32) else { return };
-                let _ = buffer.copy_to_channel(&out_data, 0);
-                let Ok(source) = ctx.create_buffer_source() else { return };
-                source.set_buffer(Some(&buffer));
-                let _ = source.connect_with_audio_node(&ctx.destination());
-
-                let current_time = ctx.current_time();
-                let next_time = HET_NEXT_TIME.with(|t| *t.borrow());
-                let start = if next_time > current_time { next_time } else { current_time };
-                let _ = source.start_with_when(start);
-
-                let duration = out_len as f64 / sr as f64;
-                HET_NEXT_TIME.with(|t| *t.borrow_mut() = start + duration);
-            });
-        }
-    })
+        let duration = out_len as f64 / sr as f64;
+        HET_NEXT_TIME.with(|t| *t.borrow_mut() = start + duration);
+    });
 }
 
 /// Clean up all native thread-local state (HET context, buffer).
@@ -795,6 +871,7 @@ fn cleanup_native_state() {
 
     NATIVE_RT_HET.with(|h| h.borrow_mut().reset());
     NATIVE_LISTEN_STATE.with(|s| s.borrow_mut().clear());
+    NATIVE_DISPLAY_DECIMATOR.with(|d| d.borrow_mut().take());
     NATIVE_REC_BUFFER.with(|buf| buf.borrow_mut().clear());
     crate::canvas::live_waterfall::clear();
 }
@@ -957,6 +1034,7 @@ async fn open_web(state: &AppState) -> bool {
                         state_cb.listen_context_samples.get_untracked(),
                         state_cb.listen_het_frequency.get_untracked(),
                         state_cb.listen_het_cutoff.get_untracked(),
+                        state_cb.het_lp_order.get_untracked(),
                         state_cb.ps_factor.get_untracked(),
                         state_cb.pv_factor.get_untracked(),
                         state_cb.zc_factor.get_untracked(),
@@ -1125,8 +1203,10 @@ async fn open_cpal(state: &AppState) -> bool {
 
     let chunk_handler = create_native_chunk_handler(*state);
     tauri_listen("mic-audio-chunk", chunk_handler);
+    tauri_listen_full_rate(create_native_listen_handler(*state));
 
     NATIVE_MIC_OPEN.with(|o| *o.borrow_mut() = Some(NativeMode::Cpal));
+    apply_display_rate(state).await;
     log::info!("Native mic opened: {} at {} Hz, {}-bit", device_name, sample_rate, bits_per_sample);
     true
 }
@@ -1138,11 +1218,40 @@ async fn close_cpal(state: &AppState) {
 
     cleanup_native_state();
     NATIVE_MIC_OPEN.with(|o| *o.borrow_mut() = None);
+    state.mic_display_factor.set(1);
 
     state.mic_samples_recorded.set(0);
     log::info!("Native mic closed");
 }
 
+/// Ask the native (cpal) backend to decimate streamed chunks to at most
+/// `mic_display_max_rate`, and record the resulting factor. The live view
+/// then runs at the reduced rate; listen DSP and recordings keep the device
+/// rate. No-op unless the cpal mic is open.
+pub async fn apply_display_rate(state: &AppState) {
+    if !NATIVE_MIC_OPEN.with(|o| *o.borrow() == Some(NativeMode::Cpal)) {
+        return;
+    }
+    let args = js_sys::Object::new();
+    js_sys::Reflect::set(&args, &"maxRate".into(),
+        &JsValue::from_f64(state.mic_display_max_rate.get_untracked() as f64)).ok();
+    match tauri_invoke("mic_set_display_rate", &args.into()).await {
+        Ok(rate) => {
+            let rate = rate.as_f64().unwrap_or(0.0) as u32;
+            let device_rate = state.mic_sample_rate.get_untracked();
+            let factor = if rate > 0 { (device_rate / rate).max(1) } else { 1 };
+            state.mic_display_factor.set(factor);
+            if factor > 1 {
+                log::info!("Mic display stream decimated {}x to {} Hz", factor, rate);
+            }
+        }
+        Err(e) => {
+            log::warn!("mic_set_display_rate failed: {}", e);
+            state.mic_display_factor.set(1);
+        }
+    }
+}
+
 // ── Raw USB backend ─────────────────────────────────────────────────────
 
 async fn open_usb(state: &AppState) -> bool {
@@ -415,7 +415,7 @@ async fn do_start_recording(state: &AppState, backend: ActiveBackend) {
             // Now safe to clear listening — recording is active, loop won't exit.
             state.mic_listening.set(false);
             state.mic_recording_start_time.set(Some(js_sys::Date::now()));
-            let sr = state.mic_sample_rate.get_untracked();
+            let sr = state.mic_stream_rate();
 
             let file_idx = if has_listen_file {
                 // Convert the existing listening file into a recording file.
@@ -542,7 +542,7 @@ async fn do_start_listening(state: &AppState, backend: ActiveBackend) {
     // as soon as the native side starts streaming.
     state.mic_listening.set(true);
     backend.set_listening(state, true).await;
-    let sr = state.mic_sample_rate.get_untracked();
+    let sr = state.mic_stream_rate();
     // Clear tile caches so previous file's spectrogram doesn't flash
     crate::canvas::tile_cache::clear_all_caches();
     // Create the transient listening file in the file list
@@ -677,6 +677,14 @@ pub async fn toggle_record_with_preroll(state: &AppState) {
         return;
     }
 
+    // A decimated display stream can't become part of a full-rate recording
+    if state.mic_display_factor.get_untracked() > 1 {
+        state.show_info_toast("Pre-roll needs the full-rate mic stream \u{2014} recording without it");
+        state.mic_preroll_samples.set(0);
+        toggle_record(state).await;
+        return;
+    }
+
     // Capture the current listen buffer length as pre-roll.
     // Compensate for audio accumulated during the hold gesture: the user's intent
     // is to capture the buffer state from when they *started* pressing, not when
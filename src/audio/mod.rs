@@ -1,8 +1,10 @@
 // Re-export modules from oversample-core.
-pub use oversample_core::audio::{source, guano, loader};
+pub use oversample_core::audio::{source, guano, loader, convert, tags, labels, recording_name, metadata_overlap, xc_sidecar};
 
 pub mod browser_decode;
 pub mod export;
+pub mod file_metrics;
+pub mod image_export;
 pub mod peak;
 pub mod live_recording;
 pub mod mic_backend;
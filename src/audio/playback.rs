@@ -1,15 +1,99 @@
 use leptos::prelude::*;
 use crate::annotations::AnnotationKind;
-use crate::state::{ActiveFocus, AppState, GainMode, Selection, PlaybackMode};
+use crate::state::{ActiveFocus, AppState, FileKey, GainMode, Selection, PlaybackMode};
 use crate::audio::streaming_playback::{self, PlaybackParams};
-use crate::audio::source::{AudioSource, TimelineAudioSource};
+use crate::audio::source::{AudioSource, ChannelView, TimelineAudioSource};
+use crate::dsp::psd;
 use crate::viewport;
 use std::cell::RefCell;
+use std::collections::HashSet;
 use std::sync::Arc;
 
 thread_local! {
     static PLAYHEAD_HANDLE: RefCell<Option<i32>> = const { RefCell::new(None) };
     static REPLAY_TIMER: RefCell<Option<i32>> = const { RefCell::new(None) };
+    /// The file being played to its end (no selection, no timeline), so its
+    /// stop position can be remembered for "resume per file".
+    static PLAYING_FILE: RefCell<Option<FileKey>> = const { RefCell::new(None) };
+    /// Files already checked against the output device's sample rate this session.
+    static RATE_CHECKED: RefCell<HashSet<FileKey>> = RefCell::new(HashSet::new());
+}
+
+/// Key of the current file, unless a multi-file timeline is active. Two
+/// loaded files with the same name keep separate positions.
+fn current_file_key(state: &AppState) -> Option<FileKey> {
+    if state.active_timeline.get_untracked().is_some() {
+        return None;
+    }
+    let idx = state.current_file_index.get_untracked()?;
+    state.files.with_untracked(|files| files.get(idx).map(|f| f.key()))
+}
+
+/// Record where whole-file playback stopped (`Some`) or that it ran to the
+/// end (`None`, so the next play starts from the beginning).
+fn remember_position(state: &AppState, time: Option<f64>) {
+    let Some(key) = PLAYING_FILE.with(|f| f.borrow_mut().take()) else { return };
+    state.playback_positions.update(|map| {
+        match time {
+            Some(t) if t > 0.0 => { map.insert(key, t); }
+            _ => { map.remove(&key); }
+        }
+    });
+}
+
+/// Saved position to resume the current file from, if resuming is enabled.
+fn resume_position(state: &AppState) -> Option<f64> {
+    if !state.resume_playback.get_untracked() {
+        return None;
+    }
+    let key = current_file_key(state)?;
+    let idx = state.current_file_index.get_untracked()?;
+    let duration = state.files.with_untracked(|files| files.get(idx).map(|f| f.audio.duration_secs))?;
+    state.playback_positions
+        .with_untracked(|map| map.get(&key).copied())
+        .filter(|&t| t > 0.0 && t < duration)
+}
+
+/// In 1:1 mode, a recording whose strongest sound is above the output
+/// device's Nyquist plays as silence. The first time such a file is played,
+/// switch to heterodyne (tuned to that sound when auto-tuning) and say why.
+/// Choosing 1:1 again afterwards is respected for the rest of the session.
+fn adapt_to_output_rate(state: &AppState, target: &PlaybackTarget) {
+    if state.playback_mode.get_untracked() != PlaybackMode::Normal {
+        return;
+    }
+    let Some(key) = current_file_key(state) else { return };
+    if !RATE_CHECKED.with(|s| s.borrow_mut().insert(key)) {
+        return;
+    }
+    let Some(device_rate) = streaming_playback::output_device_rate() else { return };
+    let device_nyquist = device_rate as f64 / 2.0;
+    let file_nyquist = target.sample_rate as f64 / 2.0;
+    if file_nyquist <= device_nyquist {
+        return;
+    }
+
+    // Look at the first half second: only intervene if the calls are out of reach
+    let n = (target.sample_rate as usize / 2).min(target.source.total_samples() as usize);
+    if n < 1024 {
+        return;
+    }
+    let samples = target.source.read_region(ChannelView::MonoMix, 0, n);
+    let peak = psd::compute_psd(&samples, target.sample_rate, 1024, Some((1000.0, file_nyquist)))
+        .peaks
+        .first()
+        .map(|p| p.freq_hz);
+    let Some(peak) = peak.filter(|&f| f > device_nyquist) else { return };
+
+    state.playback_mode.set(PlaybackMode::Heterodyne);
+    if state.het_freq_auto.get_untracked() && state.band_ff_freq_hi.get_untracked() <= state.band_ff_freq_lo.get_untracked() {
+        state.het_frequency.set((peak / 1000.0).round() * 1000.0);
+    }
+    state.show_info_toast(format!(
+        "Sound at {:.0} kHz is above your audio output's {:.0} kHz limit \u{2014} switched to heterodyne. Choose 1:1 to play unchanged.",
+        peak / 1000.0,
+        device_nyquist / 1000.0,
+    ));
 }
 
 struct PlaybackTarget {
@@ -178,7 +262,9 @@ pub fn stop(state: &AppState) {
     cancel_playhead();
     streaming_playback::stop_stream();
     state.is_buffering.set(false);
+    state.output_limiting.set(false);
     if was_playing {
+        remember_position(state, Some(state.playhead_time.get_untracked()));
         if state.user_panned_during_playback.get_untracked()
             && !is_playhead_visible(state)
         {
@@ -316,7 +402,7 @@ fn current_play_from_here_time(state: &AppState) -> f64 {
     if visible_time <= 0.0 {
         state.play_from_here_time.get_untracked()
     } else {
-        viewport::play_from_here_time(scroll, visible_time).clamp(0.0, target.duration_secs)
+        viewport::play_from_here_time(scroll, visible_time, state.play_anchor_fraction.get_untracked()).clamp(0.0, target.duration_secs)
     }
 }
 
@@ -330,6 +416,7 @@ pub fn play_from_time(state: &AppState, start_secs: f64) {
 /// Inner implementation: play from `start_secs` to `sel_end` (or end of file).
 fn play_from_time_inner(state: &AppState, start_secs: f64, selection: Option<Selection>) {
     let Some(target) = playback_target(state) else { return; };
+    adapt_to_output_rate(state, &target);
 
     let sr = target.sample_rate;
     let total = target.source.total_samples() as usize;
@@ -370,8 +457,14 @@ fn play_from_time_inner(state: &AppState, start_secs: f64, selection: Option<Sel
     };
 
     state.active_playback_selection.set(selection);
+    if state.follow_resume_on_play.get_untracked() {
+        state.resume_follow();
+    }
     state.is_playing.set(true);
     state.playhead_time.set(start_secs);
+    if selection.is_none() {
+        PLAYING_FILE.with(|f| *f.borrow_mut() = current_file_key(state));
+    }
     start_playhead(*state, start_secs, play_duration, playback_speed);
 }
 
@@ -391,8 +484,19 @@ pub fn play(state: &AppState) {
                 return;
             }
         }
+        // No selection: pick up where this file was last stopped
+        if sel.is_none() {
+            if let Some(t) = resume_position(state) {
+                state.push_nav();
+                state.pre_play_scroll.set(state.scroll_offset.get_untracked());
+                state.user_panned_during_playback.set(false);
+                play_from_time_inner(state, t, None);
+                return;
+            }
+        }
         sel
     };
+    adapt_to_output_rate(state, &target);
     let sr = target.sample_rate;
 
     let (start_sample, end_sample) = extract_selection_range(sr, target.source.total_samples() as usize, selection);
@@ -425,8 +529,14 @@ pub fn play(state: &AppState) {
     state.pre_play_scroll.set(state.scroll_offset.get_untracked());
     state.user_panned_during_playback.set(false);
     state.active_playback_selection.set(selection);
+    if state.follow_resume_on_play.get_untracked() {
+        state.resume_follow();
+    }
     state.is_playing.set(true);
     state.playhead_time.set(play_start_time);
+    if selection.is_none() {
+        PLAYING_FILE.with(|f| *f.borrow_mut() = current_file_key(state));
+    }
     start_playhead(*state, play_start_time, play_duration, playback_speed);
 }
 
@@ -449,10 +559,12 @@ pub(crate) fn snapshot_params(state: &AppState, selection: Option<Selection>, sa
         mode: state.playback_mode.get_untracked(),
         het_freq: state.het_frequency.get_untracked(),
         het_cutoff: state.het_cutoff.get_untracked(),
+        het_lp_order: state.het_lp_order.get_untracked(),
         te_factor: state.te_factor.get_untracked(),
         ps_factor: state.ps_factor.get_untracked(),
         pv_factor: state.pv_factor.get_untracked(),
         pv_hq: state.pv_hq.get_untracked(),
+        te_hq: state.te_hq.get_untracked(),
         zc_factor: state.zc_factor.get_untracked(),
         gain_db: state.gain_db.get_untracked(),
         gain_mode: state.gain_mode.get_untracked(),
@@ -481,6 +593,17 @@ pub(crate) fn snapshot_params(state: &AppState, selection: Option<Selection>, sa
         noise_reduce_enabled: state.noise_reduce_enabled.get_untracked(),
         noise_reduce_strength: state.noise_reduce_strength.get_untracked(),
         noise_reduce_floor: state.noise_reduce_floor.get_untracked(),
+        edge_fade_ms: state.clip_fade_ms.get_untracked(),
+        dc_offset: if state.active_timeline.get_untracked().is_some() {
+            0.0
+        } else {
+            state.current_file_index.get_untracked()
+                .and_then(|i| state.files.with_untracked(|files| files.get(i).and_then(|f| f.settings.dc_offset)))
+                .unwrap_or(0.0)
+        },
+        declip: state.active_timeline.get_untracked().is_none()
+            && state.current_file_index.get_untracked()
+                .is_some_and(|i| state.files.with_untracked(|files| files.get(i).is_some_and(|f| f.settings.declip))),
     }
 }
 
@@ -540,7 +663,7 @@ fn start_playhead(state: AppState, start_time: f64, duration: f64, speed: f64) {
 
     use std::rc::Rc;
     use wasm_bindgen::prelude::*;
-    use crate::audio::streaming_playback::audio_buffer_ahead_secs;
+    use crate::audio::streaming_playback::{audio_buffer_ahead_secs, output_limiting_now};
 
     let cb: Rc<RefCell<Option<wasm_bindgen::closure::Closure<dyn FnMut()>>>> =
         Rc::new(RefCell::new(None));
@@ -561,8 +684,14 @@ fn start_playhead(state: AppState, start_time: f64, duration: f64, speed: f64) {
     *cb.borrow_mut() = Some(wasm_bindgen::closure::Closure::new(move || {
         if !state.is_playing.get_untracked() {
             state.is_buffering.set(false);
+            state.output_limiting.set(false);
             return;
         }
+
+        let limiting = output_limiting_now();
+        if state.output_limiting.get_untracked() != limiting {
+            state.output_limiting.set(limiting);
+        }
         let window = web_sys::window().unwrap();
         let perf = window.performance().unwrap();
         let now_ms = perf.now();
@@ -604,6 +733,8 @@ fn start_playhead(state: AppState, start_time: f64, duration: f64, speed: f64) {
         if current >= end_time {
             state.playhead_time.set(end_time);
             state.is_buffering.set(false);
+            state.output_limiting.set(false);
+            remember_position(&state, None);
             if !(state.user_panned_during_playback.get_untracked()
                 && !is_playhead_visible(&state))
             {
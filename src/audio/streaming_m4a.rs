@@ -487,6 +487,11 @@ impl AudioSource for StreamingM4aSource {
                     total_len
                 }
             }
+            ChannelView::Sum => {
+                let n = self.read_samples(ChannelView::MonoMix, start, buf);
+                crate::audio::source::scale_to_sum(&mut buf[..n], self.channels);
+                n
+            }
             ChannelView::Difference => {
                 if self.channels < 2 {
                     for s in buf.iter_mut() { *s = 0.0; }
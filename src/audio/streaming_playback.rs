@@ -15,9 +15,13 @@ use crate::audio::streaming_source;
 use crate::state::{PlaybackMode, FilterQuality, GainMode};
 use crate::dsp::agc::{AgcConfig, AgcProcessor};
 use crate::dsp::heterodyne::heterodyne_mix;
+use crate::dsp::declip;
+use crate::dsp::limiter::SoftLimiter;
 use crate::dsp::pitch_shift::pitch_shift_realtime;
+use crate::dsp::resample::SincResampler;
 use crate::dsp::zc_divide::zc_divide;
-use crate::dsp::filters::{apply_eq_filter, apply_eq_filter_fast};
+use crate::dsp::fade::{apply_edge_fades, fade_samples};
+use crate::dsp::filters::{apply_eq_filter, apply_eq_filter_fast, eq_lookahead_end};
 use crate::audio::playback::{apply_bandpass, apply_gain};
 
 /// Number of source samples per chunk. ~0.5s at 192kHz, ~2s at 44.1kHz.
@@ -25,8 +29,11 @@ const CHUNK_SAMPLES: usize = 96_000;
 
 /// Extra overlap samples prepended to each chunk for IIR filter warmup.
 /// This lets filters (heterodyne lowpass, bandpass) settle before the
-/// actual chunk data, avoiding clicks at boundaries.
+/// actual chunk data, avoiding clicks at boundaries. It also covers the
+/// spectral EQ's kernel reach before each chunk; the reach after it comes
+/// from the lookahead in [`read_filtered`].
 const FILTER_WARMUP: usize = 4096;
+const _: () = assert!(FILTER_WARMUP >= crate::dsp::filters::EQ_FILTER_REACH);
 
 /// Overlap samples for PV HQ mode. Each chunk extends by this amount past
 /// its nominal end. The trailing overlap gets a Hann fade-out, while the
@@ -48,6 +55,8 @@ const PREBUFFER_CHUNKS: usize = 5; // 3 might be enough
 
 thread_local! {
     static STREAM_CTX: RefCell<Option<AudioContext>> = const { RefCell::new(None) };
+    /// Sample rate of the audio output device, probed once.
+    static OUTPUT_RATE: std::cell::Cell<Option<f32>> = const { std::cell::Cell::new(None) };
     /// Master gain node for fade-out on stop (avoids clicks).
     static STREAM_GAIN: RefCell<Option<web_sys::GainNode>> = const { RefCell::new(None) };
     /// Monotonically increasing generation counter to detect stale streams.
@@ -60,6 +69,9 @@ thread_local! {
     /// While this is true the already-scheduled tail is draining normally and
     /// `audio_buffer_ahead_secs` should not be interpreted as an underrun.
     static SCHEDULER_DONE: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    /// Web Audio time spans of scheduled chunks the output limiter acted on,
+    /// so the clip indicator lights while they play rather than when decoded.
+    static LIMITED_SPANS: RefCell<Vec<(f64, f64)>> = const { RefCell::new(Vec::new()) };
 }
 
 /// How many seconds of audio are scheduled beyond the audio context's
@@ -78,16 +90,30 @@ pub fn audio_buffer_ahead_secs() -> Option<f64> {
     })
 }
 
+/// True while a chunk that needed output limiting is playing.
+pub fn output_limiting_now() -> bool {
+    let Some(now) = STREAM_CTX.with(|ctx| ctx.borrow().as_ref().map(|c| c.current_time())) else {
+        return false;
+    };
+    LIMITED_SPANS.with(|spans| {
+        let mut spans = spans.borrow_mut();
+        spans.retain(|&(_, end)| end > now);
+        spans.iter().any(|&(start, _)| start <= now)
+    })
+}
+
 /// Snapshot of all playback parameters, frozen at play start so that
 /// parameter changes mid-playback don't cause glitches.
 pub(crate) struct PlaybackParams {
     pub mode: PlaybackMode,
     pub het_freq: f64,
     pub het_cutoff: f64,
+    pub het_lp_order: usize,
     pub te_factor: f64,
     pub ps_factor: f64,
     pub pv_factor: f64,
     pub pv_hq: bool,
+    pub te_hq: bool,
     pub zc_factor: f64,
     pub gain_db: f64,
     pub gain_mode: GainMode,
@@ -110,6 +136,12 @@ pub(crate) struct PlaybackParams {
     pub noise_reduce_enabled: bool,
     pub noise_reduce_strength: f64,
     pub noise_reduce_floor: Option<crate::dsp::spectral_sub::NoiseFloor>,
+    /// Fade (ms) applied at the start and end of the played/exported range.
+    pub edge_fade_ms: f64,
+    /// DC offset subtracted from the source before any filtering (0 = none).
+    pub dc_offset: f32,
+    /// Reconstruct clipped runs before any other processing.
+    pub declip: bool,
 }
 
 fn selection_bandpass_active(sample_rate: u32, params: &PlaybackParams) -> bool {
@@ -126,6 +158,21 @@ fn selection_bandpass_active(sample_rate: u32, params: &PlaybackParams) -> bool
             || params.sel_freq_high < (sample_rate as f64 / 2.0))
 }
 
+/// True when no stage of the pipeline changes sample values: normal or
+/// time-expansion mode (TE only relabels the rate) with no filtering, gain,
+/// DC removal or clip restoration. Such output can be the source samples
+/// verbatim. Export edge fades are not considered.
+pub(crate) fn is_passthrough(sample_rate: u32, params: &PlaybackParams) -> bool {
+    matches!(params.mode, PlaybackMode::Normal | PlaybackMode::TimeExpansion)
+        && params.gain_db.abs() < 0.001
+        && params.dc_offset == 0.0
+        && !params.declip
+        && !params.filter_enabled
+        && !selection_bandpass_active(sample_rate, params)
+        && !(params.notch_enabled && !params.notch_bands.is_empty())
+        && !(params.noise_reduce_enabled && params.noise_reduce_floor.is_some())
+}
+
 /// Duration of fade-in when starting playback (milliseconds).
 const FADE_IN_MS: f64 = 30.0;
 
@@ -141,6 +188,7 @@ pub(crate) fn stop_stream() {
     });
     SCHEDULED_END.with(|s| s.set(0.0));
     SCHEDULER_DONE.with(|d| d.set(false));
+    LIMITED_SPANS.with(|s| s.borrow_mut().clear());
 
     // Take the gain node but keep the context alive for reuse.
     let gain = STREAM_GAIN.with(|g| g.borrow_mut().take());
@@ -164,6 +212,21 @@ pub(crate) fn is_streaming() -> bool {
     STREAM_CTX.with(|c| c.borrow().is_some())
 }
 
+/// Sample rate of the audio output device (a default AudioContext's rate).
+///
+/// Contexts created at a file's own high rate are resampled to this on
+/// output, so anything above half of it is never heard.
+pub(crate) fn output_device_rate() -> Option<f32> {
+    if let Some(rate) = OUTPUT_RATE.with(|r| r.get()) {
+        return Some(rate);
+    }
+    let ctx = AudioContext::new().ok()?;
+    let rate = ctx.sample_rate();
+    let _ = ctx.close();
+    OUTPUT_RATE.with(|r| r.set(Some(rate)));
+    Some(rate)
+}
+
 /// Start streaming playback of a sample range.
 ///
 /// `source` provides sample data; `channel_view` selects which channel(s) to play.
@@ -179,18 +242,28 @@ pub(crate) fn start_stream(
 ) -> Option<u32> {
     stop_stream();
 
-    let final_rate = match params.mode {
-        PlaybackMode::TimeExpansion => {
-            let abs_f = params.te_factor.abs().max(1.0);
-            let rate = if params.te_factor > 0.0 {
-                sample_rate as f64 / abs_f
-            } else {
-                sample_rate as f64 * abs_f
-            };
-            (rate as u32).clamp(8000, 384_000)
+    // Rate the time-expanded samples are meant to play at
+    let te_rate = (params.mode == PlaybackMode::TimeExpansion).then(|| {
+        let abs_f = params.te_factor.abs().max(1.0);
+        if params.te_factor > 0.0 {
+            sample_rate as f64 / abs_f
+        } else {
+            sample_rate as f64 * abs_f
         }
-        _ => sample_rate,
+    });
+    // HQ time expansion converts to the device rate here with a sinc
+    // resampler; otherwise the context runs at the TE rate and the browser
+    // converts (quality varies, and it clamps extreme rates).
+    let hq_device_rate = te_rate
+        .filter(|_| params.te_hq)
+        .and_then(|_| output_device_rate())
+        .map(|r| r as u32);
+    let final_rate = match (te_rate, hq_device_rate) {
+        (_, Some(device)) => device,
+        (Some(rate), None) => (rate as u32).clamp(8000, 384_000),
+        (None, None) => sample_rate,
     };
+    let resample_from = te_rate.filter(|_| hq_device_rate.is_some());
 
     // Stereo output: stereo source + Stereo view (all modes, not just Normal)
     let stereo_out = source.channel_count() >= 2
@@ -238,6 +311,7 @@ pub(crate) fn start_stream(
         stereo_out,
         sample_rate,
         final_rate,
+        resample_from,
         start_sample,
         end_sample,
         params,
@@ -256,11 +330,16 @@ async fn chunk_loop(
     stereo_out: bool,
     source_rate: u32,
     final_rate: u32,
+    resample_from: Option<f64>,
     start_sample: usize,
     end_sample: usize,
     params: PlaybackParams,
 ) {
     let mut pos = start_sample;
+    // Rate of the chunks coming out of the processing pipeline, before any
+    // HQ time-expansion resampling to `final_rate`.
+    let process_rate = resample_from.map_or(final_rate, |r| r.round() as u32);
+    let mut te_resampler = resample_from.map(|r| TeResampler::new(r, final_rate as f64));
     let pv_hq_mode = params.pv_hq && matches!(params.mode, PlaybackMode::PhaseVocoder | PlaybackMode::PitchShift);
 
     // Gain computation depends on mode:
@@ -275,11 +354,13 @@ async fn chunk_loop(
 
     // AGC processor for Adaptive mode — persists across chunks for smooth gain transitions
     let agc = if is_adaptive {
-        Some(RefCell::new(AgcProcessor::new(AgcConfig::default(), final_rate)))
+        Some(RefCell::new(AgcProcessor::new(AgcConfig::default(), process_rate)))
     } else {
         None
     };
 
+    let limiter = output_limiter(&params, process_rate).map(RefCell::new);
+
     let mode_boost = match params.mode {
         PlaybackMode::PhaseVocoder => PV_MODE_BOOST_DB,
         _ => 0.0,
@@ -299,6 +380,7 @@ async fn chunk_loop(
         samples: Vec<f32>,
         left: Option<Vec<f32>>,
         right: Option<Vec<f32>>,
+        limited: bool,
     }
     let mut prebuf: Vec<PreBuf> = Vec::with_capacity(PREBUFFER_CHUNKS);
 
@@ -307,14 +389,17 @@ async fn chunk_loop(
         let current_gen = STREAM_GEN.with(|g| *g.borrow());
         if current_gen != generation { return; }
 
-        let (final_samples, left, right, new_pos) = process_one_chunk(
+        let (mut final_samples, mut left, mut right, limited, new_pos) = process_one_chunk(
             &source, channel_view, stereo_out, source_rate, &params,
-            global_gain, agc.as_ref(),
+            global_gain, agc.as_ref(), limiter.as_ref(),
             pos, start_sample, end_sample,
         ).await;
         pos = new_pos;
+        if let Some(rs) = te_resampler.as_mut() {
+            (final_samples, left, right) = rs.process(final_samples, left, right, pos >= end_sample);
+        }
 
-        prebuf.push(PreBuf { samples: final_samples, left, right });
+        prebuf.push(PreBuf { samples: final_samples, left, right, limited });
 
         // Yield between pre-buffer chunks so the UI stays responsive
         yield_to_browser().await;
@@ -337,6 +422,7 @@ async fn chunk_loop(
 
     for buf in prebuf {
         if !buf.samples.is_empty() {
+            let chunk_start_time = scheduled_time;
             if stereo_out {
                 if let (Some(ref left), Some(ref right)) = (buf.left, buf.right) {
                     schedule_buffer_stereo(&ctx, &gain_node, left, right, final_rate, scheduled_time);
@@ -359,6 +445,9 @@ async fn chunk_loop(
                 }
             }
             SCHEDULED_END.with(|s| s.set(scheduled_time));
+            if buf.limited {
+                LIMITED_SPANS.with(|s| s.borrow_mut().push((chunk_start_time, scheduled_time)));
+            }
         }
     }
 
@@ -368,14 +457,18 @@ async fn chunk_loop(
         let current_gen = STREAM_GEN.with(|g| *g.borrow());
         if current_gen != generation { break; }
 
-        let (final_samples, left, right, new_pos) = process_one_chunk(
+        let (mut final_samples, mut left, mut right, limited, new_pos) = process_one_chunk(
             &source, channel_view, stereo_out, source_rate, &params,
-            global_gain, agc.as_ref(),
+            global_gain, agc.as_ref(), limiter.as_ref(),
             pos, start_sample, end_sample,
         ).await;
         pos = new_pos;
+        if let Some(rs) = te_resampler.as_mut() {
+            (final_samples, left, right) = rs.process(final_samples, left, right, pos >= end_sample);
+        }
 
         if !final_samples.is_empty() {
+            let chunk_start_time = scheduled_time;
             if stereo_out {
                 if let (Some(ref left), Some(ref right)) = (left, right) {
                     schedule_buffer_stereo(&ctx, &gain_node, left, right, final_rate, scheduled_time);
@@ -396,6 +489,9 @@ async fn chunk_loop(
                 }
             }
             SCHEDULED_END.with(|s| s.set(scheduled_time));
+            if limited {
+                LIMITED_SPANS.with(|s| s.borrow_mut().push((chunk_start_time, scheduled_time)));
+            }
         }
 
         // Yield to browser so UI stays responsive
@@ -420,8 +516,9 @@ async fn chunk_loop(
     }
 }
 
-/// Process a single chunk: prefetch, read, filter, DSP, trim, gain.
-/// Returns (mono_samples, optional_left, optional_right, next_pos).
+/// Process a single chunk: prefetch, read, filter, DSP, trim, gain, limit.
+/// Returns (mono_samples, optional_left, optional_right, limited, next_pos),
+/// where `limited` is true if the output limiter reduced any samples.
 async fn process_one_chunk(
     source: &Arc<dyn AudioSource>,
     channel_view: ChannelView,
@@ -430,10 +527,11 @@ async fn process_one_chunk(
     params: &PlaybackParams,
     global_gain: f64,
     agc: Option<&RefCell<AgcProcessor>>,
+    limiter: Option<&RefCell<SoftLimiter>>,
     pos: usize,
     start_sample: usize,
     end_sample: usize,
-) -> (Vec<f32>, Option<Vec<f32>>, Option<Vec<f32>>, usize) {
+) -> (Vec<f32>, Option<Vec<f32>>, Option<Vec<f32>>, bool, usize) {
     let pv_hq_mode = params.pv_hq && matches!(params.mode, PlaybackMode::PhaseVocoder | PlaybackMode::PitchShift);
 
     let warmup_start = pos.saturating_sub(FILTER_WARMUP);
@@ -452,17 +550,26 @@ async fn process_one_chunk(
     };
     let trailing_len = trailing_end - chunk_end;
 
-    // Prefetch for streaming sources
-    streaming_source::prefetch_streaming(source.as_ref(), warmup_start as u64, trailing_end - warmup_start).await;
-
-    let chunk_with_warmup = source.read_region(channel_view, warmup_start as u64, trailing_end - warmup_start);
-    let filtered = apply_filters(&chunk_with_warmup, source_rate, params);
+    // Edge fades only on the first and last chunk of the range, so the
+    // start/stop points don't click. Chunk joins are seamless already.
+    let edge_fade = fade_samples(params.edge_fade_ms, source_rate);
+    let fade_in = if pos == start_sample { edge_fade } else { 0 };
+    let fade_out = if chunk_end == end_sample { edge_fade } else { 0 };
+
+    // Prefetch for streaming sources, including the EQ lookahead and the
+    // clip restoration context
+    let declip_context = if params.declip { declip::CONTEXT } else { 0 };
+    let read_start = warmup_start.saturating_sub(declip_context);
+    let read_end = (eq_lookahead_end(trailing_end, source.total_samples() as usize) + declip_context)
+        .min(source.total_samples() as usize);
+    streaming_source::prefetch_streaming(source.as_ref(), read_start as u64, read_end - read_start).await;
+
+    let filtered = read_filtered(source.as_ref(), channel_view, warmup_start, trailing_end, source_rate, params);
     let processed = apply_dsp_mode(&filtered, source_rate, params);
 
     // Helper: process a channel through filters + DSP (same pipeline as mono)
     let process_ch = |cv: ChannelView| -> Vec<f32> {
-        let raw = source.read_region(cv, warmup_start as u64, trailing_end - warmup_start);
-        let filtered = apply_filters(&raw, source_rate, params);
+        let filtered = read_filtered(source.as_ref(), cv, warmup_start, trailing_end, source_rate, params);
         apply_dsp_mode(&filtered, source_rate, params)
     };
 
@@ -500,19 +607,22 @@ async fn process_one_chunk(
             processed.to_vec()
         };
         apply_pv_hq_fading(&mut final_samples);
+        apply_edge_fades(&mut final_samples, fade_in, fade_out);
 
         apply_gain(&mut final_samples, global_gain);
         if let Some(agc_cell) = agc {
             agc_cell.borrow_mut().process(&mut final_samples);
         }
 
-        let (left, right) = if stereo_out {
+        let (mut left, mut right) = if stereo_out {
             let l_proc = process_ch(ChannelView::Channel(0));
             let r_proc = process_ch(ChannelView::Channel(1));
             let mut l = if trim_start < l_proc.len() { l_proc[trim_start..].to_vec() } else { l_proc };
             let mut r = if trim_start < r_proc.len() { r_proc[trim_start..].to_vec() } else { r_proc };
             apply_pv_hq_fading(&mut l);
             apply_pv_hq_fading(&mut r);
+            apply_edge_fades(&mut l, fade_in, fade_out);
+            apply_edge_fades(&mut r, fade_in, fade_out);
             apply_gain(&mut l, global_gain);
             apply_gain(&mut r, global_gain);
             if let Some(agc_cell) = agc {
@@ -523,7 +633,18 @@ async fn process_one_chunk(
             (None, None)
         };
 
-        (final_samples, left, right, chunk_end)
+        // Only restored clipping gets a limiter here. The overlaps are
+        // crossfaded, so the gain state runs slightly ahead at each join.
+        let mut limited = false;
+        if let Some(lim) = limiter {
+            let mut lim = lim.borrow_mut();
+            limited = match (left.as_mut(), right.as_mut()) {
+                (Some(l), Some(r)) => lim.process_stereo(l, r) > 0,
+                _ => lim.process(&mut final_samples) > 0,
+            };
+        }
+
+        (final_samples, left, right, limited, chunk_end)
     } else {
         // Standard mode: trim warmup and trailing
         let trim_start = warmup_len;
@@ -535,19 +656,22 @@ async fn process_one_chunk(
         };
 
         let mut final_samples = trimmed.to_vec();
+        apply_edge_fades(&mut final_samples, fade_in, fade_out);
 
         apply_gain(&mut final_samples, global_gain);
         if let Some(agc_cell) = agc {
             agc_cell.borrow_mut().process(&mut final_samples);
         }
 
-        let (left, right) = if stereo_out {
+        let (mut left, mut right) = if stereo_out {
             let l_proc = process_ch(ChannelView::Channel(0));
             let r_proc = process_ch(ChannelView::Channel(1));
             let l_trim_end = l_proc.len().saturating_sub(trailing_len);
             let r_trim_end = r_proc.len().saturating_sub(trailing_len);
             let mut l = if trim_start < l_trim_end { l_proc[trim_start..l_trim_end].to_vec() } else { l_proc };
             let mut r = if trim_start < r_trim_end { r_proc[trim_start..r_trim_end].to_vec() } else { r_proc };
+            apply_edge_fades(&mut l, fade_in, fade_out);
+            apply_edge_fades(&mut r, fade_in, fade_out);
             apply_gain(&mut l, global_gain);
             apply_gain(&mut r, global_gain);
             if let Some(agc_cell) = agc {
@@ -558,12 +682,60 @@ async fn process_one_chunk(
             (None, None)
         };
 
-        (final_samples, left, right, chunk_end)
+        // Only the scheduled output goes through the limiter, so its gain
+        // state follows one continuous signal.
+        let mut limited = false;
+        if let Some(lim) = limiter {
+            let mut lim = lim.borrow_mut();
+            limited = match (left.as_mut(), right.as_mut()) {
+                (Some(l), Some(r)) => lim.process_stereo(l, r) > 0,
+                _ => lim.process(&mut final_samples) > 0,
+            };
+        }
+
+        (final_samples, left, right, limited, chunk_end)
     }
 }
 
+/// Read `start..end` of one channel view and run it through [`apply_filters`].
+/// The zero-phase EQ looks ahead past `end`, so the read runs on to
+/// [`eq_lookahead_end`] and the extra samples are dropped after filtering.
+/// Clip restoration reads its own context either side of that.
+pub(crate) fn read_filtered(
+    source: &dyn AudioSource,
+    channel_view: ChannelView,
+    start: usize,
+    end: usize,
+    sample_rate: u32,
+    params: &PlaybackParams,
+) -> Vec<f32> {
+    let total = source.total_samples() as usize;
+    let read_end = eq_lookahead_end(end, total);
+    let raw = if params.declip {
+        // Clipping happened at the recorder, before any offset is removed
+        declip::read_declipped(
+            |s, n| source.read_region(channel_view, s as u64, n),
+            start, read_end, total, declip::CLIP_LEVEL,
+        )
+    } else {
+        source.read_region(channel_view, start as u64, read_end - start)
+    };
+    let mut filtered = apply_filters(&raw, sample_rate, params);
+    filtered.truncate(end - start);
+    filtered
+}
 
 pub(crate) fn apply_filters(samples: &[f32], sample_rate: u32, params: &PlaybackParams) -> Vec<f32> {
+    let corrected;
+    let samples = if params.dc_offset != 0.0 {
+        let mut buf = samples.to_vec();
+        crate::dsp::dc::remove_dc(&mut buf, params.dc_offset);
+        corrected = buf;
+        corrected.as_slice()
+    } else {
+        samples
+    };
+
     let mut result = if params.filter_enabled {
         match params.filter_quality {
             FilterQuality::Fast => apply_eq_filter_fast(
@@ -608,6 +780,17 @@ pub(crate) fn apply_filters(samples: &[f32], sample_rate: u32, params: &Playback
     result
 }
 
+/// Limiter for the processed output, shared by playback and export.
+/// Heterodyne and ZC output isn't level-normalized, so the gain slider can
+/// push it past full scale, and restored clipping peaks go past it by
+/// design. None when neither applies, or under adaptive gain (AGC has its
+/// own limiter).
+pub(crate) fn output_limiter(params: &PlaybackParams, sample_rate: u32) -> Option<SoftLimiter> {
+    (params.gain_mode != GainMode::Adaptive
+        && (params.declip || matches!(params.mode, PlaybackMode::Heterodyne | PlaybackMode::ZeroCrossing)))
+        .then(|| SoftLimiter::new(sample_rate))
+}
+
 pub(crate) fn apply_dsp_mode(samples: &[f32], sample_rate: u32, params: &PlaybackParams) -> Vec<f32> {
     match params.mode {
         PlaybackMode::Normal => samples.to_vec(),
@@ -619,7 +802,7 @@ pub(crate) fn apply_dsp_mode(samples: &[f32], sample_rate: u32, params: &Playbac
                 } else {
                     params.het_freq
                 };
-            heterodyne_mix(samples, sample_rate, effective_lo, params.het_cutoff)
+            heterodyne_mix(samples, sample_rate, effective_lo, params.het_cutoff, params.het_lp_order)
         }
         PlaybackMode::TimeExpansion => {
             // Rate change handled by AudioContext sample rate, not sample transform
@@ -633,6 +816,45 @@ pub(crate) fn apply_dsp_mode(samples: &[f32], sample_rate: u32, params: &Playbac
     }
 }
 
+/// Sinc resamplers carrying HQ time-expansion output across chunk joins,
+/// one per output channel.
+struct TeResampler {
+    mono: SincResampler,
+    left: SincResampler,
+    right: SincResampler,
+}
+
+impl TeResampler {
+    fn new(in_rate: f64, out_rate: f64) -> Self {
+        Self {
+            mono: SincResampler::new(in_rate, out_rate),
+            left: SincResampler::new(in_rate, out_rate),
+            right: SincResampler::new(in_rate, out_rate),
+        }
+    }
+
+    /// Resample one processed chunk; `last` flushes the tail of the range.
+    fn process(
+        &mut self,
+        samples: Vec<f32>,
+        left: Option<Vec<f32>>,
+        right: Option<Vec<f32>>,
+        last: bool,
+    ) -> (Vec<f32>, Option<Vec<f32>>, Option<Vec<f32>>) {
+        let run = |rs: &mut SincResampler, input: &[f32]| {
+            let mut out = rs.process(input);
+            if last {
+                out.extend(rs.finish());
+            }
+            out
+        };
+        let samples = run(&mut self.mono, &samples);
+        let left = left.map(|l| run(&mut self.left, &l));
+        let right = right.map(|r| run(&mut self.right, &r));
+        (samples, left, right)
+    }
+}
+
 fn schedule_buffer(ctx: &AudioContext, dest: &web_sys::GainNode, samples: &[f32], sample_rate: u32, when: f64) {
     let Ok(buffer) = ctx.create_buffer(1, samples.len() as u32, sample_rate as f32) else {
         return;
@@ -452,6 +452,11 @@ impl AudioSource for StreamingWavSource {
                     total_len
                 }
             }
+            ChannelView::Sum => {
+                let n = self.read_samples(ChannelView::MonoMix, start, buf);
+                crate::audio::source::scale_to_sum(&mut buf[..n], self.info.channels);
+                n
+            }
             ChannelView::Difference => {
                 if self.info.channels < 2 {
                     for s in buf.iter_mut() { *s = 0.0; }
@@ -974,6 +979,11 @@ impl AudioSource for StreamingFlacSource {
                     total_len
                 }
             }
+            ChannelView::Sum => {
+                let n = self.read_samples(ChannelView::MonoMix, start, buf);
+                crate::audio::source::scale_to_sum(&mut buf[..n], self.channels);
+                n
+            }
             ChannelView::Difference => {
                 if self.channels < 2 {
                     for s in buf.iter_mut() { *s = 0.0; }
@@ -11,8 +11,8 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 
 use crate::audio::export::{build_export_params, get_selected_regions, process_region, trigger_browser_download};
 use crate::audio::webcodecs_bindings as wc;
-use crate::canvas::spectrogram_renderer::{self, ColormapMode, SpectDisplaySettings, TileSource};
-use crate::state::{AppState, AudioCodecOption, PlaybackMode, VideoCodec, VideoViewMode};
+use crate::canvas::spectrogram_renderer::{self, ColormapMode, ExtraFocusBands, SpectDisplaySettings, TileSource};
+use crate::state::{AppState, AudioCodecOption, LoadedFile, PlaybackMode, VideoCodec, VideoViewMode};
 
 /// Frames per second for exported video.
 const FPS: f64 = 30.0;
@@ -27,25 +27,28 @@ const DEFAULT_AUDIO_BITRATE: u32 = 128_000;
 const KEYFRAME_INTERVAL: u32 = 60;
 
 /// Snapshot of all rendering parameters captured at export start.
-struct RenderParams {
+pub(super) struct RenderParams {
     file_idx: usize,
     total_cols: usize,
-    time_res: f64,
+    pub(super) time_res: f64,
     duration: f64,
+    /// Seconds trimmed off the start of the file on import; the time axis
+    /// is labelled in original-file time.
+    time_origin: f64,
     start_time: f64,
     #[allow(dead_code)]
     end_time: f64,
     #[allow(dead_code)]
     sample_rate: u32,
-    file_max_freq: f64,
-    freq_crop_lo: f64,
-    freq_crop_hi: f64,
+    pub(super) file_max_freq: f64,
+    pub(super) freq_crop_lo: f64,
+    pub(super) freq_crop_hi: f64,
     colormap: ColormapMode,
     display_settings: SpectDisplaySettings,
-    min_freq: f64,
-    max_freq: f64,
-    canvas_w: u32,
-    canvas_h: u32,
+    pub(super) min_freq: f64,
+    pub(super) max_freq: f64,
+    pub(super) canvas_w: u32,
+    pub(super) canvas_h: u32,
     shield_style: crate::state::ShieldStyle,
 }
 
@@ -154,81 +157,7 @@ async fn export_video_impl(state: &AppState) -> Result<(), JsValue> {
     }
     log::info!("Video export: codec supported");
 
-    // Snapshot rendering parameters from current state
-    let time_res = file.spectrogram.time_resolution;
-    let file_max_freq = file.spectrogram.max_freq;
-    let max_display_freq = state.max_display_freq.get_untracked();
-    let min_display_freq = state.min_display_freq.get_untracked();
-    let max_freq = max_display_freq.unwrap_or(file_max_freq).min(file_max_freq);
-    let min_freq = min_display_freq.unwrap_or(0.0);
-    let freq_crop_lo = min_freq / file_max_freq;
-    let freq_crop_hi = (max_freq / file_max_freq).min(1.0);
-
-    let hfr_enabled = state.hfr_enabled.get_untracked();
-    let colormap_pref = state.colormap_preference.get_untracked();
-    let hfr_colormap_pref = state.hfr_colormap_preference.get_untracked();
-    let band_ff_lo = state.band_ff_freq_lo.get_untracked();
-    let band_ff_hi = state.band_ff_freq_hi.get_untracked();
-
-    let colormap = if hfr_enabled && band_ff_hi > band_ff_lo {
-        ColormapMode::HfrFocus {
-            colormap: hfr_colormap_pref,
-            band_ff_lo_frac: band_ff_lo / file_max_freq,
-            band_ff_hi_frac: band_ff_hi / file_max_freq,
-        }
-    } else if hfr_enabled {
-        ColormapMode::Uniform(hfr_colormap_pref)
-    } else {
-        ColormapMode::Uniform(colormap_pref)
-    };
-
-    let spect_floor = state.spect_floor_db.get_untracked();
-    let spect_range = state.spect_range_db.get_untracked();
-    let spect_gamma = state.spect_gamma.get_untracked();
-    let spect_gain = state.spect_gain_db.get_untracked();
-
-    // Compute ref_db the same way the spectrogram component does
-    let fft_size = state.spect_fft_mode.get_untracked().max_fft_size() as f32;
-    let fixed_ref_db = 20.0 * (fft_size / 4.0).log10();
-    let display_auto_gain = state.display_auto_gain.get_untracked();
-    let total_cols = {
-        let tc = file.spectrogram.total_columns;
-        if tc > 0 { tc } else { file.spectrogram.columns.len() }
-    };
-    let ref_db = if display_auto_gain && total_cols > 0 {
-        let max_mag = crate::canvas::spectral_store::get_max_magnitude(file_idx);
-        if max_mag > 0.0 { 20.0 * max_mag.log10() } else { fixed_ref_db }
-    } else {
-        fixed_ref_db
-    };
-    let display_boost = state.display_gain_boost.get_untracked();
-
-    let display_settings = SpectDisplaySettings {
-        floor_db: spect_floor,
-        range_db: spect_range,
-        gamma: spect_gamma,
-        gain_db: spect_gain - ref_db + display_boost,
-    };
-
-    let render = RenderParams {
-        file_idx,
-        total_cols,
-        time_res,
-        duration: file.audio.source.duration_secs(),
-        start_time,
-        end_time,
-        sample_rate,
-        file_max_freq,
-        freq_crop_lo,
-        freq_crop_hi,
-        colormap,
-        display_settings,
-        min_freq,
-        max_freq,
-        canvas_w: vid_w,
-        canvas_h: vid_h,
-        shield_style: state.shield_style.get_untracked(),
-    };
+    let render = snapshot_render_params(state, &file, file_idx, start_time, end_time, vid_w, vid_h);
 
     // ── Process audio ────────────────────────────────────────────────────────
     state.video_export_status.set(Some("Processing audio...".to_string()));
@@ -585,8 +514,102 @@ async fn export_video_impl(state: &AppState) -> Result<(), JsValue> {
     Ok(())
 }
 
+/// Snapshot the current display settings (colormap, gain, visible
+/// frequency range, HFR focus) into render parameters for one file.
+pub(super) fn snapshot_render_params(
+    state: &AppState,
+    file: &LoadedFile,
+    file_idx: usize,
+    start_time: f64,
+    end_time: f64,
+    canvas_w: u32,
+    canvas_h: u32,
+) -> RenderParams {
+    spectrogram_renderer::set_background_color(state.spectrogram_background.get_untracked());
+    spectrogram_renderer::set_freq_marker_style(state.marker_colors.get_untracked(), state.freq_marker_interval_khz.get_untracked() * 1000.0);
+    let sample_rate = file.audio.sample_rate;
+    let time_res = file.spectrogram.time_resolution;
+    let file_max_freq = file.spectrogram.max_freq;
+    let max_display_freq = state.max_display_freq.get_untracked();
+    let min_display_freq = state.min_display_freq.get_untracked();
+    let max_freq = max_display_freq.unwrap_or(file_max_freq).min(file_max_freq);
+    let min_freq = min_display_freq.unwrap_or(0.0);
+    let freq_crop_lo = min_freq / file_max_freq;
+    let freq_crop_hi = (max_freq / file_max_freq).min(1.0);
+
+    let hfr_enabled = state.hfr_enabled.get_untracked();
+    let colormap_pref = state.colormap_preference.get_untracked();
+    let hfr_colormap_pref = state.hfr_colormap_preference.get_untracked();
+    let band_ff_lo = state.band_ff_freq_lo.get_untracked();
+    let band_ff_hi = state.band_ff_freq_hi.get_untracked();
+
+    let colormap = if hfr_enabled && band_ff_hi > band_ff_lo {
+        ColormapMode::HfrFocus {
+            colormap: hfr_colormap_pref,
+            band_ff_lo_frac: band_ff_lo / file_max_freq,
+            band_ff_hi_frac: band_ff_hi / file_max_freq,
+            extra_bands: ExtraFocusBands::from_hz(&state.extra_focus_bands.get_untracked(), file_max_freq),
+            focus_settings: None, // set below once the reference level is known
+        }
+    } else if hfr_enabled {
+        ColormapMode::Uniform(hfr_colormap_pref)
+    } else {
+        ColormapMode::Uniform(colormap_pref)
+    };
+
+    let spect_floor = state.spect_floor_db.get_untracked();
+    let spect_range = state.spect_range_db.get_untracked();
+    let spect_gamma = state.spect_gamma.get_untracked();
+    let spect_gain = state.spect_gain_db.get_untracked();
+
+    // Compute ref_db the same way the spectrogram component does
+    let fft_size = state.spect_fft_mode.get_untracked().max_fft_size() as f32;
+    let fixed_ref_db = 20.0 * (fft_size / 4.0).log10();
+    let display_auto_gain = state.display_auto_gain.get_untracked();
+    let total_cols = {
+        let tc = file.spectrogram.total_columns;
+        if tc > 0 { tc } else { file.spectrogram.columns.len() }
+    };
+    let ref_db = if display_auto_gain && total_cols > 0 {
+        let max_mag = crate::canvas::spectral_store::get_max_magnitude(file_idx);
+        if max_mag > 0.0 { 20.0 * max_mag.log10() } else { fixed_ref_db }
+    } else {
+        fixed_ref_db
+    };
+    let display_boost = state.display_gain_boost.get_untracked();
+
+    let display_settings = SpectDisplaySettings {
+        floor_db: spect_floor,
+        range_db: spect_range,
+        gamma: spect_gamma,
+        gain_db: spect_gain - ref_db + display_boost,
+    };
+    let colormap = colormap.with_focus_settings(state.hfr_focus_display_settings(&display_settings));
+
+    RenderParams {
+        file_idx,
+        total_cols,
+        time_res,
+        duration: file.audio.source.duration_secs(),
+        time_origin: file.audio.trim_offset_secs(),
+        start_time,
+        end_time,
+        sample_rate,
+        file_max_freq,
+        freq_crop_lo,
+        freq_crop_hi,
+        colormap,
+        display_settings,
+        min_freq,
+        max_freq,
+        canvas_w,
+        canvas_h,
+        shield_style: state.shield_style.get_untracked(),
+    }
+}
+
 /// Render a single spectrogram frame to the offscreen canvas.
-fn render_frame(
+pub(super) fn render_frame(
     ctx: &CanvasRenderingContext2d,
     r: &RenderParams,
     scroll_col: f64,
@@ -595,10 +618,11 @@ fn render_frame(
     scroll_offset: f64,
 ) {
     // Clear canvas
-    ctx.set_fill_style_str("#000");
-    ctx.fill_rect(0.0, 0.0, r.canvas_w as f64, r.canvas_h as f64);
+    spectrogram_renderer::clear_background(ctx, r.canvas_w as f64, r.canvas_h as f64);
 
     // Blit spectrogram tiles
+    ctx.save();
+    spectrogram_renderer::orient_freq_axis(ctx, r.canvas_h as f64);
     spectrogram_renderer::blit_tiles_viewport(
         ctx,
         r.canvas_w as f64,
@@ -607,6 +631,7 @@ fn render_frame(
         r.total_cols,
         scroll_col,
         zoom,
+        crate::canvas::tile_cache::ideal_lod(zoom),
         r.freq_crop_lo,
         r.freq_crop_hi,
         spectrogram_renderer::TileRenderMode::Spectrogram(r.colormap),
@@ -618,18 +643,20 @@ fn render_frame(
         r.duration,
         TileSource::Normal,
     );
+    ctx.restore();
 
     // Draw time markers
     crate::canvas::time_markers::draw_time_markers(
         ctx,
-        scroll_offset,
+        r.time_origin + scroll_offset,
         visible_time,
         r.canvas_w as f64,
         r.canvas_h as f64,
-        r.duration,
+        r.time_origin + r.duration,
         None,  // no clock time config
         false, // don't show clock time
         1.0,   // time_scale = 1.0 (normal)
+        crate::canvas::time_markers::DEFAULT_TICK_SPACING_PX,
     );
 
     // Draw frequency markers
@@ -667,21 +694,10 @@ fn normalize_audio_rate(samples: &[f32], rate: u32) -> (Vec<f32>, u32) {
         return (samples.to_vec(), rate);
     }
 
-    // Simple linear interpolation downsampling to 48 kHz
+    // Band-limited downsampling to 48 kHz, so ultrasonic content is removed
+    // rather than aliased into the audible range
     let target_rate = 48000u32;
-    let ratio = rate as f64 / target_rate as f64;
-    let new_len = (samples.len() as f64 / ratio).ceil() as usize;
-    let mut out = Vec::with_capacity(new_len);
-
-    for i in 0..new_len {
-        let src_pos = i as f64 * ratio;
-        let idx = src_pos as usize;
-        let frac = (src_pos - idx as f64) as f32;
-        let s0 = samples.get(idx).copied().unwrap_or(0.0);
-        let s1 = samples.get(idx + 1).copied().unwrap_or(s0);
-        out.push(s0 + frac * (s1 - s0));
-    }
-
+    let out = crate::dsp::resample::resample_sinc(samples, rate as f64, target_rate as f64);
     (out, target_rate)
 }
 
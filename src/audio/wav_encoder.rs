@@ -246,7 +246,7 @@ pub(crate) async fn save_wav_to_shared(wav_data: &[u8], filename: &str) {
 
 /// Try to save recording via Tauri IPC (web mode).
 /// Returns the saved path on success, or None on failure.
-pub(crate) async fn try_tauri_save(wav_data: &[u8], filename: &str) -> Option<String> {
+pub(crate) async fn try_tauri_save(wav_data: &[u8], filename: &str, save_dir: &str) -> Option<String> {
     use crate::tauri_bridge::get_tauri_internals;
 
     let tauri = get_tauri_internals()?;
@@ -258,6 +258,9 @@ pub(crate) async fn try_tauri_save(wav_data: &[u8], filename: &str) -> Option<St
 
     let args = js_sys::Object::new();
     js_sys::Reflect::set(&args, &JsValue::from_str("filename"), &JsValue::from_str(filename)).ok();
+    if !save_dir.trim().is_empty() {
+        js_sys::Reflect::set(&args, &JsValue::from_str("saveDir"), &JsValue::from_str(save_dir)).ok();
+    }
 
     let array = js_sys::Uint8Array::new_with_length(wav_data.len() as u32);
     array.copy_from(wav_data);
@@ -15,6 +15,63 @@ pub enum FlowAlgo {
     Phase,
 }
 
+impl FlowAlgo {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Optical => "Optical flow",
+            Self::PhaseCoherence => "Phase coherence",
+            Self::Centroid => "Centroid",
+            Self::Gradient => "Gradient",
+            Self::Phase => "Phase",
+        }
+    }
+
+    /// What one shift value means, for exported data.
+    pub fn shift_units(self) -> &'static str {
+        match self {
+            Self::Optical => "bins moved per column / max displacement (positive = upward)",
+            Self::PhaseCoherence => "phase deviation from the expected advance / pi (0 = coherent)",
+            Self::Centroid => "local centroid shift per column / radius (positive = upward)",
+            Self::Gradient => "energy gradient / local energy (positive = upward)",
+            Self::Phase => "phase angle / pi",
+        }
+    }
+}
+
+/// Window sizes for the shift estimators, in frequency bins.
+///
+/// `radius` is the half-width of the window compared between columns
+/// (centroid and optical flow); `max_displacement` is the largest shift the
+/// optical flow search tries. Fast FM sweeps move further per column and need
+/// a larger displacement; narrowband CF calls read better with a smaller one.
+/// The gradient algorithm always uses the immediate neighbouring bins.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FlowShiftParams {
+    pub radius: usize,
+    pub max_displacement: usize,
+}
+
+impl FlowShiftParams {
+    pub const DEFAULT_RADIUS: usize = 3;
+    pub const DEFAULT_MAX_DISPLACEMENT: usize = 2;
+    /// Upper bound for both values; beyond this the windows span a large
+    /// part of a typical 256-bin column and the per-pixel cost grows quickly.
+    pub const MAX: usize = 16;
+
+    pub fn new(radius: usize, max_displacement: usize) -> Self {
+        Self {
+            radius: radius.clamp(1, Self::MAX),
+            max_displacement: max_displacement.clamp(1, Self::MAX),
+        }
+    }
+}
+
+impl Default for FlowShiftParams {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_RADIUS, Self::DEFAULT_MAX_DISPLACEMENT)
+    }
+}
+
 /// Cached intermediate data: greyscale intensities + shift values per pixel.
 /// The expensive shift computation only needs to run when file or algorithm changes.
 /// Color mapping (gates, opacity) can then be applied cheaply via `composite_flow`.
@@ -28,8 +85,8 @@ pub struct FlowData {
 }
 
 /// Compute flow data (expensive): greyscale + shift values for every pixel.
-/// Only needs to re-run when the file or algorithm changes.
-pub fn compute_flow_data(data: &SpectrogramData, algo: FlowAlgo) -> FlowData {
+/// Only needs to re-run when the file, algorithm or shift parameters change.
+pub fn compute_flow_data(data: &SpectrogramData, algo: FlowAlgo, params: FlowShiftParams) -> FlowData {
     if data.columns.is_empty() {
         return FlowData {
             width: 0,
@@ -66,9 +123,9 @@ pub fn compute_flow_data(data: &SpectrogramData, algo: FlowAlgo) -> FlowData {
             let shift = match prev {
                 None => 0.0,
                 Some(prev_mags) => match algo {
-                    FlowAlgo::Centroid => compute_centroid_shift(prev_mags, &col.magnitudes, bin_idx, h),
+                    FlowAlgo::Centroid => compute_centroid_shift(prev_mags, &col.magnitudes, bin_idx, h, params.radius),
                     FlowAlgo::Gradient => compute_gradient_shift(prev_mags, &col.magnitudes, bin_idx, h),
-                    FlowAlgo::Optical => compute_flow_shift(prev_mags, &col.magnitudes, bin_idx, h),
+                    FlowAlgo::Optical => compute_flow_shift(prev_mags, &col.magnitudes, bin_idx, h, params),
                     FlowAlgo::PhaseCoherence | FlowAlgo::Phase => 0.0, // these use their own compute paths
                 },
             };
@@ -83,6 +140,38 @@ pub fn compute_flow_data(data: &SpectrogramData, algo: FlowAlgo) -> FlowData {
     FlowData { width, height, greys, shifts }
 }
 
+/// Shift field as CSV: `metadata` as `# key: value` lines, a header of bin
+/// frequencies, then one row per column. `shifts` is row-major with row 0
+/// the highest bin, like [`FlowData::shifts`]; only `bins` are written.
+pub fn flow_field_csv(
+    shifts: &[f32],
+    width: usize,
+    height: usize,
+    bins: std::ops::Range<usize>,
+    col_time: impl Fn(usize) -> f64,
+    bin_freq: impl Fn(usize) -> f64,
+    metadata: &[(&str, String)],
+) -> String {
+    let mut out = String::new();
+    for (key, value) in metadata {
+        out.push_str(&format!("# {key}: {value}\n"));
+    }
+    out.push_str("time_s");
+    for bin in bins.clone() {
+        out.push_str(&format!(",{:.1}", bin_freq(bin)));
+    }
+    out.push('\n');
+    for col in 0..width {
+        out.push_str(&format!("{:.6}", col_time(col)));
+        for bin in bins.clone() {
+            let shift = shifts[(height - 1 - bin) * width + col];
+            out.push_str(&format!(",{shift:.4}"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
 /// Composite flow data into RGBA pixels (cheap).
 /// Re-runs when intensity_gate, flow_gate, or opacity changes.
 pub fn composite_flow(
@@ -114,8 +203,7 @@ pub fn composite_flow(
 
 /// Spectral centroid shift: compute local weighted centroid in a ±radius window
 /// around `bin` for both prev and current column, return the difference.
-fn compute_centroid_shift(prev: &[f32], curr: &[f32], bin: usize, h: usize) -> f32 {
-    let radius: usize = 3;
+fn compute_centroid_shift(prev: &[f32], curr: &[f32], bin: usize, h: usize, radius: usize) -> f32 {
     let lo = bin.saturating_sub(radius);
     let hi = (bin + radius + 1).min(h);
 
@@ -158,11 +246,12 @@ fn compute_gradient_shift(prev: &[f32], curr: &[f32], bin: usize, h: usize) -> f
     (diff_above - diff_below) / (2.0 * max_energy)
 }
 
-/// 1D vertical optical flow via cross-correlation in a small window.
-/// Returns fractional bin displacement (positive = upward shift).
-fn compute_flow_shift(prev: &[f32], curr: &[f32], bin: usize, h: usize) -> f32 {
-    let radius: usize = 3;
-    let max_disp: isize = 2;
+/// 1D vertical optical flow via cross-correlation in a ±radius window,
+/// searching displacements up to ±max_displacement bins.
+/// Returns fractional displacement normalised by max_displacement (positive = upward shift).
+fn compute_flow_shift(prev: &[f32], curr: &[f32], bin: usize, h: usize, params: FlowShiftParams) -> f32 {
+    let radius = params.radius;
+    let max_disp = params.max_displacement as isize;
 
     let lo = bin.saturating_sub(radius);
     let hi = (bin + radius + 1).min(h);
@@ -231,6 +320,7 @@ pub fn pre_render_flow_columns(
     columns: &[crate::types::SpectrogramColumn],
     prev_column_mags: Option<&[f32]>,
     algo: FlowAlgo,
+    params: FlowShiftParams,
 ) -> PreRendered {
     if columns.is_empty() {
         return PreRendered { width: 0, height: 0, pixels: Vec::new(), db_data: Vec::new(), flow_shifts: Vec::new() };
@@ -256,9 +346,9 @@ pub fn pre_render_flow_columns(
             let shift = match prev_mags {
                 None => 0.0,
                 Some(prev) => match algo {
-                    FlowAlgo::Centroid => compute_centroid_shift(prev, &col.magnitudes, bin_idx, h),
+                    FlowAlgo::Centroid => compute_centroid_shift(prev, &col.magnitudes, bin_idx, h, params.radius),
                     FlowAlgo::Gradient => compute_gradient_shift(prev, &col.magnitudes, bin_idx, h),
-                    FlowAlgo::Optical => compute_flow_shift(prev, &col.magnitudes, bin_idx, h),
+                    FlowAlgo::Optical => compute_flow_shift(prev, &col.magnitudes, bin_idx, h, params),
                     FlowAlgo::PhaseCoherence | FlowAlgo::Phase => 0.0, // these use their own compute paths
                 },
             };
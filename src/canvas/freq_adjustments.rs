@@ -1,7 +1,7 @@
 use std::cell::RefCell;
 use leptos::prelude::*;
 use crate::dsp::filters::harmonics_band_bounds;
-use crate::state::{AppState, DisplayFilterMode};
+use crate::state::{AppState, DisplayFilterMode, PRE_EMPHASIS_CORNER_OCTAVES};
 
 thread_local! {
     /// Cache for freq_adjustments: (fingerprint, result).
@@ -17,6 +17,7 @@ fn freq_adj_fingerprint(state: &AppState, file_max_freq: f64, tile_height: usize
     tile_height.hash(&mut h);
     state.display_eq.get_untracked().hash(&mut h);
     state.display_noise_filter.get_untracked().hash(&mut h);
+    state.display_pre_emphasis_db_oct.get_untracked().to_bits().hash(&mut h);
     state.filter_enabled.get_untracked().hash(&mut h);
     state.filter_freq_low.get_untracked().to_bits().hash(&mut h);
     state.filter_freq_high.get_untracked().to_bits().hash(&mut h);
@@ -49,8 +50,8 @@ fn freq_adj_fingerprint(state: &AppState, file_max_freq: f64, tile_height: usize
     h.finish()
 }
 
-/// Compute per-row dB adjustments for display EQ and noise filtering.
-/// Returns None if no adjustments are needed (both checkboxes off).
+/// Compute per-row dB adjustments for display EQ, noise filtering and
+/// pre-emphasis. Returns None if no adjustments are needed (all off).
 /// Row 0 = highest frequency, row (tile_height-1) = 0 Hz.
 /// Results are cached and only recomputed when filter settings change.
 pub fn compute_freq_adjustments(state: &AppState, file_max_freq: f64, tile_height: usize) -> Option<Vec<f32>> {
@@ -77,7 +78,8 @@ pub fn compute_freq_adjustments(state: &AppState, file_max_freq: f64, tile_heigh
 fn compute_freq_adjustments_inner(state: &AppState, file_max_freq: f64, tile_height: usize) -> Option<Vec<f32>> {
     let show_eq = state.display_eq.get_untracked();
     let show_noise = state.display_noise_filter.get_untracked();
-    if !show_eq && !show_noise {
+    let pre_emphasis = state.display_pre_emphasis_db_oct.get_untracked();
+    if !show_eq && !show_noise && pre_emphasis <= 0.0 {
         return None;
     }
     if tile_height == 0 { return None; }
@@ -198,5 +200,17 @@ fn compute_freq_adjustments_inner(state: &AppState, file_max_freq: f64, tile_hei
         }
     }
 
+    // Pre-emphasis: constant dB/octave boost above the corner frequency
+    if pre_emphasis > 0.0 {
+        let corner = file_max_freq / 2f64.powf(PRE_EMPHASIS_CORNER_OCTAVES);
+        for (row, adj_val) in adj.iter_mut().enumerate().take(tile_height) {
+            let bin = tile_height - 1 - row;
+            let freq = file_max_freq * bin as f64 / (tile_height - 1).max(1) as f64;
+            if freq > corner {
+                *adj_val += (pre_emphasis * (freq / corner).log2()) as f32;
+            }
+        }
+    }
+
     Some(adj)
 }
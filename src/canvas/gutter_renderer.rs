@@ -5,8 +5,8 @@
 // highlight (for time ranges).
 
 use web_sys::CanvasRenderingContext2d;
-use crate::canvas::colors::{freq_resistor_bands, freq_shield_color};
-use crate::canvas::overlays::{draw_bend_shield, draw_solid_shield};
+use crate::canvas::colors::freq_resistor_bands;
+use crate::canvas::overlays::{draw_bend_shield, draw_solid_shield, freq_division_interval, high_contrast, line_alpha, marker_digit_color};
 use crate::state::ShieldStyle;
 
 /// Size (px) of one checkerboard cell. Two cells fit across the gutter's
@@ -48,14 +48,6 @@ pub fn draw_fog(ctx: &CanvasRenderingContext2d, x: f64, y: f64, w: f64, h: f64,
     }
 }
 
-/// Pick a frequency-division interval based on the total visible range.
-/// Mirrors the spectrogram's `draw_freq_markers` adaptive-interval logic.
-fn pick_div_interval(range_hz: f64) -> f64 {
-    if range_hz <= 5_000.0 { 1_000.0 }
-    else if range_hz <= 25_000.0 { 5_000.0 }
-    else { 10_000.0 }
-}
-
 /// Draw the band gutter. Paints fog over the whole rectangle, then overlays
 /// a stack of per-division shield flags covering the selected
 /// [band_lo, band_hi] range. The colouring algorithm matches the
@@ -71,7 +63,7 @@ fn pick_div_interval(range_hz: f64) -> f64 {
 /// users see the band light up during the first drag even when HFR is off.
 ///
 /// Coordinate mapping: `max_freq` is at y=0 (top), `min_freq` is at y=h
-/// (bottom). The gutter mirrors whatever range the host view is currently
+/// (bottom), swapped when the frequency axis is flipped. The gutter mirrors whatever range the host view is currently
 /// displaying — on the spectrogram that's min/max_display_freq, on views
 /// without a display range it's 0..Nyquist — so its ticks line up with
 /// the host's y-axis.
@@ -104,7 +96,7 @@ pub fn draw_band_gutter(
     // Label divisions use the visible range's span — same adaptive rule as
     // the spectrogram's own axis labels, so ticks sit at identical y's.
     let range = (max_freq - min_freq).max(1.0);
-    let div_for_labels = pick_div_interval(range);
+    let div_for_labels = freq_division_interval(range);
     draw_left_axis_labels(ctx, h, min_freq, max_freq, div_for_labels);
 
     // Pick the range to paint: prefer the live drag range over the stored
@@ -123,8 +115,11 @@ pub fn draw_band_gutter(
     let hi_clamped = draw_hi.max(min_freq).min(max_freq);
     if hi_clamped <= lo_clamped { return; }
 
-    // Match the spectrogram's y-mapping: min_freq at y=h, max_freq at y=0.
-    let freq_y = |f: f64| -> f64 { h - ((f - min_freq) / range).clamp(0.0, 1.0) * h };
+    // Match the spectrogram's y-mapping: screen (top, bottom) of lo..hi.
+    let span_y = |lo: f64, hi: f64| -> (f64, f64) {
+        let (a, b) = (freq_to_y(hi, min_freq, max_freq, h), freq_to_y(lo, min_freq, max_freq, h));
+        (a.min(b), a.max(b))
+    };
 
     let div_interval = div_for_labels;
     // Drag always paints bright; steady state fades when HFR is off so the
@@ -144,8 +139,7 @@ pub fn draw_band_gutter(
             if !has_minor_coverage {
                 let clamped_lo = freq.max(lo_clamped);
                 let clamped_hi = bar_top.min(hi_clamped);
-                let y_top = freq_y(clamped_hi);
-                let y_bot = freq_y(clamped_lo);
+                let (y_top, y_bot) = span_y(clamped_lo, clamped_hi);
                 let bar_h = y_bot - y_top;
                 if bar_h >= 1.0 {
                     match shield_style {
@@ -154,7 +148,7 @@ pub fn draw_band_gutter(
                             draw_bend_shield(ctx, shield_x, y_top, shield_w, bar_h, bands, alpha_active);
                         }
                         ShieldStyle::Solid => {
-                            let c = freq_shield_color(freq, div_interval);
+                            let c = marker_digit_color(freq, div_interval);
                             draw_solid_shield(ctx, shield_x, y_top, shield_w, bar_h, c, alpha_active);
                         }
                         ShieldStyle::Off => {}
@@ -176,8 +170,7 @@ pub fn draw_band_gutter(
             if bar_top > lo_clamped && mf < hi_clamped {
                 let clamped_lo = mf.max(lo_clamped);
                 let clamped_hi = bar_top.min(hi_clamped);
-                let y_top = freq_y(clamped_hi);
-                let y_bot = freq_y(clamped_lo);
+                let (y_top, y_bot) = span_y(clamped_lo, clamped_hi);
                 let bar_h = y_bot - y_top;
                 if bar_h >= 1.0 {
                     match shield_style {
@@ -186,7 +179,7 @@ pub fn draw_band_gutter(
                             draw_bend_shield(ctx, shield_x, y_top, shield_w, bar_h, bands, alpha_minor);
                         }
                         ShieldStyle::Solid => {
-                            let c = freq_shield_color(mf, minor_interval);
+                            let c = marker_digit_color(mf, minor_interval);
                             draw_solid_shield(ctx, shield_x, y_top, shield_w, bar_h, c, alpha_minor);
                         }
                         ShieldStyle::Off => {}
@@ -207,8 +200,7 @@ pub fn draw_band_gutter(
     // "previously selected — tap to resume listening"). Skipped during an
     // active drag — the bright shields already convey "being selected".
     if !hfr_on && !is_drag {
-        let y_top = freq_y(hi_clamped);
-        let y_bot = freq_y(lo_clamped);
+        let (y_top, y_bot) = span_y(lo_clamped, hi_clamped);
         ctx.save();
         ctx.set_stroke_style_str("rgba(255,255,255,0.55)");
         ctx.set_line_width(1.0);
@@ -292,11 +284,11 @@ fn draw_left_axis_labels(
 
         // Tick: colour-tinted by the shield resistor colour, lightened so
         // it reads against the dark label column.
-        let c = freq_shield_color(freq, div_interval);
+        let c = marker_digit_color(freq, div_interval);
         let r = 160 + (c[0] as u16 * 95 / 255) as u8;
         let g = 160 + (c[1] as u16 * 95 / 255) as u8;
         let b = 160 + (c[2] as u16 * 95 / 255) as u8;
-        ctx.set_stroke_style_str(&format!("rgba({},{},{},0.85)", r, g, b));
+        ctx.set_stroke_style_str(&format!("rgba({},{},{},{:.2})", r, g, b, line_alpha(0.85)));
         ctx.set_line_width(1.0);
         ctx.begin_path();
         ctx.move_to(tick_x0, y + 0.5);
@@ -307,7 +299,7 @@ fn draw_left_axis_labels(
         let label = format!("{}", (freq / 1000.0).round() as u32);
         ctx.set_fill_style_str("rgba(0,0,0,0.85)");
         let _ = ctx.fill_text(&label, label_right_x + 0.5, y + 0.5);
-        ctx.set_fill_style_str("rgba(230,230,230,0.92)");
+        ctx.set_fill_style_str(if high_contrast() { "#fff" } else { "rgba(230,230,230,0.92)" });
         let _ = ctx.fill_text(&label, label_right_x, y);
 
         freq += div_interval;
@@ -335,7 +327,7 @@ fn draw_right_edge_ticks(
     let mut freq = first_div;
     while freq < max_freq {
         let y = freq_to_y(freq, min_freq, max_freq, h);
-        let c = freq_shield_color(freq, div_interval);
+        let c = marker_digit_color(freq, div_interval);
         // Lighten toward white so ticks read against dark fog.
         let r = 160 + (c[0] as u16 * 95 / 255) as u8;
         let g = 160 + (c[1] as u16 * 95 / 255) as u8;
@@ -365,11 +357,13 @@ fn draw_right_edge_ticks(
 }
 
 /// Map a frequency (Hz) to a Y pixel in a gutter of height `h` where
-/// `max_freq` is at the top (y=0) and `min_freq` is at the bottom (y=h).
+/// `max_freq` is at the top (y=0) and `min_freq` is at the bottom (y=h),
+/// or the other way up when the frequency axis is flipped.
 pub fn freq_to_y(freq: f64, min_freq: f64, max_freq: f64, h: f64) -> f64 {
     let range = (max_freq - min_freq).max(1.0);
     let f = freq.clamp(min_freq, max_freq);
-    h - ((f - min_freq) / range) * h
+    let frac = (f - min_freq) / range;
+    if crate::viewport::freq_axis_flipped() { frac * h } else { h - frac * h }
 }
 
 /// Inverse of `freq_to_y`: map a Y pixel to a frequency (Hz) within the
@@ -377,6 +371,6 @@ pub fn freq_to_y(freq: f64, min_freq: f64, max_freq: f64, h: f64) -> f64 {
 pub fn y_to_freq(y: f64, min_freq: f64, max_freq: f64, h: f64) -> f64 {
     if h <= 0.0 { return min_freq; }
     let range = (max_freq - min_freq).max(0.0);
-    let frac = (1.0 - (y / h)).clamp(0.0, 1.0);
-    min_freq + frac * range
+    let frac = if crate::viewport::freq_axis_flipped() { y / h } else { 1.0 - y / h };
+    min_freq + frac.clamp(0.0, 1.0) * range
 }
@@ -6,7 +6,7 @@ use crate::state::{AppState, PlaybackMode, ResizeHandlePosition, SpectrogramHand
 /// Half-width of the BandFF handle interaction zone (pixels from center).
 pub const FF_HANDLE_HALF_WIDTH: f64 = 50.0;
 
-/// Hit-test all spectrogram overlay handles (BandFF + HET).
+/// Hit-test all spectrogram overlay handles (BandFF incl. extra focus bands + HET).
 /// Returns the closest handle within `threshold` pixels of mouse_y, or None.
 /// HET handles take priority over BandFF when they overlap and HET is manual.
 /// BandFF hover is full-width; drag zone is checked separately via `is_in_band_ff_drag_zone`.
@@ -37,6 +37,15 @@ pub fn hit_test_spec_handles(
         let y_mid = spectrogram_renderer::freq_to_y(mid_freq.clamp(min_freq, max_freq), min_freq, max_freq, canvas_height);
         let d_mid = (mouse_y - y_mid).abs();
         if d_mid <= threshold { candidates.push((SpectrogramHandle::BandFfMiddle, d_mid)); }
+
+        // Extra focus band edges
+        for (i, &(lo, hi)) in state.extra_focus_bands.get_untracked().iter().enumerate() {
+            if hi <= lo { continue; }
+            let d_upper = (mouse_y - spectrogram_renderer::freq_to_y(hi.min(max_freq), min_freq, max_freq, canvas_height)).abs();
+            let d_lower = (mouse_y - spectrogram_renderer::freq_to_y(lo.max(min_freq), min_freq, max_freq, canvas_height)).abs();
+            if d_upper <= threshold { candidates.push((SpectrogramHandle::ExtraBandUpper(i), d_upper)); }
+            if d_lower <= threshold { candidates.push((SpectrogramHandle::ExtraBandLower(i), d_lower)); }
+        }
     }
 
     // HET handles (only when in HET mode and parameter is manual)
@@ -223,8 +232,8 @@ pub fn hit_test_annotation_body(
 
         let (y0, y1) = match (region.freq_high, region.freq_low) {
             (Some(fh), Some(fl)) => {
-                let y0 = spectrogram_renderer::freq_to_y(fh, min_freq, max_freq, canvas_height).max(0.0);
-                let y1 = spectrogram_renderer::freq_to_y(fl, min_freq, max_freq, canvas_height).min(canvas_height);
+                let (y0, y1) = spectrogram_renderer::freq_span_y(fl, fh, min_freq, max_freq, canvas_height);
+                let (y0, y1) = (y0.max(0.0), y1.min(canvas_height));
                 if y1 <= y0 { continue; }
                 (y0, y1)
             }
@@ -286,7 +295,8 @@ pub fn hit_test_band_ff_body(
     canvas_height: f64,
 ) -> bool {
     if band_ff_hi <= band_ff_lo { return false; }
-    let y_top = spectrogram_renderer::freq_to_y(band_ff_hi.min(max_freq), min_freq, max_freq, canvas_height);
-    let y_bottom = spectrogram_renderer::freq_to_y(band_ff_lo.max(min_freq), min_freq, max_freq, canvas_height);
+    let (y_top, y_bottom) = spectrogram_renderer::freq_span_y(
+        band_ff_lo.max(min_freq), band_ff_hi.min(max_freq), min_freq, max_freq, canvas_height,
+    );
     px_y >= y_top && px_y <= y_bottom
 }
@@ -7,7 +7,8 @@
 use std::cell::RefCell;
 use web_sys::CanvasRenderingContext2d;
 use crate::canvas::colors::{magnitude_to_db, db_to_greyscale};
-use crate::canvas::spectrogram_renderer::{ColormapMode, SpectDisplaySettings};
+use crate::canvas::spectrogram_renderer::{ColormapMode, FocusRows, SpectDisplaySettings};
+use crate::dsp::smoothing::ColumnAverager;
 use crate::types::SpectrogramColumn;
 
 /// Maximum columns to keep in the circular buffer.
@@ -32,6 +33,8 @@ pub struct LiveWaterfall {
     pub sample_rate: u32,
     /// Running max magnitude for auto-gain.
     pub max_magnitude: f32,
+    /// Display-only temporal averaging of incoming columns.
+    averager: ColumnAverager,
 }
 
 thread_local! {
@@ -52,18 +55,34 @@ pub fn create(fft_size: usize, hop_size: usize, sample_rate: u32) {
             hop_size,
             sample_rate,
             max_magnitude: 0.0,
+            averager: ColumnAverager::default(),
         });
     });
 }
 
-/// Push new FFT columns into the waterfall.
-pub fn push_columns(columns: &[SpectrogramColumn]) {
+/// Push new FFT columns into the waterfall, averaging each with the ones
+/// before it over `smoothing_secs` (0 = raw columns). Only the display is
+/// smoothed; recorded audio and its final spectrogram are unaffected.
+pub fn push_columns(columns: &[SpectrogramColumn], smoothing_secs: f64) {
     WATERFALL.with(|w| {
         let mut wf = w.borrow_mut();
         let Some(wf) = wf.as_mut() else { return };
+        let retain = ColumnAverager::retain(smoothing_secs, wf.hop_size as f64 / wf.sample_rate as f64);
+        if retain == 0.0 {
+            wf.averager.reset();
+        }
+        let mut smoothed = Vec::new();
         for col in columns {
+            let mags = if retain > 0.0 {
+                smoothed.clear();
+                smoothed.extend_from_slice(&col.magnitudes[..col.magnitudes.len().min(wf.freq_bins)]);
+                wf.averager.apply(&mut smoothed, retain);
+                &smoothed
+            } else {
+                &col.magnitudes
+            };
             let offset = wf.write_pos * wf.freq_bins;
-            for (i, &mag) in col.magnitudes.iter().take(wf.freq_bins).enumerate() {
+            for (i, &mag) in mags.iter().take(wf.freq_bins).enumerate() {
                 wf.magnitudes[offset + i] = mag;
                 if mag > wf.max_magnitude {
                     wf.max_magnitude = mag;
@@ -171,9 +190,12 @@ pub fn render_viewport(
         let oldest_available = wf.total_written.saturating_sub(wf.capacity);
 
         // Precompute bin mapping for each canvas row.
-        // Row 0 = top = high freq, row (h-1) = bottom = low freq.
+        // Row 0 = top = high freq, row (h-1) = bottom = low freq; put_image_data
+        // ignores the canvas transform, so a flipped axis is applied here.
+        let flipped = crate::viewport::freq_axis_flipped();
         let bin_map: Vec<usize> = (0..img_h as usize).map(|py| {
             let frac = py as f64 / viewport_h; // 0 at top, 1 at bottom
+            let frac = if flipped { 1.0 - frac } else { frac };
             // freq_crop_hi = top, freq_crop_lo = bottom
             let freq_frac = freq_crop_hi - frac * (freq_crop_hi - freq_crop_lo);
             (freq_frac * total_bins as f64).floor().clamp(0.0, (total_bins - 1) as f64) as usize
@@ -187,6 +209,22 @@ pub fn render_viewport(
             chunk[3] = 255;
         }
 
+        // Rows drawn in colour: every row, or in HFR mode only those inside the
+        // focus band(s), which may use their own contrast settings.
+        let (focus, focus_settings) = match colormap {
+            ColormapMode::HfrFocus { band_ff_lo_frac, band_ff_hi_frac, extra_bands, focus_settings, .. } => (
+                Some(FocusRows::new(img_h as usize, band_ff_lo_frac, band_ff_hi_frac, &extra_bands)),
+                focus_settings.unwrap_or(*settings),
+            ),
+            ColormapMode::Uniform(_) => (None, *settings),
+        };
+        let in_focus: Vec<bool> = (0..img_h as usize)
+            .map(|py| focus.as_ref().is_none_or(|f| f.contains(py)))
+            .collect();
+        let lut = match colormap {
+            ColormapMode::Uniform(cm) | ColormapMode::HfrFocus { colormap: cm, .. } => cm.lut(),
+        };
+
         // Clamp rendering to live_data_cols so we don't draw past actual data.
         let data_end = live_data_cols.min(wf.total_written);
 
@@ -218,14 +256,15 @@ pub fn render_viewport(
                     sum / n_cols as f32
                 };
                 let db = magnitude_to_db(mag);
+                let s = if in_focus[py] { &focus_settings } else { settings };
                 let grey = db_to_greyscale(
                     db,
-                    settings.floor_db,
-                    settings.range_db,
-                    settings.gamma,
-                    settings.gain_db,
+                    s.floor_db,
+                    s.range_db,
+                    s.gamma,
+                    s.gain_db,
                 );
-                let [r, g, b] = apply_colormap_mode(colormap, grey, py, img_h as usize, total_bins);
+                let [r, g, b] = if in_focus[py] { lut[grey as usize] } else { [grey, grey, grey] };
                 let idx = (py as u32 * img_w + px) as usize * 4;
                 pixels[idx] = r;
                 pixels[idx + 1] = g;
@@ -244,28 +283,3 @@ pub fn render_viewport(
         true
     })
 }
-
-/// Apply colormap mode, handling uniform and HFR focus.
-#[inline]
-fn apply_colormap_mode(
-    mode: ColormapMode,
-    grey: u8,
-    canvas_row: usize,
-    canvas_height: usize,
-    _total_bins: usize,
-) -> [u8; 3] {
-    match mode {
-        ColormapMode::Uniform(cm) => cm.apply(grey),
-        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac } => {
-            // Convert canvas row to frequency fraction.
-            let h = canvas_height as f64;
-            let focus_top = (h * (1.0 - band_ff_hi_frac)).round() as usize;
-            let focus_bot = (h * (1.0 - band_ff_lo_frac)).round() as usize;
-            if canvas_row >= focus_top && canvas_row < focus_bot {
-                cm.apply(grey)
-            } else {
-                [grey, grey, grey]
-            }
-        }
-    }
-}
@@ -1,5 +1,5 @@
 // Re-export modules from oversample-core.
-pub use oversample_core::canvas::{colors, colormap_2d, spectral_store};
+pub use oversample_core::canvas::{colors, colormap_2d, custom_colormap, lod, spectral_store};
 
 pub mod coord;
 pub mod flow;
@@ -1,13 +1,86 @@
-use crate::canvas::colors::{freq_marker_color, freq_marker_label, freq_resistor_bands, freq_shield_color};
-use crate::state::ShieldStyle;
-use crate::canvas::spectrogram_renderer::freq_to_y;
+use crate::canvas::colors::{freq_marker_label, freq_resistor_bands, rgba_css};
+use crate::state::{MarkerColors, ShieldStyle};
+use crate::canvas::spectrogram_renderer::{freq_span_y, freq_to_y};
 use crate::dsp::filters::harmonics_band_bounds;
+use crate::reference_lines::ReferenceLine;
+use crate::units::FreqUnit;
 use crate::state::{FftMode, SpectrogramHandle, Selection, ResizeHandlePosition};
+use crate::canvas::spectral_store;
+use crate::types::{SpectrogramColumn, SpectrogramData};
+use std::cell::RefCell;
+use std::sync::{Arc, Weak};
 use web_sys::CanvasRenderingContext2d;
 
 // Time markers extracted to crate::canvas::time_markers
 pub use crate::canvas::time_markers::draw_time_markers;
 
+thread_local! {
+    /// Marker palette and fixed division interval (Hz, 0 = automatic).
+    static MARKER_STYLE: std::cell::Cell<(MarkerColors, f64)> = const { std::cell::Cell::new((MarkerColors::Resistor, 0.0)) };
+    /// High-contrast theme for canvas labels and marker lines.
+    static HIGH_CONTRAST: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Most labelled divisions a fixed interval may produce before the
+/// automatic spacing takes over, so zooming out never floods the axis.
+const MAX_FIXED_DIVISIONS: f64 = 40.0;
+
+/// Set the frequency marker palette and interval. Kept in sync with
+/// `AppState::marker_colors` / `freq_marker_interval_khz` by the render
+/// effects.
+pub fn set_freq_marker_style(colors: MarkerColors, interval_hz: f64) {
+    MARKER_STYLE.with(|s| s.set((colors, interval_hz)));
+}
+
+/// Switch canvas labels and marker lines to the high-contrast theme. Kept
+/// in sync with `AppState::high_contrast` by an effect in `App`.
+pub fn set_high_contrast(on: bool) {
+    HIGH_CONTRAST.with(|c| c.set(on));
+}
+
+pub fn high_contrast() -> bool {
+    HIGH_CONTRAST.with(|c| c.get())
+}
+
+/// Alpha for overlay label text; opaque in the high-contrast theme.
+pub fn text_alpha(alpha: f64) -> f64 {
+    if high_contrast() { 1.0 } else { alpha }
+}
+
+/// Alpha for marker and tick lines; doubled (up to opaque) in the
+/// high-contrast theme.
+pub fn line_alpha(alpha: f64) -> f64 {
+    if high_contrast() { (alpha * 2.0).min(1.0) } else { alpha }
+}
+
+/// Fill behind overlay labels, near-black in the high-contrast theme.
+pub fn label_backdrop(alpha: f64) -> String {
+    format!("rgba(0,0,0,{:.2})", if high_contrast() { alpha.max(0.9) } else { alpha })
+}
+
+/// Marker colour for the digit of `freq_hz` that changes every
+/// `division_hz` (e.g. the tens of kHz for 10 kHz divisions).
+pub fn marker_digit_color(freq_hz: f64, division_hz: f64) -> [u8; 3] {
+    let digit = (freq_hz / division_hz).round() as u32;
+    MARKER_STYLE.with(|s| s.get().0).digit_color(digit)
+}
+
+/// Spacing of labelled frequency divisions: the user's fixed interval, or
+/// one picked from the visible range so there are always ~3-12 markers.
+pub fn freq_division_interval(range_hz: f64) -> f64 {
+    let fixed = MARKER_STYLE.with(|s| s.get().1);
+    if fixed > 0.0 && range_hz / fixed <= MAX_FIXED_DIVISIONS {
+        return fixed;
+    }
+    if range_hz <= 5_000.0 {
+        1_000.0
+    } else if range_hz <= 25_000.0 {
+        5_000.0
+    } else {
+        10_000.0
+    }
+}
+
 /// Describes how frequency markers should show shifted output frequencies.
 #[derive(Clone, Copy)]
 pub enum FreqShiftMode {
@@ -181,15 +254,8 @@ pub fn draw_freq_markers(
     };
 
     // Collect all division freqs within visible range.
-    // Adapt division interval to visible range so we always get ~3-12 markers.
     let range = max_freq - min_freq;
-    let div_interval = if range <= 5_000.0 {
-        1_000.0
-    } else if range <= 25_000.0 {
-        5_000.0
-    } else {
-        10_000.0
-    };
+    let div_interval = freq_division_interval(range);
     let mut divisions: Vec<f64> = Vec::new();
     let first_div = ((min_freq / div_interval).ceil() * div_interval).max(div_interval);
     let mut freq = first_div;
@@ -213,7 +279,7 @@ pub fn draw_freq_markers(
             continue;
         }
 
-        let color = freq_marker_color(freq);
+        let color = marker_digit_color(freq, 10_000.0);
 
         // Determine alpha based on HET audible band
         let base_alpha = match shift_mode {
@@ -245,8 +311,7 @@ pub fn draw_freq_markers(
                 };
                 let clamped_lo = freq.max(clamp_lo);
                 let clamped_hi = bar_top_freq.min(clamp_hi);
-                let bar_y_top = freq_to_y(clamped_hi, min_freq, max_freq, canvas_height);
-                let bar_y_bot = freq_to_y(clamped_lo, min_freq, max_freq, canvas_height);
+                let (bar_y_top, bar_y_bot) = freq_span_y(clamped_lo, clamped_hi, min_freq, max_freq, canvas_height);
                 let bar_h = bar_y_bot - bar_y_top;
                 match ms.shield_style {
                     ShieldStyle::Resistor => {
@@ -254,7 +319,7 @@ pub fn draw_freq_markers(
                         draw_bend_shield(ctx, color_bar_x, bar_y_top, color_bar_w, bar_h, bands, bar_alpha);
                     }
                     ShieldStyle::Solid => {
-                        let c = freq_shield_color(freq, div_interval);
+                        let c = marker_digit_color(freq, div_interval);
                         draw_solid_shield(ctx, color_bar_x, bar_y_top, color_bar_w, bar_h, c, bar_alpha);
                     }
                     ShieldStyle::Off => {}
@@ -263,10 +328,10 @@ pub fn draw_freq_markers(
         }
 
         // --- White text label (drawn ABOVE the division line) ---
-        ctx.set_font("11px sans-serif");
+        ctx.set_font(if high_contrast() { "bold 11px sans-serif" } else { "11px sans-serif" });
         ctx.set_text_baseline("bottom"); // text sits above the line
         let base_label = freq_marker_label(freq);
-        let label_alpha = base_alpha;
+        let label_alpha = text_alpha(base_alpha);
 
         // Build label with optional kHz suffix and shift info
         let label = match shift_mode {
@@ -330,7 +395,7 @@ pub fn draw_freq_markers(
             let bg_h = 14.0;
             let text_x = if labels_on_right { label_x - bg_metrics.width() } else { label_x };
             let bg_x = text_x - 2.0;
-            ctx.set_fill_style_str("rgba(0,0,0,0.6)");
+            ctx.set_fill_style_str(&label_backdrop(0.6));
             ctx.fill_rect(bg_x, y - 2.0 - bg_h, bg_w, bg_h);
 
             ctx.set_fill_style_str(&format!("rgba(255,255,255,{:.2})", label_alpha));
@@ -348,7 +413,7 @@ pub fn draw_freq_markers(
             let bg_h = 14.0;
             let text_x = if labels_on_right { label_x - bg_metrics.width() } else { label_x };
             let bg_x = text_x - 2.0;
-            ctx.set_fill_style_str("rgba(0,0,0,0.6)");
+            ctx.set_fill_style_str(&label_backdrop(0.6));
             ctx.fill_rect(bg_x, y - 2.0 - bg_h, bg_w, bg_h);
 
             ctx.set_fill_style_str(&format!("rgba(255,255,255,{:.2})", label_alpha));
@@ -360,7 +425,7 @@ pub fn draw_freq_markers(
         let tr = 200 + (color[0] as u16 * 55 / 255) as u8;
         let tg = 200 + (color[1] as u16 * 55 / 255) as u8;
         let tb = 200 + (color[2] as u16 * 55 / 255) as u8;
-        ctx.set_stroke_style_str(&format!("rgba({},{},{},{:.2})", tr, tg, tb, base_alpha * 0.5));
+        ctx.set_stroke_style_str(&format!("rgba({},{},{},{:.2})", tr, tg, tb, line_alpha(base_alpha * 0.5)));
         ctx.set_line_width(1.0);
         ctx.begin_path();
         ctx.move_to(0.0, y);
@@ -375,7 +440,7 @@ pub fn draw_freq_markers(
 
         // --- Full-width line (fades in when hovering label area, white) ---
         if ms.label_hover_opacity > 0.001 {
-            let full_alpha = ms.label_hover_opacity * 0.7 * base_alpha;
+            let full_alpha = line_alpha(ms.label_hover_opacity * 0.7 * base_alpha);
             ctx.set_stroke_style_str(&format!("rgba(255,255,255,{:.3})", full_alpha));
             ctx.set_line_width(1.0);
             ctx.begin_path();
@@ -399,8 +464,8 @@ pub fn draw_freq_markers(
             // Draw tick marks and labels only for non-major frequencies
             if !is_major {
                 let y = freq_to_y(mf, min_freq, max_freq, canvas_height);
-                let minor_color = freq_marker_color(mf);
-                let minor_alpha = 0.3;
+                let minor_color = marker_digit_color(mf, 10_000.0);
+                let minor_alpha = line_alpha(0.3);
 
                 // Short left tick
                 let tr = minor_color[0]; let tg = minor_color[1]; let tb = minor_color[2];
@@ -422,7 +487,7 @@ pub fn draw_freq_markers(
                     let mlabel = freq_marker_label(mf);
                     ctx.set_font("9px sans-serif");
                     ctx.set_text_baseline("bottom");
-                    let label_alpha_m = 0.4 * ms.label_hover_opacity;
+                    let label_alpha_m = text_alpha(0.4) * ms.label_hover_opacity;
                     ctx.set_fill_style_str(&format!("rgba(255,255,255,{:.2})", label_alpha_m));
                     let text_x = if labels_on_right {
                         let m = ctx.measure_text(&mlabel).unwrap();
@@ -454,8 +519,7 @@ pub fn draw_freq_markers(
                 let clamped_lo = mf.max(clamp_lo);
                 let clamped_hi = bar_top_freq_m.min(clamp_hi);
                 if clamped_hi > clamped_lo {
-                    let by_top = freq_to_y(clamped_hi, min_freq, max_freq, canvas_height);
-                    let by_bot = freq_to_y(clamped_lo, min_freq, max_freq, canvas_height);
+                    let (by_top, by_bot) = freq_span_y(clamped_lo, clamped_hi, min_freq, max_freq, canvas_height);
                     let bar_h = by_bot - by_top;
                     match ms.shield_style {
                         ShieldStyle::Resistor => {
@@ -463,7 +527,7 @@ pub fn draw_freq_markers(
                             draw_bend_shield(ctx, color_bar_x, by_top, color_bar_w, bar_h, bands, bar_alpha_m);
                         }
                         ShieldStyle::Solid => {
-                            let c = freq_shield_color(mf, minor_interval);
+                            let c = marker_digit_color(mf, minor_interval);
                             draw_solid_shield(ctx, color_bar_x, by_top, color_bar_w, bar_h, c, bar_alpha_m);
                         }
                         ShieldStyle::Off => {}
@@ -475,6 +539,46 @@ pub fn draw_freq_markers(
         }
     }
 
+    // --- Nyquist / MAX line when the axis extends above Nyquist ---
+    // The empty band above the data is filled in the base pass
+    // (`draw_no_data_region`); mark its edge with a dashed MAX line.
+    if max_freq > ms.file_max_freq + 1.0 && ms.file_max_freq > min_freq {
+        let ny_y = freq_to_y(ms.file_max_freq, min_freq, max_freq, canvas_height).round() + 0.5;
+        ctx.set_stroke_style_str("rgba(255,120,80,0.7)");
+        ctx.set_line_width(1.0);
+        let _ = ctx.set_line_dash(&js_sys::Array::of2(
+            &wasm_bindgen::JsValue::from_f64(6.0),
+            &wasm_bindgen::JsValue::from_f64(4.0),
+        ));
+        ctx.begin_path();
+        ctx.move_to(0.0, ny_y);
+        ctx.line_to(canvas_width, ny_y);
+        ctx.stroke();
+        let _ = ctx.set_line_dash(&js_sys::Array::new());
+
+        let ny_khz = ms.file_max_freq / 1000.0;
+        let mut ny_label = if ny_khz == ny_khz.round() {
+            format!("{:.0}k MAX (Nyquist)", ny_khz)
+        } else {
+            format!("{:.1}k MAX (Nyquist)", ny_khz)
+        };
+        if let FreqShiftMode::Multiply(factor) = shift_mode {
+            if factor > 1.0 {
+                ny_label.push_str(&format!(" \u{2192} {:.0}k", ny_khz * factor));
+            }
+        }
+        ctx.set_fill_style_str("rgba(255,140,100,0.85)");
+        ctx.set_font("10px sans-serif");
+        ctx.set_text_baseline("bottom");
+        let ny_text_x = if labels_on_right {
+            let m = ctx.measure_text(&ny_label).unwrap();
+            label_x - m.width()
+        } else {
+            label_x
+        };
+        let _ = ctx.fill_text(&ny_label, ny_text_x, ny_y - 2.0);
+    }
+
     // --- Nyquist / MAX marker ---
     if is_nyquist_top && !ms.mouse_in_label_area {
         let ny_y = 2.0; // just below top edge
@@ -513,11 +617,16 @@ pub fn draw_freq_markers(
 
 /// Draw the Frequency Focus overlay: dim outside the BandFF range, amber edge lines with drag handles.
 /// Handles are diamond-shaped and centered horizontally. They appear on hover, pointer-down,
-/// or always on mobile.
+/// or always on mobile. `extra_bands` (Hz) are left undimmed and get their own edge handles.
+/// The dimming is drawn at `dim_alpha` (0 = none) and skips `undimmed_rect`
+/// (`x0, y0, x1, y1` in canvas pixels), so a selection can stay clear inside it.
 pub fn draw_band_ff_overlay(
     ctx: &CanvasRenderingContext2d,
     band_ff_lo: f64,
     band_ff_hi: f64,
+    extra_bands: &[(f64, f64)],
+    dim_alpha: f64,
+    undimmed_rect: Option<(f64, f64, f64, f64)>,
     min_freq: f64,
     max_freq: f64,
     canvas_height: f64,
@@ -534,17 +643,29 @@ pub fn draw_band_ff_overlay(
     let y_top = freq_to_y(band_ff_hi.min(max_freq), min_freq, max_freq, canvas_height);
     let y_bottom = freq_to_y(band_ff_lo.max(min_freq), min_freq, max_freq, canvas_height);
 
-    // Dim outside the BandFF range
-    ctx.set_fill_style_str("rgba(0, 0, 0, 0.45)");
-    if y_top > 0.0 {
-        ctx.fill_rect(0.0, 0.0, canvas_width, y_top);
-    }
-    if y_bottom < canvas_height {
-        ctx.fill_rect(0.0, y_bottom, canvas_width, canvas_height - y_bottom);
+    // Dim outside the BandFF range (and any extra focus bands)
+    let mut lit: Vec<(f64, f64)> = std::iter::once((band_ff_lo, band_ff_hi))
+        .chain(extra_bands.iter().copied().filter(|(lo, hi)| hi > lo))
+        .map(|(lo, hi)| freq_span_y(lo.max(min_freq), hi.min(max_freq), min_freq, max_freq, canvas_height))
+        .collect();
+    lit.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+    ctx.set_fill_style_str(&format!("rgba(0, 0, 0, {:.3})", dim_alpha.clamp(0.0, 1.0)));
+    let dim = |y0: f64, y1: f64| fill_rows_except(ctx, y0, y1, canvas_width, undimmed_rect);
+    if dim_alpha > 0.0 {
+        let mut dim_from = 0.0_f64;
+        for &(top, bottom) in &lit {
+            if top > dim_from {
+                dim(dim_from, top);
+            }
+            dim_from = dim_from.max(bottom);
+        }
+        if dim_from < canvas_height {
+            dim(dim_from, canvas_height);
+        }
     }
 
-    let any_band_ff_active = matches!(hover_handle, Some(SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle))
-        || matches!(drag_handle, Some(SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle));
+    let any_band_ff_active = hover_handle.is_some_and(SpectrogramHandle::is_band_ff)
+        || drag_handle.is_some_and(SpectrogramHandle::is_band_ff);
 
     let is_active = |handle: SpectrogramHandle| -> bool {
         drag_handle == Some(handle) || hover_handle == Some(handle)
@@ -558,7 +679,13 @@ pub fn draw_band_ff_overlay(
 
     // Edge lines (full width) + centered diamond drag handles
     // Focused: dotted yellow lines; Unfocused: solid muted blue-gray lines
-    for &(y, handle) in &[(y_top, SpectrogramHandle::BandFfUpper), (y_bottom, SpectrogramHandle::BandFfLower)] {
+    let mut edges = vec![(y_top, SpectrogramHandle::BandFfUpper), (y_bottom, SpectrogramHandle::BandFfLower)];
+    for (i, &(lo, hi)) in extra_bands.iter().enumerate() {
+        if hi <= lo { continue; }
+        edges.push((freq_to_y(hi.min(max_freq), min_freq, max_freq, canvas_height), SpectrogramHandle::ExtraBandUpper(i)));
+        edges.push((freq_to_y(lo.max(min_freq), min_freq, max_freq, canvas_height), SpectrogramHandle::ExtraBandLower(i)));
+    }
+    for &(y, handle) in &edges {
         let active = is_active(handle);
         if band_ff_focused {
             // Focused: dotted yellow/amber lines
@@ -631,15 +758,24 @@ pub fn draw_band_ff_overlay(
         ctx.fill();
     }
 
-    // BandFF range labels — only when hovering/dragging BandFF handles specifically
-    let band_ff_handle_active = any_band_ff_active
-        || matches!(drag_handle, Some(SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle));
-    if band_ff_focused && band_ff_handle_active {
+    // BandFF range labels — only when hovering/dragging BandFF handles specifically.
+    // Labels follow the band that owns the active handle (main or extra).
+    let labelled_band = match drag_handle.or(hover_handle) {
+        Some(SpectrogramHandle::ExtraBandUpper(i) | SpectrogramHandle::ExtraBandLower(i)) => extra_bands.get(i).copied(),
+        _ => Some((band_ff_lo, band_ff_hi)),
+    };
+    if let (true, true, Some((band_ff_lo, band_ff_hi))) = (band_ff_focused, any_band_ff_active, labelled_band) {
+        let (y_top, y_bottom) = freq_span_y(band_ff_lo.max(min_freq), band_ff_hi.min(max_freq), min_freq, max_freq, canvas_height);
+        let (top_freq, bottom_freq) = if crate::viewport::freq_axis_flipped() {
+            (band_ff_lo, band_ff_hi)
+        } else {
+            (band_ff_hi, band_ff_lo)
+        };
         ctx.set_font("11px sans-serif");
         let label_x = center_x + handle_zone_half + 8.0;
 
-        // Top frequency label: just above the upper BandFF line
-        let top_label = format!("{:.1} kHz", band_ff_hi / 1000.0);
+        // Top frequency label: just above the band's upper line on screen
+        let top_label = format!("{:.1} kHz", top_freq / 1000.0);
         ctx.set_text_baseline("bottom");
         let top_metrics = ctx.measure_text(&top_label).unwrap();
         let bg_w = top_metrics.width() + 6.0;
@@ -649,8 +785,8 @@ pub fn draw_band_ff_overlay(
         ctx.set_fill_style_str("rgba(255, 180, 60, 0.9)");
         let _ = ctx.fill_text(&top_label, label_x, y_top - 4.0);
 
-        // Bottom frequency label: just below the lower BandFF line
-        let bottom_label = format!("{:.1} kHz", band_ff_lo / 1000.0);
+        // Bottom frequency label: just below the band's lower line on screen
+        let bottom_label = format!("{:.1} kHz", bottom_freq / 1000.0);
         ctx.set_text_baseline("top");
         let bot_metrics = ctx.measure_text(&bottom_label).unwrap();
         let bg_w = bot_metrics.width() + 6.0;
@@ -841,9 +977,230 @@ pub fn draw_pulses(
     }
 }
 
-/// Draw selection rectangle overlay on spectrogram.
-pub fn draw_selection(
+/// Draw a full-height time line and full-width frequency line through
+/// (`x`, `y`), with `label` in a readout box beside the intersection.
+/// The box flips to the other side of the cursor near the canvas edges.
+pub fn draw_crosshair(
+    ctx: &CanvasRenderingContext2d,
+    x: f64,
+    y: f64,
+    label: &str,
+    canvas_width: f64,
+    canvas_height: f64,
+) {
+    ctx.set_stroke_style_str("rgba(255, 255, 255, 0.45)");
+    ctx.set_line_width(1.0);
+    ctx.begin_path();
+    ctx.move_to(x.floor() + 0.5, 0.0);
+    ctx.line_to(x.floor() + 0.5, canvas_height);
+    ctx.move_to(0.0, y.floor() + 0.5);
+    ctx.line_to(canvas_width, y.floor() + 0.5);
+    ctx.stroke();
+
+    if label.is_empty() {
+        return;
+    }
+    ctx.set_font("11px monospace");
+    ctx.set_text_baseline("top");
+    let text_w = ctx.measure_text(label).map(|m| m.width()).unwrap_or(0.0);
+    let bg_w = text_w + 8.0;
+    let bg_h = 16.0;
+    let gap = 8.0;
+    let bx = if x + gap + bg_w <= canvas_width { x + gap } else { (x - gap - bg_w).max(0.0) };
+    let by = if y - gap - bg_h >= 0.0 { y - gap - bg_h } else { (y + gap).min(canvas_height - bg_h) };
+    ctx.set_fill_style_str("rgba(0, 0, 0, 0.7)");
+    ctx.fill_rect(bx, by, bg_w, bg_h);
+    ctx.set_fill_style_str("rgba(255, 255, 255, 0.9)");
+    let _ = ctx.fill_text(label, bx + 4.0, by + 3.0);
+    ctx.set_text_baseline("alphabetic");
+}
+
+/// Draw reference frequency lines across the spectrogram, each captioned at
+/// the left edge. Lines outside the displayed range are skipped.
+pub fn draw_reference_lines(
+    ctx: &CanvasRenderingContext2d,
+    lines: &[ReferenceLine],
+    freq_unit: FreqUnit,
+    min_freq: f64,
+    max_freq: f64,
+    canvas_width: f64,
+    canvas_height: f64,
+) {
+    if max_freq <= min_freq {
+        return;
+    }
+    ctx.set_line_width(1.0);
+    ctx.set_font("10px monospace");
+    for line in lines {
+        if line.freq_hz < min_freq || line.freq_hz > max_freq {
+            continue;
+        }
+        let y = freq_to_y(line.freq_hz, min_freq, max_freq, canvas_height).floor() + 0.5;
+        ctx.set_stroke_style_str("rgba(120, 220, 255, 0.8)");
+        let _ = ctx.set_line_dash(&js_sys::Array::of2(
+            &wasm_bindgen::JsValue::from(8.0),
+            &wasm_bindgen::JsValue::from(4.0),
+        ));
+        ctx.begin_path();
+        ctx.move_to(0.0, y);
+        ctx.line_to(canvas_width, y);
+        ctx.stroke();
+        let _ = ctx.set_line_dash(&js_sys::Array::new());
+
+        // Caption above the line, or below it when at the top edge
+        let caption = line.caption(freq_unit);
+        let text_y = if y < 14.0 { y + 12.0 } else { y - 4.0 };
+        ctx.set_fill_style_str("rgba(120, 220, 255, 0.95)");
+        let _ = ctx.fill_text(&caption, 4.0, text_y);
+    }
+}
+
+/// Height in pixels of the amplitude envelope strip along the bottom edge.
+const ENVELOPE_HEIGHT: f64 = 40.0;
+/// Levels shown in the envelope strip, below the loudest visible column.
+const ENVELOPE_RANGE_DB: f32 = 60.0;
+
+thread_local! {
+    /// Column energies of the last fully in-memory spectrogram drawn, so the
+    /// envelope isn't re-summed on every redraw. The weak reference keeps the
+    /// key's address from being reused by another file's columns.
+    static ENERGY_CACHE: RefCell<Option<(Weak<Vec<SpectrogramColumn>>, Arc<Vec<f32>>)>> = const { RefCell::new(None) };
+}
+
+/// Start or stop keeping column energies for the envelope. Turning it off
+/// frees the cached energies and those the spectral store kept.
+pub fn set_envelope_enabled(on: bool) {
+    spectral_store::set_track_energies(on);
+    if !on {
+        ENERGY_CACHE.with(|c| c.borrow_mut().take());
+    }
+}
+
+fn in_memory_energies(columns: &Arc<Vec<SpectrogramColumn>>) -> Arc<Vec<f32>> {
+    ENERGY_CACHE.with(|c| {
+        let mut cache = c.borrow_mut();
+        if let Some((key, energies)) = cache.as_ref() {
+            if std::ptr::eq(key.as_ptr(), Arc::as_ptr(columns)) {
+                return energies.clone();
+            }
+        }
+        let energies = Arc::new(columns.iter().map(SpectrogramColumn::energy).collect::<Vec<_>>());
+        *cache = Some((Arc::downgrade(columns), energies.clone()));
+        energies
+    })
+}
+
+/// Level in dB of the loudest spectrogram column under each pixel column
+/// across the visible time range, NaN where no column has been computed.
+/// Uses the in-memory columns when the file has them, otherwise the
+/// energies the spectral store kept for rendered tiles.
+pub fn envelope_levels(
+    spectrogram: &SpectrogramData,
+    file_idx: usize,
+    scroll_offset: f64,
+    visible_time: f64,
+    canvas_width: usize,
+) -> Vec<f32> {
+    let tr = spectrogram.time_resolution;
+    if tr <= 0.0 || visible_time <= 0.0 || canvas_width == 0 {
+        return Vec::new();
+    }
+    let first = (scroll_offset / tr).floor().max(0.0) as usize;
+    let last = ((scroll_offset + visible_time) / tr).ceil().max(0.0) as usize + 1;
+    let (energies, offset) = if spectrogram.columns.is_empty() {
+        (Arc::new(spectral_store::column_energies(file_idx, first, last)), first)
+    } else {
+        (in_memory_energies(&spectrogram.columns), 0)
+    };
+    let secs_per_px = visible_time / canvas_width as f64;
+    (0..canvas_width)
+        .map(|x| {
+            let t0 = scroll_offset + x as f64 * secs_per_px;
+            let c0 = (t0 / tr).floor().max(0.0) as usize;
+            let c1 = (((t0 + secs_per_px) / tr).floor().max(0.0) as usize).max(c0 + 1);
+            let lo = c0.saturating_sub(offset).min(energies.len());
+            let hi = c1.saturating_sub(offset).min(energies.len());
+            energies[lo..hi]
+                .iter()
+                .filter(|e| e.is_finite())
+                .fold(None, |m: Option<f32>, &e| Some(m.map_or(e, |m| m.max(e))))
+                .map_or(f32::NAN, |e| 10.0 * e.max(1e-20).log10())
+        })
+        .collect()
+}
+
+/// Draw a thin amplitude envelope along the bottom of the spectrogram, one
+/// level per pixel column (from `envelope_levels`), scaled to the loudest
+/// visible level. Gaps are left where levels are unknown.
+pub fn draw_envelope(
     ctx: &CanvasRenderingContext2d,
+    levels: &[f32],
+    canvas_height: f64,
+) {
+    let peak = levels.iter().copied().filter(|l| l.is_finite()).fold(f32::NEG_INFINITY, f32::max);
+    if !peak.is_finite() {
+        return;
+    }
+    let floor = peak - ENVELOPE_RANGE_DB;
+    let y_of = |level: f32| {
+        canvas_height - ((level - floor) / ENVELOPE_RANGE_DB).clamp(0.0, 1.0) as f64 * ENVELOPE_HEIGHT
+    };
+
+    // Each run of known levels becomes one filled and stroked segment
+    let mut x = 0;
+    while x < levels.len() {
+        if !levels[x].is_finite() {
+            x += 1;
+            continue;
+        }
+        let start = x;
+        while x < levels.len() && levels[x].is_finite() {
+            x += 1;
+        }
+        ctx.begin_path();
+        ctx.move_to(start as f64, canvas_height);
+        for (i, &level) in levels[start..x].iter().enumerate() {
+            ctx.line_to((start + i) as f64 + 0.5, y_of(level));
+        }
+        ctx.line_to(x as f64, canvas_height);
+        ctx.close_path();
+        ctx.set_fill_style_str("rgba(255, 200, 80, 0.18)");
+        ctx.fill();
+
+        ctx.begin_path();
+        for (i, &level) in levels[start..x].iter().enumerate() {
+            let px = (start + i) as f64 + 0.5;
+            if i == 0 { ctx.move_to(px, y_of(level)); } else { ctx.line_to(px, y_of(level)); }
+        }
+        ctx.set_stroke_style_str("rgba(255, 200, 80, 0.85)");
+        ctx.set_line_width(1.0);
+        ctx.stroke();
+    }
+}
+
+/// Fill the full-width rows `y0..y1`, leaving out `hole` (`x0, y0, x1, y1`).
+fn fill_rows_except(ctx: &CanvasRenderingContext2d, y0: f64, y1: f64, width: f64, hole: Option<(f64, f64, f64, f64)>) {
+    let Some((hx0, hy0, hx1, hy1)) = hole.filter(|&(_, hy0, _, hy1)| hy0 < y1 && hy1 > y0) else {
+        ctx.fill_rect(0.0, y0, width, y1 - y0);
+        return;
+    };
+    let (top, bottom) = (hy0.max(y0), hy1.min(y1));
+    if top > y0 {
+        ctx.fill_rect(0.0, y0, width, top - y0);
+    }
+    if bottom < y1 {
+        ctx.fill_rect(0.0, bottom, width, y1 - bottom);
+    }
+    ctx.fill_rect(0.0, top, hx0.max(0.0), bottom - top);
+    if hx1 < width {
+        ctx.fill_rect(hx1, top, width - hx1, bottom - top);
+    }
+}
+
+/// On-canvas rectangle `(x0, y0, x1, y1)` of a selection, clipped to the
+/// canvas. A selection without frequency bounds is a full-height strip.
+/// None when it lies entirely off screen.
+pub fn selection_rect(
     selection: &Selection,
     min_freq: f64,
     max_freq: f64,
@@ -852,7 +1209,7 @@ pub fn draw_selection(
     zoom: f64,
     canvas_width: f64,
     canvas_height: f64,
-) {
+) -> Option<(f64, f64, f64, f64)> {
     let visible_time = (canvas_width / zoom) * time_resolution;
     let start_time = scroll_offset;
     let px_per_sec = canvas_width / visible_time;
@@ -861,22 +1218,42 @@ pub fn draw_selection(
     let x1 = ((selection.time_end - start_time) * px_per_sec).min(canvas_width);
 
     if x1 <= x0 {
-        return;
+        return None;
     }
 
-    // If frequency bounds are set, draw a bounded rectangle; otherwise full-height strip
-    let (y0, y1) = match (selection.freq_high, selection.freq_low) {
-        (Some(fh), Some(fl)) => {
-            let y0 = freq_to_y(fh, min_freq, max_freq, canvas_height).max(0.0);
-            let y1 = freq_to_y(fl, min_freq, max_freq, canvas_height).min(canvas_height);
-            if y1 <= y0 { return; }
+    let (y0, y1) = match (selection.freq_low, selection.freq_high) {
+        (Some(fl), Some(fh)) => {
+            let (y0, y1) = freq_span_y(fl, fh, min_freq, max_freq, canvas_height);
+            let (y0, y1) = (y0.max(0.0), y1.min(canvas_height));
+            if y1 <= y0 { return None; }
             (y0, y1)
         }
         _ => (0.0, canvas_height),
     };
+    Some((x0, y0, x1, y1))
+}
+
+/// Draw selection rectangle overlay on spectrogram.
+pub fn draw_selection(
+    ctx: &CanvasRenderingContext2d,
+    selection: &Selection,
+    min_freq: f64,
+    max_freq: f64,
+    scroll_offset: f64,
+    time_resolution: f64,
+    zoom: f64,
+    canvas_width: f64,
+    canvas_height: f64,
+    color: [u8; 3],
+) {
+    let Some((x0, y0, x1, y1)) = selection_rect(
+        selection, min_freq, max_freq, scroll_offset, time_resolution, zoom, canvas_width, canvas_height,
+    ) else {
+        return;
+    };
 
     // Fill
-    ctx.set_fill_style_str("rgba(50, 120, 200, 0.15)");
+    ctx.set_fill_style_str(&rgba_css(color, 0.15));
     ctx.fill_rect(x0, y0, x1 - x0, y1 - y0);
 
     // Dotted border (distinguishes transient selection from annotations)
@@ -884,12 +1261,57 @@ pub fn draw_selection(
         &wasm_bindgen::JsValue::from_f64(1.0),
         &wasm_bindgen::JsValue::from_f64(3.0),
     ));
-    ctx.set_stroke_style_str("rgba(120, 190, 255, 0.95)");
+    ctx.set_stroke_style_str(&rgba_css(color, 0.95));
     ctx.set_line_width(1.0);
     ctx.stroke_rect(x0, y0, x1 - x0, y1 - y0);
     let _ = ctx.set_line_dash(&js_sys::Array::new());
 }
 
+/// Draw the snap-to-call preview: a solid outline with corner ticks, so it
+/// reads as distinct from the dotted box that was drawn.
+pub fn draw_snap_preview(
+    ctx: &CanvasRenderingContext2d,
+    snapped: &Selection,
+    min_freq: f64,
+    max_freq: f64,
+    scroll_offset: f64,
+    time_resolution: f64,
+    zoom: f64,
+    canvas_width: f64,
+    canvas_height: f64,
+    color: [u8; 3],
+) {
+    let visible_time = (canvas_width / zoom) * time_resolution;
+    let px_per_sec = canvas_width / visible_time;
+    let x0 = (snapped.time_start - scroll_offset) * px_per_sec;
+    let x1 = (snapped.time_end - scroll_offset) * px_per_sec;
+    let (y0, y1) = match (snapped.freq_low, snapped.freq_high) {
+        (Some(fl), Some(fh)) => freq_span_y(fl, fh, min_freq, max_freq, canvas_height),
+        _ => (0.0, canvas_height),
+    };
+    if x1 < 0.0 || x0 > canvas_width || x1 <= x0 || y1 <= y0 {
+        return;
+    }
+
+    ctx.set_stroke_style_str(&rgba_css(color, 1.0));
+    ctx.set_line_width(1.5);
+    ctx.stroke_rect(x0, y0, x1 - x0, y1 - y0);
+    let tick = 6.0f64.min((x1 - x0) / 2.0).min((y1 - y0) / 2.0);
+    ctx.set_line_width(3.0);
+    ctx.begin_path();
+    for (x, y, dx, dy) in [(x0, y0, 1.0, 1.0), (x1, y0, -1.0, 1.0), (x0, y1, 1.0, -1.0), (x1, y1, -1.0, -1.0)] {
+        ctx.move_to(x + dx * tick, y);
+        ctx.line_to(x, y);
+        ctx.line_to(x, y + dy * tick);
+    }
+    ctx.stroke();
+
+    ctx.set_font("10px sans-serif");
+    ctx.set_text_baseline("bottom");
+    ctx.set_fill_style_str(&rgba_css(color, 1.0));
+    let _ = ctx.fill_text("snap \u{23CE}", x0, y0 - 2.0);
+}
+
 /// Draw shadow selection boxes one octave higher and lower to highlight harmonics.
 /// Only drawn when the selection spans less than 1 octave.
 pub fn draw_harmonic_shadows(
@@ -902,6 +1324,7 @@ pub fn draw_harmonic_shadows(
     zoom: f64,
     canvas_width: f64,
     canvas_height: f64,
+    color: [u8; 3],
 ) {
     // Need frequency bounds for harmonic shadows
     let (freq_low, freq_high) = match (selection.freq_low, selection.freq_high) {
@@ -924,6 +1347,8 @@ pub fn draw_harmonic_shadows(
         return;
     }
     let w = x1 - x0;
+    let fill = rgba_css(color, 0.06);
+    let stroke = rgba_css(color, 0.3);
 
     // Set up dashed border style
     let _ = ctx.set_line_dash(&js_sys::Array::of2(
@@ -935,12 +1360,12 @@ pub fn draw_harmonic_shadows(
     let hi_low = freq_low * 2.0;
     let hi_high = freq_high * 2.0;
     if hi_low < max_freq {
-        let y0 = freq_to_y(hi_high.min(max_freq), min_freq, max_freq, canvas_height).max(0.0);
-        let y1 = freq_to_y(hi_low, min_freq, max_freq, canvas_height).min(canvas_height);
+        let (y0, y1) = freq_span_y(hi_low, hi_high.min(max_freq), min_freq, max_freq, canvas_height);
+        let (y0, y1) = (y0.max(0.0), y1.min(canvas_height));
         if y1 > y0 {
-            ctx.set_fill_style_str("rgba(50, 120, 200, 0.06)");
+            ctx.set_fill_style_str(&fill);
             ctx.fill_rect(x0, y0, w, y1 - y0);
-            ctx.set_stroke_style_str("rgba(80, 160, 255, 0.3)");
+            ctx.set_stroke_style_str(&stroke);
             ctx.set_line_width(1.0);
             ctx.stroke_rect(x0, y0, w, y1 - y0);
         }
@@ -950,12 +1375,12 @@ pub fn draw_harmonic_shadows(
     let lo_low = freq_low / 2.0;
     let lo_high = freq_high / 2.0;
     {
-        let y0 = freq_to_y(lo_high, min_freq, max_freq, canvas_height).max(0.0);
-        let y1 = freq_to_y(lo_low.max(min_freq), min_freq, max_freq, canvas_height).min(canvas_height);
+        let (y0, y1) = freq_span_y(lo_low.max(min_freq), lo_high, min_freq, max_freq, canvas_height);
+        let (y0, y1) = (y0.max(0.0), y1.min(canvas_height));
         if y1 > y0 {
-            ctx.set_fill_style_str("rgba(50, 120, 200, 0.06)");
+            ctx.set_fill_style_str(&fill);
             ctx.fill_rect(x0, y0, w, y1 - y0);
-            ctx.set_stroke_style_str("rgba(80, 160, 255, 0.3)");
+            ctx.set_stroke_style_str(&stroke);
             ctx.set_line_width(1.0);
             ctx.stroke_rect(x0, y0, w, y1 - y0);
         }
@@ -997,8 +1422,8 @@ pub fn draw_filter_overlay(
         _ => return,
     };
 
-    let y_top = freq_to_y(band_hi.min(max_freq), min_freq, max_freq, canvas_height).max(0.0);
-    let y_bot = freq_to_y(band_lo.max(min_freq), min_freq, max_freq, canvas_height).min(canvas_height);
+    let (y_top, y_bot) = freq_span_y(band_lo.max(min_freq), band_hi.min(max_freq), min_freq, max_freq, canvas_height);
+    let (y_top, y_bot) = (y_top.max(0.0), y_bot.min(canvas_height));
 
     if y_bot <= y_top {
         return;
@@ -1068,8 +1493,7 @@ pub fn draw_notch_bands(
             continue;
         }
 
-        let y_top = freq_to_y(freq_hi.min(max_freq), min_freq, max_freq, canvas_height);
-        let y_bot = freq_to_y(freq_lo.max(min_freq), min_freq, max_freq, canvas_height);
+        let (y_top, y_bot) = freq_span_y(freq_lo.max(min_freq), freq_hi.min(max_freq), min_freq, max_freq, canvas_height);
         let y_center = freq_to_y(center, min_freq, max_freq, canvas_height);
         let band_h = (y_bot - y_top).max(1.0);
 
@@ -1194,7 +1618,7 @@ pub fn draw_tile_debug_overlay(
     let ch = viewport_height;
     if total_cols == 0 || zoom <= 0.0 { return; }
 
-    let ideal_lod = tile_cache::select_lod(zoom);
+    let ideal_lod = tile_cache::main_view_lod(zoom);
     let ratio = tile_cache::lod_ratio(ideal_lod);
 
     let vis_start = scroll_col.max(0.0).min((total_cols as f64 - 1.0).max(0.0));
@@ -1417,10 +1841,10 @@ pub fn draw_annotations(
             continue;
         }
 
-        let (y0, y1) = match (sel.freq_high, sel.freq_low) {
-            (Some(fh), Some(fl)) => {
-                let y0 = freq_to_y(fh, min_freq, max_freq, canvas_height).max(0.0);
-                let y1 = freq_to_y(fl, min_freq, max_freq, canvas_height).min(canvas_height);
+        let (y0, y1) = match (sel.freq_low, sel.freq_high) {
+            (Some(fl), Some(fh)) => {
+                let (y0, y1) = freq_span_y(fl, fh, min_freq, max_freq, canvas_height);
+                let (y0, y1) = (y0.max(0.0), y1.min(canvas_height));
                 if y1 <= y0 { continue; }
                 (y0, y1)
             }
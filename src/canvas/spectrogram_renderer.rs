@@ -1,11 +1,11 @@
 // SPDX-License-Identifier: GPL-3.0-only OR MIT OR Apache-2.0
 use crate::canvas::colors::{
     magnitude_to_greyscale, magnitude_to_db,
-    db_to_greyscale, flow_rgb_scheme, coherence_rgb, phase_rgb,
+    db_to_greyscale, flow_rgb_scheme, coherence_rgb, coherence_lut_rgb, phase_rgb,
     greyscale_to_viridis, greyscale_to_inferno,
     greyscale_to_magma, greyscale_to_plasma, greyscale_to_cividis, greyscale_to_turbo,
 };
-use crate::state::FlowColorScheme;
+use crate::state::{FlowBlendMode, FlowColorScheme, NoDataFill};
 use crate::types::{PreviewImage, SpectrogramData};
 use wasm_bindgen::JsCast;
 use wasm_bindgen::Clamped;
@@ -14,18 +14,81 @@ use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
 use crate::viewport;
 
 // Re-export from split modules so callers don't need to change imports
-pub use crate::canvas::flow::{FlowAlgo, FlowData, compute_flow_data, composite_flow, pre_render_flow_columns};
+pub use crate::canvas::flow::{FlowAlgo, FlowData, FlowShiftParams, compute_flow_data, composite_flow, pre_render_flow_columns};
 pub use crate::canvas::overlays::{
     FreqShiftMode, FreqMarkerState, TimeMarkerStyle, DebugTileKind,
     draw_freq_markers, draw_time_markers, draw_band_ff_overlay, draw_het_overlay,
-    draw_pulses, draw_selection, draw_harmonic_shadows, draw_filter_overlay,
+    draw_pulses, draw_crosshair, draw_reference_lines, draw_envelope, envelope_levels, set_envelope_enabled, draw_selection, selection_rect, draw_snap_preview, draw_harmonic_shadows, draw_filter_overlay,
     pixel_to_time_freq, draw_notch_bands, draw_tile_debug_overlay, draw_annotations,
-    draw_time_marker_lines,
+    draw_time_marker_lines, set_freq_marker_style,
 };
 
 // PreRendered and SpectDisplaySettings are defined in oversample-core::types.
 pub use crate::types::{PreRendered, SpectDisplaySettings};
 
+thread_local! {
+    /// Fill for canvas areas with no spectrogram data.
+    static BACKGROUND: std::cell::Cell<[u8; 3]> = const { std::cell::Cell::new([0, 0, 0]) };
+    /// Expanded LUTs for the user's custom colormaps, indexed by `Colormap::Custom`.
+    static CUSTOM_LUTS: std::cell::RefCell<Vec<[[u8; 3]; 256]>> = const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Rebuild the LUTs behind `Colormap::Custom`. Kept in sync with
+/// `AppState::custom_colormaps`; cached tiles are dropped so an edited map
+/// shows straight away.
+pub fn set_custom_colormaps(maps: &[crate::canvas::custom_colormap::CustomColormap]) {
+    CUSTOM_LUTS.with(|l| *l.borrow_mut() = maps.iter().map(|m| m.lut()).collect());
+    clear_tile_canvas_cache();
+}
+
+/// Set the color the blit functions clear to. Kept in sync with
+/// `AppState::spectrogram_background` by the render effects.
+pub fn set_background_color(rgb: [u8; 3]) {
+    BACKGROUND.with(|c| c.set(rgb));
+}
+
+/// Fill `w`×`h` from the origin with the spectrogram background color.
+pub fn clear_background(ctx: &CanvasRenderingContext2d, w: f64, h: f64) {
+    ctx.set_fill_style_str(&crate::canvas::colors::rgb_to_hex(BACKGROUND.with(|c| c.get())));
+    ctx.fill_rect(0.0, 0.0, w, h);
+}
+
+/// Mark the band from the top of the canvas down to `bottom_y` as "no data"
+/// (the axis extends above the file's Nyquist), so it isn't read as silence.
+pub fn draw_no_data_region(ctx: &CanvasRenderingContext2d, w: f64, bottom_y: f64, fill: NoDataFill) {
+    if bottom_y <= 0.0 || w <= 0.0 {
+        return;
+    }
+    match fill {
+        NoDataFill::Hatch => {
+            const SPACING: f64 = 8.0;
+            clear_background(ctx, w, bottom_y);
+            ctx.save();
+            ctx.begin_path();
+            ctx.rect(0.0, 0.0, w, bottom_y);
+            ctx.clip();
+            ctx.set_stroke_style_str("rgba(255,255,255,0.14)");
+            ctx.set_line_width(1.0);
+            ctx.begin_path();
+            // 45° lines, spaced along x, covering the band's full height
+            let mut x = -bottom_y;
+            while x < w {
+                ctx.move_to(x, bottom_y);
+                ctx.line_to(x + bottom_y, 0.0);
+                x += SPACING;
+            }
+            ctx.stroke();
+            ctx.restore();
+        }
+        NoDataFill::Tint => {
+            clear_background(ctx, w, bottom_y);
+            ctx.set_fill_style_str("rgba(128,128,128,0.18)");
+            ctx.fill_rect(0.0, 0.0, w, bottom_y);
+        }
+        NoDataFill::None => {}
+    }
+}
+
 /// Pre-render the entire spectrogram to an RGBA pixel buffer.
 /// Width = number of columns, Height = number of frequency bins.
 /// Frequency axis: row 0 = highest frequency (top), last row = 0 Hz (bottom).
@@ -129,20 +192,49 @@ pub enum TileRenderMode {
         color_gamma: f32,
         algo: FlowAlgo,
         scheme: FlowColorScheme,
+        blend: FlowBlendMode,
+        /// Colormap for phase coherence; None = built-in deviation colors.
+        coherence_colormap: Option<Colormap>,
     },
 }
 
 /// Convert a frequency to a canvas Y coordinate.
-/// min_freq is shown at the bottom (y = canvas_height), max_freq at the top (y = 0).
+/// min_freq is shown at the bottom (y = canvas_height), max_freq at the top (y = 0),
+/// or the other way up when the frequency axis is flipped.
 #[inline]
 pub fn freq_to_y(freq: f64, min_freq: f64, max_freq: f64, canvas_height: f64) -> f64 {
-    canvas_height * (1.0 - (freq - min_freq) / (max_freq - min_freq))
+    let frac = (freq - min_freq) / (max_freq - min_freq);
+    if crate::viewport::freq_axis_flipped() {
+        canvas_height * frac
+    } else {
+        canvas_height * (1.0 - frac)
+    }
 }
 
 /// Convert a canvas Y coordinate back to a frequency.
 #[inline]
 pub fn y_to_freq(y: f64, min_freq: f64, max_freq: f64, canvas_height: f64) -> f64 {
-    min_freq + (max_freq - min_freq) * (1.0 - y / canvas_height)
+    let frac = if crate::viewport::freq_axis_flipped() { y / canvas_height } else { 1.0 - y / canvas_height };
+    min_freq + (max_freq - min_freq) * frac
+}
+
+/// Screen extent `(top, bottom)` of the band `lo..hi` Hz, whichever way up
+/// the frequency axis runs.
+#[inline]
+pub fn freq_span_y(lo: f64, hi: f64, min_freq: f64, max_freq: f64, canvas_height: f64) -> (f64, f64) {
+    let a = freq_to_y(hi, min_freq, max_freq, canvas_height);
+    let b = freq_to_y(lo, min_freq, max_freq, canvas_height);
+    (a.min(b), a.max(b))
+}
+
+/// Apply the frequency-axis orientation to `ctx` for drawing an image whose
+/// rows run from max_freq at the top to min_freq at the bottom. Pair with
+/// `ctx.save()` / `ctx.restore()`.
+pub fn orient_freq_axis(ctx: &CanvasRenderingContext2d, canvas_height: f64) {
+    if crate::viewport::freq_axis_flipped() {
+        let _ = ctx.translate(0.0, canvas_height);
+        let _ = ctx.scale(1.0, -1.0);
+    }
 }
 
 /// A base colormap LUT choice.
@@ -156,6 +248,9 @@ pub enum Colormap {
     Cividis,
     Turbo,
     Greyscale,
+    /// A user-defined map, by index into `AppState::custom_colormaps`.
+    /// Renders as greyscale if the index no longer exists.
+    Custom(u8),
 }
 
 impl Colormap {
@@ -170,8 +265,95 @@ impl Colormap {
             Colormap::Cividis => greyscale_to_cividis(grey),
             Colormap::Turbo => greyscale_to_turbo(grey),
             Colormap::Greyscale => [grey, grey, grey],
+            Colormap::Custom(i) => CUSTOM_LUTS.with(|l| {
+                l.borrow().get(i as usize).map_or([grey, grey, grey], |lut| lut[grey as usize])
+            }),
+        }
+    }
+
+    /// Every output of [`apply`](Self::apply), for loops over many pixels.
+    /// A custom map's thread-local is borrowed once rather than per pixel.
+    pub fn lut(self) -> [[u8; 3]; 256] {
+        if let Colormap::Custom(i) = self {
+            if let Some(lut) = CUSTOM_LUTS.with(|l| l.borrow().get(i as usize).copied()) {
+                return lut;
+            }
+        }
+        std::array::from_fn(|grey| self.apply(grey as u8))
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Colormap::Viridis => "Viridis",
+            Colormap::Inferno => "Inferno",
+            Colormap::Magma => "Magma",
+            Colormap::Plasma => "Plasma",
+            Colormap::Cividis => "Cividis",
+            Colormap::Turbo => "Turbo",
+            Colormap::Greyscale => "Greyscale",
+            Colormap::Custom(_) => "Custom",
         }
     }
+
+    /// Stable key for selects and storage, e.g. "viridis" or "custom:2".
+    pub fn key(self) -> String {
+        match self {
+            Colormap::Custom(i) => format!("custom:{i}"),
+            cm => cm.label().to_ascii_lowercase(),
+        }
+    }
+
+    /// Inverse of [`key`](Self::key); unknown keys give Viridis.
+    pub fn from_key(s: &str) -> Self {
+        if let Some(i) = s.strip_prefix("custom:").and_then(|i| i.parse().ok()) {
+            return Colormap::Custom(i);
+        }
+        Self::ALL.iter().copied().find(|cm| cm.key() == s).unwrap_or_default()
+    }
+
+    /// Distinct value per colormap, for tile-cache fingerprints.
+    fn fingerprint(self) -> u64 {
+        match self {
+            Colormap::Viridis => 0,
+            Colormap::Inferno => 1,
+            Colormap::Magma => 2,
+            Colormap::Plasma => 3,
+            Colormap::Cividis => 4,
+            Colormap::Turbo => 5,
+            Colormap::Greyscale => 6,
+            Colormap::Custom(i) => 0x100 + i as u64,
+        }
+    }
+
+    pub const ALL: &'static [Colormap] = &[
+        Colormap::Viridis,
+        Colormap::Inferno,
+        Colormap::Magma,
+        Colormap::Plasma,
+        Colormap::Cividis,
+        Colormap::Turbo,
+        Colormap::Greyscale,
+    ];
+
+    /// CSS `linear-gradient` through this colormap, low to high, for legends.
+    pub fn css_gradient(self) -> String {
+        css_gradient_of(|grey| self.apply(grey))
+    }
+}
+
+/// CSS `linear-gradient` through a LUT, e.g. a custom colormap being edited.
+pub fn lut_css_gradient(lut: &[[u8; 3]; 256]) -> String {
+    css_gradient_of(|grey| lut[grey as usize])
+}
+
+fn css_gradient_of(apply: impl Fn(u8) -> [u8; 3]) -> String {
+    let stops: Vec<String> = (0..=16)
+        .map(|i| {
+            let [r, g, b] = apply((i * 255 / 16) as u8);
+            format!("rgb({r},{g},{b})")
+        })
+        .collect();
+    format!("linear-gradient(to right, {})", stops.join(", "))
 }
 
 /// Which colormap to apply when blitting the spectrogram.
@@ -179,9 +361,92 @@ impl Colormap {
 pub enum ColormapMode {
     /// Uniform colormap across the entire spectrogram.
     Uniform(Colormap),
-    /// Colormap inside HFR focus band, greyscale outside.
+    /// Colormap inside HFR focus band(s), greyscale outside.
     /// Fractions are relative to the full image (0 Hz = 0.0, file_max_freq = 1.0).
-    HfrFocus { colormap: Colormap, band_ff_lo_frac: f64, band_ff_hi_frac: f64 },
+    /// `extra_bands` are additional focus bands colored alongside the main one.
+    /// `focus_settings`, when set, replaces the display settings inside the
+    /// focus band(s) (only where dB data is available, not for previews).
+    HfrFocus {
+        colormap: Colormap,
+        band_ff_lo_frac: f64,
+        band_ff_hi_frac: f64,
+        extra_bands: ExtraFocusBands,
+        focus_settings: Option<SpectDisplaySettings>,
+    },
+}
+
+impl ColormapMode {
+    /// Set the focus-band display settings (no-op for `Uniform`).
+    pub fn with_focus_settings(self, settings: Option<SpectDisplaySettings>) -> Self {
+        match self {
+            ColormapMode::HfrFocus { colormap, band_ff_lo_frac, band_ff_hi_frac, extra_bands, .. } => ColormapMode::HfrFocus {
+                colormap,
+                band_ff_lo_frac,
+                band_ff_hi_frac,
+                extra_bands,
+                focus_settings: settings,
+            },
+            uniform => uniform,
+        }
+    }
+}
+
+/// Maximum number of extra focus bands on top of the main BandFF range.
+pub const MAX_EXTRA_FOCUS_BANDS: usize = 3;
+
+/// Additional HFR focus bands as (lo_frac, hi_frac) pairs, same units as
+/// `ColormapMode::HfrFocus`. Fixed-size so `ColormapMode` stays `Copy`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ExtraFocusBands {
+    bands: [(f64, f64); MAX_EXTRA_FOCUS_BANDS],
+    len: usize,
+}
+
+impl ExtraFocusBands {
+    /// Build from bands in Hz, converting to fractions of `file_max_freq`.
+    /// Empty or inverted bands are skipped; extras beyond the limit are dropped.
+    pub fn from_hz(bands_hz: &[(f64, f64)], file_max_freq: f64) -> Self {
+        let mut out = Self::default();
+        if file_max_freq <= 0.0 { return out; }
+        for &(lo, hi) in bands_hz.iter().filter(|(lo, hi)| hi > lo) {
+            if out.len == MAX_EXTRA_FOCUS_BANDS { break; }
+            out.bands[out.len] = (lo / file_max_freq, hi / file_max_freq);
+            out.len += 1;
+        }
+        out
+    }
+
+    pub fn as_slice(&self) -> &[(f64, f64)] {
+        &self.bands[..self.len]
+    }
+}
+
+/// Image row spans (top inclusive, bottom exclusive) covered by the HFR focus
+/// band(s). Row 0 = highest freq; last row = 0 Hz.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct FocusRows {
+    spans: [(usize, usize); 1 + MAX_EXTRA_FOCUS_BANDS],
+    len: usize,
+}
+
+impl FocusRows {
+    pub(crate) fn new(height: usize, band_ff_lo_frac: f64, band_ff_hi_frac: f64, extra: &ExtraFocusBands) -> Self {
+        let h = height as f64;
+        let span = |lo: f64, hi: f64| {
+            ((h * (1.0 - hi)).round().max(0.0) as usize, (h * (1.0 - lo)).round().max(0.0) as usize)
+        };
+        let mut spans = [(0, 0); 1 + MAX_EXTRA_FOCUS_BANDS];
+        spans[0] = span(band_ff_lo_frac, band_ff_hi_frac);
+        for (i, &(lo, hi)) in extra.as_slice().iter().enumerate() {
+            spans[i + 1] = span(lo, hi);
+        }
+        Self { spans, len: 1 + extra.len }
+    }
+
+    #[inline]
+    pub(crate) fn contains(&self, row: usize) -> bool {
+        self.spans[..self.len].iter().any(|&(top, bot)| row >= top && row < bot)
+    }
 }
 
 /// Blit the pre-rendered spectrogram to a visible canvas, handling scroll, zoom, and freq crop.
@@ -202,29 +467,29 @@ pub fn blit_viewport(
     let ch = viewport_height;
 
     // Clear canvas
-    ctx.set_fill_style_str("#000");
-    ctx.fill_rect(0.0, 0.0, cw, ch);
+    clear_background(ctx, cw, ch);
 
-    if pre_rendered.width == 0 || pre_rendered.height == 0 {
+    if pre_rendered.width == 0 || pre_rendered.height == 0 || zoom <= 0.0 {
         return;
     }
 
     let fc_lo = freq_crop_lo.max(0.0);
     let fc_hi = freq_crop_hi.max(0.01);
 
-    // How many source columns are visible at current zoom
-    let natural_visible_cols = cw / zoom;
-    let visible_cols = natural_visible_cols.min(pre_rendered.width as f64);
-    let src_start = scroll_col.max(0.0).min((pre_rendered.width as f64 - visible_cols).max(0.0));
-
-    // If file has fewer columns than the view span, draw at correct proportional
-    // width instead of stretching.  This keeps the spectrogram aligned with the
-    // time-to-pixel mapping used by the playhead, waveform, and overlays.
-    let dst_w = if (pre_rendered.width as f64) < natural_visible_cols {
-        cw * (pre_rendered.width as f64 / natural_visible_cols)
-    } else {
-        cw
-    };
+    // Visible source columns, placed at `zoom` px per column from the scroll
+    // position rather than stretched to the canvas. This keeps the spectrogram
+    // aligned with the time-to-pixel mapping used by the playhead, waveform,
+    // and overlays, including a lead-in before the first column and a clip
+    // of a single column.
+    let total_cols = pre_rendered.width as f64;
+    let src_start = scroll_col.clamp(0.0, total_cols);
+    let src_end = (scroll_col + cw / zoom).min(total_cols);
+    if src_end <= src_start {
+        return;
+    }
+    let visible_cols = src_end - src_start;
+    let dst_x = (src_start - scroll_col) * zoom;
+    let dst_w = visible_cols * zoom;
 
     // Vertical crop: row 0 = highest freq, last row = 0 Hz
     // Extract the band from fc_lo to fc_hi of the full image
@@ -250,8 +515,9 @@ pub fn blit_viewport(
             } else {
                 mapped_pixels = {
                     let mut buf = pre_rendered.pixels.clone();
+                    let lut = cm.lut();
                     for chunk in buf.chunks_exact_mut(4) {
-                        let [r, g, b] = cm.apply(chunk[0]);
+                        let [r, g, b] = lut[chunk[0] as usize];
                         chunk[0] = r;
                         chunk[1] = g;
                         chunk[2] = b;
@@ -261,20 +527,18 @@ pub fn blit_viewport(
                 &mapped_pixels
             }
         }
-        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac } => {
+        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac, extra_bands, .. } => {
             mapped_pixels = {
                 let mut buf = pre_rendered.pixels.clone();
-                let h = pre_rendered.height as f64;
                 let w = pre_rendered.width as usize;
-                // Row 0 = highest freq; last row = 0 Hz
-                let focus_top = (h * (1.0 - band_ff_hi_frac)).round() as usize;
-                let focus_bot = (h * (1.0 - band_ff_lo_frac)).round() as usize;
+                let focus = FocusRows::new(pre_rendered.height as usize, band_ff_lo_frac, band_ff_hi_frac, &extra_bands);
+                let lut = cm.lut();
                 for row in 0..pre_rendered.height as usize {
-                    if row >= focus_top && row < focus_bot {
+                    if focus.contains(row) {
                         let base = row * w * 4;
                         for col in 0..w {
                             let i = base + col * 4;
-                            let [r, g, b] = cm.apply(buf[i]);
+                            let [r, g, b] = lut[buf[i] as usize];
                             buf[i] = r;
                             buf[i + 1] = g;
                             buf[i + 2] = b;
@@ -308,7 +572,7 @@ pub fn blit_viewport(
                 src_y,
                 visible_cols,
                 src_h,
-                0.0,
+                dst_x,
                 dst_y,
                 dst_w,
                 dst_h,
@@ -338,8 +602,7 @@ pub fn blit_preview_as_background(
     let cw = viewport_width;
     let ch = viewport_height;
 
-    ctx.set_fill_style_str("#000");
-    ctx.fill_rect(0.0, 0.0, cw, ch);
+    clear_background(ctx, cw, ch);
 
     if preview.width == 0 || preview.height == 0 || total_duration <= 0.0 {
         return;
@@ -378,10 +641,10 @@ pub fn blit_preview_as_background(
     let mut pixels = preview.pixels.as_ref().clone();
     match colormap {
         ColormapMode::Uniform(cm) => apply_colormap_to_tile(&mut pixels, cm),
-        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac } => {
+        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac, extra_bands, .. } => {
             apply_hfr_colormap_to_tile(
                 &mut pixels, preview.width, preview.height,
-                cm, band_ff_lo_frac, band_ff_hi_frac,
+                cm, band_ff_lo_frac, band_ff_hi_frac, &extra_bands,
             );
         }
     }
@@ -500,16 +763,26 @@ fn tile_render_fingerprint(
         TileRenderMode::Spectrogram(colormap) => match colormap {
             ColormapMode::Uniform(cm) => {
                 mix(&mut h, 0);
-                mix(&mut h, *cm as u64);
+                mix(&mut h, cm.fingerprint());
             }
-            ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac } => {
+            ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac, extra_bands, focus_settings } => {
                 mix(&mut h, 1);
-                mix(&mut h, *cm as u64);
+                mix(&mut h, cm.fingerprint());
                 mix(&mut h, band_ff_lo_frac.to_bits());
                 mix(&mut h, band_ff_hi_frac.to_bits());
+                for &(lo, hi) in extra_bands.as_slice() {
+                    mix(&mut h, lo.to_bits());
+                    mix(&mut h, hi.to_bits());
+                }
+                if let Some(fs) = focus_settings {
+                    mix(&mut h, fs.floor_db.to_bits() as u64);
+                    mix(&mut h, fs.range_db.to_bits() as u64);
+                    mix(&mut h, fs.gamma.to_bits() as u64);
+                    mix(&mut h, fs.gain_db.to_bits() as u64);
+                }
             }
         },
-        TileRenderMode::Flow { intensity_gate, flow_gate, opacity, shift_gain, color_gamma, algo, scheme } => {
+        TileRenderMode::Flow { intensity_gate, flow_gate, opacity, shift_gain, color_gamma, algo, scheme, blend, coherence_colormap } => {
             mix(&mut h, 2);
             mix(&mut h, intensity_gate.to_bits() as u64);
             mix(&mut h, flow_gate.to_bits() as u64);
@@ -518,6 +791,8 @@ fn tile_render_fingerprint(
             mix(&mut h, color_gamma.to_bits() as u64);
             mix(&mut h, *algo as u64);
             mix(&mut h, *scheme as u64);
+            mix(&mut h, *blend as u64);
+            mix(&mut h, coherence_colormap.map_or(0, |cm| cm.fingerprint() + 1));
         }
     }
     mix(&mut h, freq_adj_hash);
@@ -577,29 +852,30 @@ fn apply_colormap_to_tile(pixels: &mut [u8], colormap: Colormap) {
     if colormap == Colormap::Greyscale {
         return;
     }
+    let lut = colormap.lut();
     for chunk in pixels.chunks_exact_mut(4) {
-        let [r, g, b] = colormap.apply(chunk[0]);
+        let [r, g, b] = lut[chunk[0] as usize];
         chunk[0] = r;
         chunk[1] = g;
         chunk[2] = b;
     }
 }
 
-/// Apply HFR-focus colormap: color inside focus band, greyscale outside.
+/// Apply HFR-focus colormap: color inside focus band(s), greyscale outside.
 fn apply_hfr_colormap_to_tile(
     pixels: &mut [u8], width: u32, height: u32,
     colormap: Colormap, band_ff_lo_frac: f64, band_ff_hi_frac: f64,
+    extra_bands: &ExtraFocusBands,
 ) {
-    let h = height as f64;
     let w = width as usize;
-    let focus_top = (h * (1.0 - band_ff_hi_frac)).round() as usize;
-    let focus_bot = (h * (1.0 - band_ff_lo_frac)).round() as usize;
+    let focus = FocusRows::new(height as usize, band_ff_lo_frac, band_ff_hi_frac, extra_bands);
+    let lut = colormap.lut();
     for row in 0..height as usize {
-        if row >= focus_top && row < focus_bot {
+        if focus.contains(row) {
             let base = row * w * 4;
             for col in 0..w {
                 let i = base + col * 4;
-                let [r, g, b] = colormap.apply(pixels[i]);
+                let [r, g, b] = lut[pixels[i] as usize];
                 pixels[i] = r;
                 pixels[i + 1] = g;
                 pixels[i + 2] = b;
@@ -624,11 +900,12 @@ fn tile_to_rgba(
     match render_mode {
         TileRenderMode::Spectrogram(colormap) => match colormap {
             ColormapMode::Uniform(cm) => {
+                let lut = cm.lut();
                 for (i, &db) in db_data.iter().enumerate() {
                     let row = if w > 0 { i / w } else { 0 };
                     let extra = freq_adjustments.and_then(|a| a.get(row).copied()).unwrap_or(0.0);
                     let grey = db_to_greyscale(db, settings.floor_db, settings.range_db, settings.gamma, settings.gain_db + extra);
-                    let [r, g, b] = cm.apply(grey);
+                    let [r, g, b] = lut[grey as usize];
                     let pi = i * 4;
                     rgba[pi] = r;
                     rgba[pi + 1] = g;
@@ -636,16 +913,18 @@ fn tile_to_rgba(
                     rgba[pi + 3] = 255;
                 }
             }
-            ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac } => {
-                let h = rendered.height as f64;
-                let focus_top = (h * (1.0 - band_ff_hi_frac)).round() as usize;
-                let focus_bot = (h * (1.0 - band_ff_lo_frac)).round() as usize;
+            ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac, extra_bands, focus_settings } => {
+                let focus = FocusRows::new(rendered.height as usize, *band_ff_lo_frac, *band_ff_hi_frac, extra_bands);
+                let focus_settings = focus_settings.as_ref().unwrap_or(settings);
+                let lut = cm.lut();
                 for (i, &db) in db_data.iter().enumerate() {
                     let row = if w > 0 { i / w } else { 0 };
                     let extra = freq_adjustments.and_then(|a| a.get(row).copied()).unwrap_or(0.0);
-                    let grey = db_to_greyscale(db, settings.floor_db, settings.range_db, settings.gamma, settings.gain_db + extra);
-                    let [r, g, b] = if row >= focus_top && row < focus_bot {
-                        cm.apply(grey)
+                    let in_focus = focus.contains(row);
+                    let s = if in_focus { focus_settings } else { settings };
+                    let grey = db_to_greyscale(db, s.floor_db, s.range_db, s.gamma, s.gain_db + extra);
+                    let [r, g, b] = if in_focus {
+                        lut[grey as usize]
                     } else {
                         [grey, grey, grey]
                     };
@@ -657,8 +936,9 @@ fn tile_to_rgba(
                 }
             }
         },
-        TileRenderMode::Flow { intensity_gate, flow_gate, opacity, shift_gain, color_gamma, algo, scheme } => {
+        TileRenderMode::Flow { intensity_gate, flow_gate, opacity, shift_gain, color_gamma, algo, scheme, blend, coherence_colormap } => {
             let flow_shifts = &rendered.flow_shifts;
+            let coherence_lut = coherence_colormap.map(Colormap::lut);
             for (i, &db) in db_data.iter().enumerate() {
                 let row = if w > 0 { i / w } else { 0 };
                 let extra = freq_adjustments.and_then(|a| a.get(row).copied()).unwrap_or(0.0);
@@ -669,8 +949,11 @@ fn tile_to_rgba(
                 let shift = if i < flow_shifts.len() { flow_shifts[i] } else { 0.0 };
                 let [r, g, b] = match algo {
                     FlowAlgo::Phase => phase_rgb(grey, shift, *intensity_gate),
-                    FlowAlgo::PhaseCoherence => coherence_rgb(grey, shift, *intensity_gate, *flow_gate, *opacity, *shift_gain, *color_gamma),
-                    _ => flow_rgb_scheme(grey, shift, *intensity_gate, *flow_gate, *opacity, *shift_gain, *color_gamma, *scheme),
+                    FlowAlgo::PhaseCoherence => match &coherence_lut {
+                        Some(lut) => coherence_lut_rgb(grey, shift, *intensity_gate, *opacity, *shift_gain, *color_gamma, *blend, |i| lut[i as usize]),
+                        None => coherence_rgb(grey, shift, *intensity_gate, *flow_gate, *opacity, *shift_gain, *color_gamma, *blend),
+                    },
+                    _ => flow_rgb_scheme(grey, shift, *intensity_gate, *flow_gate, *opacity, *shift_gain, *color_gamma, *scheme, *blend),
                 };
                 let pi = i * 4;
                 rgba[pi] = r;
@@ -684,8 +967,9 @@ fn tile_to_rgba(
     rgba
 }
 
-/// Composite spectrogram or flow tiles from the tile cache onto the canvas.
-/// Falls back to a preview image for tiles not yet cached.
+/// Composite spectrogram or flow tiles from the tile cache onto the canvas,
+/// preferring tiles at `lod` and falling back to coarser levels, then to a
+/// preview image for tiles not yet cached.
 /// Returns true if at least one tile was drawn, false if nothing was available.
 pub fn blit_tiles_viewport(
     ctx: &CanvasRenderingContext2d,
@@ -695,6 +979,7 @@ pub fn blit_tiles_viewport(
     total_cols: usize,
     scroll_col: f64,
     zoom: f64,
+    lod: u8,
     freq_crop_lo: f64,
     freq_crop_hi: f64,
     render_mode: TileRenderMode,
@@ -717,7 +1002,7 @@ pub fn blit_tiles_viewport(
         TileRenderMode::Flow { .. } => ColormapMode::Uniform(Colormap::Greyscale),
     };
 
-    let Some(vg) = ViewportGeometry::new(cw, ch, total_cols, scroll_col, zoom, freq_crop_lo, freq_crop_hi)
+    let Some(vg) = ViewportGeometry::new(cw, ch, total_cols, scroll_col, zoom, lod, freq_crop_lo, freq_crop_hi)
     else {
         if let Some(pv) = preview {
             blit_preview_as_background(
@@ -726,8 +1011,7 @@ pub fn blit_tiles_viewport(
                 freq_crop_lo, freq_crop_hi, preview_colormap,
             );
         } else {
-            ctx.set_fill_style_str("#000");
-            ctx.fill_rect(0.0, 0.0, cw, ch);
+            clear_background(ctx, cw, ch);
         }
         return preview.is_some();
     };
@@ -777,12 +1061,10 @@ pub fn blit_tiles_viewport(
                 freq_crop_lo, freq_crop_hi, preview_colormap,
             );
         } else {
-            ctx.set_fill_style_str("#000");
-            ctx.fill_rect(0.0, 0.0, cw, ch);
+            clear_background(ctx, cw, ch);
         }
     } else {
-        ctx.set_fill_style_str("#000");
-        ctx.fill_rect(0.0, 0.0, cw, ch);
+        clear_background(ctx, cw, ch);
     }
 
     // Compute fingerprint for tile canvas cache invalidation.
@@ -817,10 +1099,10 @@ pub fn blit_tiles_viewport(
                 if let TileRenderMode::Spectrogram(colormap) = &render_mode {
                     match colormap {
                         ColormapMode::Uniform(cm) => apply_colormap_to_tile(&mut px, *cm),
-                        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac } => {
+                        ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac, extra_bands, .. } => {
                             apply_hfr_colormap_to_tile(
                                 &mut px, tile.rendered.width, tile.rendered.height,
-                                *cm, *band_ff_lo_frac, *band_ff_hi_frac,
+                                *cm, *band_ff_lo_frac, *band_ff_hi_frac, extra_bands,
                             );
                         }
                     }
@@ -997,8 +1279,7 @@ pub fn blit_chromagram_tiles_viewport(
     let cw = canvas.width() as f64;
     let ch = canvas.height() as f64;
 
-    ctx.set_fill_style_str("#000");
-    ctx.fill_rect(0.0, 0.0, cw, ch);
+    clear_background(ctx, cw, ch);
 
     if total_cols == 0 || zoom <= 0.0 {
         return false;
@@ -19,14 +19,15 @@ pub struct ViewportGeometry {
 }
 
 impl ViewportGeometry {
-    /// Compute viewport geometry from scroll/zoom/canvas state.
-    /// Returns None if nothing is visible.
+    /// Compute viewport geometry from scroll/zoom/canvas state, drawing at
+    /// `ideal_lod`. Returns None if nothing is visible.
     pub fn new(
         cw: f64,
         ch: f64,
         total_cols: usize,
         scroll_col: f64,
         zoom: f64,
+        ideal_lod: u8,
         freq_crop_lo: f64,
         freq_crop_hi: f64,
     ) -> Option<Self> {
@@ -34,7 +35,6 @@ impl ViewportGeometry {
             return None;
         }
 
-        let ideal_lod = tile_cache::select_lod(zoom);
         let ratio = tile_cache::lod_ratio(ideal_lod);
 
         let vis_start = scroll_col;
@@ -17,13 +17,20 @@
 //! Levels 0–6 step 4× apart; LOD 7 is 2× finer than LOD 6 (since hop must be
 //! an integer ≥ 1). The renderer picks the ideal LOD
 //! for the current zoom and falls back to coarser LODs when tiles aren't cached.
+//! LOD selection has hysteresis: the level in use is kept until the zoom is
+//! well past its threshold, so a zoom hovering at a boundary doesn't make
+//! the view pop back and forth between coarse and fine tiles.
 //! FFT size is adaptive per LOD via `FftMode::fft_for_lod()`.
 //!
-//! Four independent caches with separate LRU eviction budgets:
+//! Independent caches with separate LRU eviction budgets:
 //! - Magnitude (512 MB) — standard STFT spectrogram tiles
 //! - Flow (120 MB) — optical-flow tiles
 //! - Reassignment (120 MB) — reassigned spectrogram tiles (LOD 2+ only)
 //! - Chromagram (64 MB) — chromagram tiles
+//! - Resonator (256 MB) — resonator-bank tiles
+//!
+//! The budgets above are the defaults; `set_budget_scale` scales them all
+//! together (user "Tile memory" setting) and evicts immediately if over.
 //!
 //! Tiles are computed asynchronously via `spawn_local` with `setTimeout(0)`
 //! yielding to keep the UI responsive. Concurrency is capped at
@@ -38,7 +45,7 @@ use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
 use crate::canvas::spectrogram_renderer::{self, PreRendered, FlowAlgo};
-use crate::state::{AppState, LoadedFile, PlaybackMode};
+use crate::state::{AppState, LoadedFile, PlaybackMode, TileScheduling};
 use crate::audio::streaming_playback::PV_HQ_OVERLAP;
 use crate::audio::streaming_source;
 use crate::viewport;
@@ -59,6 +66,10 @@ const CHROMA_MAX_BYTES: usize = 64 * 1024 * 1024;
 /// tiles (dB per pixel) so this sizing mirrors the magnitude cache.
 const RESONATOR_MAX_BYTES: usize = 256 * 1024 * 1024;
 
+/// Sum of all default cache budgets (the "Standard" memory setting).
+pub const DEFAULT_TOTAL_MAX_BYTES: usize =
+    MAGNITUDE_MAX_BYTES + FLOW_MAX_BYTES + REASSIGN_MAX_BYTES + CHROMA_MAX_BYTES + RESONATOR_MAX_BYTES;
+
 /// Maximum time (ms) a tile can be in-flight before being considered stuck.
 const IN_FLIGHT_TIMEOUT_MS: f64 = 10_000.0;
 
@@ -67,6 +78,14 @@ const IN_FLIGHT_TIMEOUT_MS: f64 = 10_000.0;
 /// tiles are scheduled simultaneously (e.g. large files).
 const MAX_CONCURRENT_SPAWNS: usize = 8;
 
+/// Spawn slots background preload leaves free for visible tiles under
+/// `TileScheduling::VisibleFirst`.
+const VISIBLE_RESERVED_SPAWNS: usize = 2;
+
+/// Longest background preload waits on the visible tiles before going ahead
+/// anyway, in case a visible tile never lands.
+const VISIBLE_WAIT_MAX_MS: f64 = 2_000.0;
+
 fn visible_window_for_file(state: &AppState, file_idx: usize) -> Option<(f64, f64)> {
     let files = state.files.get_untracked();
     let file = files.get(file_idx)?;
@@ -168,57 +187,37 @@ fn is_current_file(state: &AppState, file_idx: usize) -> bool {
 
 // ── LOD configuration ────────────────────────────────────────────────────────
 
-pub struct LodConfig {
-    pub fft_size: usize,
-    pub hop_size: usize,
-}
-
-pub const NUM_LODS: usize = 8;
-
-/// The LOD level used as the spatial coordinate baseline (hop=512).
-/// All scroll positions, total_cols, etc. are expressed in this LOD's column space.
-pub const LOD_BASELINE: u8 = 2;
+pub use crate::canvas::lod::{
+    ideal_lod, lod_ratio, select_lod, LodConfig, BASELINE_HOP, LOD_BASELINE, LOD_CONFIGS, NUM_LODS,
+};
 
-/// Hop size of the baseline LOD — used for coordinate-space calculations.
-pub const BASELINE_HOP: usize = 512;
-
-pub const LOD_CONFIGS: [LodConfig; NUM_LODS] = [
-    LodConfig { fft_size: 256, hop_size: 8192 }, // LOD 0 — ultra-wide overview (quality downscaled)
-    LodConfig { fft_size: 256, hop_size: 2048 }, // LOD 1 — wide overview (quality downscaled)
-    LodConfig { fft_size: 256, hop_size: 512 },  // LOD 2 — normal resolution (baseline)
-    LodConfig { fft_size: 256, hop_size: 128 },  // LOD 3 — zoomed in
-    LodConfig { fft_size: 256, hop_size: 32 },   // LOD 4 — deep zoom
-    LodConfig { fft_size: 256, hop_size: 8 },    // LOD 5 — extreme zoom
-    LodConfig { fft_size: 256, hop_size: 2 },    // LOD 6 — sample-level zoom
-    LodConfig { fft_size: 128, hop_size: 1 },    // LOD 7 — per-sample zoom (finest possible)
-];
+thread_local! {
+    /// LOD the main spectrogram view last showed, for [`main_view_lod`].
+    static MAIN_VIEW_LOD: std::cell::Cell<Option<u8>> = const { std::cell::Cell::new(None) };
+}
 
-/// Select the ideal LOD level for the current zoom.
-/// `zoom` is pixels per baseline (LOD2) column.
-pub fn select_lod(zoom: f64) -> u8 {
-    // LOD 7 is only 2× finer than LOD 6 (hop 1 vs 2), so its threshold is 2×.
-    if zoom >= 256.0 { 7 }
-    else if zoom >= 128.0 { 6 }
-    else if zoom >= 32.0 { 5 }
-    else if zoom >= 8.0 { 4 }
-    else if zoom >= 2.0 { 3 }
-    else if zoom >= 0.5 { 2 }
-    else if zoom >= 0.125 { 1 }
-    else { 0 }
+/// Highest useful zoom for a window of at most `max_px` pixels (see
+/// [`crate::canvas::lod::window_zoom_limit`]), within the viewport's zoom range.
+pub fn window_zoom_limit(max_px: f64, fft_for_lod: impl Fn(u8) -> usize) -> f64 {
+    crate::canvas::lod::window_zoom_limit(max_px, fft_for_lod).clamp(viewport::MIN_ZOOM, viewport::MAX_ZOOM)
 }
 
-/// Ratio of baseline (LOD2) columns to LOD_L columns (how many LOD_L cols per baseline col).
-/// LOD0: 0.0625, LOD1: 0.25, LOD2: 1.0, LOD3: 4.0, LOD4: 16.0, LOD5: 64.0, LOD6: 256.0, LOD7: 512.0
-pub fn lod_ratio(lod: u8) -> f64 {
-    BASELINE_HOP as f64 / LOD_CONFIGS[lod as usize].hop_size as f64
+/// LOD for the main spectrogram view at `zoom`, with hysteresis against the
+/// level it showed last. The main view's renderer, scheduler and readouts
+/// all call this with the same zoom, so they agree on the level. Other
+/// views (timeline segments, exports) use [`ideal_lod`].
+pub fn main_view_lod(zoom: f64) -> u8 {
+    MAIN_VIEW_LOD.with(|last| {
+        let lod = select_lod(zoom, last.get());
+        last.set(Some(lod));
+        lod
+    })
 }
 
 /// Tile count at a given LOD for a file with `total_samples` audio samples.
 pub fn tile_count_for_samples(total_samples: usize, lod: u8) -> usize {
     let config = &LOD_CONFIGS[lod as usize];
-    if total_samples < config.fft_size { return 0; }
-    let total_cols = (total_samples - config.fft_size) / config.hop_size + 1;
-    total_cols.div_ceil(TILE_COLS)
+    crate::dsp::fft::stft_frame_count(total_samples, config.fft_size, config.hop_size).div_ceil(TILE_COLS)
 }
 
 
@@ -398,6 +397,11 @@ impl TileCache {
         self.lru.clear();
         self.total_bytes = 0;
     }
+
+    fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+        self.evict_to_fit(0);
+    }
 }
 
 // ── Flow cache (multi-LOD, same CacheKey as magnitude tiles) ─────────────────
@@ -476,6 +480,11 @@ impl ChromaTileCache {
         self.tiles.get(&(file_idx, tile_idx))
     }
 
+    fn set_max_bytes(&mut self, max_bytes: usize) {
+        self.max_bytes = max_bytes;
+        self.evict_to_fit(0);
+    }
+
     fn touch(&mut self, key: ChromaKey) {
         let stamp = self.allocate_stamp();
         if let Some(tile) = self.tiles.get_mut(&key) {
@@ -532,13 +541,18 @@ thread_local! {
 /// Returns true if the total number of active in-flight tasks across all
 /// categories has reached `MAX_CONCURRENT_SPAWNS`.
 fn at_spawn_limit() -> bool {
+    in_flight_count() >= MAX_CONCURRENT_SPAWNS
+}
+
+/// Active in-flight tile tasks across all categories.
+fn in_flight_count() -> usize {
     let now = js_sys::Date::now();
     let mag = IN_FLIGHT.with(|s| s.borrow().values().filter(|&&ts| now - ts <= IN_FLIGHT_TIMEOUT_MS).count());
     let flow = FLOW_IN_FLIGHT.with(|s| s.borrow().values().filter(|&&ts| now - ts <= IN_FLIGHT_TIMEOUT_MS).count());
     let reassign = REASSIGN_IN_FLIGHT.with(|s| s.borrow().values().filter(|&&ts| now - ts <= IN_FLIGHT_TIMEOUT_MS).count());
     let chroma = CHROMA_IN_FLIGHT.with(|s| s.borrow().values().filter(|&&ts| now - ts <= IN_FLIGHT_TIMEOUT_MS).count());
     let reson = RESONATOR_IN_FLIGHT.with(|s| s.borrow().values().filter(|&&ts| now - ts <= IN_FLIGHT_TIMEOUT_MS).count());
-    mag + flow + reassign + chroma + reson >= MAX_CONCURRENT_SPAWNS
+    mag + flow + reassign + chroma + reson
 }
 
 fn has_active_in_flight<K: Eq + std::hash::Hash>(map: &mut HashMap<K, f64>, key: &K) -> bool {
@@ -670,6 +684,47 @@ pub fn cache_usage() -> (usize, usize) {
     })
 }
 
+/// Scale every tile cache budget by `scale` relative to its default, evicting
+/// least-recently-used tiles straight away if a cache is now over budget.
+pub fn set_budget_scale(scale: f64) {
+    let scaled = |bytes: usize| (bytes as f64 * scale.max(0.05)) as usize;
+    CACHE.with(|c| c.borrow_mut().set_max_bytes(scaled(MAGNITUDE_MAX_BYTES)));
+    FLOW_CACHE.with(|c| c.borrow_mut().set_max_bytes(scaled(FLOW_MAX_BYTES)));
+    REASSIGN_CACHE.with(|c| c.borrow_mut().set_max_bytes(scaled(REASSIGN_MAX_BYTES)));
+    CHROMA_CACHE.with(|c| c.borrow_mut().set_max_bytes(scaled(CHROMA_MAX_BYTES)));
+    RESONATOR_CACHE.with(|c| c.borrow_mut().set_max_bytes(scaled(RESONATOR_MAX_BYTES)));
+}
+
+/// Per-cache memory usage as (name, used_bytes, max_bytes), for diagnostics.
+pub fn memory_usage_by_cache() -> Vec<(&'static str, usize, usize)> {
+    fn usage(c: &RefCell<TileCache>) -> (usize, usize) {
+        let cache = c.borrow();
+        (cache.total_bytes, cache.max_bytes)
+    }
+    let (mag_used, mag_max) = CACHE.with(usage);
+    let (flow_used, flow_max) = FLOW_CACHE.with(usage);
+    let (reassign_used, reassign_max) = REASSIGN_CACHE.with(usage);
+    let (reson_used, reson_max) = RESONATOR_CACHE.with(usage);
+    let (chroma_used, chroma_max) = CHROMA_CACHE.with(|c| {
+        let cache = c.borrow();
+        (cache.total_bytes, cache.max_bytes)
+    });
+    vec![
+        ("Magnitude", mag_used, mag_max),
+        ("Flow", flow_used, flow_max),
+        ("Reassignment", reassign_used, reassign_max),
+        ("Chromagram", chroma_used, chroma_max),
+        ("Resonator", reson_used, reson_max),
+    ]
+}
+
+/// Total (used_bytes, max_bytes) across all tile caches.
+pub fn total_memory_usage() -> (usize, usize) {
+    memory_usage_by_cache()
+        .iter()
+        .fold((0, 0), |(u, m), &(_, used, max)| (u + used, m + max))
+}
+
 #[derive(Clone, Copy)]
 pub struct TileDebugStats {
     pub visible_cached: usize,
@@ -1276,7 +1331,7 @@ pub fn schedule_prefetch_tiles(
     reassign: bool,
     max_prefetch: usize,
 ) {
-    let lod = select_lod(zoom);
+    let lod = main_view_lod(zoom);
     let hop = LOD_CONFIGS[lod as usize].hop_size;
     let max_tiles = tile_count_for_samples(total_samples, lod);
     if max_tiles == 0 { return; }
@@ -1447,6 +1502,33 @@ pub fn borrow_flow_tile<R>(file_idx: usize, lod: u8, tile_idx: usize, f: impl Fn
     })
 }
 
+/// Value stored in the cached flow tile at (`time`, `freq`): the cell's dB
+/// and its shift (for phase coherence, the phase deviation in [-1, 1]).
+/// Looks at the LOD drawn for `zoom` first, then the baseline fallback.
+pub fn flow_value_at(file_idx: usize, zoom: f64, sample_rate: u32, time: f64, freq: f64) -> Option<(f32, f32)> {
+    if sample_rate == 0 || time < 0.0 || freq < 0.0 {
+        return None;
+    }
+    let baseline_col = time * sample_rate as f64 / BASELINE_HOP as f64;
+    let nyquist = sample_rate as f64 / 2.0;
+    let lookup = |lod: u8| {
+        let col = (baseline_col * lod_ratio(lod)) as usize;
+        borrow_flow_tile(file_idx, lod, col / TILE_COLS, |tile| {
+            let r = &tile.rendered;
+            let (w, h) = (r.width as usize, r.height as usize);
+            let c = col % TILE_COLS;
+            if c >= w || h == 0 || r.flow_shifts.len() < w * h {
+                return None;
+            }
+            let bin = ((freq / nyquist) * (h - 1) as f64).round() as usize;
+            let idx = (h - 1 - bin.min(h - 1)) * w + c;
+            Some((r.db_data[idx], r.flow_shifts[idx]))
+        })
+        .flatten()
+    };
+    lookup(main_view_lod(zoom)).or_else(|| lookup(LOD_BASELINE))
+}
+
 pub fn clear_flow_cache() {
     FLOW_CACHE.with(|c| c.borrow_mut().clear_all());
     FLOW_IN_FLIGHT.with(|s| s.borrow_mut().clear());
@@ -1490,6 +1572,10 @@ pub fn schedule_flow_tile(
 
     let config_hop = LOD_CONFIGS[lod as usize].hop_size;
     let actual_fft = state.spect_fft_mode.get_untracked().fft_for_lod(lod);
+    let shift_params = spectrogram_renderer::FlowShiftParams::new(
+        state.flow_radius.get_untracked(),
+        state.flow_max_displacement.get_untracked(),
+    );
 
     spawn_local(async move {
         yield_to_browser().await;
@@ -1607,7 +1693,7 @@ pub fn schedule_flow_tile(
                 }
 
                 spectrogram_renderer::pre_render_flow_columns(
-                    &cols, prev_col.as_deref(), algo,
+                    &cols, prev_col.as_deref(), algo, shift_params,
                 )
             }
         };
@@ -1931,6 +2017,11 @@ struct BgPreloadState {
     next_distance: usize,
     generation: u32,
     batch_timer: Option<i32>,
+    /// When batches started waiting on the visible tiles.
+    waiting_since: Option<f64>,
+    /// Second pass (visible first only): baseline tiles computed for the
+    /// spectral store.
+    fills_store: bool,
 }
 
 thread_local! {
@@ -1940,6 +2031,10 @@ thread_local! {
 /// Start (or restart) background preloading of tiles at the given LOD,
 /// expanding outward from `center_tile`. Tiles are scheduled in small batches
 /// with 50ms delays to avoid blocking the UI. Stops at 90% cache capacity.
+/// With `TileScheduling::VisibleFirst`, batches wait (up to
+/// `VISIBLE_WAIT_MAX_MS`) while the main view reports visible tiles still
+/// missing, and once the LOD is covered a file with a spectral store goes on
+/// to the baseline LOD, whose tiles fill the store for full-file analysis.
 /// The `generation` counter is checked each step to cancel stale jobs.
 pub fn start_background_preload(
     state: AppState,
@@ -1965,6 +2060,8 @@ pub fn start_background_preload(
             next_distance: 0,
             generation,
             batch_timer: None,
+            waiting_since: None,
+            fills_store: false,
         });
     });
 
@@ -2007,10 +2104,44 @@ fn schedule_preload_batch(state: AppState, generation: u32) {
 }
 
 fn run_preload_batch(state: AppState, generation: u32) {
+    use crate::canvas::spectral_store;
+
     // Check generation (cancel if stale)
     let current_gen = state.bg_preload_gen.get_untracked();
     if current_gen != generation { return; }
 
+    // Visible first: wait while on-screen tiles still need workers, and
+    // never take the slots reserved for them. `tile_progress` is what the
+    // main view reports for the tile kind it actually draws, so views that
+    // don't report (flow, resonators, loading) never hold preload back.
+    let visible_first = state.tile_scheduling.get_untracked() == TileScheduling::VisibleFirst;
+    if visible_first {
+        let now = js_sys::Date::now();
+        let visible_pending = state.tile_progress.get_untracked().is_some();
+        let wait = BG_PRELOAD.with(|bg| {
+            let mut bg = bg.borrow_mut();
+            let s = bg.as_mut().filter(|s| s.generation == generation)?;
+            let since = if visible_pending { *s.waiting_since.get_or_insert(now) } else { now };
+            if !visible_pending {
+                s.waiting_since = None;
+            }
+            Some(
+                in_flight_count() + VISIBLE_RESERVED_SPAWNS >= MAX_CONCURRENT_SPAWNS
+                    || (visible_pending && now - since < VISIBLE_WAIT_MAX_MS),
+            )
+        });
+        let Some(wait) = wait else { return };
+        if wait {
+            schedule_preload_batch(state, generation);
+            return;
+        }
+    }
+
+    let batch_size = if visible_first {
+        (MAX_CONCURRENT_SPAWNS - VISIBLE_RESERVED_SPAWNS).saturating_sub(in_flight_count()).min(4)
+    } else {
+        4
+    };
     let batch = BG_PRELOAD.with(|bg| {
         let mut bg = bg.borrow_mut();
         let s = match bg.as_mut() {
@@ -2026,11 +2157,24 @@ fn run_preload_batch(state: AppState, generation: u32) {
         if cache_full { return None; }
 
         let mut tiles_to_schedule = Vec::new();
-        let batch_size = 4;
 
         while tiles_to_schedule.len() < batch_size {
             if s.next_distance > s.max_tiles {
-                return None; // all distances covered
+                // All distances covered. Visible first goes on to fill the
+                // spectral store, which only baseline tiles write to.
+                if s.fills_store || !visible_first || !spectral_store::has_store(s.file_idx) {
+                    return None;
+                }
+                let total_samples = state.files.with_untracked(|files| {
+                    files.get(s.file_idx).map(|f| f.audio.source.total_samples() as usize).unwrap_or(0)
+                });
+                let center = s.center_tile as f64 * lod_ratio(LOD_BASELINE) / lod_ratio(s.lod);
+                s.lod = LOD_BASELINE;
+                s.max_tiles = tile_count_for_samples(total_samples, LOD_BASELINE);
+                s.center_tile = center as usize;
+                s.next_distance = 0;
+                s.fills_store = true;
+                continue;
             }
 
             let dist = s.next_distance;
@@ -2051,25 +2195,33 @@ fn run_preload_batch(state: AppState, generation: u32) {
             };
 
             for t in candidates {
-                let key = (s.file_idx, s.lod, t);
-                if CACHE.with(|c| c.borrow().tiles.contains_key(&key)) {
-                    continue; // already cached
+                let done = if s.fills_store {
+                    spectral_store::tile_complete(s.file_idx, t * TILE_COLS, (t + 1) * TILE_COLS)
+                } else {
+                    CACHE.with(|c| c.borrow().tiles.contains_key(&(s.file_idx, s.lod, t)))
+                };
+                if done {
+                    continue; // already cached or stored
                 }
                 tiles_to_schedule.push((s.file_idx, s.lod, t));
             }
         }
 
-        Some(tiles_to_schedule)
+        Some((tiles_to_schedule, s.fills_store))
     });
 
-    if let Some(tiles) = batch {
+    if let Some((tiles, fills_store)) = batch {
         if tiles.is_empty() {
             // All tiles in this batch were already cached; keep going
             schedule_preload_batch(state, generation);
             return;
         }
         for &(fi, lod, ti) in &tiles {
-            schedule_tile_lod(state, fi, lod, ti);
+            if fills_store {
+                schedule_tile_on_demand(state, fi, ti);
+            } else {
+                schedule_tile_lod(state, fi, lod, ti);
+            }
         }
         // Schedule next batch
         schedule_preload_batch(state, generation);
@@ -2112,7 +2264,8 @@ fn apply_display_transform(samples: &[f32], sample_rate: u32, state: AppState) -
         PlaybackMode::Heterodyne => {
             let lo = state.het_frequency.get_untracked();
             let cutoff = state.het_cutoff.get_untracked();
-            crate::dsp::heterodyne::heterodyne_mix(samples, sample_rate, lo, cutoff)
+            let order = state.het_lp_order.get_untracked();
+            crate::dsp::heterodyne::heterodyne_mix(samples, sample_rate, lo, cutoff, order)
         }
         PlaybackMode::PitchShift => {
             let factor = state.ps_factor.get_untracked();
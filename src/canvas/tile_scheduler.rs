@@ -37,27 +37,29 @@ fn visible_tile_order(first_tile: usize, last_tile: usize, center_tile: usize) -
 /// Schedule missing normal/reassignment tiles for the visible viewport.
 ///
 /// Called from the render Effect after blitting, to ensure tiles are being
-/// computed for the current viewport.
+/// computed for the current viewport. `ideal_lod` is the level the view
+/// renders at. Returns `(ready, total)` counts of the visible tiles at that
+/// LOD, for the render progress indicator.
 pub fn schedule_normal_tiles(
     state: AppState,
     file_idx: usize,
     total_cols: usize,
     scroll_col: f64,
     zoom: f64,
+    ideal_lod: u8,
     display_w: f64,
     time_res: f64,
     is_playing: bool,
     reassign_on: bool,
     disposed: &Arc<AtomicBool>,
-) {
-    let ideal_lod = tile_cache::select_lod(zoom);
+) -> (usize, usize) {
     let ratio = tile_cache::lod_ratio(ideal_lod);
 
     // Clamp vis_start to valid range (must match renderer's clamping)
     let vis_start = scroll_col.max(0.0).min((total_cols as f64 - 1.0).max(0.0));
     let vis_end = (vis_start + display_w / zoom).min(total_cols as f64);
 
-    if vis_end <= vis_start { return; }
+    if vis_end <= vis_start { return (0, 0); }
 
     // Tile range at ideal LOD
     let vis_start_lod = vis_start * ratio;
@@ -146,10 +148,15 @@ pub fn schedule_normal_tiles(
         }
     }
 
+    let ready = (first_tile..=last_tile)
+        .filter(|&t| tile_cache::get_tile(file_idx, ideal_lod, t).is_some())
+        .count();
+    let progress = (ready, visible_tile_count);
+
     // Recovery: if visible tiles are missing, force a retry after 250ms.
     // Skip the expensive count_missing_visible() call when the scheduling
     // loops above found no missing tiles (common case during panning).
-    if !any_missing { return; }
+    if !any_missing { return progress; }
     let missing = tile_cache::count_missing_visible(file_idx, ideal_lod, first_tile, last_tile);
     if missing > 0 {
         let state_recovery = state;
@@ -164,6 +171,7 @@ pub fn schedule_normal_tiles(
             );
         recovery_cb.forget();
     }
+    progress
 }
 
 /// Schedule missing flow tiles for the visible viewport.
@@ -173,10 +181,10 @@ pub fn schedule_flow_tiles(
     total_cols: usize,
     scroll_col: f64,
     zoom: f64,
+    ideal_lod: u8,
     display_w: f64,
     algo: FlowAlgo,
 ) {
-    let ideal_lod = tile_cache::select_lod(zoom);
     let ratio = tile_cache::lod_ratio(ideal_lod);
 
     let vis_start = scroll_col.max(0.0).min((total_cols as f64 - 1.0).max(0.0));
@@ -215,9 +223,9 @@ pub fn schedule_resonator_tiles(
     total_cols: usize,
     scroll_col: f64,
     zoom: f64,
+    ideal_lod: u8,
     display_w: f64,
 ) {
-    let ideal_lod = tile_cache::select_lod(zoom);
     let ratio = tile_cache::lod_ratio(ideal_lod);
 
     let vis_start = scroll_col.max(0.0).min((total_cols as f64 - 1.0).max(0.0));
@@ -251,6 +259,14 @@ pub fn setup_cache_clearing_effects(state: AppState) {
         crate::canvas::tile_cache::clear_flow_cache();
     });
 
+    // Recompute flow tiles when the shift windows change
+    Effect::new(move || {
+        let _radius = state.flow_radius.get();
+        let _max_disp = state.flow_max_displacement.get();
+        crate::canvas::tile_cache::clear_flow_cache();
+        state.tile_ready_signal.update(|n| *n = n.wrapping_add(1));
+    });
+
     // Clear all tiles when FFT mode changes
     Effect::new(move || {
         let _fft = state.spect_fft_mode.get();
@@ -269,6 +285,7 @@ pub fn setup_cache_clearing_effects(state: AppState) {
             let _mode = state.playback_mode.get();
             let _het = state.het_frequency.get();
             let _het_cut = state.het_cutoff.get();
+            let _het_order = state.het_lp_order.get();
             let _te = state.te_factor.get();
             let _ps = state.ps_factor.get();
             let _pv = state.pv_factor.get();
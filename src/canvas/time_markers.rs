@@ -1,3 +1,4 @@
+use crate::canvas::overlays::{label_backdrop, line_alpha, text_alpha};
 use crate::format_time;
 use web_sys::CanvasRenderingContext2d;
 
@@ -14,6 +15,20 @@ const TICK_INTERVALS: &[f64] = &[
     120.0, 300.0, 600.0,            // 2–10 min
 ];
 
+/// Default minimum spacing (px) between labelled time ticks.
+pub const DEFAULT_TICK_SPACING_PX: f64 = 100.0;
+
+/// The smallest nice interval (seconds) that keeps labelled ticks at least
+/// `min_spacing_px` apart at `px_per_sec`.
+pub fn tick_interval(px_per_sec: f64, min_spacing_px: f64) -> f64 {
+    let min_interval = min_spacing_px / px_per_sec;
+    TICK_INTERVALS
+        .iter()
+        .copied()
+        .find(|&i| i >= min_interval)
+        .unwrap_or(*TICK_INTERVALS.last().unwrap())
+}
+
 /// Configuration for clock-time display on the timeline.
 #[derive(Clone, Copy, Debug)]
 pub struct ClockTimeConfig {
@@ -103,6 +118,9 @@ fn is_key_tick(t: f64, key_interval: f64) -> bool {
 /// file (>10 s), a key-marker system kicks in: "round" boundaries (e.g. every
 /// second) get prominent absolute labels while intermediate ticks show compact
 /// relative offsets like "+50ms".
+///
+/// `tick_spacing_px` is the minimum gap between labelled ticks; smaller
+/// values give denser ticks.
 pub fn draw_time_markers(
     ctx: &CanvasRenderingContext2d,
     scroll_offset: f64,
@@ -113,6 +131,7 @@ pub fn draw_time_markers(
     clock: Option<ClockTimeConfig>,
     show_clock_time: bool,
     time_scale: f64,
+    tick_spacing_px: f64,
 ) {
     if visible_time <= 0.0 || canvas_width <= 0.0 {
         return;
@@ -125,13 +144,7 @@ pub fn draw_time_markers(
 
     let px_per_sec = canvas_width / scaled_visible;
 
-    // Pick the smallest nice interval that keeps labels ≥100 px apart
-    let min_interval = 100.0 / px_per_sec;
-    let interval = TICK_INTERVALS
-        .iter()
-        .copied()
-        .find(|&i| i >= min_interval)
-        .unwrap_or(*TICK_INTERVALS.last().unwrap());
+    let interval = tick_interval(px_per_sec, tick_spacing_px);
 
     let end_time = (scaled_scroll + scaled_visible).min(scaled_duration);
 
@@ -151,7 +164,7 @@ pub fn draw_time_markers(
     let minor_px = minor_interval * px_per_sec;
     if minor_px >= 4.0 {
         let first_minor = (scaled_scroll / minor_interval).ceil() * minor_interval;
-        ctx.set_stroke_style_str("rgba(255,255,255,0.15)");
+        ctx.set_stroke_style_str(&format!("rgba(255,255,255,{:.2})", line_alpha(0.15)));
         ctx.set_line_width(1.0);
         let mut t = first_minor;
         while t <= end_time + minor_interval * 0.5 {
@@ -187,8 +200,8 @@ pub fn draw_time_markers(
             let current_tick_h = if use_relative && is_key { key_tick_h } else { tick_h };
 
             // Bottom tick
-            let tick_alpha = if is_key { "0.5" } else { "0.3" };
-            ctx.set_stroke_style_str(&format!("rgba(255,255,255,{})", tick_alpha));
+            let tick_alpha = line_alpha(if is_key { 0.5 } else { 0.3 });
+            ctx.set_stroke_style_str(&format!("rgba(255,255,255,{:.2})", tick_alpha));
             ctx.set_line_width(1.0);
             ctx.begin_path();
             ctx.move_to(x, canvas_height - current_tick_h);
@@ -196,7 +209,7 @@ pub fn draw_time_markers(
             ctx.stroke();
 
             // Subtle top tick
-            ctx.set_stroke_style_str("rgba(255,255,255,0.10)");
+            ctx.set_stroke_style_str(&format!("rgba(255,255,255,{:.2})", line_alpha(0.10)));
             ctx.begin_path();
             ctx.move_to(x, 0.0);
             ctx.line_to(x, 4.0);
@@ -230,7 +243,7 @@ pub fn draw_time_markers(
                 let lx = x + 3.0;
                 if lx + tw < canvas_width - 2.0 {
                     // Dark background for readability
-                    ctx.set_fill_style_str("rgba(0,0,0,0.6)");
+                    ctx.set_fill_style_str(&label_backdrop(0.6));
                     ctx.fill_rect(
                         lx - 1.0,
                         canvas_height - current_tick_h - 12.0,
@@ -238,8 +251,8 @@ pub fn draw_time_markers(
                         12.0,
                     );
                     // Text with emphasis for key markers
-                    let text_alpha = if use_relative && is_key { "0.9" } else { "0.7" };
-                    ctx.set_fill_style_str(&format!("rgba(255,255,255,{})", text_alpha));
+                    let alpha = text_alpha(if use_relative && is_key { 0.9 } else { 0.7 });
+                    ctx.set_fill_style_str(&format!("rgba(255,255,255,{:.2})", alpha));
                     let _ = ctx.fill_text(&label, lx, canvas_height - current_tick_h - 1.0);
                 }
             }
@@ -259,19 +272,19 @@ pub fn draw_time_markers(
                 let tw = metrics.width();
                 let lx = 3.0;
                 if lx + tw < canvas_width - 2.0 {
-                    ctx.set_fill_style_str("rgba(0,0,0,0.75)");
+                    ctx.set_fill_style_str(&label_backdrop(0.75));
                     ctx.fill_rect(
                         lx - 1.0,
                         canvas_height - key_tick_h - 12.0,
                         tw + 2.0,
                         12.0,
                     );
-                    ctx.set_fill_style_str("rgba(255,255,255,0.9)");
+                    ctx.set_fill_style_str(&format!("rgba(255,255,255,{:.2})", text_alpha(0.9)));
                     let _ = ctx.fill_text(&label, lx, canvas_height - key_tick_h - 1.0);
                 }
             }
             // Draw the key tick line at left edge
-            ctx.set_stroke_style_str("rgba(255,255,255,0.5)");
+            ctx.set_stroke_style_str(&format!("rgba(255,255,255,{:.2})", line_alpha(0.5)));
             ctx.set_line_width(1.0);
             ctx.begin_path();
             ctx.move_to(0.0, canvas_height - key_tick_h);
@@ -283,3 +296,37 @@ pub fn draw_time_markers(
     ctx.set_text_baseline("alphabetic"); // reset
     ctx.set_font("10px sans-serif"); // reset
 }
+
+/// Draw full-height vertical gridlines at the major time ticks, matching the
+/// ticks `draw_time_markers` puts on the time ruler for the same spacing.
+pub fn draw_time_gridlines(
+    ctx: &CanvasRenderingContext2d,
+    scroll_offset: f64,
+    visible_time: f64,
+    canvas_width: f64,
+    canvas_height: f64,
+    duration: f64,
+    tick_spacing_px: f64,
+) {
+    if visible_time <= 0.0 || canvas_width <= 0.0 {
+        return;
+    }
+    let px_per_sec = canvas_width / visible_time;
+    let interval = tick_interval(px_per_sec, tick_spacing_px);
+    let end_time = (scroll_offset + visible_time).min(duration);
+
+    ctx.set_stroke_style_str("rgba(255,255,255,0.12)");
+    ctx.set_line_width(1.0);
+    let mut t = (scroll_offset / interval).ceil() * interval;
+    while t <= end_time + interval * 0.01 {
+        // Snap to the pixel centre so 1 px lines stay crisp
+        let x = ((t - scroll_offset) * px_per_sec).round() + 0.5;
+        if x >= 0.0 && x <= canvas_width {
+            ctx.begin_path();
+            ctx.move_to(x, 0.0);
+            ctx.line_to(x, canvas_height);
+            ctx.stroke();
+        }
+        t += interval;
+    }
+}
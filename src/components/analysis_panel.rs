@@ -1,37 +1,165 @@
 use leptos::prelude::*;
-use crate::state::{AppState, CanvasTool, SpectrogramHandle};
+use crate::state::{AppState, CanvasTool, DbReference, FileKey, LoadedFile, SpectrogramHandle};
 use crate::annotations::AnnotationKind;
+use crate::types::SpectrogramColumn;
+use std::sync::{Arc, Weak};
 
-/// Format a frequency value for display (e.g. "45.0 kHz" or "800 Hz").
-fn fmt_freq(f: f64) -> String {
-    if f >= 1000.0 {
-        format!("{:.1} kHz", f / 1000.0)
-    } else {
-        format!("{:.0} Hz", f)
-    }
+/// Format a frequency value in the user's preferred unit (e.g. "45.0 kHz" or "800 Hz").
+pub(crate) fn fmt_freq(state: AppState, f: f64) -> String {
+    state.freq_unit.get().format(f)
+}
+
+/// Sample indices covered by `time_start..time_end`, using the same
+/// `(time * sr) as usize` rounding as the playback and export paths.
+fn sample_bounds(time_start: f64, time_end: f64, sample_rate: u32) -> (usize, usize) {
+    let sr = sample_rate as f64;
+    ((time_start.max(0.0) * sr) as usize, (time_end.max(0.0) * sr) as usize)
 }
 
-/// Format a selection/annotation's dimensions: duration and optional freq range.
-fn format_selection_dims(duration: f64, freq_low: Option<f64>, freq_high: Option<f64>) -> String {
-    let dur_str = crate::format_time::format_duration(duration, 3);
+/// Format a selection/annotation's dimensions: duration, sample bounds (when
+/// the sample rate is known) and optional freq range.
+fn format_selection_dims(
+    state: AppState,
+    time_start: f64,
+    time_end: f64,
+    freq_low: Option<f64>,
+    freq_high: Option<f64>,
+) -> String {
+    let dur_str = state.duration_unit.get().format(time_end - time_start);
+    let sample_rate = state.current_file_index.get().and_then(|i| {
+        state.files.with(|files| files.get(i).map(|f| f.audio.sample_rate))
+    });
+    let samples_str = sample_rate
+        .map(|sr| {
+            let (s0, s1) = sample_bounds(time_start, time_end, sr);
+            format!("   Samples: {}\u{2013}{} ({})", s0, s1, s1 - s0)
+        })
+        .unwrap_or_default();
     match (freq_low, freq_high) {
         (Some(fl), Some(fh)) => format!(
-            "Duration: {}   Freq range: {:.0} – {:.0} kHz",
-            dur_str, fl / 1000.0, fh / 1000.0
+            "Duration: {}{}   Freq range: {}",
+            dur_str, samples_str, state.freq_unit.get().format_range(fl, fh)
         ),
-        _ => format!("Duration: {}", dur_str),
+        _ => format!("Duration: {}{}", dur_str, samples_str),
     }
 }
 
+/// Raw STFT magnitude at (time, freq) in the current file and the FFT size
+/// it was computed with.
+pub(crate) fn cursor_magnitude(state: AppState, time: f64, freq: f64) -> Option<(f32, usize)> {
+    use crate::canvas::spectral_store;
+
+    let idx = state.current_file_index.get_untracked()?;
+    state.files.with_untracked(|files| {
+        let spec = &files.get(idx)?.spectrogram;
+        if let Some(mag) = spec.magnitude_at(time, freq) {
+            return Some((mag, spec.fft_size()));
+        }
+        // Large files keep their columns in the spectral store instead.
+        spectral_store::magnitude_at(idx, spec.sample_rate, spec.time_resolution, time, freq)
+    })
+}
+
+/// Loudest frequency between `freq_lo` and `freq_hi` in the spectrogram
+/// column `column` of the current file. None if the peak isn't at least
+/// `min_prominence_db` above the band's median level, i.e. there's only noise.
+pub(crate) fn column_peak_freq(state: AppState, column: usize, freq_lo: f64, freq_hi: f64, min_prominence_db: f32) -> Option<f64> {
+    use crate::canvas::spectral_store;
+
+    let peak = |mags: &[f32], bin_hz: f64| -> Option<f64> {
+        let lo = ((freq_lo.max(0.0) / bin_hz).ceil() as usize).min(mags.len());
+        let hi = ((freq_hi / bin_hz).floor() as usize + 1).clamp(lo, mags.len());
+        let band = &mags[lo..hi];
+        if band.len() < 3 {
+            return None;
+        }
+        let (i, &max) = band.iter().enumerate().max_by(|a, b| a.1.total_cmp(b.1))?;
+        let mut sorted = band.to_vec();
+        let mid = sorted.len() / 2;
+        let (_, &mut median, _) = sorted.select_nth_unstable_by(mid, f32::total_cmp);
+        let prominence_db = 20.0 * (max / median.max(1e-12)).log10();
+        (prominence_db >= min_prominence_db).then_some((lo + i) as f64 * bin_hz)
+    };
+
+    let idx = state.current_file_index.get_untracked()?;
+    state.files.with_untracked(|files| {
+        let spec = &files.get(idx)?.spectrogram;
+        if let Some(col) = spec.columns.get(column) {
+            return peak(&col.magnitudes, spec.freq_resolution);
+        }
+        // Large files keep their columns in the spectral store instead.
+        let fft_size = spectral_store::fft_size(idx)?;
+        let bin_hz = spec.sample_rate as f64 / fft_size as f64;
+        spectral_store::with_columns(idx, column, column + 1, |cols, _| {
+            peak(&cols.first()?.magnitudes, bin_hz)
+        })
+        .flatten()
+    })
+}
+
+/// Spectrogram level at (time, freq) in the current file, in dB relative to `reference`.
+/// `file_peak` is the file's maximum STFT magnitude (only used for `DbReference::Peak`).
+pub(crate) fn cursor_level_db(state: AppState, time: f64, freq: f64, reference: DbReference, file_peak: f32) -> Option<f32> {
+    use crate::canvas::colors::{magnitude_to_db, magnitude_to_dbfs};
+
+    let (mag, fft_size) = cursor_magnitude(state, time, freq)?;
+    let db = match reference {
+        DbReference::FullScale => magnitude_to_dbfs(mag, fft_size),
+        DbReference::Peak if file_peak > 0.0 => magnitude_to_db(mag) - magnitude_to_db(file_peak),
+        _ => return None,
+    };
+    db.is_finite().then_some(db)
+}
+
+thread_local! {
+    /// Last [`file_peak_magnitude`] scan: the file, its column buffer, and
+    /// the peak. The buffer is replaced whenever the spectrogram is
+    /// recomputed, so it stands in for a settings generation. Holding it
+    /// weakly keeps its address from being reused by a later buffer.
+    static PEAK_CACHE: std::cell::RefCell<Option<(FileKey, Weak<Vec<SpectrogramColumn>>, f32)>> = const { std::cell::RefCell::new(None) };
+}
+
+/// Loudest STFT magnitude in file `idx`, the reference for `DbReference::Peak`.
+/// In-memory columns are scanned once per file and spectrogram; the spectral
+/// store keeps its own running maximum.
+pub(crate) fn file_peak_magnitude(files: &[LoadedFile], idx: usize) -> Option<f32> {
+    let file = files.get(idx)?;
+    let columns = &file.spectrogram.columns;
+    if columns.is_empty() {
+        return Some(crate::canvas::spectral_store::get_max_magnitude(idx));
+    }
+    let key = file.key();
+    let cached = PEAK_CACHE.with(|c| {
+        c.borrow().as_ref()
+            .filter(|(k, buf, _)| *k == key && std::ptr::eq(buf.as_ptr(), Arc::as_ptr(columns)))
+            .map(|&(_, _, peak)| peak)
+    });
+    Some(cached.unwrap_or_else(|| {
+        let peak = columns.iter()
+            .flat_map(|c| c.magnitudes.iter().copied())
+            .fold(0.0f32, f32::max);
+        PEAK_CACHE.with(|c| *c.borrow_mut() = Some((key, Arc::downgrade(columns), peak)));
+        peak
+    }))
+}
+
 #[component]
 pub fn AnalysisPanel() -> impl IntoView {
     let state = expect_context::<AppState>();
 
+    // Loudest STFT bin in the current file, for the "re. peak" cursor readout.
+    // Only computed while that reference is selected.
+    let file_peak = Memo::new(move |_| {
+        if state.cursor_db_reference.get() != DbReference::Peak { return None; }
+        let idx = state.current_file_index.get()?;
+        state.files.with(|files| file_peak_magnitude(files, idx))
+    });
+
     let selection_dims = move || {
         let selection = state.selection.get()?;
         let d = selection.time_end - selection.time_start;
         if d > 0.0001 {
-            Some(format_selection_dims(d, selection.freq_low, selection.freq_high))
+            Some(format_selection_dims(state, selection.time_start, selection.time_end, selection.freq_low, selection.freq_high))
         } else {
             None
         }
@@ -50,7 +178,7 @@ pub fn AnalysisPanel() -> impl IntoView {
                 AnnotationKind::Region(r) => {
                     let d = r.time_end - r.time_start;
                     if d > 0.0001 {
-                        Some(format_selection_dims(d, r.freq_low, r.freq_high))
+                        Some(format_selection_dims(state, r.time_start, r.time_end, r.freq_low, r.freq_high))
                     } else {
                         None
                     }
@@ -93,16 +221,20 @@ pub fn AnalysisPanel() -> impl IntoView {
                         SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle => {
                             let lo = state.band_ff_freq_lo.get();
                             let hi = state.band_ff_freq_hi.get();
-                            format!("Band: {} – {}", fmt_freq(lo), fmt_freq(hi))
+                            format!("Band: {} – {}", fmt_freq(state, lo), fmt_freq(state, hi))
                         }
                         SpectrogramHandle::HetCenter => {
                             let f = state.het_frequency.get();
-                            format!("Heterodyne: {}", fmt_freq(f))
+                            format!("Heterodyne: {}", fmt_freq(state, f))
                         }
                         SpectrogramHandle::HetBandUpper | SpectrogramHandle::HetBandLower => {
                             let f = state.het_frequency.get();
                             let c = state.het_cutoff.get();
-                            format!("Heterodyne: {} ± {}", fmt_freq(f), fmt_freq(c))
+                            format!("Heterodyne: {} ± {}", fmt_freq(state, f), fmt_freq(state, c))
+                        }
+                        SpectrogramHandle::ExtraBandUpper(i) | SpectrogramHandle::ExtraBandLower(i) => {
+                            let (lo, hi) = state.extra_focus_bands.get().get(i).copied().unwrap_or_default();
+                            format!("Band {}: {} – {}", i + 2, fmt_freq(state, lo), fmt_freq(state, hi))
                         }
                     };
                     return view! {
@@ -114,7 +246,7 @@ pub fn AnalysisPanel() -> impl IntoView {
                 if let (Some(start), Some(current)) = (state.axis_drag_start_freq.get(), state.axis_drag_current_freq.get()) {
                     let lo = start.min(current);
                     let hi = start.max(current);
-                    let msg = format!("Selecting frequency range: {} – {}", fmt_freq(lo), fmt_freq(hi));
+                    let msg = format!("Selecting frequency range: {} – {}", fmt_freq(state, lo), fmt_freq(state, hi));
                     return view! {
                         <span style="color: #888">{msg}</span>
                     }.into_any();
@@ -149,8 +281,20 @@ pub fn AnalysisPanel() -> impl IntoView {
                 let freq = state.mouse_freq.get();
                 let time = state.cursor_time.get();
                 if let (Some(f), Some(t)) = (freq, time) {
+                    let reference = state.cursor_db_reference.get();
+                    let nyquist = state.files.with(|files| {
+                        state.current_file_index.get().and_then(|i| files.get(i)).map(|file| file.spectrogram.max_freq)
+                    });
+                    if nyquist.is_some_and(|n| f > n) {
+                        return view! {
+                            <span style="color: #777">{format!("{:.3}s  {}  above Nyquist", t, fmt_freq(state, f))}</span>
+                        }.into_any();
+                    }
+                    let level = cursor_level_db(state, t, f, reference, file_peak.get().unwrap_or(0.0))
+                        .map(|db| format!("  {:.1} {}", db, reference.unit()))
+                        .unwrap_or_default();
                     return view! {
-                        <span style="color: #777">{format!("{:.3}s  {}", t, fmt_freq(f))}</span>
+                        <span style="color: #777">{format!("{:.3}s  {}{}", t, fmt_freq(state, f), level)}</span>
                     }.into_any();
                 }
 
@@ -160,6 +304,7 @@ pub fn AnalysisPanel() -> impl IntoView {
                     let msg = if loading.len() == 1 {
                         let entry = &loading[0];
                         let stage = match &entry.stage {
+                            crate::state::LoadingStage::Queued => "Queued".to_string(),
                             crate::state::LoadingStage::Decoding => "Decoding".to_string(),
                             crate::state::LoadingStage::Preview => "Generating preview".to_string(),
                             crate::state::LoadingStage::Spectrogram(pct) => format!("Spectrogram {}%", pct),
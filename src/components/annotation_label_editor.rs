@@ -2,7 +2,7 @@ use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 use crate::state::AppState;
 use crate::annotations::AnnotationKind;
-use crate::canvas::spectrogram_renderer::freq_to_y;
+use crate::canvas::spectrogram_renderer::freq_span_y;
 use crate::components::file_sidebar::settings_panel::{
     delete_annotation, update_annotation_label,
 };
@@ -49,7 +49,7 @@ pub fn AnnotationLabelEditor() -> impl IntoView {
         let px_per_sec = canvas_w / visible_time;
         let x0 = ((region.time_start - start_time) * px_per_sec).max(0.0);
         let y0 = match region.freq_high {
-            Some(fh) => freq_to_y(fh, min_freq, max_freq, canvas_h).max(0.0),
+            Some(fh) => freq_span_y(region.freq_low.unwrap_or(fh), fh, min_freq, max_freq, canvas_h).0.max(0.0),
             None => 0.0,
         };
 
@@ -22,7 +22,8 @@ use crate::components::bottom_toolbar::BottomToolbar;
 use crate::components::xc_browser::XcBrowser;
 use crate::components::zc_chart::ZcDotChart;
 use crate::components::chromagram_view::ChromagramView;
-use crate::components::file_sidebar::{fetch_demo_index, load_single_demo};
+use crate::components::diff_view::{diff_partner, DiffView, DIFF_RANGE_PRESETS};
+use crate::components::file_sidebar::{fetch_demo_index, load_single_demo, set_fetch_policy, set_network_offline};
 use crate::components::bat_book_strip::BatBookStrip;
 use crate::components::bat_book_ref_panel::BatBookRefPanel;
 use crate::components::display_filter_button::DspFilterRow;
@@ -47,8 +48,60 @@ pub fn App() -> impl IntoView {
         }
     }
 
-    // Auto-load demo sound from URL hash (e.g. #XC928094)
-    if let Some(window) = web_sys::window() {
+    // Offline mode: block frontend fetches and tell the desktop backend to
+    // skip xeno-canto network calls.
+    Effect::new(move || {
+        let offline = state.offline_mode.get();
+        set_network_offline(offline);
+        if offline && state.xc_browser_open.get_untracked() {
+            state.xc_browser_open.set(false);
+        }
+        if state.is_tauri {
+            wasm_bindgen_futures::spawn_local(async move {
+                let args = js_sys::Object::new();
+                let _ = js_sys::Reflect::set(&args, &"offline".into(), &JsValue::from_bool(offline));
+                if let Err(e) = crate::tauri_bridge::tauri_invoke("xc_set_offline", &args.into()).await {
+                    log::warn!("xc_set_offline failed: {e}");
+                }
+            });
+        }
+    });
+
+    Effect::new(move || {
+        set_fetch_policy(state.fetch_timeout_secs.get(), state.fetch_retries.get());
+    });
+
+    // Theme: follow the system contrast preference (and changes to it) when
+    // the theme is on Auto, then restyle the page and the canvas overlays.
+    {
+        const MORE_CONTRAST_QUERY: &str = "(prefers-contrast: more), (forced-colors: active)";
+        let prefers_more_contrast = move || {
+            web_sys::window()
+                .and_then(|w| w.match_media(MORE_CONTRAST_QUERY).ok().flatten())
+                .is_some_and(|mql| mql.matches())
+        };
+        let system_contrast = RwSignal::new(prefers_more_contrast());
+        if let Some(mql) = web_sys::window().and_then(|w| w.match_media(MORE_CONTRAST_QUERY).ok().flatten()) {
+            let on_change = Closure::<dyn Fn()>::new(move || system_contrast.set(prefers_more_contrast()));
+            let _ = mql.add_event_listener_with_callback("change", on_change.as_ref().unchecked_ref());
+            on_change.forget();
+        }
+        Effect::new(move || {
+            let on = state.ui_theme.get().is_high_contrast(system_contrast.get());
+            crate::canvas::overlays::set_high_contrast(on);
+            state.high_contrast.set(on);
+            if let Some(body) = web_sys::window().and_then(|w| w.document()).and_then(|d| d.body()) {
+                if on {
+                    let _ = body.class_list().add_1("high-contrast");
+                } else {
+                    let _ = body.class_list().remove_1("high-contrast");
+                }
+            }
+        });
+    }
+
+    // Auto-load demo sound from URL hash (e.g. #XC928094). Skipped in offline mode.
+    if let Some(window) = web_sys::window().filter(|_| !state.offline_mode.get_untracked()) {
         if let Ok(hash) = window.location().hash() {
             let trimmed = hash.trim_start_matches('#');
             if trimmed.len() >= 3 && trimmed[..2].eq_ignore_ascii_case("XC") && trimmed[2..].chars().all(|c| c.is_ascii_digit()) {
@@ -63,7 +116,7 @@ pub fn App() -> impl IntoView {
                             if let Some(entry) = found {
                                 if let Err(e) = load_single_demo(entry, state, load_id).await {
                                     log::error!("Failed to load {}: {}", xc_id, e);
-                                    state.show_error_toast(format!("Failed to load {}", xc_id));
+                                    state.show_error_toast(format!("Failed to load {}: {}", xc_id, e));
                                 }
                             } else {
                                 state.show_info_toast(format!(
@@ -74,7 +127,7 @@ pub fn App() -> impl IntoView {
                         }
                         Err(e) => {
                             log::error!("Failed to fetch demo index: {e}");
-                            state.show_error_toast("Could not load demo sounds index");
+                            state.show_error_toast(format!("Could not load demo sounds index: {e}"));
                         }
                     }
                     state.loading_done(load_id);
@@ -228,9 +281,11 @@ pub fn App() -> impl IntoView {
             let _ = state.ps_factor.get();
             let _ = state.pv_factor.get();
             let _ = state.pv_hq.get();
+            let _ = state.te_hq.get();
             let _ = state.zc_factor.get();
             let _ = state.het_frequency.get();
             let _ = state.het_cutoff.get();
+            let _ = state.het_lp_order.get();
             let _ = state.gain_db.get();
             let _ = state.auto_gain.get();
             let _ = state.gain_mode.get();
@@ -308,6 +363,30 @@ pub fn App() -> impl IntoView {
         });
     }
 
+    // Apply the tile cache memory budget (evicts immediately when lowered)
+    Effect::new(move |_| {
+        let scale = state.tile_memory_budget.get().scale();
+        crate::canvas::tile_cache::set_budget_scale(scale);
+        state.tile_ready_signal.update(|n| *n = n.wrapping_add(1));
+    });
+
+    // Rebuild the renderer's custom colormap LUTs when the saved maps change
+    Effect::new(move |_| {
+        state.custom_colormaps.with(|maps| crate::canvas::spectrogram_renderer::set_custom_colormaps(maps));
+        state.tile_ready_signal.update(|n| *n = n.wrapping_add(1));
+    });
+
+    // Pull the zoom back under the limit when the zoom limit or FFT mode
+    // lowers the highest allowed zoom
+    Effect::new(move |_| {
+        state.zoom_limit_px.track();
+        state.spect_fft_mode.track();
+        let max_zoom = state.max_zoom();
+        if state.zoom_level.get_untracked() > max_zoom {
+            state.zoom_level.set(max_zoom);
+        }
+    });
+
     // Sync flow_enabled with main_view (Flow view → enabled, anything else → disabled)
     Effect::new(move |_| {
         let is_flow = state.main_view.get() == MainView::Flow;
@@ -322,7 +401,8 @@ pub fn App() -> impl IntoView {
         let scroll = state.scroll_offset.get();
         let zoom = state.zoom_level.get();
         let canvas_w = state.spectrogram_canvas_width.get();
-        let from_here_mode = state.play_start_mode.get().uses_from_here();
+        let from_here = state.play_start_mode.get().uses_from_here()
+            .then(|| state.play_anchor_fraction.get());
         let timeline = state.active_timeline.get();
         let files = state.files.get();
         let idx = state.current_file_index.get();
@@ -357,7 +437,7 @@ pub fn App() -> impl IntoView {
         } else {
             duration
         };
-        let effective_from_here = from_here_mode && !is_live;
+        let effective_from_here = from_here.filter(|_| !is_live);
         let clamped = viewport::clamp_scroll_for_mode(scroll, effective_duration, visible_time, effective_from_here);
         if (clamped - scroll).abs() > f64::EPSILON {
             state.scroll_offset.set(clamped);
@@ -413,6 +493,18 @@ pub fn App() -> impl IntoView {
         }
     });
 
+    // On file switch, seed bookmarks from the file's embedded WAV cue points
+    // so markers placed in other software (Audacity etc.) are navigable.
+    Effect::new(move |_| {
+        let idx = state.current_file_index.get();
+        let bookmarks = idx
+            .and_then(|i| state.files.with_untracked(|files| {
+                files.get(i).map(|f| crate::state::Bookmark::from_wav_markers(&f.wav_markers, f.audio.sample_rate))
+            }))
+            .unwrap_or_default();
+        state.bookmarks.set(bookmarks);
+    });
+
     // Persist vertical zoom back to the current file whenever it changes.
     Effect::new(move |_| {
         let min = state.min_display_freq.get();
@@ -632,6 +724,10 @@ pub fn App() -> impl IntoView {
                     noise_reduce_enabled: state.noise_reduce_enabled.get_untracked(),
                     noise_reduce_strength: state.noise_reduce_strength.get_untracked(),
                     noise_reduce_floor: state.noise_reduce_floor.get_untracked(),
+                    // Per-file corrections, kept below rather than shared across the group
+                    dc_offset: None,
+                    declip: false,
+                    playback: Some(crate::playback_prefs::PlaybackPreference::capture(&state)),
                 };
 
                 // Save to the outgoing file and all files in its sequence group
@@ -645,7 +741,11 @@ pub fn App() -> impl IntoView {
                             groups.get(i).and_then(|g| g.as_ref()).map(|ti| &ti.group_key) == Some(gk)
                         });
                         if dominated {
-                            file.settings = settings.clone();
+                            file.settings = FileSettings {
+                                dc_offset: file.settings.dc_offset,
+                                declip: file.settings.declip,
+                                ..settings.clone()
+                            };
                         }
                     }
                 });
@@ -686,9 +786,25 @@ pub fn App() -> impl IntoView {
                     state.noise_reduce_strength.set(s.noise_reduce_strength);
                     state.noise_reduce_floor.set(s.noise_reduce_floor.clone());
                 }
+                // Re-apply this file's (or its file class's) playback mode
+                if old_idx != new_idx && state.remember_playback_mode.get_untracked() {
+                    if let Some(pref) = crate::playback_prefs::preference_for_file(&state, ni) {
+                        pref.apply(&state);
+                    }
+                }
             }
         });
     }
+    crate::playback_prefs::track_playback_preference(state);
+
+    // Remember the current file's manual gain across sessions. Only the gain
+    // signals are tracked: on a file switch they are set after the index, so
+    // the values read here always belong to the current file.
+    Effect::new(move |_| {
+        let _mode = state.gain_mode.get();
+        let _gain = state.gain_db.get();
+        crate::file_corrections::save_current(state);
+    });
 
     // Auto-save annotations to OPFS (browser) or central store (Tauri) when dirty.
     Effect::new(move |_| {
@@ -779,6 +895,22 @@ pub fn App() -> impl IntoView {
             let t = state_kb.playhead_time.get_untracked();
             crate::components::overflow_menu::add_marker_at_time(&state_kb, t);
         }
+        // F = focus HFR on the selection's frequency band
+        if (ev.key() == "f" || ev.key() == "F") && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key() {
+            if crate::components::axis_drag::focus_on_selection(state_kb) {
+                ev.prevent_default();
+                state_kb.show_info_toast("Focused on selection (F)");
+            }
+        }
+        // 0 = fit the whole file to the window, 1 = reset to the default zoom
+        if (ev.key() == "0" || ev.key() == "1") && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key() {
+            ev.prevent_default();
+            if ev.key() == "0" {
+                state_kb.zoom_to_fit();
+            } else {
+                state_kb.reset_zoom();
+            }
+        }
         // Q = toggle frequency bounds on current selection or selected annotations (region ↔ segment)
         if (ev.key() == "q" || ev.key() == "Q") && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key() {
             if let Some(sel) = state_kb.selection.get_untracked() {
@@ -891,6 +1023,37 @@ pub fn App() -> impl IntoView {
             ev.prevent_default();
             state_kb.redo_annotations();
         }
+        // Up/Down = nudge heterodyne frequency (HET mode only). Shift = fine step.
+        if (ev.key() == "ArrowUp" || ev.key() == "ArrowDown")
+            && state_kb.playback_mode.get_untracked() == PlaybackMode::Heterodyne
+            && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key()
+        {
+            ev.prevent_default();
+            let step = if ev.shift_key() { 100.0 } else { 1000.0 };
+            let delta = if ev.key() == "ArrowUp" { step } else { -step };
+            let current = state_kb.het_frequency.get_untracked();
+            // Snap to the step grid so repeated nudges land on round values
+            let next = ((current + delta) / step).round() * step;
+            state_kb.set_het_frequency_manual(next);
+        }
+        // Shift+Up/Down = pan the frequency axis by 10% of the visible band,
+        // Shift+PgUp/PgDn = zoom it in/out (HET mode keeps Shift+Up/Down for
+        // the fine heterodyne nudge above).
+        if ev.shift_key() && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key() {
+            let freq_nav = match ev.key().as_str() {
+                "ArrowUp" | "ArrowDown" if state_kb.playback_mode.get_untracked() == PlaybackMode::Heterodyne => None,
+                "ArrowUp" => Some((0.1, 1.0)),
+                "ArrowDown" => Some((-0.1, 1.0)),
+                "PageUp" => Some((0.0, 1.0 / 1.25)),
+                "PageDown" => Some((0.0, 1.25)),
+                _ => None,
+            };
+            if let Some((pan, zoom)) = freq_nav {
+                ev.prevent_default();
+                crate::components::gutter::nudge_freq_view(state_kb, pan, zoom);
+                return;
+            }
+        }
         // Navigation: arrow keys, PgUp/PgDn, Ctrl+Home/End
         let is_ctrl = ev.ctrl_key() || ev.meta_key();
         let nav_action = match ev.key().as_str() {
@@ -918,21 +1081,36 @@ pub fn App() -> impl IntoView {
                 let zoom = state_kb.zoom_level.get_untracked();
                 let canvas_w = state_kb.spectrogram_canvas_width.get_untracked();
                 let visible_time = viewport::visible_time(canvas_w, zoom, time_res);
-                let from_here_mode = state_kb.play_start_mode.get_untracked().uses_from_here();
-                let (_min_scroll, max_scroll) = viewport::scroll_bounds_for_mode(duration, visible_time, from_here_mode);
+                let from_here = state_kb.from_here_fraction();
+                let (_min_scroll, max_scroll) = viewport::scroll_bounds_for_mode(duration, visible_time, from_here);
                 let new_scroll = match key.as_str() {
-                    "Home" => viewport::clamp_scroll_for_mode(0.0, duration, visible_time, from_here_mode),
+                    "Home" => viewport::clamp_scroll_for_mode(0.0, duration, visible_time, from_here),
                     "End" => max_scroll,
-                    "ArrowLeft" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() - visible_time * 0.2, duration, visible_time, from_here_mode),
-                    "ArrowRight" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() + visible_time * 0.2, duration, visible_time, from_here_mode),
-                    "PageUp" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() - visible_time * 0.8, duration, visible_time, from_here_mode),
-                    "PageDown" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() + visible_time * 0.8, duration, visible_time, from_here_mode),
+                    "ArrowLeft" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() - visible_time * 0.2, duration, visible_time, from_here),
+                    "ArrowRight" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() + visible_time * 0.2, duration, visible_time, from_here),
+                    "PageUp" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() - visible_time * 0.8, duration, visible_time, from_here),
+                    "PageDown" => viewport::clamp_scroll_for_mode(state_kb.scroll_offset.get_untracked() + visible_time * 0.8, duration, visible_time, from_here),
                     _ => state_kb.scroll_offset.get_untracked(),
                 };
                 state_kb.suspend_follow();
                 state_kb.scroll_offset.set(new_scroll);
             }
         }
+        // Enter = apply a pending snap-to-call preview, Esc = keep the drawn box
+        let pending_snap = state_kb.snap_preview.get_untracked()
+            .filter(|(drawn, _)| Some(*drawn) == state_kb.selection.get_untracked());
+        if let Some((_, snapped)) = pending_snap {
+            if ev.key() == "Enter" {
+                ev.prevent_default();
+                state_kb.snap_preview.set(None);
+                state_kb.selection.set(Some(snapped));
+                return;
+            }
+            if ev.key() == "Escape" {
+                state_kb.snap_preview.set(None);
+                return;
+            }
+        }
         if ev.key() == "Escape" {
             if state_kb.bat_book_ref_open.get_untracked() {
                 state_kb.bat_book_ref_open.set(false);
@@ -951,16 +1129,21 @@ pub fn App() -> impl IntoView {
             && !state_kb.clean_view.get_untracked() {
                 state_kb.clean_view.set(true);
             }
+        // Shift+Backtick (~): pin or unpin clean view. Ignore auto-repeat so
+        // holding the key doesn't flicker the pin.
+        if ev.key() == "~" && !ev.repeat() && !ev.ctrl_key() && !ev.meta_key() && !ev.alt_key() {
+            state_kb.set_clean_view_pinned(!state_kb.clean_view_pinned.get_untracked());
+        }
     });
     let window = web_sys::window().unwrap();
     let _ = window.add_event_listener_with_callback("keydown", handler.as_ref().unchecked_ref());
     handler.forget();
 
-    // Keyup handler: release clean view on backtick release
+    // Keyup handler: release clean view on backtick release (unless pinned)
     let state_ku = state;
     let keyup_handler = Closure::<dyn Fn(web_sys::KeyboardEvent)>::new(move |ev: web_sys::KeyboardEvent| {
         if ev.key() == "`" {
-            state_ku.clean_view.set(false);
+            state_ku.clean_view.set(state_ku.clean_view_pinned.get_untracked());
         }
     });
     let _ = window.add_event_listener_with_callback("keyup", keyup_handler.as_ref().unchecked_ref());
@@ -969,7 +1152,7 @@ pub fn App() -> impl IntoView {
     // Reset clean view if window loses focus (so it doesn't stick)
     let state_blur = state;
     let blur_handler = Closure::<dyn Fn()>::new(move || {
-        state_blur.clean_view.set(false);
+        state_blur.clean_view.set(state_blur.clean_view_pinned.get_untracked());
     });
     let _ = window.add_event_listener_with_callback("blur", blur_handler.as_ref().unchecked_ref());
     blur_handler.forget();
@@ -1037,30 +1220,18 @@ pub fn App() -> impl IntoView {
             if file_paths.is_empty() { return; }
 
             log::info!("Tauri drag-drop: {} file(s)", file_paths.len());
-            for path in file_paths {
-                let name = path.rsplit(['/', '\\']).next().unwrap_or(&path).to_string();
-                // Filter to audio-ish extensions
-                let ext = name.rsplit('.').next().unwrap_or("").to_lowercase();
-                if !matches!(ext.as_str(), "wav" | "w4v" | "flac" | "ogg" | "mp3" | "m4a" | "m4b") {
-                    log::info!("Skipping non-audio drop: {name}");
-                    continue;
-                }
-                let state = state_drop;
-                let load_id = state.loading_start(&name);
-                let name_for_err = name.clone();
-                leptos::task::spawn_local(async move {
-                    match crate::components::file_sidebar::load_native_file(path, state, load_id).await {
-                        Ok(()) => {}
-                        Err(e) => {
-                            log::error!("Failed to load {}: {}", name_for_err, e);
-                            state.show_error_toast(&format!("Couldn't open {name_for_err}: {e}"));
-                        }
-                    }
-                    state.loading_done(load_id);
-                });
-            }
+            crate::components::file_sidebar::load_native_paths(state_drop, file_paths);
         });
         crate::tauri_bridge::tauri_listen("tauri://drag-drop", callback);
+
+        // Files from the command line or a file association: drain the queue
+        // now, and again whenever the OS hands over more while running.
+        let state_open = state;
+        let callback = wasm_bindgen::closure::Closure::<dyn FnMut(wasm_bindgen::JsValue)>::new(move |_: wasm_bindgen::JsValue| {
+            wasm_bindgen_futures::spawn_local(crate::components::file_sidebar::open_pending_native_files(state_open));
+        });
+        crate::tauri_bridge::tauri_listen("open-files", callback);
+        wasm_bindgen_futures::spawn_local(crate::components::file_sidebar::open_pending_native_files(state));
     }
 
     // Back button (Android/browser): close sidebar when open.
@@ -1238,6 +1409,7 @@ fn MainArea() -> impl IntoView {
                                     </div>
                                 }.into_any(),
                                 MainView::Chromagram => view! { <ChromagramView /> }.into_any(),
+                                MainView::Diff => view! { <DiffView /> }.into_any(),
                             }}
 
                             // VU meter — red line on right edge during recording/listening
@@ -1307,11 +1479,33 @@ fn MainArea() -> impl IntoView {
                     let empty_msg = if state.is_mobile.get() {
                         "Tap \u{2630} to load audio files"
                     } else {
-                        "Drop WAV, FLAC or MP3 files into the sidebar"
+                        "Drop WAV, FLAC, MP3 or other audio files here"
+                    };
+                    // The empty main view is a drop target too, handled like
+                    // the Files panel. (On desktop, Tauri's own drag-drop
+                    // event covers the whole window.)
+                    let drag_over = RwSignal::new(false);
+                    let on_drop = move |ev: web_sys::DragEvent| {
+                        ev.prevent_default();
+                        drag_over.set(false);
+                        if let Some(file_list) = ev.data_transfer().and_then(|dt| dt.files()) {
+                            crate::components::file_sidebar::load_dropped_files(state, &file_list);
+                        }
                     };
                     view! {
-                        <div class="empty-state">
-                            {empty_msg}
+                        <div
+                            class=move || if drag_over.get() { "empty-state drag-over" } else { "empty-state" }
+                            on:dragenter=move |ev: web_sys::DragEvent| { ev.prevent_default(); drag_over.set(true); }
+                            on:dragover=move |ev: web_sys::DragEvent| { ev.prevent_default(); drag_over.set(true); }
+                            on:dragleave=move |_: web_sys::DragEvent| drag_over.set(false)
+                            on:drop=on_drop
+                        >
+                            <div class="empty-state-hint">
+                                <div>{empty_msg}</div>
+                                {(!state.is_mobile.get()).then(|| view! {
+                                    <div class="empty-state-sub">"or use the Files panel to browse, open a URL or try a demo recording"</div>
+                                })}
+                            </div>
                         </div>
                         {move || state.bat_book_open.get().then(|| view! { <BatBookStrip /> })}
                         {move || state.bat_book_ref_open.get().then(|| view! { <BatBookRefPanel /> })}
@@ -1407,6 +1601,13 @@ fn MainArea() -> impl IntoView {
     }
 }
 
+/// Persist a focus band intensity setting under `key`.
+fn save_focus_setting(key: &str, value: &str) {
+    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = ls.set_item(key, value);
+    }
+}
+
 fn toggle_panel(state: &AppState, panel: LayerPanel) {
     state.layer_panel_open.update(|p| {
         *p = if *p == Some(panel) { None } else { Some(panel) };
@@ -1676,7 +1877,7 @@ pub fn MainViewButton() -> impl IntoView {
                         <span class="setting-label">{move || {
                             let mode = state.resonator_fft_mode.get();
                             let sr = resonator_quick_sample_rate(state);
-                            let current_lod = crate::canvas::tile_cache::select_lod(
+                            let current_lod = crate::canvas::tile_cache::main_view_lod(
                                 state.zoom_level.get(),
                             );
                             let f = mode.fft_for_lod(current_lod).max(2);
@@ -1895,6 +2096,13 @@ pub fn MainViewButton() -> impl IntoView {
                             }).collect::<Vec<_>>()
                         }}
                     </select>
+                    <div
+                        class="dsp-custom-value"
+                        style="margin: 0 8px 4px; text-align: left;"
+                        title="Parameters of the spectrogram at the current zoom, for reporting with figures"
+                    >
+                        {move || state.spect_params().map(|p| p.summary())}
+                    </div>
                 }
             })}
 
@@ -2069,6 +2277,33 @@ pub fn MainViewButton() -> impl IntoView {
                                 if g == 1.0 { "linear".to_string() } else { format!("{:.2}", g) }
                             }}</span>
                         </div>
+                        {(!is_xform).then(|| view! {
+                            <div class="dsp-custom-slider-row">
+                                <button
+                                    class=move || if state.spect_auto_exposure.get() {
+                                        "layer-panel-opt selected"
+                                    } else {
+                                        "layer-panel-opt"
+                                    }
+                                    style="font-size: 9px; padding: 2px 6px; width: auto; display: inline;"
+                                    title="Adjust gain to the loudest part of the visible view; Gain then adds on top"
+                                    on:click=move |_| {
+                                        let on = !state.spect_auto_exposure.get_untracked();
+                                        state.spect_auto_exposure.set(on);
+                                        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                            let _ = ls.set_item("oversample_spect_auto_exposure", if on { "true" } else { "false" });
+                                        }
+                                    }
+                                >"Auto-exposure"</button>
+                                <span class="dsp-custom-value">{move || {
+                                    if state.spect_auto_exposure.get() {
+                                        format!("{:+.0} dB", state.auto_exposure_db.get())
+                                    } else {
+                                        String::new()
+                                    }
+                                }}</span>
+                            </div>
+                        })}
                         <div style="text-align: right; padding-top: 4px;">
                             <button
                                 class="layer-panel-opt"
@@ -2096,6 +2331,103 @@ pub fn MainViewButton() -> impl IntoView {
                 }
             })}
 
+            // Separate intensity for the HFR focus band
+            {move || (state.main_view.get() == MainView::Spectrogram && state.hfr_enabled.get()).then(|| {
+                view! {
+                    <hr />
+                    <div class="dsp-custom-section">
+                        <div class="dsp-custom-title">"Focus Band Intensity"</div>
+                        <div class="dsp-custom-slider-row">
+                            <button
+                                class=move || if state.hfr_focus_contrast.get() {
+                                    "layer-panel-opt selected"
+                                } else {
+                                    "layer-panel-opt"
+                                }
+                                style="font-size: 9px; padding: 2px 6px; width: auto; display: inline;"
+                                title="Use separate gain, range and contrast inside the focus band"
+                                on:click=move |_| {
+                                    let on = !state.hfr_focus_contrast.get_untracked();
+                                    state.hfr_focus_contrast.set(on);
+                                    save_focus_setting("oversample_hfr_focus_contrast", if on { "true" } else { "false" });
+                                }
+                            >"Separate"</button>
+                        </div>
+                        {move || state.hfr_focus_contrast.get().then(|| view! {
+                            <div class="dsp-custom-slider-row">
+                                <span class="dsp-slider-label">"Gain"</span>
+                                <input
+                                    type="range"
+                                    class="setting-range"
+                                    min="-20" max="40" step="1"
+                                    prop:value=move || state.hfr_focus_gain_db.get().to_string()
+                                    on:input=move |ev: web_sys::Event| {
+                                        let target = ev.target().unwrap();
+                                        let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                        if let Ok(v) = input.value().parse::<f32>() {
+                                            state.hfr_focus_gain_db.set(v);
+                                            save_focus_setting("oversample_hfr_focus_gain_db", &v.to_string());
+                                        }
+                                    }
+                                    on:dblclick=move |_| {
+                                        state.hfr_focus_gain_db.set(0.0);
+                                        save_focus_setting("oversample_hfr_focus_gain_db", "0");
+                                    }
+                                />
+                                <span class="dsp-custom-value">{move || format!("{:+.0} dB", state.hfr_focus_gain_db.get())}</span>
+                            </div>
+                            <div class="dsp-custom-slider-row">
+                                <span class="dsp-slider-label">"Range"</span>
+                                <input
+                                    type="range"
+                                    class="setting-range"
+                                    min="20" max="120" step="5"
+                                    prop:value=move || state.hfr_focus_range_db.get().to_string()
+                                    on:input=move |ev: web_sys::Event| {
+                                        let target = ev.target().unwrap();
+                                        let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                        if let Ok(v) = input.value().parse::<f32>() {
+                                            state.hfr_focus_range_db.set(v);
+                                            save_focus_setting("oversample_hfr_focus_range_db", &v.to_string());
+                                        }
+                                    }
+                                    on:dblclick=move |_| {
+                                        state.hfr_focus_range_db.set(120.0);
+                                        save_focus_setting("oversample_hfr_focus_range_db", "120");
+                                    }
+                                />
+                                <span class="dsp-custom-value">{move || format!("{:.0} dB", state.hfr_focus_range_db.get())}</span>
+                            </div>
+                            <div class="dsp-custom-slider-row">
+                                <span class="dsp-slider-label">"Contrast"</span>
+                                <input
+                                    type="range"
+                                    class="setting-range"
+                                    min="0.2" max="3.0" step="0.05"
+                                    prop:value=move || state.hfr_focus_gamma.get().to_string()
+                                    on:input=move |ev: web_sys::Event| {
+                                        let target = ev.target().unwrap();
+                                        let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                        if let Ok(v) = input.value().parse::<f32>() {
+                                            state.hfr_focus_gamma.set(v);
+                                            save_focus_setting("oversample_hfr_focus_gamma", &v.to_string());
+                                        }
+                                    }
+                                    on:dblclick=move |_| {
+                                        state.hfr_focus_gamma.set(1.0);
+                                        save_focus_setting("oversample_hfr_focus_gamma", "1");
+                                    }
+                                />
+                                <span class="dsp-custom-value">{move || {
+                                    let g = state.hfr_focus_gamma.get();
+                                    if g == 1.0 { "linear".to_string() } else { format!("{:.2}", g) }
+                                }}</span>
+                            </div>
+                        })}
+                    </div>
+                }
+            })}
+
             // Waveform view gain (when Waveform is active)
             {move || (state.main_view.get() == MainView::Waveform).then(|| {
                 view! {
@@ -2304,6 +2636,99 @@ pub fn MainViewButton() -> impl IntoView {
                 }
             })}
 
+            // Diff options (when Diff is active)
+            {move || (state.main_view.get() == MainView::Diff).then(|| {
+                let current = state.current_file_index.get();
+                let others: Vec<(usize, crate::state::FileKey)> = state.files.with(|files| {
+                    files.iter().enumerate()
+                        .filter(|&(i, _)| Some(i) != current)
+                        .map(|(i, f)| (i, f.key()))
+                        .collect()
+                });
+                view! {
+                    {move || state.xc_identify.get().map(|session| {
+                        let title = format!("Identify (Fc {:.1} kHz)", session.char_freq_khz);
+                        let candidate = session.candidates.get(session.current).map(|c| {
+                            let reference = c.reference.map(|id| format!(" \u{2014} XC{id}")).unwrap_or_default();
+                            format!("{}/{}: {}{}", session.current + 1, session.candidates.len(), c.name, reference)
+                        });
+                        let many = session.candidates.len() > 1;
+                        view! {
+                            <hr />
+                            <div class="layer-panel-title">{title}</div>
+                            {candidate.map(|c| view! { <div class="layer-panel-opt">{c}</div> })}
+                            {session.status.map(|s| view! { <div class="layer-panel-opt">{s}</div> })}
+                            {many.then(|| view! {
+                                <button
+                                    class="layer-panel-opt"
+                                    on:click=move |_| crate::components::xc_browser::step_identify(state, -1)
+                                >"\u{25C0} Previous candidate"</button>
+                                <button
+                                    class="layer-panel-opt"
+                                    on:click=move |_| crate::components::xc_browser::step_identify(state, 1)
+                                >"Next candidate \u{25B6}"</button>
+                            })}
+                            <button
+                                class="layer-panel-opt"
+                                title="Stop stepping through reference recordings. Loaded references stay open."
+                                on:click=move |_| state.xc_identify.set(None)
+                            >"End identify"</button>
+                        }
+                    })}
+                    <hr />
+                    <div class="layer-panel-title">"Compare With (B)"</div>
+                    {if others.is_empty() {
+                        view! { <div class="layer-panel-opt">"Load another file"</div> }.into_any()
+                    } else {
+                        others.into_iter().map(|(i, key)| {
+                            let name = key.name.clone();
+                            view! {
+                                <button
+                                    class=move || layer_opt_class(diff_partner(state) == Some(i))
+                                    on:click=move |_| state.diff_other_file.set(Some(key.clone()))
+                                >
+                                    {name}
+                                </button>
+                            }
+                        }).collect_view().into_any()
+                    }}
+
+                    <hr />
+                    <div class="layer-panel-title">"Align"</div>
+                    <div class="dsp-custom-slider-row" title="Shift B against A, e.g. when one copy has extra audio at the start. Double-click to reset.">
+                        <span class="dsp-slider-label">"B offset"</span>
+                        <input
+                            type="range"
+                            class="setting-range"
+                            min="-0.5" max="0.5" step="0.001"
+                            prop:value=move || state.diff_offset_secs.get().to_string()
+                            on:input=move |ev: web_sys::Event| {
+                                let target = ev.target().unwrap();
+                                let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                if let Ok(v) = input.value().parse::<f64>() {
+                                    state.diff_offset_secs.set(v);
+                                }
+                            }
+                            on:dblclick=move |_| state.diff_offset_secs.set(0.0)
+                        />
+                        <span class="dsp-custom-value">{move || format!("{:+.0} ms", state.diff_offset_secs.get() * 1000.0)}</span>
+                    </div>
+
+                    <hr />
+                    <div class="layer-panel-title">"Scale"</div>
+                    {DIFF_RANGE_PRESETS.iter().map(|&db| {
+                        view! {
+                            <button
+                                class=move || layer_opt_class(state.diff_range_db.get() == db)
+                                on:click=move |_| state.diff_range_db.set(db)
+                            >
+                                {format!("±{db:.0} dB")}
+                            </button>
+                        }
+                    }).collect_view()}
+                }
+            })}
+
             // Chromagram options (when Chromagram is active)
             {move || (state.main_view.get() == MainView::Chromagram).then(|| {
                 view! {
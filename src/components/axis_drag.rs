@@ -73,6 +73,24 @@ pub fn select_all_frequencies(state: AppState) {
     }
 }
 
+/// Focus HFR on the transient selection's frequency band: copy its bounds
+/// into the BandFF range and enable HFR. Returns false (and changes nothing)
+/// if the selection has no frequency bounds or they're under 500 Hz apart.
+/// Used by the selection menu and the F key.
+pub fn focus_on_selection(state: AppState) -> bool {
+    let Some(sel) = state.selection.get_untracked() else { return false };
+    let (Some(lo), Some(hi)) = (sel.freq_low, sel.freq_high) else { return false };
+    if hi - lo <= 500.0 {
+        return false;
+    }
+    state.set_band_ff_range(lo, hi);
+    let stack = state.focus_stack.get_untracked();
+    if !stack.hfr_enabled() {
+        state.toggle_hfr();
+    }
+    true
+}
+
 /// Select all time: create a full-duration selection.
 /// If HFR is active, the selection includes frequency bounds (region);
 /// otherwise it's time-only (segment).
@@ -560,7 +560,13 @@ pub fn BottomToolbar() -> impl IntoView {
                     <button
                         class=move || if state.layer_panel_open.get() == Some(LayerPanel::Channel) { "layer-btn open" } else { "layer-btn" }
                         on:click=move |_| toggle_panel(&state, LayerPanel::Channel)
-                        title="Channel / Track view"
+                        title=move || {
+                            if state.active_timeline_track.get().is_some() {
+                                "Channel / Track view".to_string()
+                            } else {
+                                format!("Channel / Track view \u{2014} {}", state.channel_view.get().description())
+                            }
+                        }
                     >
                         <span class="layer-btn-category">"Ch"</span>
                         <span class="layer-btn-value">{move || {
@@ -568,16 +574,7 @@ pub fn BottomToolbar() -> impl IntoView {
                             if let Some(ref track) = state.active_timeline_track.get() {
                                 return format!("Trk {}", track);
                             }
-                            match state.channel_view.get() {
-                                ChannelView::Stereo => "Stereo".to_string(),
-                                ChannelView::MonoMix => "L+R".to_string(),
-                                ChannelView::Channel(0) => "L".to_string(),
-                                ChannelView::Channel(1) => "R".to_string(),
-                                ChannelView::Difference => "L-R".to_string(),
-                                ChannelView::Channel(2) => "Ch3".to_string(),
-                                ChannelView::Channel(3) => "Ch4".to_string(),
-                                ChannelView::Channel(_) => "Ch?".to_string(),
-                            }
+                            state.channel_view.get().label().to_string()
                         }}</span>
                     </button>
                     <Show when=move || state.layer_panel_open.get() == Some(LayerPanel::Channel)>
@@ -585,6 +582,11 @@ pub fn BottomToolbar() -> impl IntoView {
                             let set_ch = move |cv: ChannelView| {
                                 move |_: web_sys::MouseEvent| {
                                     state.channel_view.set(cv);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_channel_view", &cv.key());
+                                    }
                                     state.active_timeline_track.set(None); // Clear track when switching channel
                                     crate::canvas::tile_cache::clear_all_caches();
                                     state.tile_ready_signal.update(|n| *n = n.wrapping_add(1));
@@ -614,7 +616,11 @@ pub fn BottomToolbar() -> impl IntoView {
                                             <button
                                                 class=move || layer_opt_class(state.channel_view.get() == ChannelView::MonoMix && state.active_timeline_track.with(|t| t.is_none()))
                                                 on:click=set_ch(ChannelView::MonoMix)
-                                            >"Mono (L+R)"</button>
+                                            >"Average"</button>
+                                            <button
+                                                class=move || layer_opt_class(state.channel_view.get() == ChannelView::Sum && state.active_timeline_track.with(|t| t.is_none()))
+                                                on:click=set_ch(ChannelView::Sum)
+                                            >"Sum (L+R)"</button>
                                             <button
                                                 class=move || layer_opt_class(state.channel_view.get() == ChannelView::Channel(0) && state.active_timeline_track.with(|t| t.is_none()))
                                                 on:click=set_ch(ChannelView::Channel(0))
@@ -627,6 +633,7 @@ pub fn BottomToolbar() -> impl IntoView {
                                                 class=move || layer_opt_class(state.channel_view.get() == ChannelView::Difference && state.active_timeline_track.with(|t| t.is_none()))
                                                 on:click=set_ch(ChannelView::Difference)
                                             >"Diff (L-R)"</button>
+                                            <div class="layer-panel-note">{move || state.channel_view.get().description()}</div>
                                         })
                                     } else {
                                         None
@@ -873,6 +880,74 @@ pub fn BottomToolbar() -> impl IntoView {
                             <option value="30" selected=move || state.mic_preroll_buffer_secs.get() == 30>"30s"</option>
                         </select>
                     </div>
+                    // Native display stream rate
+                    {state.is_tauri.then(|| view! {
+                        <div class="layer-panel-slider-row het-text-row"
+                            title="Low-pass and decimate the samples streamed from a native mic for the live view. Lowers load at high sample rates; listening and recordings keep the full device rate (pre-roll needs the full-rate stream)">
+                            <label style="font-size: 11px;">"Display rate"</label>
+                            <select style="font-size: 11px; background: #333; color: #ccc; border: 1px solid #555; padding: 1px 2px;"
+                                on:change=move |ev| {
+                                    if let Ok(val) = leptos::prelude::event_target_value(&ev).parse::<u32>() {
+                                        state.mic_display_max_rate.set(val);
+                                        if let Some(ls) = web_sys::window()
+                                            .and_then(|w| w.local_storage().ok().flatten())
+                                        {
+                                            let _ = ls.set_item("oversample_mic_display_max_rate", &val.to_string());
+                                        }
+                                        leptos::task::spawn_local(async move {
+                                            crate::audio::mic_backend::apply_display_rate(&state).await;
+                                        });
+                                    }
+                                }
+                            >
+                                <option value="0" selected=move || state.mic_display_max_rate.get() == 0>"Full"</option>
+                                <option value="192000" selected=move || state.mic_display_max_rate.get() == 192_000>"192 kHz"</option>
+                                <option value="96000" selected=move || state.mic_display_max_rate.get() == 96_000>"96 kHz"</option>
+                                <option value="48000" selected=move || state.mic_display_max_rate.get() == 48_000>"48 kHz"</option>
+                            </select>
+                        </div>
+                    })}
+                    // Live spectrogram averaging
+                    <div class="layer-panel-slider-row het-text-row"
+                        title="Average the live spectrogram over time so persistent calls stand out and noise flicker fades. Display only: recordings and their spectrograms are unaffected">
+                        <label style="font-size: 11px;">"Live averaging"</label>
+                        <select style="font-size: 11px; background: #333; color: #ccc; border: 1px solid #555; padding: 1px 2px;"
+                            on:change=move |ev| {
+                                if let Ok(val) = leptos::prelude::event_target_value(&ev).parse::<f64>() {
+                                    state.mic_live_smoothing_secs.set(val);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_live_smoothing_secs", &val.to_string());
+                                    }
+                                }
+                            }
+                        >
+                            {[0.0, 0.02, 0.05, 0.1, 0.25, 0.5].into_iter().map(|secs: f64| view! {
+                                <option value=secs.to_string() selected=move || state.mic_live_smoothing_secs.get() == secs>
+                                    {if secs == 0.0 { "Off".to_string() } else { format!("{:.0} ms", secs * 1000.0) }}
+                                </option>
+                            }).collect::<Vec<_>>()}
+                        </select>
+                    </div>
+                    // Level-triggered bookmarks
+                    <div class="layer-panel-slider-row het-text-row"
+                        title="While recording, drop a bookmark each time the input peaks above this level (at most once a second)">
+                        <label style="font-size: 11px;">"Trigger bookmarks"</label>
+                        <select style="font-size: 11px; background: #333; color: #ccc; border: 1px solid #555; padding: 1px 2px;"
+                            on:change=move |ev| {
+                                let val = leptos::prelude::event_target_value(&ev);
+                                state.mic_trigger_level_db.set(val.parse::<f64>().ok());
+                            }
+                        >
+                            <option value="off" selected=move || state.mic_trigger_level_db.get().is_none()>"Off"</option>
+                            {[-40.0, -30.0, -20.0, -12.0, -6.0].into_iter().map(|db: f64| view! {
+                                <option value=db.to_string() selected=move || state.mic_trigger_level_db.get() == Some(db)>
+                                    {format!("{db:.0} dBFS")}
+                                </option>
+                            }).collect::<Vec<_>>()}
+                        </select>
+                    </div>
                 </div>
             </ComboButton>
 
@@ -33,6 +33,7 @@ pub fn ChromagramView() -> impl IntoView {
         let scroll = state.scroll_offset.get();
         let zoom = state.zoom_level.get();
         let chroma_colormap = state.chroma_colormap.get();
+        spectrogram_renderer::set_background_color(state.spectrogram_background.get());
         let _chroma_gain = state.chroma_gain.get(); // triggers re-render after cache clear
         let chroma_gamma = state.chroma_gamma.get();
         let chroma_range = state.chroma_range.get();
@@ -65,8 +66,7 @@ pub fn ChromagramView() -> impl IntoView {
             .dyn_into::<CanvasRenderingContext2d>().unwrap();
 
         let Some(file) = idx.and_then(|i| files.get(i)) else {
-            ctx.set_fill_style_str("#000");
-            ctx.fill_rect(0.0, 0.0, display_w as f64, display_h as f64);
+            spectrogram_renderer::clear_background(&ctx, display_w as f64, display_h as f64);
             return;
         };
 
@@ -134,8 +134,8 @@ pub fn ChromagramView() -> impl IntoView {
         // Draw "play here" marker when not playing
         if state.play_start_mode.get() .uses_from_here() && !is_playing && canvas_tool == CanvasTool::Hand {
             let visible_time = viewport::visible_time(display_w as f64, zoom, time_res);
-            let here_x = display_w as f64 * viewport::PLAY_FROM_HERE_FRACTION;
-            let here_time = viewport::play_from_here_time(scroll, visible_time);
+            let here_x = display_w as f64 * state.play_anchor_fraction.get();
+            let here_time = viewport::play_from_here_time(scroll, visible_time, state.play_anchor_fraction.get());
             state.play_from_here_time.set(here_time);
             ctx.set_stroke_style_str("rgba(100, 160, 255, 0.35)");
             ctx.set_line_width(1.5);
@@ -180,7 +180,7 @@ pub fn ChromagramView() -> impl IntoView {
             .unwrap_or((1.0, 0.0));
         let zoom = state.zoom_level.get_untracked();
         let scroll = state.scroll_offset.get_untracked();
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
 
         let visible_time = viewport::visible_time(display_w, zoom, time_res);
         let playhead_rel = playhead - scroll;
@@ -189,7 +189,7 @@ pub fn ChromagramView() -> impl IntoView {
             let playhead_visible = playhead_rel >= 0.0 && playhead_rel <= visible_time;
             if playhead_visible {
                 let resume = match state.follow_visible_since.get_untracked() {
-                    Some(since) => js_sys::Date::now() - since >= 200.0,
+                    Some(since) => js_sys::Date::now() - since >= state.follow_resume_ms.get_untracked(),
                     None => true,
                 };
                 if resume {
@@ -202,10 +202,10 @@ pub fn ChromagramView() -> impl IntoView {
 
         if visible_time < viewport::FOLLOW_EXACT_THRESHOLD_SECS {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         } else if playhead_rel > visible_time * viewport::FOLLOW_CURSOR_EDGE_FRACTION || playhead_rel < 0.0 {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         }
     });
 
@@ -213,7 +213,7 @@ pub fn ChromagramView() -> impl IntoView {
         ev.prevent_default();
         if ev.ctrl_key() {
             let delta = if ev.delta_y() > 0.0 { 0.9 } else { 1.1 };
-            state.zoom_level.update(|z| *z = (*z * delta).clamp(0.02, 100.0));
+            state.zoom_level.update(|z| *z = (*z * delta).clamp(0.02, state.max_zoom().min(100.0)));
         } else {
             let raw_delta = ev.delta_y() + ev.delta_x();
             let files = state.files.get_untracked();
@@ -227,8 +227,8 @@ pub fn ChromagramView() -> impl IntoView {
             };
             let delta = raw_delta.signum() * visible_time * 0.1 * (raw_delta.abs() / 100.0).min(3.0);
             state.suspend_follow();
-            let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
-            state.scroll_offset.update(|s| *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here_mode));
+            let from_here = state.from_here_fraction();
+            state.scroll_offset.update(|s| *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here));
         }
     };
 
@@ -254,10 +254,10 @@ pub fn ChromagramView() -> impl IntoView {
         let zoom = state.zoom_level.get_untracked();
         let visible_time = viewport::visible_time(cw, zoom, time_res);
         let duration = file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(0.0);
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
         let dt = -(dx / cw) * visible_time;
         state.suspend_follow();
-        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode));
+        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here));
     };
 
     let on_mouseup = move |ev: MouseEvent| {
@@ -297,7 +297,7 @@ pub fn ChromagramView() -> impl IntoView {
                     initial_mid_client_x: mid_x,
                     time_res,
                     duration,
-                    from_here_mode: state.play_start_mode.get_untracked() .uses_from_here(),
+                    from_here: state.from_here_fraction(),
                 }));
             }
             state.is_dragging.set(false);
@@ -328,7 +328,7 @@ pub fn ChromagramView() -> impl IntoView {
                     let canvas: &HtmlCanvasElement = canvas_el.as_ref();
                     let rect = canvas.get_bounding_client_rect();
                     let cw = canvas.width() as f64;
-                    let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw);
+                    let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw, state.max_zoom());
                     state.suspend_follow();
                     state.zoom_level.set(new_zoom);
                     state.scroll_offset.set(new_scroll);
@@ -353,10 +353,10 @@ pub fn ChromagramView() -> impl IntoView {
         let zoom = state.zoom_level.get_untracked();
         let visible_time = viewport::visible_time(cw, zoom, time_res);
         let duration = file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(0.0);
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
         let dt = -(dx / cw) * visible_time;
         state.suspend_follow();
-        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode));
+        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here));
     };
 
     let on_touchend = move |_ev: web_sys::TouchEvent| {
@@ -61,6 +61,21 @@ pub fn DebugPanel() -> impl IntoView {
                     "Debug tiles"
                 </label>
             </div>
+            <div class="setting-row" style="padding: 4px 8px;"
+                title="Show the raw STFT magnitude, display dB, greyscale value and colour of the bin under the cursor, to check gain, floor and gamma mappings">
+                <label class="setting-label" style="display:flex;align-items:center;gap:4px;cursor:pointer">
+                    <input
+                        type="checkbox"
+                        prop:checked=move || state.debug_pixel_inspect.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            state.debug_pixel_inspect.set(input.checked());
+                        }
+                    />
+                    "Pixel inspect"
+                </label>
+            </div>
             <hr style="border-color: #444; margin: 4px 0;" />
             // Focus Stack visualization
             <div class="debug-focus-stack">
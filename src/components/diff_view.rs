@@ -0,0 +1,324 @@
+use leptos::prelude::*;
+use leptos::ev::MouseEvent;
+use wasm_bindgen_futures::spawn_local;
+use wasm_bindgen::{Clamped, JsCast};
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData};
+use crate::audio::source::ChannelView;
+use crate::canvas::colors::{db_to_greyscale, diff_rgb};
+use crate::canvas::spectrogram_renderer;
+use crate::components::gutter::TimeGutter;
+use crate::components::playhead::Playhead;
+use crate::dsp::resample::resample_sinc;
+use crate::dsp::spectral_diff::{spectral_diff, SpectralDiff, DIFF_FLOOR_DBFS};
+use crate::state::{AppState, CanvasTool, FileKey};
+use crate::viewport;
+
+/// Most samples per file analysed for one frame; zoomed out further than
+/// this, the view asks to zoom in rather than stall the page.
+const MAX_DIFF_SAMPLES: usize = 8_000_000;
+
+/// Level range (dBFS) mapped to the grey base under the difference colours.
+const LEVEL_FLOOR_DBFS: f32 = -100.0;
+const LEVEL_RANGE_DB: f32 = 100.0;
+
+/// Preset colour scales for the Diff view: difference in dB at full colour.
+pub const DIFF_RANGE_PRESETS: [f32; 4] = [3.0, 12.0, 24.0, 48.0];
+
+/// The file compared against the current one: the chosen file if it is
+/// still loaded, otherwise the first other file.
+pub fn diff_partner(state: AppState) -> Option<usize> {
+    let current = state.current_file_index.get()?;
+    let n = state.files.with(|f| f.len());
+    state.diff_other_file.get()
+        .and_then(|key| state.file_index_by_key(&key))
+        .filter(|&i| i != current)
+        .or_else(|| (0..n).find(|&i| i != current))
+}
+
+/// What one difference frame was computed from.
+#[derive(Clone, Debug, PartialEq)]
+struct DiffRequest {
+    a: FileKey,
+    b: FileKey,
+    /// Start of the compared span in A's time, and its length in A samples.
+    t0: f64,
+    len: usize,
+    rate: u32,
+    fft_size: usize,
+    hop: usize,
+    /// B is read from `t0 + offset`.
+    offset: f64,
+}
+
+#[derive(Clone, Debug)]
+struct DiffFrame {
+    request: DiffRequest,
+    diff: SpectralDiff,
+}
+
+/// Read both spans (resampling B to A's rate) and take their difference.
+fn compute_diff(state: AppState, req: &DiffRequest) -> Option<SpectralDiff> {
+    let (a, b) = state.files.with_untracked(|files| {
+        let find = |key: &FileKey| files.iter().find(|f| f.key() == *key).map(|f| f.audio.clone());
+        Some((find(&req.a)?, find(&req.b)?))
+    })?;
+    let rate = req.rate as f64;
+    let samples_a = a.source.read_region(ChannelView::MonoMix, (req.t0 * rate) as u64, req.len);
+    let rate_b = b.sample_rate as f64;
+    let start_b = req.t0 + req.offset;
+    // B starting after A's span begins: pad its front with silence
+    let lead = ((-start_b).max(0.0) * rate) as usize;
+    let len_b = ((req.len - lead.min(req.len)) as f64 * rate_b / rate) as usize;
+    let raw = b.source.read_region(ChannelView::MonoMix, (start_b.max(0.0) * rate_b) as u64, len_b);
+    let mut samples_b = vec![0.0; lead];
+    if b.sample_rate == req.rate {
+        samples_b.extend(raw);
+    } else {
+        samples_b.extend(resample_sinc(&raw, rate_b, rate));
+    }
+    Some(spectral_diff(&samples_a, &samples_b, req.rate, req.fft_size, req.hop))
+}
+
+/// Magnitude difference spectrogram of the current file (A) minus another
+/// loaded file (B), aligned at their starts, over the visible time range.
+#[component]
+pub fn DiffView() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+    let hand_drag_start = RwSignal::new((0.0f64, 0.0f64));
+    // Latest computed frame, and the request being computed. The canvas keeps
+    // showing the last frame (placed by its own span) until the next lands.
+    let frame: RwSignal<Option<DiffFrame>> = RwSignal::new(None);
+    let pending: StoredValue<Option<DiffRequest>> = StoredValue::new(None);
+
+    Effect::new(move || {
+        let scroll = state.scroll_offset.get();
+        let zoom = state.zoom_level.get();
+        let range_db = state.diff_range_db.get();
+        let offset = state.diff_offset_secs.get();
+        let fft_size = state.spect_fft_mode.get().fft_for_lod(1);
+        spectrogram_renderer::set_background_color(state.spectrogram_background.get());
+        let files = state.files.get();
+        let idx = state.current_file_index.get();
+        let other = diff_partner(state);
+        let _flipped = state.freq_axis_flipped.get();
+        // Re-read canvas dimensions when sidebar layout changes
+        let _sidebar = state.sidebar_collapsed.get();
+        let _sidebar_width = state.sidebar_width.get();
+        let _rsidebar = state.right_sidebar_collapsed.get();
+        let _rsidebar_width = state.right_sidebar_width.get();
+
+        let Some(canvas_el) = canvas_ref.get() else { return };
+        let canvas: &HtmlCanvasElement = canvas_el.as_ref();
+
+        let rect = canvas.get_bounding_client_rect();
+        let display_w = rect.width() as u32;
+        let display_h = rect.height() as u32;
+        if display_w == 0 || display_h == 0 { return; }
+        if canvas.width() != display_w || canvas.height() != display_h {
+            canvas.set_width(display_w);
+            canvas.set_height(display_h);
+        }
+        state.spectrogram_canvas_width.set(display_w as f64);
+
+        let ctx = canvas
+            .get_context("2d").unwrap().unwrap()
+            .dyn_into::<CanvasRenderingContext2d>().unwrap();
+        let (w, h) = (display_w as f64, display_h as f64);
+        spectrogram_renderer::clear_background(&ctx, w, h);
+
+        let Some(a) = idx.and_then(|i| files.get(i)) else { return };
+        let Some(b) = other.and_then(|i| files.get(i)) else {
+            draw_notes(&ctx, &["Load a second file to compare against (B)".to_string()]);
+            return;
+        };
+
+        let rate = a.audio.sample_rate;
+        let rate_b = b.audio.sample_rate;
+        let mut notes = vec![format!("A − B: {} − {}", a.name, b.name)];
+        if rate_b != rate {
+            notes.push(format!("B resampled {:.1} → {:.1} kHz to match A", rate_b as f64 / 1000.0, rate as f64 / 1000.0));
+        }
+        if offset != 0.0 {
+            notes.push(format!("B shifted {:+.1} ms", offset * 1000.0));
+        }
+        let (dur_a, dur_b) = (a.audio.duration_secs, b.audio.duration_secs);
+        let overlap_end = dur_a.min(dur_b - offset);
+        if (dur_a - (dur_b - offset)).abs() > 1.0 / rate as f64 {
+            notes.push(format!("Lengths differ (A {dur_a:.3} s, B {dur_b:.3} s): comparing up to {overlap_end:.3} s"));
+        }
+
+        let visible_time = viewport::visible_time(w, zoom, a.spectrogram.time_resolution);
+        let t0 = scroll.max(0.0);
+        let t1 = (scroll + visible_time).min(overlap_end);
+        let len = ((t1 - t0) * rate as f64).max(0.0) as usize;
+        if len > MAX_DIFF_SAMPLES {
+            notes.push("Zoom in to compare".to_string());
+            draw_notes(&ctx, &notes);
+            return;
+        }
+        if len < fft_size {
+            draw_notes(&ctx, &notes);
+            return;
+        }
+
+        // About one STFT column per pixel, never sparser than the window overlap
+        let hop = (len / display_w as usize).max(fft_size / 4).max(1);
+        let request = DiffRequest { a: a.key(), b: b.key(), t0, len, rate, fft_size, hop, offset };
+
+        // Compute off the render path; the finished frame re-runs this Effect
+        let current = frame.with(|f| f.as_ref().is_some_and(|f| f.request == request));
+        if !current && pending.with_value(|p| p.as_ref() != Some(&request)) {
+            pending.set_value(Some(request.clone()));
+            spawn_local(async move {
+                crate::canvas::tile_cache::yield_to_browser().await;
+                if pending.with_value(|p| p.as_ref() != Some(&request)) {
+                    return; // superseded while waiting
+                }
+                if let Some(diff) = compute_diff(state, &request) {
+                    frame.set(Some(DiffFrame { request, diff }));
+                }
+                pending.set_value(None);
+            });
+        }
+
+        // Draw the latest frame for this pair, even if it covers an older span
+        let drawn = frame.with(|f| {
+            let f = f.as_ref().filter(|f| f.request.a == request.a && f.request.b == request.b)?;
+            (f.diff.columns > 0).then(|| draw_diff(&ctx, f, display_w, display_h, scroll, visible_time, range_db))
+        });
+        if drawn.is_none() {
+            notes.push("Computing\u{2026}".to_string());
+            draw_notes(&ctx, &notes);
+            return;
+        }
+
+        notes.push(format!(
+            "red = A louder, blue = B louder, full colour at ±{range_db:.0} dB · RMS difference {:.1} dB",
+            frame.with(|f| f.as_ref().map_or(0.0, |f| f.diff.rms_diff_db())),
+        ));
+        draw_notes(&ctx, &notes);
+    });
+
+    let on_wheel = move |ev: web_sys::WheelEvent| {
+        ev.prevent_default();
+        if ev.ctrl_key() {
+            let delta = if ev.delta_y() > 0.0 { 0.9 } else { 1.1 };
+            state.zoom_level.update(|z| *z = (*z * delta).clamp(0.02, state.max_zoom().min(100.0)));
+        } else {
+            let raw_delta = ev.delta_y() + ev.delta_x();
+            let files = state.files.get_untracked();
+            let idx = state.current_file_index.get_untracked().unwrap_or(0);
+            let Some(file) = files.get(idx) else { return };
+            let zoom = state.zoom_level.get_untracked();
+            let canvas_w = state.spectrogram_canvas_width.get_untracked();
+            let visible_time = viewport::visible_time(canvas_w, zoom, file.spectrogram.time_resolution);
+            let duration = file.audio.duration_secs;
+            let delta = raw_delta.signum() * visible_time * 0.1 * (raw_delta.abs() / 100.0).min(3.0);
+            state.suspend_follow();
+            let from_here = state.from_here_fraction();
+            state.scroll_offset.update(|s| *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here));
+        }
+    };
+
+    let on_mousedown = move |ev: MouseEvent| {
+        if ev.button() != 0 { return; }
+        if state.canvas_tool.get_untracked() != CanvasTool::Hand { return; }
+        state.is_dragging.set(true);
+        hand_drag_start.set((ev.client_x() as f64, state.scroll_offset.get_untracked()));
+    };
+
+    let on_mousemove = move |ev: MouseEvent| {
+        if !state.is_dragging.get_untracked() { return; }
+        let (start_client_x, start_scroll) = hand_drag_start.get_untracked();
+        let dx = ev.client_x() as f64 - start_client_x;
+        let cw = state.spectrogram_canvas_width.get_untracked();
+        if cw == 0.0 { return; }
+        let files = state.files.get_untracked();
+        let Some(file) = state.current_file_index.get_untracked().and_then(|i| files.get(i)) else { return };
+        let zoom = state.zoom_level.get_untracked();
+        let visible_time = viewport::visible_time(cw, zoom, file.spectrogram.time_resolution);
+        let from_here = state.from_here_fraction();
+        let dt = -(dx / cw) * visible_time;
+        state.suspend_follow();
+        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, file.audio.duration_secs, visible_time, from_here));
+    };
+
+    let on_mouseup = move |_ev: MouseEvent| state.is_dragging.set(false);
+
+    view! {
+        <div class="spectrogram-container"
+            style=move || if state.is_dragging.get() { "cursor: grabbing;" } else { "cursor: grab;" }
+        >
+            <div class="chart-row">
+            <div class="chart-stage">
+            <canvas
+                node_ref=canvas_ref
+                on:wheel=on_wheel
+                on:mousedown=on_mousedown
+                on:mousemove=on_mousemove
+                on:mouseup=on_mouseup
+                on:mouseleave=on_mouseup
+            />
+            <Playhead/>
+            </div>
+            </div>
+            <div class="view-bottom-row">
+                <TimeGutter/>
+                <div class="view-bottom-corner"></div>
+            </div>
+        </div>
+    }
+}
+
+/// Paint `frame` over the visible span, placing its columns by the span
+/// they were computed for.
+fn draw_diff(
+    ctx: &CanvasRenderingContext2d,
+    frame: &DiffFrame,
+    display_w: u32,
+    display_h: u32,
+    scroll: f64,
+    visible_time: f64,
+    range_db: f32,
+) {
+    let DiffFrame { request: req, diff } = frame;
+    let h = display_h as f64;
+    let mut pixels = vec![0u8; (display_w * display_h * 4) as usize];
+    let secs_per_px = visible_time / display_w as f64;
+    let flipped = crate::viewport::freq_axis_flipped();
+    for x in 0..display_w as usize {
+        // Column whose window centre is nearest this pixel
+        let s = (scroll + x as f64 * secs_per_px - req.t0) * req.rate as f64 - req.fft_size as f64 / 2.0;
+        let col = (s / req.hop as f64).round();
+        if col < 0.0 || col as usize >= diff.columns { continue; }
+        let col = col as usize;
+        for y in 0..display_h as usize {
+            let frac = if flipped { (y as f64 + 0.5) / h } else { 1.0 - (y as f64 + 0.5) / h };
+            let bin = ((frac * (diff.bins - 1) as f64).round() as usize).min(diff.bins - 1);
+            let level = diff.level_at(col, bin);
+            let grey = db_to_greyscale(level, LEVEL_FLOOR_DBFS, LEVEL_RANGE_DB, 1.0, 0.0);
+            let d = if level > DIFF_FLOOR_DBFS { diff.diff_at(col, bin) / range_db } else { 0.0 };
+            let [r, g, bl] = diff_rgb(grey, d);
+            let i = (y * display_w as usize + x) * 4;
+            pixels[i..i + 4].copy_from_slice(&[r, g, bl, 255]);
+        }
+    }
+    if let Ok(img) = ImageData::new_with_u8_clamped_array_and_sh(Clamped(&pixels), display_w, display_h) {
+        let _ = ctx.put_image_data(&img, 0.0, 0.0);
+    }
+}
+
+/// Caption lines in the top-left corner.
+fn draw_notes(ctx: &CanvasRenderingContext2d, notes: &[String]) {
+    ctx.set_font("11px sans-serif");
+    ctx.set_text_baseline("top");
+    for (i, note) in notes.iter().enumerate() {
+        let y = 4.0 + i as f64 * 15.0;
+        let width = ctx.measure_text(note).map(|m| m.width()).unwrap_or(0.0);
+        ctx.set_fill_style_str("rgba(0, 0, 0, 0.6)");
+        ctx.fill_rect(2.0, y - 1.0, width + 8.0, 14.0);
+        ctx.set_fill_style_str("#ccc");
+        let _ = ctx.fill_text(note, 6.0, y);
+    }
+}
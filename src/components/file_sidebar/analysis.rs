@@ -1,12 +1,41 @@
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
-use crate::audio::source::{ChannelView, DEFAULT_ANALYSIS_WINDOW_SECS};
-use crate::state::{AppState, RightSidebarTab};
-use crate::dsp::bit_analysis::{self, BitAnalysis, BitCaution};
-use crate::dsp::wsnr;
+use crate::audio::source::{AudioSource, ChannelView, DEFAULT_ANALYSIS_WINDOW_SECS};
+use crate::state::{AppState, GainMode, RightSidebarTab};
+use super::band_activity::BandActivitySection;
+use crate::dsp::bit_analysis::{self, BitAnalysis, BitCaution, NoiseFloorMethod, NoiseFloorParams};
+use crate::dsp::{call_params, dc, declip, wsnr};
+use crate::dsp::species_hint::{self, CallSummary, SpeciesTable};
 use std::sync::Arc;
 
+const SPECIES_TABLE_KEY: &str = "oversample_species_table";
+
+/// The user's species table JSON, or the built-in one if none is saved.
+fn load_species_table_json() -> String {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|ls| ls.get_item(SPECIES_TABLE_KEY).ok().flatten())
+        .unwrap_or_else(|| species_hint::DEFAULT_TABLE_JSON.to_string())
+}
+
+/// Persist the species table JSON; None restores the built-in table.
+fn persist_species_table_json(json: Option<&str>) {
+    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = match json {
+            Some(j) => ls.set_item(SPECIES_TABLE_KEY, j),
+            None => ls.remove_item(SPECIES_TABLE_KEY),
+        };
+    }
+}
+
+/// Seconds of silence trimmed on import from the start and end, if any.
+fn import_trim(audio: &crate::types::AudioData) -> Option<(f64, f64)> {
+    let lead = audio.trim_offset_secs();
+    let tail = audio.untrimmed_source().duration_secs() - lead - audio.source.duration_secs();
+    (audio.untrimmed_source().total_samples() != audio.source.total_samples()).then_some((lead, tail.max(0.0)))
+}
+
 #[component]
 pub(crate) fn AnalysisPanel() -> impl IntoView {
     let state = expect_context::<AppState>();
@@ -21,6 +50,13 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
     let analysis_is_full = RwSignal::new(false);
     // Whether the file is longer than the analysis window.
     let file_is_long = RwSignal::new(false);
+    // Median call parameters for the species hint. Outer None = not computed;
+    // inner None = no measurable calls.
+    let call_summary: RwSignal<Option<Option<CallSummary>>> = RwSignal::new(None);
+    let species_table_json = RwSignal::new(load_species_table_json());
+    let species_table = Memo::new(move |_| SpeciesTable::from_json(&species_table_json.get()));
+    let editing_species_table = RwSignal::new(false);
+    let species_table_error: RwSignal<Option<String>> = RwSignal::new(None);
 
     // Run analysis (default: first 30s; full_file=true for full scan)
     let run_analysis = move |full_file: bool| {
@@ -31,6 +67,7 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
 
         analysis.set(None);
         wsnr_result.set(None);
+        call_summary.set(None);
         is_computing.set(true);
         last_computed_idx.set(idx);
         analysis_is_full.set(full_file);
@@ -50,20 +87,29 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
         let is_long = total > max_samples;
         file_is_long.set(is_long);
 
-        let samples: Arc<Vec<f32>> = if full_file || !is_long {
+        let mut samples = if full_file || !is_long {
             analysis_is_full.set(true);
-            Arc::new(file.audio.source.read_region(ChannelView::MonoMix, 0, total))
+            file.audio.source.read_region(ChannelView::MonoMix, 0, total)
         } else {
-            Arc::new(file.audio.source.read_region(ChannelView::MonoMix, 0, max_samples))
+            file.audio.source.read_region(ChannelView::MonoMix, 0, max_samples)
         };
+        // Analyse the DC-corrected working copy if the user removed DC
+        if let Some(offset) = file.settings.dc_offset {
+            dc::remove_dc(&mut samples, offset);
+        }
+        let samples: Arc<Vec<f32>> = Arc::new(samples);
         let duration_secs = samples.len() as f64 / sample_rate as f64;
 
         spawn_local(async move {
             yield_to_browser().await;
             if compute_gen.get_untracked() != generation { return; }
 
-            let bits_result = bit_analysis::analyze_bits(
-                &samples, bits_per_sample, is_float, duration_secs,
+            let noise = NoiseFloorParams {
+                method: state.noise_floor_method.get_untracked(),
+                silence_threshold_db: state.noise_floor_silence_db.get_untracked(),
+            };
+            let bits_result = bit_analysis::analyze_bits_with(
+                &samples, bits_per_sample, is_float, duration_secs, &noise,
             );
             if compute_gen.get_untracked() != generation { return; }
             analysis.set(Some(bits_result));
@@ -75,6 +121,15 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
             if compute_gen.get_untracked() != generation { return; }
             wsnr_result.set(Some(wsnr_res));
 
+            if state.species_hint_enabled.get_untracked() {
+                yield_to_browser().await;
+                if compute_gen.get_untracked() != generation { return; }
+
+                let calls = call_params::measure_calls(&samples, sample_rate, &state.pulse_detection_params());
+                if compute_gen.get_untracked() != generation { return; }
+                call_summary.set(Some(species_hint::summarize(&calls)));
+            }
+
             is_computing.set(false);
         });
     };
@@ -150,6 +205,36 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                 "\nFile\n  Sample rate: {}\n  Channels: {}\n  Bit depth: {}\n  Duration: {}\n  Samples: {}\n",
                 sr_text, ch_text, bit_text, dur_text, total_samples
             ));
+            if let Some((lead, tail)) = import_trim(&f.audio) {
+                report.push_str(&format!(
+                    "  Silence trimmed on import: {:.2}s from start, {:.2}s from end (exports use the original)\n",
+                    lead, tail,
+                ));
+            }
+
+            // Baseline LOD rather than the current zoom, so zooming doesn't
+            // rebuild the whole report
+            {
+                let p = crate::state::SpectParams::for_lod(
+                    state.spect_fft_mode.get(),
+                    crate::canvas::tile_cache::LOD_BASELINE,
+                    f.spectrogram.sample_rate,
+                );
+                report.push_str(&format!(
+                    "\nSpectrogram (default zoom)\n  FFT size: {}\n  Window: {}, {:.2} ms\n  Hop: {} samples ({:.2} ms)\n  Frequency resolution: {:.1} Hz\n",
+                    p.fft_size,
+                    crate::state::SpectParams::WINDOW,
+                    p.window_secs() * 1000.0,
+                    p.hop,
+                    p.time_resolution() * 1000.0,
+                    p.freq_resolution(),
+                ));
+            }
+
+            let notes = f.notes.trim();
+            if !notes.is_empty() {
+                report.push_str(&format!("\nNotes\n  {}\n", notes.replace('\n', "\n  ")));
+            }
 
             // Signal stats — scan first 30s only for large files
             let max_scan = (DEFAULT_ANALYSIS_WINDOW_SECS * f.audio.sample_rate as f64) as usize;
@@ -177,6 +262,19 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                     "\nSignal\n  Min: {:.4} ({})\n  Max: {:.4} ({})\n  RMS: {}\n  DC bias: {}\n",
                     smin, min_db, smax, max_db, rms_db, dc_db
                 ));
+                if f.settings.dc_offset.is_some() {
+                    report.push_str("  DC removed for analysis and playback\n");
+                }
+                if f.settings.declip {
+                    report.push_str(&format!(
+                        "  Clipping: {} samples reconstructed for display and playback (analysis uses the original)\n",
+                        declip::count_clipped(&smp, declip::CLIP_LEVEL),
+                    ));
+                }
+                let gain = state.gain_db.get();
+                if state.gain_mode.get() == GainMode::Manual && gain != 0.0 {
+                    report.push_str(&format!("  Manual gain: {:+.1} dB\n", gain));
+                }
             }
         }
 
@@ -195,6 +293,39 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
             }
         }
 
+        // Per-pulse SNR (only if pulse detection has been run for this file)
+        let pulses = state.detected_pulses.get();
+        if !pulses.is_empty() {
+            let usable = pulses.iter().filter(|p| p.is_usable()).count();
+            report.push_str(&format!(
+                "\nPulses: {} ({} with local SNR \u{2265} {:.0} dB)\n  #    Start (s)  Dur (ms)  Peak (kHz)  SNR (dB)  Local SNR (dB)\n",
+                pulses.len(), usable, crate::dsp::pulse_detect::USABLE_PULSE_SNR_DB
+            ));
+            for p in &pulses {
+                report.push_str(&format!(
+                    "  {:<4} {:>9.4}  {:>8.2}  {:>10.1}  {:>8.1}  {:>14.1}{}\n",
+                    p.index, p.start_time, p.duration_ms(), p.peak_freq / 1000.0, p.snr_db, p.local_snr_db,
+                    if p.is_usable() { "" } else { "  faint" }
+                ));
+            }
+        }
+
+        // Species hint
+        if let (Some(Some(s)), Ok(table)) = (call_summary.get(), species_table.get()) {
+            report.push_str(&format!(
+                "\nSpecies Hint (rule-based, {} calls)\n  Median Fc {:.1} kHz, bandwidth {:.1} kHz, duration {:.1} ms{}\n",
+                s.call_count, s.char_freq_khz, s.bandwidth_khz, s.duration_ms,
+                s.interval_ms.map(|iv| format!(", interval {:.0} ms", iv)).unwrap_or_default(),
+            ));
+            let candidates = species_hint::match_species(&s, &table);
+            if candidates.is_empty() {
+                report.push_str("  No match in table\n");
+            }
+            for c in &candidates {
+                report.push_str(&format!("  {} ({:.0}%, {})\n", c.name, c.confidence * 100.0, c.confidence_label()));
+            }
+        }
+
         // Bit analysis
         if let Some(ref a) = analysis.get() {
             let total = a.total_samples;
@@ -240,7 +371,7 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
             {
                 let nf_db = a.noise_floor_db;
                 let nf_bits = -nf_db / (20.0 * 2f64.log10());
-                report.push_str(&format!("  Noise floor: {:.1} dBFS (~{:.1} bits)\n", nf_db, nf_bits));
+                report.push_str(&format!("  Noise floor: {:.1} dBFS (~{:.1} bits, {})\n", nf_db, nf_bits, a.noise_floor_method.label().to_lowercase()));
             }
             report.push_str(&split_line);
 
@@ -321,6 +452,7 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                         let total_samples = f.audio.source.total_samples() as usize;
                         let dur_text = crate::format_time::format_duration(f.audio.duration_secs, 3);
                         let samples_text = format!("{}", total_samples);
+                        let trim = import_trim(&f.audio);
 
                         // Signal stats — scan first 30s only for large files
                         let max_scan = (DEFAULT_ANALYSIS_WINDOW_SECS * f.audio.sample_rate as f64) as usize;
@@ -349,13 +481,41 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                         let dc_raw_tooltip = format!("{:.6} (raw)", dc_bias);
                         // DC relative to RMS: gives perceptual sense of DC severity
                         let dc_rms_ratio = if rms > 0.0 { dc_bias.abs() / rms } else { 0.0 };
-                        // Warning: notable DC if |dc| > 1% of full scale OR dc/rms > 5%, gated on N
-                        let dc_notable = len > 10_000 && (dc_bias.abs() > 0.01 || dc_rms_ratio > 0.05);
-                        let dc_warning = if dc_notable {
+                        let dc_notable = dc::dc_offset_notable(
+                            dc_bias, rms, len,
+                            state.dc_warn_full_scale.get(),
+                            state.dc_warn_rms_ratio.get(),
+                        );
+                        let dc_removed = f.settings.dc_offset.is_some();
+                        let clipped = declip::count_clipped(&samples, declip::CLIP_LEVEL);
+                        let clipped_scope = if scan_len < total_samples { " in first 30s" } else { "" };
+                        let declip_on = f.settings.declip;
+                        let dc_warning = if dc_notable && !dc_removed {
                             Some(format!("DC offset: {} \u{2014} {:.0}% of RMS level", dc_db, dc_rms_ratio * 100.0))
                         } else {
                             None
                         };
+                        // Store (or clear) the correction on the file, then re-run analysis on it
+                        let set_dc_offset = move |offset: Option<f32>| {
+                            let Some(i) = idx else { return };
+                            state.files.update(|files| {
+                                if let Some(file) = files.get_mut(i) {
+                                    file.settings.dc_offset = offset;
+                                }
+                            });
+                            crate::file_corrections::save_current(state);
+                            run_analysis(analysis_is_full.get_untracked());
+                        };
+                        // Analysis keeps the original samples, so no re-run here
+                        let set_declip = move |on: bool| {
+                            let Some(i) = idx else { return };
+                            state.files.update(|files| {
+                                if let Some(file) = files.get_mut(i) {
+                                    file.settings.declip = on;
+                                }
+                            });
+                            crate::file_corrections::save_current(state);
+                        };
 
                         view! {
                             <div class="setting-group">
@@ -382,6 +542,14 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                                         <span class="analysis-stat-label">"Samples"</span>
                                     </div>
                                 </div>
+                                {trim.map(|(lead, tail)| view! {
+                                    <div class="analysis-scope-row">
+                                        <span
+                                            class="analysis-scope-badge"
+                                            title=format!("Silence trimmed on import: {:.2}s from the start, {:.2}s from the end. Times are relative to the trimmed audio; exports use the untrimmed original.", lead, tail)
+                                        >{format!("Trimmed {:.2}s", lead + tail)}</span>
+                                    </div>
+                                })}
                             </div>
                             <div class="setting-group">
                                 <div class="setting-group-title">"Signal"</div>
@@ -404,6 +572,68 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                                     </div>
                                 </div>
                                 {dc_warning.map(|w| view! { <div class="analysis-warning">{w}</div> })}
+                                {move || {
+                                    let gain = state.gain_db.get();
+                                    (state.gain_mode.get() == GainMode::Manual && gain != 0.0).then(|| view! {
+                                        <div class="analysis-scope-row">
+                                            <span
+                                                class="analysis-scope-badge"
+                                                title="Manual gain, remembered for this file and applied to display and playback"
+                                            >{format!("Gain {:+.1} dB", gain)}</span>
+                                        </div>
+                                    })
+                                }}
+                                {(len > 0).then(|| if dc_removed {
+                                    view! {
+                                        <div class="analysis-scope-row">
+                                            <span
+                                                class="analysis-scope-badge"
+                                                title="Remembered for this file"
+                                            >"DC removed"</span>
+                                            <button
+                                                class="analysis-full-btn"
+                                                title="Analyse and play the original samples again"
+                                                on:click=move |_| set_dc_offset(None)
+                                            >"Restore"</button>
+                                        </div>
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="analysis-scope-row">
+                                            <button
+                                                class="analysis-full-btn"
+                                                title="Subtract the mean from a working copy used for analysis, playback and export. The file is not modified."
+                                                on:click=move |_| set_dc_offset(Some(dc_bias as f32))
+                                            >"Remove DC"</button>
+                                        </div>
+                                    }.into_any()
+                                })}
+                                {(clipped > 0).then(|| if declip_on {
+                                    view! {
+                                        <div class="analysis-scope-row">
+                                            <span
+                                                class="analysis-scope-badge analysis-reconstructed"
+                                                title="Clipped runs are redrawn in the waveform and playback, with a limiter on the restored peaks. The spectrogram, analysis and export use the original samples."
+                                            >{format!("{} samples reconstructed{}", clipped, clipped_scope)}</span>
+                                            <button
+                                                class="analysis-full-btn"
+                                                title="Show and play the original clipped samples again"
+                                                on:click=move |_| set_declip(false)
+                                            >"Use original"</button>
+                                        </div>
+                                    }.into_any()
+                                } else {
+                                    view! {
+                                        <div class="analysis-scope-row">
+                                            <span class="analysis-scope-badge">{format!("{} clipped samples{}", clipped, clipped_scope)}</span>
+                                            <button
+                                                class="analysis-full-btn"
+                                                title="Interpolate across runs of full-scale samples in a working copy used for the waveform and playback. Lost detail can't be recovered; the spectrogram, analysis and export keep the original."
+                                                on:click=move |_| set_declip(true)
+                                            >"Restore clipping"</button>
+                                        </div>
+                                    }.into_any()
+                                })}
                             </div>
                         }.into_any()
                     }
@@ -507,6 +737,159 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                     }
                 }
             }}
+            // Species hint section
+            {move || {
+                if wsnr_result.get().is_none() {
+                    return view! { <span></span> }.into_any();
+                }
+                let enabled = state.species_hint_enabled.get();
+                let body = if !enabled {
+                    view! { <span></span> }.into_any()
+                } else {
+                    match (call_summary.get(), species_table.get()) {
+                        (_, Err(e)) => view! {
+                            <div class="analysis-warning">{format!("Species table error: {e}")}</div>
+                        }.into_any(),
+                        (None, _) => view! { <div class="wsnr-detail">"Measuring calls\u{2026}"</div> }.into_any(),
+                        (Some(None), _) => view! { <div class="wsnr-detail">"No calls detected"</div> }.into_any(),
+                        (Some(Some(s)), Ok(table)) => {
+                            let measured = format!(
+                                "Median of {} calls: Fc {:.1} kHz, BW {:.1} kHz, {:.1} ms{}",
+                                s.call_count, s.char_freq_khz, s.bandwidth_khz, s.duration_ms,
+                                s.interval_ms.map(|iv| format!(", every {:.0} ms", iv)).unwrap_or_default(),
+                            );
+                            let candidates = species_hint::match_species(&s, &table);
+                            let rows = if candidates.is_empty() {
+                                view! { <div class="wsnr-detail">"No match in table"</div> }.into_any()
+                            } else {
+                                candidates.into_iter().map(|c| {
+                                    let header = format!("{} \u{2014} {:.0}% ({})", c.name, c.confidence * 100.0, c.confidence_label());
+                                    let title = c.scientific_name.clone().unwrap_or_default();
+                                    let criteria: Vec<_> = c.criteria.iter().map(|m| {
+                                        let class = if m.matched { "species-criterion matched" } else { "species-criterion" };
+                                        let mark = if m.matched { "\u{2713} " } else { "\u{2717} " };
+                                        view! { <div class=class>{mark}{m.describe()}</div> }
+                                    }).collect();
+                                    view! {
+                                        <div class="species-candidate">
+                                            <div class="species-candidate-name" title=title>{header}</div>
+                                            {criteria}
+                                        </div>
+                                    }
+                                }).collect::<Vec<_>>().into_any()
+                            };
+                            let region = (!table.region.is_empty()).then(|| format!("Table: {}", table.region));
+                            // XC references need the desktop app's xeno-canto access
+                            let identify = (state.is_tauri && !state.offline_mode.get()).then(|| {
+                                let fc = s.char_freq_khz;
+                                view! {
+                                    <div class="analysis-scope-row">
+                                        <button
+                                            class="analysis-full-btn"
+                                            title="Fetch xeno-canto reference recordings of species with a similar characteristic frequency and compare each with this recording in the Diff view"
+                                            on:click=move |_| crate::components::xc_browser::start_identify(state, fc, &table)
+                                        >"Compare with XC references"</button>
+                                    </div>
+                                }
+                            });
+                            view! {
+                                <div class="wsnr-detail">{measured}</div>
+                                {rows}
+                                {region.map(|r| view! { <div class="wsnr-detail">{r}</div> })}
+                                {identify}
+                            }.into_any()
+                        }
+                    }
+                };
+                let on_toggle = move |ev: web_sys::Event| {
+                    let target = ev.target().unwrap();
+                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                    let on = input.checked();
+                    state.species_hint_enabled.set(on);
+                    if let Some(ls) = web_sys::window()
+                        .and_then(|w| w.local_storage().ok().flatten())
+                    {
+                        let _ = ls.set_item("oversample_species_hint", if on { "true" } else { "false" });
+                    }
+                    if on && call_summary.get_untracked().is_none() {
+                        run_analysis(analysis_is_full.get_untracked());
+                    }
+                };
+                let on_save_table = move |_: web_sys::MouseEvent| {
+                    let Some(textarea) = web_sys::window()
+                        .and_then(|w| w.document())
+                        .and_then(|d| d.get_element_by_id("species-table-input"))
+                    else { return };
+                    let json = textarea.unchecked_into::<web_sys::HtmlTextAreaElement>().value();
+                    match SpeciesTable::from_json(&json) {
+                        Ok(_) => {
+                            persist_species_table_json(Some(&json));
+                            species_table_json.set(json);
+                            species_table_error.set(None);
+                            editing_species_table.set(false);
+                        }
+                        Err(e) => species_table_error.set(Some(e)),
+                    }
+                };
+                let on_reset_table = move |_: web_sys::MouseEvent| {
+                    persist_species_table_json(None);
+                    species_table_json.set(species_hint::DEFAULT_TABLE_JSON.to_string());
+                    species_table_error.set(None);
+                    editing_species_table.set(false);
+                };
+                view! {
+                    <div class="setting-group">
+                        <div class="setting-group-title">"Species Hint"</div>
+                        <div class="setting-row"
+                            title="Compare the median call parameters with typical ranges per species. \
+                                   A rough heuristic to start an identification, not a classifier.">
+                            <span class="setting-label">"Suggest species"</span>
+                            <input
+                                type="checkbox"
+                                class="setting-checkbox"
+                                prop:checked=enabled
+                                on:change=on_toggle
+                            />
+                        </div>
+                        {enabled.then(|| view! {
+                            <div class="setting-hint">"Hint only \u{2014} check against reference calls before relying on it."</div>
+                        })}
+                        {body}
+                        {enabled.then(|| if editing_species_table.get() {
+                            view! {
+                                <textarea
+                                    id="species-table-input"
+                                    class="project-notes-input species-table-input"
+                                    spellcheck="false"
+                                >{species_table_json.get_untracked()}</textarea>
+                                {move || species_table_error.get().map(|e| view! { <div class="analysis-warning">{e}</div> })}
+                                <div class="analysis-scope-row">
+                                    <button class="analysis-full-btn" on:click=on_save_table>"Save"</button>
+                                    <button class="analysis-full-btn"
+                                        title="Discard the custom table and use the built-in one"
+                                        on:click=on_reset_table
+                                    >"Reset to built-in"</button>
+                                    <button class="analysis-full-btn"
+                                        on:click=move |_| {
+                                            species_table_error.set(None);
+                                            editing_species_table.set(false);
+                                        }
+                                    >"Cancel"</button>
+                                </div>
+                            }.into_any()
+                        } else {
+                            view! {
+                                <div class="analysis-scope-row">
+                                    <button class="analysis-full-btn"
+                                        title="Edit the species ranges (JSON: kHz and ms) to suit your region"
+                                        on:click=move |_| editing_species_table.set(true)
+                                    >"Edit table"</button>
+                                </div>
+                            }.into_any()
+                        })}
+                    </div>
+                }.into_any()
+            }}
             // Bit analysis section
             {move || {
                 match analysis.get().as_ref() {
@@ -649,7 +1032,12 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
 
                         let nf_bits = -noise_floor_db / (20.0 * 2f64.log10());
                         let noise_floor_text = format!("Noise floor: {:.1} dBFS (~{:.1} bits)", noise_floor_db, nf_bits);
-                        let noise_floor_tooltip = "Minimum RMS level of 512-sample windows above digital silence (−80 dBFS); converted to equivalent bit depth at 6 dB/bit".to_string();
+                        let silence_db = state.noise_floor_silence_db.get_untracked();
+                        let noise_floor_tooltip = match a.noise_floor_method {
+                            NoiseFloorMethod::WindowedRms => format!("Minimum RMS level of 512-sample windows above the silence threshold ({silence_db:.0} dBFS)"),
+                            NoiseFloorMethod::Percentile => format!("10th percentile RMS level of 512-sample windows above the silence threshold ({silence_db:.0} dBFS)"),
+                            NoiseFloorMethod::MinimumStatistics => format!("Median of local minima of smoothed 512-sample window power above the silence threshold ({silence_db:.0} dBFS); suits recordings without true silence"),
+                        } + "; converted to equivalent bit depth at 6 dB/bit";
 
                         view! {
                             <div class="setting-group">
@@ -705,6 +1093,38 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                                     }.into_any()
                                 }}
                                 <div class="bit-depth-stat" title=noise_floor_tooltip>{noise_floor_text}</div>
+                                <div class="setting-row">
+                                    <span class="setting-label">"Noise floor"</span>
+                                    <select class="setting-select"
+                                        on:change=move |ev| {
+                                            let Some(method) = NoiseFloorMethod::from_key(&event_target_value(&ev)) else { return };
+                                            state.noise_floor_method.set(method);
+                                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                                let _ = ls.set_item("oversample_noise_floor_method", method.key());
+                                            }
+                                            run_analysis(analysis_is_full.get_untracked());
+                                        }
+                                    >
+                                        {NoiseFloorMethod::ALL.into_iter().map(|m| view! {
+                                            <option value=m.key() selected=move || state.noise_floor_method.get() == m>{m.label()}</option>
+                                        }).collect_view()}
+                                    </select>
+                                </div>
+                                <div class="setting-row" title="Windows at or below this level count as silence and are left out of the noise floor estimate">
+                                    <span class="setting-label">"Silence below (dBFS)"</span>
+                                    <input type="number" class="setting-number" min="-140" max="-20" step="5"
+                                        prop:value=move || format!("{:.0}", state.noise_floor_silence_db.get())
+                                        on:change=move |ev| {
+                                            let Ok(db) = event_target_value(&ev).parse::<f64>() else { return };
+                                            let db = db.clamp(-140.0, -20.0);
+                                            state.noise_floor_silence_db.set(db);
+                                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                                let _ = ls.set_item("oversample_noise_floor_silence_db", &db.to_string());
+                                            }
+                                            run_analysis(analysis_is_full.get_untracked());
+                                        }
+                                    />
+                                </div>
                                 <div class=if is_asymmetric { "bit-warning" } else { "bit-depth-stat" } title=split_tooltip>{split_text}</div>
                                 {warning_items}
                                 <div class="bit-sign-header" title=pos_tooltip>{format!("Samples above zero ({})", pos_pct)}</div>
@@ -721,6 +1141,7 @@ pub(crate) fn AnalysisPanel() -> impl IntoView {
                     }
                 }
             }}
+            <BandActivitySection />
         </div>
     }
 }
@@ -0,0 +1,364 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use wasm_bindgen_futures::spawn_local;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use crate::audio::source::{AudioSource, ChannelView};
+use crate::canvas::tile_cache::yield_to_browser;
+use crate::dsp::band_activity::{self, BandPoint, BandSummary};
+use crate::dsp::dc;
+use crate::state::AppState;
+
+/// Most points drawn and exported; longer files use longer steps.
+const MAX_POINTS: usize = 2000;
+/// Steps read and analysed between yields to the browser.
+const STEPS_PER_BLOCK: usize = 50;
+/// Chart margins either side of the plot, shared by drawing and click-to-seek.
+const MARGIN_LEFT: f64 = 30.0;
+const MARGIN_RIGHT: f64 = 6.0;
+
+/// A finished band track for one file.
+#[derive(Clone)]
+struct BandTrack {
+    file_idx: usize,
+    freq_low: f64,
+    freq_high: f64,
+    points: Vec<BandPoint>,
+    summary: BandSummary,
+}
+
+/// Frequency band of the current selection or selected annotation, if it has one.
+fn selection_band(state: &AppState) -> Option<(f64, f64)> {
+    let sel = crate::audio::playback::effective_selection(state)?;
+    match (sel.freq_low, sel.freq_high) {
+        (Some(lo), Some(hi)) if hi > lo => Some((lo, hi)),
+        _ => None,
+    }
+}
+
+/// Energy in one frequency band over the whole current file, as a timeline
+/// chart with totals and a CSV export. Shown at the foot of the Analysis tab.
+#[component]
+pub(crate) fn BandActivitySection() -> impl IntoView {
+    let state = expect_context::<AppState>();
+
+    // Band in Hz; starts from the focus band, else the selection's band
+    let (init_lo, init_hi) = if state.hfr_enabled.get_untracked()
+        && state.band_ff_freq_hi.get_untracked() > state.band_ff_freq_lo.get_untracked()
+    {
+        (state.band_ff_freq_lo.get_untracked(), state.band_ff_freq_hi.get_untracked())
+    } else {
+        selection_band(&state).unwrap_or((0.0, 0.0))
+    };
+    let freq_low = RwSignal::new(init_lo);
+    let freq_high = RwSignal::new(init_hi);
+    let track: RwSignal<Option<BandTrack>> = RwSignal::new(None);
+    let progress: RwSignal<Option<f64>> = RwSignal::new(None);
+    let compute_gen = RwSignal::new(0u32);
+
+    // A new file invalidates the track and stops any scan in progress
+    Effect::new(move || {
+        let idx = state.current_file_index.get();
+        if track.with_untracked(|t| t.as_ref().is_some_and(|t| Some(t.file_idx) != idx)) {
+            track.set(None);
+        }
+        if progress.get_untracked().is_some() {
+            compute_gen.update(|g| *g += 1);
+            progress.set(None);
+        }
+    });
+
+    let run = move |_| {
+        let (lo, hi) = (freq_low.get_untracked(), freq_high.get_untracked());
+        if hi <= lo {
+            state.show_error_toast("Set a frequency band first");
+            return;
+        }
+        let Some(idx) = state.current_file_index.get_untracked() else { return };
+        let Some(file) = state.files.with_untracked(|files| files.get(idx).cloned()) else { return };
+        let sr = file.audio.sample_rate;
+        let total = file.audio.source.total_samples() as usize;
+        if total == 0 || sr == 0 {
+            return;
+        }
+
+        compute_gen.update(|g| *g += 1);
+        let generation = compute_gen.get_untracked();
+        track.set(None);
+        progress.set(Some(0.0));
+
+        let step = band_activity::step_samples(total, sr, MAX_POINTS);
+        let block = step * STEPS_PER_BLOCK;
+        let dc_offset = file.settings.dc_offset;
+
+        spawn_local(async move {
+            let mut points = Vec::new();
+            let mut start = 0;
+            while start < total {
+                yield_to_browser().await;
+                if compute_gen.get_untracked() != generation { return; }
+                let len = block.min(total - start);
+                let mut samples = file.audio.source.read_region(ChannelView::MonoMix, start as u64, len);
+                if let Some(offset) = dc_offset {
+                    dc::remove_dc(&mut samples, offset);
+                }
+                points.extend(band_activity::band_activity(&samples, sr, lo, hi, step, start as f64 / sr as f64));
+                start += len;
+                progress.set(Some(start as f64 / total as f64));
+            }
+            progress.set(None);
+            if let Some(summary) = band_activity::summarize(&points) {
+                track.set(Some(BandTrack { file_idx: idx, freq_low: lo, freq_high: hi, points, summary }));
+            }
+        });
+    };
+
+    let export_csv = move |_| {
+        let Some(t) = track.get_untracked() else { return };
+        let (name, trim) = state.files.with_untracked(|files| {
+            files.get(t.file_idx).map(|f| (f.name.clone(), f.audio.trim_offset_secs()))
+        }).unwrap_or_default();
+        let stem = name.rsplit_once('.').map(|(s, _)| s).unwrap_or(&name);
+        // Times in the CSV are in original-file time
+        let points: Vec<_> = t.points.iter().map(|p| BandPoint { time_secs: p.time_secs + trim, ..*p }).collect();
+        let mut csv = String::from(band_activity::CSV_HEADER);
+        csv.push_str(&band_activity::csv_rows(&points));
+        super::project_panel::download_text(
+            &csv,
+            &format!("{stem}_band_{:.0}-{:.0}kHz.csv", t.freq_low / 1000.0, t.freq_high / 1000.0),
+        );
+    };
+
+    let khz_input = move |sig: RwSignal<f64>| view! {
+        <input type="number" class="setting-number" min="0" step="0.5"
+            prop:value=move || format!("{:.1}", sig.get() / 1000.0)
+            on:change=move |ev| {
+                let Ok(khz) = event_target_value(&ev).parse::<f64>() else { return };
+                sig.set(khz.max(0.0) * 1000.0);
+            }
+        />
+    };
+
+    view! {
+        <div class="setting-group">
+            <div class="setting-group-title">"Band Activity"</div>
+            <div class="setting-row" title="Frequency band to follow through the whole file">
+                <span class="setting-label">"Band (kHz)"</span>
+                {khz_input(freq_low)}
+                <span class="band-activity-dash">"\u{2013}"</span>
+                {khz_input(freq_high)}
+            </div>
+            <div class="analysis-scope-row">
+                <button
+                    class="analysis-full-btn"
+                    title="Use the Frequency Focus band"
+                    disabled=move || !state.hfr_enabled.get() || state.band_ff_freq_hi.get() <= state.band_ff_freq_lo.get()
+                    on:click=move |_| {
+                        freq_low.set(state.band_ff_freq_lo.get_untracked());
+                        freq_high.set(state.band_ff_freq_hi.get_untracked());
+                    }
+                >"Focus band"</button>
+                <button
+                    class="analysis-full-btn"
+                    title="Use the frequency band of the selection"
+                    disabled=move || selection_band(&state).is_none()
+                    on:click=move |_| {
+                        if let Some((lo, hi)) = selection_band(&state) {
+                            freq_low.set(lo);
+                            freq_high.set(hi);
+                        }
+                    }
+                >"Selection"</button>
+                <button
+                    class="analysis-full-btn"
+                    title="Measure the band's level through the whole file"
+                    disabled=move || progress.get().is_some()
+                    on:click=run
+                >"Analyze file"</button>
+            </div>
+            {move || progress.get().map(|p| view! {
+                <div class="sidebar-panel-empty">{format!("Scanning band\u{2026} {:.0}%", p * 100.0)}</div>
+            })}
+            {move || track.get().map(|t| {
+                let s = t.summary;
+                let band_text = format!("{:.1}\u{2013}{:.1} kHz", t.freq_low / 1000.0, t.freq_high / 1000.0);
+                let peak_tooltip = format!("Loudest step, at {}", crate::format_time::format_time_display(s.peak_time_secs, 1));
+                let active_tooltip = format!(
+                    "Share of the file at least {:.0} dB above the band's noise floor ({:.1} dBFS)",
+                    band_activity::ACTIVE_MARGIN_DB, s.noise_floor_db,
+                );
+                view! {
+                    <div class="psd-meta-text band-activity-meta">{band_text}</div>
+                    <BandChart points=t.points.clone() noise_floor_db=s.noise_floor_db />
+                    <div class="analysis-stats">
+                        <div class="analysis-stat">
+                            <span class="analysis-stat-value">{format!("{:.1} dB", s.total_energy_db)}</span>
+                            <span class="analysis-stat-label" title="Band energy summed over the file, relative to one second of a full-scale tone">"Total"</span>
+                        </div>
+                        <div class="analysis-stat">
+                            <span class="analysis-stat-value">{format!("{:.1} dB", s.peak_db)}</span>
+                            <span class="analysis-stat-label" title=peak_tooltip>"Peak"</span>
+                        </div>
+                        <div class="analysis-stat">
+                            <span class="analysis-stat-value">{format!("{:.1} dB", s.mean_db)}</span>
+                            <span class="analysis-stat-label" title="Mean band power over the file, in dBFS">"Mean"</span>
+                        </div>
+                        <div class="analysis-stat">
+                            <span class="analysis-stat-value">{format!("{:.0}%", s.active_fraction * 100.0)}</span>
+                            <span class="analysis-stat-label" title=active_tooltip>"Active"</span>
+                        </div>
+                    </div>
+                    <div class="analysis-scope-row">
+                        <button
+                            class="analysis-full-btn"
+                            title="Download the band level of every step as CSV"
+                            on:click=export_csv
+                        >"Export CSV"</button>
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
+
+fn draw_band_canvas(canvas: &HtmlCanvasElement, points: &[BandPoint], noise_floor_db: f32) {
+    let parent_width = canvas.parent_element()
+        .map(|p| p.client_width() as u32)
+        .unwrap_or(250);
+    // Skip drawing if parent has no real width yet (sidebar still opening)
+    if parent_width < 50 {
+        return;
+    }
+    let w = parent_width.max(150);
+    let h = 120u32;
+    canvas.set_width(w);
+    canvas.set_height(h);
+
+    let Some(ctx) = canvas.get_context("2d").ok().flatten()
+        .and_then(|c| c.dyn_into::<CanvasRenderingContext2d>().ok()) else { return };
+
+    ctx.set_fill_style_str("#111");
+    ctx.fill_rect(0.0, 0.0, w as f64, h as f64);
+    let (Some(first), Some(last)) = (points.first(), points.last()) else { return };
+
+    let (margin_left, margin_right) = (MARGIN_LEFT, MARGIN_RIGHT);
+    let margin_top = 6.0;
+    let margin_bottom = 16.0;
+    let chart_w = w as f64 - margin_left - margin_right;
+    let chart_h = h as f64 - margin_top - margin_bottom;
+
+    let t0 = first.time_secs;
+    let span = (last.time_secs - t0).max(1e-9);
+    let db_max = points.iter().map(|p| p.level_db).fold(f32::MIN, f32::max) as f64;
+    let db_min = (noise_floor_db as f64 - 6.0).min(db_max - 20.0);
+    let db_range = db_max - db_min;
+    let time_to_x = |t: f64| margin_left + (t - t0) / span * chart_w;
+    let db_to_y = |db: f64| margin_top + (1.0 - ((db - db_min) / db_range).clamp(0.0, 1.0)) * chart_h;
+
+    // dB grid
+    ctx.set_stroke_style_str("rgba(255,255,255,0.1)");
+    ctx.set_fill_style_str("#666");
+    ctx.set_font("9px monospace");
+    ctx.set_line_width(0.5);
+    let db_step = if db_range > 60.0 { 20.0 } else { 10.0 };
+    let mut db_tick = (db_min / db_step).ceil() * db_step;
+    while db_tick <= db_max {
+        let y = db_to_y(db_tick);
+        ctx.begin_path();
+        ctx.move_to(margin_left, y);
+        ctx.line_to(w as f64 - margin_right, y);
+        ctx.stroke();
+        let _ = ctx.fill_text(&format!("{:.0}", db_tick), 2.0, y + 3.0);
+        db_tick += db_step;
+    }
+
+    // Start and end times
+    let _ = ctx.fill_text(&crate::format_time::format_duration_compact(t0), margin_left, h as f64 - 4.0);
+    let end_label = crate::format_time::format_duration_compact(last.time_secs);
+    let end_w = ctx.measure_text(&end_label).map(|m| m.width()).unwrap_or(0.0);
+    let _ = ctx.fill_text(&end_label, w as f64 - margin_right - end_w, h as f64 - 4.0);
+
+    // Noise floor
+    ctx.set_stroke_style_str("rgba(255,200,50,0.4)");
+    ctx.begin_path();
+    ctx.move_to(margin_left, db_to_y(noise_floor_db as f64));
+    ctx.line_to(w as f64 - margin_right, db_to_y(noise_floor_db as f64));
+    ctx.stroke();
+
+    // Level, filled down to the floor of the chart
+    ctx.begin_path();
+    ctx.move_to(margin_left, margin_top + chart_h);
+    for p in points {
+        ctx.line_to(time_to_x(p.time_secs), db_to_y(p.level_db as f64));
+    }
+    ctx.line_to(time_to_x(last.time_secs), margin_top + chart_h);
+    ctx.close_path();
+    ctx.set_fill_style_str("rgba(68,221,221,0.25)");
+    ctx.fill();
+    ctx.set_stroke_style_str("#4dd");
+    ctx.set_line_width(1.0);
+    ctx.stroke();
+
+    ctx.set_stroke_style_str("rgba(255,255,255,0.2)");
+    ctx.stroke_rect(margin_left, margin_top, chart_w, chart_h);
+}
+
+#[component]
+fn BandChart(points: Vec<BandPoint>, noise_floor_db: f32) -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
+    let (t0, t1) = (
+        points.first().map(|p| p.time_secs).unwrap_or(0.0),
+        points.last().map(|p| p.time_secs).unwrap_or(0.0),
+    );
+
+    Effect::new(move || {
+        let Some(el) = canvas_ref.get() else { return };
+        let canvas: &HtmlCanvasElement = el.as_ref();
+        let canvas = canvas.clone();
+        draw_band_canvas(&canvas, &points, noise_floor_db);
+
+        // Redraw when the sidebar is resized
+        let points2 = points.clone();
+        let canvas2 = canvas.clone();
+        let cb = wasm_bindgen::closure::Closure::<dyn Fn(js_sys::Array)>::new(move |_entries: js_sys::Array| {
+            draw_band_canvas(&canvas2, &points2, noise_floor_db);
+        });
+        if let Ok(observer) = web_sys::ResizeObserver::new(cb.as_ref().unchecked_ref()) {
+            if let Some(parent) = canvas.parent_element() {
+                observer.observe(&parent);
+            }
+            let _ = js_sys::Reflect::set(
+                &canvas,
+                &wasm_bindgen::JsValue::from_str("__band_resize_obs"),
+                &observer,
+            );
+        }
+        cb.forget();
+    });
+
+    // Click to centre the spectrogram on that moment
+    let on_click = move |ev: web_sys::MouseEvent| {
+        let Some(el) = canvas_ref.get_untracked() else { return };
+        let canvas: &HtmlCanvasElement = el.as_ref();
+        let width = canvas.client_width() as f64;
+        let frac = ((ev.offset_x() as f64 - MARGIN_LEFT) / (width - MARGIN_LEFT - MARGIN_RIGHT)).clamp(0.0, 1.0);
+        let time = t0 + frac * (t1 - t0);
+        let time_res = state.files.with_untracked(|files| {
+            state.current_file_index.get_untracked().and_then(|i| files.get(i)).map(|f| f.spectrogram.time_resolution)
+        });
+        let Some(time_res) = time_res else { return };
+        let visible_time = (state.spectrogram_canvas_width.get_untracked() / state.zoom_level.get_untracked()) * time_res;
+        state.scroll_offset.set((time - visible_time / 2.0).max(0.0));
+    };
+
+    view! {
+        <div class="psd-chart-wrap">
+            <canvas
+                node_ref=canvas_ref
+                title="Click to jump to that point in the file"
+                style="width:100%;height:120px;display:block;border-radius:3px;cursor:pointer"
+                on:click=on_click
+            />
+        </div>
+    }
+}
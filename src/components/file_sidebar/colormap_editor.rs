@@ -0,0 +1,150 @@
+use leptos::prelude::*;
+use wasm_bindgen::JsCast;
+use crate::canvas::custom_colormap::{self, CustomColormap};
+use crate::canvas::spectrogram_renderer::lut_css_gradient;
+use crate::state::AppState;
+
+/// Starting point for a new map: dark blue through magenta to pale yellow.
+const NEW_MAP_STOPS: &str = "#000020\n#3b0f70\n#b5367a\n#fb8761\n#fcfdbf\n";
+
+/// Saved custom colormaps (preview, edit, export, delete) and an editor for
+/// creating one from pasted or imported control colors.
+#[component]
+pub(super) fn CustomColormapEditor() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let editing = RwSignal::new(false);
+    let name = RwSignal::new(String::new());
+    let text = RwSignal::new(String::new());
+    let parsed = Memo::new(move |_| text.with(|t| custom_colormap::parse_stops(t)));
+    let import_ref = NodeRef::<leptos::html::Input>::new();
+
+    let open_editor = move |map_name: String, stops_text: String| {
+        name.set(map_name);
+        text.set(stops_text);
+        editing.set(true);
+    };
+
+    let on_save = move |_: web_sys::MouseEvent| {
+        let Ok(stops) = parsed.get_untracked() else { return };
+        let map_name = match name.get_untracked().trim() {
+            "" => format!("Custom {}", state.custom_colormaps.with_untracked(|m| m.len()) + 1),
+            n => n.to_string(),
+        };
+        match state.save_custom_colormap(CustomColormap { name: map_name.clone(), stops }) {
+            Some(cm) => {
+                state.colormap_preference.set(cm);
+                state.show_info_toast(format!("Saved colormap: {map_name}"));
+                editing.set(false);
+            }
+            None => state.show_error_toast("Too many custom colormaps \u{2014} delete one first"),
+        }
+    };
+
+    let on_import_change = move |ev: web_sys::Event| {
+        let input: web_sys::HtmlInputElement = ev.target().unwrap().unchecked_into();
+        let Some(file) = input.files().and_then(|f| f.get(0)) else { return };
+        let file_name = file.name();
+        wasm_bindgen_futures::spawn_local(async move {
+            match wasm_bindgen_futures::JsFuture::from(file.text()).await {
+                Ok(val) => {
+                    let stem = file_name.rsplit_once('.').map_or(file_name.as_str(), |(s, _)| s).to_string();
+                    open_editor(stem, val.as_string().unwrap_or_default());
+                }
+                Err(e) => log::error!("Failed to read colormap file: {e:?}"),
+            }
+        });
+        input.set_value("");
+    };
+
+    view! {
+        <div class="setting-row" title="Colormaps of your own, from a list of colors (hex or RGB, e.g. exported from matplotlib)">
+            <span class="setting-label">"Custom colormaps"</span>
+            <button
+                class="setting-button"
+                on:click=move |_| open_editor(String::new(), NEW_MAP_STOPS.to_string())
+            >"New"</button>
+            <button
+                class="setting-button"
+                title="Import a text file of colors"
+                on:click=move |_| {
+                    if let Some(el) = import_ref.get() { el.click(); }
+                }
+            >"Import"</button>
+            <input
+                node_ref=import_ref
+                type="file"
+                accept=".txt,.csv"
+                style="display:none"
+                on:change=on_import_change
+            />
+        </div>
+        {move || state.custom_colormaps.get().into_iter().enumerate().map(|(i, map)| {
+            let gradient = lut_css_gradient(&map.lut());
+            let export_text = custom_colormap::format_stops(&map.stops);
+            let edit_text = export_text.clone();
+            let map_name = map.name.clone();
+            let file_name = format!("{}.txt", map.name);
+            view! {
+                <div class="setting-row">
+                    <span class="setting-label">{map.name.clone()}</span>
+                    <div class="colormap-swatch" style:background=gradient></div>
+                    <button
+                        class="setting-button"
+                        on:click=move |_| open_editor(map_name.clone(), edit_text.clone())
+                    >"Edit"</button>
+                    <button
+                        class="setting-button"
+                        title="Download as a text file of hex colors"
+                        on:click=move |_| super::project_panel::download_text(&export_text, &file_name)
+                    >"Export"</button>
+                    <button
+                        class="setting-reset-btn"
+                        title="Delete this colormap"
+                        on:click=move |_| state.remove_custom_colormap(i)
+                    >{"\u{00D7}"}</button>
+                </div>
+            }
+        }).collect::<Vec<_>>()}
+        {move || editing.get().then(|| view! {
+            <div class="colormap-editor">
+                <div class="setting-row">
+                    <input
+                        type="text"
+                        class="setting-text"
+                        placeholder="Name"
+                        prop:value=move || name.get()
+                        on:input=move |ev: web_sys::Event| name.set(event_target_value(&ev))
+                    />
+                </div>
+                <textarea
+                    class="colormap-editor-text"
+                    rows="6"
+                    spellcheck="false"
+                    placeholder="One color per line, low to high: #rrggbb, or r g b (0\u{2013}1 or 0\u{2013}255)"
+                    prop:value=move || text.get()
+                    on:input=move |ev: web_sys::Event| text.set(event_target_value(&ev))
+                ></textarea>
+                {move || match parsed.get() {
+                    Ok(stops) => view! {
+                        <div
+                            class="colormap-swatch colormap-editor-preview"
+                            style:background=lut_css_gradient(&custom_colormap::lut_from_stops(&stops))
+                        ></div>
+                    }.into_any(),
+                    Err(e) => view! { <div class="setting-hint">{e}</div> }.into_any(),
+                }}
+                <div class="setting-row">
+                    <button
+                        class="setting-button"
+                        prop:disabled=move || parsed.with(|p| p.is_err())
+                        on:click=on_save
+                    >"Save"</button>
+                    <button
+                        class="setting-button"
+                        on:click=move |_| editing.set(false)
+                    >"Cancel"</button>
+                </div>
+            </div>
+        })}
+    }
+}
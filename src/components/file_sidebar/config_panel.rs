@@ -1,17 +1,360 @@
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
+use crate::canvas::colors::{hex_to_rgb, rgb_to_hex};
 use crate::canvas::spectrogram_renderer::Colormap;
-use crate::state::{AppState, ChromaColormap, ShieldStyle};
+use super::colormap_editor::CustomColormapEditor;
+use crate::playback_prefs::{FileClass, PlaybackPreference};
+use crate::reference_lines::{self, ReferenceLine, DEFAULT_REFERENCE_FREQ_HZ};
+use crate::units::{DurationUnit, FreqUnit};
+use crate::state::{AppState, ChromaColormap, DbReference, MarkerColors, MetadataOverlap, PlayAnchor, ShieldStyle, TimeTickDensity, NoDataFill, PreviewDetail, TileMemoryBudget, TilePrefetch, TileScheduling, UiTheme, DEFAULT_BOOKMARK_COLOR, MAX_CONCURRENT_LOADS_LIMIT, SINGLE_PASS_MAX_SECS_LIMIT, MAX_PRE_EMPHASIS_DB_OCT, DEFAULT_HARMONIC_SHADOW_COLOR, DEFAULT_OVERLAY_OPACITY, DEFAULT_PLAYHEAD_COLOR, DEFAULT_PLAYHEAD_TRAIL_COLOR, DEFAULT_SELECTION_COLOR, MAX_PLAYHEAD_TRAIL_SECS, MIN_PLAYHEAD_TRAIL_SECS, PlayheadTrail, SelectionFocusDim, DEFAULT_FOCUS_DIM_OPACITY, DEFAULT_SPECTROGRAM_BACKGROUND, FREQ_MARKER_INTERVALS_KHZ, ZOOM_LIMIT_WINDOW_PX, DEFAULT_FETCH_TIMEOUT_SECS, MIN_FETCH_TIMEOUT_SECS, MAX_FETCH_TIMEOUT_SECS, DEFAULT_FETCH_RETRIES, MAX_FETCH_RETRIES};
 
-fn parse_colormap_pref(s: &str) -> Colormap {
-    match s {
-        "inferno" => Colormap::Inferno,
-        "magma" => Colormap::Magma,
-        "plasma" => Colormap::Plasma,
-        "cividis" => Colormap::Cividis,
-        "turbo" => Colormap::Turbo,
-        "greyscale" => Colormap::Greyscale,
-        _ => Colormap::Viridis,
+/// Set and persist the frequency range new files open cropped to.
+fn set_default_display_range(state: AppState, range: Option<(f64, f64)>) {
+    state.default_display_range.set(range);
+    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = match range {
+            Some((lo, hi)) => ls.set_item("oversample_default_display_range", &format!("{lo},{hi}")),
+            None => ls.remove_item("oversample_default_display_range"),
+        };
+    }
+}
+
+/// `<option>`s for the built-in colormaps followed by the user's custom ones.
+fn colormap_options(state: AppState, selected: RwSignal<Colormap>) -> impl IntoView {
+    move || {
+        let mut choices: Vec<(Colormap, String)> = Colormap::ALL.iter()
+            .map(|&cm| (cm, cm.label().to_string()))
+            .collect();
+        state.custom_colormaps.with(|maps| {
+            choices.extend(maps.iter().enumerate().map(|(i, m)| (Colormap::Custom(i as u8), m.name.clone())));
+        });
+        choices.into_iter().map(|(cm, label)| view! {
+            <option value=cm.key() selected=move || selected.get() == cm>{label}</option>
+        }).collect::<Vec<_>>()
+    }
+}
+
+/// Remembered playback mode for one file class, with a button to forget it.
+fn playback_pref_row(state: AppState, class: FileClass, version: RwSignal<u32>) -> impl IntoView {
+    view! {
+        {move || {
+            // Re-read when the current mode (and so the saved default) changes
+            state.playback_mode.track();
+            version.track();
+            PlaybackPreference::load(class).map(|pref| view! {
+                <div class="setting-row">
+                    <span class="setting-label">{class.label()}</span>
+                    <span class="setting-hint">{pref.summary()}</span>
+                    <button
+                        class="setting-reset-btn"
+                        title="Forget this default"
+                        on:click=move |_| {
+                            PlaybackPreference::forget(class);
+                            version.update(|v| *v += 1);
+                        }
+                    >{"\u{00D7}"}</button>
+                </div>
+            })
+        }}
+    }
+}
+
+/// Color picker row bound to an RGB signal, persisted to localStorage as `#rrggbb`.
+/// The reset button restores `default`.
+fn color_setting_row(
+    label: &'static str,
+    signal: RwSignal<[u8; 3]>,
+    storage_key: &'static str,
+    default: [u8; 3],
+) -> impl IntoView {
+    let set_color = move |rgb: [u8; 3]| {
+        signal.set(rgb);
+        if let Some(ls) = web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+        {
+            let _ = ls.set_item(storage_key, &rgb_to_hex(rgb));
+        }
+    };
+    view! {
+        <div class="setting-row">
+            <span class="setting-label">{label}</span>
+            <input
+                type="color"
+                class="setting-color"
+                prop:value=move || rgb_to_hex(signal.get())
+                on:input=move |ev: web_sys::Event| {
+                    let target = ev.target().unwrap();
+                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                    if let Some(rgb) = hex_to_rgb(&input.value()) {
+                        set_color(rgb);
+                    }
+                }
+            />
+            <button
+                class="setting-reset-btn"
+                title="Reset to default"
+                prop:disabled=move || signal.get() == default
+                on:click=move |_| set_color(default)
+            >{"\u{21BA}"}</button>
+        </div>
+    }
+}
+
+/// Color picker plus opacity (%) for an overlay, persisted to localStorage as
+/// `{storage_key}_color` and `{storage_key}_opacity`. The reset button
+/// restores `default` at the default overlay opacity.
+fn overlay_color_row(
+    label: &'static str,
+    color: RwSignal<[u8; 3]>,
+    opacity: RwSignal<f64>,
+    storage_key: &'static str,
+    default: [u8; 3],
+) -> impl IntoView {
+    let store = move |suffix: &str, value: String| {
+        if let Some(ls) = web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+        {
+            let _ = ls.set_item(&format!("{storage_key}_{suffix}"), &value);
+        }
+    };
+    let set_color = move |rgb: [u8; 3]| {
+        color.set(rgb);
+        store("color", rgb_to_hex(rgb));
+    };
+    let set_opacity = move |v: f64| {
+        opacity.set(v);
+        store("opacity", v.to_string());
+    };
+    view! {
+        <div class="setting-row">
+            <span class="setting-label">{label}</span>
+            <input
+                type="color"
+                class="setting-color"
+                prop:value=move || rgb_to_hex(color.get())
+                on:input=move |ev: web_sys::Event| {
+                    let target = ev.target().unwrap();
+                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                    if let Some(rgb) = hex_to_rgb(&input.value()) {
+                        set_color(rgb);
+                    }
+                }
+            />
+            <input
+                type="number"
+                class="setting-number"
+                title="Opacity (%)"
+                min="10" max="100" step="5"
+                prop:value=move || format!("{:.0}", opacity.get() * 100.0)
+                on:change=move |ev: web_sys::Event| {
+                    let target = ev.target().unwrap();
+                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                    if let Ok(pct) = input.value().parse::<f64>() {
+                        set_opacity((pct / 100.0).clamp(0.1, 1.0));
+                    }
+                }
+            />
+            <button
+                class="setting-reset-btn"
+                title="Reset to default"
+                prop:disabled=move || color.get() == default && opacity.get() == DEFAULT_OVERLAY_OPACITY
+                on:click=move |_| {
+                    set_color(default);
+                    set_opacity(DEFAULT_OVERLAY_OPACITY);
+                }
+            >{"\u{21BA}"}</button>
+        </div>
+    }
+}
+
+fn set_show_reference_lines(state: AppState, show: bool) {
+    state.show_reference_lines.set(show);
+    if let Some(ls) = web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+    {
+        let _ = ls.set_item("oversample_show_reference_lines", if show { "true" } else { "false" });
+    }
+}
+
+fn update_reference_lines(state: AppState, f: impl FnOnce(&mut Vec<ReferenceLine>)) {
+    state.reference_lines.update(f);
+    state.reference_lines.with_untracked(|lines| reference_lines::save(lines));
+}
+
+/// Toggle plus one editable row (label, kHz, remove) per reference line.
+fn reference_lines_rows(state: AppState) -> impl IntoView {
+    view! {
+        <div class="setting-row">
+            <span class="setting-label">"Reference lines"</span>
+            <input
+                type="checkbox"
+                class="setting-checkbox"
+                prop:checked=move || state.show_reference_lines.get()
+                on:change=move |ev: web_sys::Event| {
+                    let target = ev.target().unwrap();
+                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                    set_show_reference_lines(state, input.checked());
+                }
+            />
+            <button
+                class="setting-button"
+                title="Add a reference frequency line"
+                on:click=move |_| {
+                    update_reference_lines(state, |lines| lines.push(ReferenceLine {
+                        freq_hz: DEFAULT_REFERENCE_FREQ_HZ,
+                        label: String::new(),
+                    }));
+                    set_show_reference_lines(state, true);
+                }
+            >"Add"</button>
+        </div>
+        {move || state.reference_lines.get().into_iter().enumerate().map(|(i, line)| {
+            view! {
+                <div class="setting-row">
+                    <input
+                        type="text"
+                        class="setting-text"
+                        placeholder="Label"
+                        prop:value=line.label.clone()
+                        on:change=move |ev: web_sys::Event| {
+                            let label = event_target_value(&ev);
+                            update_reference_lines(state, |lines| {
+                                if let Some(l) = lines.get_mut(i) { l.label = label; }
+                            });
+                        }
+                    />
+                    <input
+                        type="number"
+                        class="setting-number"
+                        title="Frequency (kHz)"
+                        min="0" step="0.5"
+                        prop:value=format!("{}", line.freq_hz / 1000.0)
+                        on:change=move |ev: web_sys::Event| {
+                            if let Ok(khz) = event_target_value(&ev).parse::<f64>() {
+                                if khz >= 0.0 {
+                                    update_reference_lines(state, |lines| {
+                                        if let Some(l) = lines.get_mut(i) { l.freq_hz = khz * 1000.0; }
+                                    });
+                                }
+                            }
+                        }
+                    />
+                    <button
+                        class="setting-reset-btn"
+                        title="Remove this line"
+                        on:click=move |_| update_reference_lines(state, |lines| {
+                            if i < lines.len() { lines.remove(i); }
+                        })
+                    >{"\u{00D7}"}</button>
+                </div>
+            }
+        }).collect::<Vec<_>>()}
+    }
+}
+
+/// Persist a recording setting string under `key`.
+fn save_recording_setting(key: &str, value: &str) {
+    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = ls.set_item(key, value);
+    }
+}
+
+/// Name template, site, operator, metadata and save-folder settings for new
+/// recordings.
+fn recording_settings_group(state: AppState) -> impl IntoView {
+    use crate::audio::recording_name::{self, DEFAULT_TEMPLATE};
+
+    let text_row = move |label: &'static str, title: &'static str, sig: RwSignal<String>, key: &'static str| view! {
+        <div class="setting-row" title=title>
+            <span class="setting-label">{label}</span>
+            <input
+                type="text"
+                class="setting-text"
+                prop:value=move || sig.get()
+                on:change=move |ev: web_sys::Event| {
+                    let value = event_target_value(&ev).trim().to_string();
+                    save_recording_setting(key, &value);
+                    sig.set(value);
+                }
+            />
+        </div>
+    };
+
+    view! {
+        <div class="setting-group">
+            <div class="setting-group-title">"Recording"</div>
+            <div class="setting-row" title="File name for new recordings. Fields: {date}, {time}, {site}, {operator}. Must include {date} and {time}.">
+                <span class="setting-label">"Name template"</span>
+                <input
+                    type="text"
+                    class="setting-text"
+                    prop:value=move || state.recording_name_template.get()
+                    on:change=move |ev: web_sys::Event| {
+                        let target = ev.target().unwrap();
+                        let input: web_sys::HtmlInputElement = target.unchecked_into();
+                        let template = input.value().trim().to_string();
+                        match recording_name::validate_template(&template) {
+                            Ok(()) => {
+                                save_recording_setting("oversample_recording_name_template", &template);
+                                state.recording_name_template.set(template);
+                            }
+                            Err(e) => {
+                                input.set_value(&state.recording_name_template.get_untracked());
+                                state.show_error_toast(format!("Name template not changed: {e}"));
+                            }
+                        }
+                    }
+                />
+                <button
+                    class="setting-reset-btn"
+                    title="Reset to default"
+                    prop:disabled=move || state.recording_name_template.get() == DEFAULT_TEMPLATE
+                    on:click=move |_| {
+                        save_recording_setting("oversample_recording_name_template", DEFAULT_TEMPLATE);
+                        state.recording_name_template.set(DEFAULT_TEMPLATE.to_string());
+                    }
+                >{"\u{21BA}"}</button>
+            </div>
+            <div class="setting-hint">
+                {move || {
+                    let _ = (state.recording_name_template.get(), state.recording_site.get(), state.recording_operator.get());
+                    format!("e.g. {}", crate::audio::live_recording::recording_file_name(&state, &js_sys::Date::new_0()))
+                }}
+            </div>
+            {text_row("Site", "Survey site, for {site} in the name template and the recording metadata", state.recording_site, "oversample_recording_site")}
+            {text_row("Operator", "Person recording, for {operator} in the name template and the recording metadata", state.recording_operator, "oversample_recording_operator")}
+            <div class="setting-row" title="Write the site and operator into each recording's GUANO metadata">
+                <span class="setting-label">"Add site to metadata"</span>
+                <input
+                    type="checkbox"
+                    class="setting-checkbox"
+                    prop:checked=move || state.recording_embed_survey.get()
+                    on:change=move |ev: web_sys::Event| {
+                        let target = ev.target().unwrap();
+                        let input: web_sys::HtmlInputElement = target.unchecked_into();
+                        let checked = input.checked();
+                        state.recording_embed_survey.set(checked);
+                        save_recording_setting("oversample_recording_embed_survey", if checked { "true" } else { "false" });
+                    }
+                />
+            </div>
+            {(state.is_tauri && !state.is_mobile.get_untracked()).then(|| view! {
+                <div class="setting-row" title="Folder new recordings are saved to. Leave empty for the app's own recordings folder.">
+                    <span class="setting-label">"Save folder"</span>
+                    <input
+                        type="text"
+                        class="setting-text"
+                        placeholder="App data"
+                        prop:value=move || state.recording_save_dir.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let dir = event_target_value(&ev).trim().to_string();
+                            save_recording_setting("oversample_recording_save_dir", &dir);
+                            state.recording_save_dir.set(dir);
+                        }
+                    />
+                </div>
+            })}
+        </div>
     }
 }
 
@@ -25,27 +368,27 @@ pub(super) fn ConfigPanel() -> impl IntoView {
         let checked = input.checked();
         state.follow_cursor.set(checked);
         if checked {
-            state.follow_suspended.set(false);
-            state.follow_visible_since.set(None);
+            state.resume_follow();
         }
     };
 
     let on_colormap_change = move |ev: web_sys::Event| {
         let target = ev.target().unwrap();
         let select: web_sys::HtmlSelectElement = target.unchecked_into();
-        state.colormap_preference.set(parse_colormap_pref(&select.value()));
+        state.colormap_preference.set(Colormap::from_key(&select.value()));
         state.tile_ready_signal.update(|n| *n = n.wrapping_add(1));
     };
 
     let on_hfr_colormap_change = move |ev: web_sys::Event| {
         let target = ev.target().unwrap();
         let select: web_sys::HtmlSelectElement = target.unchecked_into();
-        state.hfr_colormap_preference.set(parse_colormap_pref(&select.value()));
+        state.hfr_colormap_preference.set(Colormap::from_key(&select.value()));
         state.tile_ready_signal.update(|n| *n = n.wrapping_add(1));
     };
 
     let is_tauri = state.is_tauri;
     let _ = is_tauri; // used in the view
+    let pref_version = RwSignal::new(0u32);
 
     view! {
         <div class="sidebar-panel">
@@ -62,23 +405,146 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                         on:change=on_follow_cursor
                     />
                 </div>
+                <div class="setting-row" title="After you scroll during playback, wait this long before the view follows the playhead again">
+                    <span class="setting-label">"Follow resume delay (ms)"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="0"
+                        max="10000"
+                        step="50"
+                        prop:value=move || state.follow_resume_ms.get().to_string()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(ms) = input.value().parse::<f64>() {
+                                if ms.is_finite() {
+                                    let ms = ms.clamp(0.0, 10_000.0);
+                                    state.follow_resume_ms.set(ms);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_follow_resume_ms", &ms.to_string());
+                                    }
+                                }
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Starting playback always brings the view back to the playhead">
+                    <span class="setting-label">"Resume follow on play"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.follow_resume_on_play.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let on = input.checked();
+                            state.follow_resume_on_play.set(on);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_follow_resume_on_play", if on { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Where the dashed \"play from here\" marker sits in the view. \"Where clicked\" moves it to each click with the Hand tool while stopped.">
+                    <span class="setting-label">"Play-from-here marker"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            state.set_play_anchor(PlayAnchor::from_key(&select.value()));
+                        }
+                    >
+                        {PlayAnchor::ALL.iter().map(|&a| view! {
+                            <option
+                                value=a.key()
+                                selected=move || state.play_anchor.get() == a
+                            >{a.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Pick up where you stopped when returning to a file">
+                    <span class="setting-label">"Resume per file"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.resume_playback.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let resume = input.checked();
+                            state.resume_playback.set(resume);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_resume_playback", if resume { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Open ultrasonic and audible files in the playback mode last used with each">
+                    <span class="setting-label">"Remember mode per file type"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.remember_playback_mode.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let remember = input.checked();
+                            state.remember_playback_mode.set(remember);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_remember_playback_mode", if remember { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                {move || state.remember_playback_mode.get().then(|| view! {
+                    {playback_pref_row(state, FileClass::Ultrasonic, pref_version)}
+                    {playback_pref_row(state, FileClass::Audible, pref_version)}
+                })}
             </div>
 
             <div class="setting-group">
                 <div class="setting-group-title">"Display"</div>
+                <div class="setting-row" title="High contrast uses white text, solid borders and opaque canvas labels. Follow system switches to it when the system asks for more contrast.">
+                    <span class="setting-label">"Theme"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let theme = UiTheme::from_key(&select.value());
+                            state.ui_theme.set(theme);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_ui_theme", theme.key());
+                            }
+                        }
+                    >
+                        {UiTheme::ALL.iter().map(|&t| view! {
+                            <option
+                                value=t.key()
+                                selected=move || state.ui_theme.get() == t
+                            >{t.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
                 <div class="setting-row">
                     <span class="setting-label">"Color scheme"</span>
                     <select
                         class="setting-select"
                         on:change=on_colormap_change
                     >
-                        <option value="viridis" selected=move || state.colormap_preference.get() == Colormap::Viridis>"Viridis"</option>
-                        <option value="inferno" selected=move || state.colormap_preference.get() == Colormap::Inferno>"Inferno"</option>
-                        <option value="magma" selected=move || state.colormap_preference.get() == Colormap::Magma>"Magma"</option>
-                        <option value="plasma" selected=move || state.colormap_preference.get() == Colormap::Plasma>"Plasma"</option>
-                        <option value="cividis" selected=move || state.colormap_preference.get() == Colormap::Cividis>"Cividis"</option>
-                        <option value="turbo" selected=move || state.colormap_preference.get() == Colormap::Turbo>"Turbo"</option>
-                        <option value="greyscale" selected=move || state.colormap_preference.get() == Colormap::Greyscale>"Greyscale"</option>
+                        {colormap_options(state, state.colormap_preference)}
                     </select>
                 </div>
                 <div class="setting-row">
@@ -87,15 +553,10 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                         class="setting-select"
                         on:change=on_hfr_colormap_change
                     >
-                        <option value="viridis" selected=move || state.hfr_colormap_preference.get() == Colormap::Viridis>"Viridis"</option>
-                        <option value="inferno" selected=move || state.hfr_colormap_preference.get() == Colormap::Inferno>"Inferno"</option>
-                        <option value="magma" selected=move || state.hfr_colormap_preference.get() == Colormap::Magma>"Magma"</option>
-                        <option value="plasma" selected=move || state.hfr_colormap_preference.get() == Colormap::Plasma>"Plasma"</option>
-                        <option value="cividis" selected=move || state.hfr_colormap_preference.get() == Colormap::Cividis>"Cividis"</option>
-                        <option value="turbo" selected=move || state.hfr_colormap_preference.get() == Colormap::Turbo>"Turbo"</option>
-                        <option value="greyscale" selected=move || state.hfr_colormap_preference.get() == Colormap::Greyscale>"Greyscale"</option>
+                        {colormap_options(state, state.hfr_colormap_preference)}
                     </select>
                 </div>
+                <CustomColormapEditor />
                 <div class="setting-row">
                     <span class="setting-label">"Chromagram colors"</span>
                     <select
@@ -120,6 +581,64 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                         <option value="flow" selected=move || state.chroma_colormap.get() == ChromaColormap::Flow>"Flow"</option>
                     </select>
                 </div>
+                <div class="setting-row" title="Boosts higher frequencies in the spectrogram image only. Measurements, analysis, playback and exported audio are unaffected.">
+                    <span class="setting-label">"Pre-emphasis (display only)"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let slope = select.value().parse::<f64>().unwrap_or(0.0).clamp(0.0, MAX_PRE_EMPHASIS_DB_OCT);
+                            state.display_pre_emphasis_db_oct.set(slope);
+                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                let _ = ls.set_item("oversample_display_pre_emphasis", &slope.to_string());
+                            }
+                        }
+                    >
+                        {[0.0, 3.0, 6.0, 9.0, 12.0].into_iter().map(|slope: f64| {
+                            let label = if slope == 0.0 { "Off".to_string() } else { format!("+{slope} dB/oct") };
+                            view! {
+                                <option value=slope.to_string() selected=move || state.display_pre_emphasis_db_oct.get() == slope>{label}</option>
+                            }
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Stop zooming in once one FFT window is drawn this wide. Zooming further only stretches each window and shows no finer detail.">
+                    <span class="setting-label">"Zoom limit"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let value = select.value();
+                            let limit = value.parse::<f64>().ok().filter(|px| ZOOM_LIMIT_WINDOW_PX.contains(px));
+                            state.zoom_limit_px.set(limit);
+                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                let _ = ls.set_item("oversample_zoom_limit_px", limit.map(|px| px.to_string()).as_deref().unwrap_or("off"));
+                            }
+                        }
+                    >
+                        <option value="off" selected=move || state.zoom_limit_px.get().is_none()>"Off"</option>
+                        {ZOOM_LIMIT_WINDOW_PX.into_iter().map(|px| view! {
+                            <option value=px.to_string() selected=move || state.zoom_limit_px.get() == Some(px)>
+                                {format!("FFT window \u{2264} {px} px")}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Mirror the frequency axis so low frequencies are drawn at the top, as in some other analysis software. Off: high frequencies at the top.">
+                    <span class="setting-label">"Low frequencies at top"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.freq_axis_flipped.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            state.set_freq_axis_flipped(input.checked());
+                        }
+                    />
+                </div>
                 <div class="setting-row">
                     <span class="setting-label">"Show clock time"</span>
                     <input
@@ -138,6 +657,125 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                         }
                     />
                 </div>
+                <div class="setting-row" title="Hide every overlay and show just the spectrogram. Hold ` for a momentary view; Shift+` toggles this.">
+                    <span class="setting-label">"Clean view"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.clean_view_pinned.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            state.set_clean_view_pinned(input.checked());
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Frequency range (kHz) newly loaded files open cropped to. Each file can still be zoomed on its own; leave the top empty for the file's Nyquist">
+                    <span class="setting-label">"Default freq range"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="0"
+                        step="1"
+                        placeholder="0"
+                        prop:value=move || state.default_display_range.get()
+                            .map(|(lo, _)| format!("{}", lo / 1000.0))
+                            .unwrap_or_default()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let lo = input.value().parse::<f64>().ok().filter(|v| *v >= 0.0).unwrap_or(0.0) * 1000.0;
+                            let hi = state.default_display_range.get_untracked().map_or(f64::MAX, |(_, hi)| hi);
+                            if hi > lo {
+                                let range = (lo > 0.0 || hi < f64::MAX).then_some((lo, hi));
+                                set_default_display_range(state, range);
+                            }
+                        }
+                    />
+                    <span>"\u{2013}"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="1"
+                        step="1"
+                        placeholder="Nyquist"
+                        prop:value=move || state.default_display_range.get()
+                            .filter(|&(_, hi)| hi < f64::MAX)
+                            .map(|(_, hi)| format!("{}", hi / 1000.0))
+                            .unwrap_or_default()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let lo = state.default_display_range.get_untracked().map_or(0.0, |(lo, _)| lo);
+                            let hi = input.value().parse::<f64>().ok().map_or(f64::MAX, |v| v * 1000.0);
+                            if hi > lo {
+                                let range = (lo > 0.0 || hi < f64::MAX).then_some((lo, hi));
+                                set_default_display_range(state, range);
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row">
+                    <button class="setting-button"
+                        title="Open new files at the frequency range currently shown"
+                        on:click=move |_| {
+                            let nyquist = state.current_file().map(|f| f.audio.sample_rate as f64 / 2.0);
+                            let lo = state.min_display_freq.get_untracked().unwrap_or(0.0);
+                            let hi = state.max_display_freq.get_untracked().or(nyquist).unwrap_or(f64::MAX);
+                            if hi > lo {
+                                set_default_display_range(state, Some((lo, hi)));
+                            }
+                        }
+                    >"Pin current view"</button>
+                    <button class="setting-button"
+                        title="Open new files at their full 0\u{2013}Nyquist range"
+                        disabled=move || state.default_display_range.get().is_none()
+                        on:click=move |_| set_default_display_range(state, None)
+                    >"Use file Nyquist"</button>
+                </div>
+                <div class="setting-row" title="How closely spaced the time-axis ticks are">
+                    <span class="setting-label">"Time ticks"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let density = TimeTickDensity::from_key(&select.value());
+                            state.time_tick_density.set(density);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_time_tick_density", density.key());
+                            }
+                        }
+                    >
+                        {TimeTickDensity::ALL.iter().map(|&d| view! {
+                            <option
+                                value=d.key()
+                                selected=move || state.time_tick_density.get() == d
+                            >{d.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Continue the time-axis ticks as vertical lines across the spectrogram">
+                    <span class="setting-label">"Time gridlines"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.time_gridlines.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let on = input.checked();
+                            state.time_gridlines.set(on);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_time_gridlines", if on { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
                 <div class="setting-row">
                     <span class="setting-label">"Freq flags"</span>
                     <select
@@ -162,27 +800,304 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                         }).collect::<Vec<_>>()}
                     </select>
                 </div>
-                <div class="setting-row">
-                    <span class="setting-label">"Status bar"</span>
-                    <input
-                        type="checkbox"
-                        class="setting-checkbox"
-                        prop:checked=move || state.show_status_bar.get()
+                <div class="setting-row" title="Colours of the frequency marker ticks and solid flags, one per digit">
+                    <span class="setting-label">"Marker colours"</span>
+                    <select
+                        class="setting-select"
                         on:change=move |ev: web_sys::Event| {
                             let target = ev.target().unwrap();
-                            let input: web_sys::HtmlInputElement = target.unchecked_into();
-                            let show = input.checked();
-                            state.show_status_bar.set(show);
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let colors = MarkerColors::from_key(&select.value());
+                            state.marker_colors.set(colors);
                             if let Some(ls) = web_sys::window()
                                 .and_then(|w| w.local_storage().ok().flatten())
                             {
-                                let _ = ls.set_item("oversample_show_status_bar", if show { "true" } else { "false" });
+                                let _ = ls.set_item("oversample_marker_colors", colors.key());
                             }
                         }
-                    />
-                </div>
-            </div>
-
+                    >
+                        {MarkerColors::ALL.iter().map(|&c| view! {
+                            <option
+                                value=c.key()
+                                selected=move || state.marker_colors.get() == c
+                            >{c.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Spacing of the labelled frequency markers. A fixed spacing gives way to automatic when zoomed out too far for it.">
+                    <span class="setting-label">"Marker interval"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let khz = select.value().parse::<f64>().unwrap_or(0.0);
+                            state.freq_marker_interval_khz.set(khz);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_freq_marker_interval", &khz.to_string());
+                            }
+                        }
+                    >
+                        {FREQ_MARKER_INTERVALS_KHZ.iter().map(|&khz| view! {
+                            <option
+                                value=khz.to_string()
+                                selected=move || state.freq_marker_interval_khz.get() == khz
+                            >{if khz == 0.0 { "Auto".to_string() } else { format!("{khz} kHz") }}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Let the frequency axis zoom and pan past the file's Nyquist (e.g. when shifting up). A dashed MAX line marks where the data ends.">
+                    <span class="setting-label">"Allow above Nyquist"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.freq_above_nyquist.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let on = input.checked();
+                            state.freq_above_nyquist.set(on);
+                            if !on {
+                                // Pull a range that ends above Nyquist back to the full data range
+                                let nyquist = state.files.with_untracked(|files| {
+                                    state.current_file_index.get_untracked()
+                                        .and_then(|i| files.get(i))
+                                        .map(|f| f.spectrogram.max_freq)
+                                });
+                                if let (Some(max), Some(n)) = (state.max_display_freq.get_untracked(), nyquist) {
+                                    if max > n {
+                                        state.min_display_freq.set(None);
+                                        state.max_display_freq.set(None);
+                                    }
+                                }
+                            }
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_freq_above_nyquist", if on { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="How to draw the region above Nyquist, where the file has no data">
+                    <span class="setting-label">"Above Nyquist fill"</span>
+                    <select
+                        class="setting-select"
+                        prop:disabled=move || !state.freq_above_nyquist.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let fill = NoDataFill::from_key(&select.value());
+                            state.no_data_fill.set(fill);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_no_data_fill", fill.key());
+                            }
+                        }
+                    >
+                        {NoDataFill::ALL.iter().map(|&f| view! {
+                            <option
+                                value=f.key()
+                                selected=move || state.no_data_fill.get() == f
+                            >{f.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row">
+                    <span class="setting-label">"Cursor level"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let reference = DbReference::from_key(&select.value());
+                            state.cursor_db_reference.set(reference);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_cursor_db_ref", reference.key());
+                            }
+                        }
+                    >
+                        {DbReference::ALL.iter().map(|&r| view! {
+                            <option
+                                value=r.key()
+                                selected=move || state.cursor_db_reference.get() == r
+                            >{r.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row">
+                    <span class="setting-label">"Frequency units"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let unit = FreqUnit::from_key(&select.value());
+                            state.freq_unit.set(unit);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_freq_unit", unit.key());
+                            }
+                        }
+                    >
+                        {FreqUnit::ALL.iter().map(|&u| view! {
+                            <option
+                                value=u.key()
+                                selected=move || state.freq_unit.get() == u
+                            >{u.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row">
+                    <span class="setting-label">"Duration units"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let unit = DurationUnit::from_key(&select.value());
+                            state.duration_unit.set(unit);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_duration_unit", unit.key());
+                            }
+                        }
+                    >
+                        {DurationUnit::ALL.iter().map(|&u| view! {
+                            <option
+                                value=u.key()
+                                selected=move || state.duration_unit.get() == u
+                            >{u.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                {color_setting_row("Selection color", state.selection_color, "oversample_selection_color", DEFAULT_SELECTION_COLOR)}
+                {color_setting_row("Harmonic shadow color", state.harmonic_shadow_color, "oversample_harmonic_shadow_color", DEFAULT_HARMONIC_SHADOW_COLOR)}
+                {color_setting_row("Spectrogram background", state.spectrogram_background, "oversample_spectrogram_background", DEFAULT_SPECTROGRAM_BACKGROUND)}
+                {overlay_color_row("Playhead color", state.playhead_color, state.playhead_opacity, "oversample_playhead", DEFAULT_PLAYHEAD_COLOR)}
+                <div class="setting-row" title="During playback, mark the loudest frequency under the playhead on the spectrogram, tracing each call as it plays">
+                    <span class="setting-label">"Playhead trail"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let trail = PlayheadTrail::from_key(&select.value());
+                            state.playhead_trail.set(trail);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_playhead_trail", trail.key());
+                            }
+                        }
+                    >
+                        {PlayheadTrail::ALL.iter().map(|&t| view! {
+                            <option
+                                value=t.key()
+                                selected=move || state.playhead_trail.get() == t
+                            >{t.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                {move || (state.playhead_trail.get() != PlayheadTrail::Off).then(|| view! {
+                    {(state.playhead_trail.get_untracked() == PlayheadTrail::Fading).then(|| view! {
+                        <div class="setting-row" title="How much of the file the fading trail spans, in seconds of recording time">
+                            <span class="setting-label">"Trail length (s)"</span>
+                            <input
+                                type="number"
+                                class="setting-number"
+                                min=MIN_PLAYHEAD_TRAIL_SECS.to_string()
+                                max=MAX_PLAYHEAD_TRAIL_SECS.to_string()
+                                step="0.05"
+                                prop:value=move || state.playhead_trail_secs.get().to_string()
+                                on:change=move |ev: web_sys::Event| {
+                                    let target = ev.target().unwrap();
+                                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                    if let Ok(secs) = input.value().parse::<f64>() {
+                                        let secs = secs.clamp(MIN_PLAYHEAD_TRAIL_SECS, MAX_PLAYHEAD_TRAIL_SECS);
+                                        state.playhead_trail_secs.set(secs);
+                                        if let Some(ls) = web_sys::window()
+                                            .and_then(|w| w.local_storage().ok().flatten())
+                                        {
+                                            let _ = ls.set_item("oversample_playhead_trail_secs", &secs.to_string());
+                                        }
+                                    }
+                                }
+                            />
+                        </div>
+                    })}
+                    {overlay_color_row("Trail color", state.playhead_trail_color, state.playhead_trail_opacity, "oversample_playhead_trail", DEFAULT_PLAYHEAD_TRAIL_COLOR)}
+                })}
+                {overlay_color_row("Bookmark color", state.bookmark_color, state.bookmark_opacity, "oversample_bookmark", DEFAULT_BOOKMARK_COLOR)}
+                <div class="setting-row">
+                    <span class="setting-label">"Status bar"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.show_status_bar.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let show = input.checked();
+                            state.show_status_bar.set(show);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_show_status_bar", if show { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row">
+                    <span class="setting-label">"Crosshair"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.show_crosshair.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let show = input.checked();
+                            state.show_crosshair.set(show);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_show_crosshair", if show { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Thin trace along the bottom of the spectrogram showing the energy of each time column">
+                    <span class="setting-label">"Amplitude envelope"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.show_envelope_overlay.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let show = input.checked();
+                            state.show_envelope_overlay.set(show);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_show_envelope", if show { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                {reference_lines_rows(state)}
+            </div>
+
+            {recording_settings_group(state)}
+
             {move || {
                 if state.is_tauri {
                     view! {
@@ -238,6 +1153,59 @@ pub(super) fn ConfigPanel() -> impl IntoView {
 
             <div class="setting-group">
                 <div class="setting-group-title">"Selection"</div>
+                <div class="setting-row" title="How the dimming outside the Frequency Focus band treats the selection, so the spectrogram stays clear while a selection is placed">
+                    <span class="setting-label">"Focus dimming"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let mode = SelectionFocusDim::from_key(&select.value());
+                            state.selection_focus_dim.set(mode);
+                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                let _ = ls.set_item("oversample_selection_focus_dim", mode.key());
+                            }
+                        }
+                    >
+                        {SelectionFocusDim::ALL.iter().map(|&m| view! {
+                            <option
+                                value=m.key()
+                                selected=move || state.selection_focus_dim.get() == m
+                            >{m.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Opacity of the dimming outside the Frequency Focus band (0 turns it off)">
+                    <span class="setting-label">"Focus dim opacity (%)"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="0" max="90" step="5"
+                        prop:value=move || format!("{:.0}", state.focus_dim_opacity.get() * 100.0)
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(pct) = input.value().parse::<f64>() {
+                                let opacity = (pct / 100.0).clamp(0.0, 0.9);
+                                state.focus_dim_opacity.set(opacity);
+                                if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                    let _ = ls.set_item("oversample_focus_dim_opacity", &opacity.to_string());
+                                }
+                            }
+                        }
+                    />
+                    <button
+                        class="setting-reset-btn"
+                        title="Reset to default"
+                        prop:disabled=move || state.focus_dim_opacity.get() == DEFAULT_FOCUS_DIM_OPACITY
+                        on:click=move |_| {
+                            state.focus_dim_opacity.set(DEFAULT_FOCUS_DIM_OPACITY);
+                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                let _ = ls.remove_item("oversample_focus_dim_opacity");
+                            }
+                        }
+                    >{"\u{21BA}"}</button>
+                </div>
                 <div class="setting-row">
                     <span class="setting-label">"Auto-band: Selection"</span>
                     <input
@@ -251,6 +1219,48 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                         }
                     />
                 </div>
+                <div class="setting-row" title="After drawing a selection, preview its edges snapped to the call inside it; Enter applies, Esc keeps the box as drawn. Selections over a second are left alone">
+                    <span class="setting-label">"Snap to call"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.snap_to_call.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let on = input.checked();
+                            state.snap_to_call.set(on);
+                            if !on {
+                                state.snap_preview.set(None);
+                            }
+                            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                let _ = ls.set_item("oversample_snap_to_call", if on { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                {move || state.snap_to_call.get().then(|| view! {
+                    <div class="setting-row" title="Edges are where the level falls this far below the call's peak">
+                        <span class="setting-label">"Snap threshold (dB)"</span>
+                        <input
+                            type="number"
+                            class="setting-number"
+                            min="6" max="60" step="1"
+                            prop:value=move || format!("{:.0}", state.snap_threshold_db.get())
+                            on:change=move |ev: web_sys::Event| {
+                                let target = ev.target().unwrap();
+                                let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                if let Ok(db) = input.value().parse::<f64>() {
+                                    let db = db.clamp(6.0, 60.0);
+                                    state.snap_threshold_db.set(db);
+                                    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                        let _ = ls.set_item("oversample_snap_threshold_db", &db.to_string());
+                                    }
+                                }
+                            }
+                        />
+                    </div>
+                })}
                 <div class="setting-row">
                     <span class="setting-label">"Auto-band: Annotation"</span>
                     <input
@@ -279,6 +1289,404 @@ pub(super) fn ConfigPanel() -> impl IntoView {
                 </div>
             </div>
 
+            <div class="setting-group">
+                <div class="setting-group-title">"Analysis"</div>
+                <div class="setting-row" title="Warn when |DC| exceeds this share of full scale (0 = off)">
+                    <span class="setting-label">"DC warn (% FS)"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="0" max="100" step="0.1"
+                        prop:value=move || format!("{}", state.dc_warn_full_scale.get() * 100.0)
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(pct) = input.value().parse::<f64>() {
+                                let v = (pct / 100.0).clamp(0.0, 1.0);
+                                state.dc_warn_full_scale.set(v);
+                                if let Some(ls) = web_sys::window()
+                                    .and_then(|w| w.local_storage().ok().flatten())
+                                {
+                                    let _ = ls.set_item("oversample_dc_warn_fs", &v.to_string());
+                                }
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Warn when |DC| exceeds this share of the RMS level (0 = off)">
+                    <span class="setting-label">"DC warn (% RMS)"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="0" max="100" step="1"
+                        prop:value=move || format!("{}", state.dc_warn_rms_ratio.get() * 100.0)
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(pct) = input.value().parse::<f64>() {
+                                let v = (pct / 100.0).clamp(0.0, 1.0);
+                                state.dc_warn_rms_ratio.set(v);
+                                if let Some(ls) = web_sys::window()
+                                    .and_then(|w| w.local_storage().ok().flatten())
+                                {
+                                    let _ = ls.set_item("oversample_dc_warn_rms", &v.to_string());
+                                }
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Fields carried by both a xeno-canto record and the file's GUANO metadata (species, position, date): show both with a cross-check, or keep one and note the other where they differ">
+                    <span class="setting-label">"XC vs GUANO"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let mode = MetadataOverlap::from_key(&select.value());
+                            state.metadata_overlap.set(mode);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_metadata_overlap", mode.key());
+                            }
+                        }
+                    >
+                        {MetadataOverlap::ALL.iter().map(|&m| view! {
+                            <option
+                                value=m.key()
+                                selected=move || state.metadata_overlap.get() == m
+                            >{m.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+            </div>
+
+            <div class="setting-group">
+                <div class="setting-group-title">"Network"</div>
+                <div class="setting-row">
+                    <span class="setting-label">"Offline mode"</span>
+                    <input
+                        type="checkbox"
+                        class="setting-checkbox"
+                        prop:checked=move || state.offline_mode.get()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            let offline = input.checked();
+                            state.offline_mode.set(offline);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_offline_mode", if offline { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-hint">"Disable demo sounds, xeno-canto and all other network access"</div>
+                <div class="setting-row" title="Give up on a demo or URL download that hasn't finished after this long">
+                    <span class="setting-label">"Download timeout (s)"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min=MIN_FETCH_TIMEOUT_SECS.to_string()
+                        max=MAX_FETCH_TIMEOUT_SECS.to_string()
+                        step="5"
+                        prop:value=move || state.fetch_timeout_secs.get().to_string()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(secs) = input.value().parse::<u32>() {
+                                let secs = secs.clamp(MIN_FETCH_TIMEOUT_SECS, MAX_FETCH_TIMEOUT_SECS);
+                                state.fetch_timeout_secs.set(secs);
+                                if let Some(ls) = web_sys::window()
+                                    .and_then(|w| w.local_storage().ok().flatten())
+                                {
+                                    let _ = ls.set_item("oversample_fetch_timeout_secs", &secs.to_string());
+                                }
+                            }
+                        }
+                    />
+                    <button
+                        class="setting-reset-btn"
+                        title="Reset to default"
+                        prop:disabled=move || state.fetch_timeout_secs.get() == DEFAULT_FETCH_TIMEOUT_SECS
+                        on:click=move |_| {
+                            state.fetch_timeout_secs.set(DEFAULT_FETCH_TIMEOUT_SECS);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.remove_item("oversample_fetch_timeout_secs");
+                            }
+                        }
+                    >{"\u{21BA}"}</button>
+                </div>
+                <div class="setting-row" title="Try again this many times after a download times out or hits a network or server error">
+                    <span class="setting-label">"Download retries"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let retries = select.value().parse::<u32>().unwrap_or(DEFAULT_FETCH_RETRIES).min(MAX_FETCH_RETRIES);
+                            state.fetch_retries.set(retries);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_fetch_retries", &retries.to_string());
+                            }
+                        }
+                    >
+                        {(0..=MAX_FETCH_RETRIES).map(|n| view! {
+                            <option value=n.to_string() selected=move || state.fetch_retries.get() == n>
+                                {if n == 0 { "None".to_string() } else { n.to_string() }}
+                            </option>
+                        }).collect_view()}
+                    </select>
+                </div>
+            </div>
+
+            <div class="setting-group">
+                <div class="setting-group-title">"Performance"</div>
+                <div class="setting-row">
+                    <span class="setting-label">"Tile memory"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let budget = TileMemoryBudget::from_key(&select.value());
+                            state.tile_memory_budget.set(budget);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_tile_memory", budget.key());
+                            }
+                        }
+                    >
+                        {TileMemoryBudget::ALL.iter().map(|&b| view! {
+                            <option
+                                value=b.key()
+                                selected=move || state.tile_memory_budget.get() == b
+                            >{b.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row">
+                    <span class="setting-label">"Prefetch"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let prefetch = TilePrefetch::from_key(&select.value());
+                            state.tile_prefetch.set(prefetch);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_tile_prefetch", prefetch.key());
+                            }
+                        }
+                    >
+                        {TilePrefetch::ALL.iter().map(|&p| view! {
+                            <option
+                                value=p.key()
+                                selected=move || state.tile_prefetch.get() == p
+                            >{p.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Visible first: background preloading and spectral store filling pause while on-screen tiles are still computing, so scrolling large files stays smooth. Sequential: both share the workers equally.">
+                    <span class="setting-label">"Tile priority"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let scheduling = TileScheduling::from_key(&select.value());
+                            state.tile_scheduling.set(scheduling);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_tile_scheduling", scheduling.key());
+                            }
+                        }
+                    >
+                        {TileScheduling::ALL.iter().map(|&s| view! {
+                            <option
+                                value=s.key()
+                                selected=move || state.tile_scheduling.get() == s
+                            >{s.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row" title="Resolution of file previews, relative to the screen. Applies to files loaded afterwards; use Re-render to update the rest.">
+                    <span class="setting-label">"Preview detail"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let detail = PreviewDetail::from_key(&select.value());
+                            state.preview_detail.set(detail);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_preview_detail", detail.key());
+                            }
+                        }
+                    >
+                        {PreviewDetail::ALL.iter().map(|&d| view! {
+                            <option
+                                value=d.key()
+                                selected=move || state.preview_detail.get() == d
+                            >{d.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row">
+                    <span class="setting-label">"Existing previews"</span>
+                    <button
+                        class="setting-button"
+                        prop:disabled=move || state.preview_rerender_progress.get().is_some() || state.files.with(|f| f.is_empty())
+                        on:click=move |_| super::rerender_all_previews(state)
+                    >
+                        {move || match state.preview_rerender_progress.get() {
+                            Some((done, total)) => format!("Re-rendering {done}/{total}\u{2026}"),
+                            None => "Re-render all".to_string(),
+                        }}
+                    </button>
+                </div>
+                <div class="setting-row" title="How many opened files decode at the same time. Further files wait in a queue; lower this if dropping many files makes the app unresponsive">
+                    <span class="setting-label">"Concurrent file loads"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="1"
+                        max=MAX_CONCURRENT_LOADS_LIMIT.to_string()
+                        step="1"
+                        prop:value=move || state.max_concurrent_loads.get().to_string()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(n) = input.value().parse::<usize>() {
+                                let n = n.clamp(1, MAX_CONCURRENT_LOADS_LIMIT);
+                                state.max_concurrent_loads.set(n);
+                                if let Some(ls) = web_sys::window()
+                                    .and_then(|w| w.local_storage().ok().flatten())
+                                {
+                                    let _ = ls.set_item("oversample_max_concurrent_loads", &n.to_string());
+                                }
+                                super::start_queued_loads(state);
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Files up to this length compute their full spectrogram while loading instead of showing a quick preview first. 0 = always show the preview first">
+                    <span class="setting-label">"Single-pass load up to (s)"</span>
+                    <input
+                        type="number"
+                        class="setting-number"
+                        min="0"
+                        max=SINGLE_PASS_MAX_SECS_LIMIT.to_string()
+                        step="1"
+                        prop:value=move || state.single_pass_max_secs.get().to_string()
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let input: web_sys::HtmlInputElement = target.unchecked_into();
+                            if let Ok(secs) = input.value().parse::<f64>() {
+                                let secs = secs.clamp(0.0, SINGLE_PASS_MAX_SECS_LIMIT);
+                                state.single_pass_max_secs.set(secs);
+                                if let Some(ls) = web_sys::window()
+                                    .and_then(|w| w.local_storage().ok().flatten())
+                                {
+                                    let _ = ls.set_item("oversample_single_pass_max_secs", &secs.to_string());
+                                }
+                            }
+                        }
+                    />
+                </div>
+                <div class="setting-row" title="Crop leading and trailing silence from files as they load. Silence is judged against each file's own noise floor. Exports still use the untrimmed original. Applies to files loaded after enabling">
+                    <span class="setting-label">"Trim silence on import"</span>
+                    <input
+                        type="checkbox"
+                        prop:checked=move || state.import_trim_silence.get()
+                        on:change=move |ev| {
+                            let on = event_target_checked(&ev);
+                            state.import_trim_silence.set(on);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_import_trim", if on { "true" } else { "false" });
+                            }
+                        }
+                    />
+                </div>
+                {move || state.import_trim_silence.get().then(|| view! {
+                    <div class="setting-row" title="How far above the noise floor a sound must be to count as activity">
+                        <span class="setting-label">"Trim threshold (dB)"</span>
+                        <input
+                            type="number"
+                            class="setting-number"
+                            min="1"
+                            max="60"
+                            step="1"
+                            prop:value=move || state.import_trim_threshold_db.get().to_string()
+                            on:change=move |ev: web_sys::Event| {
+                                let target = ev.target().unwrap();
+                                let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                if let Ok(db) = input.value().parse::<f64>() {
+                                    let db = db.clamp(1.0, 60.0);
+                                    state.import_trim_threshold_db.set(db);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_import_trim_threshold_db", &db.to_string());
+                                    }
+                                }
+                            }
+                        />
+                    </div>
+                    <div class="setting-row" title="Audio kept before the first and after the last activity">
+                        <span class="setting-label">"Trim padding (ms)"</span>
+                        <input
+                            type="number"
+                            class="setting-number"
+                            min="0"
+                            max="10000"
+                            step="50"
+                            prop:value=move || state.import_trim_pad_ms.get().to_string()
+                            on:change=move |ev: web_sys::Event| {
+                                let target = ev.target().unwrap();
+                                let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                if let Ok(ms) = input.value().parse::<f64>() {
+                                    let ms = ms.clamp(0.0, 10_000.0);
+                                    state.import_trim_pad_ms.set(ms);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_import_trim_pad_ms", &ms.to_string());
+                                    }
+                                }
+                            }
+                        />
+                    </div>
+                })}
+                <div class="setting-hint" title=move || {
+                    let _ = state.tile_ready_signal.get();
+                    crate::canvas::tile_cache::memory_usage_by_cache()
+                        .iter()
+                        .map(|(name, used, max)| format!("{}: {:.0} / {:.0} MB", name, *used as f64 / 1_048_576.0, *max as f64 / 1_048_576.0))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }>
+                    {move || {
+                        let _ = state.tile_ready_signal.get();
+                        let (used, max) = crate::canvas::tile_cache::total_memory_usage();
+                        format!("Tile cache: {:.0} MB of {:.0} MB in use", used as f64 / 1_048_576.0, max as f64 / 1_048_576.0)
+                    }}
+                </div>
+            </div>
+
             <div class="setting-group">
                 <div class="setting-group-title">"Beta"</div>
                 <div class="setting-row">
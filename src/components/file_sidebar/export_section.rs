@@ -1,9 +1,12 @@
-//! Collapsible export section: WAV / MP4 export with format radio buttons,
-//! video settings, progress bar, and .batm import/export.
+//! Collapsible export section: WAV / MP4 / PNG export with format radio buttons,
+//! video settings, progress bar, .batm import/export, Raven/Audacity labels
+//! and the flow shift field as CSV.
 
 use leptos::prelude::*;
 
+use crate::audio::convert::SampleFormat;
 use crate::audio::export;
+use crate::audio::image_export;
 use crate::audio::video_export;
 use crate::audio::webcodecs_bindings as wc;
 use crate::state::{AppState, AudioCodecOption, ExportFormat, VideoCodec, VideoResolution, VideoViewMode};
@@ -42,10 +45,13 @@ pub fn ExportSection(
         let ext = match format {
             ExportFormat::Wav => ".wav",
             ExportFormat::Mp4 => ".mp4",
+            ExportFormat::Png => ".png",
         };
         match export::get_export_info(&state) {
             Some(info) => {
+                // Playback mode doesn't affect a still image
                 let mode_suffix = info.mode_label
+                    .filter(|_| format != ExportFormat::Png)
                     .map(|m| format!(" ({m})"))
                     .unwrap_or_default();
                 format!("Export {} {} to {ext}{mode_suffix}", info.count, info.source_label)
@@ -67,6 +73,9 @@ pub fn ExportSection(
             ExportFormat::Mp4 => {
                 video_export::start_export(&state);
             }
+            ExportFormat::Png => {
+                image_export::export_png(&state);
+            }
         }
     };
 
@@ -128,8 +137,136 @@ pub fn ExportSection(
                             None
                         }}
                     </label>
+                    <label class="export-radio">
+                        <input
+                            type="radio"
+                            name="export-format"
+                            checked=move || state.export_format.get() == ExportFormat::Png
+                            on:change=move |_| on_format_change(ExportFormat::Png)
+                        />
+                        " PNG"
+                    </label>
                 </div>
 
+                // Crop images to the HFR focus band, optionally band-limiting audio too
+                {move || {
+                    let lo = state.band_ff_freq_lo.get();
+                    let hi = state.band_ff_freq_hi.get();
+                    (state.hfr_enabled.get() && hi > lo).then(|| view! {
+                        <div class="setting-row" title="Crop exported images to the focus band">
+                            <label class="export-radio">
+                                <input
+                                    type="checkbox"
+                                    prop:checked=move || state.export_crop_to_ff.get()
+                                    on:change=move |ev| state.export_crop_to_ff.set(event_target_checked(&ev))
+                                />
+                                {format!(" Crop to focus band ({:.0}\u{2013}{:.0} kHz)", lo / 1000.0, hi / 1000.0)}
+                            </label>
+                        </div>
+                        {move || state.export_crop_to_ff.get().then(|| view! {
+                            <div class="setting-row"
+                                title="Also band-limit exported audio to the focus band (unless a frequency selection or EQ filter already applies)"
+                            >
+                                <label class="export-radio">
+                                    <input
+                                        type="checkbox"
+                                        prop:checked=move || state.export_crop_audio_to_ff.get()
+                                        on:change=move |ev| state.export_crop_audio_to_ff.set(event_target_checked(&ev))
+                                    />
+                                    " Band-limit audio too"
+                                </label>
+                            </div>
+                        })}
+                    })
+                }}
+
+                // PNG-specific options
+                {move || (state.export_format.get() == ExportFormat::Png).then(|| view! {
+                    <div class="setting-row" style="gap: 4px; align-items: center;">
+                        <span class="export-option-label">"Size:"</span>
+                        {resolution_select(state)}
+                        <button
+                            class="setting-button"
+                            title="Copy the visible part of the spectrogram to the clipboard as an image"
+                            disabled=move || state.current_file_index.get().is_none()
+                            on:click=move |_| image_export::copy_view_to_clipboard(&state)
+                        >"Copy view"</button>
+                    </div>
+                    <div class="setting-row" title="Write the FFT size, window, hop and frequency resolution in the corner of the image">
+                        <label class="export-radio">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || state.export_params_caption.get()
+                                on:change=move |ev| {
+                                    let on = event_target_checked(&ev);
+                                    state.export_params_caption.set(on);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_export_params_caption", if on { "true" } else { "false" });
+                                    }
+                                }
+                            />
+                            " Caption with FFT parameters"
+                        </label>
+                    </div>
+                    <div class="setting-row" style="gap: 4px; align-items: center;"
+                        title="Export the whole recording at a fixed time scale, at the chosen size's height. Images wider than a browser canvas allows are saved as numbered parts."
+                    >
+                        <span class="export-option-label">"Whole file:"</span>
+                        <input
+                            type="number"
+                            class="export-fade-input"
+                            min=crate::state::MIN_FULL_EXPORT_PX_PER_SEC
+                            max=crate::state::MAX_FULL_EXPORT_PX_PER_SEC
+                            step="10"
+                            prop:value=move || state.export_full_px_per_sec.get().to_string()
+                            on:change=move |ev| {
+                                let Ok(pps) = event_target_value(&ev).trim().parse::<f64>() else {
+                                    state.export_full_px_per_sec.update(|_| {});
+                                    return;
+                                };
+                                let pps = pps.clamp(crate::state::MIN_FULL_EXPORT_PX_PER_SEC, crate::state::MAX_FULL_EXPORT_PX_PER_SEC);
+                                state.export_full_px_per_sec.set(pps);
+                                if let Some(ls) = web_sys::window()
+                                    .and_then(|w| w.local_storage().ok().flatten())
+                                {
+                                    let _ = ls.set_item("oversample_export_full_px_per_sec", &pps.to_string());
+                                }
+                            }
+                        />
+                        <span class="export-fade-unit">"px/s"</span>
+                        <button
+                            class="setting-button"
+                            disabled=move || state.current_file_index.get().is_none()
+                                || state.export_full_progress.get().is_some()
+                            on:click=move |_| image_export::export_full_png(&state)
+                        >
+                            {move || match state.export_full_progress.get() {
+                                Some((done, total)) => format!("Exporting {}/{total}\u{2026}", done + 1),
+                                None => "Export".to_string(),
+                            }}
+                        </button>
+                    </div>
+                    {move || image_export::full_export_size(&state).map(|(w, h, parts)| {
+                        let pixels = w * h as u64;
+                        let size = if parts > 1 {
+                            format!("{w} \u{00D7} {h} px in {parts} images")
+                        } else {
+                            format!("{w} \u{00D7} {h} px")
+                        };
+                        if pixels > image_export::LARGE_EXPORT_PIXELS {
+                            view! {
+                                <div class="analysis-warning">
+                                    {format!("{size} ({:.0} Mpx) \u{2014} large; may be slow and use a lot of memory", pixels as f64 / 1e6)}
+                                </div>
+                            }.into_any()
+                        } else {
+                            view! { <div class="setting-hint">{size}</div> }.into_any()
+                        }
+                    })}
+                })}
+
                 // MP4-specific options (shown when MP4 selected)
                 {move || {
                     if state.export_format.get() == ExportFormat::Mp4 && webcodecs_available {
@@ -158,29 +295,7 @@ pub fn ExportSection(
                                 </div>
                                 <div class="setting-row" style="gap: 4px; align-items: center;">
                                     <span class="export-option-label">"Resolution:"</span>
-                                    <select
-                                        class="sidebar-select"
-                                        on:change=move |ev| {
-                                            let val = event_target_value(&ev);
-                                            let res = match val.as_str() {
-                                                "720" => VideoResolution::Hd720,
-                                                "1080" => VideoResolution::Hd1080,
-                                                "canvas" => VideoResolution::MatchCanvas,
-                                                _ => VideoResolution::Hd720,
-                                            };
-                                            state.video_resolution.set(res);
-                                        }
-                                    >
-                                        <option value="720" selected=move || state.video_resolution.get() == VideoResolution::Hd720>
-                                            "720p"
-                                        </option>
-                                        <option value="1080" selected=move || state.video_resolution.get() == VideoResolution::Hd1080>
-                                            "1080p"
-                                        </option>
-                                        <option value="canvas" selected=move || state.video_resolution.get() == VideoResolution::MatchCanvas>
-                                            "Match canvas"
-                                        </option>
-                                    </select>
+                                    {resolution_select(state)}
                                 </div>
                                 <div class="setting-row" style="gap: 4px; align-items: center;">
                                     <span class="export-option-label">"Video:"</span>
@@ -266,6 +381,105 @@ pub fn ExportSection(
                     }
                 }}
 
+                // Edge fade (applies to exports and playback)
+                <div class="setting-row" style="gap: 4px; align-items: center;"
+                    title="Raised-cosine fade at clip edges to avoid clicks. 0 = bit-exact extraction. Also used for playback."
+                >
+                    <span class="export-option-label">"Edge fade:"</span>
+                    <input
+                        type="number"
+                        class="export-fade-input"
+                        min="0"
+                        max=crate::dsp::fade::MAX_EDGE_FADE_MS
+                        step="0.5"
+                        prop:value=move || state.clip_fade_ms.get().to_string()
+                        on:change=move |ev| {
+                            let Ok(ms) = event_target_value(&ev).trim().parse::<f64>() else {
+                                // Re-sync the field with the current value
+                                state.clip_fade_ms.update(|_| {});
+                                return;
+                            };
+                            let ms = ms.clamp(0.0, crate::dsp::fade::MAX_EDGE_FADE_MS);
+                            state.clip_fade_ms.set(ms);
+                            if let Some(ls) = web_sys::window()
+                                .and_then(|w| w.local_storage().ok().flatten())
+                            {
+                                let _ = ls.set_item("oversample_clip_fade_ms", &ms.to_string());
+                            }
+                        }
+                    />
+                    <span class="export-fade-unit">"ms"</span>
+                </div>
+
+                // Lossless WAV export from the original integer samples
+                {move || (state.export_format.get() == ExportFormat::Wav).then(|| view! {
+                    <div class="setting-row"
+                        title="Keep the original integer samples of WAV/FLAC files (loaded after enabling) so WAV exports of mono files with no processing or gain, in the file's own bit depth, are bit-identical to the source (the edge fade is skipped). Uses more memory."
+                    >
+                        <label class="export-radio">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || state.keep_exact_samples.get()
+                                on:change=move |ev| {
+                                    let on = event_target_checked(&ev);
+                                    state.keep_exact_samples.set(on);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_keep_exact_samples", if on { "true" } else { "false" });
+                                    }
+                                }
+                            />
+                            " Keep exact samples (lossless clips)"
+                        </label>
+                    </div>
+                    <div class="setting-row" style="gap: 4px; align-items: center;"
+                        title="Sample format of processed WAV exports. Bit-exact exports keep the source's format."
+                    >
+                        <span class="export-option-label">"Bit depth:"</span>
+                        <select
+                            class="sidebar-select"
+                            on:change=move |ev| {
+                                if let Some(format) = SampleFormat::from_key(&event_target_value(&ev)) {
+                                    state.export_wav_format.set(format);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_export_wav_format", format.key());
+                                    }
+                                }
+                            }
+                        >
+                            {SampleFormat::ALL.into_iter().map(|format| view! {
+                                <option value=format.key() selected=move || state.export_wav_format.get() == format>
+                                    {format.label()}
+                                </option>
+                            }).collect_view()}
+                        </select>
+                    </div>
+                    <div class="setting-row"
+                        title="Add triangular (TPDF) dither of ±1 LSB before rounding to 16 or 24 bits, turning quantisation distortion of quiet signals into a low, even noise floor."
+                    >
+                        <label class="export-radio">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || state.export_wav_dither.get()
+                                prop:disabled=move || state.export_wav_format.get() == SampleFormat::Float32
+                                on:change=move |ev| {
+                                    let on = event_target_checked(&ev);
+                                    state.export_wav_dither.set(on);
+                                    if let Some(ls) = web_sys::window()
+                                        .and_then(|w| w.local_storage().ok().flatten())
+                                    {
+                                        let _ = ls.set_item("oversample_export_wav_dither", if on { "true" } else { "false" });
+                                    }
+                                }
+                            />
+                            " Dither (TPDF)"
+                        </label>
+                    </div>
+                })}
+
                 // Main export button
                 <div class="setting-row" style="gap: 4px; align-items: center;">
                     <button
@@ -389,7 +603,74 @@ pub fn ExportSection(
                         "Import .batm"
                     </button>
                 </div>
+
+                // Labels for Raven / Audacity
+                <div class="setting-row" style="gap: 4px;">
+                    <button
+                        class="sidebar-btn"
+                        style="flex: 1;"
+                        on:click=move |_| export::export_labels(&state, export::LabelFormat::Raven)
+                        disabled=move || state.current_file_index.get().is_none()
+                        title="Download the selection, regions, markers and bookmarks as a Raven Pro selection table"
+                    >
+                        "Raven table"
+                    </button>
+                    <button
+                        class="sidebar-btn"
+                        style="flex: 1;"
+                        on:click=move |_| export::export_labels(&state, export::LabelFormat::Audacity)
+                        disabled=move || state.current_file_index.get().is_none()
+                        title="Download the selection, regions, markers and bookmarks as an Audacity label file"
+                    >
+                        "Audacity labels"
+                    </button>
+                </div>
+
+                // Raw shift field behind the flow view
+                <div class="setting-row">
+                    <button
+                        class="sidebar-btn"
+                        style="flex: 1;"
+                        on:click=move |_| export::export_flow_field(&state)
+                        disabled=move || state.current_file_index.get().is_none()
+                        title=move || format!(
+                            "Download the {} shift field of the selection (or visible view) as CSV, at the resolution shown",
+                            state.spectrogram_display.get().flow_algo().label().to_lowercase(),
+                        )
+                    >
+                        "Flow field (CSV)"
+                    </button>
+                </div>
             </div>
         </div>
     }
 }
+
+/// Output size select shared by MP4 and PNG export.
+fn resolution_select(state: AppState) -> impl IntoView {
+    view! {
+        <select
+            class="sidebar-select"
+            on:change=move |ev| {
+                let val = event_target_value(&ev);
+                let res = match val.as_str() {
+                    "720" => VideoResolution::Hd720,
+                    "1080" => VideoResolution::Hd1080,
+                    "canvas" => VideoResolution::MatchCanvas,
+                    _ => VideoResolution::Hd720,
+                };
+                state.video_resolution.set(res);
+            }
+        >
+            <option value="720" selected=move || state.video_resolution.get() == VideoResolution::Hd720>
+                "720p"
+            </option>
+            <option value="1080" selected=move || state.video_resolution.get() == VideoResolution::Hd1080>
+                "1080p"
+            </option>
+            <option value="canvas" selected=move || state.video_resolution.get() == VideoResolution::MatchCanvas>
+                "Match canvas"
+            </option>
+        </select>
+    }
+}
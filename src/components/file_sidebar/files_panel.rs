@@ -3,6 +3,7 @@ use leptos::prelude::*;
 use leptos::task::spawn_local;
 use wasm_bindgen::{Clamped, JsCast};
 use web_sys::{CanvasRenderingContext2d, DragEvent, HtmlCanvasElement, HtmlInputElement, ImageData, MouseEvent};
+use crate::audio::file_metrics::{self, FileMetrics};
 use crate::audio::playback;
 use crate::audio::streaming_source;
 use crate::canvas::tile_cache;
@@ -12,7 +13,7 @@ use super::file_groups;
 use super::file_badges;
 use crate::format_time::format_duration_compact;
 
-use super::loading::{read_and_load_file, load_native_file, DemoEntry, fetch_demo_index, load_single_demo};
+use super::loading::{read_and_load_file, load_dropped_files, matching_sidecar, split_sidecar_files, load_native_file, queue_file_load, load_from_url, DemoEntry, fetch_demo_index, load_single_demo};
 use super::suggestions::BatsForYou;
 
 #[component]
@@ -39,6 +40,18 @@ pub(super) fn FilesPanel() -> impl IntoView {
 
     let file_input_ref = NodeRef::<leptos::html::Input>::new();
 
+    // Metric sorts: compute any missing metrics in the background. The list
+    // re-sorts as each file's result lands in `state.files`.
+    Effect::new(move || {
+        if !state.file_sort_mode.get().needs_metrics() {
+            return;
+        }
+        let missing = files.with(|f| f.iter().any(|f| f.metrics.is_none() && f.loading_id.is_none()));
+        if missing {
+            file_metrics::start_metrics_scan(state);
+        }
+    });
+
     let state_for_upload = state;
     let on_upload_click = move |_: web_sys::MouseEvent| {
         if state.is_tauri && !state.is_mobile.get_untracked() {
@@ -56,7 +69,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
                             let name = path.rsplit(['/', '\\']).next().unwrap_or(&path).to_string();
                             let state = state;
                             let load_id = state.loading_start(&name);
-                            spawn_local(async move {
+                            queue_file_load(state, load_id, async move {
                                 match load_native_file(path, state, load_id).await {
                                     Ok(()) => {}
                                     Err(e) => log::error!("Failed to load file: {e}"),
@@ -79,12 +92,13 @@ pub(super) fn FilesPanel() -> impl IntoView {
         let input: HtmlInputElement = target.unchecked_into();
         let Some(file_list) = input.files() else { return };
 
-        for i in 0..file_list.length() {
-            let Some(file) = file_list.get(i) else { continue };
+        let (audio_files, sidecars) = split_sidecar_files(&file_list);
+        for file in audio_files {
             let state = state_for_upload;
+            let sidecar = matching_sidecar(&file.name(), &sidecars);
             let load_id = state.loading_start(&file.name());
-            spawn_local(async move {
-                match read_and_load_file(file, state, load_id).await {
+            queue_file_load(state, load_id, async move {
+                match read_and_load_file(file, sidecar, state, load_id).await {
                     Ok(()) => {}
                     Err(e) => log::error!("Failed to load file: {e}"),
                 }
@@ -100,6 +114,26 @@ pub(super) fn FilesPanel() -> impl IntoView {
     let demo_picker_open = RwSignal::new(false);
     let demo_loading = RwSignal::new(false);
     let bats_expanded = RwSignal::new(true);
+    let url_text = RwSignal::new(String::new());
+
+    let on_url_submit = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let url = url_text.get_untracked().trim().to_string();
+        if url.is_empty() {
+            return;
+        }
+        let load_id = state.loading_start(&url);
+        spawn_local(async move {
+            match load_from_url(&url, state, load_id).await {
+                Ok(()) => url_text.set(String::new()),
+                Err(e) => {
+                    log::error!("Failed to load {url}: {e}");
+                    state.show_error_toast(&format!("Couldn't load URL: {e}"));
+                }
+            }
+            state.loading_done(load_id);
+        });
+    };
 
     let on_demo_click = move |_: web_sys::MouseEvent| {
         if demo_picker_open.get_untracked() {
@@ -118,7 +152,10 @@ pub(super) fn FilesPanel() -> impl IntoView {
                     demo_entries.set(entries);
                     demo_picker_open.set(true);
                 }
-                Err(e) => log::error!("Failed to fetch demo index: {e}"),
+                Err(e) => {
+                    log::error!("Failed to fetch demo index: {e}");
+                    state.show_error_toast(format!("Couldn't load the demo list: {e}"));
+                }
             }
             demo_loading.set(false);
         });
@@ -138,25 +175,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
             log::warn!("Drop: no files in DataTransfer");
             return;
         };
-
-        log::info!("Drop: {} file(s)", file_list.length());
-
-        for i in 0..file_list.length() {
-            let Some(file) = file_list.get(i) else { continue };
-            let state = state_for_drop;
-            let file_name = file.name();
-            let load_id = state.loading_start(&file_name);
-            spawn_local(async move {
-                match read_and_load_file(file, state, load_id).await {
-                    Ok(()) => {}
-                    Err(e) => {
-                        log::error!("Failed to load {}: {}", file_name, e);
-                        state.show_error_toast(&format!("Couldn't open {file_name}: {e}"));
-                    }
-                }
-                state.loading_done(load_id);
-            });
-        }
+        load_dropped_files(state_for_drop, &file_list);
     };
 
     view! {
@@ -170,11 +189,14 @@ pub(super) fn FilesPanel() -> impl IntoView {
             <input
                 node_ref=file_input_ref
                 type="file"
-                accept=".wav,.w4v,.flac,.mp3,.ogg,.m4a,.m4b"
+                accept=".wav,.w4v,.flac,.mp3,.ogg,.m4a,.m4b,.json"
                 multiple=true
                 style="display:none"
                 on:change=on_file_input_change
             />
+            <Show when=move || files.with(|f| f.len() > 1) || !state.file_filter.with(|q| q.is_empty())>
+                <FileFilterBar />
+            </Show>
             {move || {
                 let file_vec = files.get();
                 let loading_empty = loading_files.with(|v| v.is_empty());
@@ -183,20 +205,45 @@ pub(super) fn FilesPanel() -> impl IntoView {
                         <div class="drop-hint">
                             {(!state.is_mobile.get_untracked()).then_some("Drop audio files here")}
                             <button class="upload-btn" on:click=on_upload_click>"Browse files"</button>
-                            <button class="upload-btn demo-btn" on:click=on_demo_click>
+                            <button
+                                class="upload-btn demo-btn"
+                                on:click=on_demo_click
+                                prop:disabled=move || state.offline_mode.get()
+                                title=move || if state.offline_mode.get() { "Unavailable in offline mode" } else { "" }
+                            >
                                 {move || if demo_loading.get() { "Loading..." } else { "Load demo" }}
                             </button>
                             {if state.is_tauri {
                                 Some(view! {
-                                    <button class="upload-btn xc-btn" on:click=move |_| {
-                                        state.xc_browser_open.set(true);
-                                    }>"Explore XC"</button>
+                                    <button
+                                        class="upload-btn xc-btn"
+                                        prop:disabled=move || state.offline_mode.get()
+                                        title=move || if state.offline_mode.get() { "Unavailable in offline mode" } else { "" }
+                                        on:click=move |_| {
+                                            state.xc_browser_open.set(true);
+                                        }
+                                    >"Explore XC"</button>
                                 })
                             } else {
                                 None
                             }}
+                            <Show when=move || !state.offline_mode.get()>
+                                <form class="url-load" on:submit=on_url_submit>
+                                    <input
+                                        type="url"
+                                        class="url-load-input"
+                                        placeholder="Paste audio URL"
+                                        prop:value=move || url_text.get()
+                                        on:input=move |ev| url_text.set(event_target_value(&ev))
+                                    />
+                                    <button type="submit" class="upload-btn">"Load"</button>
+                                </form>
+                            </Show>
+                            {move || state.offline_mode.get().then(|| view! {
+                                <div class="offline-hint">"Offline mode \u{2014} demo sounds and xeno-canto are off"</div>
+                            })}
                             {move || {
-                                if demo_picker_open.get() {
+                                if demo_picker_open.get() && !state.offline_mode.get() {
                                     let entries = demo_entries.get();
 
                                     // "Random bat" button — pick a random bat from the demos
@@ -219,7 +266,10 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                                     spawn_local(async move {
                                                         match load_single_demo(&entry, state, load_id).await {
                                                             Ok(()) => {}
-                                                            Err(e) => log::error!("Failed to load random bat: {e}"),
+                                                            Err(e) => {
+                                                                log::error!("Failed to load random bat: {e}");
+                                                                state.show_error_toast(format!("Couldn't load {label}: {e}"));
+                                                            }
                                                         }
                                                         state.loading_done(load_id);
                                                     });
@@ -251,7 +301,10 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                                     spawn_local(async move {
                                                         match load_single_demo(&entry, state, load_id).await {
                                                             Ok(()) => {}
-                                                            Err(e) => log::error!("Failed to load demo sound: {e}"),
+                                                            Err(e) => {
+                                                                log::error!("Failed to load demo sound: {e}");
+                                                                state.show_error_toast(format!("Couldn't load {}: {e}", entry.filename));
+                                                            }
                                                         }
                                                         state.loading_done(load_id);
                                                     });
@@ -271,7 +324,9 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                     view! { <span></span> }.into_any()
                                 }
                             }}
-                            <BatsForYou demo_entries=demo_entries expanded=bats_expanded />
+                            <Show when=move || !state.offline_mode.get()>
+                                <BatsForYou demo_entries=demo_entries expanded=bats_expanded />
+                            </Show>
                         </div>
                     }.into_any()
                 } else {
@@ -287,9 +342,18 @@ pub(super) fn FilesPanel() -> impl IntoView {
                         .and_then(|g| g.sequence.as_ref())
                         .map(|s| (s.sequence_key.clone(), s.track_label.clone()));
 
-                    // Compute sorted display order
+                    // Compute sorted display order, then apply the filter box.
+                    // The current file stays listed, so typing a filter never
+                    // switches files (and loses their bookmarks and history).
                     let sort_mode = state.file_sort_mode.get();
-                    let sorted_indices = compute_sorted_indices(&file_vec, sort_mode, &names, &group_infos);
+                    let query = state.file_filter.get();
+                    let include_metadata = state.file_filter_metadata.get();
+                    let current = current_idx.get();
+                    let sorted_indices: Vec<usize> = compute_sorted_indices(&file_vec, sort_mode, &names, &group_infos)
+                        .into_iter()
+                        .filter(|&i| Some(i) == current || file_matches_filter(&file_vec[i], &query, include_metadata))
+                        .collect();
+                    let filtered_out = file_vec.len() - sorted_indices.len();
 
                     let mut items: Vec<leptos::tachys::view::any_view::AnyView> = Vec::new();
                     for (_pos, &i) in sorted_indices.iter().enumerate() {
@@ -303,6 +367,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
                         let seq_badge = gi.sequence.clone();
                         let is_streaming = streaming_source::is_streaming(f.audio.source.as_ref());
                         let file_loading_id = f.loading_id;
+                        let metric_text = metric_label(f, sort_mode);
                         let is_active = move || current_idx.get() == Some(i);
                         let is_selected = move || state.selected_file_indices.with(|sel| sel.contains(&i));
 
@@ -364,13 +429,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                 return;
                             }
 
-                            state.selected_file_indices.set(Vec::new());
-                            state.active_timeline.set(None);
-                            state.active_timeline_track.set(None);
-                            state.nav_history.set(vec![]);
-                            state.nav_index.set(0);
-                            state.bookmarks.set(vec![]);
-                            current_idx.set(Some(i));
+                            activate_file(state, i);
                         };
                         let on_close = move |ev: MouseEvent| {
                             ev.stop_propagation();
@@ -519,6 +578,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                         show_download=has_download
                                         on_download=Callback::new(on_download)
                                     />
+                                    {metric_text.map(|t| view! { <span class="file-metric">{t}</span> })}
                                 </div>
                                 {file_loading_id.map(|lid| {
                                     view! {
@@ -528,6 +588,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                                 let entry = entries.iter().find(|e| e.id == lid);
                                                 if let Some(entry) = entry {
                                                     let stage_text = match &entry.stage {
+                                                        crate::state::LoadingStage::Queued => "Queued".to_string(),
                                                         crate::state::LoadingStage::Decoding => "Decoding\u{2026}".to_string(),
                                                         crate::state::LoadingStage::Preview => "Preview\u{2026}".to_string(),
                                                         crate::state::LoadingStage::Spectrogram(pct) => format!("Spectrogram {pct}%"),
@@ -595,6 +656,15 @@ pub(super) fn FilesPanel() -> impl IntoView {
                             } else {
                                 None
                             }}
+                            {(filtered_out > 0).then(|| view! {
+                                <div class="file-filter-hidden">
+                                    {if filtered_out == file_vec.len() {
+                                        "No files match the filter".to_string()
+                                    } else {
+                                        format!("{} of {} files hidden by the filter", filtered_out, file_vec.len())
+                                    }}
+                                </div>
+                            })}
                             // Active timeline banner
                             {move || {
                                 if state.active_timeline.with(|t| t.is_some()) {
@@ -604,10 +674,13 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                     let total_dur = state.active_timeline.with(|t| {
                                         t.as_ref().map(|tv| tv.total_duration_secs).unwrap_or(0.0)
                                     });
+                                    let label = state.active_timeline.with(|t| {
+                                        t.as_ref().and_then(|tv| tv.label.clone())
+                                    }).unwrap_or_else(|| "Timeline".to_string());
                                     view! {
                                         <div class="timeline-banner">
                                             <span class="timeline-banner-label">
-                                                {format!("Timeline: {} files, {}", seg_count, format_duration_compact(total_dur))}
+                                                {format!("{}: {} files, {}", label, seg_count, format_duration_compact(total_dur))}
                                             </span>
                                             <button class="timeline-exit-btn" on:click=on_exit_timeline
                                                 title="Exit timeline view"
@@ -618,6 +691,27 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                     view! { <span></span> }.into_any()
                                 }
                             }}
+                            // Multi-selection actions
+                            {move || {
+                                let count = state.selected_file_indices.with(|sel| sel.len());
+                                (count >= 2 && state.active_timeline.with(|t| t.is_none())).then(|| view! {
+                                    <div class="timeline-banner file-selection-bar">
+                                        <span class="timeline-banner-label">{format!("{count} files selected")}</span>
+                                        <button
+                                            class="timeline-join-btn"
+                                            title="Play and view the selected files as one continuous recording, in the order selected. The files are not changed."
+                                            on:click=move |_| {
+                                                let indices = state.selected_file_indices.get_untracked();
+                                                let files = state.files.get_untracked();
+                                                match crate::timeline::TimelineView::concatenate(&indices, &files, "Concatenated") {
+                                                    Ok(tv) => state.open_timeline(tv),
+                                                    Err(e) => state.show_error_toast(format!("Can\u{2019}t concatenate: {e}")),
+                                                }
+                                            }
+                                        >"Concatenate"</button>
+                                    </div>
+                                })
+                            }}
                             {items}
                             // Show loading entries that don't yet have a file in the list
                             // (still decoding/streaming before the file is added)
@@ -635,6 +729,7 @@ pub(super) fn FilesPanel() -> impl IntoView {
                                 }
                                 let items: Vec<_> = orphan_entries.iter().map(|entry| {
                                     let stage_text = match &entry.stage {
+                                        crate::state::LoadingStage::Queued => "Queued".to_string(),
                                         crate::state::LoadingStage::Decoding => "Decoding\u{2026}".to_string(),
                                         crate::state::LoadingStage::Preview => "Preview\u{2026}".to_string(),
                                         crate::state::LoadingStage::Spectrogram(pct) => format!("Spectrogram {pct}%"),
@@ -679,12 +774,28 @@ pub(super) fn FilesPanel() -> impl IntoView {
     }
 }
 
+/// Lift faint thumbnails with a gamma curve (black and white stay fixed).
+/// `amount` 0..1 maps to gamma 1..1/4, enough to make quiet recordings readable.
+fn brighten_preview(pixels: &[u8], amount: f32) -> Vec<u8> {
+    let gamma = 1.0 / (1.0 + 3.0 * amount.clamp(0.0, 1.0));
+    let mut lut = [0u8; 256];
+    for (i, v) in lut.iter_mut().enumerate() {
+        *v = ((i as f32 / 255.0).powf(gamma) * 255.0).round() as u8;
+    }
+    pixels
+        .chunks_exact(4)
+        .flat_map(|px| [lut[px[0] as usize], lut[px[1] as usize], lut[px[2] as usize], px[3]])
+        .collect()
+}
+
 #[component]
 fn PreviewCanvas(preview: PreviewImage) -> impl IntoView {
+    let state = expect_context::<AppState>();
     let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
     let pv = preview.clone();
 
     Effect::new(move || {
+        let brightness = state.file_preview_brightness.get();
         let Some(el) = canvas_ref.get() else { return };
         let canvas: &HtmlCanvasElement = el.as_ref();
         canvas.set_width(pv.width);
@@ -695,7 +806,14 @@ fn PreviewCanvas(preview: PreviewImage) -> impl IntoView {
             .unwrap()
             .dyn_into::<CanvasRenderingContext2d>()
             .unwrap();
-        let clamped = Clamped(pv.pixels.as_slice());
+        let boosted;
+        let pixels = if brightness > 0.0 {
+            boosted = brighten_preview(&pv.pixels, brightness);
+            boosted.as_slice()
+        } else {
+            pv.pixels.as_slice()
+        };
+        let clamped = Clamped(pixels);
         if let Ok(img) = ImageData::new_with_u8_clamped_array_and_sh(clamped, pv.width, pv.height) {
             let _ = ctx.put_image_data(&img, 0.0, 0.0);
         }
@@ -709,6 +827,94 @@ fn PreviewCanvas(preview: PreviewImage) -> impl IntoView {
     }
 }
 
+/// Make file `i` the current file, leaving any timeline and clearing the
+/// multi-selection and per-file navigation state.
+fn activate_file(state: AppState, i: usize) {
+    state.selected_file_indices.set(Vec::new());
+    state.active_timeline.set(None);
+    state.active_timeline_track.set(None);
+    state.nav_history.set(vec![]);
+    state.nav_index.set(0);
+    state.bookmarks.set(vec![]);
+    state.current_file_index.set(Some(i));
+}
+
+/// Species IDs and the xeno-canto quality grade, for the list filter.
+fn filter_metadata(f: &LoadedFile) -> (Vec<String>, Option<String>) {
+    let mut species = Vec::new();
+    let mut quality = None;
+    if let Some(meta) = f.xc_metadata.as_ref() {
+        for (k, v) in meta {
+            match k.as_str() {
+                "Species" | "Scientific name" | "Also heard" => species.push(v.to_lowercase()),
+                "Quality" => quality = Some(v.trim().to_lowercase()),
+                _ => {}
+            }
+        }
+    }
+    if let Some(guano) = f.audio.metadata.guano.as_ref() {
+        for (k, v) in &guano.fields {
+            if k.starts_with("Species") {
+                species.push(v.replace('_', " ").to_lowercase());
+            }
+        }
+    }
+    (species, quality)
+}
+
+/// Whether a file matches the list filter: every whitespace-separated term
+/// must appear in the filename or, with `include_metadata`, a species ID.
+/// `q:A` (or `quality:A`) matches the xeno-canto quality grade.
+fn file_matches_filter(f: &LoadedFile, query: &str, include_metadata: bool) -> bool {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return true;
+    }
+    let name = f.name.to_lowercase();
+    let (species, quality) = if include_metadata { filter_metadata(f) } else { (Vec::new(), None) };
+    query.split_whitespace().all(|term| {
+        if let Some(grade) = term.strip_prefix("q:").or_else(|| term.strip_prefix("quality:")) {
+            return include_metadata && quality.as_deref() == Some(grade);
+        }
+        name.contains(term) || species.iter().any(|s| s.contains(term))
+    })
+}
+
+/// Filter box above the file list. Kept out of the list closure so typing
+/// doesn't rebuild the input and lose focus.
+#[component]
+fn FileFilterBar() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let include_metadata = state.file_filter_metadata;
+    let on_toggle_metadata = move |_: web_sys::MouseEvent| {
+        let on = !include_metadata.get_untracked();
+        include_metadata.set(on);
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = ls.set_item("oversample_file_filter_metadata", if on { "true" } else { "false" });
+        }
+    };
+
+    view! {
+        <div class="file-sort-bar file-filter-bar">
+            <input
+                type="search"
+                class="file-filter-input"
+                placeholder="Filter files"
+                title="Show files whose name contains every word. With Tags on, species IDs also match, and q:A filters by xeno-canto quality"
+                prop:value=move || state.file_filter.get()
+                on:input=move |ev| state.file_filter.set(event_target_value(&ev))
+            />
+            <button
+                class=move || if include_metadata.get() { "file-preview-toggle active" } else { "file-preview-toggle" }
+                title="Also match species and quality from xeno-canto and GUANO metadata"
+                on:click=on_toggle_metadata
+            >
+                "Tags"
+            </button>
+        </div>
+    }
+}
+
 #[component]
 fn SortBar(sort_mode: FileSortMode) -> impl IntoView {
     let state = expect_context::<AppState>();
@@ -735,12 +941,33 @@ fn SortBar(sort_mode: FileSortMode) -> impl IntoView {
         show_previews.update(|v| *v = !*v);
     };
 
+    let brightness = state.file_preview_brightness;
+    let on_brightness = move |ev: web_sys::Event| {
+        let input: HtmlInputElement = ev.target().unwrap().unchecked_into();
+        if let Ok(v) = input.value().parse::<f32>() {
+            brightness.set(v);
+            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                let _ = ls.set_item("oversample_preview_brightness", &v.to_string());
+            }
+        }
+    };
+
     view! {
         <div class="file-sort-bar">
             <span class="file-sort-label">"Sort:"</span>
             <select class="file-sort-select" on:change=on_change>
                 {options}
             </select>
+            <Show when=move || show_previews.get()>
+                <input
+                    type="range"
+                    class="file-preview-brightness"
+                    min="0" max="1" step="0.05"
+                    title="Preview brightness"
+                    prop:value=move || brightness.get().to_string()
+                    on:input=on_brightness
+                />
+            </Show>
             <button
                 class=move || if show_previews.get() { "file-preview-toggle active" } else { "file-preview-toggle" }
                 title=move || if show_previews.get() { "Hide previews" } else { "Show previews" }
@@ -888,6 +1115,69 @@ fn compute_sorted_indices(
                     })
             });
         }
+        FileSortMode::ByDuration => {
+            // Longest first
+            indices.sort_by(|&a, &b| {
+                files[b].audio.duration_secs.partial_cmp(&files[a].audio.duration_secs)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+                    .then_with(|| files[a].add_order.cmp(&files[b].add_order))
+            });
+        }
+        FileSortMode::ByWsnr => {
+            // Best quality first
+            sort_by_metric(&mut indices, files, |m| Some(-m.wsnr_db));
+        }
+        FileSortMode::ByPeakFreq => {
+            sort_by_metric(&mut indices, files, |m| m.peak_freq_hz);
+        }
+        FileSortMode::ByCalls => {
+            // Most detected calls first
+            sort_by_metric(&mut indices, files, |m| Some(-(m.call_count as f64)));
+        }
     }
     indices
 }
+
+/// Sort ascending by a metric key. Files whose metrics aren't computed yet
+/// (or have no value) go last, in add order.
+fn sort_by_metric(
+    indices: &mut [usize],
+    files: &[LoadedFile],
+    key: impl Fn(&FileMetrics) -> Option<f64>,
+) {
+    indices.sort_by(|&a, &b| {
+        let ka = files[a].metrics.as_ref().and_then(&key);
+        let kb = files[b].metrics.as_ref().and_then(&key);
+        match (ka, kb) {
+            (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(std::cmp::Ordering::Equal),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        }
+        .then_with(|| files[a].add_order.cmp(&files[b].add_order))
+    });
+}
+
+/// Short label for the metric the list is sorted by, shown on each file row.
+/// None for modes that don't use metrics.
+fn metric_label(f: &LoadedFile, mode: FileSortMode) -> Option<String> {
+    if !mode.needs_metrics() {
+        return None;
+    }
+    let Some(m) = f.metrics.as_ref() else {
+        return Some("\u{2026}".to_string());
+    };
+    Some(match mode {
+        FileSortMode::ByWsnr => format!("wSNR {} ({:.0} dB)", m.wsnr_grade.label(), m.wsnr_db),
+        FileSortMode::ByPeakFreq => match m.peak_freq_hz {
+            Some(hz) => format!("Peak {:.1} kHz", hz / 1000.0),
+            None => "No peak".to_string(),
+        },
+        FileSortMode::ByCalls => match m.call_count {
+            0 => "No calls".to_string(),
+            1 => "1 call".to_string(),
+            n => format!("{n} calls"),
+        },
+        _ => return None,
+    })
+}
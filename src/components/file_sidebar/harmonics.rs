@@ -1,43 +1,319 @@
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
-use crate::state::AppState;
-use crate::dsp::harmonics;
+use crate::state::{AppState, FileKey, HarmonicsWindow};
+use crate::audio::source::{ChannelView, DEFAULT_ANALYSIS_WINDOW_SECS};
+use crate::canvas::spectral_store;
+use crate::dsp::incremental::{AverageSpectrum, Incremental};
+use crate::dsp::{harmonics, qc_report};
 
 #[component]
 pub(crate) fn HarmonicsPanel() -> impl IntoView {
     let state = expect_context::<AppState>();
 
+    // Time range for the chosen analysis window. None = whole file (also the
+    // fallback when there's no selection / selected pulse). Held at its
+    // previous value while a selection is being dragged so we don't re-run
+    // the STFT on every pointer move.
+    let window_range = Memo::new(move |prev: Option<&Option<(f64, f64)>>| {
+        if state.is_dragging.get() {
+            if let Some(prev) = prev {
+                return *prev;
+            }
+        }
+        match state.harmonics_window.get() {
+            HarmonicsWindow::WholeFile => None,
+            HarmonicsWindow::Selection => state.selection.get()
+                .filter(|s| s.time_end > s.time_start)
+                .map(|s| (s.time_start, s.time_end)),
+            HarmonicsWindow::Pulse => {
+                let sel = state.selected_pulse_index.get()?;
+                state.detected_pulses.with(|pulses| {
+                    pulses.iter()
+                        .find(|p| p.index == sel)
+                        .map(|p| (p.start_time, p.end_time))
+                })
+            }
+        }
+    });
+
     let harmonics = Memo::new(move |_| {
         let files = state.files.get();
         let idx = state.current_file_index.get();
-        idx.and_then(|i| files.get(i).cloned()).map(|file| {
-            harmonics::analyze_harmonics(&file.audio, &file.spectrogram)
+        let range = window_range.get();
+        let count = state.harmonic_count.get();
+        let smoothing = state.contour_smoothing.get();
+        let fit = state.harmonic_decay_fit.get();
+        idx.and_then(|i| files.get(i).cloned()).map(|file| match range {
+            Some((t0, t1)) => harmonics::analyze_harmonics_range(&file.audio, &file.spectrogram, t0, t1, count, smoothing, fit),
+            None => harmonics::analyze_harmonics(&file.audio, &file.spectrogram, count, smoothing, fit),
         })
     });
 
+    // Publish the fundamental for the selection harmonic ratio
+    Effect::new(move || {
+        let f0 = harmonics.with(|h| h.as_ref().and_then(|h| h.fundamental_freq));
+        let detected = state.current_file_index.get_untracked().zip(f0.map(|f| f as f64));
+        state.detected_fundamental.set(detected);
+    });
+
+    // Large files keep their columns in the spectral store rather than
+    // `spectrogram.columns`, filled in a tile at a time. Average whatever has
+    // arrived and refine the decay profile as more tiles land.
+    let store_run: StoredValue<Option<(FileKey, Incremental<AverageSpectrum>)>> = StoredValue::new(None);
+    let store_decay = Memo::new(move |_| {
+        let _ = state.tile_ready_signal.get();
+        if window_range.get().is_some() {
+            return None;
+        }
+        let idx = state.current_file_index.get()?;
+        let count = state.harmonic_count.get();
+        let fit = state.harmonic_decay_fit.get();
+        let (key, freq_resolution, max_freq) = state.files.with(|files| {
+            files.get(idx)
+                .filter(|f| f.spectrogram.columns.is_empty())
+                .map(|f| (f.key(), f.spectrogram.freq_resolution, f.spectrogram.max_freq))
+        })?;
+        if !spectral_store::has_store(idx) {
+            return None;
+        }
+        store_run.update_value(|run| {
+            // A different file, even at the same index, starts a new average
+            if run.as_ref().is_none_or(|(k, _)| *k != key) {
+                *run = Some((key, Incremental::new(AverageSpectrum::default())));
+            }
+            if let Some((_, r)) = run {
+                r.feed_from_store(idx);
+            }
+        });
+        store_run.with_value(|run| {
+            let (_, r) = run.as_ref()?;
+            if r.analysis().count() == 0 {
+                return None;
+            }
+            let mean = r.analysis().mean();
+            let decay = harmonics::harmonic_decay_from_spectrum(&mean, freq_resolution, max_freq, count, fit);
+            Some((decay, r.progress()))
+        })
+    });
+
+    // Batch QC report across all loaded files
+    let qc_entries: RwSignal<Vec<qc_report::QcEntry>> = RwSignal::new(Vec::new());
+    // (files done, total) while the report is running
+    let qc_progress: RwSignal<Option<(usize, usize)>> = RwSignal::new(None);
+
+    let on_run_qc = move |_: web_sys::MouseEvent| {
+        if qc_progress.get_untracked().is_some() {
+            return;
+        }
+        let keys: Vec<_> = state.files.with_untracked(|files| files.iter().map(|f| f.key()).collect());
+        let total = keys.len();
+        qc_entries.set(Vec::new());
+        qc_progress.set(Some((0, total)));
+
+        leptos::task::spawn_local(async move {
+            let mut entries = Vec::new();
+            for (i, key) in keys.iter().enumerate() {
+                crate::canvas::tile_cache::yield_to_browser().await;
+                // Same analysis window as the Files panel metrics keeps long recordings cheap
+                let next = state.files.with_untracked(|files| {
+                    files.iter().find(|f| f.key() == *key).filter(|f| f.loading_id.is_none()).map(|f| {
+                        let sr = f.audio.sample_rate;
+                        let n = (f.audio.source.total_samples() as usize)
+                            .min((DEFAULT_ANALYSIS_WINDOW_SECS * sr as f64) as usize);
+                        (f.name.clone(), f.audio.source.read_region(ChannelView::MonoMix, 0, n), sr)
+                    })
+                });
+                if let Some((name, samples, sr)) = next {
+                    entries.push(qc_report::analyze_file(&name, &samples, sr));
+                }
+                qc_progress.set(Some((i + 1, total)));
+            }
+            qc_entries.set(entries);
+            qc_progress.set(None);
+        });
+    };
+
+    let set_decay_fit = move |fit: harmonics::DecayFit| {
+        state.harmonic_decay_fit.set(fit);
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let value = format!("{}-{}-{}", fit.first_harmonic, fit.last_harmonic, fit.method.key());
+            let _ = ls.set_item("oversample_decay_fit", &value);
+        }
+    };
+
+    let window_hint = move || {
+        match (state.harmonics_window.get(), window_range.get()) {
+            (HarmonicsWindow::WholeFile, _) => String::new(),
+            (_, Some((t0, t1))) => format!(
+                "Analysing {:.1} ms from {}",
+                (t1 - t0) * 1000.0,
+                crate::format_time::format_time_display(t0, 3),
+            ),
+            (HarmonicsWindow::Selection, None) => "No selection \u{2014} showing whole file".to_string(),
+            (HarmonicsWindow::Pulse, None) => "No pulse selected (see Pulses tab) \u{2014} showing whole file".to_string(),
+        }
+    };
+
     view! {
         <div class="sidebar-panel">
+            <div class="setting-group">
+                <div class="setting-row">
+                    <span class="setting-label">"Analyse"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            state.harmonics_window.set(HarmonicsWindow::from_key(&select.value()));
+                        }
+                    >
+                        {HarmonicsWindow::ALL.iter().map(|&w| view! {
+                            <option
+                                value=w.key()
+                                selected=move || state.harmonics_window.get() == w
+                            >{w.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                {move || {
+                    let hint = window_hint();
+                    (!hint.is_empty()).then(|| view! { <div class="setting-hint">{hint}</div> })
+                }}
+                <div class="setting-row"
+                    title="How many harmonics (including the fundamental) to extract for the decay fit and chart. \
+                           Harmonics above Nyquist are never charted.">
+                    <span class="setting-label">"Harmonics"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            if let Ok(n) = select.value().parse::<usize>() {
+                                state.harmonic_count.set(n);
+                                if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                    let _ = ls.set_item("oversample_harmonic_count", &n.to_string());
+                                }
+                            }
+                        }
+                    >
+                        {(2..=harmonics::MAX_HARMONIC_COUNT).map(|n| view! {
+                            <option
+                                value=n.to_string()
+                                selected=move || state.harmonic_count.get() == n
+                            >{n.to_string()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row"
+                    title="Harmonics the decay exponent is fitted over. The fundamental is the reference \
+                           level, so the fit starts at H2 at the earliest. Leave out weak, noisy upper \
+                           harmonics that would otherwise skew the exponent.">
+                    <span class="setting-label">"Fit harmonics"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            if let Ok(n) = select.value().parse::<usize>() {
+                                let fit = state.harmonic_decay_fit.get_untracked();
+                                set_decay_fit(harmonics::DecayFit {
+                                    first_harmonic: n,
+                                    last_harmonic: fit.last_harmonic.max(n),
+                                    ..fit
+                                });
+                            }
+                        }
+                    >
+                        {(2..=harmonics::MAX_HARMONIC_COUNT).map(|n| view! {
+                            <option
+                                value=n.to_string()
+                                selected=move || state.harmonic_decay_fit.get().first_harmonic == n
+                            >{format!("H{n}")}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                    <span>"to"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            if let Ok(n) = select.value().parse::<usize>() {
+                                let fit = state.harmonic_decay_fit.get_untracked();
+                                set_decay_fit(harmonics::DecayFit {
+                                    first_harmonic: fit.first_harmonic.min(n),
+                                    last_harmonic: n,
+                                    ..fit
+                                });
+                            }
+                        }
+                    >
+                        {(2..=harmonics::MAX_HARMONIC_COUNT).map(|n| view! {
+                            <option
+                                value=n.to_string()
+                                selected=move || state.harmonic_decay_fit.get().last_harmonic == n
+                            >{format!("H{n}")}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row"
+                    title="Least squares uses every fitted harmonic equally. Robust takes the median of \
+                           the exponents each harmonic implies, so a single aberrant harmonic can't pull \
+                           the result.">
+                    <span class="setting-label">"Fit method"</span>
+                    <select
+                        class="setting-select"
+                        on:change=move |ev: web_sys::Event| {
+                            let target = ev.target().unwrap();
+                            let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                            let method = harmonics::DecayFitMethod::from_key(&select.value());
+                            set_decay_fit(harmonics::DecayFit { method, ..state.harmonic_decay_fit.get_untracked() });
+                        }
+                    >
+                        {harmonics::DecayFitMethod::ALL.iter().map(|&m| view! {
+                            <option
+                                value=m.key()
+                                selected=move || state.harmonic_decay_fit.get().method == m
+                            >{m.label()}</option>
+                        }).collect::<Vec<_>>()}
+                    </select>
+                </div>
+                <div class="setting-row"
+                    title="Smooth the per-frame spectral flux before the chart and the pre-ringing and \
+                           staircasing checks. A median removes single-frame transients without blurring onsets.">
+                    <span class="setting-label">"Flux smoothing"</span>
+                    <super::pulse_panel::SmoothingSelect/>
+                </div>
+            </div>
             {move || {
                 match harmonics.get() {
                     None => view! {
                         <div class="sidebar-panel-empty">"No file selected"</div>
                     }.into_any(),
-                    Some(h) => {
+                    Some(mut h) => {
+                        let mut computing_hint = None;
+                        if let Some((decay, (done, total))) = store_decay.get() {
+                            h.fundamental_freq = decay.fundamental_freq;
+                            h.harmonic_amplitudes = decay.harmonic_amplitudes;
+                            h.decay_exponent = decay.decay_exponent;
+                            h.decay_is_monotonic = decay.decay_is_monotonic;
+                            h.decay_anomaly_indices = decay.decay_anomaly_indices;
+                            h.decay_fit_indices = decay.decay_fit_indices;
+                            h.harmonics_nyquist_limited = decay.harmonics_nyquist_limited;
+                            if done < total {
+                                computing_hint = Some(format!(
+                                    "Computing\u{2026} based on {:.0}% of the file so far",
+                                    done as f64 * 100.0 / total.max(1) as f64,
+                                ));
+                            }
+                        }
                         let coherence_pct = format!("{:.0}%", h.phase_coherence_mean * 100.0);
-                        let coherence_label = if h.phase_coherence_mean >= 0.65 {
-                            "High (natural)"
-                        } else if h.phase_coherence_mean >= 0.45 {
-                            "Moderate"
-                        } else {
-                            "Low (processed)"
-                        };
-                        let coherence_color = if h.phase_coherence_mean >= 0.65 {
-                            "#4c8"
-                        } else if h.phase_coherence_mean >= 0.45 {
-                            "#fc8"
-                        } else {
-                            "#f64"
+                        let verdict = h.coherence_verdict();
+                        let coherence_label = verdict.label();
+                        let coherence_color = match verdict {
+                            harmonics::CoherenceVerdict::Natural => "#4c8",
+                            harmonics::CoherenceVerdict::Moderate => "#fc8",
+                            harmonics::CoherenceVerdict::Processed => "#f64",
                         };
 
                         let ratio_text = format!("{:.2}×", h.harmonic_coherence_ratio);
@@ -83,7 +359,30 @@ pub(crate) fn HarmonicsPanel() -> impl IntoView {
                         let all_clear = indicators.len() == 1
                             && indicators[0].contains("No significant");
 
+                        let count_hint = if h.harmonics_nyquist_limited {
+                            Some(format!(
+                                "Charting {} of {} harmonics \u{2014} the rest lie above Nyquist",
+                                h.harmonic_amplitudes.len(),
+                                h.harmonics_requested,
+                            ))
+                        } else {
+                            None
+                        };
+
+                        let fit_hint = match (h.decay_fit_indices.first(), h.decay_fit_indices.last()) {
+                            (Some(&lo), Some(&hi)) if lo == hi => format!("Fitted to H{}", lo + 1),
+                            (Some(&lo), Some(&hi)) => format!(
+                                "Fitted to H{}\u{2013}H{} ({})",
+                                lo + 1,
+                                hi + 1,
+                                state.harmonic_decay_fit.get_untracked().method.label().to_lowercase(),
+                            ),
+                            _ if h.harmonic_amplitudes.len() >= 2 => "No harmonics in the fit range \u{2014} exponent not fitted".to_string(),
+                            _ => String::new(),
+                        };
+
                         let amplitudes_for_chart = h.harmonic_amplitudes.clone();
+                        let fitted_for_chart = h.decay_fit_indices.clone();
                         let anomalies_for_chart = h.decay_anomaly_indices.clone();
                         let decay_exp_for_chart = h.decay_exponent;
                         let flux_for_chart = h.flux_per_frame.clone();
@@ -126,12 +425,13 @@ pub(crate) fn HarmonicsPanel() -> impl IntoView {
                             // --- Harmonic Decay ---
                             <div class="setting-group">
                                 <div class="setting-group-title">"Harmonic Decay"</div>
+                                {computing_hint.map(|hint| view! { <div class="setting-hint">{hint}</div> })}
                                 <div style="padding:2px 12px 6px;font-size:10px;color:#666;line-height:1.5">
                                     "Natural overtones follow a power-law: each harmonic (2f, 3f\u{2026}) has less \
                                     energy than the one below it, roughly A\u{2099} \u{221d} 1/n\u{1d45}. Pitch-shifting \
                                     can produce alias harmonics that violate this — a higher overtone equalling or \
                                     exceeding the one below it is a red flag. The dashed curve on the chart shows the \
-                                    fitted decay law; red bars are anomalies."
+                                    fitted decay law; red bars are anomalies and dimmed bars are left out of the fit."
                                 </div>
                                 <div class="analysis-stats">
                                     <div class="analysis-stat"
@@ -161,12 +461,15 @@ pub(crate) fn HarmonicsPanel() -> impl IntoView {
                                 <div class="analysis-warning" style="color:#888;font-style:italic">
                                     {decay_label}
                                 </div>
+                                {count_hint.map(|hint| view! { <div class="setting-hint">{hint}</div> })}
+                                {(!fit_hint.is_empty()).then(|| view! { <div class="setting-hint">{fit_hint}</div> })}
                                 // Harmonic decay bar chart
                                 {if !amplitudes_for_chart.is_empty() {
                                     view! {
                                         <HarmonicDecayChart
                                             amplitudes=amplitudes_for_chart
                                             anomaly_indices=anomalies_for_chart
+                                            fit_indices=fitted_for_chart
                                             decay_exponent=decay_exp_for_chart
                                         />
                                     }.into_any()
@@ -266,6 +569,67 @@ pub(crate) fn HarmonicsPanel() -> impl IntoView {
                     }
                 }
             }}
+
+            // --- Batch QC report ---
+            <div class="setting-group">
+                <div class="setting-group-title">"Batch QC"</div>
+                <div class="setting-row">
+                    <button
+                        class="setting-button"
+                        title="Grade every loaded file (wSNR) and check it for processing artifacts. \
+                               Each file is analysed over its first 30 seconds."
+                        prop:disabled=move || qc_progress.get().is_some() || state.files.with(|f| f.is_empty())
+                        on:click=on_run_qc
+                    >"Run QC (all files)"</button>
+                </div>
+                {move || qc_progress.get().map(|(done, total)| view! {
+                    <div class="setting-hint">{format!("Checking file {} of {}\u{2026}", (done + 1).min(total), total)}</div>
+                })}
+                {move || {
+                    let entries = qc_entries.get();
+                    (!entries.is_empty()).then(|| {
+                        let flagged = entries.iter().filter(|e| e.flagged()).count();
+                        let rows = entries.iter().map(|e| {
+                            let title = if e.findings.is_empty() { "No findings".to_string() } else { e.findings.join("\n") };
+                            view! {
+                                <div class=if e.flagged() { "qc-row flagged" } else { "qc-row" } title=title>
+                                    <span class="qc-name">{e.file_name.clone()}</span>
+                                    <span class="qc-grade">{e.grade.label()}</span>
+                                    <span class="qc-snr">{format!("{:.0} dB", e.snr_db)}</span>
+                                    <span class="qc-coherence">{format!("{:.0}%", e.phase_coherence_mean * 100.0)}</span>
+                                </div>
+                            }
+                        }).collect::<Vec<_>>();
+                        let csv_entries = entries.clone();
+                        let md_entries = entries.clone();
+                        view! {
+                            <div class="setting-hint">{format!("{} of {} files flagged", flagged, entries.len())}</div>
+                            <div class="qc-row qc-header">
+                                <span class="qc-name">"File"</span>
+                                <span class="qc-grade">"wSNR"</span>
+                                <span class="qc-snr">"SNR"</span>
+                                <span class="qc-coherence" title="Mean phase coherence">"Coh."</span>
+                            </div>
+                            <div class="qc-list">{rows}</div>
+                            <div class="setting-row">
+                                <button
+                                    class="setting-button"
+                                    on:click=move |_| {
+                                        let csv = format!("{}{}", qc_report::CSV_HEADER, qc_report::csv_rows(&csv_entries));
+                                        super::project_panel::download_text(&csv, "qc_report.csv");
+                                    }
+                                >"Export CSV"</button>
+                                <button
+                                    class="setting-button"
+                                    on:click=move |_| {
+                                        super::project_panel::download_text(&qc_report::markdown_table(&md_entries), "qc_report.md");
+                                    }
+                                >"Export Markdown"</button>
+                            </div>
+                        }
+                    })
+                }}
+            </div>
         </div>
     }
 }
@@ -274,11 +638,13 @@ pub(crate) fn HarmonicsPanel() -> impl IntoView {
 fn HarmonicDecayChart(
     amplitudes: Vec<f32>,
     anomaly_indices: Vec<usize>,
+    fit_indices: Vec<usize>,
     decay_exponent: f32,
 ) -> impl IntoView {
     let canvas_ref = NodeRef::<leptos::html::Canvas>::new();
     let amps = amplitudes.clone();
     let anom = anomaly_indices.clone();
+    let fitted = fit_indices.clone();
     let alpha = decay_exponent;
 
     Effect::new(move || {
@@ -327,8 +693,14 @@ fn HarmonicDecayChart(
 
         // Draw bars
         for (i, &amp) in amps.iter().enumerate() {
-            let is_anomaly = anom.contains(&i);
-            let color = if is_anomaly { "#f64" } else { "#4a8" };
+            // The fundamental is the fit's reference, so it counts as fitted
+            let color = if anom.contains(&i) {
+                "#f64"
+            } else if i == 0 || fitted.contains(&i) {
+                "#4a8"
+            } else {
+                "#3a4a42"
+            };
             ctx.set_fill_style_str(color);
             let bar_h = (amp as f64 * chart_h).max(1.0);
             let x = x_off + i as f64 * bar_w + 1.0;
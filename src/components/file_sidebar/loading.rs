@@ -5,10 +5,15 @@ use js_sys;
 use wasm_bindgen_futures::JsFuture;
 use web_sys::{File, FileReader};
 use crate::audio::loader::load_audio;
-use crate::dsp::fft::compute_preview;
+use crate::audio::xc_sidecar;
+use crate::dsp::fft::{compute_overview_from_spectrogram, compute_preview, compute_spectrogram, stft_frame_count};
 use crate::canvas::spectral_store;
 use crate::state::{AppState, FileSettings, LoadedFile};
 use crate::types::SpectrogramData;
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
 use std::sync::Arc;
 
 use super::streaming_load::{SilenceCheck, try_streaming_wav, try_streaming_flac, try_streaming_m4a, try_streaming_mp3, try_streaming_ogg, build_streaming_overview};
@@ -21,6 +26,40 @@ const MAX_FILE_SIZE: f64 = 2_000_000_000.0;
 /// for newly opened supported formats to avoid piling up more in-memory decodes.
 const TOTAL_OPEN_FILE_STREAMING_THRESHOLD: u64 = 500_000_000;
 
+type QueuedLoad = Pin<Box<dyn Future<Output = ()>>>;
+
+thread_local! {
+    /// File loads waiting for a free slot, oldest first, with their loading entry IDs.
+    static LOAD_QUEUE: RefCell<VecDeque<(u64, QueuedLoad)>> = RefCell::new(VecDeque::new());
+    /// File loads currently running.
+    static ACTIVE_LOADS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Run a file load once fewer than `max_concurrent_loads` are in progress, so
+/// a bulk drop doesn't decode hundreds of files at once. The loading entry
+/// (already created with `loading_start`) shows as queued until then, and the
+/// task is still responsible for calling `loading_done`.
+pub(crate) fn queue_file_load(state: AppState, load_id: u64, task: impl Future<Output = ()> + 'static) {
+    state.loading_update(load_id, crate::state::LoadingStage::Queued);
+    LOAD_QUEUE.with(|q| q.borrow_mut().push_back((load_id, Box::pin(task))));
+    start_queued_loads(state);
+}
+
+/// Start queued loads while slots are free. Also called when the limit is raised.
+pub(crate) fn start_queued_loads(state: AppState) {
+    let max = state.max_concurrent_loads.get_untracked().max(1);
+    while ACTIVE_LOADS.get() < max {
+        let Some((load_id, task)) = LOAD_QUEUE.with(|q| q.borrow_mut().pop_front()) else { break };
+        ACTIVE_LOADS.set(ACTIVE_LOADS.get() + 1);
+        state.loading_update(load_id, crate::state::LoadingStage::Decoding);
+        wasm_bindgen_futures::spawn_local(async move {
+            task.await;
+            ACTIVE_LOADS.set(ACTIVE_LOADS.get() - 1);
+            start_queued_loads(state);
+        });
+    }
+}
+
 fn total_open_file_bytes(state: AppState) -> u64 {
     state.files.with_untracked(|files| {
         files.iter()
@@ -29,21 +68,146 @@ fn total_open_file_bytes(state: AppState) -> u64 {
     })
 }
 
-pub(super) async fn read_and_load_file(file: File, state: AppState, load_id: u64) -> Result<(), String> {
+/// Queue every file of a drag-and-drop for loading, reporting failures as
+/// toasts. Shared by the Files panel and the empty main view.
+pub(crate) fn load_dropped_files(state: AppState, file_list: &web_sys::FileList) {
+    log::info!("Drop: {} file(s)", file_list.length());
+
+    let (audio_files, sidecars) = split_sidecar_files(file_list);
+    for file in audio_files {
+        let file_name = file.name();
+        let sidecar = matching_sidecar(&file_name, &sidecars);
+        let load_id = state.loading_start(&file_name);
+        queue_file_load(state, load_id, async move {
+            match read_and_load_file(file, sidecar, state, load_id).await {
+                Ok(()) => {}
+                Err(e) => {
+                    log::error!("Failed to load {}: {}", file_name, e);
+                    state.show_error_toast(&format!("Couldn't open {file_name}: {e}"));
+                }
+            }
+            state.loading_done(load_id);
+        });
+    }
+}
+
+/// Queue native filesystem paths for loading (Tauri only), skipping
+/// anything that isn't an audio file. Shared by native drag-and-drop and
+/// files the app was launched with or asked to open by the OS.
+pub(crate) fn load_native_paths(state: AppState, paths: Vec<String>) {
+    for path in paths {
+        let name = path.rsplit(['/', '\\']).next().unwrap_or(&path).to_string();
+        let ext = name.rsplit('.').next().unwrap_or("").to_lowercase();
+        if !matches!(ext.as_str(), "wav" | "w4v" | "flac" | "ogg" | "mp3" | "m4a" | "m4b") {
+            log::info!("Skipping non-audio file: {name}");
+            continue;
+        }
+        let load_id = state.loading_start(&name);
+        queue_file_load(state, load_id, async move {
+            match load_native_file(path, state, load_id).await {
+                Ok(()) => {}
+                Err(e) => {
+                    log::error!("Failed to load {}: {}", name, e);
+                    state.show_error_toast(&format!("Couldn't open {name}: {e}"));
+                }
+            }
+            state.loading_done(load_id);
+        });
+    }
+}
+
+/// Load any files the desktop app was asked to open — on its command line
+/// or by a file association — that haven't been picked up yet.
+pub(crate) async fn open_pending_native_files(state: AppState) {
+    match crate::tauri_bridge::tauri_invoke_no_args("take_pending_open_files").await {
+        Ok(result) => {
+            let paths: Vec<String> = js_sys::Array::from(&result).iter().filter_map(|v| v.as_string()).collect();
+            if !paths.is_empty() {
+                log::info!("Opening {} file(s) passed to the app", paths.len());
+                load_native_paths(state, paths);
+            }
+        }
+        Err(e) => log::error!("take_pending_open_files failed: {e}"),
+    }
+}
+
+/// Split a selection of files into audio and candidate xeno-canto sidecars
+/// (anything ending in `.json`), so sidecars picked or dropped alongside
+/// their recordings tag them instead of failing to load as audio.
+pub(super) fn split_sidecar_files(file_list: &web_sys::FileList) -> (Vec<File>, Vec<File>) {
+    (0..file_list.length())
+        .filter_map(|i| file_list.get(i))
+        .partition(|f| !f.name().to_ascii_lowercase().ends_with(".json"))
+}
+
+/// The sidecar among `sidecars` that belongs to `audio_name`, if any.
+/// Names are compared case-insensitively, as file systems often are.
+pub(super) fn matching_sidecar(audio_name: &str, sidecars: &[File]) -> Option<File> {
+    xc_sidecar::sidecar_names(audio_name).iter().find_map(|candidate| {
+        sidecars.iter().find(|f| f.name().eq_ignore_ascii_case(candidate)).cloned()
+    })
+}
+
+/// Metadata fields and reference hashes from a parsed `.xc.json` sidecar.
+fn xc_sidecar_fields(json: &serde_json::Value) -> (Vec<(String, String)>, Option<crate::state::SidecarHashes>) {
+    let hashes = extract_sidecar_hashes(json);
+    let hashes = if hashes.is_empty() { None } else { Some(hashes) };
+    (parse_xc_metadata(json), hashes)
+}
+
+/// Read and parse a sidecar file picked alongside its audio. Unreadable or
+/// unrelated JSON is logged and ignored: the recording still loads, just
+/// without the extra metadata.
+async fn read_sidecar_file(file: &File) -> Option<(Vec<(String, String)>, Option<crate::state::SidecarHashes>)> {
+    let name = file.name();
+    let text = match JsFuture::from(file.text()).await {
+        Ok(t) => t.as_string().unwrap_or_default(),
+        Err(e) => {
+            log::warn!("Couldn't read sidecar {name}: {e:?}");
+            return None;
+        }
+    };
+    let json = match serde_json::from_str::<serde_json::Value>(&text) {
+        Ok(json) => json,
+        Err(e) => {
+            log::warn!("Ignoring sidecar {name}: {e}");
+            return None;
+        }
+    };
+    if !xc_sidecar::accepts_sidecar(&name, &json) {
+        log::info!("{name} is not a xeno-canto record; not using it as a sidecar");
+        return None;
+    }
+    Some(xc_sidecar_fields(&json))
+}
+
+pub(super) async fn read_and_load_file(file: File, sidecar: Option<File>, state: AppState, load_id: u64) -> Result<(), String> {
     let name = file.name();
     let size = file.size();
     let last_modified_ms = Some(file.last_modified());
     let projected_total_open_bytes = total_open_file_bytes(state).saturating_add(size as u64);
     let force_streaming = projected_total_open_bytes >= TOTAL_OPEN_FILE_STREAMING_THRESHOLD;
+    let xc_sidecar = match &sidecar {
+        Some(f) => read_sidecar_file(f).await,
+        None => None,
+    };
 
-    // Helper: set last_modified_ms and compute file identity on the most recently added file
+    // Helper: attach the sidecar, set last_modified_ms and compute file
+    // identity on the most recently added file
     let name_for_identity = name.clone();
+    let sidecar_for_file = xc_sidecar.clone();
     let finalize_loaded_file = move |state: AppState, lm: Option<f64>| {
         let file_size = size as u64;
         let file_name = name_for_identity.clone();
+        let sidecar = sidecar_for_file.clone();
         state.files.update(|files| {
             if let Some(f) = files.last_mut() {
                 f.last_modified_ms = lm;
+                // Before identity computation, which checks the sidecar's hashes
+                if let Some((fields, hashes)) = sidecar {
+                    f.xc_metadata = Some(fields);
+                    f.xc_hashes = hashes;
+                }
             }
         });
         // Compute file identity (Layer 1 + Layer 2 async)
@@ -108,7 +272,8 @@ pub(super) async fn read_and_load_file(file: File, state: AppState, load_id: u64
         return Err(msg);
     }
     let bytes = read_file_bytes(&file).await?;
-    let result = load_named_bytes(name, &bytes, None, None, state, load_id, false).await;
+    let (xc_metadata, xc_hashes) = xc_sidecar.map_or((None, None), |(fields, hashes)| (Some(fields), hashes));
+    let result = load_named_bytes(name, &bytes, xc_metadata, xc_hashes, state, load_id, false).await;
     if result.is_ok() {
         finalize_loaded_file(state, last_modified_ms);
     }
@@ -136,6 +301,8 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
                 }
             }
         }
+    } else if state.keep_exact_samples.get_untracked() {
+        crate::audio::loader::load_audio_exact(bytes)?
     } else {
         load_audio(bytes)?
     };
@@ -156,6 +323,7 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
             }
         }
     }
+    let xc_metadata = xc_metadata.or_else(|| xc_metadata_from_tags(audio.metadata.guano.as_ref()));
     log::info!(
         "Loaded {}: {} samples, {} Hz, {:.2}s",
         name,
@@ -164,15 +332,57 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
         audio.duration_secs
     );
 
-    // Phase 1: fast preview
-    state.loading_update(load_id, crate::state::LoadingStage::Preview);
-    let preview = compute_preview(&audio, 256, 128);
-    let audio_for_stft = audio.clone();
-    let name_check = name.clone();
+    // Optional silence trim: the file shows a window onto the loaded samples,
+    // and its source keeps the original so exports and saved times are
+    // unaffected (see `AudioData::trim_offset_secs`).
+    let mut trim_notice = None;
+    if state.import_trim_silence.get_untracked() {
+        let region = crate::dsp::silence_trim::active_region(
+            &audio.samples,
+            audio.sample_rate,
+            state.import_trim_threshold_db.get_untracked() as f32,
+            state.import_trim_pad_ms.get_untracked() / 1000.0,
+        );
+        if let Some((start, end)) = region.filter(|&(s, e)| s > 0 || e < audio.samples.len()) {
+            let removed = (audio.samples.len() - (end - start)) as f64 / audio.sample_rate as f64;
+            audio = audio.trimmed(start, end);
+            wav_markers.retain(|m| (start as u64..end as u64).contains(&m.position));
+            for m in &mut wav_markers {
+                m.position -= start as u64;
+            }
+            trim_notice = Some(format!(
+                "Trimmed {:.2}s of silence ({:.2}s from start, {:.2}s from end)",
+                removed,
+                start as f64 / audio.sample_rate as f64,
+                removed - start as f64 / audio.sample_rate as f64,
+            ));
+        }
+    }
 
     const HOP_SIZE: usize = 512; // baseline LOD hop
     let fft_size: usize = state.spect_fft_mode.get_untracked().fft_for_lod(crate::canvas::tile_cache::LOD_BASELINE);
 
+    // Short clips skip the preview phase: the full spectrogram is cheap, and
+    // computing it directly avoids the preview-then-overview flash.
+    let single_pass = audio.duration_secs <= state.single_pass_max_secs.get_untracked();
+    let (preview, full_spec, overview_image) = if single_pass {
+        state.loading_update(load_id, crate::state::LoadingStage::Spectrogram(0));
+        let spec = compute_spectrogram(&audio, fft_size, HOP_SIZE);
+        let overview = compute_overview_from_spectrogram(&spec);
+        let preview = overview.clone().unwrap_or_else(|| {
+            let (preview_w, preview_h) = state.preview_size();
+            compute_preview(&audio, preview_w, preview_h)
+        });
+        (preview, Some(spec), overview)
+    } else {
+        // Phase 1: fast preview
+        state.loading_update(load_id, crate::state::LoadingStage::Preview);
+        let (preview_w, preview_h) = state.preview_size();
+        (compute_preview(&audio, preview_w, preview_h), None, None)
+    };
+    let audio_for_stft = audio.clone();
+    let name_check = name.clone();
+
     // Check for silent/quiet files — scan first 30s only
     let (silence_check, cached_peak_db) = {
         use crate::audio::source::{ChannelView, DEFAULT_ANALYSIS_WINDOW_SECS};
@@ -195,21 +405,18 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
     };
 
     let total_len = audio.source.total_samples() as usize;
-    let total_cols = if total_len >= fft_size {
-        (total_len - fft_size) / HOP_SIZE + 1
-    } else {
-        0
-    };
+    let total_cols = stft_frame_count(total_len, fft_size, HOP_SIZE);
 
-    let placeholder_spec = SpectrogramData {
+    let placeholder_spec = full_spec.clone().unwrap_or_else(|| SpectrogramData {
         columns: Arc::new(Vec::new()),
         total_columns: total_cols,
         freq_resolution: audio.sample_rate as f64 / fft_size as f64,
         time_resolution: HOP_SIZE as f64 / audio.sample_rate as f64,
         max_freq: audio.sample_rate as f64 / 2.0,
         sample_rate: audio.sample_rate,
-    };
+    });
 
+    let (min_display_freq, max_display_freq) = state.default_display_range_for(audio.sample_rate);
     let file_index;
     {
         let mut idx = 0;
@@ -220,7 +427,7 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
                 audio,
                 spectrogram: placeholder_spec,
                 preview: Some(preview),
-                overview_image: None,
+                overview_image,
                 xc_metadata,
                 xc_hashes,
                 is_demo,
@@ -239,8 +446,11 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
                 all_hashes_verified: false,
                 wav_markers,
                 loading_id: Some(load_id),
-                min_display_freq: None,
-                max_display_freq: None,
+                min_display_freq,
+                max_display_freq,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
             state.current_file_index.set(Some(idx));
         });
@@ -260,6 +470,11 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
 
     // Schedule async full-file peak scan (for files > 30s)
     crate::audio::peak::start_full_peak_scan(state, file_index);
+    check_identical_channels(state, file_index);
+
+    if let Some(notice) = trim_notice {
+        state.show_info_toast(notice);
+    }
 
     // Notify user about silent/quiet files
     if let Some(check) = silence_check {
@@ -279,7 +494,8 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
 
     // Set spectrogram metadata — tiles are computed on-demand by the tile
     // cache (schedule_tile_lod / schedule_tile_on_demand) as the user
-    // scrolls, so no upfront full-file STFT is needed.
+    // scrolls, so no upfront full-file STFT is needed. A single-pass load
+    // already holds its full spectrogram.
     let time_resolution = HOP_SIZE as f64 / audio_for_stft.sample_rate as f64;
     let freq_resolution = audio_for_stft.sample_rate as f64 / fft_size as f64;
     let max_freq = audio_for_stft.sample_rate as f64 / 2.0;
@@ -292,19 +508,22 @@ pub(crate) async fn load_named_bytes(name: String, bytes: &[u8], xc_metadata: Op
         max_freq,
         sample_rate: audio_for_stft.sample_rate,
     };
-    state.files.update(|files| {
-        if let Some(f) = files.get_mut(file_index) {
-            if f.name == name_check {
-                f.spectrogram = spectrogram;
+    if full_spec.is_none() {
+        state.files.update(|files| {
+            if let Some(f) = files.get_mut(file_index) {
+                if f.name == name_check {
+                    f.spectrogram = spectrogram;
+                }
             }
-        }
-    });
+        });
+    }
 
     // Initialise spectral store so on-demand tile computation can cache
     // STFT columns for chromagram and other consumers.
     spectral_store::init(file_index, total_cols, fft_size);
 
-    // Build waveform overview in the background
+    // Build waveform overview in the background (returns early when the
+    // single-pass load already made one)
     let name_for_overview = name_check.clone();
     wasm_bindgen_futures::spawn_local(build_streaming_overview(
         state,
@@ -322,6 +541,49 @@ const DEMO_SOUNDS_BASE: &str = "https://archive.oversample.com";
 const DEMO_SOUNDS_FALLBACK_BASE: &str =
     "https://cdn.jsdelivr.net/gh/pengowray/bat-demo-sounds@main";
 
+/// Recompute every loaded file's list preview at the current preview size,
+/// one file per browser turn so the list updates as it goes. Files loaded
+/// earlier otherwise keep the detail they were loaded with.
+pub(crate) fn rerender_all_previews(state: AppState) {
+    if state.preview_rerender_progress.get_untracked().is_some() {
+        return;
+    }
+    let names: Vec<String> = state.files.with_untracked(|files| files.iter().map(|f| f.name.clone()).collect());
+    let total = names.len();
+    if total == 0 {
+        return;
+    }
+    state.preview_rerender_progress.set(Some((0, total)));
+    wasm_bindgen_futures::spawn_local(async move {
+        let (preview_w, preview_h) = state.preview_size();
+        let mut rendered = 0;
+        for (i, name) in names.into_iter().enumerate() {
+            crate::canvas::tile_cache::yield_to_browser().await;
+            // The live file keeps updating its own preview while recording
+            let live = state.mic_live_file_idx.get_untracked() == Some(i);
+            let audio = state.files.with_untracked(|files| {
+                files.get(i)
+                    .filter(|f| f.name == name && !f.is_live_listen && !live)
+                    .map(|f| f.audio.clone())
+            });
+            if let Some(audio) = audio {
+                let preview = compute_preview(&audio, preview_w, preview_h);
+                state.files.update(|files| {
+                    if let Some(f) = files.get_mut(i) {
+                        if f.name == name {
+                            f.preview = Some(preview);
+                            rendered += 1;
+                        }
+                    }
+                });
+            }
+            state.preview_rerender_progress.set(Some((i + 1, total)));
+        }
+        state.preview_rerender_progress.set(None);
+        state.show_info_toast(format!("Re-rendered {rendered} preview{}", if rendered == 1 { "" } else { "s" }));
+    });
+}
+
 async fn fetch_demo_bytes(url: &str) -> Result<Vec<u8>, String> {
     match fetch_bytes(url).await {
         Ok(b) => Ok(b),
@@ -350,41 +612,207 @@ async fn fetch_demo_text(url: &str) -> Result<String, String> {
     }
 }
 
+thread_local! {
+    static NETWORK_OFFLINE: Cell<bool> = const { Cell::new(false) };
+    /// (timeout seconds, retries) for remote fetches.
+    static FETCH_POLICY: Cell<(u32, u32)> = const {
+        Cell::new((crate::state::DEFAULT_FETCH_TIMEOUT_SECS, crate::state::DEFAULT_FETCH_RETRIES))
+    };
+}
+
+/// Pause before retry `n` (1-based) is `n` times this.
+const FETCH_RETRY_DELAY_MS: u32 = 1_000;
+
+/// Error returned by every fetch while offline mode is on.
+const OFFLINE_ERROR: &str = "Offline mode is on \u{2014} network access is disabled";
+
+/// Block (or re-allow) all remote fetches. Kept in sync with
+/// `AppState::offline_mode` by an effect in `App`.
+pub(crate) fn set_network_offline(offline: bool) {
+    NETWORK_OFFLINE.with(|c| c.set(offline));
+}
+
+fn check_online() -> Result<(), String> {
+    if NETWORK_OFFLINE.with(|c| c.get()) {
+        return Err(OFFLINE_ERROR.to_string());
+    }
+    Ok(())
+}
+
+/// Set the fetch timeout and retry count. Kept in sync with
+/// `AppState::fetch_timeout_secs` / `fetch_retries` by an effect in `App`.
+pub(crate) fn set_fetch_policy(timeout_secs: u32, retries: u32) {
+    FETCH_POLICY.with(|c| c.set((timeout_secs, retries)));
+}
+
+/// Why a single fetch attempt failed, and whether another attempt could help.
+struct FetchFailure {
+    message: String,
+    retryable: bool,
+}
+
+impl FetchFailure {
+    fn fatal(message: impl Into<String>) -> Self {
+        Self { message: message.into(), retryable: false }
+    }
+
+    fn transient(message: impl Into<String>) -> Self {
+        Self { message: message.into(), retryable: true }
+    }
+}
+
+/// Fetch `url` and read its body as text or an ArrayBuffer, together with
+/// the Content-Type header. Each attempt is aborted after the configured
+/// timeout; timeouts, network errors and 5xx/429 responses are retried the
+/// configured number of times.
+async fn fetch_body(url: &str, as_text: bool) -> Result<(JsValue, Option<String>), String> {
+    check_online()?;
+    let (timeout_secs, retries) = FETCH_POLICY.with(|c| c.get());
+    let mut attempt = 0;
+    loop {
+        match fetch_body_once(url, as_text, timeout_secs).await {
+            Ok(body) => return Ok(body),
+            Err(f) if f.retryable && attempt < retries => {
+                attempt += 1;
+                log::warn!("Fetch of {url} failed ({}), retrying ({attempt}/{retries})", f.message);
+                let delay = (FETCH_RETRY_DELAY_MS * attempt) as i32;
+                let p = js_sys::Promise::new(&mut |resolve, _| {
+                    if let Some(w) = web_sys::window() {
+                        let _ = w.set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, delay);
+                    }
+                });
+                JsFuture::from(p).await.ok();
+                check_online()?;
+            }
+            Err(f) => return Err(f.message),
+        }
+    }
+}
+
+async fn fetch_body_once(url: &str, as_text: bool, timeout_secs: u32) -> Result<(JsValue, Option<String>), FetchFailure> {
+    let window = web_sys::window().ok_or_else(|| FetchFailure::fatal("No window"))?;
+    let controller = web_sys::AbortController::new().map_err(|e| FetchFailure::fatal(format!("{e:?}")))?;
+    let init = web_sys::RequestInit::new();
+    init.set_signal(Some(&controller.signal()));
+    let abort = {
+        let controller = controller.clone();
+        Closure::once(move || controller.abort())
+    };
+    let timer = window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(
+            abort.as_ref().unchecked_ref(),
+            (timeout_secs.saturating_mul(1000)).min(i32::MAX as u32) as i32,
+        )
+        .ok();
+
+    // The abort signal also cancels a body that stalls part-way through
+    let result = async {
+        let resp_value = JsFuture::from(window.fetch_with_str_and_init(url, &init))
+            .await
+            .map_err(|e| FetchFailure::transient(format!("fetch error: {e:?}")))?;
+        let resp: web_sys::Response = resp_value
+            .dyn_into()
+            .map_err(|_| FetchFailure::fatal("Response cast failed"))?;
+        if !resp.ok() {
+            let status = resp.status();
+            let message = format!("HTTP {status}");
+            return Err(if status >= 500 || status == 429 {
+                FetchFailure::transient(message)
+            } else {
+                FetchFailure::fatal(message)
+            });
+        }
+        let content_type = resp.headers().get("content-type").ok().flatten();
+        let read = if as_text { resp.text() } else { resp.array_buffer() };
+        let body = JsFuture::from(read.map_err(|e| FetchFailure::fatal(format!("{e:?}")))?)
+            .await
+            .map_err(|e| FetchFailure::transient(format!("reading response: {e:?}")))?;
+        Ok((body, content_type))
+    }
+    .await;
+
+    if let Some(id) = timer {
+        window.clear_timeout_with_handle(id);
+    }
+    if result.is_err() && controller.signal().aborted() {
+        return Err(FetchFailure::transient(format!("timed out after {timeout_secs} s")));
+    }
+    result
+}
+
 pub(super) async fn fetch_bytes(url: &str) -> Result<Vec<u8>, String> {
-    let window = web_sys::window().ok_or("No window")?;
-    let resp_value = JsFuture::from(window.fetch_with_str(url))
-        .await
-        .map_err(|e| format!("fetch error: {e:?}"))?;
-    let resp: web_sys::Response = resp_value
-        .dyn_into()
-        .map_err(|_| "Response cast failed".to_string())?;
-    if !resp.ok() {
-        return Err(format!("HTTP {}", resp.status()));
-    }
-    let buf = JsFuture::from(resp.array_buffer().map_err(|e| format!("{e:?}"))?)
-        .await
-        .map_err(|e| format!("array_buffer: {e:?}"))?;
+    fetch_bytes_with_type(url).await.map(|(bytes, _)| bytes)
+}
+
+/// Like `fetch_bytes`, but also returns the response's Content-Type header.
+async fn fetch_bytes_with_type(url: &str) -> Result<(Vec<u8>, Option<String>), String> {
+    let (buf, content_type) = fetch_body(url, false).await?;
     let uint8 = js_sys::Uint8Array::new(&buf);
-    Ok(uint8.to_vec())
+    Ok((uint8.to_vec(), content_type))
 }
 
 async fn fetch_text(url: &str) -> Result<String, String> {
-    let window = web_sys::window().ok_or("No window")?;
-    let resp_value = JsFuture::from(window.fetch_with_str(url))
-        .await
-        .map_err(|e| format!("fetch error: {e:?}"))?;
-    let resp: web_sys::Response = resp_value
-        .dyn_into()
-        .map_err(|_| "Response cast failed".to_string())?;
-    if !resp.ok() {
-        return Err(format!("HTTP {}", resp.status()));
-    }
-    let text = JsFuture::from(resp.text().map_err(|e| format!("{e:?}"))?)
-        .await
-        .map_err(|e| format!("text: {e:?}"))?;
+    let (text, _) = fetch_body(url, true).await?;
     text.as_string().ok_or("Not a string".to_string())
 }
 
+/// Xeno-canto fields recovered from embedded ID3/Vorbis/M4A tags, for a
+/// download that arrived without its `.xc.json` sidecar.
+pub(super) fn xc_metadata_from_tags(tags: Option<&crate::audio::guano::GuanoMetadata>) -> Option<Vec<(String, String)>> {
+    let fields = crate::audio::tags::xeno_canto_fields(&tags?.fields);
+    (!fields.is_empty()).then_some(fields)
+}
+
+/// Flag a multi-channel file whose channels are all the same signal over the
+/// first 30 s, and let the user know it can be collapsed to mono.
+pub(super) fn check_identical_channels(state: AppState, file_index: usize) {
+    use crate::audio::source::{channels_identical, DEFAULT_ANALYSIS_WINDOW_SECS};
+    let Some(source) = state.files.with_untracked(|files| files.get(file_index).map(|f| f.audio.source.clone())) else { return };
+    if source.channel_count() < 2 {
+        return;
+    }
+    let window = (DEFAULT_ANALYSIS_WINDOW_SECS * source.sample_rate() as f64) as usize;
+    if !channels_identical(source.as_ref(), 0, window) {
+        return;
+    }
+    state.files.update(|files| {
+        if let Some(f) = files.get_mut(file_index) {
+            f.identical_channels = true;
+        }
+    });
+    state.show_info_toast("Channels are identical \u{2014} file is effectively mono (see Metadata)");
+}
+
+/// Replace a file's in-memory source with a single-channel copy, after
+/// confirming over the whole file that its channels really are identical.
+/// Streaming sources can't be collapsed and are left alone.
+pub(super) fn collapse_to_mono(state: AppState, file_index: usize) {
+    use crate::audio::source::{channels_identical, InMemorySource};
+    let Some(source) = state.files.with_untracked(|files| files.get(file_index).map(|f| f.audio.source.clone())) else { return };
+    let Some(mem) = source.as_any().downcast_ref::<InMemorySource>() else {
+        state.show_error_toast("Only fully loaded files can be collapsed to mono");
+        return;
+    };
+    if !channels_identical(source.as_ref(), 0, source.total_samples() as usize) {
+        state.files.update(|files| {
+            if let Some(f) = files.get_mut(file_index) {
+                f.identical_channels = false;
+            }
+        });
+        state.show_error_toast("Channels differ later in the file \u{2014} keeping all channels");
+        return;
+    }
+    let mono: Arc<dyn crate::audio::source::AudioSource> = Arc::new(mem.to_mono());
+    state.files.update(|files| {
+        if let Some(f) = files.get_mut(file_index) {
+            f.audio.source = mono;
+            f.audio.channels = 1;
+            f.identical_channels = false;
+        }
+    });
+    state.show_info_toast("Treating file as mono");
+}
+
 fn parse_xc_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
     let mut fields = Vec::new();
     let s = |key: &str| json[key].as_str().unwrap_or("").to_string();
@@ -415,13 +843,36 @@ fn parse_xc_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
     if !lat.is_empty() && !lon.is_empty() {
         fields.push(("Coordinates".into(), format!("{}, {}", lat, lon)));
     }
+    let alt = s("alt");
+    if !alt.is_empty() {
+        fields.push(("Altitude".into(), format!("{} m", alt)));
+    }
     for (key, label) in [
         ("date", "Date"),
+        ("time", "Time"),
+        ("length", "Length"),
         ("type", "Sound type"),
         ("q", "Quality"),
         ("dvc", "Device"),
         ("mic", "Microphone"),
         ("method", "Method"),
+    ] {
+        let v = s(key);
+        if !v.is_empty() {
+            fields.push((label.into(), v));
+        }
+    }
+    // "also" (background species) is an array in both the API response and the sidecar
+    let also: Vec<&str> = json["also"]
+        .as_array()
+        .map(|arr| arr.iter().filter_map(|v| v.as_str()).filter(|v| !v.is_empty()).collect())
+        .unwrap_or_default();
+    if !also.is_empty() {
+        fields.push(("Also heard".into(), also.join(", ")));
+    }
+    for (key, label) in [
+        ("rmk", "Remarks"),
+        ("regnr", "Registration no."),
         ("url", "URL"),
     ] {
         let v = s(key);
@@ -429,6 +880,17 @@ fn parse_xc_metadata(json: &serde_json::Value) -> Vec<(String, String)> {
             fields.push((label.into(), v));
         }
     }
+    // Sidecars store a single URL; raw API JSON has a map of sizes, picked
+    // largest first as xc-lib's `api::image_url` does
+    for (key, label) in [("sono", "Sonogram"), ("osci", "Oscillogram")] {
+        let v = json[key].as_str()
+            .or_else(|| ["full", "large", "med", "small"].iter().find_map(|size| json[key][*size].as_str().filter(|u| !u.is_empty())))
+            .unwrap_or("");
+        if !v.is_empty() {
+            let v = if v.starts_with("//") { format!("https:{}", v) } else { v.to_string() };
+            fields.push((label.into(), v));
+        }
+    }
     fields
 }
 
@@ -575,9 +1037,8 @@ pub(crate) async fn load_single_demo(entry: &DemoEntry, state: AppState, load_id
             Ok(text) => {
                 match serde_json::from_str::<serde_json::Value>(&text) {
                     Ok(json) => {
-                        let hashes = extract_sidecar_hashes(&json);
-                        let hashes = if hashes.is_empty() { None } else { Some(hashes) };
-                        (Some(parse_xc_metadata(&json)), hashes)
+                        let (fields, hashes) = xc_sidecar_fields(&json);
+                        (Some(fields), hashes)
                     }
                     Err(e) => {
                         log::warn!("Failed to parse XC metadata for {}: {}", entry.filename, e);
@@ -605,6 +1066,78 @@ pub(crate) async fn load_single_demo(entry: &DemoEntry, state: AppState, load_id
     load_named_bytes(entry.filename.clone(), &bytes, xc_metadata, xc_hashes, state, load_id, true).await
 }
 
+/// Whether a Content-Type header could plausibly carry an audio file.
+/// Servers often send generic binary types for downloads, so only obviously
+/// non-audio types (HTML pages, JSON, images…) are rejected.
+fn is_audio_content_type(content_type: &str) -> bool {
+    let mime = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+    mime.is_empty()
+        || mime.starts_with("audio/")
+        || matches!(
+            mime.as_str(),
+            "application/octet-stream" | "binary/octet-stream" | "application/ogg"
+                | "application/x-flac" | "video/mp4" | "application/mp4"
+        )
+}
+
+/// Load an audio file from a user-supplied http(s) URL.
+///
+/// The file name is taken from the last path segment. If a `<stem>.xc.json`
+/// sidecar sits next to the audio, its xeno-canto metadata is attached.
+pub(crate) async fn load_from_url(url: &str, state: AppState, load_id: u64) -> Result<(), String> {
+    let parsed = web_sys::Url::new(url.trim()).map_err(|_| "Not a valid URL".to_string())?;
+    if !matches!(parsed.protocol().as_str(), "http:" | "https:") {
+        return Err("Only http and https URLs are supported".into());
+    }
+    let host = parsed.host();
+    let path = parsed.pathname();
+    let segment = path.rsplit('/').find(|s| !s.is_empty()).unwrap_or("");
+    let name = js_sys::decode_uri_component(segment)
+        .ok()
+        .and_then(|s| s.as_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| host.clone());
+    let url = parsed.href();
+
+    log::info!("Fetching URL: {url}");
+    let (bytes, content_type) = fetch_bytes_with_type(&url).await.map_err(|e| {
+        if e.starts_with("fetch error") {
+            format!(
+                "Couldn't fetch from {host} \u{2014} the server may not allow cross-origin requests (CORS). Download the file and open it instead."
+            )
+        } else {
+            e
+        }
+    })?;
+    if let Some(ct) = content_type.as_deref() {
+        if !is_audio_content_type(ct) {
+            let mime = ct.split(';').next().unwrap_or(ct).trim();
+            return Err(format!("URL returned {mime}, not audio \u{2014} use a direct link to the audio file"));
+        }
+    }
+
+    // Optional sidecar next to the audio: same path with the extension swapped for .xc.json
+    let (xc_metadata, xc_hashes) = match segment.rfind('.') {
+        Some(dot) if dot > 0 && path.ends_with(segment) => {
+            parsed.set_pathname(&format!("{}{}.xc.json", &path[..path.len() - segment.len()], &segment[..dot]));
+            parsed.set_search("");
+            match fetch_text(&parsed.href()).await
+                .ok()
+                .and_then(|text| serde_json::from_str::<serde_json::Value>(&text).ok())
+            {
+                Some(json) => {
+                    let (fields, hashes) = xc_sidecar_fields(&json);
+                    (Some(fields), hashes)
+                }
+                None => (None, None),
+            }
+        }
+        _ => (None, None),
+    };
+
+    load_named_bytes(name, &bytes, xc_metadata, xc_hashes, state, load_id, false).await
+}
+
 async fn read_file_bytes(file: &File) -> Result<Vec<u8>, String> {
     let reader = FileReader::new().map_err(|e| format!("FileReader: {e:?}"))?;
     let reader_clone = reader.clone();
@@ -664,8 +1197,29 @@ pub(crate) async fn load_native_file(path: String, state: AppState, load_id: u64
     let uint8 = js_sys::Uint8Array::new(&array_buffer);
     let bytes = uint8.to_vec();
 
+    // xeno-canto sidecar next to the file, under any of the usual names
+    let args = js_sys::Object::new();
+    let _ = js_sys::Reflect::set(&args, &wasm_bindgen::JsValue::from_str("path"), &wasm_bindgen::JsValue::from_str(&path));
+    let (xc_metadata, xc_hashes) = match crate::tauri_bridge::tauri_invoke("read_xc_sidecar", &args.into()).await {
+        Ok(v) => match v.as_string().map(|text| serde_json::from_str::<serde_json::Value>(&text)) {
+            Some(Ok(json)) => {
+                let (fields, hashes) = xc_sidecar_fields(&json);
+                (Some(fields), hashes)
+            }
+            Some(Err(e)) => {
+                log::warn!("Ignoring sidecar for {name}: {e}");
+                (None, None)
+            }
+            None => (None, None),
+        },
+        Err(e) => {
+            log::warn!("read_xc_sidecar failed for {name}: {e}");
+            (None, None)
+        }
+    };
+
     // Decode and add to state using existing pipeline
-    load_named_bytes(name.clone(), &bytes, None, None, state, load_id, false).await?;
+    load_named_bytes(name.clone(), &bytes, xc_metadata, xc_hashes, state, load_id, false).await?;
 
     // The file was just added — set the native path on identity
     let file_index = state.files.get_untracked().len().saturating_sub(1);
@@ -688,6 +1242,8 @@ pub(crate) async fn load_native_file(path: String, state: AppState, load_id: u64
     if let Some(id) = identity {
         crate::opfs::load_annotations(state, file_index, id);
     }
+    // Same for the `.notes.json` sidecar.
+    crate::file_notes::load_notes(state, file_index);
 
     Ok(())
 }
@@ -1,5 +1,7 @@
 use leptos::prelude::*;
-use crate::state::AppState;
+use wasm_bindgen::JsCast;
+use crate::state::{AppState, MetadataOverlap};
+use crate::audio::metadata_overlap::{find_overlaps, Overlap};
 
 /// Returns (section, display_key) for a GUANO field.
 /// Known fields return "GUANO" as section; unknown pipe-separated keys
@@ -93,6 +95,39 @@ fn hash_row(label: &str, hash: &str, reference: Option<&str>, from_reference: bo
     }
 }
 
+/// Note under a shared field shown from one source, giving the other
+/// source's differing value.
+fn overlap_note(other_source: &str, value: &str) -> impl IntoView {
+    let text = format!("{other_source} differs: {value}");
+    view! {
+        <div class="setting-row metadata-row">
+            <span class="setting-label hash-note" title=text.clone()>{text}</span>
+        </div>
+    }
+}
+
+/// Cross-check row for a field both sources carry: the shared value with a
+/// tick, or both values with a cross when they differ.
+fn overlap_row(o: Overlap) -> impl IntoView {
+    let (value, indicator, indicator_class, title) = if o.agrees {
+        (o.xc_value, "\u{2713}", "hash-indicator match", "Xeno-canto and GUANO agree".to_string())
+    } else {
+        (
+            format!("{} (XC) \u{2260} {} (GUANO)", o.xc_value, o.guano_value),
+            "\u{2717}",
+            "hash-indicator mismatch",
+            format!("Xeno-canto {}: {}\nGUANO {}: {}", o.xc_label, o.xc_value, o.guano_keys.join(" + "), o.guano_value),
+        )
+    };
+    view! {
+        <div class="setting-row metadata-row" title=title>
+            <span class="setting-label">{o.label}</span>
+            <span class="setting-value metadata-value">{value}</span>
+            <span class=indicator_class>{indicator}</span>
+        </div>
+    }
+}
+
 fn format_file_size(bytes: usize) -> String {
     if bytes < 1024 {
         format!("{} B", bytes)
@@ -258,12 +293,58 @@ fn file_identity_section(f: &crate::state::LoadedFile) -> impl IntoView {
     }
 }
 
+/// Free-text note for the current file. Kept outside the main metadata closure
+/// so the textarea isn't rebuilt (losing focus) on every keystroke.
+#[component]
+fn FileNotes() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    let notes = Memo::new(move |_| {
+        let idx = state.current_file_index.get();
+        state.files.with(|files| idx.and_then(|i| files.get(i)).map(|f| f.notes.clone()).unwrap_or_default())
+    });
+    let read_only = Memo::new(move |_| {
+        let idx = state.current_file_index.get();
+        state.files.with(|files| idx.and_then(|i| files.get(i)).is_some_and(|f| f.read_only))
+    });
+    let on_input = move |ev: web_sys::Event| {
+        let textarea: web_sys::HtmlTextAreaElement = ev.target().unwrap().unchecked_into();
+        if let Some(idx) = state.current_file_index.get_untracked() {
+            crate::file_notes::set_notes(state, idx, textarea.value());
+        }
+    };
+
+    view! {
+        <div class="setting-group">
+            <div class="setting-group-title">"Notes"</div>
+            <textarea
+                class="project-notes-input file-notes-input"
+                placeholder="e.g. Pip, weak pass, possible social call"
+                prop:value=move || notes.get()
+                on:input=on_input
+            ></textarea>
+            {move || {
+                let hint = if read_only.get() {
+                    "Read-only file \u{2014} note is not saved"
+                } else if state.is_tauri {
+                    "Saved to a .notes.json file next to the recording"
+                } else {
+                    "Saved in this browser"
+                };
+                view! { <div class="setting-hint">{hint}</div> }
+            }}
+        </div>
+    }
+}
+
 #[component]
 pub(crate) fn MetadataPanel() -> impl IntoView {
     let state = expect_context::<AppState>();
 
     view! {
         <div class="sidebar-panel">
+            <Show when=move || state.current_file_index.get().is_some()>
+                <FileNotes />
+            </Show>
             {move || {
                 let files = state.files.get();
                 let idx = state.current_file_index.get();
@@ -292,6 +373,19 @@ pub(crate) fn MetadataPanel() -> impl IntoView {
                             .map(|g| g.fields.clone())
                             .unwrap_or_default();
                         let has_guano = !guano_fields.is_empty();
+                        let is_guano_source = matches!(meta.format, "WAV" | "W4V");
+                        let overlap_mode = state.metadata_overlap.get();
+                        let overlaps = if has_xc && is_guano_source {
+                            find_overlaps(&xc_fields, &guano_fields)
+                        } else {
+                            Vec::new()
+                        };
+                        let channels_str = if f.identical_channels {
+                            format!("{} (identical \u{2014} effectively mono)", f.audio.channels)
+                        } else {
+                            f.audio.channels.to_string()
+                        };
+                        let can_collapse = f.identical_channels && f.audio.source.is_fully_loaded();
 
                         view! {
                             <div class="setting-group">
@@ -300,14 +394,39 @@ pub(crate) fn MetadataPanel() -> impl IntoView {
                                 {metadata_row("Format".into(), meta.format.to_string(), None)}
                                 {metadata_row("Duration".into(), crate::format_time::format_duration(f.audio.duration_secs, 3), None)}
                                 {metadata_row("Sample rate".into(), format!("{} kHz", f.audio.sample_rate / 1000), None)}
-                                {metadata_row("Channels".into(), f.audio.channels.to_string(), None)}
+                                {metadata_row("Channels".into(), channels_str, None)}
+                                {if can_collapse {
+                                    let on_mono = move |_: web_sys::MouseEvent| {
+                                        if let Some(idx) = state.current_file_index.get_untracked() {
+                                            super::loading::collapse_to_mono(state, idx);
+                                        }
+                                    };
+                                    view! {
+                                        <div class="setting-row metadata-row">
+                                            <button class="setting-button" on:click=on_mono
+                                                title="Drop the duplicate channels and keep one copy in memory">"Treat as mono"</button>
+                                        </div>
+                                    }.into_any()
+                                } else {
+                                    view! { <span></span> }.into_any()
+                                }}
                                 {metadata_row("Bit depth".into(), format!("{}-bit", meta.bits_per_sample), None)}
                                 {metadata_row(size_label, size_str, None)}
                             </div>
                             {if has_xc {
-                                let items: Vec<_> = xc_fields.into_iter().map(|(label, value)| {
-                                    metadata_row(label, value, None).into_any()
-                                }).collect();
+                                let mut items: Vec<leptos::tachys::view::any_view::AnyView> = Vec::new();
+                                for (label, value) in xc_fields {
+                                    let shared: Vec<&Overlap> = overlaps.iter().filter(|o| o.xc_label == label).collect();
+                                    if overlap_mode == MetadataOverlap::PreferGuano && !shared.is_empty() {
+                                        continue;
+                                    }
+                                    items.push(metadata_row(label, value, None).into_any());
+                                    if overlap_mode == MetadataOverlap::PreferXc {
+                                        for o in shared.iter().filter(|o| !o.agrees) {
+                                            items.push(overlap_note("GUANO", &o.guano_value).into_any());
+                                        }
+                                    }
+                                }
                                 view! {
                                     <div class="setting-group">
                                         <div class="setting-group-title">"Xeno-canto"</div>
@@ -318,7 +437,6 @@ pub(crate) fn MetadataPanel() -> impl IntoView {
                                 view! { <span></span> }.into_any()
                             }}
                             {if has_guano {
-                                let is_guano_source = matches!(meta.format, "WAV" | "W4V");
                                 let default_section: &str = if is_guano_source {
                                     "Guano metadata"
                                 } else {
@@ -327,6 +445,12 @@ pub(crate) fn MetadataPanel() -> impl IntoView {
                                 let mut items: Vec<leptos::tachys::view::any_view::AnyView> = Vec::new();
                                 let mut current_section: Option<String> = None;
                                 for (k, v) in guano_fields {
+                                    let shared: Vec<&Overlap> = overlaps.iter()
+                                        .filter(|o| o.guano_keys.contains(&k.as_str()))
+                                        .collect();
+                                    if overlap_mode == MetadataOverlap::PreferXc && !shared.is_empty() {
+                                        continue;
+                                    }
                                     let (section, display_key) = if is_guano_source {
                                         let (s, d) = categorize_guano_key(&k);
                                         let s = if s == "GUANO" { default_section.to_string() } else { s };
@@ -349,7 +473,18 @@ pub(crate) fn MetadataPanel() -> impl IntoView {
                                         }.into_any());
                                         current_section = Some(section);
                                     }
+                                    let notes: Vec<String> = if overlap_mode == MetadataOverlap::PreferGuano {
+                                        shared.iter()
+                                            .filter(|o| !o.agrees && o.guano_keys.first() == Some(&k.as_str()))
+                                            .map(|o| o.xc_value.clone())
+                                            .collect()
+                                    } else {
+                                        Vec::new()
+                                    };
                                     items.push(metadata_row(display_key, v, Some(k)).into_any());
+                                    for value in notes {
+                                        items.push(overlap_note("Xeno-canto", &value).into_any());
+                                    }
                                 }
                                 view! {
                                     <div class="setting-group">
@@ -359,6 +494,23 @@ pub(crate) fn MetadataPanel() -> impl IntoView {
                             } else {
                                 view! { <span></span> }.into_any()
                             }}
+                            {if overlap_mode == MetadataOverlap::ShowBoth && !overlaps.is_empty() {
+                                let differ = overlaps.iter().filter(|o| !o.agrees).count();
+                                let rows: Vec<_> = overlaps.into_iter().map(overlap_row).collect();
+                                view! {
+                                    <div class="setting-group">
+                                        <div class="setting-group-title">
+                                            "Xeno-canto vs GUANO"
+                                            {(differ > 0).then(|| view! {
+                                                <span class="metadata-source-badge">{format!("{differ} differ")}</span>
+                                            })}
+                                        </div>
+                                        {rows}
+                                    </div>
+                                }.into_any()
+                            } else {
+                                view! { <span></span> }.into_any()
+                            }}
                             // File Identity / Hash section — hidden while recording in progress
                             {if !f.is_recording {
                                 file_identity_section(f).into_any()
@@ -2,11 +2,13 @@ pub(crate) mod file_groups;
 pub(crate) mod file_badges;
 mod files_panel;
 mod config_panel;
+mod colormap_editor;
 mod export_section;
 mod project_panel;
 pub(crate) use project_panel::save_project_async;
 pub mod settings_panel;
 pub mod analysis;
+mod band_activity;
 pub mod metadata_panel;
 pub mod harmonics;
 pub mod notch_panel;
@@ -36,7 +38,7 @@ pub(crate) use harmonics::HarmonicsPanel;
 pub(crate) use notch_panel::NotchPanel;
 pub(crate) use psd_panel::PsdPanel;
 pub(crate) use pulse_panel::PulsePanel;
-pub(crate) use loading::{load_named_bytes, load_native_file, fetch_demo_index, load_single_demo};
+pub(crate) use loading::{load_dropped_files, load_named_bytes, load_native_paths, open_pending_native_files, start_queued_loads, fetch_demo_index, load_single_demo, rerender_all_previews, set_fetch_policy, set_network_offline};
 
 fn copy_to_clipboard(text: &str) {
     if let Some(window) = web_sys::window() {
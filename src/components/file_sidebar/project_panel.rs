@@ -475,11 +475,11 @@ fn ProjectView(project: BatProject) -> impl IntoView {
                 state.current_file_index.set(None);
                 state.suspend_follow();
                 if canvas_w > 0.0 && primary_time_res > 0.0 && timeline_duration > 0.0 {
-                    let fit_zoom = ((canvas_w * primary_time_res) / timeline_duration).clamp(viewport::MIN_ZOOM, viewport::MAX_ZOOM);
+                    let fit_zoom = viewport::fit_zoom(canvas_w, primary_time_res, timeline_duration, state.max_zoom());
                     state.zoom_level.set(fit_zoom);
                     let visible_time = viewport::visible_time(canvas_w, fit_zoom, primary_time_res);
-                    let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
-                    state.scroll_offset.set(viewport::clamp_scroll_for_mode(0.0, timeline_duration, visible_time, from_here_mode));
+                    let from_here = state.from_here_fraction();
+                    state.scroll_offset.set(viewport::clamp_scroll_for_mode(0.0, timeline_duration, visible_time, from_here));
                 } else {
                     state.scroll_offset.set(0.0);
                 }
@@ -717,11 +717,11 @@ fn ProjectView(project: BatProject) -> impl IntoView {
                                 state.current_file_index.set(None);
                                 state.suspend_follow();
                                 if canvas_w > 0.0 && primary_time_res > 0.0 && timeline_duration > 0.0 {
-                                    let fit_zoom = ((canvas_w * primary_time_res) / timeline_duration).clamp(viewport::MIN_ZOOM, viewport::MAX_ZOOM);
+                                    let fit_zoom = viewport::fit_zoom(canvas_w, primary_time_res, timeline_duration, state.max_zoom());
                                     state.zoom_level.set(fit_zoom);
                                     let visible_time = viewport::visible_time(canvas_w, fit_zoom, primary_time_res);
-                                    let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
-                                    state.scroll_offset.set(viewport::clamp_scroll_for_mode(0.0, timeline_duration, visible_time, from_here_mode));
+                                    let from_here = state.from_here_fraction();
+                                    state.scroll_offset.set(viewport::clamp_scroll_for_mode(0.0, timeline_duration, visible_time, from_here));
                                 } else {
                                     state.scroll_offset.set(0.0);
                                 }
@@ -821,7 +821,7 @@ fn ProjectView(project: BatProject) -> impl IntoView {
 // ─── Helpers ────────────────────────────────────────────────────────────────
 
 /// Trigger a browser file download with text content.
-fn download_text(content: &str, filename: &str) {
+pub(super) fn download_text(content: &str, filename: &str) {
     let Some(window) = web_sys::window() else { return };
     let Some(document) = window.document() else { return };
 
@@ -1,18 +1,21 @@
 use leptos::prelude::*;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen_futures::spawn_local;
-use crate::state::{AppState, RightSidebarTab};
-use crate::dsp::pulse_detect::{self, DetectedPulse, PulseDetectionParams};
+use crate::state::{AppState, FileKey, RightSidebarTab};
+use crate::audio::source::ChannelView;
+use crate::dsp::call_params;
+use crate::dsp::pulse_detect::{self, DetectedPulse};
+use crate::dsp::smoothing::Smoothing;
 
 #[component]
 pub(crate) fn PulsePanel() -> impl IntoView {
     let state = expect_context::<AppState>();
 
-    // Local detection parameters
-    let threshold_db = RwSignal::new(6.0f64);
-    let min_duration_ms = RwSignal::new(0.3f64);
-    let max_duration_ms = RwSignal::new(50.0f64);
-    let min_gap_ms = RwSignal::new(3.0f64);
+    // Detection parameters, shared with the species hint
+    let threshold_db = state.pulse_threshold_db;
+    let min_duration_ms = state.pulse_min_duration_ms;
+    let max_duration_ms = state.pulse_max_duration_ms;
+    let min_snr_db = state.pulse_min_snr_db;
 
     // Generation counter for cancellation
     let compute_gen = RwSignal::new(0u32);
@@ -20,6 +23,10 @@ pub(crate) fn PulsePanel() -> impl IntoView {
     let last_computed_ff: RwSignal<(f64, f64)> = RwSignal::new((0.0, 0.0));
     // Bumped by Re-detect to force the Effect to re-run without remounting the component
     let redetect_trigger = RwSignal::new(0u32);
+    // (files done, total) while a batch CSV export is running
+    let batch_progress: RwSignal<Option<(usize, usize)>> = RwSignal::new(None);
+
+    let detection_params = move || state.pulse_detection_params();
 
     // Trigger pulse detection when tab is active and file changes
     Effect::new(move || {
@@ -62,24 +69,12 @@ pub(crate) fn PulsePanel() -> impl IntoView {
 
         let audio = file.audio.clone();
         let spectrogram = file.spectrogram.clone();
-        let thresh = threshold_db.get_untracked();
-        let min_dur = min_duration_ms.get_untracked();
-        let max_dur = max_duration_ms.get_untracked();
-        let gap = min_gap_ms.get_untracked();
+        let params = detection_params();
 
         spawn_local(async move {
             yield_to_browser().await;
             if compute_gen.get_untracked() != generation { return; }
 
-            let params = PulseDetectionParams {
-                min_pulse_duration_ms: min_dur,
-                max_pulse_duration_ms: max_dur,
-                min_gap_ms: gap,
-                threshold_db: thresh,
-                bandpass_low_hz: band_ff_lo,
-                bandpass_high_hz: if band_ff_hi > band_ff_lo { band_ff_hi } else { 0.0 },
-            };
-
             let pulses = pulse_detect::detect_pulses(&audio, &spectrogram, &params);
 
             if compute_gen.get_untracked() != generation { return; }
@@ -98,6 +93,41 @@ pub(crate) fn PulsePanel() -> impl IntoView {
         redetect_trigger.update(|t| *t += 1);
     };
 
+    // Detect and measure calls in every loaded file, then download one CSV
+    let on_batch_export = move |_: web_sys::MouseEvent| {
+        if batch_progress.get_untracked().is_some() {
+            return;
+        }
+        let params = detection_params();
+        let keys: Vec<FileKey> = state.files.with_untracked(|files| files.iter().map(|f| f.key()).collect());
+        let total = keys.len();
+        batch_progress.set(Some((0, total)));
+
+        spawn_local(async move {
+            let mut csv = String::from(call_params::CSV_HEADER);
+            for (i, key) in keys.iter().enumerate() {
+                yield_to_browser().await;
+                // Files may have been closed or reordered since the export started
+                let next = state.files.with_untracked(|files| {
+                    files.iter().find(|f| f.key() == *key).filter(|f| f.loading_id.is_none()).map(|f| {
+                        // Whole original file, so times match the recording
+                        let source = f.audio.untrimmed_source();
+                        let n = source.total_samples() as usize;
+                        let samples = source.read_region(ChannelView::MonoMix, 0, n);
+                        (f.name.clone(), samples, f.audio.sample_rate)
+                    })
+                });
+                if let Some((name, samples, sr)) = next {
+                    let calls = call_params::measure_calls(&samples, sr, &params);
+                    csv.push_str(&call_params::csv_rows(&name, &calls));
+                }
+                batch_progress.set(Some((i + 1, total)));
+            }
+            super::project_panel::download_text(&csv, "pulses.csv");
+            batch_progress.set(None);
+        });
+    };
+
     // Click a pulse to navigate
     let on_pulse_click = move |pulse: DetectedPulse| {
         state.selected_pulse_index.set(Some(pulse.index));
@@ -137,6 +167,23 @@ pub(crate) fn PulsePanel() -> impl IntoView {
                         }
                     />
                 </div>
+                <div class="setting-row">
+                    <span class="setting-label" title="Ignore pulses whose peak is less than this far above the noise around them">"Min SNR"</span>
+                    <span class="setting-value">{move || if min_snr_db.get() > 0.0 { format!("{:.0} dB", min_snr_db.get()) } else { "Off".to_string() }}</span>
+                </div>
+                <div class="setting-row">
+                    <input
+                        type="range"
+                        class="setting-range"
+                        min="0" max="30" step="1"
+                        prop:value=move || min_snr_db.get().to_string()
+                        on:input=move |ev| {
+                            if let Ok(v) = event_target_value(&ev).parse::<f64>() {
+                                min_snr_db.set(v);
+                            }
+                        }
+                    />
+                </div>
                 <div class="setting-row">
                     <span class="setting-label">"Min duration"</span>
                     <span class="setting-value">{move || format!("{:.1} ms", min_duration_ms.get())}</span>
@@ -184,10 +231,30 @@ pub(crate) fn PulsePanel() -> impl IntoView {
                         " Show overlay"
                     </label>
                 </div>
+                <div class="setting-row"
+                    title="Smooth each call's frequency contour before its start, end and characteristic \
+                           frequencies are measured for the CSV. Shared with the Harmonics flux timeline.">
+                    <span class="setting-label">"Contour smoothing"</span>
+                    <SmoothingSelect/>
+                </div>
                 <div class="setting-row">
                     <button class="setting-button" on:click=on_redetect>"Re-detect"</button>
                 </div>
             </div>
+            <div class="setting-group">
+                <div class="setting-group-title">"Batch"</div>
+                <div class="setting-row">
+                    <button
+                        class="setting-button"
+                        title="Detect pulses in every loaded file and download start/end/peak/characteristic frequency, duration, bandwidth and interval as CSV"
+                        prop:disabled=move || batch_progress.get().is_some() || state.files.with(|f| f.is_empty())
+                        on:click=on_batch_export
+                    >"Export CSV (all files)"</button>
+                </div>
+                {move || batch_progress.get().map(|(done, total)| view! {
+                    <div class="setting-hint">{format!("Measuring file {} of {}\u{2026}", (done + 1).min(total), total)}</div>
+                })}
+            </div>
             // Status / Results
             {move || {
                 let files = state.files.get();
@@ -220,16 +287,23 @@ pub(crate) fn PulsePanel() -> impl IntoView {
                     let pulse = p.clone();
                     let pulse2 = p.clone();
                     let is_selected = selected == Some(p.index);
-                    let item_class = if is_selected { "pulse-item selected" } else { "pulse-item" };
+                    let item_class = match (is_selected, p.is_usable()) {
+                        (true, true) => "pulse-item selected",
+                        (true, false) => "pulse-item selected faint",
+                        (false, true) => "pulse-item",
+                        (false, false) => "pulse-item faint",
+                    };
                     let dur_ms = p.duration_ms();
                     let freq_khz = p.peak_freq / 1000.0;
                     let time_text = crate::format_time::format_time_display(p.start_time, 3);
                     let dur_text = format!("{:.1}ms", dur_ms);
                     let freq_text = format!("{:.1}kHz", freq_khz);
                     let snr_text = format!("{:.0}dB", p.snr_db);
+                    let local_snr_text = format!("{:.0}dB", p.local_snr_db);
                     let tooltip = format!(
-                        "Pulse #{}: {:.4}s \u{2013} {:.4}s ({:.2}ms)\nPeak freq: {:.1} kHz\nSNR: {:.1} dB",
-                        p.index, p.start_time, p.end_time, dur_ms, freq_khz, p.snr_db
+                        "Pulse #{}: {:.4}s \u{2013} {:.4}s ({:.2}ms)\nPeak freq: {:.1} kHz\nSNR (file noise floor): {:.1} dB\nSNR (local): {:.1} dB{}",
+                        p.index, p.start_time, p.end_time, dur_ms, freq_khz, p.snr_db, p.local_snr_db,
+                        if p.is_usable() { "" } else { "\nToo faint for reliable ID" }
                     );
 
                     view! {
@@ -243,13 +317,26 @@ pub(crate) fn PulsePanel() -> impl IntoView {
                             <span class="pulse-dur">{dur_text}</span>
                             <span class="pulse-freq">{freq_text}</span>
                             <span class="pulse-snr">{snr_text}</span>
+                            <span class="pulse-local-snr">{local_snr_text}</span>
                         </div>
                     }
                 }).collect();
+                let usable = pulses.iter().filter(|p| p.is_usable()).count();
 
                 view! {
                     <div class="setting-group">
                         <div class="setting-group-title">{format!("Pulses ({})", count)}</div>
+                        <div class="setting-hint">
+                            {format!("{} of {} with local SNR \u{2265} {:.0} dB", usable, count, pulse_detect::USABLE_PULSE_SNR_DB)}
+                        </div>
+                        <div class="pulse-item pulse-header">
+                            <span class="pulse-index">"#"</span>
+                            <span class="pulse-time">"Time"</span>
+                            <span class="pulse-dur">"Dur"</span>
+                            <span class="pulse-freq">"Peak"</span>
+                            <span class="pulse-snr" title="Peak vs whole-file noise floor">"SNR"</span>
+                            <span class="pulse-local-snr" title="Peak vs noise in the adjacent gaps">"Local"</span>
+                        </div>
                         <div class="pulse-list">
                             {pulse_items}
                         </div>
@@ -290,3 +377,30 @@ async fn yield_to_browser() {
     });
     let _ = wasm_bindgen_futures::JsFuture::from(promise).await;
 }
+
+/// Picker for `state.contour_smoothing`, shared by the Pulses and Harmonics panels.
+#[component]
+pub(super) fn SmoothingSelect() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    view! {
+        <select
+            class="setting-select"
+            on:change=move |ev: web_sys::Event| {
+                let target = ev.target().unwrap();
+                let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                let smoothing = Smoothing::from_key(&select.value());
+                state.contour_smoothing.set(smoothing);
+                if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                    let _ = ls.set_item("oversample_contour_smoothing", &smoothing.key());
+                }
+            }
+        >
+            {Smoothing::PRESETS.iter().map(|&s| view! {
+                <option
+                    value=s.key()
+                    selected=move || state.contour_smoothing.get() == s
+                >{s.label()}</option>
+            }).collect::<Vec<_>>()}
+        </select>
+    }
+}
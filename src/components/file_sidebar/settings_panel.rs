@@ -1,10 +1,12 @@
 use leptos::prelude::*;
 use wasm_bindgen::JsCast;
+use crate::canvas::spectrogram_renderer::{Colormap, FlowShiftParams};
 use crate::state::{
-    AppState, FlowColorScheme, MainView, ResonatorFftMode, ResonatorLayout, SpectrogramDisplay,
+    AppState, FlowBlendMode, FlowColorScheme, MainView, ResonatorFftMode, ResonatorLayout, SpectrogramDisplay,
     RESONATOR_BW_SLIDER_MAX, resonator_bw_to_slider, resonator_slider_to_bw,
 };
 use crate::annotations::{Annotation, AnnotationKind, AnnotationSet, Group, generate_uuid, now_iso8601, build_annotation_tree, AnnotationNode, collect_descendants, renumber_children};
+use crate::units::FreqUnit;
 
 /// Sample rate used to annotate the Resonators settings panel — the live
 /// waterfall's rate when mic is active, otherwise the current file's, with a
@@ -20,6 +22,59 @@ fn current_resonator_sample_rate(state: AppState) -> f64 {
         .unwrap_or(192_000.0)
 }
 
+/// Audio read per block by [`export_zc_track`], in seconds.
+const ZC_TRACK_BLOCK_SECS: f64 = 10.0;
+
+/// Download the zero-crossing frequency track of the current file (or just
+/// the selected time range) as `<name>_zc_track.csv`. The audio is read in
+/// blocks, so a long recording is never held in memory whole.
+fn export_zc_track(state: AppState) {
+    use crate::audio::source::ChannelView;
+    use crate::dsp::zero_crossing;
+
+    let window_secs = state.zc_track_window_ms.get_untracked() / 1000.0;
+    let selection = state.selection.get_untracked();
+    let Some(file) = state.current_file_index.get_untracked()
+        .and_then(|i| state.files.with_untracked(|files| files.get(i).cloned()))
+    else {
+        return;
+    };
+    let sr = file.audio.sample_rate;
+    if sr == 0 {
+        return;
+    }
+    let total = file.audio.source.total_samples() as usize;
+    let (start, end) = match selection {
+        Some(sel) => (
+            ((sel.time_start * sr as f64) as usize).min(total),
+            ((sel.time_end * sr as f64) as usize).min(total),
+        ),
+        None => (0, total),
+    };
+    // Times in the CSV are in original-file time
+    let trim = file.audio.trim_offset_secs();
+    // Blocks hold whole windows, so the track matches a single read of the range
+    let window = zero_crossing::track_window_samples(window_secs, sr);
+    let block = window * ((ZC_TRACK_BLOCK_SECS * sr as f64) as usize / window).max(1);
+
+    wasm_bindgen_futures::spawn_local(async move {
+        let mut csv = String::from(zero_crossing::TRACK_CSV_HEADER);
+        let mut pos = start;
+        while pos < end {
+            crate::canvas::tile_cache::yield_to_browser().await;
+            // One sample past the block, so a crossing on its edge is counted once
+            let len = (block + 1).min(end - pos);
+            let samples = file.audio.source.read_region(ChannelView::MonoMix, pos as u64, len);
+            let start_time = pos as f64 / sr as f64 + trim;
+            let track = zero_crossing::zero_crossing_track(&samples, sr, window_secs, start_time);
+            csv.push_str(&zero_crossing::track_csv_rows(&track));
+            pos += block;
+        }
+        let stem = file.name.rsplit_once('.').map(|(s, _)| s).unwrap_or(&file.name);
+        super::project_panel::download_text(&csv, &format!("{stem}_zc_track.csv"));
+    });
+}
+
 /// Format a frequency as "X Hz" or "X.X kHz" depending on scale.
 fn format_hz(hz: f64) -> String {
     if hz >= 1000.0 {
@@ -220,6 +275,80 @@ pub(crate) fn SpectrogramSettingsPanel() -> impl IntoView {
                                     view! { <span></span> }.into_any()
                                 }
                             }}
+                            // Coherence colormap + legend (phase coherence only)
+                            {move || {
+                                (state.spectrogram_display.get() == SpectrogramDisplay::PhaseCoherence).then(|| {
+                                    let legend = match state.coherence_colormap.get() {
+                                        Some(cm) => view! {
+                                            <div class="coherence-legend">
+                                                <div class="coherence-legend-bar" style=format!("background: {}", cm.css_gradient())></div>
+                                                <div class="coherence-legend-labels">
+                                                    <span>"Drifting"</span>
+                                                    <span>"Coherent"</span>
+                                                </div>
+                                            </div>
+                                        }.into_any(),
+                                        None => view! {
+                                            <div class="setting-hint">
+                                                "Blue-white = coherent \u{2014} red = phase running ahead \u{2014} deep blue = lagging"
+                                            </div>
+                                        }.into_any(),
+                                    };
+                                    view! {
+                                        <div class="setting-row">
+                                            <span class="setting-label">"Colormap"</span>
+                                            <select
+                                                class="setting-select"
+                                                on:change=move |ev: web_sys::Event| {
+                                                    let target = ev.target().unwrap();
+                                                    let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                                                    let cm = select.value().parse::<usize>().ok()
+                                                        .and_then(|i| Colormap::ALL.get(i).copied());
+                                                    state.coherence_colormap.set(cm);
+                                                }
+                                            >
+                                                <option value="" selected=move || state.coherence_colormap.get().is_none()>"Deviation"</option>
+                                                {Colormap::ALL.iter().enumerate().map(|(i, &cm)| view! {
+                                                    <option value=i.to_string() selected=move || state.coherence_colormap.get() == Some(cm)>
+                                                        {cm.label()}
+                                                    </option>
+                                                }).collect::<Vec<_>>()}
+                                            </select>
+                                        </div>
+                                        {legend}
+                                    }
+                                })
+                            }}
+                            // Blend mode (phase view has no grey base to composite onto)
+                            {move || {
+                                (state.spectrogram_display.get() != SpectrogramDisplay::Phase).then(|| view! {
+                                    <div class="setting-row">
+                                        <span class="setting-label">"Blend"</span>
+                                        <select
+                                            class="setting-select"
+                                            on:change=move |ev: web_sys::Event| {
+                                                let target = ev.target().unwrap();
+                                                let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                                                let blend = match select.value().as_str() {
+                                                    "additive" => FlowBlendMode::Additive,
+                                                    "screen" => FlowBlendMode::Screen,
+                                                    _ => FlowBlendMode::Normal,
+                                                };
+                                                state.flow_blend_mode.set(blend);
+                                            }
+                                            prop:value=move || match state.flow_blend_mode.get() {
+                                                FlowBlendMode::Normal => "normal",
+                                                FlowBlendMode::Additive => "additive",
+                                                FlowBlendMode::Screen => "screen",
+                                            }
+                                        >
+                                            <option value="normal">"Normal"</option>
+                                            <option value="additive">"Additive"</option>
+                                            <option value="screen">"Screen"</option>
+                                        </select>
+                                    </div>
+                                })
+                            }}
                             <div class="setting-row">
                                 <span class="setting-label">"Intensity gate"</span>
                                 <div class="setting-slider-row">
@@ -262,6 +391,73 @@ pub(crate) fn SpectrogramSettingsPanel() -> impl IntoView {
                                     <span class="setting-value">{move || format!("{:.1}x", state.flow_shift_gain.get())}</span>
                                 </div>
                             </div>
+                            // Shift windows (optical flow and centroid only; gradient uses adjacent bins)
+                            {move || {
+                                let display = state.spectrogram_display.get();
+                                let windowed = matches!(display,
+                                    SpectrogramDisplay::FlowOptical | SpectrogramDisplay::FlowCentroid
+                                );
+                                windowed.then(|| view! {
+                                    <div class="setting-row" title="Half-width of the frequency window compared between columns. Larger follows broader structures; smaller resolves nearby harmonics">
+                                        <span class="setting-label">"Window radius"</span>
+                                        <div class="setting-slider-row">
+                                            <input
+                                                type="range"
+                                                class="setting-range"
+                                                min="1"
+                                                max=FlowShiftParams::MAX.to_string()
+                                                step="1"
+                                                prop:value=move || state.flow_radius.get().to_string()
+                                                on:change=move |ev: web_sys::Event| {
+                                                    let target = ev.target().unwrap();
+                                                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                                    if let Ok(val) = input.value().parse::<usize>() {
+                                                        let val = val.clamp(1, FlowShiftParams::MAX);
+                                                        state.flow_radius.set(val);
+                                                        if let Some(ls) = web_sys::window()
+                                                            .and_then(|w| w.local_storage().ok().flatten())
+                                                        {
+                                                            let _ = ls.set_item("oversample_flow_radius", &val.to_string());
+                                                        }
+                                                    }
+                                                }
+                                            />
+                                            <span class="setting-value">{move || format!("{} bins", state.flow_radius.get())}</span>
+                                        </div>
+                                    </div>
+                                })
+                            }}
+                            {move || {
+                                (state.spectrogram_display.get() == SpectrogramDisplay::FlowOptical).then(|| view! {
+                                    <div class="setting-row" title="Largest shift per column the optical flow looks for. Raise it for fast FM sweeps; lower it for slow CF tones">
+                                        <span class="setting-label">"Max shift"</span>
+                                        <div class="setting-slider-row">
+                                            <input
+                                                type="range"
+                                                class="setting-range"
+                                                min="1"
+                                                max=FlowShiftParams::MAX.to_string()
+                                                step="1"
+                                                prop:value=move || state.flow_max_displacement.get().to_string()
+                                                on:change=move |ev: web_sys::Event| {
+                                                    let target = ev.target().unwrap();
+                                                    let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                                    if let Ok(val) = input.value().parse::<usize>() {
+                                                        let val = val.clamp(1, FlowShiftParams::MAX);
+                                                        state.flow_max_displacement.set(val);
+                                                        if let Some(ls) = web_sys::window()
+                                                            .and_then(|w| w.local_storage().ok().flatten())
+                                                        {
+                                                            let _ = ls.set_item("oversample_flow_max_displacement", &val.to_string());
+                                                        }
+                                                    }
+                                                }
+                                            />
+                                            <span class="setting-value">{move || format!("\u{00B1}{} bins", state.flow_max_displacement.get())}</span>
+                                        </div>
+                                    </div>
+                                })
+                            }}
                             <div class="setting-row">
                                 <span class="setting-label">{move || {
                                     let g = state.flow_color_gamma.get();
@@ -366,7 +562,7 @@ pub(crate) fn SpectrogramSettingsPanel() -> impl IntoView {
                                 <span class="setting-label">{move || {
                                     let mode = state.resonator_fft_mode.get();
                                     let sr = current_resonator_sample_rate(state);
-                                    let current_lod = crate::canvas::tile_cache::select_lod(
+                                    let current_lod = crate::canvas::tile_cache::main_view_lod(
                                         state.zoom_level.get(),
                                     );
                                     let f = mode.fft_for_lod(current_lod).max(2);
@@ -478,6 +674,70 @@ pub(crate) fn SpectrogramSettingsPanel() -> impl IntoView {
                 }
             }}
 
+            // ZC chart settings (shown only when ZC Chart view is active)
+            {move || {
+                if state.main_view.get() == MainView::ZcChart {
+                    view! {
+                        <div class="setting-group">
+                            <div class="setting-group-title">"Zero Crossing"</div>
+                            <div class="setting-row">
+                                <span class="setting-label" title="Binned: average rate per 1 ms. Per crossing: one dot every N cycles, as on Anabat/AnalookW">"Dots"</span>
+                                <select
+                                    class="setting-select"
+                                    on:change=move |ev: web_sys::Event| {
+                                        let target = ev.target().unwrap();
+                                        let select: web_sys::HtmlSelectElement = target.unchecked_into();
+                                        state.zc_dot_division.set(select.value().parse().unwrap_or(0));
+                                    }
+                                    prop:value=move || state.zc_dot_division.get().to_string()
+                                >
+                                    <option value="0">"1 ms bins"</option>
+                                    <option value="1">"Every cycle"</option>
+                                    <option value="4">"Every 4 cycles"</option>
+                                    <option value="8">"Every 8 cycles"</option>
+                                    <option value="16">"Every 16 cycles"</option>
+                                </select>
+                            </div>
+                            <div class="setting-row" title="Zero-crossing frequency per window over the selection (or the whole file), as time_s, estimated_frequency_hz, crossing_count">
+                                <span class="setting-label">"Track window (ms)"</span>
+                                <input
+                                    type="number"
+                                    class="setting-number"
+                                    min="0.1"
+                                    max="1000"
+                                    step="0.5"
+                                    prop:value=move || state.zc_track_window_ms.get().to_string()
+                                    on:change=move |ev| {
+                                        let Ok(ms) = event_target_value(&ev).trim().parse::<f64>() else {
+                                            state.zc_track_window_ms.update(|_| {});
+                                            return;
+                                        };
+                                        let ms = ms.clamp(0.1, 1000.0);
+                                        state.zc_track_window_ms.set(ms);
+                                        if let Some(ls) = web_sys::window()
+                                            .and_then(|w| w.local_storage().ok().flatten())
+                                        {
+                                            let _ = ls.set_item("oversample_zc_track_window_ms", &ms.to_string());
+                                        }
+                                    }
+                                />
+                            </div>
+                            <div class="setting-row">
+                                <button class="setting-button" on:click=move |_| export_zc_track(state)>
+                                    {move || if state.selection.get().is_some() {
+                                        "Export ZC track of selection (CSV)"
+                                    } else {
+                                        "Export ZC track (CSV)"
+                                    }}
+                                </button>
+                            </div>
+                        </div>
+                    }.into_any()
+                } else {
+                    view! { <span></span> }.into_any()
+                }
+            }}
+
             // Chromagram-specific settings (shown only when Chromagram view is active)
             {move || {
                 if state.main_view.get() == MainView::Chromagram {
@@ -578,12 +838,76 @@ pub(crate) fn SelectionPanel() -> impl IntoView {
                     view! { <span></span> }.into_any()
                 }
             }}
+            <HarmonicRatioRow />
             <WavMarkersList />
             <AnnotationsList />
         </div>
     }
 }
 
+/// Centre frequency of the current selection as a multiple of the
+/// fundamental (detected by the Harmonics panel, or entered here), to judge
+/// whether a selected band is a true harmonic.
+#[component]
+fn HarmonicRatioRow() -> impl IntoView {
+    let state = expect_context::<AppState>();
+
+    let centre = move || {
+        let sel = state.selection.get()?;
+        let (lo, hi) = (sel.freq_low?, sel.freq_high?);
+        (hi > lo).then_some((lo + hi) / 2.0)
+    };
+
+    view! {
+        {move || centre().map(|centre| {
+            let unit = state.freq_unit.get();
+            let reference = state.reference_fundamental();
+            let ratio = reference
+                .and_then(|(f0, _)| crate::dsp::harmonics::harmonic_ratio(centre, f0))
+                .map(|r| format!("{:.2}\u{00D7} fundamental (H{} {:+.1}%)", r.ratio, r.nearest, r.deviation * 100.0))
+                .unwrap_or_else(|| "\u{2014}".to_string());
+            let source = match reference {
+                Some((f0, true)) => format!("Fundamental {} (manual)", unit.format(f0)),
+                Some((f0, false)) => format!("Fundamental {} (detected in Harmonics)", unit.format(f0)),
+                None => "No fundamental \u{2014} run the Harmonics panel or enter one".to_string(),
+            };
+            view! {
+                <div class="setting-group">
+                    <div class="setting-group-title">"Selection vs harmonics"</div>
+                    <div class="setting-row">
+                        <span class="setting-label">"Centre"</span>
+                        <span>{unit.format(centre)}</span>
+                    </div>
+                    <div class="setting-row">
+                        <span class="setting-label">"Ratio"</span>
+                        <span>{ratio}</span>
+                    </div>
+                    <div class="setting-row" title="Fundamental to compare against, in kHz. Leave empty to use the one detected by the Harmonics panel">
+                        <span class="setting-label">"Fundamental (kHz)"</span>
+                        <input
+                            type="number"
+                            class="setting-number"
+                            min="0"
+                            step="0.1"
+                            placeholder="auto"
+                            prop:value=move || state.fundamental_override_hz.get()
+                                .map(|hz| format!("{:.2}", hz / 1000.0))
+                                .unwrap_or_default()
+                            on:change=move |ev: web_sys::Event| {
+                                let target = ev.target().unwrap();
+                                let input: web_sys::HtmlInputElement = target.unchecked_into();
+                                let khz = input.value().trim().parse::<f64>().ok().filter(|v| *v > 0.0);
+                                state.fundamental_override_hz.set(khz.map(|k| k * 1000.0));
+                            }
+                        />
+                    </div>
+                    <div class="setting-hint">{source}</div>
+                </div>
+            }
+        })}
+    }
+}
+
 /// Read-only list of file-embedded time markers — WAV cue points or
 /// M4A/M4B chapters — parsed from the current file.
 #[component]
@@ -668,14 +992,14 @@ fn WavMarkersList() -> impl IntoView {
 /// Get the display label for an annotation.
 /// Returns (display_text, stored_label, is_default).
 /// is_default is true when the label was auto-generated (render italic).
-fn annotation_display(a: &Annotation) -> (String, Option<String>, bool) {
+fn annotation_display(a: &Annotation, freq_unit: FreqUnit) -> (String, Option<String>, bool) {
     let is_default = a.label_default.unwrap_or(false);
     match &a.kind {
         AnnotationKind::Region(reg) => {
             let auto_label = match (reg.freq_low, reg.freq_high) {
-                (Some(fl), Some(fh)) => format!("{}, {:.0}–{:.0} kHz",
+                (Some(fl), Some(fh)) => format!("{}, {}",
                     crate::format_time::format_time_range(reg.time_start, reg.time_end, 3),
-                    fl / 1000.0, fh / 1000.0),
+                    freq_unit.format_range(fl, fh)),
                 _ => crate::format_time::format_time_range(reg.time_start, reg.time_end, 3),
             };
             let (display, italic) = match &reg.label {
@@ -816,7 +1140,7 @@ fn AnnotationsList() -> impl IntoView {
 fn render_tree_nodes(nodes: Vec<AnnotationNode>, state: AppState) -> impl IntoView {
     nodes.into_iter().map(move |node| {
         let id = node.annotation.id.clone();
-        let (display, existing_label, label_is_default) = annotation_display(&node.annotation);
+        let (display, existing_label, label_is_default) = annotation_display(&node.annotation, state.freq_unit.get());
         let icon = annotation_icon(&node.annotation.kind);
         let is_group = matches!(node.annotation.kind, AnnotationKind::Group(_));
         let is_collapsed = match &node.annotation.kind {
@@ -1486,12 +1810,14 @@ fn export_annotations(state: AppState) {
         None => { state.show_error_toast("No file selected"); return; }
     };
     let store = state.annotation_store.get_untracked();
-    let set = match store.sets.get(idx).and_then(|s| s.as_ref()) {
-        Some(s) => s,
+    let mut set = match store.sets.get(idx).and_then(|s| s.as_ref()) {
+        Some(s) => s.clone(),
         None => { state.show_error_toast("No annotations to export"); return; }
     };
+    // Exported sets are in original-file time, like saved sidecars
+    set.shift_times(crate::opfs::import_trim_secs(state, idx));
 
-    let yaml = match yaml_serde::to_string(set) {
+    let yaml = match yaml_serde::to_string(&set) {
         Ok(y) => y,
         Err(e) => { state.show_error_toast(format!("Serialize error: {e}")); return; }
     };
@@ -1549,8 +1875,9 @@ fn import_annotations(state: AppState) {
             let result = reader_clone.result().unwrap();
             let text = result.as_string().unwrap_or_default();
             match yaml_serde::from_str::<AnnotationSet>(&text) {
-                Ok(imported) => {
+                Ok(mut imported) => {
                     let idx = state.current_file_index.get_untracked().unwrap_or(0);
+                    imported.shift_times(-crate::opfs::import_trim_secs(state, idx));
                     state.snapshot_annotations();
                     state.annotation_store.update(|store| {
                         store.ensure_len(idx + 1);
@@ -4,7 +4,7 @@ use web_sys::File;
 use std::sync::Arc;
 use crate::audio::loader::{id3v2_tag_size, is_m4a, is_mp3, is_ogg, parse_flac_header, parse_m4a_chapters, parse_mp3_header, parse_ogg_header, parse_wav_header_with_file_size};
 use crate::audio::streaming_source::{FileHandle, StreamingFlacSource, StreamingM4aSource, StreamingMp3Source, StreamingOggSource, StreamingWavSource, read_blob_range};
-use crate::dsp::fft::compute_preview;
+use crate::dsp::fft::{compute_preview, stft_frame_count};
 use crate::state::{AppState, FileSettings, LoadedFile};
 use crate::types::{AudioData, SpectrogramData};
 
@@ -133,7 +133,7 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
     let samples = Arc::new(head_mono);
 
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source,
         sample_rate,
         channels: header.channels as u32,
@@ -141,7 +141,7 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
         metadata: crate::types::FileMetadata {
             file_size: file.size() as usize,
             format: "WAV",
-            bits_per_sample: header.bits_per_sample,
+            bits_per_sample: header.valid_bits_per_sample,
             is_float: header.is_float,
             guano,
             data_offset: Some(header.data_offset),
@@ -150,7 +150,8 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
     };
 
     // Compute preview from head samples (fast)
-    let preview = compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = compute_preview(&audio, preview_w, preview_h);
 
     // Check for silence/quiet in head
     let (silence_check, cached_peak_db) = {
@@ -175,11 +176,7 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
     const HOP_SIZE: usize = 512;
     let fft_size: usize = state.spect_fft_mode.get_untracked().fft_for_lod(crate::canvas::tile_cache::LOD_BASELINE);
     let total_len = total_frames as usize;
-    let total_cols = if total_len >= fft_size {
-        (total_len - fft_size) / HOP_SIZE + 1
-    } else {
-        0
-    };
+    let total_cols = stft_frame_count(total_len, fft_size, HOP_SIZE);
 
     let spectrogram = SpectrogramData {
         columns: Arc::new(Vec::new()),
@@ -192,6 +189,7 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
 
     let wav_markers = header.wav_markers.clone();
     let name_owned = name.to_string();
+    let (min_display_freq, max_display_freq) = state.default_display_range_for(audio.sample_rate);
     let file_index;
     {
         let mut idx = 0;
@@ -221,8 +219,11 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
                 all_hashes_verified: false,
                 wav_markers,
                 loading_id: Some(load_id),
-                min_display_freq: None,
-                max_display_freq: None,
+                min_display_freq,
+                max_display_freq,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
             state.current_file_index.set(Some(idx));
         });
@@ -231,6 +232,7 @@ pub(super) async fn try_streaming_wav(file: &File, name: &str, state: AppState,
 
     // Schedule async full-file peak scan (for files > 30s)
     crate::audio::peak::start_full_peak_scan(state, file_index);
+    super::loading::check_identical_channels(state, file_index);
 
     // Notify user about silent/quiet files
     if let Some(check) = silence_check {
@@ -430,7 +432,7 @@ pub(super) async fn try_streaming_flac(file: &File, name: &str, state: AppState,
     let samples = Arc::new(head_mono);
 
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source: source.clone(),
         sample_rate,
         channels: header.channels as u32,
@@ -447,7 +449,8 @@ pub(super) async fn try_streaming_flac(file: &File, name: &str, state: AppState,
     };
 
     // Compute preview from head samples
-    let preview = compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = compute_preview(&audio, preview_w, preview_h);
 
     // Check for silence/quiet in head
     let (silence_check, cached_peak_db) = {
@@ -472,11 +475,7 @@ pub(super) async fn try_streaming_flac(file: &File, name: &str, state: AppState,
     const HOP_SIZE: usize = 512;
     let fft_size: usize = state.spect_fft_mode.get_untracked().fft_for_lod(crate::canvas::tile_cache::LOD_BASELINE);
     let total_len = total_frames as usize;
-    let total_cols = if total_len >= fft_size {
-        (total_len - fft_size) / HOP_SIZE + 1
-    } else {
-        0
-    };
+    let total_cols = stft_frame_count(total_len, fft_size, HOP_SIZE);
 
     let spectrogram = SpectrogramData {
         columns: Arc::new(Vec::new()),
@@ -488,6 +487,7 @@ pub(super) async fn try_streaming_flac(file: &File, name: &str, state: AppState,
     };
 
     let name_owned = name.to_string();
+    let (min_display_freq, max_display_freq) = state.default_display_range_for(audio.sample_rate);
     let file_index;
     {
         let mut idx = 0;
@@ -517,8 +517,11 @@ pub(super) async fn try_streaming_flac(file: &File, name: &str, state: AppState,
                 all_hashes_verified: false,
                 wav_markers: Vec::new(),
                 loading_id: Some(load_id),
-                min_display_freq: None,
-                max_display_freq: None,
+                min_display_freq,
+                max_display_freq,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
             state.current_file_index.set(Some(idx));
         });
@@ -527,6 +530,7 @@ pub(super) async fn try_streaming_flac(file: &File, name: &str, state: AppState,
 
     // Schedule async full-file peak scan (for files > 30s)
     crate::audio::peak::start_full_peak_scan(state, file_index);
+    super::loading::check_identical_channels(state, file_index);
 
     if let Some(check) = silence_check {
         match check {
@@ -814,8 +818,11 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
 
     let samples = Arc::new(head_mono);
 
+    // Embedded ID3/Vorbis tags sit in the header, which is already in memory
+    let guano = crate::audio::tags::tags_to_guano(crate::audio::tags::parse_embedded_tags(&header_bytes));
+    let xc_metadata = super::loading::xc_metadata_from_tags(guano.as_ref());
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source: source.clone(),
         sample_rate,
         channels: channels as u32,
@@ -825,14 +832,15 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
             format: "MP3",
             bits_per_sample: 16,
             is_float: false,
-            guano: None,
+            guano,
             data_offset: Some(header.data_offset),
             data_size: Some((file.size() as u64).saturating_sub(header.data_offset)),
         },
     };
 
     // Compute preview from head samples
-    let preview = compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = compute_preview(&audio, preview_w, preview_h);
 
     // Check for silence/quiet in head
     let (silence_check, cached_peak_db) = {
@@ -857,11 +865,7 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
     const HOP_SIZE: usize = 512;
     let fft_size: usize = state.spect_fft_mode.get_untracked().fft_for_lod(crate::canvas::tile_cache::LOD_BASELINE);
     let total_len = total_frames as usize;
-    let total_cols = if total_len >= fft_size {
-        (total_len - fft_size) / HOP_SIZE + 1
-    } else {
-        0
-    };
+    let total_cols = stft_frame_count(total_len, fft_size, HOP_SIZE);
 
     let spectrogram = SpectrogramData {
         columns: Arc::new(Vec::new()),
@@ -873,6 +877,7 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
     };
 
     let name_owned = name.to_string();
+    let (min_display_freq, max_display_freq) = state.default_display_range_for(audio.sample_rate);
     let file_index;
     {
         let mut idx = 0;
@@ -884,7 +889,7 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
                 spectrogram,
                 preview: Some(preview),
                 overview_image: None,
-                xc_metadata: None,
+                xc_metadata,
                 xc_hashes: None,
                 is_demo: false,
                 is_recording: false,
@@ -902,8 +907,11 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
                 all_hashes_verified: false,
                 wav_markers: Vec::new(),
                 loading_id: Some(load_id),
-                min_display_freq: None,
-                max_display_freq: None,
+                min_display_freq,
+                max_display_freq,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
             state.current_file_index.set(Some(idx));
         });
@@ -912,6 +920,7 @@ pub(super) async fn try_streaming_mp3(file: &File, name: &str, state: AppState,
 
     // Schedule async full-file peak scan (for files > 30s)
     crate::audio::peak::start_full_peak_scan(state, file_index);
+    super::loading::check_identical_channels(state, file_index);
 
     if let Some(check) = silence_check {
         match check {
@@ -1206,8 +1215,11 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
 
     let samples = Arc::new(head_mono);
 
+    // Embedded ID3/Vorbis tags sit in the header, which is already in memory
+    let guano = crate::audio::tags::tags_to_guano(crate::audio::tags::parse_embedded_tags(&header_bytes));
+    let xc_metadata = super::loading::xc_metadata_from_tags(guano.as_ref());
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source: source.clone(),
         sample_rate,
         channels: channels as u32,
@@ -1217,14 +1229,15 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
             format: "OGG",
             bits_per_sample: 16,
             is_float: false,
-            guano: None,
+            guano,
             data_offset: None,
             data_size: None,
         },
     };
 
     // Compute preview from head samples
-    let preview = compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = compute_preview(&audio, preview_w, preview_h);
 
     // Check for silence/quiet in head
     let (silence_check, cached_peak_db) = {
@@ -1249,11 +1262,7 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
     const HOP_SIZE: usize = 512;
     let fft_size: usize = state.spect_fft_mode.get_untracked().fft_for_lod(crate::canvas::tile_cache::LOD_BASELINE);
     let total_len = total_frames as usize;
-    let total_cols = if total_len >= fft_size {
-        (total_len - fft_size) / HOP_SIZE + 1
-    } else {
-        0
-    };
+    let total_cols = stft_frame_count(total_len, fft_size, HOP_SIZE);
 
     let spectrogram = SpectrogramData {
         columns: Arc::new(Vec::new()),
@@ -1265,6 +1274,7 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
     };
 
     let name_owned = name.to_string();
+    let (min_display_freq, max_display_freq) = state.default_display_range_for(audio.sample_rate);
     let file_index;
     {
         let mut idx = 0;
@@ -1276,7 +1286,7 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
                 spectrogram,
                 preview: Some(preview),
                 overview_image: None,
-                xc_metadata: None,
+                xc_metadata,
                 xc_hashes: None,
                 is_demo: false,
                 is_recording: false,
@@ -1294,8 +1304,11 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
                 all_hashes_verified: false,
                 wav_markers: Vec::new(),
                 loading_id: Some(load_id),
-                min_display_freq: None,
-                max_display_freq: None,
+                min_display_freq,
+                max_display_freq,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
             state.current_file_index.set(Some(idx));
         });
@@ -1304,6 +1317,7 @@ pub(super) async fn try_streaming_ogg(file: &File, name: &str, state: AppState,
 
     // Schedule async full-file peak scan (for files > 30s)
     crate::audio::peak::start_full_peak_scan(state, file_index);
+    super::loading::check_identical_channels(state, file_index);
 
     if let Some(check) = silence_check {
         match check {
@@ -1867,7 +1881,7 @@ pub(super) async fn try_streaming_m4a(file: &File, name: &str, state: AppState,
     let samples = Arc::new(head_mono);
 
     let audio = AudioData {
-        samples,
+        samples: samples.into(),
         source: source.clone(),
         sample_rate,
         channels: channels as u32,
@@ -1883,7 +1897,8 @@ pub(super) async fn try_streaming_m4a(file: &File, name: &str, state: AppState,
         },
     };
 
-    let preview = compute_preview(&audio, 256, 128);
+    let (preview_w, preview_h) = state.preview_size();
+    let preview = compute_preview(&audio, preview_w, preview_h);
 
     let (silence_check, cached_peak_db) = {
         use crate::audio::source::ChannelView;
@@ -1906,7 +1921,7 @@ pub(super) async fn try_streaming_m4a(file: &File, name: &str, state: AppState,
     const HOP_SIZE: usize = 512;
     let fft_size: usize = state.spect_fft_mode.get_untracked().fft_for_lod(crate::canvas::tile_cache::LOD_BASELINE);
     let total_len = total_frames as usize;
-    let total_cols = if total_len >= fft_size { (total_len - fft_size) / HOP_SIZE + 1 } else { 0 };
+    let total_cols = stft_frame_count(total_len, fft_size, HOP_SIZE);
 
     let spectrogram = SpectrogramData {
         columns: Arc::new(Vec::new()),
@@ -1918,6 +1933,7 @@ pub(super) async fn try_streaming_m4a(file: &File, name: &str, state: AppState,
     };
 
     let name_owned = name.to_string();
+    let (min_display_freq, max_display_freq) = state.default_display_range_for(audio.sample_rate);
     let file_index;
     {
         let mut idx = 0;
@@ -1947,8 +1963,11 @@ pub(super) async fn try_streaming_m4a(file: &File, name: &str, state: AppState,
                 all_hashes_verified: false,
                 wav_markers,
                 loading_id: Some(load_id),
-                min_display_freq: None,
-                max_display_freq: None,
+                min_display_freq,
+                max_display_freq,
+                notes: String::new(),
+                metrics: None,
+                identical_channels: false,
             });
             state.current_file_index.set(Some(idx));
         });
@@ -1957,6 +1976,8 @@ pub(super) async fn try_streaming_m4a(file: &File, name: &str, state: AppState,
 
     crate::audio::peak::start_full_peak_scan(state, file_index);
 
+    super::loading::check_identical_channels(state, file_index);
+
     if let Some(check) = silence_check {
         match check {
             SilenceCheck::Silent => {
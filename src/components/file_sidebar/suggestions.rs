@@ -213,6 +213,7 @@ pub(super) fn BatsForYou(
                 spawn_local(async move {
                     if let Err(e) = load_single_demo(&entry, state, load_id).await {
                         log::error!("Failed to load suggested demo: {e}");
+                        state.show_error_toast(format!("Couldn't load {label}: {e}"));
                     }
                     state.loading_done(load_id);
                 });
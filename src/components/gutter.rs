@@ -46,6 +46,26 @@ fn gutter_nyquist(state: AppState) -> f64 {
     }
 }
 
+/// Keyboard frequency-axis navigation: pan the visible band by `pan_frac`
+/// of its height (positive = up), then scale its height by `zoom_factor`
+/// around the centre. The band is kept within 0 Hz and the view ceiling.
+pub fn nudge_freq_view(state: AppState, pan_frac: f64, zoom_factor: f64) {
+    let nyquist = gutter_nyquist(state);
+    if nyquist <= 0.0 { return; }
+    let ceiling = state.freq_view_ceiling(nyquist);
+    let cur_min = state.min_display_freq.get_untracked().unwrap_or(0.0);
+    let cur_max = state.max_display_freq.get_untracked().unwrap_or(nyquist);
+    let range = (cur_max - cur_min).max(1.0);
+    let new_range = (range * zoom_factor).clamp(500.0_f64.min(nyquist), ceiling);
+    let centre = (cur_min + cur_max) / 2.0 + pan_frac * range;
+    let mut new_min = centre - new_range / 2.0;
+    let mut new_max = centre + new_range / 2.0;
+    if new_min < 0.0 { new_min = 0.0; new_max = new_range; }
+    if new_max > ceiling { new_max = ceiling; new_min = (new_max - new_range).max(0.0); }
+    state.min_display_freq.set(Some(new_min));
+    state.max_display_freq.set(Some(new_max));
+}
+
 /// True if the primary pointer is a finger — used to reserve the larger
 /// slop / explicit-dbltap paths for touch only, so mouse precision
 /// isn't degraded.
@@ -112,6 +132,7 @@ pub fn BandGutter() -> impl IntoView {
         let band_hi = state.band_ff_freq_hi.get();
         let hfr_on = state.hfr_enabled.get();
         let shield_style = state.shield_style.get();
+        crate::canvas::overlays::set_freq_marker_style(state.marker_colors.get(), state.freq_marker_interval_khz.get() * 1000.0);
         // Live drag range from either this gutter or the spectrogram's
         // y-axis — when Some, overrides the stored band so the shield
         // lights up mid-drag even before the band has been committed.
@@ -129,6 +150,8 @@ pub fn BandGutter() -> impl IntoView {
         let _rsidebar_width = state.right_sidebar_width.get();
         let _tile_ready = state.tile_ready_signal.get();
         let _size_tick = canvas_size_tick.get();
+        let _flipped = state.freq_axis_flipped.get();
+        let _high_contrast = state.high_contrast.get();
 
         let Some(canvas_el) = canvas_ref.get() else { return };
         let canvas: &HtmlCanvasElement = canvas_el.as_ref();
@@ -391,7 +414,7 @@ pub fn BandGutter() -> impl IntoView {
                 initial_min_freq,
                 initial_max_freq,
                 initial_mid_canvas_y: mid_client_y - rect.top(),
-                nyquist,
+                nyquist: state.freq_view_ceiling(nyquist),
             }));
         }
     };
@@ -437,18 +460,20 @@ pub fn BandGutter() -> impl IntoView {
         let cur_min = state.min_display_freq.get_untracked().unwrap_or(0.0);
         let cur_max = state.max_display_freq.get_untracked().unwrap_or(nyquist);
         let range = (cur_max - cur_min).max(1.0);
+        let ceiling = state.freq_view_ceiling(nyquist);
 
         if ev.shift_key() || ev.ctrl_key() || ev.meta_key() {
             // Zoom around pointer y. delta_y > 0 (wheel down) → zoom out.
             let local_y = ev.client_y() as f64 - rect.top();
             let anchor_frac = (local_y / h).clamp(0.0, 1.0);
+            let anchor_frac = if crate::viewport::freq_axis_flipped() { 1.0 - anchor_frac } else { anchor_frac };
             let anchor_freq = cur_max - anchor_frac * range;
             let factor = if ev.delta_y() > 0.0 { 1.15 } else { 1.0 / 1.15 };
-            let new_range = (range * factor).clamp(500.0_f64.min(nyquist), nyquist);
+            let new_range = (range * factor).clamp(500.0_f64.min(nyquist), ceiling);
             let mut new_max = anchor_freq + anchor_frac * new_range;
             let mut new_min = new_max - new_range;
-            if new_min < 0.0 { new_min = 0.0; new_max = new_range.min(nyquist); }
-            if new_max > nyquist { new_max = nyquist; new_min = (new_max - new_range).max(0.0); }
+            if new_min < 0.0 { new_min = 0.0; new_max = new_range.min(ceiling); }
+            if new_max > ceiling { new_max = ceiling; new_min = (new_max - new_range).max(0.0); }
             state.min_display_freq.set(Some(new_min));
             state.max_display_freq.set(Some(new_max));
         } else {
@@ -458,19 +483,19 @@ pub fn BandGutter() -> impl IntoView {
             let step = raw.signum() * range * 0.1 * (raw.abs() / 100.0).min(3.0);
             let mut new_max = cur_max - step;
             let mut new_min = cur_min - step;
-            if new_min < 0.0 { new_min = 0.0; new_max = range.min(nyquist); }
-            if new_max > nyquist { new_max = nyquist; new_min = (new_max - range).max(0.0); }
+            if new_min < 0.0 { new_min = 0.0; new_max = range.min(ceiling); }
+            if new_max > ceiling { new_max = ceiling; new_min = (new_max - range).max(0.0); }
             state.min_display_freq.set(Some(new_min));
             state.max_display_freq.set(Some(new_max));
         }
     };
 
-    // Format "40.0 – 72.5 kHz" for the drag tooltip.
+    // Format "40.0–72.5 kHz" for the drag tooltip.
     let format_range = move || {
         let lo = state.band_ff_freq_lo.get();
         let hi = state.band_ff_freq_hi.get();
         if hi <= lo { return String::new(); }
-        format!("{:.1} – {:.1} kHz", lo / 1000.0, hi / 1000.0)
+        state.freq_unit.get().format_range(lo, hi)
     };
 
     view! {
@@ -584,7 +609,9 @@ pub fn TimeGutter(#[prop(default = 0.0)] data_left_offset: f64) -> impl IntoView
         let _rsidebar_width = state.right_sidebar_width.get();
         let _main_view = state.main_view.get();
         let show_clock = state.show_clock_time.get();
+        let tick_spacing = state.time_tick_density.get().spacing_px();
         let _size_tick = canvas_size_tick.get();
+        let _high_contrast = state.high_contrast.get();
         let Some((scroll, visible_time, duration, _time_res, clock)) = time_window() else { return };
 
         let Some(canvas_el) = canvas_ref.get() else { return };
@@ -637,7 +664,7 @@ pub fn TimeGutter(#[prop(default = 0.0)] data_left_offset: f64) -> impl IntoView
         let _ = ctx.translate(data_x, 0.0);
         crate::canvas::time_markers::draw_time_markers(
             &ctx, scroll, visible_time, data_w, h,
-            duration, clock, show_clock, 1.0,
+            duration, clock, show_clock, 1.0, tick_spacing,
         );
         ctx.restore();
     });
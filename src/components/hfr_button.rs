@@ -98,6 +98,29 @@ fn format_factor_value(f: f64) -> String {
     }
 }
 
+/// Common heterodyne tuning frequencies (Hz, label).
+const HET_PRESETS: &[(f64, &str)] = &[
+    (25_000.0, "25k"),
+    (35_000.0, "35k"),
+    (45_000.0, "45k"),
+    (55_000.0, "55k"),
+];
+
+/// Heterodyne low-pass stages and their rolloff labels (dB/octave).
+const HET_ORDER_PRESETS: &[(usize, &str)] = &[
+    (1, "6"),
+    (2, "12"),
+    (4, "24"),
+    (8, "48"),
+];
+
+/// Parse a user-entered frequency in kHz ("45", "45.5", "45k", "45 kHz") into Hz.
+fn parse_khz(s: &str) -> Option<f64> {
+    let s = s.trim().to_ascii_lowercase();
+    let s = s.strip_suffix("khz").or_else(|| s.strip_suffix('k')).unwrap_or(&s).trim();
+    s.parse::<f64>().ok().filter(|v| v.is_finite() && *v > 0.0).map(|v| v * 1000.0)
+}
+
 /// Parse a user-entered factor string. Accepts "10", "10.5", "-2", "÷2", etc.
 fn parse_factor_input(s: &str) -> Option<f64> {
     let s = s.trim();
@@ -109,6 +132,115 @@ fn parse_factor_input(s: &str) -> Option<f64> {
     }
 }
 
+/// Current file's max frequency (Nyquist), or 96 kHz when no file is loaded.
+fn current_file_max_freq(state: AppState) -> f64 {
+    state.files.with_untracked(|files| {
+        state.current_file_index.get_untracked()
+            .and_then(|i| files.get(i))
+            .map(|f| f.spectrogram.max_freq)
+            .unwrap_or(96_000.0)
+    })
+}
+
+/// Share of above-floor energy the auto-focus band must contain.
+const AUTO_FOCUS_ENERGY_FRACTION: f64 = 0.9;
+
+/// Set the main focus band to where the current file's energy concentrates
+/// (in the selection, or else the first 30 s) and turn HFR on.
+fn auto_focus(state: AppState) {
+    use crate::audio::source::{ChannelView, DEFAULT_ANALYSIS_WINDOW_SECS};
+    let Some((source, sample_rate)) = state.files.with_untracked(|files| {
+        state.current_file_index.get_untracked()
+            .and_then(|i| files.get(i))
+            .map(|f| (f.audio.source.clone(), f.audio.sample_rate))
+    }) else { return };
+    let sr = sample_rate as f64;
+    let total = source.total_samples() as usize;
+    let (start, end) = match state.selection.get_untracked() {
+        Some(sel) if sel.time_end > sel.time_start => (
+            ((sel.time_start * sr) as usize).min(total),
+            ((sel.time_end * sr) as usize).min(total),
+        ),
+        _ => (0, total.min((DEFAULT_ANALYSIS_WINDOW_SECS * sr) as usize)),
+    };
+    let samples = source.read_region(ChannelView::MonoMix, start as u64, end - start);
+    if samples.len() < 1024 {
+        return;
+    }
+    let psd = crate::dsp::psd::compute_psd(&samples, sample_rate, 1024, None);
+    let Some((lo, hi)) = crate::dsp::psd::energy_band(&psd, AUTO_FOCUS_ENERGY_FRACTION, 1000.0) else {
+        state.show_info_toast("No energy above the noise floor to focus on");
+        return;
+    };
+    state.set_band_ff_range(lo, hi);
+    if !state.focus_stack.get_untracked().hfr_enabled() {
+        state.toggle_hfr();
+    }
+    state.show_info_toast(format!("Focused on {:.1}\u{2013}{:.1} kHz", lo / 1000.0, hi / 1000.0));
+}
+
+/// List of extra HFR focus bands with editable kHz bounds.
+/// The main band is edited on the spectrogram / gutter as before.
+#[component]
+fn ExtraFocusBandsEditor() -> impl IntoView {
+    use crate::canvas::spectrogram_renderer::MAX_EXTRA_FOCUS_BANDS;
+    let state = expect_context::<AppState>();
+
+    let on_edge = move |index: usize, upper: bool| {
+        move |ev: web_sys::Event| {
+            let Some(hz) = parse_khz(&event_target_value(&ev)) else { return };
+            let Some((lo, hi)) = state.extra_focus_bands.get_untracked().get(index).copied() else { return };
+            let max = current_file_max_freq(state);
+            if upper {
+                state.set_extra_focus_band(index, lo, hz.min(max));
+            } else {
+                state.set_extra_focus_band(index, hz, hi);
+            }
+            // Re-sync the input if the value was rejected or clamped
+            state.extra_focus_bands.update(|_| {});
+        }
+    };
+
+    view! {
+        <div class="layer-panel-title">"Focus bands"</div>
+        {move || {
+            let bands = state.extra_focus_bands.get();
+            bands.into_iter().enumerate().map(|(i, (lo, hi))| {
+                view! {
+                    <div class="layer-panel-slider-row extra-band-row"
+                        on:mouseenter=move |_| state.spec_hover_handle.set(Some(SpectrogramHandle::ExtraBandUpper(i)))
+                        on:mouseleave=move |_| state.spec_hover_handle.set(None)
+                    >
+                        <label>{format!("Band {}", i + 2)}</label>
+                        <input type="text" class="factor-input extra-band-input"
+                            prop:value=format!("{:.1}", lo / 1000.0)
+                            on:change=on_edge(i, false)
+                            title="Lower edge in kHz"
+                        />
+                        <span class="het-value">"\u{2013}"</span>
+                        <input type="text" class="factor-input extra-band-input"
+                            prop:value=format!("{:.1}", hi / 1000.0)
+                            on:change=on_edge(i, true)
+                            title="Upper edge in kHz"
+                        />
+                        <span class="het-value">"kHz"</span>
+                        <button class="extra-band-remove"
+                            on:click=move |_| state.remove_extra_focus_band(i)
+                            title="Remove this focus band"
+                        >"\u{00d7}"</button>
+                    </div>
+                }
+            }).collect::<Vec<_>>()
+        }}
+        <Show when=move || state.extra_focus_bands.with(|b| b.len() < MAX_EXTRA_FOCUS_BANDS)>
+            <button class="layer-panel-opt"
+                on:click=move |_| state.add_extra_focus_band(current_file_max_freq(state))
+                title="Color another frequency band (defaults to the 2nd harmonic of the main band)"
+            >"+ Add band"</button>
+        </Show>
+    }
+}
+
 #[component]
 pub fn HfrButton() -> impl IntoView {
     let state = expect_context::<AppState>();
@@ -379,6 +511,16 @@ pub fn HfrButton() -> impl IntoView {
         }
     };
 
+    let on_het_text = move |ev: web_sys::Event| {
+        use wasm_bindgen::JsCast;
+        let input: web_sys::HtmlInputElement = ev.target().unwrap().unchecked_into();
+        match parse_khz(&input.value()) {
+            Some(hz) => state.set_het_frequency_manual(hz),
+            // Restore the current value on bad input
+            None => input.set_value(&format!("{:.1}", state.het_frequency.get_untracked() / 1000.0)),
+        }
+    };
+
     let on_ps_text = move |ev: web_sys::Event| {
         use wasm_bindgen::JsCast;
         let input: web_sys::HtmlInputElement = ev.target().unwrap().unchecked_into();
@@ -479,6 +621,11 @@ pub fn HfrButton() -> impl IntoView {
                 <button class=move || layer_opt_class(state.hfr_enabled.get() && state.playback_mode.get() == PlaybackMode::ZeroCrossing)
                     on:click=set_mode(state, PlaybackMode::ZeroCrossing)
                 >"ZC \u{2014} Zero Crossing"</button>
+                <hr />
+                <button class="layer-panel-opt"
+                    on:click=move |_: web_sys::MouseEvent| auto_focus(state)
+                    title="Set the focus band to the range holding 90% of the energy above the noise floor (selection, or the first 30 s)"
+                >"Auto focus on call energy"</button>
 
                 // ── Inaudible notice ──
                 {move || (state.playback_mode.get() == PlaybackMode::Normal && state.band_ff_freq_lo.get() >= 20_000.0).then(|| {
@@ -489,6 +636,12 @@ pub fn HfrButton() -> impl IntoView {
                     }
                 })}
 
+                // ── Extra focus bands ──
+                <Show when=move || state.hfr_enabled.get() && state.band_ff_freq_hi.get() > state.band_ff_freq_lo.get()>
+                    <hr />
+                    <ExtraFocusBandsEditor />
+                </Show>
+
                 // ── Adjustment ──
                 <Show when=move || state.playback_mode.get() != PlaybackMode::Normal>
                     <hr />
@@ -508,12 +661,33 @@ pub fn HfrButton() -> impl IntoView {
                                     }
                                 >
                                     <label>"Freq"</label>
-                                    <span class="het-value">{move || format!("{:.1} kHz", state.het_frequency.get() / 1000.0)}</span>
+                                    <input type="text" class="factor-input het-freq-input"
+                                        prop:value=move || format!("{:.1}", state.het_frequency.get() / 1000.0)
+                                        on:change=on_het_text
+                                        on:focus=move |ev: web_sys::FocusEvent| {
+                                            use wasm_bindgen::JsCast;
+                                            if let Some(input) = ev.target().and_then(|t| t.dyn_into::<web_sys::HtmlInputElement>().ok()) {
+                                                input.select();
+                                            }
+                                        }
+                                        title="Heterodyne frequency in kHz (\u{2191}/\u{2193} nudges by 1 kHz, Shift for 0.1 kHz)"
+                                    />
+                                    <span class="het-value">"kHz"</span>
                                     <button class=move || if state.het_freq_auto.get() { "auto-toggle on" } else { "auto-toggle" }
                                         on:click=move |_| state.het_freq_auto.update(|v| *v = !*v)
                                         title="Toggle auto HET frequency"
                                     >"A"</button>
                                 </div>
+                                <div class="factor-presets">
+                                    {HET_PRESETS.iter().map(|&(hz, label)| {
+                                        let is_sel = move || (state.het_frequency.get() - hz).abs() < 1.0 && !state.het_freq_auto.get();
+                                        view! {
+                                            <button class=move || if is_sel() { "factor-preset sel" } else { "factor-preset" }
+                                                on:click=move |_| state.set_het_frequency_manual(hz)
+                                            >{label}</button>
+                                        }
+                                    }).collect::<Vec<_>>()}
+                                </div>
                                 <div class="layer-panel-slider-row het-text-row"
                                     on:mouseenter=move |_| {
                                         state.het_interacting.set(true);
@@ -531,6 +705,26 @@ pub fn HfrButton() -> impl IntoView {
                                         title="Toggle auto LP cutoff"
                                     >"A"</button>
                                 </div>
+                                <div class="layer-panel-slider-row het-text-row">
+                                    <label title="Low-pass steepness in dB/octave. Gentle sounds more open; steep isolates a narrow band around the tuned frequency.">"Rolloff"</label>
+                                    <div class="factor-presets">
+                                        {HET_ORDER_PRESETS.iter().map(|&(order, label)| {
+                                            let is_sel = move || state.het_lp_order.get() == order;
+                                            let on_click = move |_| {
+                                                state.het_lp_order.set(order);
+                                                if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                                                    let _ = ls.set_item("oversample_het_lp_order", &order.to_string());
+                                                }
+                                            };
+                                            view! {
+                                                <button class=move || if is_sel() { "factor-preset sel" } else { "factor-preset" }
+                                                    on:click=on_click
+                                                    title=format!("{label} dB/octave")
+                                                >{label}</button>
+                                            }
+                                        }).collect::<Vec<_>>()}
+                                    </div>
+                                </div>
                             }.into_any(),
 
                             PlaybackMode::TimeExpansion => view! {
@@ -583,6 +777,17 @@ pub fn HfrButton() -> impl IntoView {
                                         }}</div>
                                     </div>
                                 </Show>
+                                <div class="layer-panel-slider-row">
+                                    <label>"Quality"</label>
+                                    <button class=move || if !state.te_hq.get() { "auto-toggle on" } else { "auto-toggle" }
+                                        on:click=move |_| state.te_hq.set(false)
+                                        title="Standard mode \u{2014} the browser converts the slowed audio to the output rate"
+                                    >"Std"</button>
+                                    <button class=move || if state.te_hq.get() { "auto-toggle on" } else { "auto-toggle" }
+                                        on:click=move |_| state.te_hq.set(true)
+                                        title="HQ mode \u{2014} windowed-sinc resampling to the output rate, free of interpolation buzz"
+                                    >"HQ"</button>
+                                </div>
                             }.into_any(),
 
                             PlaybackMode::PitchShift => view! {
@@ -113,7 +113,7 @@ pub fn start_inertia(
     canvas_width: f64,
     time_resolution: f64,
     duration: f64,
-    from_here_mode: bool,
+    from_here: Option<f64>,
     generation: StoredValue<u32>,
 ) {
     if velocity_px_per_sec.abs() < MIN_VELOCITY || canvas_width == 0.0 {
@@ -138,7 +138,7 @@ pub fn start_inertia(
         let max = (total_time - visible_time).max(oldest);
         (oldest, max)
     } else {
-        viewport::scroll_bounds_for_mode(duration, visible_time, from_here_mode)
+        viewport::scroll_bounds_for_mode(duration, visible_time, from_here)
     };
 
     // Threshold in time-domain units
@@ -19,6 +19,7 @@ pub mod gutter;
 pub mod xc_browser;
 pub mod zc_chart;
 pub mod chromagram_view;
+pub mod diff_view;
 pub mod pinch;
 pub mod inertia;
 pub mod debug_panel;
@@ -1,7 +1,7 @@
 use leptos::prelude::*;
 use crate::state::{ActiveFocus, AppState, Selection};
 use crate::annotations::{Annotation, AnnotationKind, AnnotationSet, Marker, Region, generate_default_label, generate_uuid, now_iso8601};
-use crate::canvas::spectrogram_renderer::freq_to_y;
+use crate::canvas::spectrogram_renderer::freq_span_y;
 use crate::components::file_sidebar::settings_panel::{
     toggle_annotation_lock, delete_annotation,
 };
@@ -150,8 +150,8 @@ fn get_freq_bounds(state: &AppState) -> (f64, f64) {
 }
 
 /// Format freq range for tooltip.
-fn fmt_freq_range(lo: f64, hi: f64) -> String {
-    format!("{:.0}\u{2013}{:.0} kHz", lo / 1000.0, hi / 1000.0)
+fn fmt_freq_range(state: &AppState, lo: f64, hi: f64) -> String {
+    state.freq_unit.get().format_range(lo, hi)
 }
 
 /// Compute the pixel position of the top-right corner of the transient selection
@@ -173,7 +173,7 @@ fn selection_top_right(
     let x1 = ((sel.time_end - start_time) * px_per_sec).min(canvas_width);
 
     let y0 = match sel.freq_high {
-        Some(fh) => freq_to_y(fh, min_freq, max_freq, canvas_height).max(0.0),
+        Some(fh) => freq_span_y(sel.freq_low.unwrap_or(fh), fh, min_freq, max_freq, canvas_height).0.max(0.0),
         None => 0.0,
     };
 
@@ -183,6 +183,7 @@ fn selection_top_right(
 /// Compute the pixel position of the top-right corner of an annotation.
 fn annotation_top_right(
     time_end: f64,
+    freq_low: Option<f64>,
     freq_high: Option<f64>,
     scroll_offset: f64,
     time_resolution: f64,
@@ -199,7 +200,7 @@ fn annotation_top_right(
     let x1 = ((time_end - start_time) * px_per_sec).min(canvas_width);
 
     let y0 = match freq_high {
-        Some(fh) => freq_to_y(fh, min_freq, max_freq, canvas_height).max(0.0),
+        Some(fh) => freq_span_y(freq_low.unwrap_or(fh), fh, min_freq, max_freq, canvas_height).0.max(0.0),
         None => 0.0,
     };
 
@@ -339,9 +340,9 @@ fn SelectionOverflowMenu() -> impl IntoView {
         let sel = state.selection.get()?;
         let d = sel.time_end - sel.time_start;
         if d < 0.0001 { return None; }
-        let dur = crate::format_time::format_duration(d, 3);
+        let dur = state.duration_unit.get().format(d);
         let freq_text = match (sel.freq_low, sel.freq_high) {
-            (Some(fl), Some(fh)) => format!("{:.0} \u{2013} {:.0} kHz", fl / 1000.0, fh / 1000.0),
+            (Some(fl), Some(fh)) => state.freq_unit.get().format_range(fl, fh),
             _ => "\u{2014}".to_string(),
         };
         let has_freq = sel.freq_low.is_some() && sel.freq_high.is_some();
@@ -355,7 +356,7 @@ fn SelectionOverflowMenu() -> impl IntoView {
     let freq_text_sig = Signal::derive(move || {
         state.selection.get()
             .and_then(|s| match (s.freq_low, s.freq_high) {
-                (Some(fl), Some(fh)) => Some(format!("{:.0} \u{2013} {:.0} kHz", fl / 1000.0, fh / 1000.0)),
+                (Some(fl), Some(fh)) => Some(state.freq_unit.get().format_range(fl, fh)),
                 _ => None,
             })
             .unwrap_or_else(|| "\u{2014}".to_string())
@@ -373,7 +374,7 @@ fn SelectionOverflowMenu() -> impl IntoView {
 
     let contract_target_sig = Signal::derive(move || {
         let (lo, hi) = get_freq_bounds(&state);
-        if hi > lo { fmt_freq_range(lo, hi) } else { String::new() }
+        if hi > lo { fmt_freq_range(&state, lo, hi) } else { String::new() }
     });
 
     let on_expand = Callback::new(move |_: ()| {
@@ -458,6 +459,18 @@ fn SelectionOverflowMenu() -> impl IntoView {
                                             >
                                                 {btn_label}
                                             </button>
+                                            {has_freq.then(|| view! {
+                                                <button
+                                                    class="canvas-overflow-item"
+                                                    title="Set the HFR focus band to this selection (F)"
+                                                    on:click=move |_| {
+                                                        crate::components::axis_drag::focus_on_selection(state);
+                                                        is_open.set(false);
+                                                    }
+                                                >
+                                                    "Focus on selection"
+                                                </button>
+                                            })}
                                         }.into_any()
                                     } else {
                                         view! { <span></span> }.into_any()
@@ -510,7 +523,7 @@ fn AnnotationOverflowMenu() -> impl IntoView {
             .unwrap_or(400.0);
 
         let (x, y) = annotation_top_right(
-            region.time_end, region.freq_high,
+            region.time_end, region.freq_low, region.freq_high,
             scroll, time_res, zoom, canvas_w, canvas_h, min_freq, max_freq,
         );
         Some((x, y))
@@ -528,9 +541,9 @@ fn AnnotationOverflowMenu() -> impl IntoView {
         match &ann.kind {
             AnnotationKind::Region(r) => {
                 let d = r.time_end - r.time_start;
-                let dur = if d > 0.0001 { Some(crate::format_time::format_duration(d, 3)) } else { None };
+                let dur = if d > 0.0001 { Some(state.duration_unit.get().format(d)) } else { None };
                 let freq_text = match (r.freq_low, r.freq_high) {
-                    (Some(fl), Some(fh)) => format!("{:.0} \u{2013} {:.0} kHz", fl / 1000.0, fh / 1000.0),
+                    (Some(fl), Some(fh)) => state.freq_unit.get().format_range(fl, fh),
                     _ => "\u{2014}".to_string(),
                 };
                 let has_freq = r.freq_low.is_some() && r.freq_high.is_some();
@@ -577,7 +590,7 @@ fn AnnotationOverflowMenu() -> impl IntoView {
 
     let contract_target_sig = Signal::derive(move || {
         let (lo, hi) = get_freq_bounds(&state);
-        if hi > lo { fmt_freq_range(lo, hi) } else { String::new() }
+        if hi > lo { fmt_freq_range(&state, lo, hi) } else { String::new() }
     });
 
     let on_expand = Callback::new(move |_: ()| {
@@ -2,6 +2,8 @@ use std::cell::RefCell;
 use leptos::prelude::*;
 use wasm_bindgen::{Clamped, JsCast};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, ImageData, MouseEvent};
+use crate::canvas::colors::rgba_css;
+use crate::canvas::spectrogram_renderer::orient_freq_axis;
 use crate::state::{AppState, OverviewView};
 use crate::types::PreviewImage;
 
@@ -90,6 +92,7 @@ fn draw_overview_spectrogram(
     main_freq_crop_lo: f64,   // 0..1: low fraction of Nyquist shown in main view
     main_freq_crop_hi: f64,   // 0..1: high fraction of Nyquist shown in main view
     bookmarks: &[(f64,)],
+    bookmark_fill: &str,      // CSS colour for bookmark dots
     overview_freq_crop: f64,  // 0..1: fraction shown in the overview itself
     band_ff_range: Option<(f64, f64)>, // BandFF range as (lo_frac, hi_frac) of Nyquist
     clean_view: bool,         // hide all overlays (viewport rect, bookmarks, BandFF range)
@@ -131,6 +134,8 @@ fn draw_overview_spectrogram(
                 let _ = tc.put_image_data(&img, 0.0, 0.0);
             }
         }
+        ctx.save();
+        orient_freq_axis(ctx, ch);
         let _ = ctx.draw_image_with_html_canvas_element_and_sw_and_sh_and_dx_and_dy_and_dw_and_dh(
             &tmp,
             0.0, src_y,
@@ -138,6 +143,7 @@ fn draw_overview_spectrogram(
             0.0, 0.0,
             cw, ch,
         );
+        ctx.restore();
     }
 
     // Convert to px/sec using the true audio duration (not preview columns × FFT hop)
@@ -154,7 +160,10 @@ fn draw_overview_spectrogram(
         let vp_w = (visible_time * px_per_sec).max(2.0);
 
         // Vertical: map main view freq range into the overview's freq coordinate space.
-        // overview y=0 → top freq (ofc * Nyquist), y=ch → 0 Hz.
+        // overview y=0 → top freq (ofc * Nyquist), y=ch → 0 Hz, mirrored
+        // when the frequency axis is flipped.
+        ctx.save();
+        orient_freq_axis(ctx, ch);
         let vp_y1 = (ch * (1.0 - main_freq_crop_hi / ofc)).clamp(0.0, ch);
         let vp_y2 = (ch * (1.0 - main_freq_crop_lo / ofc)).clamp(0.0, ch);
         let vp_h = vp_y2 - vp_y1;
@@ -177,9 +186,10 @@ fn draw_overview_spectrogram(
                 ctx.stroke_rect(vp_x, band_ff_y1, vp_w, band_ff_y2 - band_ff_y1);
             }
         }
+        ctx.restore();
 
-        // Bookmark dots (yellow, top edge)
-        ctx.set_fill_style_str("rgba(255, 200, 50, 0.9)");
+        // Bookmark dots (top edge)
+        ctx.set_fill_style_str(bookmark_fill);
         for &(t,) in bookmarks {
             let x = t * px_per_sec;
             if x >= 0.0 && x <= cw {
@@ -228,6 +238,7 @@ fn draw_overview_waveform(
     zoom: f64,
     main_canvas_width: f64,
     bookmarks: &[(f64,)],
+    bookmark_fill: &str,
     gain_db: f64,
     clean_view: bool,
 ) {
@@ -364,7 +375,7 @@ fn draw_overview_waveform(
         ctx.stroke_rect(vp_x, 0.0, vp_w, ch as f64);
 
         // Bookmark dots
-        ctx.set_fill_style_str("rgba(255, 200, 50, 0.9)");
+        ctx.set_fill_style_str(bookmark_fill);
         for &(t,) in bookmarks {
             let x = t * px_per_sec;
             if x >= 0.0 && x <= cw as f64 {
@@ -452,8 +463,10 @@ pub fn OverviewPanel() -> impl IntoView {
         let cv = state.channel_view.get();
         let _mic_recording = state.mic_recording.get();
         let _mic_listening = state.mic_listening.get();
+        let _flipped = state.freq_axis_flipped.get();
         let auto_gain = state.auto_gain.get();
         let gain_db = if auto_gain { state.compute_auto_gain_untracked() } else { state.gain_db.get() };
+        let bookmark_fill = rgba_css(state.bookmark_color.get_untracked(), state.bookmark_opacity.get_untracked());
         // Re-read canvas dimensions when sidebar layout changes
         let _sidebar = state.sidebar_collapsed.get();
         let _sidebar_width = state.sidebar_width.get();
@@ -502,6 +515,7 @@ pub fn OverviewPanel() -> impl IntoView {
                         clamped, preview.width, preview.height,
                     ) {
                         let _ = tc.put_image_data(&img, 0.0, 0.0);
+                        orient_freq_axis(&ctx, ch);
                         let _ = ctx.draw_image_with_html_canvas_element_and_dw_and_dh(
                             &tmp, seg_x, 0.0, seg_w, ch,
                         );
@@ -551,7 +565,7 @@ pub fn OverviewPanel() -> impl IntoView {
                             file.audio.duration_secs,
                             0.0,
                             0.0, 1.0,
-                            &[],
+                            &[], &bookmark_fill,
                             1.0,
                             None,
                             true, // clean_view — overlays drawn by overlay Effect
@@ -572,7 +586,7 @@ pub fn OverviewPanel() -> impl IntoView {
                             file.audio.sample_rate,
                             file.spectrogram.time_resolution,
                             buf_scroll, 1.0, 0.0,
-                            &[], gain_db, true,
+                            &[], &bookmark_fill, gain_db, true,
                         );
                     } else if file.is_recording {
                         ctx.set_fill_style_str("#1a1a1a");
@@ -633,7 +647,7 @@ pub fn OverviewPanel() -> impl IntoView {
                         file.audio.sample_rate,
                         file.spectrogram.time_resolution,
                         0.0, 1.0, 0.0,
-                        &[], gain_db, true, // clean_view — overlays drawn by overlay Effect
+                        &[], &bookmark_fill, gain_db, true, // clean_view — overlays drawn by overlay Effect
                     );
                 }
             }
@@ -648,10 +662,13 @@ pub fn OverviewPanel() -> impl IntoView {
         let scroll = state.scroll_offset.get();
         let zoom = state.zoom_level.get();
         let bookmarks = state.bookmarks.get();
+        let bookmark_fill = rgba_css(state.bookmark_color.get(), state.bookmark_opacity.get());
         let clean_view = state.clean_view.get();
         let main_canvas_w = state.spectrogram_canvas_width.get();
         let min_display_freq = state.min_display_freq.get();
         let max_display_freq = state.max_display_freq.get();
+        let _flipped = state.freq_axis_flipped.get();
+        let _high_contrast = state.high_contrast.get();
         let band_ff_lo_hz = state.band_ff_freq_lo.get();
         let band_ff_hi_hz = state.band_ff_freq_hi.get();
         let overview_view = state.overview_view.get();
@@ -706,9 +723,12 @@ pub fn OverviewPanel() -> impl IntoView {
             let vp_y2 = (ch * (1.0 - main_freq_crop_lo)).clamp(0.0, ch);
             let vp_h = (vp_y2 - vp_y1).max(1.0);
 
+            ctx.save();
+            orient_freq_axis(&ctx, ch);
             ctx.set_stroke_style_str("rgba(255, 255, 255, 0.7)");
             ctx.set_line_width(1.5);
             ctx.stroke_rect(vp_x, vp_y1, vp_w, vp_h);
+            ctx.restore();
 
             // Gap indicators
             for i in 0..tl.segments.len() {
@@ -742,6 +762,7 @@ pub fn OverviewPanel() -> impl IntoView {
                 clock_cfg,
                 state.show_clock_time.get(),
                 1.0,
+                state.time_tick_density.get().spacing_px(),
             );
         } else {
             // ── Single file overlay ──
@@ -777,6 +798,8 @@ pub fn OverviewPanel() -> impl IntoView {
                         .unwrap_or(0.0);
 
                     let ofc = 1.0; // overview always shows full frequency range
+                    ctx.save();
+                    orient_freq_axis(&ctx, ch);
                     let vp_y1 = (ch * (1.0 - main_freq_crop_hi / ofc)).clamp(0.0, ch);
                     let vp_y2 = (ch * (1.0 - main_freq_crop_lo / ofc)).clamp(0.0, ch);
                     let vp_h = vp_y2 - vp_y1;
@@ -801,6 +824,7 @@ pub fn OverviewPanel() -> impl IntoView {
                             ctx.stroke_rect(vp_x, band_ff_y1, vp_w, band_ff_y2 - band_ff_y1);
                         }
                     }
+                    ctx.restore();
                 }
                 OverviewView::Waveform => {
                     // Full-height viewport rect
@@ -813,7 +837,7 @@ pub fn OverviewPanel() -> impl IntoView {
             }
 
             // Bookmark dots
-            ctx.set_fill_style_str("rgba(255, 200, 50, 0.9)");
+            ctx.set_fill_style_str(&bookmark_fill);
             for bm in bookmarks.iter() {
                 let x = bm.time * px_per_sec;
                 if x >= 0.0 && x <= cw {
@@ -845,6 +869,7 @@ pub fn OverviewPanel() -> impl IntoView {
                 clock_cfg,
                 state.show_clock_time.get(),
                 1.0,
+                state.time_tick_density.get().spacing_px(),
             );
         }
     });
@@ -1068,6 +1093,7 @@ pub fn OverviewPanel() -> impl IntoView {
                     } else { 0.0 };
                     format!("{:.2}%", pct)
                 }
+                style:background=move || rgba_css(state.playhead_color.get(), state.playhead_opacity.get())
                 style:display=move || if state.is_playing.get() && !state.clean_view.get() { "block" } else { "none" }
             />
 
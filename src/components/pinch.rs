@@ -15,7 +15,8 @@ pub struct FreqPinchState {
     pub initial_max_freq: f64,
     /// Gutter-canvas-local y of the midpoint at gesture start.
     pub initial_mid_canvas_y: f64,
-    /// File Nyquist. new_min/new_max are clamped to [0, nyquist].
+    /// Display ceiling: the file's Nyquist, or above it when "Allow above
+    /// Nyquist" is on. new_min/new_max are clamped to [0, nyquist].
     pub nyquist: f64,
 }
 
@@ -53,13 +54,20 @@ pub fn apply_freq_pinch(
     let min_range_hz = 500.0_f64.min(ps.nyquist.max(500.0));
     let new_range = (initial_range * scale).clamp(min_range_hz, ps.nyquist.max(min_range_hz));
 
+    // Fraction of the range down from the top frequency at canvas y.
+    let flipped = crate::viewport::freq_axis_flipped();
+    let frac_from_top = |y: f64| {
+        let frac = (y / canvas_h).clamp(0.0, 1.0);
+        if flipped { 1.0 - frac } else { frac }
+    };
+
     // Anchor: freq under the initial midpoint y.
-    let initial_mid_frac = (ps.initial_mid_canvas_y / canvas_h).clamp(0.0, 1.0);
+    let initial_mid_frac = frac_from_top(ps.initial_mid_canvas_y);
     let anchor_freq = ps.initial_max_freq - initial_mid_frac * initial_range;
 
     // Place that freq at the CURRENT midpoint y — this handles both zoom
     // (scale change) and two-finger pan (midpoint shift) simultaneously.
-    let current_mid_frac = (current_mid_canvas_y / canvas_h).clamp(0.0, 1.0);
+    let current_mid_frac = frac_from_top(current_mid_canvas_y);
     let mut new_max = anchor_freq + current_mid_frac * new_range;
     let mut new_min = new_max - new_range;
 
@@ -89,8 +97,8 @@ pub struct PinchState {
     pub time_res: f64,
     /// File duration in seconds (for scroll clamping).
     pub duration: f64,
-    /// Whether FromHere viewport bounds should be used.
-    pub from_here_mode: bool,
+    /// Marker fraction when FromHere viewport bounds should be used.
+    pub from_here: Option<f64>,
 }
 
 /// Returns (midpoint_client_x, distance) for exactly 2 touches.
@@ -113,12 +121,14 @@ pub fn two_finger_geometry(touches: &web_sys::TouchList) -> Option<(f64, f64)> {
 ///
 /// Anchor-point zoom: the time under the initial midpoint stays fixed as fingers spread/contract.
 /// Two-finger pan: horizontal midpoint movement also translates scroll_offset.
+/// Zoom is capped at `max_zoom` (see `AppState::max_zoom`).
 pub fn apply_pinch(
     pinch: &PinchState,
     current_dist: f64,
     current_mid_client_x: f64,
     canvas_left: f64,
     canvas_width: f64,
+    max_zoom: f64,
 ) -> (f64, f64) {
     if canvas_width == 0.0 || pinch.initial_dist < 10.0 {
         return (pinch.initial_zoom, pinch.initial_scroll);
@@ -126,7 +136,7 @@ pub fn apply_pinch(
 
     // Zoom proportional to finger distance ratio
     let scale = current_dist / pinch.initial_dist;
-    let new_zoom = (pinch.initial_zoom * scale).clamp(viewport::MIN_ZOOM, viewport::MAX_ZOOM);
+    let new_zoom = (pinch.initial_zoom * scale).clamp(viewport::MIN_ZOOM, max_zoom);
 
     // What time was under the initial midpoint?
     let initial_visible_time = viewport::visible_time(canvas_width, pinch.initial_zoom, pinch.time_res);
@@ -149,7 +159,7 @@ pub fn apply_pinch(
         raw_scroll,
         pinch.duration,
         new_visible_time,
-        pinch.from_here_mode,
+        pinch.from_here,
     );
 
     (new_zoom, new_scroll)
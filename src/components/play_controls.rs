@@ -17,6 +17,15 @@ pub fn ToastDisplay() -> impl IntoView {
                     <span class="status-toast status-toast-info">"Buffering\u{2026}"</span>
                 }
             })}
+            // Output limiter engaged (heterodyne/ZC too loud for full scale)
+            {move || state.output_limiting.get().then(|| {
+                view! {
+                    <span
+                        class="status-toast status-toast-info"
+                        title="Output is being limited to avoid clipping \u{2014} lower the gain for a cleaner sound"
+                    >"Limiting"</span>
+                }
+            })}
             {move || state.status_message.get().map(|msg| {
                 let state2 = state;
                 wasm_bindgen_futures::spawn_local(async move {
@@ -5,7 +5,23 @@
 // smoothly at 60fps without invalidating the expensive underlying image.
 
 use leptos::prelude::*;
-use crate::state::AppState;
+use crate::canvas::colors::rgba_css;
+use crate::canvas::spectrogram_renderer::freq_to_y;
+use crate::components::analysis_panel::column_peak_freq;
+use crate::state::{AppState, PlayheadTrail};
+
+/// How far the loudest bin must stand above the band's median level to
+/// leave a trail mark, so silence between calls doesn't scatter marks
+/// across the noise.
+const TRAIL_MIN_PROMINENCE_DB: f32 = 15.0;
+/// Most columns sampled per playhead update; larger jumps are seeks.
+const TRAIL_MAX_STEP_COLUMNS: usize = 256;
+/// Cap on marks kept, so a persistent trail over a long file stays cheap.
+const TRAIL_MAX_POINTS: usize = 20_000;
+/// Opacity steps a fading trail is drawn in (one SVG path each).
+const TRAIL_FADE_STEPS: usize = 6;
+/// Height of the trail's SVG coordinate space; stretched to the stage.
+const TRAIL_VIEW_HEIGHT: f64 = 1000.0;
 
 /// Shared playhead line. Mount inside a position-relative stage whose
 /// width matches the main canvas. `x_offset` inserts a left-side inset
@@ -46,9 +62,180 @@ pub fn Playhead(#[prop(default = 0.0)] x_offset: f64) -> impl IntoView {
         <div
             class="playhead-line"
             style:transform=transform
+            style:background=move || rgba_css(state.playhead_color.get(), state.playhead_opacity.get())
             style:display=move || {
                 if state.is_playing.get() && !state.clean_view.get() { "block" } else { "none" }
             }
         />
     }
 }
+
+/// Time resolution and Nyquist of the current file's spectrogram.
+fn current_axes(state: AppState, track: bool) -> Option<(f64, f64)> {
+    let axes = |files: &Vec<crate::state::LoadedFile>, idx: Option<usize>| {
+        idx.and_then(|i| files.get(i)).map(|f| (f.spectrogram.time_resolution, f.spectrogram.max_freq))
+    };
+    if track {
+        state.files.with(|files| axes(files, state.current_file_index.get()))
+    } else {
+        state.files.with_untracked(|files| axes(files, state.current_file_index.get_untracked()))
+    }
+}
+
+/// Trail of marks at the loudest frequency under the playhead, left behind
+/// as it plays so the call's frequency track is traced while listening.
+/// Mount next to [`Playhead`] in the spectrogram stage. Consecutive marks
+/// are joined; marks fade over `playhead_trail_secs` or persist until
+/// playback restarts, depending on `playhead_trail`. Drawn as a few SVG
+/// paths so the spectrogram canvas itself is never redrawn.
+#[component]
+pub fn PlayheadTrail() -> impl IntoView {
+    let state = expect_context::<AppState>();
+    // Spectrogram column and peak frequency of each mark, oldest first
+    let marks: RwSignal<Vec<(usize, f64)>> = RwSignal::new(Vec::new());
+
+    // Start afresh when playback starts and whenever the file changes
+    let last_source: StoredValue<(bool, Option<usize>)> = StoredValue::new((false, None));
+    Effect::new(move || {
+        let source = (state.is_playing.get(), state.current_file_index.get());
+        let (was_playing, prev_file) = last_source.get_value();
+        last_source.set_value(source);
+        if (source.0 && !was_playing) || source.1 != prev_file {
+            marks.set(Vec::new());
+        }
+    });
+
+    // Sample every column the playhead passed since the last update
+    let last_column: StoredValue<Option<usize>> = StoredValue::new(None);
+    Effect::new(move || {
+        let playhead = state.playhead_time.get();
+        if !state.is_playing.get_untracked()
+            || state.playhead_trail.get_untracked() == PlayheadTrail::Off
+            || state.active_timeline.with_untracked(|tl| tl.is_some())
+        {
+            last_column.set_value(None);
+            return;
+        }
+        let Some((time_res, file_max_freq)) = current_axes(state, false) else { return };
+        if time_res <= 0.0 || playhead < 0.0 {
+            return;
+        }
+        let column = (playhead / time_res) as usize;
+        let first = match last_column.get_value() {
+            Some(last) if last < column && column - last <= TRAIL_MAX_STEP_COLUMNS => last + 1,
+            Some(last) if last == column => return,
+            _ => column,
+        };
+        last_column.set_value(Some(column));
+        let freq_lo = state.min_display_freq.get_untracked().unwrap_or(0.0);
+        let freq_hi = state.max_display_freq.get_untracked().unwrap_or(file_max_freq).min(file_max_freq);
+        let new_marks: Vec<(usize, f64)> = (first..=column)
+            .filter_map(|c| column_peak_freq(state, c, freq_lo, freq_hi, TRAIL_MIN_PROMINENCE_DB).map(|f| (c, f)))
+            .collect();
+        if new_marks.is_empty() {
+            return;
+        }
+        marks.update(|m| {
+            m.extend(new_marks);
+            if m.len() > TRAIL_MAX_POINTS {
+                m.drain(..m.len() - TRAIL_MAX_POINTS);
+            }
+        });
+    });
+
+    // One path per opacity step, in a coordinate space TRAIL_VIEW_HEIGHT
+    // tall and as wide as the canvas in pixels. Only marks on screen are
+    // formatted, and only a fading trail redraws as the playhead moves.
+    let paths = move || {
+        let style = state.playhead_trail.get();
+        let cw = state.spectrogram_canvas_width.get();
+        let scroll = state.scroll_offset.get();
+        let zoom = state.zoom_level.get();
+        let playhead = if style == PlayheadTrail::Fading { state.playhead_time.get() } else { 0.0 };
+        let trail_secs = state.playhead_trail_secs.get();
+        let min_display = state.min_display_freq.get();
+        let max_display = state.max_display_freq.get();
+        let _flipped = state.freq_axis_flipped.get();
+        let Some((time_res, file_max_freq)) = current_axes(state, true) else {
+            return Vec::new();
+        };
+        let max_freq = max_display.unwrap_or(file_max_freq).min(state.freq_view_ceiling(file_max_freq));
+        let min_freq = min_display.unwrap_or(0.0);
+        let visible_time = (cw / zoom) * time_res;
+        if visible_time <= 0.0 || max_freq <= min_freq {
+            return Vec::new();
+        }
+        let px_per_sec = cw / visible_time;
+        let steps = if style == PlayheadTrail::Fading { TRAIL_FADE_STEPS } else { 1 };
+        let mut ds = vec![String::new(); steps];
+        let mut prev: Option<(usize, usize)> = None;
+        // One column of slack either side so lines run off the edges
+        let first_col = (scroll / time_res).floor() - 1.0;
+        let last_col = ((scroll + visible_time) / time_res).ceil() + 1.0;
+        marks.with(|m| {
+            for &(column, freq) in m {
+                if !(first_col..=last_col).contains(&(column as f64)) {
+                    prev = None;
+                    continue;
+                }
+                let t = (column as f64 + 0.5) * time_res;
+                let step = if style == PlayheadTrail::Fading {
+                    let age = (playhead - t) / trail_secs;
+                    if !(0.0..1.0).contains(&age) {
+                        prev = None;
+                        continue;
+                    }
+                    // Oldest marks in step 0
+                    steps - 1 - (age * steps as f64) as usize
+                } else {
+                    0
+                };
+                let x = (t - scroll) * px_per_sec;
+                let y = freq_to_y(freq, min_freq, max_freq, TRAIL_VIEW_HEIGHT);
+                // Join to the previous mark if it's the neighbouring column
+                let joined = prev.is_some_and(|(c, s)| column <= c + 2 && s == step);
+                let d = &mut ds[step];
+                if joined {
+                    d.push_str(&format!("L{x:.1} {y:.1}"));
+                } else {
+                    d.push_str(&format!("M{x:.1} {y:.1}l0 0"));
+                }
+                prev = Some((column, step));
+            }
+        });
+        ds.into_iter()
+            .enumerate()
+            .filter(|(_, d)| !d.is_empty())
+            .map(|(i, d)| (d, (i + 1) as f64 / steps as f64))
+            .collect::<Vec<_>>()
+    };
+
+    let visible = move || {
+        match state.playhead_trail.get() {
+            PlayheadTrail::Off => false,
+            PlayheadTrail::Fading => state.is_playing.get(),
+            PlayheadTrail::Persistent => true,
+        }
+    };
+
+    view! {
+        <svg
+            class="playhead-trail"
+            viewBox=move || format!("0 0 {:.0} {}", state.spectrogram_canvas_width.get().max(1.0), TRAIL_VIEW_HEIGHT)
+            preserveAspectRatio="none"
+            style:display=move || if visible() && !state.clean_view.get() { "block" } else { "none" }
+        >
+            {move || {
+                let stroke = rgba_css(state.playhead_trail_color.get(), 1.0);
+                let opacity = state.playhead_trail_opacity.get();
+                paths().into_iter().map(|(d, fade)| view! {
+                    <path
+                        d=d
+                        stroke=stroke.clone()
+                        stroke-opacity=format!("{:.2}", opacity * fade)
+                    />
+                }).collect_view()
+            }}
+        </svg>
+    }
+}
@@ -8,12 +8,13 @@ use std::rc::Rc;
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
+use crate::canvas::colors::rgba_css;
 use crate::canvas::freq_adjustments::compute_freq_adjustments;
-use crate::canvas::spectrogram_renderer::{self, Colormap, ColormapMode, FreqMarkerState, FreqShiftMode, PreRendered, SpectDisplaySettings};
+use crate::canvas::spectrogram_renderer::{self, Colormap, ColormapMode, ExtraFocusBands, FreqMarkerState, FreqShiftMode, PreRendered, SpectDisplaySettings};
 use crate::components::spectrogram_events::{self, SpectInteraction, LABEL_AREA_WIDTH};
 use crate::components::gutter::{BandGutter, TimeGutter};
-use crate::components::playhead::Playhead;
-use crate::state::{AppState, CanvasTool, SpectrogramHandle, MainView, PlaybackMode};
+use crate::components::playhead::{Playhead, PlayheadTrail};
+use crate::state::{AppState, CanvasTool, SelectionFocusDim, SpectrogramHandle, MainView, PlaybackMode, SpectrogramDisplay};
 use crate::viewport;
 
 /// Pick the right `DebugTileKind` so the debug-tiles overlay reads from the
@@ -56,6 +57,10 @@ pub fn Spectrogram() -> impl IntoView {
     let ix = SpectInteraction::new();
     let label_hover_target = ix.label_hover_target;
     let anim_gen: Rc<Cell<u32>> = Rc::new(Cell::new(0));
+    // Auto-exposure offset the draw effect would like; state.auto_exposure_db
+    // eases toward it so levels don't jump as columns scroll in and out.
+    let exposure_target = RwSignal::new(0.0f32);
+    let exposure_gen: Rc<Cell<u32>> = Rc::new(Cell::new(0));
 
     // Disposal guard: async callbacks (rAF, setTimeout) check this before
     // accessing any reactive state, preventing panics after component unmount.
@@ -114,6 +119,34 @@ pub fn Spectrogram() -> impl IntoView {
         cb.forget();
     }});
 
+    // Auto-exposure easing: same rAF-only write pattern as the label hover
+    // animation above. Turning auto-exposure off snaps the offset to 0.
+    Effect::new({
+        let disposed = disposed.clone();
+        move || {
+        let target = if state.spect_auto_exposure.get() { exposure_target.get() } else { 0.0 };
+        let current = state.auto_exposure_db.get();
+        if current == target {
+            return;
+        }
+        let generation = exposure_gen.get().wrapping_add(1);
+        exposure_gen.set(generation);
+        let eg = exposure_gen.clone();
+        let disposed_rc = disposed.clone();
+        let cb = Closure::once(move || {
+            if disposed_rc.load(Ordering::Relaxed) || eg.get() != generation { return; }
+            let Some(cur) = state.auto_exposure_db.try_get_untracked() else { return; };
+            let Some(on) = state.spect_auto_exposure.try_get_untracked() else { return; };
+            let next = if on { cur + (target - cur) * 0.15 } else { target };
+            let next = if (next - target).abs() < 0.1 { target } else { next };
+            state.auto_exposure_db.set(next);
+        });
+        let _ = web_sys::window().unwrap().request_animation_frame(
+            cb.as_ref().unchecked_ref(),
+        );
+        cb.forget();
+    }});
+
     // Effect 1: keep legacy pre-render state cleared.
     // The active draw path is tile-based for normal spectrogram rendering, so
     // building a full-image pre-render here only duplicates load-time work.
@@ -127,6 +160,11 @@ pub fn Spectrogram() -> impl IntoView {
     // Cache-clearing effects: invalidate tile caches when FFT mode, flow, transform, or reassignment changes
     crate::canvas::tile_scheduler::setup_cache_clearing_effects(state);
 
+    // Only keep column energies while the amplitude envelope is shown
+    Effect::new(move || {
+        spectrogram_renderer::set_envelope_enabled(state.show_envelope_overlay.get());
+    });
+
     // ResizeObserver: watch the parent .chart-stage's CSS box and bump the
     // render tick whenever it changes. Observing the parent (which flex sizes
     // correctly) rather than the canvas (which may be stuck at its intrinsic
@@ -164,8 +202,16 @@ pub fn Spectrogram() -> impl IntoView {
         let scroll = state.scroll_offset.get();
         let zoom = state.zoom_level.get();
         let bookmarks = state.bookmarks.get();
+        let bookmark_fill = rgba_css(state.bookmark_color.get(), state.bookmark_opacity.get());
         let canvas_tool = state.canvas_tool.get();
         let selection = state.selection.get();
+        let snap_preview = state.snap_preview.get()
+            .filter(|(drawn, _)| Some(*drawn) == selection)
+            .map(|(_, snapped)| snapped);
+        let selection_color = state.selection_color.get();
+        let selection_focus_dim = state.selection_focus_dim.get();
+        let focus_dim_opacity = state.focus_dim_opacity.get();
+        let harmonic_shadow_color = state.harmonic_shadow_color.get();
         let is_playing = state.is_playing.get();
         let het_interacting = state.het_interacting.get();
         let dragging = state.is_dragging.get();
@@ -177,8 +223,20 @@ pub fn Spectrogram() -> impl IntoView {
         let playback_mode = state.playback_mode.get();
         let min_display_freq = state.min_display_freq.get();
         let max_display_freq = state.max_display_freq.get();
+        let _flipped = state.freq_axis_flipped.get();
+        let _high_contrast = state.high_contrast.get();
         let mouse_freq = state.mouse_freq.get();
         let mouse_cx = state.mouse_canvas_x.get();
+        // Pixel inspect needs the crosshair readout to show its trace
+        let pixel_inspect = state.debug_pixel_inspect.get();
+        let show_crosshair = state.show_crosshair.get() || pixel_inspect;
+        let show_envelope = state.show_envelope_overlay.get();
+        let reference_lines = if state.show_reference_lines.get() {
+            state.reference_lines.get()
+        } else {
+            Vec::new()
+        };
+        let cursor_time = if show_crosshair { state.cursor_time.get() } else { None };
         let label_opacity = state.label_hover_opacity.get();
         let filter_hovering = state.filter_hovering_band.get();
         let filter_enabled = state.filter_enabled.get();
@@ -187,6 +245,7 @@ pub fn Spectrogram() -> impl IntoView {
         let pointer_down = state.pointer_is_down.get();
         let band_ff_lo = state.band_ff_freq_lo.get();
         let band_ff_hi = state.band_ff_freq_hi.get();
+        let extra_focus_bands = state.extra_focus_bands.get();
         let het_freq_auto = state.het_freq_auto.get();
         let het_cutoff_auto = state.het_cutoff_auto.get();
         let hfr_enabled = state.hfr_enabled.get();
@@ -197,8 +256,18 @@ pub fn Spectrogram() -> impl IntoView {
         let _flow_sg = state.flow_shift_gain.get();
         let _flow_cg = state.flow_color_gamma.get();
         let _flow_scheme = state.flow_color_scheme.get(); // trigger redraw on color scheme change
+        let _flow_blend = state.flow_blend_mode.get();
+        let _coherence_cm = state.coherence_colormap.get();
         let colormap_pref = state.colormap_preference.get();
+        spectrogram_renderer::set_background_color(state.spectrogram_background.get());
+        spectrogram_renderer::set_freq_marker_style(state.marker_colors.get(), state.freq_marker_interval_khz.get() * 1000.0);
         let hfr_colormap_pref = state.hfr_colormap_preference.get();
+        let _hfr_focus_contrast = (
+            state.hfr_focus_contrast.get(),
+            state.hfr_focus_gain_db.get(),
+            state.hfr_focus_range_db.get(),
+            state.hfr_focus_gamma.get(),
+        );
         let axis_drag_start = state.axis_drag_start_freq.get();
         let axis_drag_current = state.axis_drag_current_freq.get();
         let notch_bands = state.notch_bands.get();
@@ -220,6 +289,7 @@ pub fn Spectrogram() -> impl IntoView {
         let display_auto_gain = state.display_auto_gain.get();
         let _display_eq = state.display_eq.get();
         let _display_noise_filter = state.display_noise_filter.get();
+        let _display_pre_emphasis = state.display_pre_emphasis_db_oct.get();
         let _f_freq_lo = state.filter_freq_low.get();
         let _f_freq_hi = state.filter_freq_high.get();
         let _f_db_below = state.filter_db_below.get();
@@ -253,6 +323,9 @@ pub fn Spectrogram() -> impl IntoView {
         let _rsidebar = state.right_sidebar_collapsed.get();
         let _rsidebar_width = state.right_sidebar_width.get();
         let clean_view = state.clean_view.get();
+        let time_gridlines = state.time_gridlines.get();
+        let no_data_fill = state.no_data_fill.get();
+        let tick_spacing = state.time_tick_density.get().spacing_px();
 
         let Some(canvas_el) = canvas_ref.get() else { return };
         let canvas: &HtmlCanvasElement = canvas_el.as_ref();
@@ -335,10 +408,12 @@ pub fn Spectrogram() -> impl IntoView {
         } else {
             original_max_freq
         };
-        let max_freq = max_display_freq.unwrap_or(file_max_freq).min(file_max_freq);
+        // With "Allow above Nyquist" the range may end past the data; the
+        // blits then leave the band above Nyquist empty (freq_crop_hi > 1).
+        let max_freq = max_display_freq.unwrap_or(file_max_freq).min(state.freq_view_ceiling(file_max_freq));
         let min_freq = min_display_freq.unwrap_or(0.0);
         let freq_crop_lo = min_freq / file_max_freq;
-        let freq_crop_hi = (max_freq / file_max_freq).min(1.0);
+        let freq_crop_hi = max_freq / file_max_freq;
 
         // In viewport-zoom Resonators mode, tiles don't cover [0, file_max_freq].
         // Their frequency axis is the committed `resonator_viewport_range`,
@@ -371,6 +446,8 @@ pub fn Spectrogram() -> impl IntoView {
                 colormap: hfr_colormap_pref,
                 band_ff_lo_frac: band_ff_lo / file_max_freq,
                 band_ff_hi_frac: band_ff_hi / file_max_freq,
+                extra_bands: ExtraFocusBands::from_hz(&extra_focus_bands, file_max_freq),
+                focus_settings: None, // filled in once the reference level is known
             }
         } else if hfr_enabled {
             ColormapMode::Uniform(hfr_colormap_pref)
@@ -419,18 +496,45 @@ pub fn Spectrogram() -> impl IntoView {
         // Extra dB boost from Auto/Same gain modes (computed in app.rs Effect)
         let display_boost = state.display_gain_boost.get();
 
+        // Auto-exposure: place the 99th-percentile column peak of the visible
+        // range a few dB under the top of the colour range. The manual gain
+        // stays on top as exposure compensation. Only the target is set here;
+        // the easing effect moves auto_exposure_db, which this effect reads.
+        let auto_exposure = state.auto_exposure_db.get();
+        if state.spect_auto_exposure.get() && !waterfall_active && timeline.is_none() && total_cols > 0 {
+            const HEADROOM_DB: f32 = 3.0;
+            let first_col = scroll_col.max(0.0) as usize;
+            let last_col = first_col + (display_w as f64 / zoom).ceil() as usize + 1;
+            let peak_db = crate::canvas::spectral_store::visible_peak_db(file_idx_val, first_col, last_col, 0.99);
+            if let Some(peak_db) = peak_db {
+                let target = (spect_floor + spect_range - HEADROOM_DB - (peak_db - ref_db) - display_boost)
+                    .clamp(-60.0, 60.0);
+                if (target - exposure_target.get_untracked()).abs() > 0.25 {
+                    exposure_target.set(target);
+                }
+            }
+        }
+
         let display_settings = SpectDisplaySettings {
             floor_db: spect_floor,
             range_db: spect_range,
             gamma: spect_gamma,
-            gain_db: spect_gain - ref_db + display_boost,
+            gain_db: spect_gain - ref_db + display_boost + auto_exposure,
         };
+        let colormap = colormap.with_focus_settings(state.hfr_focus_display_settings(&display_settings));
 
         // ── Live waterfall rendering (recording / listening) ──
         // When the waterfall is active, render directly from it and skip the
         // tile-based pipeline entirely.
         let live_data_cols = state.mic_live_data_cols.get_untracked();
 
+        // Visible tiles rendered vs. total, for the progress indicator
+        let mut tiles_ready = 0usize;
+        let mut tiles_visible = 0usize;
+
+        // Spectrogram images are stored high-frequency-first; flip them with the axis
+        ctx.save();
+        spectrogram_renderer::orient_freq_axis(&ctx, display_h as f64);
         let base_drawn = if waterfall_active {
             // Use waterfall's own time_res and max_freq for correct viewport mapping
             let wf_time_res = crate::canvas::live_waterfall::time_resolution();
@@ -463,14 +567,13 @@ pub fn Spectrogram() -> impl IntoView {
                     wf_freq_crop_lo,
                     wf_freq_crop_hi,
                     &wf_display_settings,
-                    colormap,
+                    colormap.with_focus_settings(state.hfr_focus_display_settings(&wf_display_settings)),
                     live_data_cols,
                 );
                 if !rendered {
-                    // No waterfall data yet — clear to black so the old file's
+                    // No waterfall data yet — clear to background so the old file's
                     // spectrogram doesn't remain visible.
-                    ctx.set_fill_style_str("#000");
-                    ctx.fill_rect(0.0, 0.0, display_w as f64, display_h as f64);
+                    spectrogram_renderer::clear_background(&ctx, display_w as f64, display_h as f64);
                 }
                 rendered
             }
@@ -488,9 +591,8 @@ pub fn Spectrogram() -> impl IntoView {
             let visible_start = scroll;
             let visible_end = scroll + visible_time;
 
-            // Fill entire canvas with black first (covers gaps)
-            ctx.set_fill_style_str("#000");
-            ctx.fill_rect(0.0, 0.0, display_w as f64, display_h as f64);
+            // Fill entire canvas with the background first (covers gaps)
+            spectrogram_renderer::clear_background(&ctx, display_w as f64, display_h as f64);
 
             let mut any_drawn = false;
             for seg in tl.segments_in_range(visible_start, visible_end) {
@@ -531,7 +633,9 @@ pub fn Spectrogram() -> impl IntoView {
                 let seg_zoom = seg_px_per_sec * seg_time_res;
 
                 let resonators_on = main_view == MainView::Resonators;
-                let ideal_lod_for_source = crate::canvas::tile_cache::select_lod(seg_zoom);
+                // Segments sit at their own zooms, so they take the plain
+                // threshold LOD rather than the main view's hysteresis state
+                let ideal_lod_for_source = crate::canvas::tile_cache::ideal_lod(seg_zoom);
                 let tile_source = if resonators_on {
                     spectrogram_renderer::TileSource::Resonators
                 } else if reassign_on && ideal_lod_for_source > 1 {
@@ -548,7 +652,7 @@ pub fn Spectrogram() -> impl IntoView {
 
                 let drawn = spectrogram_renderer::blit_tiles_viewport(
                     &ctx, clip_right - clip_left, display_h as f64, seg.file_index, seg_total_cols,
-                    file_scroll_col, seg_zoom, freq_crop_lo, freq_crop_hi,
+                    file_scroll_col, seg_zoom, ideal_lod_for_source, freq_crop_lo, freq_crop_hi,
                     spectrogram_renderer::TileRenderMode::Spectrogram(colormap),
                     &display_settings,
                     freq_adjustments.as_deref(),
@@ -561,13 +665,15 @@ pub fn Spectrogram() -> impl IntoView {
                 if resonators_on {
                     crate::canvas::tile_scheduler::schedule_resonator_tiles(
                         state, seg.file_index, seg_total_cols, file_scroll_col, seg_zoom,
-                        clip_right - clip_left,
+                        ideal_lod_for_source, clip_right - clip_left,
                     );
                 } else {
-                    crate::canvas::tile_scheduler::schedule_normal_tiles(
+                    let (ready, total) = crate::canvas::tile_scheduler::schedule_normal_tiles(
                         state, seg.file_index, seg_total_cols, file_scroll_col, seg_zoom,
-                        clip_right - clip_left, seg_time_res, is_playing, reassign_on, &disposed,
+                        ideal_lod_for_source, clip_right - clip_left, seg_time_res, is_playing, reassign_on, &disposed,
                     );
+                    tiles_ready += ready;
+                    tiles_visible += total;
                 }
 
                 ctx.restore();
@@ -617,6 +723,7 @@ pub fn Spectrogram() -> impl IntoView {
             let cg = state.flow_color_gamma.get_untracked();
             let algo = state.spectrogram_display.get_untracked().flow_algo();
             let flow_scheme = state.flow_color_scheme.get_untracked();
+            let flow_blend = state.flow_blend_mode.get_untracked();
             let flow_render_mode = spectrogram_renderer::TileRenderMode::Flow {
                 intensity_gate: ig,
                 flow_gate: mg,
@@ -625,10 +732,13 @@ pub fn Spectrogram() -> impl IntoView {
                 color_gamma: cg,
                 algo,
                 scheme: flow_scheme,
+                blend: flow_blend,
+                coherence_colormap: state.coherence_colormap.get_untracked(),
             };
+            let lod = crate::canvas::tile_cache::main_view_lod(zoom);
             let drawn = spectrogram_renderer::blit_tiles_viewport(
                 &ctx, display_w as f64, display_h as f64, file_idx_val, total_cols,
-                scroll_col, zoom, freq_crop_lo, freq_crop_hi,
+                scroll_col, zoom, lod, freq_crop_lo, freq_crop_hi,
                 flow_render_mode, &display_settings, freq_adjustments.as_deref(),
                 file.and_then(|f| f.preview.as_ref()),
                 scroll, visible_time, duration,
@@ -637,14 +747,14 @@ pub fn Spectrogram() -> impl IntoView {
 
             // Schedule missing flow tiles
             crate::canvas::tile_scheduler::schedule_flow_tiles(
-                state, file_idx_val, total_cols, scroll_col, zoom, display_w as f64, algo,
+                state, file_idx_val, total_cols, scroll_col, zoom, lod, display_w as f64, algo,
             );
 
             drawn
         } else if !flow_on && total_cols > 0 {
             // Normal / reassignment / resonator tile-based rendering.
             let resonators_on = main_view == MainView::Resonators;
-            let ideal_lod_for_source = crate::canvas::tile_cache::select_lod(zoom);
+            let ideal_lod_for_source = crate::canvas::tile_cache::main_view_lod(zoom);
             let tile_source = if resonators_on {
                 spectrogram_renderer::TileSource::Resonators
             } else if reassign_on && ideal_lod_for_source > 1 {
@@ -669,7 +779,7 @@ pub fn Spectrogram() -> impl IntoView {
             };
             let drawn = spectrogram_renderer::blit_tiles_viewport(
                 &ctx, display_w as f64, display_h as f64, file_idx_val, total_cols,
-                scroll_col, zoom, blit_fc_lo, blit_fc_hi,
+                scroll_col, zoom, ideal_lod_for_source, blit_fc_lo, blit_fc_hi,
                 spectrogram_renderer::TileRenderMode::Spectrogram(colormap),
                 &display_settings,
                 freq_adjustments.as_deref(),
@@ -681,11 +791,12 @@ pub fn Spectrogram() -> impl IntoView {
             // Schedule missing tiles for the active source.
             if resonators_on {
                 crate::canvas::tile_scheduler::schedule_resonator_tiles(
-                    state, file_idx_val, total_cols, scroll_col, zoom, display_w as f64,
+                    state, file_idx_val, total_cols, scroll_col, zoom, ideal_lod_for_source,
+                    display_w as f64,
                 );
             } else {
-                crate::canvas::tile_scheduler::schedule_normal_tiles(
-                    state, file_idx_val, total_cols, scroll_col, zoom,
+                (tiles_ready, tiles_visible) = crate::canvas::tile_scheduler::schedule_normal_tiles(
+                    state, file_idx_val, total_cols, scroll_col, zoom, ideal_lod_for_source,
                     display_w as f64, time_res, is_playing, reassign_on, &disposed,
                 );
             }
@@ -711,11 +822,11 @@ pub fn Spectrogram() -> impl IntoView {
             );
             true
         } else {
-            ctx.set_fill_style_str("#000");
-            ctx.fill_rect(0.0, 0.0, display_w as f64, display_h as f64);
+            spectrogram_renderer::clear_background(&ctx, display_w as f64, display_h as f64);
             false
         }
         }; // end of waterfall-or-tile if/else
+        ctx.restore();
 
         // Tile debug overlay (drawn on top of tiles, under other overlays)
         if debug_tiles {
@@ -762,6 +873,24 @@ pub fn Spectrogram() -> impl IntoView {
             }
         }
 
+        // Above-Nyquist band: nothing was recorded there, so draw it as "no
+        // data" rather than leaving it looking like a silent region.
+        if base_drawn && max_freq > file_max_freq + 1.0 && file_max_freq > min_freq {
+            // Depth of the band from the high-frequency edge, drawn in image orientation
+            let h = display_h as f64;
+            let ny_y = spectrogram_renderer::freq_to_y(file_max_freq, min_freq, max_freq, h).round();
+            let depth = if crate::viewport::freq_axis_flipped() { h - ny_y } else { ny_y };
+            ctx.save();
+            spectrogram_renderer::orient_freq_axis(&ctx, h);
+            spectrogram_renderer::draw_no_data_region(&ctx, display_w as f64, depth, no_data_fill);
+            ctx.restore();
+        }
+
+        let tile_progress = (tiles_ready < tiles_visible).then_some((tiles_ready, tiles_visible));
+        if state.tile_progress.get_untracked() != tile_progress {
+            state.tile_progress.set(tile_progress);
+        }
+
         // Step 2: Draw overlays on top of the base spectrogram
         if base_drawn && !clean_view {
             let show_het = het_interacting
@@ -853,7 +982,15 @@ pub fn Spectrogram() -> impl IntoView {
 
             // Time scale moved out of the canvas — it now lives in the
             // <TimeGutter/> strip below, which keeps the bottom rows of
-            // the spectrogram readable for low frequencies.
+            // the spectrogram readable for low frequencies. Optional
+            // gridlines continue its major ticks up through the data.
+            if time_gridlines {
+                crate::canvas::time_markers::draw_time_gridlines(
+                    &ctx, scroll, visible_time,
+                    display_w as f64, display_h as f64,
+                    duration, tick_spacing,
+                );
+            }
 
             // Pulse detection overlay
             if pulse_overlay && !detected_pulses.is_empty() {
@@ -894,9 +1031,23 @@ pub fn Spectrogram() -> impl IntoView {
                 (0.0, 0.0)
             };
             if overlay_hi > overlay_lo && !xform_on {
+                let overlay_extra: &[(f64, f64)] = if band_ff_hi > band_ff_lo { &extra_focus_bands } else { &[] };
+                // Keep the spectrogram under a selection readable while it's placed
+                let selecting = dragging && spec_drag.is_none() && canvas_tool == CanvasTool::Selection;
+                let dim_alpha = match selection_focus_dim {
+                    SelectionFocusDim::HideWhileSelecting if selecting && selection.is_some() => 0.0,
+                    _ => focus_dim_opacity,
+                };
+                let undimmed_rect = selection
+                    .filter(|_| selection_focus_dim == SelectionFocusDim::ClearInside)
+                    .and_then(|sel| spectrogram_renderer::selection_rect(
+                        &sel, min_freq, max_freq, scroll, time_res, zoom,
+                        display_w as f64, display_h as f64,
+                    ));
                 spectrogram_renderer::draw_band_ff_overlay(
                     &ctx,
-                    overlay_lo, overlay_hi,
+                    overlay_lo, overlay_hi, overlay_extra,
+                    dim_alpha, undimmed_rect,
                     min_freq, max_freq,
                     display_h as f64, display_w as f64,
                     spec_hover, spec_drag,
@@ -913,8 +1064,7 @@ pub fn Spectrogram() -> impl IntoView {
                     let ch = display_h as f64;
                     let freq_range = max_freq - min_freq;
                     if freq_range > 0.0 {
-                        let y_top = ch * (1.0 - (hl_hi - min_freq) / freq_range);
-                        let y_bot = ch * (1.0 - (hl_lo - min_freq) / freq_range);
+                        let (y_top, y_bot) = spectrogram_renderer::freq_span_y(hl_lo, hl_hi, min_freq, max_freq, ch);
                         // Semi-transparent blue band
                         ctx.set_fill_style_str("rgba(100, 180, 255, 0.15)");
                         ctx.fill_rect(0.0, y_top, display_w as f64, y_bot - y_top);
@@ -960,6 +1110,7 @@ pub fn Spectrogram() -> impl IntoView {
                     zoom,
                     display_w as f64,
                     display_h as f64,
+                    selection_color,
                 );
                 if dragging {
                     spectrogram_renderer::draw_harmonic_shadows(
@@ -972,6 +1123,21 @@ pub fn Spectrogram() -> impl IntoView {
                         zoom,
                         display_w as f64,
                         display_h as f64,
+                        harmonic_shadow_color,
+                    );
+                }
+                if let Some(snapped) = snap_preview {
+                    spectrogram_renderer::draw_snap_preview(
+                        &ctx,
+                        &snapped,
+                        min_freq,
+                        max_freq,
+                        scroll,
+                        time_res,
+                        zoom,
+                        display_w as f64,
+                        display_h as f64,
+                        selection_color,
                     );
                 }
             }
@@ -1066,6 +1232,18 @@ pub fn Spectrogram() -> impl IntoView {
                 }
             }
 
+            if !reference_lines.is_empty() {
+                spectrogram_renderer::draw_reference_lines(
+                    &ctx,
+                    &reference_lines,
+                    state.freq_unit.get(),
+                    min_freq,
+                    max_freq,
+                    display_w as f64,
+                    display_h as f64,
+                );
+            }
+
             // Draw PSD hover frequency overlays
             {
                 let psd_hovers = state.psd_hover_freqs.get();
@@ -1074,7 +1252,7 @@ pub fn Spectrogram() -> impl IntoView {
                     let dw = display_w as f64;
                     for (freq, label, color) in &psd_hovers {
                         if *freq < min_freq || *freq > max_freq { continue; }
-                        let y = dh * (1.0 - (freq - min_freq) / (max_freq - min_freq));
+                        let y = spectrogram_renderer::freq_to_y(*freq, min_freq, max_freq, dh);
                         // Horizontal line
                         ctx.set_stroke_style_str(color);
                         ctx.set_line_width(1.5);
@@ -1095,13 +1273,102 @@ pub fn Spectrogram() -> impl IntoView {
                 }
             }
 
+            // Amplitude envelope from the column energies (single file only)
+            if show_envelope && !waterfall_active && timeline.is_none() {
+                if let Some(f) = file {
+                    let levels = spectrogram_renderer::envelope_levels(
+                        &f.spectrogram, file_idx_val, scroll, visible_time, display_w as usize,
+                    );
+                    spectrogram_renderer::draw_envelope(&ctx, &levels, display_h as f64);
+                }
+            }
+
+            // Time/frequency crosshair with readout at the cursor
+            if let (true, Some(t), Some(f)) = (show_crosshair, cursor_time, mouse_freq) {
+                if visible_time > 0.0 && max_freq > min_freq && !marker_state.mouse_in_label_area {
+                    let x = (t - scroll) * display_w as f64 / visible_time;
+                    let y = spectrogram_renderer::freq_to_y(f, min_freq, max_freq, display_h as f64);
+                    let reference = state.cursor_db_reference.get_untracked();
+                    let level = idx
+                        .and_then(|i| {
+                            let peak = if reference == crate::state::DbReference::Peak {
+                                crate::components::analysis_panel::file_peak_magnitude(&files, i).unwrap_or(0.0)
+                            } else {
+                                0.0
+                            };
+                            crate::components::analysis_panel::cursor_level_db(state, t, f, reference, peak)
+                        })
+                        .map(|db| format!("  {:.1} {}", db, reference.unit()))
+                        .unwrap_or_default();
+                    // Phase coherence: the number behind the colour at this bin
+                    let coherence = idx
+                        .filter(|_| flow_on && state.spectrogram_display.get_untracked() == SpectrogramDisplay::PhaseCoherence)
+                        .and_then(|i| crate::canvas::tile_cache::flow_value_at(i, zoom, original_sample_rate, t, f))
+                        .filter(|(db, _)| db.is_finite())
+                        .map(|(_, dev)| format!("  coh {:.2}  \u{0394}\u{03c6} {:+.0}\u{00b0}", 1.0 - dev.abs(), dev * 180.0))
+                        .unwrap_or_default();
+                    // Pixel inspect: trace the bin through the display mapping
+                    let inspect = idx
+                        .filter(|_| pixel_inspect && !waterfall_active && timeline.is_none())
+                        .and_then(|_| crate::components::analysis_panel::cursor_magnitude(state, t, f))
+                        .map(|(mag, _)| {
+                            use crate::canvas::colors::{db_to_greyscale, magnitude_to_db};
+                            let frac = f / file_max_freq;
+                            let (settings, cm) = match colormap {
+                                ColormapMode::Uniform(cm) => (display_settings, Some(cm)),
+                                ColormapMode::HfrFocus { colormap: cm, band_ff_lo_frac, band_ff_hi_frac, ref extra_bands, focus_settings } => {
+                                    let in_focus = (band_ff_lo_frac..=band_ff_hi_frac).contains(&frac)
+                                        || extra_bands.as_slice().iter().any(|&(lo, hi)| (lo..=hi).contains(&frac));
+                                    if in_focus {
+                                        (focus_settings.unwrap_or(display_settings), Some(cm))
+                                    } else {
+                                        (display_settings, None)
+                                    }
+                                }
+                            };
+                            // Display EQ / noise filter / pre-emphasis offset for this bin's row
+                            let tile_height = state.spect_fft_mode.get_untracked().max_fft_size() / 2 + 1;
+                            let row = (tile_height - 1).saturating_sub((frac.clamp(0.0, 1.0) * (tile_height - 1) as f64).round() as usize);
+                            let extra = compute_freq_adjustments(&state, file_max_freq, tile_height)
+                                .and_then(|a| a.get(row).copied())
+                                .unwrap_or(0.0);
+                            let db = magnitude_to_db(mag);
+                            let shown_db = db + settings.gain_db + extra;
+                            let grey = db_to_greyscale(db, settings.floor_db, settings.range_db, settings.gamma, settings.gain_db + extra);
+                            let rgb = cm.map_or([grey, grey, grey], |cm| cm.apply(grey));
+                            format!(
+                                "  | mag {:.3e} = {:.1} dB{} \u{2192} {:.1} dB shown \u{2192} grey {} \u{2192} {}{}",
+                                mag,
+                                db,
+                                if extra != 0.0 { format!(" (display adj {:+.1})", extra) } else { String::new() },
+                                shown_db,
+                                grey,
+                                crate::canvas::colors::rgb_to_hex(rgb),
+                                if flow_on { " (before flow colour)" } else { "" },
+                            )
+                        })
+                        .unwrap_or_default();
+                    let label = format!(
+                        "{}  {}{}{}{}",
+                        crate::format_time::format_time_display(t, 3),
+                        crate::components::analysis_panel::fmt_freq(state, f),
+                        level,
+                        coherence,
+                        inspect,
+                    );
+                    spectrogram_renderer::draw_crosshair(
+                        &ctx, x, y, &label, display_w as f64, display_h as f64,
+                    );
+                }
+            }
+
             if visible_time <= 0.0 { return; }
             let px_per_sec = display_w as f64 / visible_time;
 
             // Draw static position marker when not playing in FromHere mode
             if state.play_start_mode.get() .uses_from_here() && !is_playing && canvas_tool == CanvasTool::Hand {
-                let here_x = display_w as f64 * viewport::PLAY_FROM_HERE_FRACTION;
-                let here_time = viewport::play_from_here_time(scroll, visible_time);
+                let here_x = display_w as f64 * state.play_anchor_fraction.get();
+                let here_time = viewport::play_from_here_time(scroll, visible_time, state.play_anchor_fraction.get());
                 state.play_from_here_time.set(here_time);
                 ctx.set_stroke_style_str("rgba(100, 160, 255, 0.35)");
                 ctx.set_line_width(1.5);
@@ -1116,8 +1383,8 @@ pub fn Spectrogram() -> impl IntoView {
                 let _ = ctx.set_line_dash(&js_sys::Array::new());
             }
 
-            // Draw bookmark dots (yellow circles at top edge)
-            ctx.set_fill_style_str("rgba(255, 200, 50, 0.9)");
+            // Draw bookmark dots (circles at top edge)
+            ctx.set_fill_style_str(&bookmark_fill);
             for bm in &bookmarks {
                 let x = (bm.time - scroll) * px_per_sec;
                 if x >= 0.0 && x <= display_w as f64 {
@@ -1131,8 +1398,8 @@ pub fn Spectrogram() -> impl IntoView {
 
     // Effect 4: auto-scroll to follow playhead during playback
     // Supports temporary suspension: when the user manually scrolls, following
-    // pauses until the playhead is on-screen and 200ms have passed since the
-    // last scroll action — so it resumes even when very zoomed in.
+    // pauses until the playhead is on-screen and `follow_resume_ms` have passed
+    // since the last scroll action — so it resumes even when very zoomed in.
     Effect::new(move || {
         let playhead = state.playhead_time.get();
         let is_playing = state.is_playing.get();
@@ -1166,7 +1433,7 @@ pub fn Spectrogram() -> impl IntoView {
             .unwrap_or((1.0, 0.0));
         let zoom = state.zoom_level.get_untracked();
         let scroll = state.scroll_offset.get_untracked();
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
 
         let visible_time = viewport::visible_time(display_w, zoom, time_res);
         let playhead_rel = playhead - scroll;
@@ -1176,7 +1443,7 @@ pub fn Spectrogram() -> impl IntoView {
             let playhead_visible = playhead_rel >= 0.0 && playhead_rel <= visible_time;
             if playhead_visible {
                 let resume = match state.follow_visible_since.get_untracked() {
-                    Some(since) => js_sys::Date::now() - since >= 200.0,
+                    Some(since) => js_sys::Date::now() - since >= state.follow_resume_ms.get_untracked(),
                     None => true, // no recorded scroll time — safe to resume
                 };
                 if resume {
@@ -1192,12 +1459,12 @@ pub fn Spectrogram() -> impl IntoView {
         // trigger would fire too frequently and look jarring.
         if visible_time < viewport::FOLLOW_EXACT_THRESHOLD_SECS {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         }
         // Normal follow: scroll when playhead nears the edge
         else if playhead_rel > visible_time * viewport::FOLLOW_CURSOR_EDGE_FRACTION || playhead_rel < 0.0 {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         }
     });
 
@@ -1275,6 +1542,10 @@ pub fn Spectrogram() -> impl IntoView {
                 } else {
                     (5.0, 30)
                 };
+                let prefetch_scale = state.tile_prefetch.get_untracked().scale();
+                if prefetch_scale <= 0.0 { return; }
+                let ahead_secs = ahead_secs * prefetch_scale;
+                let max_prefetch = (max_prefetch as f64 * prefetch_scale) as usize;
 
                 tile_cache::schedule_prefetch_tiles(
                     state,
@@ -1336,7 +1607,7 @@ pub fn Spectrogram() -> impl IntoView {
         }
 
         let zoom = state.zoom_level.get_untracked();
-        let lod = tile_cache::select_lod(zoom);
+        let lod = tile_cache::main_view_lod(zoom);
         let max_tiles = tile_cache::tile_count_for_samples(total_samples, lod);
         if max_tiles == 0 { return; }
 
@@ -1412,8 +1683,7 @@ pub fn Spectrogram() -> impl IntoView {
                     return format!("cursor: ns-resize; touch-action: {ta};");
                 }
                 if let Some(handle) = state.spec_hover_handle.get() {
-                    let is_ff = matches!(handle, SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle);
-                    if !is_ff || crate::canvas::hit_test::is_in_band_ff_drag_zone(
+                    if !handle.is_band_ff() || crate::canvas::hit_test::is_in_band_ff_drag_zone(
                         state.mouse_canvas_x.get(),
                         state.spectrogram_canvas_width.get(),
                     ) {
@@ -1460,7 +1730,17 @@ pub fn Spectrogram() -> impl IntoView {
                 on:touchmove=on_touchmove
                 on:touchend=on_touchend
             />
+            <PlayheadTrail/>
             <Playhead/>
+            {move || state.tile_progress.get().map(|(ready, total)| {
+                let pct = ready * 100 / total.max(1);
+                view! {
+                    <div class="tile-progress" title="Visible spectrogram tiles computed at full resolution; the rest are upscaled previews">
+                        <div class="tile-progress-bar" style=format!("width: {pct}%")></div>
+                        <span class="tile-progress-label">{format!("{pct}%")}</span>
+                    </div>
+                }
+            })}
             </div>
             <BandGutter/>
             </div>
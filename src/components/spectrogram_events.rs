@@ -3,8 +3,10 @@ use wasm_bindgen::JsCast;
 use web_sys::{HtmlCanvasElement, MouseEvent, PointerEvent};
 use crate::canvas::coord::pointer_to_xtf;
 use crate::canvas::hit_test::{hit_test_spec_handles, is_in_band_ff_drag_zone, hit_test_annotation_handles, hit_test_annotation_body, hit_test_band_ff_body};
+use crate::audio::source::ChannelView;
 use crate::canvas::spectrogram_renderer;
-use crate::state::{ActiveFocus, AppState, CanvasTool, SpectrogramHandle, Selection, UndoEntry};
+use crate::dsp::call_snap::{snap_to_call, MAX_SNAP_SECS};
+use crate::state::{ActiveFocus, AppState, CanvasTool, PlayAnchor, SpectrogramHandle, Selection, UndoEntry};
 use crate::viewport;
 
 pub const LABEL_AREA_WIDTH: f64 = 60.0;
@@ -90,7 +92,8 @@ fn file_nyquist(state: AppState) -> f64 {
 
 /// Pan the frequency display range so that `anchor_freq` stays pinned to
 /// the current pointer y. The span (start_max - start_min) is preserved,
-/// and the new range is clamped to [0, nyquist]. Called on every
+/// and the new range is clamped to [0, ceiling] (Nyquist, or above it when
+/// "Allow above Nyquist" is on). Called on every
 /// pointermove / touchmove during a left-axis viewport pan.
 pub fn apply_freq_axis_pan(
     state: AppState,
@@ -102,18 +105,30 @@ pub fn apply_freq_axis_pan(
 ) {
     if canvas_h <= 0.0 { return; }
     let span = (start_max - start_min).max(1.0);
-    let nyquist = file_nyquist(state);
-    // freq_to_y: y = h * (1 - (f - min) / span). Solve for min to pin
-    // anchor_freq at canvas_y.
-    let new_min = anchor_freq - span * (1.0 - (canvas_y / canvas_h));
-    // Clamp so neither edge escapes the file's frequency range.
-    let max_low = (nyquist - span).max(0.0);
+    let ceiling = state.freq_view_ceiling(file_nyquist(state));
+    // freq_to_y: y = h * (1 - (f - min) / span), or h * (f - min) / span
+    // with the axis flipped. Solve for min to pin anchor_freq at canvas_y.
+    let frac_from_low = if crate::viewport::freq_axis_flipped() {
+        canvas_y / canvas_h
+    } else {
+        1.0 - canvas_y / canvas_h
+    };
+    let new_min = anchor_freq - span * frac_from_low;
+    // Clamp so neither edge escapes the viewable frequency range.
+    let max_low = (ceiling - span).max(0.0);
     let clamped_min = new_min.clamp(0.0, max_low);
-    let clamped_max = (clamped_min + span).min(nyquist);
+    let clamped_max = (clamped_min + span).min(ceiling);
     state.min_display_freq.set(Some(clamped_min));
     state.max_display_freq.set(Some(clamped_max));
 }
 
+/// Clamp a pointer frequency to the file's data range. With the axis
+/// extended above Nyquist the pointer can land where there is no signal; a
+/// selection dragged there stops at Nyquist so its band filter stays valid.
+fn clamp_to_data(state: AppState, freq: f64) -> f64 {
+    freq.clamp(0.0, file_nyquist(state))
+}
+
 /// Reset the frequency display range to "auto" (None) so the spectrogram
 /// shows 0..Nyquist again. Used for tap-to-reset and double-click on the
 /// left axis.
@@ -167,6 +182,16 @@ pub fn apply_handle_drag(
             let new_cutoff = (het_freq - freq_at_pointer).clamp(1000.0, 30000.0);
             state.het_cutoff.set(new_cutoff);
         }
+        SpectrogramHandle::ExtraBandUpper(i) => {
+            if let Some((lo, _)) = state.extra_focus_bands.get_untracked().get(i).copied() {
+                state.set_extra_focus_band(i, lo, freq_at_pointer.clamp(lo + 500.0, file_max_freq));
+            }
+        }
+        SpectrogramHandle::ExtraBandLower(i) => {
+            if let Some((_, hi)) = state.extra_focus_bands.get_untracked().get(i).copied() {
+                state.set_extra_focus_band(i, freq_at_pointer.clamp(0.0, hi - 500.0), hi);
+            }
+        }
     }
 }
 
@@ -246,8 +271,8 @@ pub fn apply_hand_pan(
         } else {
             file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(f64::MAX)
         };
-        let from_here_mode = state.play_start_mode.get_untracked().uses_from_here();
-        viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode)
+        let from_here = state.from_here_fraction();
+        viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here)
     };
     state.scroll_offset.set(new_scroll);
 }
@@ -313,6 +338,22 @@ pub fn apply_annotation_resize(
     });
 }
 
+/// "Where clicked" anchor: a click or tap while stopped moves the marker to `client_x`.
+fn set_play_anchor_from_click(state: AppState, canvas_ref: &NodeRef<leptos::html::Canvas>, client_x: f64) {
+    if state.play_anchor.get_untracked() != PlayAnchor::Click
+        || state.is_playing.get_untracked()
+        || !state.play_start_mode.get_untracked().uses_from_here()
+    {
+        return;
+    }
+    let Some(canvas_el) = canvas_ref.get() else { return };
+    let canvas: &HtmlCanvasElement = canvas_el.as_ref();
+    let rect = canvas.get_bounding_client_rect();
+    if rect.width() > 0.0 {
+        state.set_play_anchor_fraction((client_x - rect.left()) / rect.width());
+    }
+}
+
 // ── Pointer capture helper ────────────────────────────────────────────────
 
 /// Call setPointerCapture on the event target so that pointermove/pointerup
@@ -383,8 +424,7 @@ pub fn on_pointerdown(
     // Check for spec handle drag (BandFF or HET — takes priority over axis/tool drags)
     // BandFF handles only start drag when clicking within the center handle zone.
     if let Some(handle) = state.spec_hover_handle.get_untracked() {
-        let is_ff = matches!(handle, SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle);
-        let allow_drag = if is_ff {
+        let allow_drag = if handle.is_band_ff() {
             if let Some((px_x, _, _, _)) = pointer_to_xtf(ev.client_x() as f64, ev.client_y() as f64, canvas_ref, &state) {
                 if let Some(canvas_el) = canvas_ref.get() {
                     let canvas: &HtmlCanvasElement = canvas_el.as_ref();
@@ -522,6 +562,7 @@ pub fn on_pointerdown(
                 state.is_dragging.set(true);
                 ix.drag_start.set((t, f));
                 state.selection.set(None);
+                state.snap_preview.set(None);
             }
         }
     }
@@ -592,6 +633,7 @@ pub fn on_pointermove(
                 }
                 CanvasTool::Selection => {
                     let (t0, f0) = ix.drag_start.get_untracked();
+                    let (f0, f) = (clamp_to_data(state, f0), clamp_to_data(state, f));
                     state.selection.set(Some(Selection {
                         time_start: t0.min(t),
                         time_end: t0.max(t),
@@ -762,6 +804,7 @@ pub fn on_pointerup(
                         state.selected_annotation_ids.set(Vec::new());
                     }
                     state.active_focus.set(None);
+                    set_play_anchor_from_click(state, canvas_ref, ev.client_x() as f64);
                 }
             }
             // Bookmark while playing
@@ -779,6 +822,7 @@ pub fn on_pointerup(
     if state.canvas_tool.get_untracked() != CanvasTool::Selection { return; }
     if let Some((_, _, t, f)) = pointer_to_xtf(ev.client_x() as f64, ev.client_y() as f64, canvas_ref, &state) {
         let (t0, f0) = ix.drag_start.get_untracked();
+        let (f0, f) = (clamp_to_data(state, f0), clamp_to_data(state, f));
         let sel = Selection {
             time_start: t0.min(t),
             time_end: t0.max(t),
@@ -788,6 +832,15 @@ pub fn on_pointerup(
         if sel.time_end - sel.time_start > 0.0001 {
             state.selection.set(Some(sel));
             state.active_focus.set(Some(ActiveFocus::TransientSelection));
+            if state.snap_to_call.get_untracked() {
+                let snapped = snap_selection(state, &sel);
+                if snapped.is_some() {
+                    state.show_info_toast("Snapped to call: Enter to apply, Esc to keep as drawn");
+                } else {
+                    state.show_info_toast("No call found to snap to");
+                }
+                state.snap_preview.set(snapped.map(|s| (sel, s)));
+            }
             if state.selection_auto_focus.get_untracked() {
                 if let (Some(lo), Some(hi)) = (sel.freq_low, sel.freq_high) {
                     if hi - lo > 100.0 {
@@ -801,6 +854,34 @@ pub fn on_pointerup(
     }
 }
 
+/// Selection edges snapped to the call inside `sel`, or None if no call
+/// stands out from the noise there or `sel` is too long to snap.
+pub fn snap_selection(state: AppState, sel: &Selection) -> Option<Selection> {
+    // Don't read and analyse long stretches on pointerup
+    if sel.time_end - sel.time_start > MAX_SNAP_SECS {
+        return None;
+    }
+    let file = state.current_file()?;
+    let rate = file.audio.sample_rate as f64;
+    // Room either side for edges drawn too tightly to grow into
+    let margin = ((sel.time_end - sel.time_start) * 0.5).clamp(0.005, 0.1);
+    let start = (sel.time_start - margin).max(0.0);
+    let end = (sel.time_end + margin).min(file.audio.duration_secs);
+    let first = (start * rate) as u64;
+    let samples = file.audio.source.read_region(ChannelView::MonoMix, first, ((end - start) * rate) as usize);
+    let band = sel.freq_low.zip(sel.freq_high);
+    let snap = snap_to_call(
+        &samples, file.audio.sample_rate, first as f64 / rate,
+        sel.time_start, sel.time_end, band, state.snap_threshold_db.get_untracked(),
+    )?;
+    Some(Selection {
+        time_start: snap.time_start.max(0.0),
+        time_end: snap.time_end.min(file.audio.duration_secs),
+        freq_low: Some(snap.freq_low),
+        freq_high: Some(snap.freq_high),
+    })
+}
+
 pub fn on_dblclick(
     ev: MouseEvent,
     canvas_ref: &NodeRef<leptos::html::Canvas>,
@@ -943,7 +1024,7 @@ pub fn on_touchstart(
                 initial_mid_client_x: mid_x,
                 time_res,
                 duration,
-                from_here_mode: state.play_start_mode.get_untracked() .uses_from_here(),
+                from_here: state.from_here_fraction(),
             }));
         }
         // End any in-progress single-touch gesture
@@ -1043,8 +1124,7 @@ pub fn on_touchstart(
                 &state, px_y, min_freq_val, max_freq_val, ch, 16.0, band_ff_focused, // wider touch target
             );
             if let Some(handle) = handle {
-                let is_ff = matches!(handle, SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle);
-                if !is_ff || is_in_band_ff_drag_zone(px_x, cw) {
+                if !handle.is_band_ff() || is_in_band_ff_drag_zone(px_x, cw) {
                     state.spec_drag_handle.set(Some(handle));
                     state.is_dragging.set(true);
                     ev.prevent_default();
@@ -1121,7 +1201,7 @@ pub fn on_touchmove(
                 let canvas: &HtmlCanvasElement = canvas_el.as_ref();
                 let rect = canvas.get_bounding_client_rect();
                 let cw = canvas.width() as f64;
-                let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw);
+                let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw, state.max_zoom());
                 state.suspend_follow();
                 state.zoom_level.set(new_zoom);
                 state.scroll_offset.set(new_scroll);
@@ -1262,7 +1342,9 @@ pub fn on_touchend(
                 if dx < 5.0 && state.is_playing.get_untracked() {
                     let t = state.playhead_time.get_untracked();
                     state.bookmarks.update(|bm| bm.push(crate::state::Bookmark { time: t }));
-                } else if dx >= 5.0 {
+                } else if dx < 5.0 {
+                    set_play_anchor_from_click(state, canvas_ref, touch.client_x() as f64);
+                } else {
                     // Flick → launch inertia
                     let velocity = ix.velocity_tracker.with_value(|t| t.velocity_px_per_sec());
                     if let Some(canvas_el) = canvas_ref.get() {
@@ -1283,9 +1365,9 @@ pub fn on_touchend(
                         } else {
                             file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(f64::MAX)
                         };
-                        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+                        let from_here = state.from_here_fraction();
                         crate::components::inertia::start_inertia(
-                            state, velocity, cw, time_res, duration, from_here_mode, ix.inertia_generation,
+                            state, velocity, cw, time_res, duration, from_here, ix.inertia_generation,
                         );
                     }
                 }
@@ -1348,10 +1430,11 @@ pub fn on_wheel(
         };
 
         let factor = if ev.delta_y() > 0.0 { 1.15 } else { 1.0 / 1.15 };
-        let new_range = (range * factor).clamp(500.0, file_max_freq);
+        let ceiling = state.freq_view_ceiling(file_max_freq);
+        let new_range = (range * factor).clamp(500.0, ceiling);
         let anchor_freq = cur_min + anchor_frac * range;
         let new_min = (anchor_freq - anchor_frac * new_range).max(0.0);
-        let new_max = (new_min + new_range).min(file_max_freq);
+        let new_max = (new_min + new_range).min(ceiling);
         let new_min = (new_max - new_range).max(0.0);
 
         state.min_display_freq.set(Some(new_min));
@@ -1359,7 +1442,7 @@ pub fn on_wheel(
     } else if ev.ctrl_key() {
         let delta = if ev.delta_y() > 0.0 { 0.9 } else { 1.1 };
         state.zoom_level.update(|z| {
-            *z = (*z * delta).clamp(viewport::MIN_ZOOM, viewport::MAX_ZOOM);
+            *z = (*z * delta).clamp(viewport::MIN_ZOOM, state.max_zoom());
         });
     } else {
         let raw_delta = ev.delta_y() + ev.delta_x();
@@ -1384,13 +1467,13 @@ pub fn on_wheel(
             let zoom = state.zoom_level.get_untracked();
             let canvas_w = state.spectrogram_canvas_width.get_untracked();
             let visible_time = viewport::visible_time(canvas_w, zoom, time_res);
-            let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+            let from_here = state.from_here_fraction();
             // Scroll proportional to visible time (like arrow keys),
             // normalized so a typical wheel tick (~100px) scrolls ~10% of the view
             let delta = raw_delta.signum() * visible_time * 0.1 * (raw_delta.abs() / 100.0).min(3.0);
             state.suspend_follow();
             state.scroll_offset.update(|s| {
-                *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here_mode);
+                *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here);
             });
         }
     }
@@ -4,6 +4,7 @@ use crate::state::{AppState, RightSidebarTab, ListenMode, MicAcquisitionState, R
 use crate::audio::streaming_source;
 use crate::components::file_sidebar::file_groups;
 use crate::components::file_sidebar::file_badges::{FileBadgeData, FileBadgeRow, parse_cc_license, get_xc_field};
+use crate::timeline::TimelineView;
 
 #[component]
 pub fn Toolbar() -> impl IntoView {
@@ -110,6 +111,26 @@ pub fn Toolbar() -> impl IntoView {
         Some(matches)
     });
 
+    // Join the current file's sequence into one continuous timeline
+    let on_join_sequence = move |e: web_sys::MouseEvent| {
+        e.stop_propagation();
+        seq_dropdown_open.set(false);
+        let Some(members) = seq_group_files.get_untracked() else { return };
+        let members: Vec<(usize, u32)> = members.iter().map(|(i, _, n)| (*i, *n)).collect();
+        let files = state.files.get_untracked();
+        let label = state.current_file_index.get_untracked()
+            .and_then(|idx| {
+                let names: Vec<String> = files.iter().map(|f| f.name.clone()).collect();
+                file_groups::compute_all_groups(&names, &files).get(idx)?.sequence.clone()
+            })
+            .map(|s| format!("Sequence {}", s.sequence_key))
+            .unwrap_or_else(|| "Sequence".to_string());
+        match TimelineView::join_sequence(&members, &files, &label) {
+            Ok(tv) => state.open_timeline(tv),
+            Err(e) => state.show_error_toast(format!("Can\u{2019}t join sequence: {e}")),
+        }
+    };
+
     // Derived: track group files (for dropdown)
     let track_group_files = Memo::new(move |_| {
         let files = state.files.get();
@@ -446,6 +467,11 @@ pub fn Toolbar() -> impl IntoView {
                                         </div>
                                     }
                                 }).collect::<Vec<_>>()}
+                                <div
+                                    class="badge-dropdown-item badge-dropdown-action"
+                                    title="View the whole sequence as one continuous recording"
+                                    on:click=on_join_sequence
+                                >"Join sequence"</div>
                             </div>
                         }
                     })}
@@ -521,7 +547,7 @@ pub fn Toolbar() -> impl IntoView {
                                     if let Some(entry) = hist.get(new_idx) {
                                         state.suspend_follow();
                                         state.scroll_offset.set(entry.scroll_offset);
-                                        state.zoom_level.set(entry.zoom_level);
+                                        state.zoom_level.set(entry.zoom_level.min(state.max_zoom()));
                                     }
                                 }
                                 disabled=move || state.nav_index.get() == 0
@@ -540,7 +566,7 @@ pub fn Toolbar() -> impl IntoView {
                                     if let Some(entry) = hist.get(new_idx) {
                                         state.suspend_follow();
                                         state.scroll_offset.set(entry.scroll_offset);
-                                        state.zoom_level.set(entry.zoom_level);
+                                        state.zoom_level.set(entry.zoom_level.min(state.max_zoom()));
                                     }
                                 }
                                 disabled=move || {
@@ -552,6 +578,55 @@ pub fn Toolbar() -> impl IntoView {
                                 <span class="toolbar-overflow-icon">"→"</span>
                                 "Forward"
                             </button>
+                            <div class="toolbar-overflow-separator"></div>
+                            <button
+                                class="toolbar-overflow-item"
+                                on:click=move |_| state.zoom_to_fit()
+                                disabled=move || state.current_file_index.get().is_none() && state.active_timeline.get().is_none()
+                                title="Fit the whole recording to the window (0)"
+                            >
+                                <span class="toolbar-overflow-icon">{"\u{2194}"}</span>
+                                "Fit to window"
+                            </button>
+                            <button
+                                class="toolbar-overflow-item"
+                                on:click=move |_| state.reset_zoom()
+                                disabled=move || state.current_file_index.get().is_none() && state.active_timeline.get().is_none()
+                                title="Return to the default zoom (1)"
+                            >
+                                <span class="toolbar-overflow-icon">"1:1"</span>
+                                "Reset zoom"
+                            </button>
+                            <div class="toolbar-overflow-separator"></div>
+                            <div
+                                class="toolbar-overflow-item toolbar-goto"
+                                on:click=|ev: web_sys::MouseEvent| ev.stop_propagation()
+                                title="Centre the view on a time: 83.5, 1:23.456 or 1h02m03s. Enter moves the view; Shift+Enter also moves the playhead."
+                            >
+                                <span class="toolbar-overflow-icon">{"\u{23F1}"}</span>
+                                <input
+                                    type="text"
+                                    class="toolbar-goto-input"
+                                    placeholder="Go to mm:ss.mmm"
+                                    on:keydown=move |ev: web_sys::KeyboardEvent| {
+                                        if ev.key() != "Enter" { return; }
+                                        let input: web_sys::HtmlInputElement = ev.target().unwrap().unchecked_into();
+                                        let Some(time) = crate::format_time::parse_time_input(&input.value()) else {
+                                            state.show_error_toast("Enter a time like 83.5, 1:23.456 or 1h02m03s");
+                                            return;
+                                        };
+                                        let Some(landed) = state.go_to_time(time, ev.shift_key()) else { return };
+                                        if (landed - time).abs() > 1e-9 {
+                                            state.show_info_toast(format!(
+                                                "Past the end; went to {}",
+                                                crate::format_time::format_time_display(landed, 3),
+                                            ));
+                                        }
+                                        input.set_value("");
+                                        overflow_menu_open.set(false);
+                                    }
+                                />
+                            </div>
                         </div>
                     })}
                 </div>
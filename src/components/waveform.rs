@@ -284,7 +284,16 @@ pub fn Waveform() -> impl IntoView {
             let region_start = ((vis_start_time * sr as f64) as usize).saturating_sub(margin_samples);
             let region_end = ((vis_end_time * sr as f64) as usize) + margin_samples;
             let region_len = region_end.saturating_sub(region_start);
-            let waveform_buf = file.audio.source.read_region(cv, region_start as u64, region_len);
+            let waveform_buf = if file.settings.declip {
+                use crate::dsp::declip;
+                declip::read_declipped(
+                    |s, n| file.audio.source.read_region(cv, s as u64, n),
+                    region_start, region_start + region_len,
+                    file.audio.source.total_samples() as usize, declip::CLIP_LEVEL,
+                )
+            } else {
+                file.audio.source.read_region(cv, region_start as u64, region_len)
+            };
 
             if mode == PlaybackMode::ZeroCrossing {
                 if let Some(bins) = zc_bins.get().as_ref() {
@@ -465,8 +474,8 @@ pub fn Waveform() -> impl IntoView {
             // Draw "play here" marker when not playing
             if !clean_view && state.play_start_mode.get() .uses_from_here() && !is_playing && canvas_tool == CanvasTool::Hand {
                 let visible_time = viewport::visible_time(display_w as f64, zoom, file.spectrogram.time_resolution);
-                let here_x = display_w as f64 * viewport::PLAY_FROM_HERE_FRACTION;
-                let here_time = viewport::play_from_here_time(scroll, visible_time);
+                let here_x = display_w as f64 * state.play_anchor_fraction.get();
+                let here_time = viewport::play_from_here_time(scroll, visible_time, state.play_anchor_fraction.get());
                 state.play_from_here_time.set(here_time);
                 ctx.set_stroke_style_str("rgba(100, 160, 255, 0.35)");
                 ctx.set_line_width(1.5);
@@ -519,7 +528,7 @@ pub fn Waveform() -> impl IntoView {
             .unwrap_or((1.0, 0.0));
         let zoom = state.zoom_level.get_untracked();
         let scroll = state.scroll_offset.get_untracked();
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
 
         let visible_time = viewport::visible_time(display_w, zoom, time_res);
         let playhead_rel = playhead - scroll;
@@ -528,7 +537,7 @@ pub fn Waveform() -> impl IntoView {
             let playhead_visible = playhead_rel >= 0.0 && playhead_rel <= visible_time;
             if playhead_visible {
                 let resume = match state.follow_visible_since.get_untracked() {
-                    Some(since) => js_sys::Date::now() - since >= 200.0,
+                    Some(since) => js_sys::Date::now() - since >= state.follow_resume_ms.get_untracked(),
                     None => true,
                 };
                 if resume {
@@ -541,10 +550,10 @@ pub fn Waveform() -> impl IntoView {
 
         if visible_time < viewport::FOLLOW_EXACT_THRESHOLD_SECS {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         } else if playhead_rel > visible_time * viewport::FOLLOW_CURSOR_EDGE_FRACTION || playhead_rel < 0.0 {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         }
     });
 
@@ -556,7 +565,7 @@ pub fn Waveform() -> impl IntoView {
         if ev.ctrl_key() {
             let delta = if ev.delta_y() > 0.0 { 0.9 } else { 1.1 };
             state.zoom_level.update(|z| {
-                *z = (*z * delta).clamp(viewport::MIN_ZOOM, viewport::MAX_ZOOM);
+                *z = (*z * delta).clamp(viewport::MIN_ZOOM, state.max_zoom());
             });
         } else {
             let raw_delta = ev.delta_y() + ev.delta_x();
@@ -570,10 +579,10 @@ pub fn Waveform() -> impl IntoView {
                 return;
             };
             let delta = raw_delta.signum() * visible_time * 0.1 * (raw_delta.abs() / 100.0).min(3.0);
-            let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+            let from_here = state.from_here_fraction();
             state.suspend_follow();
             state.scroll_offset.update(|s| {
-                *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here_mode);
+                *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here);
             });
         }
     };
@@ -624,8 +633,8 @@ pub fn Waveform() -> impl IntoView {
             (start_scroll + dt).clamp(oldest, max_scroll)
         } else {
             let duration = file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(0.0);
-            let from_here_mode = state.play_start_mode.get_untracked().uses_from_here();
-            viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode)
+            let from_here = state.from_here_fraction();
+            viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here)
         };
         state.scroll_offset.set(new_scroll);
     };
@@ -675,7 +684,7 @@ pub fn Waveform() -> impl IntoView {
                     initial_mid_client_x: mid_x,
                     time_res,
                     duration,
-                    from_here_mode: state.play_start_mode.get_untracked() .uses_from_here(),
+                    from_here: state.from_here_fraction(),
                 }));
             }
             state.is_dragging.set(false);
@@ -709,7 +718,7 @@ pub fn Waveform() -> impl IntoView {
                     let canvas: &web_sys::HtmlCanvasElement = canvas_el.as_ref();
                     let rect = canvas.get_bounding_client_rect();
                     let cw = canvas.width() as f64;
-                    let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw);
+                    let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw, state.max_zoom());
                     state.suspend_follow();
                     state.zoom_level.set(new_zoom);
                     state.scroll_offset.set(new_scroll);
@@ -750,8 +759,8 @@ pub fn Waveform() -> impl IntoView {
             (start_scroll + dt).clamp(oldest, max_scroll)
         } else {
             let duration = file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(0.0);
-            let from_here_mode = state.play_start_mode.get_untracked().uses_from_here();
-            viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode)
+            let from_here = state.from_here_fraction();
+            viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here)
         };
         state.scroll_offset.set(new_scroll);
         // Record velocity sample for inertia
@@ -802,9 +811,9 @@ pub fn Waveform() -> impl IntoView {
                         } else {
                             file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(f64::MAX)
                         };
-                        let from_here_mode = state.play_start_mode.get_untracked().uses_from_here();
+                        let from_here = state.from_here_fraction();
                         crate::components::inertia::start_inertia(
-                            state, vel, cw, time_res, duration, from_here_mode, inertia_generation,
+                            state, vel, cw, time_res, duration, from_here, inertia_generation,
                         );
                     }
                 }
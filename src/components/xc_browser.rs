@@ -1,21 +1,161 @@
 use leptos::prelude::*;
 use leptos::task::spawn_local;
 use wasm_bindgen::prelude::*;
+use crate::audio::convert::SampleFormat;
 use crate::state::AppState;
-use crate::tauri_bridge::tauri_invoke;
+use crate::tauri_bridge::tauri_invoke_raw;
 
 const XC_GROUPS: &[&str] = &["bats", "birds", "frogs", "grasshoppers", "land mammals"];
 
+/// Sample rates offered for converting the cache.
+const CONVERT_RATES: &[u32] = &[96_000, 192_000, 250_000, 256_000, 384_000, 500_000];
+
+thread_local! {
+    /// Whether the "xc-convert-progress" listener has been registered.
+    static CONVERT_LISTENER: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    /// Whether the "xc-taxonomy-progress" listener has been registered.
+    static TAXONOMY_LISTENER: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+    /// (group, list) that species from "xc-taxonomy-progress" stream into
+    /// while a taxonomy is being built.
+    static TAXONOMY_TARGET: std::cell::RefCell<Option<(String, RwSignal<Vec<SpeciesInfo>>)>> =
+        const { std::cell::RefCell::new(None) };
+}
+
+/// Route cache-conversion progress events into `state.xc_convert_status`.
+/// Registered once, so it outlives the browser modal.
+fn ensure_convert_listener(state: AppState) {
+    if CONVERT_LISTENER.with(|l| l.replace(true)) {
+        return;
+    }
+    let callback = Closure::<dyn FnMut(JsValue)>::new(move |ev: JsValue| {
+        // Payload shape: { done, total, name, error? }
+        let payload = js_sys::Reflect::get(&ev, &JsValue::from_str("payload")).unwrap_or_default();
+        let get = |key: &str| js_sys::Reflect::get(&payload, &JsValue::from_str(key)).unwrap_or_default();
+        let done = get("done").as_f64().unwrap_or(0.0) as u32;
+        let total = get("total").as_f64().unwrap_or(0.0) as u32;
+        let name = get("name").as_string().unwrap_or_default();
+        if let Some(err) = get("error").as_string() {
+            log::warn!("Convert {name}: {err}");
+        }
+        state.xc_convert_status.set(Some(format!("Converting {done}/{total}: {name}")));
+    });
+    crate::tauri_bridge::tauri_listen("xc-convert-progress", callback);
+}
+
+/// Route taxonomy page events into `state.xc_taxonomy_progress`, and
+/// stream newly discovered species into the `TAXONOMY_TARGET` list.
+fn ensure_taxonomy_listener(state: AppState) {
+    if TAXONOMY_LISTENER.with(|l| l.replace(true)) {
+        return;
+    }
+    let callback = Closure::<dyn FnMut(JsValue)>::new(move |ev: JsValue| {
+        // Payload shape: { group, page, total_pages, species_found, new_species }
+        let payload = js_sys::Reflect::get(&ev, &JsValue::from_str("payload")).unwrap_or_default();
+        let get = |key: &str| js_sys::Reflect::get(&payload, &JsValue::from_str(key)).unwrap_or_default();
+        let page = get("page").as_f64().unwrap_or(0.0) as u32;
+        let total = get("total_pages").as_f64().unwrap_or(0.0) as u32;
+        if state.xc_taxonomy_progress.get_untracked().is_some() {
+            state.xc_taxonomy_progress.set(Some((page, total)));
+        }
+        let group = get("group").as_string().unwrap_or_default();
+        let target = TAXONOMY_TARGET.with(|t| t.borrow().clone())
+            .filter(|(g, _)| *g == group)
+            .map(|(_, list)| list);
+        if let Some(list) = target {
+            let new_species = parse_species_array(&get("new_species"));
+            // The modal may have closed since the request started
+            let _ = list.try_update(|list| {
+                list.extend(new_species);
+                list.sort_by_cached_key(|s| s.en.to_lowercase());
+            });
+        }
+    });
+    crate::tauri_bridge::tauri_listen("xc-taxonomy-progress", callback);
+}
+
+/// Parse the `{ label, stale, stale_after_days }` result of `xc_taxonomy_age`.
+/// The stale period comes back as `Some(days)` only when the cache is stale.
+fn parse_taxonomy_age(val: &JsValue) -> Option<(String, Option<u32>)> {
+    let label = js_sys::Reflect::get(val, &JsValue::from_str("label")).ok()?.as_string()?;
+    let stale = js_sys::Reflect::get(val, &JsValue::from_str("stale")).ok()
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false);
+    let days = js_sys::Reflect::get(val, &JsValue::from_str("stale_after_days")).ok()
+        .and_then(|v| v.as_f64())
+        .unwrap_or(0.0) as u32;
+    Some((label, stale.then_some(days)))
+}
+
 const XC_COUNTRIES_RAW: &str = include_str!("../data/countries.txt");
 
 fn xc_countries() -> Vec<&'static str> {
     XC_COUNTRIES_RAW.lines().filter(|l| !l.is_empty()).collect()
 }
 
+// ── Errors from the xc commands ──────────────────────────────────────
+
+/// Mirrors `xc_lib::XcError`'s `kind`, so the UI can react to the cause.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum XcErrorKind {
+    Auth,
+    RateLimited,
+    NotFound,
+    Network,
+    Offline,
+    Other,
+}
+
+#[derive(Clone, Debug)]
+struct XcFailure {
+    kind: XcErrorKind,
+    message: String,
+}
+
+impl XcFailure {
+    /// Parse a rejected command: `{ kind, message }` from the xc commands,
+    /// or a plain string from the rest.
+    fn from_js(cmd: &str, err: &JsValue) -> Self {
+        if let Some(message) = err.as_string() {
+            return message.into();
+        }
+        let get = |key: &str| js_sys::Reflect::get(err, &JsValue::from_str(key)).ok().and_then(|v| v.as_string());
+        let Some(message) = get("message") else {
+            return format!("Command '{cmd}' failed: {err:?}").into();
+        };
+        let kind = match get("kind").as_deref() {
+            Some("auth") => XcErrorKind::Auth,
+            Some("rate_limited") => XcErrorKind::RateLimited,
+            Some("not_found") => XcErrorKind::NotFound,
+            Some("network") => XcErrorKind::Network,
+            Some("offline") => XcErrorKind::Offline,
+            _ => XcErrorKind::Other,
+        };
+        XcFailure { kind, message }
+    }
+
+    fn prefixed(self, prefix: &str) -> Self {
+        XcFailure { kind: self.kind, message: format!("{prefix}: {}", self.message) }
+    }
+}
+
+impl From<String> for XcFailure {
+    fn from(message: String) -> Self {
+        XcFailure { kind: XcErrorKind::Other, message }
+    }
+}
+
+impl std::fmt::Display for XcFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
 // ── Helper to call tauri_invoke with a JS object of args ─────────────
 
-async fn invoke_with(cmd: &str, args: &js_sys::Object) -> Result<JsValue, String> {
-    tauri_invoke(cmd, &args.into()).await
+async fn invoke_with(cmd: &str, args: &js_sys::Object) -> Result<JsValue, XcFailure> {
+    tauri_invoke_raw(cmd, &args.into())
+        .await
+        .map_err(|e| XcFailure::from_js(cmd, &e))
 }
 
 fn js_obj() -> js_sys::Object {
@@ -85,10 +225,16 @@ struct CachedFile {
 // ── Parse helpers ────────────────────────────────────────────────────
 
 fn parse_species_list(val: &JsValue) -> Vec<SpeciesInfo> {
-    let species_arr = js_sys::Reflect::get(val, &"species".into())
-        .ok()
-        .map(|v| js_sys::Array::from(&v));
-    let Some(arr) = species_arr else { return Vec::new() };
+    js_sys::Reflect::get(val, &"species".into())
+        .map(|v| parse_species_array(&v))
+        .unwrap_or_default()
+}
+
+fn parse_species_array(val: &JsValue) -> Vec<SpeciesInfo> {
+    if !val.is_array() {
+        return Vec::new();
+    }
+    let arr = js_sys::Array::from(val);
     let mut result = Vec::new();
     for i in 0..arr.length() {
         let item = arr.get(i);
@@ -224,6 +370,220 @@ fn parse_cached_file(val: &JsValue) -> Option<CachedFile> {
     Some(CachedFile { path, filename, _xc_id: xc_id, metadata, hashes })
 }
 
+/// XC number typed as "XC928094" or pasted as a recording URL
+/// ("https://xeno-canto.org/928094"). Bare numbers stay ordinary searches.
+fn parse_xc_number(input: &str) -> Option<u64> {
+    let s = input.trim();
+    if let Some(rest) = s.strip_prefix("XC").or_else(|| s.strip_prefix("xc")) {
+        return rest.trim().parse().ok();
+    }
+    if s.contains("xeno-canto.org/") {
+        return s.trim_end_matches('/').rsplit('/').next()?.parse().ok();
+    }
+    None
+}
+
+/// Index of an open file downloaded from XC recording `id`. Cached
+/// recordings are named "XC{id} - …".
+fn open_xc_file_index(state: AppState, id: u64) -> Option<usize> {
+    let prefix = format!("XC{id} -");
+    state.files.with_untracked(|files| {
+        files.iter().position(|f| f.xc_metadata.is_some() && f.name.starts_with(&prefix))
+    })
+}
+
+/// Download XC recording `id` (or take it from the cache) and load it,
+/// returning its file index. An already open copy is reused. The current
+/// file is left to the caller.
+async fn open_xc_recording(state: AppState, id: u64) -> Result<usize, XcFailure> {
+    if let Some(idx) = open_xc_file_index(state, id) {
+        return Ok(idx);
+    }
+    let args = js_obj();
+    set_u64(&args, "id", id);
+
+    let val = invoke_with("xc_download", &args).await?;
+    let cached = parse_cached_file(&val)
+        .ok_or_else(|| XcFailure::from("Failed to parse download result".to_string()))?;
+
+    // Read raw file bytes via efficient binary IPC
+    let path_args = js_obj();
+    set_str(&path_args, "path", &cached.path);
+    let bytes_val = invoke_with("read_file_bytes", &path_args).await?;
+
+    // Convert ArrayBuffer/Uint8Array → Vec<u8>
+    let bytes: Vec<u8> = if let Ok(ab) = bytes_val.dyn_into::<js_sys::ArrayBuffer>() {
+        js_sys::Uint8Array::new(&ab).to_vec()
+    } else {
+        return Err("read_file_bytes did not return ArrayBuffer".to_string().into());
+    };
+
+    // Use the standard loading pipeline (WASM-side decode, spectrogram, etc.)
+    let load_id = state.loading_start(&cached.filename);
+    let load_result = crate::components::file_sidebar::load_named_bytes(
+        cached.filename.clone(),
+        &bytes,
+        Some(cached.metadata),
+        cached.hashes,
+        state,
+        load_id,
+        false,
+    ).await;
+    state.loading_done(load_id);
+    load_result?;
+
+    open_xc_file_index(state, id)
+        .ok_or_else(|| XcFailure::from(format!("XC{id} did not load")))
+}
+
+// ── Identify by comparison ───────────────────────────────────────────
+
+/// How far outside a species' Fc range the measured value may fall and
+/// still offer it as a candidate (kHz).
+const IDENTIFY_TOLERANCE_KHZ: f64 = 3.0;
+
+/// Start comparing the current file with XC reference recordings of the
+/// species in `table` whose characteristic frequency is near
+/// `char_freq_khz`, beginning with the closest.
+pub fn start_identify(state: AppState, char_freq_khz: f64, table: &crate::dsp::species_hint::SpeciesTable) {
+    let Some(file_name) = state.current_file().map(|f| f.name.clone()) else { return };
+    let candidates: Vec<crate::state::IdentifyCandidate> =
+        crate::dsp::species_hint::species_near_char_freq(table, char_freq_khz, IDENTIFY_TOLERANCE_KHZ)
+            .into_iter()
+            .filter_map(|rule| Some(crate::state::IdentifyCandidate {
+                name: rule.name.clone(),
+                scientific_name: rule.scientific_name.clone()?,
+                reference: None,
+                searched: false,
+            }))
+            .collect();
+    let status = candidates.is_empty().then(|| format!(
+        "No species in the table with Fc near {char_freq_khz:.1} kHz"
+    ));
+    let empty = candidates.is_empty();
+    state.xc_identify.set(Some(crate::state::XcIdentify {
+        file_name,
+        char_freq_khz,
+        candidates,
+        current: 0,
+        status,
+    }));
+    if !empty {
+        spawn_local(compare_identify_candidate(state));
+    }
+}
+
+/// Move to the next (`delta` > 0) or previous candidate, wrapping around.
+pub fn step_identify(state: AppState, delta: isize) {
+    let mut moved = false;
+    state.xc_identify.update(|s| {
+        if let Some(s) = s.as_mut().filter(|s| !s.candidates.is_empty()) {
+            let n = s.candidates.len() as isize;
+            s.current = (s.current as isize + delta).rem_euclid(n) as usize;
+            s.status = None;
+            moved = true;
+        }
+    });
+    if moved {
+        spawn_local(compare_identify_candidate(state));
+    }
+}
+
+fn set_identify_status(state: AppState, status: Option<String>) {
+    state.xc_identify.update(|s| if let Some(s) = s.as_mut() { s.status = status; });
+}
+
+/// Best reference for a species: a quality-A recording if XC has one,
+/// otherwise its first recording, preferring copies already cached.
+async fn find_reference(scientific_name: &str) -> Result<Option<u64>, XcFailure> {
+    let mut parts = scientific_name.split_whitespace();
+    let (Some(genus), Some(species)) = (parts.next(), parts.next()) else {
+        return Ok(None);
+    };
+    for query in [format!("gen:{genus} sp:{species} q:A"), format!("gen:{genus} sp:{species}")] {
+        let args = js_obj();
+        set_str(&args, "query", &query);
+        set_opt_u32(&args, "page", None);
+        let recordings = parse_recordings(&invoke_with("xc_search", &args).await?);
+        if recordings.is_empty() {
+            continue;
+        }
+        for r in recordings.iter().take(5) {
+            let args = js_obj();
+            set_u64(&args, "id", r.id);
+            if invoke_with("xc_is_cached", &args).await.ok().and_then(|v| v.as_bool()).unwrap_or(false) {
+                return Ok(Some(r.id));
+            }
+        }
+        return Ok(Some(recordings[0].id));
+    }
+    Ok(None)
+}
+
+/// Fetch the current candidate's reference if needed and show it as file B
+/// of the Diff view against the identified file.
+async fn compare_identify_candidate(state: AppState) {
+    let Some(session) = state.xc_identify.get_untracked() else { return };
+    let Some(candidate) = session.candidates.get(session.current).cloned() else { return };
+    let current = session.current;
+    let file_name = session.file_name.clone();
+    // Ignore results for a candidate the user has already stepped past
+    let still_current = move || {
+        state.xc_identify.with_untracked(|s| s.as_ref().is_some_and(|s| s.current == current && s.file_name == file_name))
+    };
+
+    let reference = if candidate.searched {
+        candidate.reference
+    } else {
+        set_identify_status(state, Some(format!("Searching xeno-canto for {}\u{2026}", candidate.scientific_name)));
+        match find_reference(&candidate.scientific_name).await {
+            Ok(found) => {
+                state.xc_identify.update(|s| {
+                    if let Some(c) = s.as_mut().and_then(|s| s.candidates.get_mut(current)) {
+                        c.reference = found;
+                        c.searched = true;
+                    }
+                });
+                found
+            }
+            Err(e) => {
+                if still_current() {
+                    set_identify_status(state, Some(format!("Search failed: {e}")));
+                }
+                return;
+            }
+        }
+    };
+    if !still_current() {
+        return;
+    }
+    let Some(id) = reference else {
+        set_identify_status(state, Some(format!("No reference recordings of {} on xeno-canto", candidate.name)));
+        return;
+    };
+
+    set_identify_status(state, Some(format!("Loading XC{id}\u{2026}")));
+    let result = open_xc_recording(state, id).await;
+    if !still_current() {
+        return;
+    }
+    match result {
+        Ok(ref_idx) => {
+            let own = state.files.with_untracked(|files| files.iter().position(|f| f.name == session.file_name));
+            let Some(own) = own else {
+                state.xc_identify.set(None);
+                return;
+            };
+            state.current_file_index.set(Some(own));
+            let reference = state.files.with_untracked(|files| files.get(ref_idx).map(|f| f.key()));
+            state.diff_other_file.set(reference);
+            state.main_view.set(crate::state::MainView::Diff);
+            set_identify_status(state, None);
+        }
+        Err(e) => set_identify_status(state, Some(format!("Failed to load XC{id}: {e}"))),
+    }
+}
+
 // ── View states ──────────────────────────────────────────────────────
 
 #[derive(Clone, Debug, PartialEq)]
@@ -248,8 +608,9 @@ pub fn XcBrowser() -> impl IntoView {
     let species_list: RwSignal<Vec<SpeciesInfo>> = RwSignal::new(Vec::new());
     let recordings: RwSignal<Vec<RecordingInfo>> = RwSignal::new(Vec::new());
     let loading = RwSignal::new(false);
-    let error_msg: RwSignal<Option<String>> = RwSignal::new(None);
-    let taxonomy_age: RwSignal<Option<String>> = RwSignal::new(None);
+    let error_msg: RwSignal<Option<XcFailure>> = RwSignal::new(None);
+    // (age label, stale period in days if stale) of the selected group's cached taxonomy
+    let taxonomy_age: RwSignal<Option<(String, Option<u32>)>> = RwSignal::new(None);
     let search_input = RwSignal::new(String::new());
     let recordings_page = RwSignal::new(1u32);
     let recordings_total_pages = RwSignal::new(1u32);
@@ -306,7 +667,7 @@ pub fn XcBrowser() -> impl IntoView {
                     view.set(BrowserView::GroupBrowse);
                     error_msg.set(None);
                 }
-                Err(e) => error_msg.set(Some(format!("Failed to save key: {e}"))),
+                Err(e) => error_msg.set(Some(e.prefixed("Failed to save key"))),
             }
         });
     };
@@ -320,31 +681,49 @@ pub fn XcBrowser() -> impl IntoView {
         loading.set(true);
         error_msg.set(None);
         species_list.set(Vec::new());
+        ensure_taxonomy_listener(state);
+        TAXONOMY_TARGET.with(|t| *t.borrow_mut() = Some((group.clone(), species_list)));
 
         spawn_local(async move {
             let args = js_obj();
             set_str(&args, "group", &group);
             set_opt_str(&args, "country", &country);
 
-            match invoke_with("xc_browse_group", &args).await {
+            let result = invoke_with("xc_browse_group", &args).await;
+            TAXONOMY_TARGET.with(|t| *t.borrow_mut() = None);
+            match result {
                 Ok(val) => {
                     species_list.set(parse_species_list(&val));
                 }
                 Err(e) => error_msg.set(Some(e)),
             }
 
-            // Get cache age
-            let age_args = js_obj();
-            set_str(&age_args, "group", &group);
-            set_opt_str(&age_args, "country", &country);
-            if let Ok(val) = invoke_with("xc_taxonomy_age", &age_args).await {
-                taxonomy_age.set(val.as_string());
-            }
-
             loading.set(false);
         });
     };
 
+    // Cache age of the selected group, refetched whenever the group or
+    // committed country changes (before "Go" as well as after).
+    Effect::new(move |_| {
+        let group = selected_group.get();
+        let country = {
+            let c = country_input.get().trim().to_string();
+            if c.is_empty() { None } else { Some(c) }
+        };
+        let _ = loading.get();
+        if !has_key.get() {
+            return;
+        }
+        spawn_local(async move {
+            let args = js_obj();
+            set_str(&args, "group", &group);
+            set_opt_str(&args, "country", &country);
+            let age = invoke_with("xc_taxonomy_age", &args).await.ok()
+                .and_then(|val| parse_taxonomy_age(&val));
+            taxonomy_age.set(age);
+        });
+    });
+
     let on_load_group = move |_: web_sys::MouseEvent| {
         // Commit any pending filter text
         let text = country_filter_text.get_untracked().trim().to_string();
@@ -353,27 +732,71 @@ pub fn XcBrowser() -> impl IntoView {
         load_group();
     };
 
+    let convert_rate = RwSignal::new(256_000u32);
+    let convert_format = RwSignal::new(SampleFormat::Int16);
+
+    let on_convert = move |_: web_sys::MouseEvent| {
+        if state.xc_converting.get_untracked() {
+            return;
+        }
+        ensure_convert_listener(state);
+        let args = js_obj();
+        set_u64(&args, "sampleRate", convert_rate.get_untracked() as u64);
+        set_str(&args, "format", convert_format.get_untracked().key());
+        js_sys::Reflect::set(&args, &JsValue::from_str("force"), &JsValue::FALSE).ok();
+        state.xc_converting.set(true);
+
+        spawn_local(async move {
+            match invoke_with("xc_convert_cache", &args).await {
+                // Folder dialog cancelled
+                Ok(val) if val.is_null() || val.is_undefined() => state.xc_convert_status.set(None),
+                Ok(val) => {
+                    let count = |key: &str| {
+                        js_sys::Reflect::get(&val, &JsValue::from_str(key)).ok()
+                            .and_then(|v| v.as_f64()).unwrap_or(0.0) as u32
+                    };
+                    state.xc_convert_status.set(Some(format!(
+                        "Converted {}, already converted {}, failed {}",
+                        count("converted"), count("skipped"), count("failed"),
+                    )));
+                }
+                Err(e) => state.xc_convert_status.set(Some(format!("Conversion failed: {e}"))),
+            }
+            state.xc_converting.set(false);
+        });
+    };
+
     let on_refresh = move |_: web_sys::MouseEvent| {
         let group = selected_group.get_untracked();
         let country = {
             let c = country_input.get_untracked().trim().to_string();
             if c.is_empty() { None } else { Some(c) }
         };
+        if state.xc_taxonomy_progress.get_untracked().is_some() {
+            return;
+        }
+        ensure_taxonomy_listener(state);
         loading.set(true);
         error_msg.set(None);
+        state.xc_taxonomy_progress.set(Some((0, 0)));
+        species_list.set(Vec::new());
+        TAXONOMY_TARGET.with(|t| *t.borrow_mut() = Some((group.clone(), species_list)));
 
         spawn_local(async move {
             let args = js_obj();
             set_str(&args, "group", &group);
             set_opt_str(&args, "country", &country);
 
-            match invoke_with("xc_refresh_taxonomy", &args).await {
+            let result = invoke_with("xc_refresh_taxonomy", &args).await;
+            TAXONOMY_TARGET.with(|t| *t.borrow_mut() = None);
+            match result {
                 Ok(val) => {
                     species_list.set(parse_species_list(&val));
-                    taxonomy_age.set(Some("just now".to_string()));
+                    taxonomy_age.set(Some(("just now".to_string(), None)));
                 }
                 Err(e) => error_msg.set(Some(e)),
             }
+            state.xc_taxonomy_progress.set(None);
             loading.set(false);
         });
     };
@@ -426,11 +849,42 @@ pub fn XcBrowser() -> impl IntoView {
         });
     };
 
+    let download_and_load = move |id: u64| {
+        // Already open: just switch to it
+        if let Some(idx) = open_xc_file_index(state, id) {
+            state.current_file_index.set(Some(idx));
+            state.xc_browser_open.set(false);
+            return;
+        }
+        downloading.set(Some(id));
+        error_msg.set(None);
+        spawn_local(async move {
+            match open_xc_recording(state, id).await {
+                Ok(idx) => {
+                    // Switch to the newly loaded file
+                    state.current_file_index.set(Some(idx));
+                    cached_ids.update(|s| { s.insert(id); });
+                    state.xc_browser_open.set(false);
+                }
+                Err(e) => {
+                    log::error!("Failed to load XC{id}: {e}");
+                    error_msg.set(Some(e.prefixed("Failed to load")));
+                }
+            }
+            downloading.set(None);
+        });
+    };
+
     let on_search = move |_: web_sys::MouseEvent| {
         let query = search_input.get_untracked().trim().to_string();
         if query.is_empty() {
             return;
         }
+        // An XC number or recording URL opens that recording directly
+        if let Some(id) = parse_xc_number(&query) {
+            download_and_load(id);
+            return;
+        }
         view.set(BrowserView::SearchResults);
         loading.set(true);
         recordings.set(Vec::new());
@@ -546,63 +1000,6 @@ pub fn XcBrowser() -> impl IntoView {
         });
     };
 
-    let download_and_load = move |id: u64| {
-        downloading.set(Some(id));
-        error_msg.set(None);
-        spawn_local(async move {
-            let result: Result<(), String> = async {
-                let args = js_obj();
-                set_u64(&args, "id", id);
-
-                let val = invoke_with("xc_download", &args).await?;
-                let cached = parse_cached_file(&val)
-                    .ok_or_else(|| "Failed to parse download result".to_string())?;
-
-                // Read raw file bytes via efficient binary IPC
-                let path_args = js_obj();
-                set_str(&path_args, "path", &cached.path);
-                let bytes_val = invoke_with("read_file_bytes", &path_args).await?;
-
-                // Convert ArrayBuffer/Uint8Array → Vec<u8>
-                let bytes: Vec<u8> = if let Ok(ab) = bytes_val.dyn_into::<js_sys::ArrayBuffer>() {
-                    js_sys::Uint8Array::new(&ab).to_vec()
-                } else {
-                    return Err("read_file_bytes did not return ArrayBuffer".to_string());
-                };
-
-                // Use the standard loading pipeline (WASM-side decode, spectrogram, etc.)
-                let load_id = state.loading_start(&cached.filename);
-                let load_result = crate::components::file_sidebar::load_named_bytes(
-                    cached.filename.clone(),
-                    &bytes,
-                    Some(cached.metadata),
-                    cached.hashes,
-                    state,
-                    load_id,
-                    false,
-                ).await;
-                state.loading_done(load_id);
-                load_result?;
-
-                // Switch to the newly loaded file
-                let file_count = state.files.with_untracked(|files| files.len());
-                if file_count > 0 {
-                    state.current_file_index.set(Some(file_count - 1));
-                }
-
-                cached_ids.update(|s| { s.insert(id); });
-                state.xc_browser_open.set(false);
-                Ok(())
-            }.await;
-
-            if let Err(e) = result {
-                log::error!("Failed to load XC{id}: {e}");
-                error_msg.set(Some(format!("Failed to load: {e}")));
-            }
-            downloading.set(None);
-        });
-    };
-
     view! {
         <div class="xc-modal-overlay" on:click=on_close>
             <div class="xc-modal" on:click=on_content_click>
@@ -611,12 +1008,21 @@ pub fn XcBrowser() -> impl IntoView {
                     <button class="xc-modal-close" on:click=on_close>{"\u{00D7}"}</button>
                 </div>
 
-                // Error display
-                {move || error_msg.get().map(|msg| view! {
-                    <div class="xc-error">
-                        <span>{msg}</span>
-                        <button class="xc-error-dismiss" on:click=move |_| error_msg.set(None)>{"\u{00D7}"}</button>
-                    </div>
+                // Error display; an auth failure offers to re-enter the key
+                {move || error_msg.get().map(|err| {
+                    let reauth = (err.kind == XcErrorKind::Auth).then(|| view! {
+                        <button class="xc-btn xc-error-action" on:click=move |_| {
+                            has_key.set(false);
+                            view.set(BrowserView::ApiKeyPrompt);
+                        }>"Update API key"</button>
+                    });
+                    view! {
+                        <div class="xc-error">
+                            <span>{err.message}</span>
+                            {reauth}
+                            <button class="xc-error-dismiss" on:click=move |_| error_msg.set(None)>{"\u{00D7}"}</button>
+                        </div>
+                    }
                 })}
 
                 // Download progress indicator
@@ -665,7 +1071,7 @@ pub fn XcBrowser() -> impl IntoView {
                                 <input
                                     type="text"
                                     class="xc-input xc-search-input"
-                                    placeholder="Search (e.g. Myotis, bat, Australia...)"
+                                    placeholder="Search (e.g. Myotis, bat, Australia...) or XC number to open"
                                     prop:value=move || search_input.get()
                                     on:input=move |ev| search_input.set(event_target_value(&ev))
                                     on:keydown=on_search_keydown
@@ -796,11 +1202,82 @@ pub fn XcBrowser() -> impl IntoView {
                                             <button class="xc-btn" on:click=on_load_group>"Go"</button>
                                         </div>
                                         <div class="xc-cache-info">
-                                            {move || taxonomy_age.get().map(|age| view! {
-                                                <span class="xc-cache-age">{"Cached: "}{age}</span>
-                                            })}
-                                            <button class="xc-btn xc-btn-small" on:click=on_refresh>"Refresh"</button>
+                                            {move || match state.xc_taxonomy_progress.get() {
+                                                Some((page, total)) => view! {
+                                                    <div class="loading-spinner"></div>
+                                                    <span class="xc-cache-age">
+                                                        {if total > 0 {
+                                                            format!("Refreshing page {page} of {total}\u{2026}")
+                                                        } else {
+                                                            "Refreshing\u{2026}".to_string()
+                                                        }}
+                                                    </span>
+                                                }.into_any(),
+                                                None => match taxonomy_age.get() {
+                                                    Some((age, stale_days)) => view! {
+                                                        <span
+                                                            class="xc-cache-age"
+                                                            class:stale=stale_days.is_some()
+                                                            title=stale_days.map(|days| format!(
+                                                                "This cache is over {days} days old; refresh to pick up new species and recordings"
+                                                            )).unwrap_or_default()
+                                                        >
+                                                            {format!("{} cached: {}", selected_group.get_untracked(), age)}
+                                                            {stale_days.map(|_| " (stale)")}
+                                                        </span>
+                                                    }.into_any(),
+                                                    None => view! {
+                                                        <span class="xc-cache-age">{"Not cached yet"}</span>
+                                                    }.into_any(),
+                                                },
+                                            }}
+                                            <button
+                                                class="xc-btn xc-btn-small"
+                                                prop:disabled=move || state.xc_taxonomy_progress.get().is_some()
+                                                on:click=on_refresh
+                                            >"Refresh"</button>
                                         </div>
+                                        <div class="xc-convert" title="Convert every cached recording to mono WAV in a chosen folder, copying the .xc.json sidecars. Files already in the folder are skipped.">
+                                            <label>"Convert cache: "</label>
+                                            <select
+                                                class="xc-select"
+                                                on:change=move |ev| {
+                                                    if let Ok(rate) = event_target_value(&ev).parse() {
+                                                        convert_rate.set(rate);
+                                                    }
+                                                }
+                                            >
+                                                {CONVERT_RATES.iter().map(|&rate| view! {
+                                                    <option
+                                                        value=rate.to_string()
+                                                        selected=move || convert_rate.get() == rate
+                                                    >{format!("{} kHz", rate / 1000)}</option>
+                                                }).collect::<Vec<_>>()}
+                                            </select>
+                                            <select
+                                                class="xc-select"
+                                                on:change=move |ev| {
+                                                    if let Some(format) = SampleFormat::from_key(&event_target_value(&ev)) {
+                                                        convert_format.set(format);
+                                                    }
+                                                }
+                                            >
+                                                {SampleFormat::ALL.iter().map(|&format| view! {
+                                                    <option
+                                                        value=format.key()
+                                                        selected=move || convert_format.get() == format
+                                                    >{format.label()}</option>
+                                                }).collect::<Vec<_>>()}
+                                            </select>
+                                            <button
+                                                class="xc-btn xc-btn-small"
+                                                disabled=move || state.xc_converting.get()
+                                                on:click=on_convert
+                                            >{"Convert\u{2026}"}</button>
+                                        </div>
+                                        {move || state.xc_convert_status.get().map(|msg| view! {
+                                            <div class="xc-convert-status">{msg}</div>
+                                        })}
                                     })
                                 } else {
                                     None
@@ -835,8 +1312,13 @@ pub fn XcBrowser() -> impl IntoView {
                     if list.is_empty() && !loading.get() { return None; }
 
                     let count = list.len();
+                    let summary = if loading.get() {
+                        format!("{count} species so far\u{2026}")
+                    } else {
+                        format!("{count} species")
+                    };
                     Some(view! {
-                        <div class="xc-result-summary">{format!("{count} species")}</div>
+                        <div class="xc-result-summary">{summary}</div>
                         <div class="xc-species-list">
                             <div class="xc-list-header">
                                 <span class="xc-col-name">"Species"</span>
@@ -945,8 +1427,9 @@ pub fn XcBrowser() -> impl IntoView {
                                                     class="xc-btn xc-btn-load"
                                                     disabled=move || downloading.get().is_some()
                                                     on:click=move |_| dl(id)
+                                                    title="Download (if not cached) and open in the analysis view with its xeno-canto metadata"
                                                 >
-                                                    {move || if downloading.get() == Some(id) { "Downloading\u{2026}" } else { "Load" }}
+                                                    {move || if downloading.get() == Some(id) { "Downloading\u{2026}" } else { "Open" }}
                                                 </button>
                                             </span>
                                         </div>
@@ -4,7 +4,7 @@ use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement};
 use crate::canvas::spectrogram_renderer::{self, FreqMarkerState, FreqShiftMode};
 use crate::dsp::filters::{apply_eq_filter, apply_eq_filter_fast};
-use crate::dsp::zc_divide::zc_rate_per_bin;
+use crate::dsp::zc_divide::{zc_dots, zc_rate_per_bin};
 use crate::state::{AppState, CanvasTool, FilterQuality, SpectrogramHandle};
 use crate::components::axis_drag::{apply_axis_drag, freq_snap};
 use crate::components::gutter::{BandGutter, TimeGutter};
@@ -15,6 +15,15 @@ const ZC_BIN_DURATION: f64 = 0.001; // 1ms bins
 const TAU: f64 = std::f64::consts::TAU;
 const LABEL_AREA_WIDTH: f64 = 60.0;
 
+/// Points to plot: per-bin average rates, or one dot per group of cycles.
+#[derive(Clone, PartialEq)]
+enum ZcPoints {
+    /// (crossings per second, armed) per `ZC_BIN_DURATION` bin
+    Bins(Vec<(f64, bool)>),
+    /// (time, frequency) per `zc_dot_division` cycles
+    Dots(Vec<(f64, f64)>),
+}
+
 /// Pick a nice grid interval (in kHz) for the visible frequency range.
 fn grid_interval_khz(range_khz: f64) -> f64 {
     if range_khz <= 10.0 { 2.0 }
@@ -56,8 +65,8 @@ pub fn ZcDotChart() -> impl IntoView {
         Some(candidates[0].0)
     };
 
-    // Cache ZC bins — recompute when the file or EQ settings change.
-    let zc_bins = Memo::new(move |_| {
+    // Cache ZC points — recompute when the file, EQ settings or dot mode change.
+    let zc_points = Memo::new(move |_| {
         let files = state.files.get();
         let idx = state.current_file_index.get();
         let filter_enabled = state.filter_enabled.get();
@@ -69,6 +78,12 @@ pub fn ZcDotChart() -> impl IntoView {
         let db_above = state.filter_db_above.get();
         let band_mode = state.filter_band_mode.get();
         let quality = state.filter_quality.get();
+        let division = state.zc_dot_division.get() as usize;
+        let points = |samples: &[f32], sr: u32| if division > 0 {
+            ZcPoints::Dots(zc_dots(samples, sr, division, filter_enabled))
+        } else {
+            ZcPoints::Bins(zc_rate_per_bin(samples, sr, ZC_BIN_DURATION, filter_enabled))
+        };
 
         idx.and_then(|i| files.get(i).cloned()).map(|file| {
             let sr = file.audio.sample_rate;
@@ -80,9 +95,9 @@ pub fn ZcDotChart() -> impl IntoView {
                     FilterQuality::Fast => apply_eq_filter_fast(raw, sr, freq_low, freq_high, db_below, db_selected, db_harmonics, db_above, band_mode),
                     FilterQuality::Spectral => apply_eq_filter(raw, sr, freq_low, freq_high, db_below, db_selected, db_harmonics, db_above, band_mode),
                 };
-                zc_rate_per_bin(&filtered, sr, ZC_BIN_DURATION, filter_enabled)
+                points(&filtered, sr)
             } else {
-                zc_rate_per_bin(raw, sr, ZC_BIN_DURATION, filter_enabled)
+                points(raw, sr)
             }
         })
     });
@@ -98,6 +113,8 @@ pub fn ZcDotChart() -> impl IntoView {
         let canvas_tool = state.canvas_tool.get();
         let display_min_freq = state.min_display_freq.get();
         let display_max_freq = state.max_display_freq.get();
+        let _flipped = state.freq_axis_flipped.get();
+        let _high_contrast = state.high_contrast.get();
         let band_ff_lo = state.band_ff_freq_lo.get();
         let band_ff_hi = state.band_ff_freq_hi.get();
         let axis_drag_start = state.axis_drag_start_freq.get();
@@ -137,8 +154,10 @@ pub fn ZcDotChart() -> impl IntoView {
         ctx.fill_rect(0.0, 0.0, cw, ch);
 
         let Some(file) = idx.and_then(|i| files.get(i)) else { return };
-        let Some(bins) = zc_bins.get().as_ref().cloned() else { return };
-        if bins.is_empty() { return; }
+        let Some(points) = zc_points.get() else { return };
+        if let ZcPoints::Bins(bins) = &points {
+            if bins.is_empty() { return; }
+        }
 
         let time_res = file.spectrogram.time_resolution;
         let total_duration = file.audio.duration_secs;
@@ -198,54 +217,76 @@ pub fn ZcDotChart() -> impl IntoView {
             freq_khz += interval;
         }
 
-        // Dot size scaling based on zoom
-        let dot_spacing_px = ZC_BIN_DURATION * px_per_sec;
-        let radius_armed = (dot_spacing_px * 0.4).clamp(0.7, 3.0);
-        let radius_unarmed = (dot_spacing_px * 0.3).clamp(0.5, 2.5);
-
-        // Brightness boost when dots are small: 0.0 at full size, 1.0 at minimum
-        let small_t = (1.0 - (radius_armed - 0.7) / 2.3).clamp(0.0, 1.0);
-
-        // Only iterate visible bins
-        let first_bin = ((start_time / ZC_BIN_DURATION) as usize).saturating_sub(1);
-        let last_bin = ((end_time / ZC_BIN_DURATION) as usize + 2).min(bins.len());
-
-        // Batch armed dots — brighter when small
-        let armed_alpha = 0.9 + small_t * 0.1;
-        let armed_g = (200.0 + small_t * 55.0) as u32;
-        ctx.set_fill_style_str(&format!("rgba(100, {armed_g}, 100, {armed_alpha:.2})"));
-        ctx.begin_path();
-        for (bin_idx, &(rate_hz, armed)) in bins.iter().enumerate().take(last_bin).skip(first_bin) {
-            if rate_hz <= 0.0 || !armed { continue; }
-            if rate_hz < min_freq || rate_hz > max_freq { continue; }
-            let bin_time = bin_idx as f64 * ZC_BIN_DURATION;
-            let x = LABEL_AREA_WIDTH + data_x + (bin_time - start_time) * px_per_sec;
-            let y = spectrogram_renderer::freq_to_y(rate_hz, min_freq, max_freq, ch);
-            ctx.move_to(x + radius_armed, y);
-            let _ = ctx.arc(x, y, radius_armed, 0.0, TAU);
-        }
-        ctx.fill();
-
-        // Batch unarmed dots (dim green, visible but secondary) — brighter when small
-        let unarmed_alpha = 0.35 + small_t * 0.35;
-        let unarmed_g = (130.0 + small_t * 50.0) as u32;
-        ctx.set_fill_style_str(&format!("rgba(60, {unarmed_g}, 60, {unarmed_alpha:.2})"));
-        ctx.begin_path();
-        for (bin_idx, &(rate_hz, armed)) in bins.iter().enumerate().take(last_bin).skip(first_bin) {
-            if rate_hz <= 0.0 || armed { continue; }
-            if rate_hz < min_freq || rate_hz > max_freq { continue; }
-            let bin_time = bin_idx as f64 * ZC_BIN_DURATION;
-            let x = LABEL_AREA_WIDTH + data_x + (bin_time - start_time) * px_per_sec;
-            let y = spectrogram_renderer::freq_to_y(rate_hz, min_freq, max_freq, ch);
-            ctx.move_to(x + radius_unarmed, y);
-            let _ = ctx.arc(x, y, radius_unarmed, 0.0, TAU);
+        match &points {
+            ZcPoints::Bins(bins) => {
+                // Dot size scaling based on zoom
+                let dot_spacing_px = ZC_BIN_DURATION * px_per_sec;
+                let radius_armed = (dot_spacing_px * 0.4).clamp(0.7, 3.0);
+                let radius_unarmed = (dot_spacing_px * 0.3).clamp(0.5, 2.5);
+
+                // Brightness boost when dots are small: 0.0 at full size, 1.0 at minimum
+                let small_t = (1.0 - (radius_armed - 0.7) / 2.3).clamp(0.0, 1.0);
+
+                // Only iterate visible bins
+                let first_bin = ((start_time / ZC_BIN_DURATION) as usize).saturating_sub(1);
+                let last_bin = ((end_time / ZC_BIN_DURATION) as usize + 2).min(bins.len());
+
+                // Batch armed dots — brighter when small
+                let armed_alpha = 0.9 + small_t * 0.1;
+                let armed_g = (200.0 + small_t * 55.0) as u32;
+                ctx.set_fill_style_str(&format!("rgba(100, {armed_g}, 100, {armed_alpha:.2})"));
+                ctx.begin_path();
+                for (bin_idx, &(rate_hz, armed)) in bins.iter().enumerate().take(last_bin).skip(first_bin) {
+                    if rate_hz <= 0.0 || !armed { continue; }
+                    if rate_hz < min_freq || rate_hz > max_freq { continue; }
+                    let bin_time = bin_idx as f64 * ZC_BIN_DURATION;
+                    let x = LABEL_AREA_WIDTH + data_x + (bin_time - start_time) * px_per_sec;
+                    let y = spectrogram_renderer::freq_to_y(rate_hz, min_freq, max_freq, ch);
+                    ctx.move_to(x + radius_armed, y);
+                    let _ = ctx.arc(x, y, radius_armed, 0.0, TAU);
+                }
+                ctx.fill();
+
+                // Batch unarmed dots (dim green, visible but secondary) — brighter when small
+                let unarmed_alpha = 0.35 + small_t * 0.35;
+                let unarmed_g = (130.0 + small_t * 50.0) as u32;
+                ctx.set_fill_style_str(&format!("rgba(60, {unarmed_g}, 60, {unarmed_alpha:.2})"));
+                ctx.begin_path();
+                for (bin_idx, &(rate_hz, armed)) in bins.iter().enumerate().take(last_bin).skip(first_bin) {
+                    if rate_hz <= 0.0 || armed { continue; }
+                    if rate_hz < min_freq || rate_hz > max_freq { continue; }
+                    let bin_time = bin_idx as f64 * ZC_BIN_DURATION;
+                    let x = LABEL_AREA_WIDTH + data_x + (bin_time - start_time) * px_per_sec;
+                    let y = spectrogram_renderer::freq_to_y(rate_hz, min_freq, max_freq, ch);
+                    ctx.move_to(x + radius_unarmed, y);
+                    let _ = ctx.arc(x, y, radius_unarmed, 0.0, TAU);
+                }
+                ctx.fill();
+            }
+            ZcPoints::Dots(dots) => {
+                // One small dot per group of cycles, as on AnalookW; dots are
+                // sorted by time so the visible slice is found by bisection.
+                let first = dots.partition_point(|&(t, _)| t < start_time);
+                let last = dots.partition_point(|&(t, _)| t <= end_time);
+                let visible = &dots[first..last];
+                let radius = 1.0;
+                ctx.set_fill_style_str("rgba(110, 230, 110, 0.9)");
+                ctx.begin_path();
+                for &(t, f) in visible {
+                    if f < min_freq || f > max_freq { continue; }
+                    let x = LABEL_AREA_WIDTH + data_x + (t - start_time) * px_per_sec;
+                    let y = spectrogram_renderer::freq_to_y(f, min_freq, max_freq, ch);
+                    ctx.move_to(x + radius, y);
+                    let _ = ctx.arc(x, y, radius, 0.0, TAU);
+                }
+                ctx.fill();
+            }
         }
-        ctx.fill();
 
         // Draw "play here" marker when not playing
         if state.play_start_mode.get() .uses_from_here() && !is_playing && canvas_tool == CanvasTool::Hand {
-            let here_x = LABEL_AREA_WIDTH + dot_area_w * viewport::PLAY_FROM_HERE_FRACTION;
-            let here_time = viewport::play_from_here_time(scroll, visible_time);
+            let here_x = LABEL_AREA_WIDTH + dot_area_w * state.play_anchor_fraction.get();
+            let here_time = viewport::play_from_here_time(scroll, visible_time, state.play_anchor_fraction.get());
             state.play_from_here_time.set(here_time);
             ctx.set_stroke_style_str("rgba(100, 160, 255, 0.35)");
             ctx.set_line_width(1.5);
@@ -266,7 +307,8 @@ pub fn ZcDotChart() -> impl IntoView {
         if band_ff_hi > band_ff_lo {
             spectrogram_renderer::draw_band_ff_overlay(
                 &ctx,
-                band_ff_lo, band_ff_hi,
+                band_ff_lo, band_ff_hi, &[],
+                state.focus_dim_opacity.get_untracked(), None,
                 min_freq, max_freq,
                 ch, cw,
                 spec_hover, spec_drag,
@@ -291,6 +333,7 @@ pub fn ZcDotChart() -> impl IntoView {
         ctx.stroke();
 
         // Frequency markers (color bars, labels, ticks, cursor indicator)
+        spectrogram_renderer::set_freq_marker_style(state.marker_colors.get(), state.freq_marker_interval_khz.get() * 1000.0);
         let shift_mode = FreqShiftMode::Divide(state.zc_factor.get());
         let (adl, adh) = match (axis_drag_start, axis_drag_current) {
             (Some(a), Some(b)) => (Some(a.min(b)), Some(a.max(b))),
@@ -360,7 +403,7 @@ pub fn ZcDotChart() -> impl IntoView {
             .unwrap_or((1.0, 0.0));
         let zoom = state.zoom_level.get_untracked();
         let scroll = state.scroll_offset.get_untracked();
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
 
         let visible_time = viewport::visible_time(display_w, zoom, time_res);
         let playhead_rel = playhead - scroll;
@@ -369,7 +412,7 @@ pub fn ZcDotChart() -> impl IntoView {
             let playhead_visible = playhead_rel >= 0.0 && playhead_rel <= visible_time;
             if playhead_visible {
                 let resume = match state.follow_visible_since.get_untracked() {
-                    Some(since) => js_sys::Date::now() - since >= 200.0,
+                    Some(since) => js_sys::Date::now() - since >= state.follow_resume_ms.get_untracked(),
                     None => true,
                 };
                 if resume {
@@ -382,10 +425,10 @@ pub fn ZcDotChart() -> impl IntoView {
 
         if visible_time < viewport::FOLLOW_EXACT_THRESHOLD_SECS {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         } else if playhead_rel > visible_time * viewport::FOLLOW_CURSOR_EDGE_FRACTION || playhead_rel < 0.0 {
             let target_scroll = playhead - visible_time * viewport::FOLLOW_CURSOR_FRACTION;
-            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here_mode));
+            state.scroll_offset.set(viewport::clamp_scroll_for_mode(target_scroll, duration, visible_time, from_here));
         }
     });
 
@@ -433,7 +476,7 @@ pub fn ZcDotChart() -> impl IntoView {
         ev.prevent_default();
         if ev.ctrl_key() {
             let delta = if ev.delta_y() > 0.0 { 0.9 } else { 1.1 };
-            state.zoom_level.update(|z| *z = (*z * delta).clamp(0.02, 100.0));
+            state.zoom_level.update(|z| *z = (*z * delta).clamp(0.02, state.max_zoom().min(100.0)));
         } else {
             let raw_delta = ev.delta_y() + ev.delta_x();
             let files = state.files.get_untracked();
@@ -446,9 +489,9 @@ pub fn ZcDotChart() -> impl IntoView {
                 return;
             };
             let delta = raw_delta.signum() * visible_time * 0.1 * (raw_delta.abs() / 100.0).min(3.0);
-            let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+            let from_here = state.from_here_fraction();
             state.suspend_follow();
-            state.scroll_offset.update(|s| *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here_mode));
+            state.scroll_offset.update(|s| *s = viewport::clamp_scroll_for_mode(*s + delta, duration, visible_time, from_here));
         }
     };
 
@@ -557,10 +600,10 @@ pub fn ZcDotChart() -> impl IntoView {
                 let zoom = state.zoom_level.get_untracked();
                 let visible_time = viewport::visible_time(cw, zoom, time_res);
                 let duration = file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(0.0);
-                let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+                let from_here = state.from_here_fraction();
                 let dt = -(dx / cw) * visible_time;
                 state.suspend_follow();
-                state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode));
+                state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here));
             } else {
                 // Not dragging: do BandFF handle hover detection (skip in label area)
                 if !in_label_area {
@@ -634,7 +677,7 @@ pub fn ZcDotChart() -> impl IntoView {
                     initial_mid_client_x: mid_x,
                     time_res,
                     duration,
-                    from_here_mode: state.play_start_mode.get_untracked() .uses_from_here(),
+                    from_here: state.from_here_fraction(),
                 }));
             }
             state.is_dragging.set(false);
@@ -685,7 +728,7 @@ pub fn ZcDotChart() -> impl IntoView {
                     let canvas: &HtmlCanvasElement = canvas_el.as_ref();
                     let rect = canvas.get_bounding_client_rect();
                     let cw = canvas.width() as f64;
-                    let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw);
+                    let (new_zoom, new_scroll) = apply_pinch(&ps, dist, mid_x, rect.left(), cw, state.max_zoom());
                     state.suspend_follow();
                     state.zoom_level.set(new_zoom);
                     state.scroll_offset.set(new_scroll);
@@ -752,10 +795,10 @@ pub fn ZcDotChart() -> impl IntoView {
         let zoom = state.zoom_level.get_untracked();
         let visible_time = viewport::visible_time(cw, zoom, time_res);
         let duration = file.as_ref().map(|f| f.audio.duration_secs).unwrap_or(0.0);
-        let from_here_mode = state.play_start_mode.get_untracked() .uses_from_here();
+        let from_here = state.from_here_fraction();
         let dt = -(dx / cw) * visible_time;
         state.suspend_follow();
-        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here_mode));
+        state.scroll_offset.set(viewport::clamp_scroll_for_mode(start_scroll + dt, duration, visible_time, from_here));
     };
 
     let on_touchend = move |_ev: web_sys::TouchEvent| {
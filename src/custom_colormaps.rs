@@ -0,0 +1,28 @@
+//! The user's saved custom colormaps.
+//!
+//! Parsing and LUT expansion live in `canvas::custom_colormap`; here the
+//! list is remembered in localStorage. Selecting a map gives
+//! `Colormap::Custom(index)`.
+
+use crate::canvas::custom_colormap::CustomColormap;
+
+const STORAGE_KEY: &str = "oversample_custom_colormaps";
+
+/// Most custom maps kept (indices must fit `Colormap::Custom(u8)`).
+pub const MAX_CUSTOM_COLORMAPS: usize = 32;
+
+/// The saved custom colormaps, or an empty list.
+pub fn load() -> Vec<CustomColormap> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|ls| ls.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(maps: &[CustomColormap]) {
+    let Ok(json) = serde_json::to_string(maps) else { return };
+    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = ls.set_item(STORAGE_KEY, &json);
+    }
+}
@@ -1,7 +1,7 @@
 // Re-export all DSP modules from oversample-core.
 // Individual module re-exports so `crate::dsp::fft`, `crate::dsp::filters`, etc. work.
 pub use oversample_core::dsp::{
-    agc, bit_analysis, fft, filters, harmonics, heterodyne, notch,
+    agc, band_activity, bit_analysis, call_params, call_snap, dc, declip, fade, fft, filters, harmonics, heterodyne, incremental, limiter, notch,
     phase_vocoder, pitch_shift, spectral_sub, zc_divide, wsnr,
-    zero_crossing, chromagram, psd, pulse_detect, resonators,
+    zero_crossing, chromagram, psd, pulse_detect, qc_report, resample, spectral_diff, resonators, silence_trim, smoothing, species_hint,
 };
@@ -0,0 +1,130 @@
+//! Per-file level corrections remembered across sessions.
+//!
+//! A manual gain, a DC-removal offset and clip restoration dialled in for one recording are
+//! saved to local storage, keyed by filename + size like browser-side notes,
+//! and re-applied when the same file is opened again. Corrections only
+//! affect the working copy used for display, analysis and playback; the
+//! decoded audio is never modified.
+
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashSet;
+
+use crate::state::{AppState, GainMode};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct FileCorrections {
+    /// Fixed gain in dB (manual gain mode). None = gain off or automatic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gain_db: Option<f64>,
+    /// DC offset subtracted from the working copy. None = no DC removal.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dc_offset: Option<f32>,
+    /// Clipped runs reconstructed for the waveform and playback.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub declip: bool,
+}
+
+impl FileCorrections {
+    pub fn is_empty(&self) -> bool {
+        self.gain_db.is_none() && self.dc_offset.is_none() && !self.declip
+    }
+}
+
+thread_local! {
+    /// Storage keys loaded or written this session. Clearing an entry is only
+    /// done for these, so the default settings a newly opened file starts
+    /// with can't wipe its saved corrections before they are loaded.
+    static KNOWN_KEYS: RefCell<HashSet<String>> = RefCell::new(HashSet::new());
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+fn storage_key(state: AppState, file_idx: usize) -> Option<String> {
+    state.files.with_untracked(|files| {
+        let f = files.get(file_idx)?;
+        let (name, size) = match f.identity.as_ref() {
+            Some(id) => (id.filename.clone(), id.file_size),
+            None => (f.name.clone(), f.audio.metadata.file_size as u64),
+        };
+        Some(format!("oversample_corrections_{}_{}", name, size))
+    })
+}
+
+/// Restore saved corrections into a file's settings, and into the live gain
+/// signals if it is the current file.
+pub fn load_corrections(state: AppState, file_idx: usize) {
+    let Some(key) = storage_key(state, file_idx) else { return };
+    let Some(saved) = local_storage()
+        .and_then(|ls| ls.get_item(&key).ok().flatten())
+        .and_then(|json| serde_json::from_str::<FileCorrections>(&json).ok())
+    else {
+        return;
+    };
+    KNOWN_KEYS.with(|k| k.borrow_mut().insert(key));
+    state.files.update(|files| {
+        if let Some(f) = files.get_mut(file_idx) {
+            if let Some(db) = saved.gain_db {
+                f.settings.gain_mode = GainMode::Manual;
+                f.settings.gain_db = db;
+            }
+            f.settings.dc_offset = saved.dc_offset;
+            f.settings.declip = saved.declip;
+        }
+    });
+    if state.current_file_index.get_untracked() == Some(file_idx) {
+        if let Some(db) = saved.gain_db {
+            state.gain_mode.set(GainMode::Manual);
+            state.auto_gain.set(false);
+            state.gain_db.set(db);
+        }
+    }
+}
+
+/// Persist corrections for a file; empty corrections remove the entry.
+/// Read-only files keep their corrections in memory only.
+pub fn save_corrections(state: AppState, file_idx: usize, corrections: FileCorrections) {
+    if state.files.with_untracked(|files| files.get(file_idx).is_none_or(|f| f.read_only)) {
+        return;
+    }
+    let (Some(key), Some(ls)) = (storage_key(state, file_idx), local_storage()) else { return };
+    if corrections.is_empty() && !KNOWN_KEYS.with(|k| k.borrow().contains(&key)) {
+        return;
+    }
+    let result = if corrections.is_empty() {
+        ls.remove_item(&key)
+    } else {
+        match serde_json::to_string(&corrections) {
+            Ok(json) => ls.set_item(&key, &json),
+            Err(_) => return,
+        }
+    };
+    match result {
+        Ok(()) => {
+            KNOWN_KEYS.with(|k| k.borrow_mut().insert(key));
+        }
+        Err(e) => log::warn!("Saving file corrections failed: {e:?}"),
+    }
+}
+
+/// Save the current file's corrections from the live gain signals and its
+/// stored DC offset and clip restoration. With HFR on, the normal-mode gain is the stashed one.
+pub fn save_current(state: AppState) {
+    let Some(idx) = state.current_file_index.get_untracked() else { return };
+    let gain_db = if state.focus_stack.with_untracked(|fs| fs.hfr_enabled()) {
+        state.gain_db_stash.get_untracked()
+    } else {
+        state.gain_db.get_untracked()
+    };
+    let manual = state.gain_mode.get_untracked() == GainMode::Manual && gain_db != 0.0;
+    let (dc_offset, declip) = state.files.with_untracked(|files| {
+        files.get(idx).map(|f| (f.settings.dc_offset, f.settings.declip)).unwrap_or_default()
+    });
+    save_corrections(state, idx, FileCorrections {
+        gain_db: manual.then_some(gain_db),
+        dc_offset,
+        declip,
+    });
+}
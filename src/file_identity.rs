@@ -389,6 +389,8 @@ pub fn start_identity_computation(
         if let Some(id) = identity {
             crate::opfs::load_annotations(state, file_index, id);
         }
+        crate::file_notes::load_notes(state, file_index);
+        crate::file_corrections::load_corrections(state, file_index);
         return;
     }
 
@@ -406,6 +408,8 @@ pub fn start_identity_computation(
 
     // Try loading annotations with Layer 1 key
     crate::opfs::load_annotations(state, file_index, identity);
+    crate::file_notes::load_notes(state, file_index);
+    crate::file_corrections::load_corrections(state, file_index);
 
     // Layer 2: compute BLAKE3 spot hash async (+ Layers 3+4 when bytes available)
     wasm_bindgen_futures::spawn_local(async move {
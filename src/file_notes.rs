@@ -0,0 +1,183 @@
+//! Per-file free-text notes.
+//!
+//! Desktop (Tauri): saved to a `<audio file>.notes.json` sidecar next to the file.
+//! Browser (or files without a path): saved to local storage, keyed by
+//! filename + size so the note survives reloading the same file.
+//!
+//! Saves are debounced so typing doesn't write on every keystroke.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use wasm_bindgen::JsCast;
+
+use crate::state::AppState;
+
+/// Debounce delay between the last edit and the write.
+const SAVE_DELAY_MS: i32 = 800;
+
+/// Contents of a `.notes.json` sidecar.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+struct NotesSidecar {
+    version: u32,
+    note: String,
+    #[serde(default)]
+    modified_at: Option<String>,
+}
+
+/// Where a file's note lives.
+#[derive(Clone, Debug, PartialEq)]
+enum NotesTarget {
+    /// Native path of the audio file; the sidecar is `<path>.notes.json`.
+    Sidecar(String),
+    /// Local storage key.
+    Local(String),
+}
+
+fn local_key(filename: &str, file_size: u64) -> String {
+    format!("oversample_notes_{}_{}", filename, file_size)
+}
+
+fn target_for(state: AppState, file_idx: usize) -> Option<NotesTarget> {
+    state.files.with_untracked(|files| {
+        let f = files.get(file_idx)?;
+        let path = f.identity.as_ref().and_then(|id| id.file_path.clone());
+        match path {
+            Some(p) if state.is_tauri => Some(NotesTarget::Sidecar(p)),
+            _ => {
+                let (name, size) = match f.identity.as_ref() {
+                    Some(id) => (id.filename.clone(), id.file_size),
+                    None => (f.name.clone(), f.audio.metadata.file_size as u64),
+                };
+                Some(NotesTarget::Local(local_key(&name, size)))
+            }
+        }
+    })
+}
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|w| w.local_storage().ok().flatten())
+}
+
+fn parse_note(json: &str) -> Option<String> {
+    serde_json::from_str::<NotesSidecar>(json).ok().map(|s| s.note)
+}
+
+async fn read_note(target: &NotesTarget) -> Result<Option<String>, String> {
+    match target {
+        NotesTarget::Sidecar(path) => {
+            let args = js_sys::Object::new();
+            js_sys::Reflect::set(&args, &wasm_bindgen::JsValue::from_str("path"), &wasm_bindgen::JsValue::from_str(path))
+                .map_err(|e| format!("set path: {e:?}"))?;
+            let result = crate::tauri_bridge::tauri_invoke("read_notes_sidecar", &args.into()).await?;
+            Ok(result.as_string().and_then(|json| parse_note(&json)))
+        }
+        NotesTarget::Local(key) => Ok(local_storage()
+            .and_then(|ls| ls.get_item(key).ok().flatten())
+            .and_then(|json| parse_note(&json))),
+    }
+}
+
+async fn write_note(target: &NotesTarget, note: &str) -> Result<(), String> {
+    // An empty note removes the sidecar / storage entry rather than leaving a stub behind.
+    let json = if note.trim().is_empty() {
+        String::new()
+    } else {
+        serde_json::to_string_pretty(&NotesSidecar {
+            version: 1,
+            note: note.to_string(),
+            modified_at: Some(crate::annotations::now_iso8601()),
+        })
+        .map_err(|e| format!("serialize notes: {e}"))?
+    };
+    match target {
+        NotesTarget::Sidecar(path) => {
+            let args = js_sys::Object::new();
+            js_sys::Reflect::set(&args, &wasm_bindgen::JsValue::from_str("path"), &wasm_bindgen::JsValue::from_str(path))
+                .map_err(|e| format!("set path: {e:?}"))?;
+            js_sys::Reflect::set(&args, &wasm_bindgen::JsValue::from_str("json"), &wasm_bindgen::JsValue::from_str(&json))
+                .map_err(|e| format!("set json: {e:?}"))?;
+            crate::tauri_bridge::tauri_invoke("write_notes_sidecar", &args.into()).await?;
+            Ok(())
+        }
+        NotesTarget::Local(key) => {
+            let ls = local_storage().ok_or("local storage unavailable")?;
+            let result = if json.is_empty() { ls.remove_item(key) } else { ls.set_item(key, &json) };
+            result.map_err(|e| format!("local storage: {e:?}"))
+        }
+    }
+}
+
+/// Load the saved note for a file (if any) into `LoadedFile::notes`.
+///
+/// Safe to call repeatedly (e.g. once the native path becomes known): a note
+/// the user has already started typing is never overwritten.
+pub fn load_notes(state: AppState, file_idx: usize) {
+    let Some(target) = target_for(state, file_idx) else { return };
+    let name = state.files.with_untracked(|files| files.get(file_idx).map(|f| f.name.clone()));
+    wasm_bindgen_futures::spawn_local(async move {
+        match read_note(&target).await {
+            Ok(Some(note)) => {
+                state.files.update(|files| {
+                    if let Some(f) = files.get_mut(file_idx) {
+                        if Some(&f.name) == name.as_ref() && f.notes.is_empty() {
+                            f.notes = note;
+                        }
+                    }
+                });
+            }
+            Ok(None) => {}
+            Err(e) => log::debug!("Notes load skipped: {e}"),
+        }
+    });
+}
+
+thread_local! {
+    /// Pending debounced save: (timer handle, target, note text).
+    static PENDING_SAVE: RefCell<Option<(i32, NotesTarget, String)>> = const { RefCell::new(None) };
+}
+
+fn spawn_write(target: NotesTarget, note: String) {
+    wasm_bindgen_futures::spawn_local(async move {
+        if let Err(e) = write_note(&target, &note).await {
+            log::warn!("Notes save failed: {e}");
+        }
+    });
+}
+
+/// Update a file's note and schedule a debounced save.
+///
+/// Read-only files keep the edit in memory only.
+pub fn set_notes(state: AppState, file_idx: usize, note: String) {
+    let read_only = state.files.with_untracked(|files| files.get(file_idx).is_some_and(|f| f.read_only));
+    state.files.update(|files| {
+        if let Some(f) = files.get_mut(file_idx) {
+            f.notes = note.clone();
+        }
+    });
+    if read_only {
+        return;
+    }
+    let Some(target) = target_for(state, file_idx) else { return };
+
+    // Replace any pending save. If it was for a different file, flush it now
+    // instead of dropping it.
+    let window = web_sys::window().unwrap();
+    if let Some((handle, prev_target, prev_note)) = PENDING_SAVE.with(|p| p.borrow_mut().take()) {
+        window.clear_timeout_with_handle(handle);
+        if prev_target != target {
+            spawn_write(prev_target, prev_note);
+        }
+    }
+
+    let cb = wasm_bindgen::closure::Closure::once(move || {
+        if let Some((_, target, note)) = PENDING_SAVE.with(|p| p.borrow_mut().take()) {
+            spawn_write(target, note);
+        }
+    });
+    let handle = window
+        .set_timeout_with_callback_and_timeout_and_arguments_0(cb.as_ref().unchecked_ref(), SAVE_DELAY_MS)
+        .unwrap_or(0);
+    cb.forget();
+    PENDING_SAVE.with(|p| *p.borrow_mut() = Some((handle, target, note)));
+}
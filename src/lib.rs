@@ -8,12 +8,18 @@ pub mod focus_stack;
 pub mod tauri_bridge;
 pub mod bat_book;
 pub mod annotations;
+pub mod custom_colormaps;
+pub mod file_corrections;
 pub mod file_identity;
+pub mod file_notes;
 pub mod format_time;
 pub mod opfs;
+pub mod playback_prefs;
 pub mod project;
+pub mod reference_lines;
 pub mod project_store;
 pub mod timeline;
+pub mod units;
 pub mod viewport;
 
 use leptos::prelude::*;
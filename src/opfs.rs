@@ -159,6 +159,13 @@ fn sync_noise_profile_from_state(state: crate::state::AppState) -> Option<crate:
     })
 }
 
+/// Seconds trimmed off the start of a file on import. Annotation sets are
+/// saved in original-file time and shifted by this on the way in and out.
+pub(crate) fn import_trim_secs(state: crate::state::AppState, file_idx: usize) -> f64 {
+    use leptos::prelude::WithUntracked;
+    state.files.with_untracked(|files| files.get(file_idx).map_or(0.0, |f| f.audio.trim_offset_secs()))
+}
+
 /// Produce YAML for a file-adjacent sidecar with `file_path` stripped from the identity.
 /// The sidecar sits next to the audio file, so the full path is redundant (and `filename` already
 /// stores the basename). Central annotations keep the full path for re-finding.
@@ -199,10 +206,11 @@ pub fn save_annotations(state: crate::state::AppState, file_idx: usize) {
     });
 
     let store = state.annotation_store.get_untracked();
-    let set = match store.sets.get(file_idx).and_then(|s| s.as_ref()) {
+    let mut set = match store.sets.get(file_idx).and_then(|s| s.as_ref()) {
         Some(s) => s.clone(),
         None => return,
     };
+    set.shift_times(import_trim_secs(state, file_idx));
 
     let key = opfs_key(&set.file_identity);
     let yaml = match yaml_serde::to_string(&set) {
@@ -288,10 +296,11 @@ pub fn save_sidecar_explicit(state: crate::state::AppState, file_idx: usize) {
     });
 
     let store = state.annotation_store.get_untracked();
-    let set = match store.sets.get(file_idx).and_then(|s| s.as_ref()) {
+    let mut set = match store.sets.get(file_idx).and_then(|s| s.as_ref()) {
         Some(s) => s.clone(),
         None => { state.show_error_toast("Failed to create annotation set"); return; }
     };
+    set.shift_times(import_trim_secs(state, file_idx));
 
     let sidecar_yaml = sidecar_yaml_without_full_path(&set);
 
@@ -316,9 +325,11 @@ pub fn save_annotations_to_opfs(state: crate::state::AppState, file_idx: usize)
 }
 
 /// Apply a loaded sidecar to the annotation store and restore NR profile to file settings.
-fn apply_loaded_sidecar(state: crate::state::AppState, file_idx: usize, loaded: crate::annotations::AnnotationSet) {
+fn apply_loaded_sidecar(state: crate::state::AppState, file_idx: usize, mut loaded: crate::annotations::AnnotationSet) {
     use leptos::prelude::Update;
 
+    loaded.shift_times(-import_trim_secs(state, file_idx));
+
     // If the sidecar has a noise profile, store it in the file's per-file settings.
     // Also restore cached peak values from sidecar metadata if not yet computed.
     let has_noise_profile = loaded.noise_profile.is_some();
@@ -0,0 +1,176 @@
+//! Remembered playback mode per file class.
+//!
+//! Files are classed by Nyquist: ultrasonic (above 24 kHz) or audible. Each
+//! class keeps the playback mode and parameters last used with it, so a bat
+//! file opens in e.g. heterodyne at the saved frequency while an audible file
+//! opens in straight playback. A file's own settings (kept in `FileSettings`
+//! when switching away) take precedence over its class default.
+
+use leptos::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::state::{AppState, PlaybackMode};
+
+/// Files sampled above this rate have ultrasonic content worth transforming.
+const ULTRASONIC_MIN_RATE: u32 = 48_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileClass {
+    Audible,
+    Ultrasonic,
+}
+
+impl FileClass {
+    pub fn of_sample_rate(sample_rate: u32) -> Self {
+        if sample_rate > ULTRASONIC_MIN_RATE {
+            FileClass::Ultrasonic
+        } else {
+            FileClass::Audible
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            FileClass::Audible => "Audible files",
+            FileClass::Ultrasonic => "Ultrasonic files",
+        }
+    }
+
+    fn storage_key(self) -> &'static str {
+        match self {
+            FileClass::Audible => "oversample_playback_pref_audible",
+            FileClass::Ultrasonic => "oversample_playback_pref_ultrasonic",
+        }
+    }
+}
+
+/// Playback mode and the parameters that go with it.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PlaybackPreference {
+    pub mode: PlaybackMode,
+    pub het_frequency: f64,
+    pub het_freq_auto: bool,
+    pub te_factor: f64,
+    pub ps_factor: f64,
+    pub pv_factor: f64,
+    pub zc_factor: f64,
+}
+
+impl PlaybackPreference {
+    /// The current playback mode and parameters.
+    pub fn capture(state: &AppState) -> Self {
+        Self {
+            mode: state.playback_mode.get_untracked(),
+            het_frequency: state.het_frequency.get_untracked(),
+            het_freq_auto: state.het_freq_auto.get_untracked(),
+            te_factor: state.te_factor.get_untracked(),
+            ps_factor: state.ps_factor.get_untracked(),
+            pv_factor: state.pv_factor.get_untracked(),
+            zc_factor: state.zc_factor.get_untracked(),
+        }
+    }
+
+    /// Switch to this mode, turning HFR on for a transform or off for
+    /// straight playback, and restore its parameters.
+    pub fn apply(&self, state: &AppState) {
+        let hfr = state.focus_stack.get_untracked().hfr_enabled();
+        if self.mode == PlaybackMode::Normal {
+            if hfr {
+                state.toggle_hfr();
+            }
+            state.playback_mode.set(PlaybackMode::Normal);
+        } else {
+            state.focus_stack.update(|s| s.set_saved_playback_mode(Some(self.mode)));
+            if !hfr {
+                state.toggle_hfr(); // enables HFR with the saved mode
+            }
+            state.playback_mode.set(self.mode);
+        }
+
+        state.het_freq_auto.set(self.het_freq_auto);
+        if !self.het_freq_auto {
+            state.het_frequency.set(self.het_frequency);
+        }
+        state.te_factor.set(self.te_factor);
+        state.ps_factor.set(self.ps_factor);
+        state.pv_factor.set(self.pv_factor);
+        state.zc_factor.set(self.zc_factor);
+    }
+
+    /// Short description, e.g. "Heterodyne 45.0 kHz".
+    pub fn summary(&self) -> String {
+        match self.mode {
+            PlaybackMode::Normal => "1:1 playback".into(),
+            PlaybackMode::Heterodyne if self.het_freq_auto => "Heterodyne (auto)".into(),
+            PlaybackMode::Heterodyne => format!("Heterodyne {:.1} kHz", self.het_frequency / 1000.0),
+            PlaybackMode::TimeExpansion => format!("Time expansion {}x", self.te_factor),
+            PlaybackMode::PitchShift => format!("Pitch shift {}x", self.ps_factor),
+            PlaybackMode::PhaseVocoder => format!("Phase vocoder {}x", self.pv_factor),
+            PlaybackMode::ZeroCrossing => format!("Zero crossing \u{00f7}{}", self.zc_factor),
+        }
+    }
+
+    /// The remembered preference for a class, if any.
+    pub fn load(class: FileClass) -> Option<Self> {
+        web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .and_then(|ls| ls.get_item(class.storage_key()).ok().flatten())
+            .and_then(|v| serde_json::from_str(&v).ok())
+    }
+
+    pub fn save(&self, class: FileClass) {
+        let Ok(json) = serde_json::to_string(self) else { return };
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = ls.set_item(class.storage_key(), &json);
+        }
+    }
+
+    pub fn forget(class: FileClass) {
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = ls.remove_item(class.storage_key());
+        }
+    }
+}
+
+/// Preference to apply when a file becomes current: the file's own last
+/// settings, else its class default. Audible files with no remembered
+/// preference get straight playback; ultrasonic ones are left as they are.
+pub fn preference_for_file(state: &AppState, index: usize) -> Option<PlaybackPreference> {
+    let (sample_rate, own) = state.files.with_untracked(|files| {
+        files.get(index).map(|f| (f.audio.sample_rate, f.settings.playback))
+    })?;
+    if own.is_some() {
+        return own;
+    }
+    let class = FileClass::of_sample_rate(sample_rate);
+    PlaybackPreference::load(class).or_else(|| {
+        (class == FileClass::Audible).then(|| PlaybackPreference {
+            mode: PlaybackMode::Normal,
+            ..PlaybackPreference::capture(state)
+        })
+    })
+}
+
+/// Keep the current file's class preference up to date as the user changes
+/// playback mode or parameters.
+pub fn track_playback_preference(state: AppState) {
+    Effect::new(move |_| {
+        let _ = (
+            state.playback_mode.get(),
+            state.het_frequency.get(),
+            state.het_freq_auto.get(),
+            state.te_factor.get(),
+            state.ps_factor.get(),
+            state.pv_factor.get(),
+            state.zc_factor.get(),
+        );
+        if !state.remember_playback_mode.get() {
+            return;
+        }
+        let Some(idx) = state.current_file_index.get_untracked() else { return };
+        let Some(sample_rate) = state.files.with_untracked(|files| files.get(idx).map(|f| f.audio.sample_rate)) else {
+            return;
+        };
+        PlaybackPreference::capture(&state).save(FileClass::of_sample_rate(sample_rate));
+    });
+}
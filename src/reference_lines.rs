@@ -0,0 +1,49 @@
+//! Reference frequency lines drawn across the spectrogram.
+//!
+//! A quick-ID aid: put a line at a target species' known peak frequency
+//! (e.g. 45 kHz for common pipistrelle) and see at a glance which calls sit
+//! on it. The lines and whether they're shown are remembered in localStorage.
+
+use serde::{Deserialize, Serialize};
+
+use crate::units::FreqUnit;
+
+const STORAGE_KEY: &str = "oversample_reference_lines";
+
+/// Frequency a newly added line starts at.
+pub const DEFAULT_REFERENCE_FREQ_HZ: f64 = 45_000.0;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ReferenceLine {
+    pub freq_hz: f64,
+    /// Short label drawn beside the line, e.g. "P. pip"; may be empty.
+    pub label: String,
+}
+
+impl ReferenceLine {
+    /// Text drawn on the line: the label, if any, and the frequency.
+    pub fn caption(&self, unit: FreqUnit) -> String {
+        let freq = unit.format(self.freq_hz);
+        if self.label.trim().is_empty() {
+            freq
+        } else {
+            format!("{} {}", self.label.trim(), freq)
+        }
+    }
+}
+
+/// The saved reference lines, or an empty list.
+pub fn load() -> Vec<ReferenceLine> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|ls| ls.get_item(STORAGE_KEY).ok().flatten())
+        .and_then(|v| serde_json::from_str(&v).ok())
+        .unwrap_or_default()
+}
+
+pub fn save(lines: &[ReferenceLine]) {
+    let Ok(json) = serde_json::to_string(lines) else { return };
+    if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+        let _ = ls.set_item(STORAGE_KEY, &json);
+    }
+}
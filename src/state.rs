@@ -1,7 +1,7 @@
 use leptos::prelude::*;
 use crate::audio::source::ChannelView;
 use crate::canvas::spectrogram_renderer::Colormap;
-use crate::canvas::flow::FlowAlgo;
+use crate::canvas::flow::{FlowAlgo, FlowShiftParams};
 use crate::annotations::AnnotationKind;
 use crate::types::{AudioData, PreviewImage, SpectrogramData};
 use crate::annotations::{AnnotationId, AnnotationStore, FileIdentity};
@@ -27,6 +27,33 @@ impl SidecarHashes {
     }
 }
 
+/// A species offered during identify-by-comparison, with the XC reference
+/// recording chosen for it once searched.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IdentifyCandidate {
+    pub name: String,
+    pub scientific_name: String,
+    /// XC number of the reference recording, once found.
+    pub reference: Option<u64>,
+    /// True once XC has been searched, whether or not anything was found.
+    pub searched: bool,
+}
+
+/// Identify-by-comparison session: XC reference recordings of species whose
+/// characteristic frequency matches the identified file, stepped through in
+/// the Diff view one candidate at a time.
+#[derive(Clone, Debug, PartialEq)]
+pub struct XcIdentify {
+    /// Name of the file being identified (indices shift as references load).
+    pub file_name: String,
+    pub char_freq_khz: f64,
+    pub candidates: Vec<IdentifyCandidate>,
+    /// Index into `candidates` of the one being compared.
+    pub current: usize,
+    /// Progress or problem with the current candidate.
+    pub status: Option<String>,
+}
+
 /// Overall verification result against reference hashes (XC sidecar or .batm).
 #[derive(Clone, Debug, Default, PartialEq)]
 pub enum VerifyOutcome {
@@ -56,6 +83,16 @@ pub struct FileSettings {
     pub noise_reduce_enabled: bool,
     pub noise_reduce_strength: f64,
     pub noise_reduce_floor: Option<crate::dsp::spectral_sub::NoiseFloor>,
+    /// DC offset (mono-mix mean) subtracted before analysis, playback and
+    /// export. None = no correction. The decoded audio itself is never modified.
+    pub dc_offset: Option<f32>,
+    /// Reconstruct clipped runs in the working copy used for the waveform
+    /// and playback. The spectrogram, analysis and export keep the original
+    /// samples.
+    pub declip: bool,
+    /// Playback mode and parameters last used with this file. Overrides the
+    /// remembered default for its file class.
+    pub playback: Option<crate::playback_prefs::PlaybackPreference>,
 }
 
 impl Default for FileSettings {
@@ -71,10 +108,21 @@ impl Default for FileSettings {
             noise_reduce_enabled: false,
             noise_reduce_strength: 0.6,
             noise_reduce_floor: None,
+            dc_offset: None,
+            declip: false,
+            playback: None,
         }
     }
 }
 
+/// Stable handle on a loaded file. Unlike its index it keeps pointing at the
+/// same file when others are closed or reordered.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FileKey {
+    pub name: String,
+    pub add_order: usize,
+}
+
 #[derive(Clone, Debug)]
 pub struct LoadedFile {
     pub name: String,
@@ -126,9 +174,22 @@ pub struct LoadedFile {
     pub min_display_freq: Option<f64>,
     /// Per-file vertical zoom: upper frequency bound in Hz. `None` = default (Nyquist).
     pub max_display_freq: Option<f64>,
+    /// Free-text reviewer note. Persisted by `file_notes` (`.notes.json` sidecar
+    /// on desktop, local storage in the browser).
+    pub notes: String,
+    /// Quality/content metrics for sorting the Files panel. None = not yet computed.
+    /// Filled lazily by `file_metrics::start_metrics_scan` when a metric sort is chosen.
+    pub metrics: Option<crate::audio::file_metrics::FileMetrics>,
+    /// All channels carried the same signal when checked at load, i.e. a mono
+    /// recording stored as stereo. Cleared once the file is collapsed to mono.
+    pub identical_channels: bool,
 }
 
 impl LoadedFile {
+    pub fn key(&self) -> FileKey {
+        FileKey { name: self.name.clone(), add_order: self.add_order }
+    }
+
     /// Get the recording start time as milliseconds since Unix epoch, if available.
     ///
     /// Sources (in priority order):
@@ -142,19 +203,22 @@ impl LoadedFile {
     /// Get the recording start time and its source description.
     ///
     /// Returns `(epoch_ms, source_label)` where `source_label` describes the
-    /// origin: "GUANO Timestamp" or "File modified date (approx.)".
+    /// origin: "GUANO Timestamp" or "File modified date (approx.)". For a file
+    /// trimmed on import this is the time of its first kept sample.
     pub fn recording_start_info(&self) -> Option<(f64, &'static str)> {
+        let trim_ms = self.audio.trim_offset_secs() * 1000.0;
         // Try GUANO Timestamp first
         if let Some(ref guano) = self.audio.metadata.guano {
             if let Some((_, ts)) = guano.fields.iter().find(|(k, _)| k == "Timestamp") {
                 if let Some(epoch) = parse_iso8601_to_epoch_ms(ts) {
-                    return Some((epoch, "GUANO Timestamp"));
+                    return Some((epoch + trim_ms, "GUANO Timestamp"));
                 }
             }
         }
         // Fallback: file last-modified minus duration ≈ recording start
+        let original_secs = self.audio.untrimmed_source().duration_secs();
         self.last_modified_ms
-            .map(|lm| (lm - self.audio.duration_secs * 1000.0, "File modified date (approx.)"))
+            .map(|lm| (lm - original_secs * 1000.0 + trim_ms, "File modified date (approx.)"))
     }
 }
 
@@ -167,7 +231,7 @@ fn parse_iso8601_to_epoch_ms(s: &str) -> Option<f64> {
     if ms.is_nan() { None } else { Some(ms) }
 }
 
-#[derive(Clone, Copy, Debug, Default)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
 pub struct Selection {
     pub time_start: f64,
     pub time_end: f64,
@@ -177,7 +241,7 @@ pub struct Selection {
     pub freq_high: Option<f64>,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
 pub enum PlaybackMode {
     Normal,
     Heterodyne,
@@ -192,6 +256,7 @@ pub enum ExportFormat {
     #[default]
     Wav,
     Mp4,
+    Png,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
@@ -281,7 +346,7 @@ impl SpectrogramDisplay {
 }
 
 // FlowColorScheme is defined in oversample-core and re-exported here for backward compatibility.
-pub use oversample_core::types::FlowColorScheme;
+pub use oversample_core::types::{FlowBlendMode, FlowColorScheme};
 
 // Resonator layout lives in the DSP crate — it needs to be the same type used
 // by compute_resonator_columns. Re-exported so UI code can reference it via
@@ -327,6 +392,46 @@ impl RightSidebarTab {
     ];
 }
 
+/// Which time span the Harmonics panel analyses.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum HarmonicsWindow {
+    /// The entire file (multi-pulse passes dilute the metrics).
+    #[default]
+    WholeFile,
+    /// The current transient selection's time range.
+    Selection,
+    /// The pulse selected in the Pulses panel.
+    Pulse,
+}
+
+impl HarmonicsWindow {
+    pub const ALL: [HarmonicsWindow; 3] = [Self::WholeFile, Self::Selection, Self::Pulse];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::WholeFile => "Whole file",
+            Self::Selection => "Selection",
+            Self::Pulse => "Selected pulse",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::WholeFile => "file",
+            Self::Selection => "selection",
+            Self::Pulse => "pulse",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "selection" => Self::Selection,
+            "pulse" => Self::Pulse,
+            _ => Self::WholeFile,
+        }
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
 pub enum FilterQuality {
     #[default]
@@ -362,6 +467,17 @@ pub enum SpectrogramHandle {
     HetCenter,     // HET center freq
     HetBandUpper,  // HET upper band edge
     HetBandLower,  // HET lower band edge
+    ExtraBandUpper(usize), // Extra focus band upper boundary (index into extra_focus_bands)
+    ExtraBandLower(usize), // Extra focus band lower boundary
+}
+
+impl SpectrogramHandle {
+    /// True for handles that belong to the frequency focus (main or extra bands).
+    pub fn is_band_ff(self) -> bool {
+        matches!(self,
+            SpectrogramHandle::BandFfUpper | SpectrogramHandle::BandFfLower | SpectrogramHandle::BandFfMiddle
+            | SpectrogramHandle::ExtraBandUpper(_) | SpectrogramHandle::ExtraBandLower(_))
+    }
 }
 
 /// How the Play button initiates playback.
@@ -381,6 +497,112 @@ impl PlayStartMode {
     }
 }
 
+/// Where the "play from here" marker sits in the view.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum PlayAnchor {
+    /// A short lead-in from the left edge (10% of the view).
+    #[default]
+    NearStart,
+    /// The left edge of the view.
+    Start,
+    /// The centre of the view.
+    Centre,
+    /// Wherever the view was last clicked with the Hand tool.
+    Click,
+}
+
+impl PlayAnchor {
+    pub const ALL: [PlayAnchor; 4] = [Self::NearStart, Self::Start, Self::Centre, Self::Click];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::NearStart => "Near start of view",
+            Self::Start => "Start of view",
+            Self::Centre => "Centre of view",
+            Self::Click => "Where clicked",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::NearStart => "near_start",
+            Self::Start => "start",
+            Self::Centre => "centre",
+            Self::Click => "click",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "start" => Self::Start,
+            "centre" => Self::Centre,
+            "click" => Self::Click,
+            _ => Self::NearStart,
+        }
+    }
+
+    /// Marker position as a fraction of the view width. `Click` starts at
+    /// the default until the first click moves it.
+    pub fn fraction(self) -> f64 {
+        match self {
+            Self::NearStart | Self::Click => crate::viewport::PLAY_FROM_HERE_FRACTION,
+            Self::Start => 0.0,
+            Self::Centre => 0.5,
+        }
+    }
+}
+
+/// STFT parameters behind the spectrogram at one LOD, for reporting with
+/// figures and measurements.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpectParams {
+    pub fft_size: usize,
+    /// Hop between columns, in samples.
+    pub hop: usize,
+    pub sample_rate: u32,
+}
+
+impl SpectParams {
+    /// Analysis window; every spectrogram path uses Hann.
+    pub const WINDOW: &'static str = "Hann";
+
+    pub fn for_lod(mode: FftMode, lod: u8, sample_rate: u32) -> Self {
+        Self {
+            fft_size: mode.fft_for_lod(lod),
+            hop: crate::canvas::tile_cache::LOD_CONFIGS[lod as usize].hop_size,
+            sample_rate,
+        }
+    }
+
+    /// Bin spacing in Hz.
+    pub fn freq_resolution(&self) -> f64 {
+        self.sample_rate as f64 / self.fft_size.max(1) as f64
+    }
+
+    /// Column spacing in seconds.
+    pub fn time_resolution(&self) -> f64 {
+        self.hop as f64 / self.sample_rate.max(1) as f64
+    }
+
+    /// Window length in seconds.
+    pub fn window_secs(&self) -> f64 {
+        self.fft_size as f64 / self.sample_rate.max(1) as f64
+    }
+
+    /// One-line summary, e.g. "FFT 1024 (Hann, 5.33 ms), hop 512 (2.67 ms), 187.5 Hz/bin".
+    pub fn summary(&self) -> String {
+        format!(
+            "FFT {} ({}, {:.2} ms), hop {} ({:.2} ms), {:.1} Hz/bin",
+            self.fft_size,
+            Self::WINDOW,
+            self.window_secs() * 1000.0,
+            self.hop,
+            self.time_resolution() * 1000.0,
+            self.freq_resolution(),
+        )
+    }
+}
+
 /// What happens when the Record button is pressed.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum RecordMode {
@@ -474,6 +696,7 @@ pub enum MainView {
     Flow,
     Chromagram,
     Resonators,
+    Diff,
 }
 
 impl MainView {
@@ -486,6 +709,7 @@ impl MainView {
             Self::Flow => "Flow",
             Self::Chromagram => "Chromagram",
             Self::Resonators => "Resonators",
+            Self::Diff => "Diff (A − B)",
         }
     }
 
@@ -498,6 +722,7 @@ impl MainView {
             Self::Flow => "Flow",
             Self::Chromagram => "Chroma",
             Self::Resonators => "Reson",
+            Self::Diff => "Diff",
         }
     }
 
@@ -514,6 +739,7 @@ impl MainView {
         Self::Flow,
         Self::Chromagram,
         Self::Resonators,
+        Self::Diff,
     ];
 }
 
@@ -791,6 +1017,20 @@ pub struct Bookmark {
     pub time: f64,
 }
 
+impl Bookmark {
+    /// Bookmarks for a file's embedded WAV cue points, in time order.
+    pub fn from_wav_markers(markers: &[crate::types::WavMarker], sample_rate: u32) -> Vec<Bookmark> {
+        if sample_rate == 0 {
+            return Vec::new();
+        }
+        let mut bookmarks: Vec<Bookmark> = markers.iter()
+            .map(|m| Bookmark { time: m.position as f64 / sample_rate as f64 })
+            .collect();
+        bookmarks.sort_by(|a, b| a.time.total_cmp(&b.time));
+        bookmarks
+    }
+}
+
 
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
 pub enum ChromaColormap {
@@ -908,141 +1148,719 @@ impl ShieldStyle {
     }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum FileSortMode {
+/// Colours for frequency marker ticks and solid flags, one per digit of
+/// the frequency. The three-band "Resistor bands" flag style always uses
+/// the resistor code.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum MarkerColors {
+    /// Resistor colour code (0 = black, 1 = brown, ..., 9 = white).
     #[default]
-    GroupedAdded,
-    AddOrder,
-    ByName,
-    ByDate,
-    ByMetadataDate,
-    Grouped,
-    ByDateGrouped,
+    Resistor,
+    /// Okabe–Ito based palette, distinguishable with colour blindness.
+    ColorblindSafe,
+    /// Plain grey for every marker.
+    Mono,
 }
 
-impl FileSortMode {
-    pub const ALL: &[FileSortMode] = &[
-        Self::GroupedAdded,
-        Self::AddOrder,
-        Self::ByName,
-        Self::ByDate,
-        Self::ByMetadataDate,
-        Self::Grouped,
-        Self::ByDateGrouped,
-    ];
+impl MarkerColors {
+    pub const ALL: [MarkerColors; 3] = [Self::Resistor, Self::ColorblindSafe, Self::Mono];
 
     pub fn label(self) -> &'static str {
         match self {
-            Self::GroupedAdded => "Grouped + added",
-            Self::AddOrder => "Added",
-            Self::ByName => "Name",
-            Self::ByDate => "Date",
-            Self::ByMetadataDate => "Meta date",
-            Self::Grouped => "Grouped",
-            Self::ByDateGrouped => "Date, grouped",
+            Self::Resistor => "Resistor code",
+            Self::ColorblindSafe => "Colour-blind safe",
+            Self::Mono => "Monochrome",
         }
     }
-}
 
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum LeftSidebarTab {
-    #[default]
-    Files,
-    Project,
-    Settings,
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Resistor => "resistor",
+            Self::ColorblindSafe => "colorblind",
+            Self::Mono => "mono",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "colorblind" => Self::ColorblindSafe,
+            "mono" => Self::Mono,
+            _ => Self::Resistor,
+        }
+    }
+
+    /// Colour for a digit 0–9.
+    pub fn digit_color(self, digit: u32) -> [u8; 3] {
+        let d = (digit % 10) as usize;
+        match self {
+            Self::Resistor => crate::canvas::colors::RESISTOR_BANDS[d],
+            Self::ColorblindSafe => crate::canvas::colors::COLORBLIND_BANDS[d],
+            Self::Mono => [200, 200, 200],
+        }
+    }
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum StatusLevel {
+/// Interface theme. Both themes are dark; high contrast uses black panels,
+/// white text and solid borders, and makes canvas labels and marker lines
+/// fully opaque.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum UiTheme {
+    /// High contrast when the system asks for more contrast
+    /// (`prefers-contrast: more` or forced colours), standard otherwise.
     #[default]
-    Error,
-    Info,
+    Auto,
+    Standard,
+    HighContrast,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum MicMode {
-    Auto,
-    Browser,
-    Cpal,
-    RawUsb,
+impl UiTheme {
+    pub const ALL: [UiTheme; 3] = [Self::Auto, Self::Standard, Self::HighContrast];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Follow system",
+            Self::Standard => "Standard",
+            Self::HighContrast => "High contrast",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Standard => "standard",
+            Self::HighContrast => "high_contrast",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "standard" => Self::Standard,
+            "high_contrast" => Self::HighContrast,
+            _ => Self::Auto,
+        }
+    }
+
+    /// Whether the high-contrast styling applies, given the system preference.
+    pub fn is_high_contrast(self, system_prefers_contrast: bool) -> bool {
+        match self {
+            Self::Auto => system_prefers_contrast,
+            Self::Standard => false,
+            Self::HighContrast => true,
+        }
+    }
 }
 
-/// Playback mode for live listening (like PlaybackMode but without TimeExpansion).
+/// Choices for the spacing of labelled frequency markers (kHz); 0 = pick
+/// from the visible range.
+pub const FREQ_MARKER_INTERVALS_KHZ: [f64; 7] = [0.0, 1.0, 2.0, 5.0, 10.0, 20.0, 50.0];
+
+/// Fill for the part of the spectrogram above the file's Nyquist frequency,
+/// where there is no data (only reachable with "Allow above Nyquist").
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum ListenMode {
+pub enum NoDataFill {
+    /// Diagonal hatching.
     #[default]
-    Heterodyne,
-    PitchShift,
-    PhaseVocoder,
-    ZeroCrossing,
-    Normal,
-    ReadyMic,
-}
-
-/// Microphone acquisition strategy.
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum MicStrategy {
-    Ask,
-    Selected,
-    Browser,
+    Hatch,
+    /// Flat grey tint.
+    Tint,
+    /// Plain background color.
     None,
 }
 
-/// Which backend is handling mic audio.
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum MicBackend {
-    Browser,
-    Cpal,
-    RawUsb,
+impl NoDataFill {
+    pub const ALL: [NoDataFill; 3] = [Self::Hatch, Self::Tint, Self::None];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Hatch => "Hatched",
+            Self::Tint => "Tinted",
+            Self::None => "None",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Hatch => "hatch",
+            Self::Tint => "tint",
+            Self::None => "none",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "tint" => Self::Tint,
+            "none" => Self::None,
+            _ => Self::Hatch,
+        }
+    }
 }
 
-/// State of mic acquisition lifecycle.
+/// How closely spaced the time-axis ticks (and gridlines) are.
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum MicAcquisitionState {
+pub enum TimeTickDensity {
+    Sparse,
     #[default]
-    Idle,
-    AwaitingChoice,
-    Acquiring,
-    Ready,
-    Failed,
+    Normal,
+    Dense,
 }
 
-/// Pending mic action (what to do once mic is acquired).
-#[derive(Clone, Copy, Debug, PartialEq)]
-pub enum MicPendingAction {
-    Listen,
-    Record,
-}
+impl TimeTickDensity {
+    pub const ALL: [TimeTickDensity; 3] = [Self::Sparse, Self::Normal, Self::Dense];
 
-/// Whether a recording is ready to begin.
-#[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum RecordReadyState {
-    #[default]
-    None,
-    AwaitingConfirmation,
-    Confirmed,
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Sparse => "Sparse",
+            Self::Normal => "Normal",
+            Self::Dense => "Dense",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Sparse => "sparse",
+            Self::Normal => "normal",
+            Self::Dense => "dense",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "sparse" => Self::Sparse,
+            "dense" => Self::Dense,
+            _ => Self::Normal,
+        }
+    }
+
+    /// Minimum gap in pixels between labelled ticks.
+    pub fn spacing_px(self) -> f64 {
+        match self {
+            Self::Sparse => 160.0,
+            Self::Normal => crate::canvas::time_markers::DEFAULT_TICK_SPACING_PX,
+            Self::Dense => 60.0,
+        }
+    }
 }
 
-/// Mono or stereo channel mode for mic recording.
+/// Reference level for the dB readout at the cursor.
 #[derive(Clone, Copy, Debug, PartialEq, Default)]
-pub enum ChannelMode {
+pub enum DbReference {
+    /// dB relative to digital full scale (a full-scale sine reads 0 dBFS).
     #[default]
-    Mono,
-    Stereo,
+    FullScale,
+    /// dB relative to the loudest bin in the file's spectrogram.
+    Peak,
+    /// No level readout.
+    Off,
 }
 
-/// Information about the selected mic device.
-#[derive(Clone, Debug)]
-pub struct MicDeviceInfo {
-    pub name: String,
-    pub connection_type: String,
-    pub supported_rates: Vec<u32>,
-    pub supported_bit_depths: Vec<u16>,
-    pub max_channels: u16,
-}
+impl DbReference {
+    pub const ALL: [DbReference; 3] = [Self::FullScale, Self::Peak, Self::Off];
 
-/// GPS location fix for embedding in recording GUANO metadata.
-#[derive(Clone, Debug)]
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::FullScale => "dB full scale",
+            Self::Peak => "dB re. file peak",
+            Self::Off => "Off",
+        }
+    }
+
+    /// Unit suffix shown after the value.
+    pub fn unit(self) -> &'static str {
+        match self {
+            Self::FullScale => "dBFS",
+            Self::Peak => "dB re. peak",
+            Self::Off => "",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::FullScale => "fullscale",
+            Self::Peak => "peak",
+            Self::Off => "off",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "peak" => Self::Peak,
+            "off" => Self::Off,
+            _ => Self::FullScale,
+        }
+    }
+}
+
+/// How the metadata panel treats fields carried by both the xeno-canto
+/// record and the file's embedded GUANO metadata (species, position, date).
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum MetadataOverlap {
+    /// Keep both sections and add a cross-check of the shared fields.
+    #[default]
+    ShowBoth,
+    /// Hide the GUANO copy of shared fields, noting its value where it differs.
+    PreferXc,
+    /// Hide the xeno-canto copy of shared fields, noting its value where it differs.
+    PreferGuano,
+}
+
+impl MetadataOverlap {
+    pub const ALL: [MetadataOverlap; 3] = [Self::ShowBoth, Self::PreferXc, Self::PreferGuano];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::ShowBoth => "Show both",
+            Self::PreferXc => "Prefer xeno-canto",
+            Self::PreferGuano => "Prefer GUANO",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::ShowBoth => "both",
+            Self::PreferXc => "xc",
+            Self::PreferGuano => "guano",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "xc" => Self::PreferXc,
+            "guano" => Self::PreferGuano,
+            _ => Self::ShowBoth,
+        }
+    }
+}
+
+/// Memory budget for the spectrogram tile caches, as a multiple of the
+/// built-in per-cache budgets (~1 GB total at `Standard`).
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum TileMemoryBudget {
+    Low,
+    Reduced,
+    #[default]
+    Standard,
+    High,
+}
+
+impl TileMemoryBudget {
+    pub const ALL: [TileMemoryBudget; 4] = [Self::Low, Self::Reduced, Self::Standard, Self::High];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Low => "~256 MB",
+            Self::Reduced => "~512 MB",
+            Self::Standard => "~1 GB",
+            Self::High => "~2 GB",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Reduced => "reduced",
+            Self::Standard => "standard",
+            Self::High => "high",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "low" => Self::Low,
+            "reduced" => Self::Reduced,
+            "high" => Self::High,
+            _ => Self::Standard,
+        }
+    }
+
+    /// Multiplier applied to every tile cache's default budget.
+    pub fn scale(self) -> f64 {
+        match self {
+            Self::Low => 0.25,
+            Self::Reduced => 0.5,
+            Self::Standard => 1.0,
+            Self::High => 2.0,
+        }
+    }
+}
+
+/// Marks left on the spectrogram at the loudest frequency under the
+/// playhead as it moves, tracing the call's shape while it plays.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum PlayheadTrail {
+    #[default]
+    Off,
+    /// Marks fade out over `playhead_trail_secs` of playback.
+    Fading,
+    /// Marks stay until playback restarts or the file changes.
+    Persistent,
+}
+
+impl PlayheadTrail {
+    pub const ALL: [PlayheadTrail; 3] = [Self::Off, Self::Fading, Self::Persistent];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Fading => "Fading",
+            Self::Persistent => "Persistent",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Fading => "fading",
+            Self::Persistent => "persistent",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "fading" => Self::Fading,
+            "persistent" => Self::Persistent,
+            _ => Self::Off,
+        }
+    }
+}
+
+/// How the Frequency Focus dimming treats the selection box, so calls inside
+/// the focused band stay easy to see while a selection is placed.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum SelectionFocusDim {
+    /// Dim the selection like the rest of the canvas.
+    Always,
+    /// Drop the dimming while a selection is being dragged.
+    #[default]
+    HideWhileSelecting,
+    /// Never dim inside the selection box.
+    ClearInside,
+}
+
+impl SelectionFocusDim {
+    pub const ALL: [SelectionFocusDim; 3] = [Self::Always, Self::HideWhileSelecting, Self::ClearInside];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Always => "Always dim",
+            Self::HideWhileSelecting => "Hide while selecting",
+            Self::ClearInside => "Clear inside selection",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Always => "always",
+            Self::HideWhileSelecting => "hide_while_selecting",
+            Self::ClearInside => "clear_inside",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "always" => Self::Always,
+            "clear_inside" => Self::ClearInside,
+            _ => Self::HideWhileSelecting,
+        }
+    }
+}
+
+/// How far ahead of the viewport tiles are computed in the background.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum TilePrefetch {
+    /// Only compute visible tiles.
+    Off,
+    #[default]
+    Normal,
+    /// Look twice as far ahead (smoother playback, more memory).
+    Aggressive,
+}
+
+impl TilePrefetch {
+    pub const ALL: [TilePrefetch; 3] = [Self::Off, Self::Normal, Self::Aggressive];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Off => "Off",
+            Self::Normal => "Normal",
+            Self::Aggressive => "Aggressive",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Off => "off",
+            Self::Normal => "normal",
+            Self::Aggressive => "aggressive",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "off" => Self::Off,
+            "aggressive" => Self::Aggressive,
+            _ => Self::Normal,
+        }
+    }
+
+    /// Multiplier for the look-ahead time and tile count (0 = disabled).
+    pub fn scale(self) -> f64 {
+        match self {
+            Self::Off => 0.0,
+            Self::Normal => 1.0,
+            Self::Aggressive => 2.0,
+        }
+    }
+}
+
+/// How background tile preloading shares the tile workers with the tiles
+/// currently on screen.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum TileScheduling {
+    /// Background preload waits while visible tiles are missing and never
+    /// takes the last few worker slots, so scrolling stays smooth.
+    #[default]
+    VisibleFirst,
+    /// Background and visible tiles compete for workers equally.
+    Sequential,
+}
+
+impl TileScheduling {
+    pub const ALL: [TileScheduling; 2] = [Self::VisibleFirst, Self::Sequential];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::VisibleFirst => "Visible first",
+            Self::Sequential => "Sequential",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::VisibleFirst => "visible_first",
+            Self::Sequential => "sequential",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "sequential" => Self::Sequential,
+            _ => Self::VisibleFirst,
+        }
+    }
+}
+
+/// Resolution of the file-list preview thumbnails, relative to the size
+/// they're drawn at on this display.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum PreviewDetail {
+    /// Half resolution (fastest to generate for large batches).
+    Low,
+    /// One preview pixel per device pixel.
+    #[default]
+    Normal,
+    /// Twice the device resolution (sharper when the preview stands in
+    /// for the main view while tiles load).
+    High,
+}
+
+impl PreviewDetail {
+    pub const ALL: [PreviewDetail; 3] = [Self::Low, Self::Normal, Self::High];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Low => "Low",
+            Self::Normal => "Normal",
+            Self::High => "High",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Normal => "normal",
+            Self::High => "high",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "low" => Self::Low,
+            "high" => Self::High,
+            _ => Self::Normal,
+        }
+    }
+
+    pub fn scale(self) -> f64 {
+        match self {
+            Self::Low => 0.5,
+            Self::Normal => 1.0,
+            Self::High => 2.0,
+        }
+    }
+
+    /// Preview size in pixels for a thumbnail `css_width` CSS pixels wide
+    /// at `device_pixel_ratio`. Never below the old fixed 256×128 at Normal,
+    /// since the preview also stands in for the main spectrogram.
+    pub fn preview_size(self, css_width: f64, device_pixel_ratio: f64) -> (u32, u32) {
+        let dpr = device_pixel_ratio.max(1.0);
+        let w = ((css_width * dpr).max(256.0) * self.scale()).clamp(128.0, 2048.0);
+        let h = (128.0 * dpr * self.scale()).clamp(64.0, 512.0);
+        (w.round() as u32, h.round() as u32)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum FileSortMode {
+    #[default]
+    GroupedAdded,
+    AddOrder,
+    ByName,
+    ByDate,
+    ByMetadataDate,
+    Grouped,
+    ByDateGrouped,
+    ByDuration,
+    ByWsnr,
+    ByPeakFreq,
+    ByCalls,
+}
+
+impl FileSortMode {
+    pub const ALL: &[FileSortMode] = &[
+        Self::GroupedAdded,
+        Self::AddOrder,
+        Self::ByName,
+        Self::ByDate,
+        Self::ByMetadataDate,
+        Self::Grouped,
+        Self::ByDateGrouped,
+        Self::ByDuration,
+        Self::ByWsnr,
+        Self::ByPeakFreq,
+        Self::ByCalls,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::GroupedAdded => "Grouped + added",
+            Self::AddOrder => "Added",
+            Self::ByName => "Name",
+            Self::ByDate => "Date",
+            Self::ByMetadataDate => "Meta date",
+            Self::Grouped => "Grouped",
+            Self::ByDateGrouped => "Date, grouped",
+            Self::ByDuration => "Duration",
+            Self::ByWsnr => "wSNR",
+            Self::ByPeakFreq => "Peak freq",
+            Self::ByCalls => "Has calls",
+        }
+    }
+
+    /// True for modes that sort on `LoadedFile::metrics` (computed in the background).
+    pub fn needs_metrics(self) -> bool {
+        matches!(self, Self::ByWsnr | Self::ByPeakFreq | Self::ByCalls)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum LeftSidebarTab {
+    #[default]
+    Files,
+    Project,
+    Settings,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum StatusLevel {
+    #[default]
+    Error,
+    Info,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MicMode {
+    Auto,
+    Browser,
+    Cpal,
+    RawUsb,
+}
+
+/// Playback mode for live listening (like PlaybackMode but without TimeExpansion).
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ListenMode {
+    #[default]
+    Heterodyne,
+    PitchShift,
+    PhaseVocoder,
+    ZeroCrossing,
+    Normal,
+    ReadyMic,
+}
+
+/// Microphone acquisition strategy.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MicStrategy {
+    Ask,
+    Selected,
+    Browser,
+    None,
+}
+
+/// Which backend is handling mic audio.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MicBackend {
+    Browser,
+    Cpal,
+    RawUsb,
+}
+
+/// State of mic acquisition lifecycle.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum MicAcquisitionState {
+    #[default]
+    Idle,
+    AwaitingChoice,
+    Acquiring,
+    Ready,
+    Failed,
+}
+
+/// Pending mic action (what to do once mic is acquired).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MicPendingAction {
+    Listen,
+    Record,
+}
+
+/// Whether a recording is ready to begin.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum RecordReadyState {
+    #[default]
+    None,
+    AwaitingConfirmation,
+    Confirmed,
+}
+
+/// Mono or stereo channel mode for mic recording.
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum ChannelMode {
+    #[default]
+    Mono,
+    Stereo,
+}
+
+/// Information about the selected mic device.
+#[derive(Clone, Debug)]
+pub struct MicDeviceInfo {
+    pub name: String,
+    pub connection_type: String,
+    pub supported_rates: Vec<u32>,
+    pub supported_bit_depths: Vec<u16>,
+    pub max_channels: u16,
+}
+
+/// GPS location fix for embedding in recording GUANO metadata.
+#[derive(Clone, Debug)]
 pub struct GpsLocation {
     pub latitude: f64,
     pub longitude: f64,
@@ -1054,6 +1872,8 @@ pub struct GpsLocation {
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum LoadingStage {
+    /// Waiting for a free load slot (see `max_concurrent_loads`).
+    Queued,
     Decoding,
     Preview,
     Spectrogram(u16), // 0–100 %
@@ -1061,6 +1881,40 @@ pub enum LoadingStage {
     Streaming,
 }
 
+/// Default number of files decoded at the same time.
+pub const DEFAULT_MAX_CONCURRENT_LOADS: usize = 4;
+/// Upper bound for the concurrent load setting.
+pub const MAX_CONCURRENT_LOADS_LIMIT: usize = 16;
+/// Default length (seconds) up to which files load in a single pass.
+pub const DEFAULT_SINGLE_PASS_MAX_SECS: f64 = 5.0;
+/// Upper bound for the single-pass length setting; the full spectrogram of
+/// a file is held in memory, so this stays small.
+pub const SINGLE_PASS_MAX_SECS_LIMIT: f64 = 60.0;
+/// Default level above the noise floor (dB) that counts as activity when
+/// trimming silence on import.
+pub const DEFAULT_IMPORT_TRIM_THRESHOLD_DB: f64 = 12.0;
+/// Default audio kept either side of the active region when trimming (ms).
+pub const DEFAULT_IMPORT_TRIM_PAD_MS: f64 = 250.0;
+/// Steepest display pre-emphasis slope offered (dB/octave).
+pub const MAX_PRE_EMPHASIS_DB_OCT: f64 = 12.0;
+/// Display pre-emphasis starts this many octaves below Nyquist; rows under
+/// the corner are left as they are.
+pub const PRE_EMPHASIS_CORNER_OCTAVES: f64 = 4.0;
+/// Zoom limits offered, as the widest an analysis window may be drawn (px).
+pub const ZOOM_LIMIT_WINDOW_PX: [f64; 4] = [50.0, 100.0, 200.0, 400.0];
+/// Opacity of the dimming outside the Frequency Focus band.
+pub const DEFAULT_FOCUS_DIM_OPACITY: f64 = 0.45;
+/// Range of horizontal scales offered for whole-recording PNG exports (px/s).
+pub const MIN_FULL_EXPORT_PX_PER_SEC: f64 = 10.0;
+pub const MAX_FULL_EXPORT_PX_PER_SEC: f64 = 20_000.0;
+/// Time allowed for a whole demo or URL download before it is abandoned (s).
+pub const DEFAULT_FETCH_TIMEOUT_SECS: u32 = 60;
+pub const MIN_FETCH_TIMEOUT_SECS: u32 = 5;
+pub const MAX_FETCH_TIMEOUT_SECS: u32 = 600;
+/// Retries after a failed fetch.
+pub const DEFAULT_FETCH_RETRIES: u32 = 1;
+pub const MAX_FETCH_RETRIES: u32 = 3;
+
 #[derive(Clone, Debug)]
 pub struct LoadingEntry {
     pub id: u64,
@@ -1075,18 +1929,31 @@ pub struct AppState {
     pub files: RwSignal<Vec<LoadedFile>>,
     pub current_file_index: RwSignal<Option<usize>>,
     pub file_sort_mode: RwSignal<FileSortMode>,
+    /// Text filter for the file list (empty = show all). The current file is
+    /// always listed.
+    pub file_filter: RwSignal<String>,
+    /// Whether the file list filter also matches species and quality metadata (persisted).
+    pub file_filter_metadata: RwSignal<bool>,
     pub show_file_previews: RwSignal<bool>,
+    /// Brightness boost for file-list preview thumbnails (0.0 = as computed, 1.0 = max).
+    pub file_preview_brightness: RwSignal<f32>,
     pub selection: RwSignal<Option<Selection>>,
     pub last_selection: RwSignal<Option<Selection>>,
     pub playback_mode: RwSignal<PlaybackMode>,
     pub het_frequency: RwSignal<f64>,
     pub te_factor: RwSignal<f64>,
     pub zoom_level: RwSignal<f64>,
+    /// Widest an analysis window may be drawn (px) before zooming in stops,
+    /// None = only the fixed `viewport::MAX_ZOOM` (persisted).
+    pub zoom_limit_px: RwSignal<Option<f64>>,
     pub scroll_offset: RwSignal<f64>,
     pub is_playing: RwSignal<bool>,
     /// True when playback is frozen waiting for streaming chunks to decode.
     /// Drives the "Buffering…" toast and pauses the playhead animation.
     pub is_buffering: RwSignal<bool>,
+    /// True while heterodyne/ZC playback output is being limited to avoid
+    /// clipping. Drives the "Limiting" indicator.
+    pub output_limiting: RwSignal<bool>,
     pub playhead_time: RwSignal<f64>,
     pub active_playback_selection: RwSignal<Option<Selection>>,
     pub loading_files: RwSignal<Vec<LoadingEntry>>,
@@ -1094,7 +1961,15 @@ pub struct AppState {
     pub ps_factor: RwSignal<f64>,
     pub pv_factor: RwSignal<f64>,
     pub pv_hq: RwSignal<bool>,
+    /// Time expansion resamples to the output rate with a windowed-sinc
+    /// interpolator instead of leaving it to the browser.
+    pub te_hq: RwSignal<bool>,
     pub zc_factor: RwSignal<f64>,
+    /// ZC chart dot mode: 0 = one dot per 1 ms bin, N > 0 = one dot per N
+    /// cycles (Anabat-style division ratio).
+    pub zc_dot_division: RwSignal<u32>,
+    /// Window length for the exported ZC frequency track, in ms.
+    pub zc_track_window_ms: RwSignal<f64>,
     pub het_interacting: RwSignal<bool>,
     pub is_dragging: RwSignal<bool>,
     /// True while any pointer button is held down on the spectrogram canvas.
@@ -1109,17 +1984,50 @@ pub struct AppState {
     pub flow_gate: RwSignal<f32>,
     pub flow_opacity: RwSignal<f32>,
     pub flow_shift_gain: RwSignal<f32>,
+    /// Half-width in bins of the window compared between columns (Optical,
+    /// Centroid). Persisted, as is the max displacement below.
+    pub flow_radius: RwSignal<usize>,
+    /// Largest per-column shift in bins the Optical search tries.
+    pub flow_max_displacement: RwSignal<usize>,
     pub flow_color_gamma: RwSignal<f32>,
     pub flow_color_scheme: RwSignal<FlowColorScheme>,
+    pub flow_blend_mode: RwSignal<FlowBlendMode>,
     pub min_display_freq: RwSignal<Option<f64>>,
     pub max_display_freq: RwSignal<Option<f64>>,
+    /// Draw low frequencies at the top of the spectrogram and everything that
+    /// shares its frequency axis. Mirrors `viewport::freq_axis_flipped`; set
+    /// via `set_freq_axis_flipped` (persisted).
+    pub freq_axis_flipped: RwSignal<bool>,
+    /// Frequency range (lo, hi Hz) newly loaded files open cropped to, instead
+    /// of the full 0–Nyquist range. None = full range. Persisted.
+    pub default_display_range: RwSignal<Option<(f64, f64)>>,
+    /// Let the frequency axis zoom/pan past the file's Nyquist (persisted).
+    /// Useful with a shift-up (multiply) playback factor and for annotating.
+    pub freq_above_nyquist: RwSignal<bool>,
     pub mouse_freq: RwSignal<Option<f64>>,
     pub mouse_canvas_x: RwSignal<f64>,
     pub mouse_in_label_area: RwSignal<bool>,
     pub label_hover_opacity: RwSignal<f64>,
     pub follow_cursor: RwSignal<bool>,
+    /// Resume playback where it was last stopped in each file (persisted).
+    pub resume_playback: RwSignal<bool>,
+    /// Re-apply the last-used playback mode per file class (ultrasonic vs
+    /// audible) when opening a file (persisted).
+    pub remember_playback_mode: RwSignal<bool>,
+    /// Last stopped playhead time per loaded file. Session only.
+    pub playback_positions: RwSignal<std::collections::HashMap<FileKey, f64>>,
     pub follow_suspended: RwSignal<bool>,
     pub follow_visible_since: RwSignal<Option<f64>>,
+    /// Time (ms) since the last manual scroll before a suspended follow-cursor
+    /// re-engages (persisted).
+    pub follow_resume_ms: RwSignal<f64>,
+    /// Starting playback always re-engages a suspended follow-cursor (persisted).
+    pub follow_resume_on_play: RwSignal<bool>,
+    /// Where the "play from here" marker sits (persisted).
+    pub play_anchor: RwSignal<PlayAnchor>,
+    /// Marker position as a fraction of the view width (persisted for
+    /// `PlayAnchor::Click`). Set via `set_play_anchor_fraction`.
+    pub play_anchor_fraction: RwSignal<f64>,
     pub pre_play_scroll: RwSignal<f64>,
     pub user_panned_during_playback: RwSignal<bool>,
     // Filter EQ (driven by bandpass_mode effect)
@@ -1134,6 +2042,9 @@ pub struct AppState {
     pub filter_hovering_band: RwSignal<Option<u8>>,
     pub filter_quality: RwSignal<FilterQuality>,
     pub het_cutoff: RwSignal<f64>,
+    /// Heterodyne low-pass stages (-6 dB/octave each), for both playback and
+    /// live listening. Persisted.
+    pub het_lp_order: RwSignal<usize>,
     pub sidebar_collapsed: RwSignal<bool>,
     pub sidebar_width: RwSignal<f64>,
     // Gain
@@ -1194,6 +2105,9 @@ pub struct AppState {
 
     // Tile system: incrementing this triggers a spectrogram redraw
     pub tile_ready_signal: RwSignal<u32>,
+    /// Visible spectrogram tiles (ready, total) while some are still being
+    /// computed. None once the viewport is fully rendered.
+    pub tile_progress: RwSignal<Option<(usize, usize)>>,
 
     /// Generation counter for background preload. Incremented when file/LOD changes
     /// to cancel stale preload jobs.
@@ -1210,6 +2124,9 @@ pub struct AppState {
     pub spect_gain_db: RwSignal<f32>,
     /// Show tile debug overlay (borders, LOD labels) on the spectrogram canvas.
     pub debug_tiles: RwSignal<bool>,
+    /// Show the raw magnitude, greyscale value and colour of the bin under
+    /// the cursor in the crosshair readout.
+    pub debug_pixel_inspect: RwSignal<bool>,
     /// FFT window mode for spectrogram computation.
     /// Single size or multi-resolution (different sizes per frequency band).
     pub spect_fft_mode: RwSignal<FftMode>,
@@ -1233,6 +2150,10 @@ pub struct AppState {
     // BandFF frequency range (0.0 = no BandFF active)
     pub band_ff_freq_lo: RwSignal<f64>,
     pub band_ff_freq_hi: RwSignal<f64>,
+    /// Additional HFR focus bands (lo, hi) in Hz, colored alongside the main
+    /// BandFF range (e.g. a harmonic). Empty in the common single-band case
+    /// (persisted).
+    pub extra_focus_bands: RwSignal<Vec<(f64, f64)>>,
 
     /// True while the user is live-dragging the band gutter. Heavy consumers
     /// (e.g. the waveform's full-file FFT band-split) can early-return with
@@ -1257,6 +2178,11 @@ pub struct AppState {
     pub mic_samples_recorded: RwSignal<usize>,
     pub mic_bits_per_sample: RwSignal<u16>,
     pub mic_max_sample_rate: RwSignal<u32>, // 0 = auto (device default)
+    /// Highest rate streamed from the native mic for display (0 = full rate).
+    /// Listening and recordings always keep the device rate (persisted).
+    pub mic_display_max_rate: RwSignal<u32>,
+    /// Decimation the native backend applies to streamed chunks (1 = none).
+    pub mic_display_factor: RwSignal<u32>,
     /// Maximum seconds of listen buffer to capture on long-press record.
     pub mic_preroll_buffer_secs: RwSignal<u32>,
     pub mic_mode: RwSignal<MicMode>,
@@ -1275,6 +2201,14 @@ pub struct AppState {
     /// Wall-clock time (Date.now()) when the long-press gesture started.
     /// Used to compensate for audio accumulated during the gesture hold period.
     pub mic_gesture_start_ms: RwSignal<Option<f64>>,
+    /// Input level (dBFS) that drops a bookmark while recording. None = off.
+    pub mic_trigger_level_db: RwSignal<Option<f64>>,
+    /// Time constant (s) of the live spectrogram's display averaging; 0 = off
+    /// (persisted).
+    pub mic_live_smoothing_secs: RwSignal<f64>,
+    /// Times (s, on the recording's timeline) of level-triggered bookmarks in
+    /// the current recording. Written as WAV cue points when it is saved.
+    pub mic_trigger_times: RwSignal<Vec<f64>>,
     /// Wall-clock time (Date.now()) when recording started, for timer display.
     pub mic_recording_start_time: RwSignal<Option<f64>>,
     /// Wrapping counter incremented by setInterval(100ms) while recording.
@@ -1291,6 +2225,16 @@ pub struct AppState {
     pub home_wifi_ssids: RwSignal<Vec<String>>,
     /// Whether to include phone model in recording metadata (privacy toggle, persisted, default true).
     pub device_model_enabled: RwSignal<bool>,
+    /// File name template for new recordings, e.g. "{site}_{date}_{time}" (persisted).
+    pub recording_name_template: RwSignal<String>,
+    /// Survey site name for recording names and metadata (persisted).
+    pub recording_site: RwSignal<String>,
+    /// Person recording, for recording names and metadata (persisted).
+    pub recording_operator: RwSignal<String>,
+    /// Whether site and operator are written into each recording's GUANO metadata (persisted, default true).
+    pub recording_embed_survey: RwSignal<bool>,
+    /// Folder for native recordings; empty = app data "recordings" folder (persisted). Desktop only.
+    pub recording_save_dir: RwSignal<String>,
     /// Cached device manufacturer (e.g. "samsung"), fetched once on first recording. Android only.
     pub cached_device_make: RwSignal<Option<String>>,
     /// Cached device model (e.g. "SM-A556E"), fetched once on first recording. Android only.
@@ -1375,6 +2319,14 @@ pub struct AppState {
 
     // XC browser
     pub xc_browser_open: RwSignal<bool>,
+    /// True while a batch conversion of the XC cache is running.
+    pub xc_converting: RwSignal<bool>,
+    /// Progress or result line of the last cache conversion.
+    pub xc_convert_status: RwSignal<Option<String>>,
+    /// (page, total pages) while a taxonomy refresh is fetching.
+    pub xc_taxonomy_progress: RwSignal<Option<(u32, u32)>>,
+    /// Identify-by-comparison session, if one is running.
+    pub xc_identify: RwSignal<Option<XcIdentify>>,
 
     // (hfr_saved_* signals removed — now in FocusStack)
 
@@ -1390,6 +2342,11 @@ pub struct AppState {
 
     // User colormap preference (when not overridden by HFR/flow)
     pub colormap_preference: RwSignal<Colormap>,
+    /// User-defined colormaps, selected as `Colormap::Custom(index)` (persisted).
+    pub custom_colormaps: RwSignal<Vec<crate::canvas::custom_colormap::CustomColormap>>,
+    /// Colormap for the phase-coherence display, independent of the
+    /// spectrogram colormap. None = built-in deviation colors.
+    pub coherence_colormap: RwSignal<Option<Colormap>>,
     // Chromagram colormap mode
     pub chroma_colormap: RwSignal<ChromaColormap>,
     // Chromagram display: gain boost in dB (0 = no boost, positive = amplify)
@@ -1398,6 +2355,13 @@ pub struct AppState {
     pub chroma_gamma: RwSignal<f32>,
     // Chromagram frequency range preset
     pub chroma_range: RwSignal<ChromaRange>,
+    // Diff view: file B, subtracted from the current file. None = first other file.
+    pub diff_other_file: RwSignal<Option<FileKey>>,
+    // Diff view: B is compared at A's time plus this, so a positive offset
+    // skips that much extra audio at the start of B
+    pub diff_offset_secs: RwSignal<f64>,
+    // Diff view: difference in dB shown at full colour
+    pub diff_range_db: RwSignal<f32>,
 
     // Resonator view: per-bin EMA bandwidth in Hz (controls time-frequency tradeoff)
     pub resonator_bandwidth_hz: RwSignal<f32>,
@@ -1418,6 +2382,15 @@ pub struct AppState {
     pub resonator_viewport_range: RwSignal<Option<(f64, f64)>>,
     // Colormap preference used when HFR mode is active
     pub hfr_colormap_preference: RwSignal<Colormap>,
+    /// Give the HFR focus band its own contrast (below), independent of the
+    /// main spectrogram intensity settings. Persisted, along with the three below.
+    pub hfr_focus_contrast: RwSignal<bool>,
+    /// Extra dB gain inside the focus band, on top of the main gain.
+    pub hfr_focus_gain_db: RwSignal<f32>,
+    /// dB range inside the focus band (floor = -range, like the main slider).
+    pub hfr_focus_range_db: RwSignal<f32>,
+    /// Gamma curve inside the focus band.
+    pub hfr_focus_gamma: RwSignal<f32>,
     // When false, the Range button is hidden at full range
     pub always_show_view_range: RwSignal<bool>,
 
@@ -1441,6 +2414,30 @@ pub struct AppState {
     pub pulse_overlay_enabled: RwSignal<bool>,
     pub selected_pulse_index: RwSignal<Option<usize>>,
     pub pulse_detecting: RwSignal<bool>,
+    /// Detection settings from the Pulses panel. The species hint measures
+    /// calls with these too, so both see the same pulses.
+    pub pulse_threshold_db: RwSignal<f64>,
+    pub pulse_min_duration_ms: RwSignal<f64>,
+    pub pulse_max_duration_ms: RwSignal<f64>,
+    pub pulse_min_gap_ms: RwSignal<f64>,
+    pub pulse_min_snr_db: RwSignal<f64>,
+
+    // Harmonics analysis
+    /// Time span analysed by the Harmonics panel.
+    pub harmonics_window: RwSignal<HarmonicsWindow>,
+    /// Number of harmonics (including the fundamental) extracted and charted.
+    pub harmonic_count: RwSignal<usize>,
+    /// Harmonics and method used for the decay-exponent fit (persisted).
+    pub harmonic_decay_fit: RwSignal<crate::dsp::harmonics::DecayFit>,
+    /// Fundamental found by the Harmonics panel's latest analysis, with the
+    /// index of the file it belongs to.
+    pub detected_fundamental: RwSignal<Option<(usize, f64)>>,
+    /// Manually entered fundamental (Hz); overrides the detected one when
+    /// relating a selection to the harmonic series.
+    pub fundamental_override_hz: RwSignal<Option<f64>>,
+    /// Smoothing for per-frame tracks: the spectral flux timeline and the
+    /// call frequency contours measured for pulses (persisted).
+    pub contour_smoothing: RwSignal<crate::dsp::smoothing::Smoothing>,
 
     // File identity hashing
     /// Whether a full hash computation (Layer 3/4) is currently running.
@@ -1456,10 +2453,25 @@ pub struct AppState {
     pub last_clicked_annotation_id: RwSignal<Option<AnnotationId>>,
     /// When true, finalizing a transient selection sets the frequency focus range to match.
     pub selection_auto_focus: RwSignal<bool>,
+    /// When true, a newly drawn selection is snapped to the edges of the
+    /// call inside it (shown as a preview until Enter applies it).
+    pub snap_to_call: RwSignal<bool>,
+    /// How far below the call's peak (dB) its snapped edges lie.
+    pub snap_threshold_db: RwSignal<f64>,
+    /// (drawn, snapped) bounds awaiting Enter (apply) or Escape (keep the
+    /// drawn box). Stale once the selection no longer equals the drawn one.
+    pub snap_preview: RwSignal<Option<(Selection, Selection)>>,
+    /// How the Frequency Focus dimming treats the selection (persisted).
+    pub selection_focus_dim: RwSignal<SelectionFocusDim>,
+    /// Opacity of the dimming outside the Frequency Focus band (persisted).
+    pub focus_dim_opacity: RwSignal<f64>,
     /// When true, clicking an annotation pushes its frequency focus override.
     pub annotation_auto_focus: RwSignal<bool>,
     /// When true, export uses each region's own freq bounds for DSP; when false, uses global HFR.
     pub export_use_region_focus: RwSignal<bool>,
+    /// Raised-cosine fade (ms) at the edges of exported clips and playback,
+    /// to avoid boundary clicks. 0 = bit-exact (persisted to localStorage).
+    pub clip_fade_ms: RwSignal<f64>,
     /// Id of annotation currently being dragged in the sidebar tree.
     pub dragging_annotation_id: RwSignal<Option<AnnotationId>>,
     /// Drop target: (target_id, position) where position is "before", "after", or "inside" (for groups).
@@ -1501,6 +2513,10 @@ pub struct AppState {
     pub display_auto_gain: RwSignal<bool>,
     pub display_eq: RwSignal<bool>,
     pub display_noise_filter: RwSignal<bool>,
+    /// Display-only pre-emphasis slope in dB/octave (0 = off). Boosts rows
+    /// above `PRE_EMPHASIS_CORNER_OCTAVES` below Nyquist so faint high
+    /// harmonics are easier to see; never applied to analysis or audio.
+    pub display_pre_emphasis_db_oct: RwSignal<f64>,
     /// When true, spectrogram tiles are computed from DSP-transformed audio
     /// (same transform as playback mode: pitch shift, heterodyne, etc.)
     pub display_transform: RwSignal<bool>,
@@ -1528,6 +2544,11 @@ pub struct AppState {
     pub display_filter_gain: RwSignal<DisplayFilterMode>,
     /// Extra dB boost applied to spectrogram display from Auto/Same gain modes.
     pub display_gain_boost: RwSignal<f32>,
+    /// When true, display gain follows the level of the visible columns so
+    /// quiet and loud passages both fill the colour range while scrolling.
+    pub spect_auto_exposure: RwSignal<bool>,
+    /// Current (smoothed) auto-exposure offset in dB; 0 when off.
+    pub auto_exposure_db: RwSignal<f32>,
     // Decimation (downsample after DSP transform)
     pub display_filter_decimate: RwSignal<DisplayFilterMode>,
     /// Target decimation sample rate in Hz (used for Custom mode; Auto computes from transform).
@@ -1571,24 +2592,134 @@ pub struct AppState {
 
     // Timeline display: show wall-clock time instead of file-relative time
     pub show_clock_time: RwSignal<bool>,
+    /// Spacing of time-axis ticks (persisted to localStorage).
+    pub time_tick_density: RwSignal<TimeTickDensity>,
+    /// Draw vertical gridlines across the spectrogram at the time ticks (persisted).
+    pub time_gridlines: RwSignal<bool>,
+    /// How the no-data band above Nyquist is filled (persisted to localStorage).
+    pub no_data_fill: RwSignal<NoDataFill>,
 
     /// Frequency shield/flag color bar style (persisted to localStorage).
     pub shield_style: RwSignal<ShieldStyle>,
+    /// Palette for frequency marker ticks and solid flags.
+    pub marker_colors: RwSignal<MarkerColors>,
+    /// Interface theme choice (persisted to localStorage).
+    pub ui_theme: RwSignal<UiTheme>,
+    /// Whether high-contrast styling is in effect, resolved from `ui_theme`
+    /// and the system contrast preference by an effect in `App`.
+    pub high_contrast: RwSignal<bool>,
+    /// Spacing of labelled frequency markers in kHz; 0 = automatic.
+    pub freq_marker_interval_khz: RwSignal<f64>,
+    /// Reference for the cursor dB readout in the status bar (persisted to localStorage).
+    pub cursor_db_reference: RwSignal<DbReference>,
+    /// Handling of fields in both xeno-canto and GUANO metadata (persisted).
+    pub metadata_overlap: RwSignal<MetadataOverlap>,
+    /// Tile cache memory budget (persisted to localStorage; applied by an Effect in `App`).
+    pub tile_memory_budget: RwSignal<TileMemoryBudget>,
+    /// Background tile prefetch aggressiveness (persisted to localStorage).
+    pub tile_prefetch: RwSignal<TilePrefetch>,
+    /// Whether visible tiles preempt background preload (persisted to localStorage).
+    pub tile_scheduling: RwSignal<TileScheduling>,
+    /// File-list preview thumbnail resolution (persisted to localStorage).
+    pub preview_detail: RwSignal<PreviewDetail>,
+    /// Progress of a "re-render previews" pass as (done, total); None when idle.
+    pub preview_rerender_progress: RwSignal<Option<(usize, usize)>>,
+    /// How many opened files decode at once; the rest wait in a queue (persisted).
+    pub max_concurrent_loads: RwSignal<usize>,
+    /// Files up to this long (seconds) skip the preview phase and compute
+    /// their full spectrogram while loading. 0 = always preview (persisted).
+    pub single_pass_max_secs: RwSignal<f64>,
+    /// Trim leading and trailing silence from files as they load. The
+    /// original audio is kept for export (persisted).
+    pub import_trim_silence: RwSignal<bool>,
+    /// Level above the noise floor (dB) that counts as activity (persisted).
+    pub import_trim_threshold_db: RwSignal<f64>,
+    /// Audio kept either side of the active region (ms, persisted).
+    pub import_trim_pad_ms: RwSignal<f64>,
+    /// Units for displayed frequencies and durations (persisted to localStorage).
+    pub freq_unit: RwSignal<crate::units::FreqUnit>,
+    pub duration_unit: RwSignal<crate::units::DurationUnit>,
+    /// Transient selection outline/fill color (persisted to localStorage).
+    pub selection_color: RwSignal<[u8; 3]>,
+    /// Harmonic-shadow (octave above/below) box color (persisted to localStorage).
+    pub harmonic_shadow_color: RwSignal<[u8; 3]>,
+    /// Fill behind the spectrogram where there is no data (persisted to localStorage).
+    pub spectrogram_background: RwSignal<[u8; 3]>,
+    /// Playhead line/dot color and opacity (persisted to localStorage).
+    pub playhead_color: RwSignal<[u8; 3]>,
+    pub playhead_opacity: RwSignal<f64>,
+    /// Peak-frequency trail left behind the playhead (persisted).
+    pub playhead_trail: RwSignal<PlayheadTrail>,
+    /// Seconds of playback a fading trail spans (persisted).
+    pub playhead_trail_secs: RwSignal<f64>,
+    /// Trail mark color and opacity (persisted to localStorage).
+    pub playhead_trail_color: RwSignal<[u8; 3]>,
+    pub playhead_trail_opacity: RwSignal<f64>,
+    /// Bookmark dot color and opacity (persisted to localStorage).
+    pub bookmark_color: RwSignal<[u8; 3]>,
+    pub bookmark_opacity: RwSignal<f64>,
 
     /// Whether the analysis/status bar is visible (persisted to localStorage).
     pub show_status_bar: RwSignal<bool>,
+    /// Draw a time/frequency crosshair with a readout at the cursor (persisted to localStorage).
+    pub show_crosshair: RwSignal<bool>,
+    /// Draw a thin amplitude envelope along the bottom of the spectrogram (persisted to localStorage).
+    pub show_envelope_overlay: RwSignal<bool>,
+    /// Reference frequency lines across the spectrogram, and whether they're shown (persisted to localStorage).
+    pub reference_lines: RwSignal<Vec<crate::reference_lines::ReferenceLine>>,
+    pub show_reference_lines: RwSignal<bool>,
+    /// Suggest candidate species from measured call parameters in the Analysis panel (persisted to localStorage).
+    pub species_hint_enabled: RwSignal<bool>,
+    /// Noise floor estimation method and silence threshold (dBFS) for the
+    /// Analysis panel's bit usage section (persisted to localStorage).
+    pub noise_floor_method: RwSignal<crate::dsp::bit_analysis::NoiseFloorMethod>,
+    pub noise_floor_silence_db: RwSignal<f64>,
+
+    /// Offline mode: no demo sounds, xeno-canto or other remote fetches (persisted).
+    pub offline_mode: RwSignal<bool>,
+    /// Seconds before a demo or URL fetch is abandoned (persisted).
+    pub fetch_timeout_secs: RwSignal<u32>,
+    /// Extra attempts after a fetch times out or fails with a network or
+    /// server error (persisted).
+    pub fetch_retries: RwSignal<u32>,
+
+    /// DC-offset warning threshold as a fraction of full scale (persisted, 0 = off).
+    pub dc_warn_full_scale: RwSignal<f64>,
+    /// DC-offset warning threshold relative to RMS level (persisted, 0 = off).
+    pub dc_warn_rms_ratio: RwSignal<f64>,
 
     // Layered frequency focus stack
     pub focus_stack: RwSignal<crate::focus_stack::FocusStack>,
 
     // Clean view: hide all overlays while holding backtick
     pub clean_view: RwSignal<bool>,
+    /// Keep clean view on after the backtick is released (Shift+backtick or
+    /// the Display setting) (persisted).
+    pub clean_view_pinned: RwSignal<bool>,
 
     // Export UI
     /// Whether the export section is expanded/collapsed.
     pub export_section_open: RwSignal<bool>,
-    /// Selected export format: WAV or MP4.
+    /// Selected export format: WAV, MP4 or PNG.
     pub export_format: RwSignal<ExportFormat>,
+    /// Crop exported images to the HFR focus band.
+    pub export_crop_to_ff: RwSignal<bool>,
+    /// With `export_crop_to_ff`, also band-limit exported audio to the focus band.
+    pub export_crop_audio_to_ff: RwSignal<bool>,
+    /// Stamp the FFT/hop/window parameters onto exported PNGs (persisted).
+    pub export_params_caption: RwSignal<bool>,
+    /// Horizontal scale of whole-recording PNG exports, in pixels per second (persisted).
+    pub export_full_px_per_sec: RwSignal<f64>,
+    /// Whole-recording PNG export progress as (parts done, total parts), None = idle.
+    pub export_full_progress: RwSignal<Option<(usize, usize)>>,
+    /// Keep the original integer samples of WAV/FLAC files loaded from now on,
+    /// so unprocessed WAV exports are bit-identical to the source (persisted).
+    pub keep_exact_samples: RwSignal<bool>,
+    /// Sample format of processed WAV exports (persisted).
+    pub export_wav_format: RwSignal<crate::audio::convert::SampleFormat>,
+    /// Apply TPDF dither when reducing processed WAV exports to 16/24-bit.
+    /// Off by default (persisted).
+    pub export_wav_dither: RwSignal<bool>,
     /// Video export progress (0.0 to 1.0), None = not exporting.
     pub video_export_progress: RwSignal<Option<f64>>,
     /// Video export status message.
@@ -1613,6 +2744,55 @@ pub struct AppState {
     pub annotation_overflow_open: RwSignal<bool>,
 }
 
+/// Default transient selection color (light blue, readable on all colormaps).
+pub const DEFAULT_SELECTION_COLOR: [u8; 3] = [120, 190, 255];
+/// Default harmonic-shadow color.
+pub const DEFAULT_HARMONIC_SHADOW_COLOR: [u8; 3] = [80, 160, 255];
+/// Default spectrogram background.
+pub const DEFAULT_SPECTROGRAM_BACKGROUND: [u8; 3] = [0, 0, 0];
+/// Default playhead color.
+pub const DEFAULT_PLAYHEAD_COLOR: [u8; 3] = [255, 80, 80];
+/// Default playhead trail color.
+pub const DEFAULT_PLAYHEAD_TRAIL_COLOR: [u8; 3] = [255, 230, 90];
+/// Default span of a fading playhead trail, and the limits offered (seconds of file time).
+pub const DEFAULT_PLAYHEAD_TRAIL_SECS: f64 = 0.5;
+pub const MIN_PLAYHEAD_TRAIL_SECS: f64 = 0.01;
+pub const MAX_PLAYHEAD_TRAIL_SECS: f64 = 30.0;
+/// Default bookmark dot color.
+pub const DEFAULT_BOOKMARK_COLOR: [u8; 3] = [255, 200, 50];
+/// Default opacity of the playhead and bookmark dots.
+pub const DEFAULT_OVERLAY_OPACITY: f64 = 0.9;
+
+/// Read an `#rrggbb` color from localStorage, falling back to `default`.
+fn stored_color(key: &str, default: [u8; 3]) -> [u8; 3] {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|ls| ls.get_item(key).ok().flatten())
+        .and_then(|v| crate::canvas::colors::hex_to_rgb(&v))
+        .unwrap_or(default)
+}
+
+/// Read an opacity (0..1) from localStorage, falling back to `default`.
+fn stored_opacity(key: &str, default: f64) -> f64 {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|ls| ls.get_item(key).ok().flatten())
+        .and_then(|v| v.parse::<f64>().ok())
+        .map(|v| v.clamp(0.0, 1.0))
+        .unwrap_or(default)
+}
+
+/// Read a number from localStorage, clamped to `min..=max`, falling back to `default`.
+fn stored_f32(key: &str, default: f32, min: f32, max: f32) -> f32 {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|ls| ls.get_item(key).ok().flatten())
+        .and_then(|v| v.parse::<f32>().ok())
+        .filter(|v| v.is_finite())
+        .map(|v| v.clamp(min, max))
+        .unwrap_or(default)
+}
+
 fn detect_tauri() -> bool {
     let Some(window) = web_sys::window() else { return false };
     js_sys::Reflect::get(&window, &wasm_bindgen::JsValue::from_str("__TAURI_INTERNALS__"))
@@ -1656,20 +2836,53 @@ impl Default for AppState {
 
 impl AppState {
     pub fn new() -> Self {
+        let initial_play_anchor = web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .and_then(|ls| ls.get_item("oversample_play_anchor").ok().flatten())
+            .map(|v| PlayAnchor::from_key(&v))
+            .unwrap_or_default();
+        let initial_clean_view_pinned = web_sys::window()
+            .and_then(|w| w.local_storage().ok().flatten())
+            .and_then(|ls| ls.get_item("oversample_clean_view_pinned").ok().flatten())
+            .is_some_and(|v| v == "true");
         let s = Self {
             files: RwSignal::new(Vec::new()),
             current_file_index: RwSignal::new(None),
             file_sort_mode: RwSignal::new(FileSortMode::AddOrder),
+            file_filter: RwSignal::new(String::new()),
+            file_filter_metadata: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_file_filter_metadata").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(true)
+            }),
             show_file_previews: RwSignal::new(false),
+            file_preview_brightness: RwSignal::new(
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_preview_brightness").ok().flatten())
+                    .and_then(|v| v.parse::<f32>().ok())
+                    .map(|v| v.clamp(0.0, 1.0))
+                    .unwrap_or(0.0),
+            ),
             selection: RwSignal::new(None),
             last_selection: RwSignal::new(None),
             playback_mode: RwSignal::new(PlaybackMode::Normal),
             het_frequency: RwSignal::new(45_000.0),
             te_factor: RwSignal::new(10.0),
-            zoom_level: RwSignal::new(1.0),
+            zoom_level: RwSignal::new(crate::viewport::DEFAULT_ZOOM),
+            zoom_limit_px: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_zoom_limit_px").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|px| ZOOM_LIMIT_WINDOW_PX.contains(px))
+            }),
             scroll_offset: RwSignal::new(0.0),
             is_playing: RwSignal::new(false),
             is_buffering: RwSignal::new(false),
+            output_limiting: RwSignal::new(false),
             playhead_time: RwSignal::new(0.0),
             active_playback_selection: RwSignal::new(None),
             loading_files: RwSignal::new(Vec::new()),
@@ -1677,7 +2890,17 @@ impl AppState {
             ps_factor: RwSignal::new(10.0),
             pv_factor: RwSignal::new(10.0),
             pv_hq: RwSignal::new(true),
+            te_hq: RwSignal::new(true),
             zc_factor: RwSignal::new(8.0),
+            zc_dot_division: RwSignal::new(0),
+            zc_track_window_ms: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_zc_track_window_ms").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(0.1, 1000.0))
+                    .unwrap_or(crate::dsp::zero_crossing::DEFAULT_TRACK_WINDOW_MS)
+            }),
             het_interacting: RwSignal::new(false),
             is_dragging: RwSignal::new(false),
             pointer_is_down: RwSignal::new(false),
@@ -1691,17 +2914,102 @@ impl AppState {
             flow_gate: RwSignal::new(0.75),
             flow_opacity: RwSignal::new(0.75),
             flow_shift_gain: RwSignal::new(3.0),
+            flow_radius: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_flow_radius").ok().flatten())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .map(|v| v.clamp(1, FlowShiftParams::MAX))
+                    .unwrap_or(FlowShiftParams::DEFAULT_RADIUS)
+            }),
+            flow_max_displacement: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_flow_max_displacement").ok().flatten())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .map(|v| v.clamp(1, FlowShiftParams::MAX))
+                    .unwrap_or(FlowShiftParams::DEFAULT_MAX_DISPLACEMENT)
+            }),
             flow_color_gamma: RwSignal::new(1.0),
             flow_color_scheme: RwSignal::new(FlowColorScheme::default()),
+            flow_blend_mode: RwSignal::new(FlowBlendMode::default()),
             min_display_freq: RwSignal::new(None),
             max_display_freq: RwSignal::new(None),
+            freq_axis_flipped: RwSignal::new({
+                let flipped = web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_freq_axis_flipped").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false);
+                crate::viewport::set_freq_axis_flipped(flipped);
+                flipped
+            }),
+            default_display_range: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_default_display_range").ok().flatten())
+                    .and_then(|v| {
+                        let (lo, hi) = v.split_once(',')?;
+                        Some((lo.parse::<f64>().ok()?, hi.parse::<f64>().ok()?))
+                    })
+                    .filter(|&(lo, hi)| lo >= 0.0 && hi > lo)
+            }),
+            freq_above_nyquist: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_freq_above_nyquist").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
             mouse_freq: RwSignal::new(None),
             mouse_canvas_x: RwSignal::new(0.0),
             mouse_in_label_area: RwSignal::new(false),
             label_hover_opacity: RwSignal::new(0.0),
             follow_cursor: RwSignal::new(true),
+            resume_playback: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_resume_playback").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            remember_playback_mode: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_remember_playback_mode").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(true)
+            }),
+            playback_positions: RwSignal::new(std::collections::HashMap::new()),
             follow_suspended: RwSignal::new(false),
             follow_visible_since: RwSignal::new(None),
+            follow_resume_ms: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_follow_resume_ms").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(0.0, 10_000.0))
+                    .unwrap_or(crate::viewport::DEFAULT_FOLLOW_RESUME_MS)
+            }),
+            follow_resume_on_play: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_follow_resume_on_play").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            play_anchor: RwSignal::new(initial_play_anchor),
+            play_anchor_fraction: RwSignal::new(match initial_play_anchor {
+                PlayAnchor::Click => web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_play_anchor_fraction").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(0.0, 1.0))
+                    .unwrap_or_else(|| initial_play_anchor.fraction()),
+                anchor => anchor.fraction(),
+            }),
             pre_play_scroll: RwSignal::new(0.0),
             user_panned_during_playback: RwSignal::new(false),
             filter_enabled: RwSignal::new(false),
@@ -1715,6 +3023,14 @@ impl AppState {
             filter_hovering_band: RwSignal::new(None),
             filter_quality: RwSignal::new(FilterQuality::Spectral),
             het_cutoff: RwSignal::new(15_000.0),
+            het_lp_order: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_het_lp_order").ok().flatten())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .map(|v| v.clamp(1, crate::dsp::heterodyne::MAX_LP_ORDER))
+                    .unwrap_or(crate::dsp::heterodyne::DEFAULT_LP_ORDER)
+            }),
             sidebar_collapsed: RwSignal::new(false),
             sidebar_width: RwSignal::new(220.0),
             gain_db: RwSignal::new(0.0),
@@ -1728,7 +3044,13 @@ impl AppState {
             wave_view_gain_db: RwSignal::new(0.0),
             wave_view_auto_gain: RwSignal::new(false),
 
-            channel_view: RwSignal::new(ChannelView::Stereo),
+            channel_view: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_channel_view").ok().flatten())
+                    .and_then(|v| ChannelView::from_key(&v))
+                    .unwrap_or(ChannelView::Stereo)
+            }),
 
             // New
             canvas_tool: RwSignal::new(CanvasTool::Hand),
@@ -1745,12 +3067,14 @@ impl AppState {
             record_mode: RwSignal::new(if detect_tauri() { RecordMode::ToFile } else { RecordMode::ToMemory }),
             play_from_here_time: RwSignal::new(0.0),
             tile_ready_signal: RwSignal::new(0),
+            tile_progress: RwSignal::new(None),
             bg_preload_gen: RwSignal::new(0),
             spect_floor_db: RwSignal::new(-120.0),
             spect_range_db: RwSignal::new(120.0),
             spect_gamma: RwSignal::new(1.0),
             spect_gain_db: RwSignal::new(0.0),
             debug_tiles: RwSignal::new(false),
+            debug_pixel_inspect: RwSignal::new(false),
             spect_fft_mode: RwSignal::new(FftMode::AdaptiveM),
             reassign_enabled: RwSignal::new(false),
             layer_panel_open: RwSignal::new(None),
@@ -1760,6 +3084,22 @@ impl AppState {
             spec_hover_handle: RwSignal::new(None),
             band_ff_freq_lo: RwSignal::new(0.0),
             band_ff_freq_hi: RwSignal::new(0.0),
+            extra_focus_bands: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_extra_focus_bands").ok().flatten())
+                    .map(|v| {
+                        v.split(';')
+                            .filter_map(|band| {
+                                let (lo, hi) = band.split_once(',')?;
+                                Some((lo.parse::<f64>().ok()?, hi.parse::<f64>().ok()?))
+                            })
+                            .filter(|&(lo, hi)| lo >= 0.0 && hi - lo >= 500.0)
+                            .take(crate::canvas::spectrogram_renderer::MAX_EXTRA_FOCUS_BANDS)
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            }),
             band_ff_dragging: RwSignal::new(false),
             het_freq_auto: RwSignal::new(true),
             het_cutoff_auto: RwSignal::new(true),
@@ -1774,12 +3114,30 @@ impl AppState {
             mic_bits_per_sample: RwSignal::new(16),
             mic_max_sample_rate: RwSignal::new(0),
             mic_preroll_buffer_secs: RwSignal::new(10),
+            mic_display_max_rate: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_mic_display_max_rate").ok().flatten())
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .unwrap_or(0)
+            }),
+            mic_display_factor: RwSignal::new(1),
             mic_mode: RwSignal::new(if detect_tauri() { MicMode::Auto } else { MicMode::Browser }),
             mic_supported_rates: RwSignal::new(Vec::new()),
             mic_live_file_idx: RwSignal::new(None),
             mic_processing_gen: RwSignal::new(0),
             mic_preroll_samples: RwSignal::new(0),
             mic_gesture_start_ms: RwSignal::new(None),
+            mic_trigger_level_db: RwSignal::new(None),
+            mic_live_smoothing_secs: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_live_smoothing_secs").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(0.0, 0.5))
+                    .unwrap_or(0.0)
+            }),
+            mic_trigger_times: RwSignal::new(Vec::new()),
             mic_recording_start_time: RwSignal::new(None),
             mic_timer_tick: RwSignal::new(0),
             mic_device_name: RwSignal::new(None),
@@ -1811,6 +3169,38 @@ impl AppState {
                     .map(|v| v != "false")
                     .unwrap_or(true) // default on
             }),
+            recording_name_template: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_recording_name_template").ok().flatten())
+                    .filter(|t| crate::audio::recording_name::validate_template(t).is_ok())
+                    .unwrap_or_else(|| crate::audio::recording_name::DEFAULT_TEMPLATE.to_string())
+            }),
+            recording_site: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_recording_site").ok().flatten())
+                    .unwrap_or_default()
+            }),
+            recording_operator: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_recording_operator").ok().flatten())
+                    .unwrap_or_default()
+            }),
+            recording_embed_survey: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_recording_embed_survey").ok().flatten())
+                    .map(|v| v != "false")
+                    .unwrap_or(true) // default on
+            }),
+            recording_save_dir: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_recording_save_dir").ok().flatten())
+                    .unwrap_or_default()
+            }),
             cached_device_make: RwSignal::new(None),
             cached_device_model: RwSignal::new(None),
             mic_manufacturer: RwSignal::new(None),
@@ -1849,21 +3239,40 @@ impl AppState {
             viewport_zoomed: RwSignal::new(false),
             visual_viewport_rect: RwSignal::new((0.0, 0.0, 0.0, 1.0)),
             xc_browser_open: RwSignal::new(false),
+            xc_converting: RwSignal::new(false),
+            xc_convert_status: RwSignal::new(None),
+            xc_taxonomy_progress: RwSignal::new(None),
+            xc_identify: RwSignal::new(None),
             axis_drag_start_freq: RwSignal::new(None),
             axis_drag_current_freq: RwSignal::new(None),
             cursor_time: RwSignal::new(None),
             left_sidebar_tab: RwSignal::new(LeftSidebarTab::default()),
             colormap_preference: RwSignal::new(Colormap::Viridis),
+            custom_colormaps: RwSignal::new(crate::custom_colormaps::load()),
+            coherence_colormap: RwSignal::new(None),
             chroma_colormap: RwSignal::new(ChromaColormap::PitchClass),
             chroma_gain: RwSignal::new(0.0),
             chroma_gamma: RwSignal::new(1.0),
             chroma_range: RwSignal::new(ChromaRange::Full),
+            diff_other_file: RwSignal::new(None),
+            diff_offset_secs: RwSignal::new(0.0),
+            diff_range_db: RwSignal::new(12.0),
             resonator_bandwidth_hz: RwSignal::new(20.0),
             resonator_fft_mode: RwSignal::new(ResonatorFftMode::Single(512)),
             resonator_layout: RwSignal::new(ResonatorLayout::Linear),
             resonator_viewport_bins: RwSignal::new(true),
             resonator_viewport_range: RwSignal::new(None),
             hfr_colormap_preference: RwSignal::new(Colormap::Inferno),
+            hfr_focus_contrast: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_hfr_focus_contrast").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            hfr_focus_gain_db: RwSignal::new(stored_f32("oversample_hfr_focus_gain_db", 0.0, -20.0, 40.0)),
+            hfr_focus_range_db: RwSignal::new(stored_f32("oversample_hfr_focus_range_db", 120.0, 20.0, 120.0)),
+            hfr_focus_gamma: RwSignal::new(stored_f32("oversample_hfr_focus_gamma", 1.0, 0.2, 3.0)),
             always_show_view_range: RwSignal::new(false),
 
             notch_enabled: RwSignal::new(false),
@@ -1882,6 +3291,45 @@ impl AppState {
             pulse_overlay_enabled: RwSignal::new(false),
             selected_pulse_index: RwSignal::new(None),
             pulse_detecting: RwSignal::new(false),
+            pulse_threshold_db: RwSignal::new(6.0),
+            pulse_min_duration_ms: RwSignal::new(0.3),
+            pulse_max_duration_ms: RwSignal::new(50.0),
+            pulse_min_gap_ms: RwSignal::new(3.0),
+            pulse_min_snr_db: RwSignal::new(crate::dsp::pulse_detect::PulseDetectionParams::default().min_snr_db),
+
+            harmonics_window: RwSignal::new(HarmonicsWindow::WholeFile),
+            harmonic_count: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_harmonic_count").ok().flatten())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .map(|v| v.clamp(2, crate::dsp::harmonics::MAX_HARMONIC_COUNT))
+                    .unwrap_or(crate::dsp::harmonics::DEFAULT_HARMONIC_COUNT)
+            }),
+            harmonic_decay_fit: RwSignal::new({
+                use crate::dsp::harmonics::{DecayFit, DecayFitMethod, MAX_HARMONIC_COUNT};
+                // Stored as "first-last-method", e.g. "2-6-robust"
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_decay_fit").ok().flatten())
+                    .and_then(|v| {
+                        let mut parts = v.split('-');
+                        let first = parts.next()?.parse::<usize>().ok()?.clamp(2, MAX_HARMONIC_COUNT);
+                        let last = parts.next()?.parse::<usize>().ok()?.clamp(first, MAX_HARMONIC_COUNT);
+                        let method = DecayFitMethod::from_key(parts.next().unwrap_or_default());
+                        Some(DecayFit { first_harmonic: first, last_harmonic: last, method })
+                    })
+                    .unwrap_or_default()
+            }),
+            detected_fundamental: RwSignal::new(None),
+            fundamental_override_hz: RwSignal::new(None),
+            contour_smoothing: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_contour_smoothing").ok().flatten())
+                    .map(|v| crate::dsp::smoothing::Smoothing::from_key(&v))
+                    .unwrap_or_default()
+            }),
 
             hash_computing: RwSignal::new(false),
             hash_generation: RwSignal::new(0),
@@ -1891,8 +3339,40 @@ impl AppState {
             selected_annotation_ids: RwSignal::new(Vec::new()),
             last_clicked_annotation_id: RwSignal::new(None),
             selection_auto_focus: RwSignal::new(false),
+            snap_to_call: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_snap_to_call").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            snap_threshold_db: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_snap_threshold_db").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(6.0, 60.0))
+                    .unwrap_or(20.0)
+            }),
+            snap_preview: RwSignal::new(None),
+            selection_focus_dim: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_selection_focus_dim").ok().flatten())
+                    .map(|v| SelectionFocusDim::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            focus_dim_opacity: RwSignal::new(stored_opacity("oversample_focus_dim_opacity", DEFAULT_FOCUS_DIM_OPACITY)),
             annotation_auto_focus: RwSignal::new(false),
             export_use_region_focus: RwSignal::new(true),
+            clip_fade_ms: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_clip_fade_ms").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(0.0, crate::dsp::fade::MAX_EDGE_FADE_MS))
+                    .unwrap_or(crate::dsp::fade::DEFAULT_EDGE_FADE_MS)
+            }),
             dragging_annotation_id: RwSignal::new(None),
             drop_target: RwSignal::new(None),
             undo_stack: RwSignal::new(UndoStack::default()),
@@ -1921,6 +3401,15 @@ impl AppState {
             display_auto_gain: RwSignal::new(false),
             display_eq: RwSignal::new(false),
             display_noise_filter: RwSignal::new(false),
+            display_pre_emphasis_db_oct: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_display_pre_emphasis").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(0.0, MAX_PRE_EMPHASIS_DB_OCT))
+                    .unwrap_or(0.0)
+            }),
             display_transform: RwSignal::new(false),
 
             zc_saved_display_auto_gain: RwSignal::new(false),
@@ -1946,6 +3435,14 @@ impl AppState {
             display_decimate_effective: RwSignal::new(0),
             browser_sample_rate: RwSignal::new(0),
             display_gain_boost: RwSignal::new(0.0),
+            spect_auto_exposure: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_spect_auto_exposure").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            auto_exposure_db: RwSignal::new(0.0),
             display_nr_strength: RwSignal::new(0.8),
             display_auto_noise_floor: RwSignal::new(None),
 
@@ -1996,6 +3493,27 @@ impl AppState {
             bat_book_last_clicked_id: RwSignal::new(None),
             bat_book_auto_focus: RwSignal::new(true),
             show_clock_time: RwSignal::new(false),
+            time_tick_density: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_time_tick_density").ok().flatten())
+                    .map(|v| TimeTickDensity::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            time_gridlines: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_time_gridlines").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            no_data_fill: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_no_data_fill").ok().flatten())
+                    .map(|v| NoDataFill::from_key(&v))
+                    .unwrap_or_default()
+            }),
             shield_style: RwSignal::new({
                 web_sys::window()
                     .and_then(|w| w.local_storage().ok().flatten())
@@ -2003,6 +3521,153 @@ impl AppState {
                     .map(|v| ShieldStyle::from_key(&v))
                     .unwrap_or_default()
             }),
+            marker_colors: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_marker_colors").ok().flatten())
+                    .map(|v| MarkerColors::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            ui_theme: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_ui_theme").ok().flatten())
+                    .map(|v| UiTheme::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            high_contrast: RwSignal::new(false),
+            freq_marker_interval_khz: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_freq_marker_interval").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| FREQ_MARKER_INTERVALS_KHZ.contains(v))
+                    .unwrap_or(0.0)
+            }),
+            cursor_db_reference: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_cursor_db_ref").ok().flatten())
+                    .map(|v| DbReference::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            metadata_overlap: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_metadata_overlap").ok().flatten())
+                    .map(|v| MetadataOverlap::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            tile_memory_budget: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_tile_memory").ok().flatten())
+                    .map(|v| TileMemoryBudget::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            tile_prefetch: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_tile_prefetch").ok().flatten())
+                    .map(|v| TilePrefetch::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            tile_scheduling: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_tile_scheduling").ok().flatten())
+                    .map(|v| TileScheduling::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            preview_detail: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_preview_detail").ok().flatten())
+                    .map(|v| PreviewDetail::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            preview_rerender_progress: RwSignal::new(None),
+            max_concurrent_loads: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_max_concurrent_loads").ok().flatten())
+                    .and_then(|v| v.parse::<usize>().ok())
+                    .map(|v| v.clamp(1, MAX_CONCURRENT_LOADS_LIMIT))
+                    .unwrap_or(DEFAULT_MAX_CONCURRENT_LOADS)
+            }),
+            single_pass_max_secs: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_single_pass_max_secs").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(0.0, SINGLE_PASS_MAX_SECS_LIMIT))
+                    .unwrap_or(DEFAULT_SINGLE_PASS_MAX_SECS)
+            }),
+            import_trim_silence: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_import_trim").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            import_trim_threshold_db: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_import_trim_threshold_db").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(1.0, 60.0))
+                    .unwrap_or(DEFAULT_IMPORT_TRIM_THRESHOLD_DB)
+            }),
+            import_trim_pad_ms: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_import_trim_pad_ms").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(0.0, 10_000.0))
+                    .unwrap_or(DEFAULT_IMPORT_TRIM_PAD_MS)
+            }),
+            freq_unit: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_freq_unit").ok().flatten())
+                    .map(|v| crate::units::FreqUnit::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            duration_unit: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_duration_unit").ok().flatten())
+                    .map(|v| crate::units::DurationUnit::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            selection_color: RwSignal::new(stored_color("oversample_selection_color", DEFAULT_SELECTION_COLOR)),
+            harmonic_shadow_color: RwSignal::new(stored_color("oversample_harmonic_shadow_color", DEFAULT_HARMONIC_SHADOW_COLOR)),
+            spectrogram_background: RwSignal::new(stored_color("oversample_spectrogram_background", DEFAULT_SPECTROGRAM_BACKGROUND)),
+            playhead_color: RwSignal::new(stored_color("oversample_playhead_color", DEFAULT_PLAYHEAD_COLOR)),
+            playhead_opacity: RwSignal::new(stored_opacity("oversample_playhead_opacity", DEFAULT_OVERLAY_OPACITY)),
+            playhead_trail: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_playhead_trail").ok().flatten())
+                    .map(|v| PlayheadTrail::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            playhead_trail_secs: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_playhead_trail_secs").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .filter(|v| v.is_finite())
+                    .map(|v| v.clamp(MIN_PLAYHEAD_TRAIL_SECS, MAX_PLAYHEAD_TRAIL_SECS))
+                    .unwrap_or(DEFAULT_PLAYHEAD_TRAIL_SECS)
+            }),
+            playhead_trail_color: RwSignal::new(stored_color("oversample_playhead_trail_color", DEFAULT_PLAYHEAD_TRAIL_COLOR)),
+            playhead_trail_opacity: RwSignal::new(stored_opacity("oversample_playhead_trail_opacity", DEFAULT_OVERLAY_OPACITY)),
+            bookmark_color: RwSignal::new(stored_color("oversample_bookmark_color", DEFAULT_BOOKMARK_COLOR)),
+            bookmark_opacity: RwSignal::new(stored_opacity("oversample_bookmark_opacity", DEFAULT_OVERLAY_OPACITY)),
             show_status_bar: RwSignal::new({
                 web_sys::window()
                     .and_then(|w| w.local_storage().ok().flatten())
@@ -2010,12 +3675,135 @@ impl AppState {
                     .map(|v| v == "true")
                     .unwrap_or(false)
             }),
+            show_crosshair: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_show_crosshair").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            show_envelope_overlay: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_show_envelope").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            reference_lines: RwSignal::new(crate::reference_lines::load()),
+            show_reference_lines: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_show_reference_lines").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            species_hint_enabled: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_species_hint").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            noise_floor_method: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_noise_floor_method").ok().flatten())
+                    .and_then(|v| crate::dsp::bit_analysis::NoiseFloorMethod::from_key(&v))
+                    .unwrap_or_default()
+            }),
+            noise_floor_silence_db: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_noise_floor_silence_db").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(-140.0, -20.0))
+                    .unwrap_or(-80.0)
+            }),
+            offline_mode: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_offline_mode").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            fetch_timeout_secs: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_fetch_timeout_secs").ok().flatten())
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .map(|v| v.clamp(MIN_FETCH_TIMEOUT_SECS, MAX_FETCH_TIMEOUT_SECS))
+                    .unwrap_or(DEFAULT_FETCH_TIMEOUT_SECS)
+            }),
+            fetch_retries: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_fetch_retries").ok().flatten())
+                    .and_then(|v| v.parse::<u32>().ok())
+                    .map(|v| v.min(MAX_FETCH_RETRIES))
+                    .unwrap_or(DEFAULT_FETCH_RETRIES)
+            }),
+            dc_warn_full_scale: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_dc_warn_fs").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(0.0, 1.0))
+                    .unwrap_or(crate::dsp::dc::DEFAULT_DC_WARN_FULL_SCALE)
+            }),
+            dc_warn_rms_ratio: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_dc_warn_rms").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(0.0, 1.0))
+                    .unwrap_or(crate::dsp::dc::DEFAULT_DC_WARN_RMS_RATIO)
+            }),
             focus_stack: RwSignal::new(crate::focus_stack::FocusStack::new()),
-            clean_view: RwSignal::new(false),
+            clean_view: RwSignal::new(initial_clean_view_pinned),
+            clean_view_pinned: RwSignal::new(initial_clean_view_pinned),
 
             // Export UI
             export_section_open: RwSignal::new(false),
             export_format: RwSignal::new(ExportFormat::default()),
+            export_crop_to_ff: RwSignal::new(false),
+            export_crop_audio_to_ff: RwSignal::new(false),
+            export_params_caption: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_export_params_caption").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            export_full_px_per_sec: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_export_full_px_per_sec").ok().flatten())
+                    .and_then(|v| v.parse::<f64>().ok())
+                    .map(|v| v.clamp(MIN_FULL_EXPORT_PX_PER_SEC, MAX_FULL_EXPORT_PX_PER_SEC))
+                    .unwrap_or(200.0)
+            }),
+            export_full_progress: RwSignal::new(None),
+            keep_exact_samples: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_keep_exact_samples").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
+            export_wav_format: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_export_wav_format").ok().flatten())
+                    .and_then(|v| crate::audio::convert::SampleFormat::from_key(&v))
+                    .unwrap_or(crate::audio::convert::SampleFormat::Int16)
+            }),
+            export_wav_dither: RwSignal::new({
+                web_sys::window()
+                    .and_then(|w| w.local_storage().ok().flatten())
+                    .and_then(|ls| ls.get_item("oversample_export_wav_dither").ok().flatten())
+                    .map(|v| v == "true")
+                    .unwrap_or(false)
+            }),
             video_export_progress: RwSignal::new(None),
             video_export_status: RwSignal::new(None),
             video_export_cancel: RwSignal::new(false),
@@ -2043,6 +3831,21 @@ impl AppState {
         if ids.len() == 1 { Some(ids[0].clone()) } else { None }
     }
 
+    /// STFT parameters of the spectrogram as currently displayed (FFT mode
+    /// and zoom level), or `None` with no file open. Tracked.
+    pub fn spect_params(&self) -> Option<SpectParams> {
+        let sample_rate = self.files.with(|files| {
+            files.get(self.current_file_index.get()?).map(|f| f.spectrogram.sample_rate)
+        })?;
+        let lod = crate::canvas::tile_cache::main_view_lod(self.zoom_level.get());
+        Some(SpectParams::for_lod(self.spect_fft_mode.get(), lod, sample_rate))
+    }
+
+    /// Current index of the file `key` names, if it is still loaded.
+    pub fn file_index_by_key(&self, key: &FileKey) -> Option<usize> {
+        self.files.with(|files| files.iter().position(|f| f.key() == *key))
+    }
+
     pub fn current_file(&self) -> Option<LoadedFile> {
         let files = self.files.get();
         let idx = self.current_file_index.get()?;
@@ -2155,6 +3958,15 @@ impl AppState {
         self.status_message.set(Some(msg.into()));
     }
 
+    /// Pixel size to compute file previews at: the file-list thumbnail's
+    /// width (sidebar minus item padding) at this display's pixel ratio,
+    /// scaled by the preview detail setting.
+    pub fn preview_size(&self) -> (u32, u32) {
+        let dpr = web_sys::window().map(|w| w.device_pixel_ratio()).unwrap_or(1.0);
+        let css_width = self.sidebar_width.get_untracked() - 32.0;
+        self.preview_detail.get_untracked().preview_size(css_width, dpr)
+    }
+
     pub fn show_error_toast(&self, msg: impl Into<String>) {
         self.status_level.set(StatusLevel::Error);
         self.status_message.set(Some(msg.into()));
@@ -2207,7 +4019,7 @@ impl AppState {
 
     /// Temporarily suspend follow-cursor when the user scrolls or pans.
     /// Re-engagement happens automatically once the playhead is on-screen
-    /// and 200ms have passed since the last scroll action.
+    /// and `follow_resume_ms` have passed since the last scroll action.
     pub fn suspend_follow(&self) {
         if self.is_playing.get_untracked() {
             self.user_panned_during_playback.set(true);
@@ -2218,6 +4030,266 @@ impl AppState {
         }
     }
 
+    /// Add a custom colormap, replacing any with the same name, and return
+    /// the `Colormap` that selects it. `None` if the list is full.
+    pub fn save_custom_colormap(&self, map: crate::canvas::custom_colormap::CustomColormap) -> Option<Colormap> {
+        let mut index = None;
+        self.custom_colormaps.update(|maps| {
+            if let Some(i) = maps.iter().position(|m| m.name == map.name) {
+                maps[i] = map;
+                index = Some(i);
+            } else if maps.len() < crate::custom_colormaps::MAX_CUSTOM_COLORMAPS {
+                maps.push(map);
+                index = Some(maps.len() - 1);
+            }
+        });
+        self.custom_colormaps.with_untracked(|maps| crate::custom_colormaps::save(maps));
+        index.map(|i| Colormap::Custom(i as u8))
+    }
+
+    /// Delete a custom colormap. Anything using it falls back to its default
+    /// colormap; anything using a later one follows it to its new index.
+    pub fn remove_custom_colormap(&self, index: usize) {
+        if index >= self.custom_colormaps.with_untracked(|m| m.len()) {
+            return;
+        }
+        self.custom_colormaps.update(|maps| { maps.remove(index); });
+        self.custom_colormaps.with_untracked(|maps| crate::custom_colormaps::save(maps));
+        let remap = |cm: Colormap| match cm {
+            Colormap::Custom(i) if i as usize == index => None,
+            Colormap::Custom(i) if i as usize > index => Some(Colormap::Custom(i - 1)),
+            cm => Some(cm),
+        };
+        self.colormap_preference.update(|cm| *cm = remap(*cm).unwrap_or(Colormap::Viridis));
+        self.hfr_colormap_preference.update(|cm| *cm = remap(*cm).unwrap_or(Colormap::Inferno));
+        self.coherence_colormap.update(|cm| *cm = cm.and_then(remap));
+    }
+
+    /// Initial vertical zoom for a newly loaded file: the pinned default range,
+    /// limited to the file's Nyquist. `(None, None)` shows the full range.
+    pub fn default_display_range_for(&self, sample_rate: u32) -> (Option<f64>, Option<f64>) {
+        let Some((lo, hi)) = self.default_display_range.get_untracked() else {
+            return (None, None);
+        };
+        let nyquist = sample_rate as f64 / 2.0;
+        if lo >= nyquist {
+            return (None, None);
+        }
+        ((lo > 0.0).then_some(lo), (hi < nyquist).then_some(hi))
+    }
+
+    /// Sample rate of the live mic samples on the frontend: the device rate,
+    /// divided by any display decimation in the native backend.
+    pub fn mic_stream_rate(&self) -> u32 {
+        self.mic_sample_rate.get_untracked() / self.mic_display_factor.get_untracked().max(1)
+    }
+
+    /// Fundamental to relate selections to: the manual override, else the
+    /// one detected for the current file. The flag is true for the override.
+    pub fn reference_fundamental(&self) -> Option<(f64, bool)> {
+        if let Some(f0) = self.fundamental_override_hz.get() {
+            return Some((f0, true));
+        }
+        let idx = self.current_file_index.get()?;
+        self.detected_fundamental.get()
+            .filter(|&(i, _)| i == idx)
+            .map(|(_, f0)| (f0, false))
+    }
+
+    /// Pin clean view (spectrogram only, no overlays) on or off, persisting
+    /// the choice.
+    pub fn set_clean_view_pinned(&self, pinned: bool) {
+        self.clean_view_pinned.set(pinned);
+        self.clean_view.set(pinned);
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = ls.set_item("oversample_clean_view_pinned", if pinned { "true" } else { "false" });
+        }
+    }
+
+    /// Re-engage a suspended follow-cursor straight away.
+    pub fn resume_follow(&self) {
+        self.follow_suspended.set(false);
+        self.follow_visible_since.set(None);
+    }
+
+    /// (time resolution, duration) of what the main view shows: the active
+    /// timeline, or else the current file. `None` with nothing open.
+    pub fn view_time_extent(&self) -> Option<(f64, f64)> {
+        let files = self.files.get_untracked();
+        if let Some(tl) = self.active_timeline.get_untracked() {
+            let time_res = tl.segments.first().and_then(|s| files.get(s.file_index))
+                .map(|f| f.spectrogram.time_resolution).unwrap_or(1.0);
+            return Some((time_res, tl.total_duration_secs));
+        }
+        let file = files.get(self.current_file_index.get_untracked()?)?;
+        Some((file.spectrogram.time_resolution, file.audio.duration_secs))
+    }
+
+    /// Highest zoom allowed: `viewport::MAX_ZOOM`, lowered by the zoom limit
+    /// so an analysis window is never drawn wider than the chosen width.
+    pub fn max_zoom(&self) -> f64 {
+        match self.zoom_limit_px.get_untracked() {
+            Some(px) => {
+                let mode = self.spect_fft_mode.get_untracked();
+                crate::canvas::tile_cache::window_zoom_limit(px, |lod| mode.fft_for_lod(lod))
+            }
+            None => crate::viewport::MAX_ZOOM,
+        }
+    }
+
+    /// Zoom so the whole file (or timeline) fits the canvas width, from the start.
+    pub fn zoom_to_fit(&self) {
+        let Some((time_res, duration)) = self.view_time_extent() else { return };
+        let canvas_w = self.spectrogram_canvas_width.get_untracked();
+        if canvas_w <= 0.0 || time_res <= 0.0 || duration <= 0.0 {
+            return;
+        }
+        self.push_nav();
+        let zoom = crate::viewport::fit_zoom(canvas_w, time_res, duration, self.max_zoom());
+        let visible_time = crate::viewport::visible_time(canvas_w, zoom, time_res);
+        let from_here = self.from_here_fraction();
+        self.suspend_follow();
+        self.zoom_level.set(zoom);
+        self.scroll_offset.set(crate::viewport::clamp_scroll_for_mode(0.0, duration, visible_time, from_here));
+    }
+
+    /// Move the "play from here" marker to `fraction` of the view width,
+    /// persisting it when the marker was placed by clicking.
+    pub fn set_play_anchor_fraction(&self, fraction: f64) {
+        let fraction = fraction.clamp(0.0, 1.0);
+        self.play_anchor_fraction.set(fraction);
+        if self.play_anchor.get_untracked() == PlayAnchor::Click {
+            if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+                let _ = ls.set_item("oversample_play_anchor_fraction", &fraction.to_string());
+            }
+        }
+    }
+
+    /// The marker's fraction of the view width when the play start mode
+    /// scrolls with "play from here" bounds, None otherwise. Pass to
+    /// `viewport::clamp_scroll_for_mode`.
+    pub fn from_here_fraction(&self) -> Option<f64> {
+        self.play_start_mode.get_untracked().uses_from_here()
+            .then(|| self.play_anchor_fraction.get_untracked())
+    }
+
+    /// Pulse detection parameters as set in the Pulses panel, bandpassed to
+    /// the focus band when one is set.
+    pub fn pulse_detection_params(&self) -> crate::dsp::pulse_detect::PulseDetectionParams {
+        let band_ff_lo = self.band_ff_freq_lo.get_untracked();
+        let band_ff_hi = self.band_ff_freq_hi.get_untracked();
+        crate::dsp::pulse_detect::PulseDetectionParams {
+            min_pulse_duration_ms: self.pulse_min_duration_ms.get_untracked(),
+            max_pulse_duration_ms: self.pulse_max_duration_ms.get_untracked(),
+            min_gap_ms: self.pulse_min_gap_ms.get_untracked(),
+            threshold_db: self.pulse_threshold_db.get_untracked(),
+            bandpass_low_hz: band_ff_lo,
+            bandpass_high_hz: if band_ff_hi > band_ff_lo { band_ff_hi } else { 0.0 },
+            min_snr_db: self.pulse_min_snr_db.get_untracked(),
+            contour_smoothing: self.contour_smoothing.get_untracked(),
+        }
+    }
+
+    /// Flip the frequency axis (low frequencies at the top), persisting the choice.
+    pub fn set_freq_axis_flipped(&self, flipped: bool) {
+        crate::viewport::set_freq_axis_flipped(flipped);
+        self.freq_axis_flipped.set(flipped);
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = ls.set_item("oversample_freq_axis_flipped", if flipped { "true" } else { "false" });
+        }
+    }
+
+    /// Choose where the "play from here" marker sits, persisting the choice.
+    pub fn set_play_anchor(&self, anchor: PlayAnchor) {
+        self.play_anchor.set(anchor);
+        self.set_play_anchor_fraction(anchor.fraction());
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let _ = ls.set_item("oversample_play_anchor", anchor.key());
+        }
+    }
+
+    /// Show `tv` in the main view in place of the current file, selecting its
+    /// files and zooming to fit the whole timeline.
+    pub fn open_timeline(&self, tv: crate::timeline::TimelineView) {
+        let timeline_duration = tv.total_duration_secs;
+        let primary_time_res = self.files.with_untracked(|files| {
+            tv.segments.first()
+                .and_then(|s| files.get(s.file_index))
+                .map(|f| f.spectrogram.time_resolution)
+                .unwrap_or(1.0)
+        });
+        let canvas_w = self.spectrogram_canvas_width.get_untracked();
+        self.selected_file_indices.set(tv.segments.iter().map(|s| s.file_index).collect());
+        self.active_timeline.set(Some(tv));
+        self.active_timeline_track.set(None);
+        self.current_file_index.set(None);
+        self.suspend_follow();
+        if canvas_w > 0.0 && primary_time_res > 0.0 && timeline_duration > 0.0 {
+            let fit_zoom = crate::viewport::fit_zoom(canvas_w, primary_time_res, timeline_duration, self.max_zoom());
+            self.zoom_level.set(fit_zoom);
+            let visible_time = crate::viewport::visible_time(canvas_w, fit_zoom, primary_time_res);
+            let from_here = self.from_here_fraction();
+            self.scroll_offset.set(crate::viewport::clamp_scroll_for_mode(0.0, timeline_duration, visible_time, from_here));
+        } else {
+            self.scroll_offset.set(0.0);
+        }
+    }
+
+    /// Centre the view on `time` (clamped to the file or timeline), recording
+    /// a nav history entry. With `move_playhead`, the playhead and "play
+    /// from here" position move there too. Returns the clamped time, or
+    /// `None` with nothing open.
+    pub fn go_to_time(&self, time: f64, move_playhead: bool) -> Option<f64> {
+        let (time_res, duration) = self.view_time_extent()?;
+        let time = time.clamp(0.0, duration.max(0.0));
+        let canvas_w = self.spectrogram_canvas_width.get_untracked();
+        let zoom = self.zoom_level.get_untracked();
+        let visible_time = crate::viewport::visible_time(canvas_w, zoom, time_res);
+        let from_here = self.from_here_fraction();
+        self.push_nav();
+        self.suspend_follow();
+        self.scroll_offset.set(crate::viewport::clamp_scroll_for_mode(
+            time - visible_time / 2.0, duration, visible_time, from_here,
+        ));
+        if move_playhead {
+            self.play_from_here_time.set(time);
+            self.playhead_time.set(time);
+            if self.is_playing.get_untracked() {
+                crate::audio::playback::play_from_time(self, time);
+            }
+        }
+        Some(time)
+    }
+
+    /// Highest frequency the display range may reach for a file whose
+    /// Nyquist is `nyquist`: Nyquist itself, or `viewport::ABOVE_NYQUIST_FACTOR`
+    /// times it when `freq_above_nyquist` is on.
+    pub fn freq_view_ceiling(&self, nyquist: f64) -> f64 {
+        if self.freq_above_nyquist.get_untracked() {
+            nyquist * crate::viewport::ABOVE_NYQUIST_FACTOR
+        } else {
+            nyquist
+        }
+    }
+
+    /// Return to `viewport::DEFAULT_ZOOM`, keeping the centre of the view in place.
+    pub fn reset_zoom(&self) {
+        let Some((time_res, duration)) = self.view_time_extent() else { return };
+        let canvas_w = self.spectrogram_canvas_width.get_untracked();
+        if canvas_w <= 0.0 || time_res <= 0.0 {
+            return;
+        }
+        self.push_nav();
+        let old_visible = crate::viewport::visible_time(canvas_w, self.zoom_level.get_untracked(), time_res);
+        let centre = self.scroll_offset.get_untracked() + old_visible / 2.0;
+        let zoom = crate::viewport::DEFAULT_ZOOM.min(self.max_zoom());
+        let visible_time = crate::viewport::visible_time(canvas_w, zoom, time_res);
+        let from_here = self.from_here_fraction();
+        self.suspend_follow();
+        self.zoom_level.set(zoom);
+        self.scroll_offset.set(crate::viewport::clamp_scroll_for_mode(centre - visible_time / 2.0, duration, visible_time, from_here));
+    }
+
     /// Suspend the waterfall smooth-scroll animation for `delay_ms` from now so
     /// the user can drag backwards during live listening/recording without
     /// the display immediately snapping back to the live edge. Called on every
@@ -2229,6 +4301,21 @@ impl AppState {
         }
     }
 
+    /// Display settings for the HFR focus band when it has its own contrast.
+    /// `base` supplies the shared gain and reference level. Untracked.
+    pub fn hfr_focus_display_settings(&self, base: &crate::types::SpectDisplaySettings) -> Option<crate::types::SpectDisplaySettings> {
+        if !self.hfr_focus_contrast.get_untracked() {
+            return None;
+        }
+        let range_db = self.hfr_focus_range_db.get_untracked();
+        Some(crate::types::SpectDisplaySettings {
+            floor_db: -range_db,
+            range_db,
+            gamma: self.hfr_focus_gamma.get_untracked(),
+            gain_db: base.gain_db + self.hfr_focus_gain_db.get_untracked(),
+        })
+    }
+
     pub fn compute_auto_gain(&self) -> f64 {
         let files = self.files.get();
         let idx = self.current_file_index.get();
@@ -2329,6 +4416,63 @@ impl AppState {
         self.set_band_ff_range(lo, hi);
     }
 
+    /// Add an extra focus band. Defaults to the 2nd harmonic of the main
+    /// BandFF range, clamped to `file_max_freq`. No-op at the band limit.
+    pub fn add_extra_focus_band(&self, file_max_freq: f64) {
+        use crate::canvas::spectrogram_renderer::MAX_EXTRA_FOCUS_BANDS;
+        let lo = self.band_ff_freq_lo.get_untracked();
+        let hi = self.band_ff_freq_hi.get_untracked();
+        if hi <= lo { return; }
+        let (mut new_lo, mut new_hi) = (lo * 2.0, (hi * 2.0).min(file_max_freq));
+        if new_hi - new_lo < 500.0 {
+            // No room for the harmonic: take the band just below the main one instead.
+            new_hi = lo;
+            new_lo = (lo - (hi - lo)).max(0.0);
+        }
+        if new_hi - new_lo < 500.0 { return; }
+        self.extra_focus_bands.update(|bands| {
+            if bands.len() < MAX_EXTRA_FOCUS_BANDS {
+                bands.push((new_lo, new_hi));
+            }
+        });
+        self.save_extra_focus_bands();
+    }
+
+    /// Set the range of an extra focus band (keeps at least 500 Hz width).
+    pub fn set_extra_focus_band(&self, index: usize, lo: f64, hi: f64) {
+        if hi - lo < 500.0 { return; }
+        self.extra_focus_bands.update(|bands| {
+            if let Some(b) = bands.get_mut(index) {
+                *b = (lo.max(0.0), hi);
+            }
+        });
+        self.save_extra_focus_bands();
+    }
+
+    /// Store the extra focus bands as `lo,hi;lo,hi` in Hz.
+    fn save_extra_focus_bands(&self) {
+        if let Some(ls) = web_sys::window().and_then(|w| w.local_storage().ok().flatten()) {
+            let bands = self.extra_focus_bands.with_untracked(|bands| {
+                bands.iter().map(|(lo, hi)| format!("{lo},{hi}")).collect::<Vec<_>>().join(";")
+            });
+            let _ = ls.set_item("oversample_extra_focus_bands", &bands);
+        }
+    }
+
+    /// Remove an extra focus band.
+    pub fn remove_extra_focus_band(&self, index: usize) {
+        self.extra_focus_bands.update(|bands| {
+            if index < bands.len() {
+                bands.remove(index);
+            }
+        });
+        self.save_extra_focus_bands();
+        if matches!(self.spec_drag_handle.get_untracked(),
+            Some(SpectrogramHandle::ExtraBandUpper(_) | SpectrogramHandle::ExtraBandLower(_))) {
+            self.spec_drag_handle.set(None);
+        }
+    }
+
     /// Push a bat book BandFF override. Enables HFR if not already on.
     pub fn push_bat_book_ff(&self, lo: f64, hi: f64) {
         use crate::focus_stack::{FocusRange, FocusSource};
@@ -2466,6 +4610,18 @@ impl AppState {
         }
     }
 
+    /// Set the heterodyne frequency from the numeric input, presets or arrow keys.
+    /// Clamps to 1 kHz..Nyquist of the current file and turns auto tuning off.
+    pub fn set_het_frequency_manual(&self, hz: f64) {
+        let max_freq = self.files.with_untracked(|files| {
+            self.current_file_index.get_untracked()
+                .and_then(|i| files.get(i))
+                .map(|f| f.spectrogram.max_freq)
+        }).unwrap_or(96_000.0);
+        self.het_freq_auto.set(false);
+        self.het_frequency.set(hz.clamp(1000.0, max_freq.max(1000.0)));
+    }
+
     /// Toggle HFR on/off. Saves/restores playback mode, bandpass, and gain.
     pub fn toggle_hfr(&self) {
         // Swap gain_db between HFR-on and HFR-off so we don't blast eardrums
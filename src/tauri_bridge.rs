@@ -15,22 +15,28 @@ pub fn get_tauri_internals() -> Option<JsValue> {
 
 /// Invoke a Tauri command and return the result as a JsValue.
 pub async fn tauri_invoke(cmd: &str, args: &JsValue) -> Result<JsValue, String> {
-    let tauri = get_tauri_internals().ok_or("Not running in Tauri")?;
+    tauri_invoke_raw(cmd, args)
+        .await
+        .map_err(|e| format!("Command '{}' failed: {:?}", cmd, e))
+}
+
+/// Like `tauri_invoke`, but a failed command yields the error value the
+/// command returned (a string, or an object for structured errors).
+pub async fn tauri_invoke_raw(cmd: &str, args: &JsValue) -> Result<JsValue, JsValue> {
+    let tauri = get_tauri_internals().ok_or_else(|| JsValue::from_str("Not running in Tauri"))?;
     let invoke = js_sys::Reflect::get(&tauri, &JsValue::from_str("invoke"))
-        .map_err(|_| "No invoke function")?;
+        .map_err(|_| JsValue::from_str("No invoke function"))?;
     let invoke_fn = js_sys::Function::from(invoke);
 
     let promise_val = invoke_fn
         .call2(&tauri, &JsValue::from_str(cmd), args)
-        .map_err(|e| format!("Invoke call failed: {:?}", e))?;
+        .map_err(|e| JsValue::from_str(&format!("Invoke call failed: {:?}", e)))?;
 
     let promise: js_sys::Promise = promise_val
         .dyn_into()
-        .map_err(|_| "Result is not a Promise")?;
+        .map_err(|_| JsValue::from_str("Result is not a Promise"))?;
 
-    JsFuture::from(promise)
-        .await
-        .map_err(|e| format!("Command '{}' failed: {:?}", cmd, e))
+    JsFuture::from(promise).await
 }
 
 /// Invoke a Tauri command with no arguments.
@@ -1,6 +1,10 @@
 use crate::state::LoadedFile;
 use crate::components::file_sidebar::file_groups::{self, TrackInfo};
 
+/// How far a file's timestamp may be from the previous file's end and still
+/// count as contiguous. Recorder timestamps have one-second resolution.
+const SEQUENCE_GAP_TOLERANCE_SECS: f64 = 2.0;
+
 /// Runtime view of a timeline — maps timeline-local time to file data.
 /// Built from selected files and their recording timestamps.
 #[derive(Clone, Debug)]
@@ -13,6 +17,8 @@ pub struct TimelineView {
     pub origin_epoch_ms: f64,
     /// Multitrack groups available for track switching.
     pub multitrack_groups: Vec<MultitrackOption>,
+    /// Name shown in place of "Timeline" (e.g. a joined sequence's prefix).
+    pub label: Option<String>,
 }
 
 /// A single file's placement on the timeline.
@@ -167,6 +173,100 @@ impl TimelineView {
             total_duration_secs,
             origin_epoch_ms: origin_ms,
             multitrack_groups,
+            label: None,
+        })
+    }
+
+    /// Join a rollover sequence (one recording the detector split into
+    /// several files) into a gapless timeline.
+    ///
+    /// `members` are (file index, sequence number) pairs in sequence order.
+    /// The numbers must run without gaps or repeats, every file must share
+    /// the first file's sample rate and channel count, and where timestamps
+    /// are available each file must start where the previous one ended.
+    /// Segments are placed end to end, so the timeline is continuous even if
+    /// the timestamps drift by a fraction of a second.
+    pub fn join_sequence(members: &[(usize, u32)], files: &[LoadedFile], label: &str) -> Result<Self, String> {
+        if members.len() < 2 {
+            return Err("A sequence needs at least two files".into());
+        }
+        let name = |idx: usize| files.get(idx).map_or("?", |f| f.name.as_str());
+        for pair in members.windows(2) {
+            let ((prev_idx, prev_number), (idx, number)) = (pair[0], pair[1]);
+            if number == prev_number {
+                return Err(format!("{} and {} have the same sequence number", name(prev_idx), name(idx)));
+            }
+            if prev_number.checked_add(1) != Some(number) {
+                return Err(format!("Missing file(s) between {} and {}", name(prev_idx), name(idx)));
+            }
+        }
+        let file_indices: Vec<usize> = members.iter().map(|&(idx, _)| idx).collect();
+        Self::end_to_end(&file_indices, files, label, true)
+    }
+
+    /// Concatenate any loaded files, in the given order, into one gapless
+    /// virtual recording. Unlike `join_sequence` the files needn't be one
+    /// recording: timestamps are ignored, but the sample rate and channel
+    /// count must still match. The files themselves are untouched.
+    pub fn concatenate(file_indices: &[usize], files: &[LoadedFile], label: &str) -> Result<Self, String> {
+        if file_indices.len() < 2 {
+            return Err("Select at least two files".into());
+        }
+        Self::end_to_end(file_indices, files, label, false)
+    }
+
+    /// Place `file_indices` end to end. With `check_gaps`, neighbouring
+    /// files' timestamps must also say they are contiguous.
+    fn end_to_end(file_indices: &[usize], files: &[LoadedFile], label: &str, check_gaps: bool) -> Result<Self, String> {
+        let first = files.get(file_indices[0]).ok_or("File not loaded")?;
+
+        let mut segments = Vec::with_capacity(file_indices.len());
+        let mut cursor_secs = 0.0;
+        let mut prev: Option<&LoadedFile> = None;
+        for &idx in file_indices {
+            let f = files.get(idx).ok_or("File not loaded")?;
+            if f.audio.sample_rate != first.audio.sample_rate {
+                return Err(format!(
+                    "{} is {} Hz but {} is {} Hz",
+                    f.name, f.audio.sample_rate, first.name, first.audio.sample_rate,
+                ));
+            }
+            if f.audio.channels != first.audio.channels {
+                return Err(format!(
+                    "{} has {} channels but {} has {}",
+                    f.name, f.audio.channels, first.name, first.audio.channels,
+                ));
+            }
+            if let Some(p) = prev.filter(|_| check_gaps) {
+                if let (Some(prev_ms), Some(start_ms)) = (p.recording_start_epoch_ms(), f.recording_start_epoch_ms()) {
+                    let gap = (start_ms - prev_ms) / 1000.0 - p.audio.duration_secs;
+                    if gap > SEQUENCE_GAP_TOLERANCE_SECS {
+                        return Err(format!("{:.1} s gap between {} and {}", gap, p.name, f.name));
+                    }
+                    if gap < -SEQUENCE_GAP_TOLERANCE_SECS {
+                        return Err(format!("{} starts {:.1} s before {} ends", f.name, -gap, p.name));
+                    }
+                }
+            }
+
+            segments.push(TimelineSegment {
+                file_index: idx,
+                timeline_offset_secs: cursor_secs,
+                duration_secs: f.audio.duration_secs,
+                has_overlap: false,
+            });
+            cursor_secs += f.audio.duration_secs;
+            prev = Some(f);
+        }
+
+        // Clock times only hold across a timestamp-checked join
+        let origin_epoch_ms = if check_gaps { first.recording_start_epoch_ms() } else { None };
+        Ok(TimelineView {
+            segments,
+            total_duration_secs: cursor_secs,
+            origin_epoch_ms: origin_epoch_ms.unwrap_or(0.0),
+            multitrack_groups: Vec::new(),
+            label: Some(label.to_string()),
         })
     }
 
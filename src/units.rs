@@ -0,0 +1,108 @@
+//! Display units for frequencies and durations.
+//!
+//! Panels, the cursor readout and overlays format through these so the unit
+//! preference applies everywhere. Time *positions* (e.g. "1m30.500s") still go
+//! through `format_time`; only lengths of time follow `DurationUnit`.
+
+use crate::format_time::format_duration;
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum FreqUnit {
+    /// kHz from 1 kHz up, Hz below.
+    #[default]
+    Auto,
+    KHz,
+    Hz,
+}
+
+impl FreqUnit {
+    pub const ALL: [FreqUnit; 3] = [Self::Auto, Self::KHz, Self::Hz];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::KHz => "kHz",
+            Self::Hz => "Hz",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::KHz => "khz",
+            Self::Hz => "hz",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "khz" => Self::KHz,
+            "hz" => Self::Hz,
+            _ => Self::Auto,
+        }
+    }
+
+    /// e.g. "45.0 kHz" or "800 Hz".
+    pub fn format(self, hz: f64) -> String {
+        match self {
+            Self::Auto if hz.abs() < 1000.0 => format!("{:.0} Hz", hz),
+            Self::Auto | Self::KHz => format!("{:.1} kHz", hz / 1000.0),
+            Self::Hz => format!("{:.0} Hz", hz),
+        }
+    }
+
+    /// A band with one unit suffix, e.g. "20.0–45.5 kHz".
+    pub fn format_range(self, lo: f64, hi: f64) -> String {
+        match self {
+            Self::Auto if hi.abs() < 1000.0 => format!("{:.0}\u{2013}{:.0} Hz", lo, hi),
+            Self::Auto | Self::KHz => format!("{:.1}\u{2013}{:.1} kHz", lo / 1000.0, hi / 1000.0),
+            Self::Hz => format!("{:.0}\u{2013}{:.0} Hz", lo, hi),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Default)]
+pub enum DurationUnit {
+    /// ms under a second, seconds (and minutes) above.
+    #[default]
+    Auto,
+    Ms,
+    Seconds,
+}
+
+impl DurationUnit {
+    pub const ALL: [DurationUnit; 3] = [Self::Auto, Self::Ms, Self::Seconds];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Auto => "Auto",
+            Self::Ms => "ms",
+            Self::Seconds => "Seconds",
+        }
+    }
+
+    pub fn key(self) -> &'static str {
+        match self {
+            Self::Auto => "auto",
+            Self::Ms => "ms",
+            Self::Seconds => "s",
+        }
+    }
+
+    pub fn from_key(s: &str) -> Self {
+        match s {
+            "ms" => Self::Ms,
+            "s" => Self::Seconds,
+            _ => Self::Auto,
+        }
+    }
+
+    /// e.g. "3.2 ms" or "1.250s".
+    pub fn format(self, seconds: f64) -> String {
+        match self {
+            Self::Auto if seconds.abs() < 1.0 => format!("{:.1} ms", seconds.abs() * 1000.0),
+            Self::Ms => format!("{:.1} ms", seconds.abs() * 1000.0),
+            Self::Auto | Self::Seconds => format_duration(seconds, 3),
+        }
+    }
+}
@@ -1,4 +1,7 @@
 // SPDX-License-Identifier: GPL-3.0-only OR MIT OR Apache-2.0
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Default position of the "play from here" marker, as a fraction of the view width.
 pub const PLAY_FROM_HERE_FRACTION: f64 = 0.10;
 pub const FOLLOW_CURSOR_FRACTION: f64 = 0.20;
 pub const FOLLOW_CURSOR_EDGE_FRACTION: f64 = 0.80;
@@ -7,14 +10,20 @@ pub const FOLLOW_CURSOR_EDGE_FRACTION: f64 = 0.80;
 /// At zoom=512 the waveform shows ~1 pixel per sample; 2000 gives headroom.
 pub const MAX_ZOOM: f64 = 2000.0;
 pub const MIN_ZOOM: f64 = 0.02;
+/// Zoom a file opens at and "Reset zoom" returns to: one pixel per baseline column.
+pub const DEFAULT_ZOOM: f64 = 1.0;
+
+/// How far above the file's Nyquist the frequency axis may extend when
+/// "Allow above Nyquist" is on, as a multiple of Nyquist.
+pub const ABOVE_NYQUIST_FACTOR: f64 = 2.0;
 
 /// When the visible window is shorter than this (seconds), follow the cursor
 /// every frame instead of waiting for the 80% edge trigger.
 pub const FOLLOW_EXACT_THRESHOLD_SECS: f64 = 0.5;
 
-pub fn uses_from_here_bounds(enabled: bool) -> bool {
-    enabled
-}
+/// After a manual scroll suspends follow-cursor, how long (ms) since the last
+/// scroll before following resumes once the playhead is back on-screen.
+pub const DEFAULT_FOLLOW_RESUME_MS: f64 = 200.0;
 
 pub fn visible_time(canvas_width: f64, zoom: f64, time_resolution: f64) -> f64 {
     if canvas_width <= 0.0 || zoom <= 0.0 || time_resolution <= 0.0 {
@@ -24,20 +33,34 @@ pub fn visible_time(canvas_width: f64, zoom: f64, time_resolution: f64) -> f64 {
     }
 }
 
-pub fn play_from_here_time(scroll_offset: f64, visible_time: f64) -> f64 {
-    scroll_offset + visible_time * PLAY_FROM_HERE_FRACTION
+/// Whether the frequency axis runs low-at-top; set through
+/// `AppState::set_freq_axis_flipped`.
+static FREQ_AXIS_FLIPPED: AtomicBool = AtomicBool::new(false);
+
+pub fn freq_axis_flipped() -> bool {
+    FREQ_AXIS_FLIPPED.load(Ordering::Relaxed)
+}
+
+pub fn set_freq_axis_flipped(flipped: bool) {
+    FREQ_AXIS_FLIPPED.store(flipped, Ordering::Relaxed);
+}
+
+/// Time under the "play from here" marker, which sits at `fraction` of the view width.
+pub fn play_from_here_time(scroll_offset: f64, visible_time: f64, fraction: f64) -> f64 {
+    scroll_offset + visible_time * fraction
 }
 
-pub fn scroll_for_play_from_here(target_time: f64, visible_time: f64) -> f64 {
-    target_time - visible_time * PLAY_FROM_HERE_FRACTION
+pub fn scroll_for_play_from_here(target_time: f64, visible_time: f64, fraction: f64) -> f64 {
+    target_time - visible_time * fraction
 }
 
-pub fn scroll_bounds(duration: f64, visible_time: f64) -> (f64, f64) {
+/// Scroll bounds that let the marker at `fraction` reach both ends of the file.
+pub fn scroll_bounds(duration: f64, visible_time: f64, fraction: f64) -> (f64, f64) {
     if visible_time <= 0.0 {
         return (0.0, duration.max(0.0));
     }
 
-    let lead_in = visible_time * PLAY_FROM_HERE_FRACTION;
+    let lead_in = visible_time * fraction;
     let min_scroll = -lead_in;
     let max_scroll = (duration - lead_in).max(min_scroll);
     (min_scroll, max_scroll)
@@ -50,16 +73,17 @@ pub fn standard_scroll_bounds(duration: f64, visible_time: f64) -> (f64, f64) {
     (0.0, (duration - visible_time).max(0.0))
 }
 
-pub fn scroll_bounds_for_mode(duration: f64, visible_time: f64, from_here_mode: bool) -> (f64, f64) {
-    if uses_from_here_bounds(from_here_mode) {
-        scroll_bounds(duration, visible_time)
-    } else {
-        standard_scroll_bounds(duration, visible_time)
+/// `from_here` is the marker's fraction of the view width when "play from
+/// here" bounds apply (see `AppState::from_here_fraction`), None otherwise.
+pub fn scroll_bounds_for_mode(duration: f64, visible_time: f64, from_here: Option<f64>) -> (f64, f64) {
+    match from_here {
+        Some(fraction) => scroll_bounds(duration, visible_time, fraction),
+        None => standard_scroll_bounds(duration, visible_time),
     }
 }
 
-pub fn clamp_scroll(scroll_offset: f64, duration: f64, visible_time: f64) -> f64 {
-    let (min_scroll, max_scroll) = scroll_bounds(duration, visible_time);
+pub fn clamp_scroll(scroll_offset: f64, duration: f64, visible_time: f64, fraction: f64) -> f64 {
+    let (min_scroll, max_scroll) = scroll_bounds(duration, visible_time, fraction);
     scroll_offset.clamp(min_scroll, max_scroll)
 }
 
@@ -67,9 +91,9 @@ pub fn clamp_scroll_for_mode(
     scroll_offset: f64,
     duration: f64,
     visible_time: f64,
-    from_here_mode: bool,
+    from_here: Option<f64>,
 ) -> f64 {
-    let (min_scroll, max_scroll) = scroll_bounds_for_mode(duration, visible_time, from_here_mode);
+    let (min_scroll, max_scroll) = scroll_bounds_for_mode(duration, visible_time, from_here);
     scroll_offset.clamp(min_scroll, max_scroll)
 }
 
@@ -97,12 +121,13 @@ pub fn recording_zoom(canvas_width: f64, time_resolution: f64) -> f64 {
     (canvas_width * time_resolution / target_secs).max(0.005)
 }
 
-/// Compute zoom level that fits `duration` seconds into the canvas.
-pub fn fit_zoom(canvas_width: f64, time_resolution: f64, duration: f64) -> f64 {
+/// Compute zoom level that fits `duration` seconds into the canvas, no
+/// higher than `max_zoom` (see `AppState::max_zoom`).
+pub fn fit_zoom(canvas_width: f64, time_resolution: f64, duration: f64, max_zoom: f64) -> f64 {
     if canvas_width <= 0.0 || time_resolution <= 0.0 || duration <= 0.0 {
-        return 1.0;
+        return 1.0f64.min(max_zoom);
     }
-    (canvas_width * time_resolution / duration).clamp(MIN_ZOOM, MAX_ZOOM)
+    (canvas_width * time_resolution / duration).clamp(MIN_ZOOM, max_zoom.max(MIN_ZOOM))
 }
 
 pub fn data_region_px(
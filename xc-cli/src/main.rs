@@ -1,4 +1,5 @@
 use clap::{Parser, Subcommand};
+use oversample_core::audio::convert::{convert_bytes, ConvertSpec, SampleFormat};
 use std::path::PathBuf;
 use xc_lib::{api, cache, key_store, taxonomy, XC_GROUPS};
 
@@ -31,9 +32,11 @@ enum Commands {
     /// Browse species for a group
     Browse {
         /// Group name: bats, birds, frogs, grasshoppers, "land mammals"
-        group: String,
+        /// (default: the group saved with set-default)
+        group: Option<String>,
 
-        /// Filter by country
+        /// Filter by country (default: the country saved with set-default;
+        /// pass --country "" to browse all countries)
         #[arg(long)]
         country: Option<String>,
 
@@ -93,6 +96,18 @@ enum Commands {
     ShowKey,
     /// Remove the stored API key
     ClearKey,
+    /// Save a default group and/or country for browse (an empty value clears it)
+    SetDefault {
+        /// Default group: bats, birds, frogs, grasshoppers, "land mammals"
+        #[arg(long)]
+        group: Option<String>,
+
+        /// Default country filter
+        #[arg(long)]
+        country: Option<String>,
+    },
+    /// Show the stored browse defaults
+    ShowDefault,
     /// Migrate .xc.json files to new format (hashes nested under "_app")
     Migrate {
         /// Directory containing sounds/ with .xc.json files (default: current directory)
@@ -135,6 +150,33 @@ enum Commands {
         #[arg(long)]
         force: bool,
     },
+    /// Convert all cached recordings to mono WAV at one sample rate and bit depth
+    /// (e.g. for an ML dataset), copying the .xc.json sidecars alongside
+    Convert {
+        /// Output directory for the converted files
+        out_dir: PathBuf,
+
+        /// Directory containing sounds/ with .xc.json and audio files (default: current directory)
+        #[arg(long)]
+        cache_dir: Option<PathBuf>,
+
+        /// Target sample rate in Hz (default: 256000)
+        #[arg(long, default_value_t = 256_000)]
+        sample_rate: u32,
+
+        /// Sample format: 16, 24, or f32 (default: 16)
+        #[arg(long, default_value = "16")]
+        format: String,
+
+        /// Reconvert files that already exist in the output directory
+        #[arg(long)]
+        force: bool,
+    },
+}
+
+fn print_defaults(defaults: &key_store::BrowseDefaults) {
+    println!("Group:    {}", defaults.group.as_deref().unwrap_or("(not set)"));
+    println!("Country:  {}", defaults.country.as_deref().unwrap_or("(not set)"));
 }
 
 fn require_api_key(explicit: &Option<String>) -> String {
@@ -200,6 +242,43 @@ async fn main() {
             }
         }
 
+        Commands::SetDefault { group, country } => {
+            if group.is_none() && country.is_none() {
+                eprintln!("Nothing to set. Use --group and/or --country.");
+                std::process::exit(1);
+            }
+            let mut defaults = key_store::load_defaults();
+            if let Some(g) = group {
+                let g = g.trim().to_string();
+                if !g.is_empty() && !XC_GROUPS.contains(&g.as_str()) {
+                    eprintln!("Unknown group '{g}'. Available: {}", XC_GROUPS.join(", "));
+                    std::process::exit(1);
+                }
+                defaults.group = Some(g).filter(|g| !g.is_empty());
+            }
+            if let Some(c) = country {
+                defaults.country = Some(c.trim().to_string()).filter(|c| !c.is_empty());
+            }
+            match key_store::save_defaults(&defaults) {
+                Ok(path) => {
+                    println!("Defaults saved to {}", path.display());
+                    print_defaults(&defaults);
+                }
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
+        Commands::ShowDefault => {
+            match key_store::defaults_path() {
+                Some(path) => println!("Defaults file: {}", path.display()),
+                None => println!("Could not determine config directory"),
+            }
+            print_defaults(&key_store::load_defaults());
+        }
+
         Commands::Fetch {
             recording,
             metadata_only,
@@ -272,6 +351,15 @@ async fn main() {
             refresh,
             cache_dir,
         } => {
+            // Explicit arguments win over stored defaults
+            let defaults = key_store::load_defaults();
+            let Some(group) = group.or(defaults.group) else {
+                eprintln!("Group required. Pass one (e.g. xc-fetch browse bats) or save a default:");
+                eprintln!("  xc-fetch set-default --group bats");
+                std::process::exit(1);
+            };
+            let country = country.or(defaults.country).filter(|c| !c.is_empty());
+
             if !XC_GROUPS.contains(&group.as_str()) {
                 eprintln!(
                     "Unknown group '{group}'. Available: {}",
@@ -301,8 +389,8 @@ async fn main() {
                 &api_key,
                 &group,
                 country_ref,
-                |page, total| {
-                    eprint!("\rPage {page}/{total}...");
+                |p| {
+                    eprint!("\rPage {}/{}: {} species...", p.page, p.total_pages, p.species_found);
                 },
             )
             .await
@@ -342,7 +430,7 @@ async fn main() {
                     eprintln!("Fetching bat species list...");
                     let tax = taxonomy::build_species_list(
                         &client, &api_key, "bats", None,
-                        |page, total| { eprint!("\rPage {page}/{total}..."); },
+                        |p| { eprint!("\rPage {}/{}: {} species...", p.page, p.total_pages, p.species_found); },
                     )
                     .await
                     .unwrap_or_else(|e| {
@@ -799,6 +887,54 @@ async fn main() {
             );
         }
 
+        Commands::Convert { out_dir, cache_dir, sample_rate, format, force } => {
+            let root = cache_dir.unwrap_or_else(|| PathBuf::from("."));
+            if !root.join("sounds").exists() {
+                eprintln!("No sounds/ directory found at {}", root.display());
+                std::process::exit(1);
+            }
+            let Some(format) = SampleFormat::from_key(&format) else {
+                eprintln!("Unknown format: {format} (expected 16, 24, or f32)");
+                std::process::exit(1);
+            };
+            if sample_rate == 0 {
+                eprintln!("Sample rate must be positive");
+                std::process::exit(1);
+            }
+            let spec = ConvertSpec { sample_rate, format };
+
+            eprintln!(
+                "Converting to {} Hz {} mono WAV in {}",
+                sample_rate,
+                format.label(),
+                out_dir.display()
+            );
+
+            let result = cache::convert_cached_recordings(
+                &root,
+                &out_dir,
+                "wav",
+                force,
+                |bytes| convert_bytes(bytes, spec),
+                |i, total, rec, outcome| match outcome {
+                    cache::ConvertOutcome::Converted => eprintln!("[{i}/{total}] {}", rec.stem),
+                    cache::ConvertOutcome::Skipped => eprintln!("[{i}/{total}] {} (already converted)", rec.stem),
+                    cache::ConvertOutcome::Failed(e) => eprintln!("[{i}/{total}] {}: {e}", rec.stem),
+                },
+            );
+
+            match result {
+                Ok(summary) => println!(
+                    "Done. Converted: {}, Already converted: {}, Errors: {}",
+                    summary.converted, summary.skipped, summary.failed
+                ),
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    std::process::exit(1);
+                }
+            }
+        }
+
         Commands::Delete {
             recording,
             cache_dir,
@@ -1,7 +1,11 @@
+use crate::error::XcError;
 use crate::types::{XcRecording, XcSearchResult};
 
 const API_BASE: &str = "https://xeno-canto.org/api/3/recordings";
 
+/// Site root that relative recording URLs resolve against.
+const SITE_ROOT: &str = "https://xeno-canto.org";
+
 /// Parse an XC recording from API JSON.
 fn parse_recording(rec: &serde_json::Value) -> Option<XcRecording> {
     let _id = rec["id"].as_str()?; // ensure id exists
@@ -32,7 +36,9 @@ fn parse_recording(rec: &serde_json::Value) -> Option<XcRecording> {
         stage: s("stage"),
         method: s("method"),
         url: s("url"),
-        file_url: s("file"),
+        // Keep the raw value if it can't be normalised; download_audio
+        // reports why.
+        file_url: normalize_file_url(&s("file")).unwrap_or_else(|_| s("file")),
         file_name: s("file-name"),
         lic: s("lic"),
         q: s("q"),
@@ -50,13 +56,73 @@ fn parse_recording(rec: &serde_json::Value) -> Option<XcRecording> {
         dvc: s("dvc"),
         mic: s("mic"),
         smp: s("smp"),
+        sono_url: image_url(&rec["sono"]),
+        osci_url: image_url(&rec["osci"]),
     })
 }
 
+/// Image sizes of an XC `sono`/`osci` map, largest first.
+pub const IMAGE_SIZES: [&str; 4] = ["full", "large", "med", "small"];
+
+/// Pick the largest image from an XC `sono`/`osci` size map, or take the
+/// single URL a sidecar stores in its place. The API returns
+/// protocol-relative URLs (`//xeno-canto.org/...`), so these are
+/// normalised to https.
+pub fn image_url(sizes: &serde_json::Value) -> String {
+    let url = sizes
+        .as_str()
+        .or_else(|| IMAGE_SIZES.iter().find_map(|size| sizes[*size].as_str().filter(|u| !u.is_empty())))
+        .unwrap_or("");
+    if url.starts_with("//") {
+        format!("https:{url}")
+    } else {
+        url.to_string()
+    }
+}
+
+/// Turn the `file` URL of a recording into a clean absolute https link.
+///
+/// The v3 API normally returns `https://xeno-canto.org/{id}/download`, but
+/// older records and format changes have produced protocol-relative
+/// (`//xeno-canto.org/...`), plain http, scheme-less (`xeno-canto.org/...`)
+/// and site-relative (`/928094/download`) URLs. Relative paths resolve
+/// against the xeno-canto site root, not the API path.
+pub fn normalize_file_url(url: &str) -> Result<String, XcError> {
+    let url = url.trim();
+    if url.is_empty() {
+        return Err(XcError::Other("Recording has no download URL".into()));
+    }
+    let unusable = || XcError::Other(format!("Unusable download URL: {url}"));
+    if url.chars().any(char::is_whitespace) {
+        return Err(unusable());
+    }
+    let absolute = if let Some(rest) = url.strip_prefix("//") {
+        format!("https://{rest}")
+    } else if let Some(rest) = url.strip_prefix("http://") {
+        format!("https://{rest}")
+    } else if url.starts_with("https://") {
+        url.to_string()
+    } else if url.contains("://") {
+        // Some other scheme (ftp, file, ...)
+        return Err(unusable());
+    } else if url.starts_with('/') {
+        format!("{SITE_ROOT}{url}")
+    } else if url.starts_with("xeno-canto.org") || url.starts_with("www.xeno-canto.org") {
+        format!("https://{url}")
+    } else {
+        format!("{SITE_ROOT}/{url}")
+    };
+    let host = absolute["https://".len()..].split(['/', '?', '#']).next().unwrap_or("");
+    if host.is_empty() || !host.contains('.') {
+        return Err(unusable());
+    }
+    Ok(absolute)
+}
+
 /// Parse a search response from the XC API.
-fn parse_search_response(body: &serde_json::Value) -> Result<XcSearchResult, String> {
-    if let Some(err) = body.get("error") {
-        return Err(format!("API error: {}", err));
+fn parse_search_response(body: &serde_json::Value) -> Result<XcSearchResult, XcError> {
+    if body.get("error").is_some() {
+        return Err(XcError::from_api_error(200, body, "Search"));
     }
 
     let num_recordings = body["numRecordings"]
@@ -108,7 +174,7 @@ pub async fn search(
     query: &str,
     page: u32,
     per_page: u32,
-) -> Result<XcSearchResult, String> {
+) -> Result<XcSearchResult, XcError> {
     let query = normalize_query(query);
     let url = format!(
         "{}?query={}&key={}&page={}&per_page={}",
@@ -123,18 +189,20 @@ pub async fn search(
         .get(&url)
         .send()
         .await
-        .map_err(|e| format!("HTTP request failed: {e}"))?;
+        .map_err(|e| XcError::from_reqwest(&e, "Search"))?;
 
     if !resp.status().is_success() {
-        let status = resp.status();
-        let body = resp.text().await.unwrap_or_default();
-        return Err(format!("HTTP {status}: {body}"));
+        let status = resp.status().as_u16();
+        // The v3 API explains rejections (e.g. a bad key) in a JSON body
+        let text = resp.text().await.unwrap_or_default();
+        let body = serde_json::from_str(&text).unwrap_or(serde_json::Value::String(text));
+        return Err(XcError::from_api_error(status, &body, "Search"));
     }
 
     let body: serde_json::Value = resp
         .json()
         .await
-        .map_err(|e| format!("Failed to parse JSON: {e}"))?;
+        .map_err(|e| XcError::Other(format!("Failed to parse JSON: {e}")))?;
 
     parse_search_response(&body)
 }
@@ -144,42 +212,32 @@ pub async fn fetch_recording(
     client: &reqwest::Client,
     api_key: &str,
     id: u64,
-) -> Result<XcRecording, String> {
+) -> Result<XcRecording, XcError> {
     let result = search(client, api_key, &format!("nr:{id}"), 1, 50).await?;
     result
         .recordings
         .into_iter()
         .next()
-        .ok_or_else(|| format!("No recording found for XC{id}"))
+        .ok_or_else(|| XcError::NotFound(format!("No recording found for XC{id}")))
 }
 
 /// Download audio bytes for a recording.
 pub async fn download_audio(
     client: &reqwest::Client,
     file_url: &str,
-) -> Result<Vec<u8>, String> {
+) -> Result<Vec<u8>, XcError> {
+    let file_url = normalize_file_url(file_url)?;
     let resp = client
-        .get(file_url)
+        .get(&file_url)
         .send()
         .await
-        .map_err(|e| {
-            if e.is_timeout() {
-                "Download timed out — try again".to_string()
-            } else if e.is_connect() {
-                "Could not connect to server — check your internet connection".to_string()
-            } else {
-                format!("Download failed: {e}")
-            }
-        })?;
+        .map_err(|e| XcError::from_reqwest(&e, "Download"))?;
 
     if !resp.status().is_success() {
         let status = resp.status().as_u16();
-        return Err(match status {
-            401 | 403 => "Access denied — check your API key".into(),
-            404 => "Recording not found on server".into(),
-            429 => "Too many requests — wait a moment and try again".into(),
-            500..=599 => format!("Server error (HTTP {status}) — try again later"),
-            _ => format!("Download failed (HTTP {status})"),
+        return Err(match XcError::from_status(status, "Download") {
+            XcError::NotFound(_) => XcError::NotFound("Recording not found on server".into()),
+            e => e,
         });
     }
 
@@ -188,9 +246,9 @@ pub async fn download_audio(
         .map(|b| b.to_vec())
         .map_err(|e| {
             if e.is_timeout() {
-                "Download timed out while reading data — try again".to_string()
+                XcError::Network("Download timed out while reading data — try again".into())
             } else {
-                format!("Failed to read audio data: {e}")
+                XcError::Network(format!("Failed to read audio data: {e}"))
             }
         })
 }
@@ -260,3 +318,56 @@ pub fn parse_xc_number(input: &str) -> Result<u64, String> {
 
     Err(format!("Can't parse XC number from: {s}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CANONICAL: &str = "https://xeno-canto.org/928094/download";
+
+    #[test]
+    fn test_file_url_variants() {
+        for url in [
+            CANONICAL,
+            "//xeno-canto.org/928094/download",
+            "http://xeno-canto.org/928094/download",
+            "xeno-canto.org/928094/download",
+            "/928094/download",
+            "928094/download",
+            "  https://xeno-canto.org/928094/download\n",
+        ] {
+            assert_eq!(normalize_file_url(url).as_deref(), Ok(CANONICAL), "{url:?}");
+        }
+        assert_eq!(
+            normalize_file_url("//www.xeno-canto.org/sounds/uploaded/ABC/XC1-a.mp3").as_deref(),
+            Ok("https://www.xeno-canto.org/sounds/uploaded/ABC/XC1-a.mp3"),
+        );
+    }
+
+    #[test]
+    fn test_unusable_file_urls() {
+        for url in ["", "   ", "ftp://xeno-canto.org/1/download", "https:///1/download", "https://localhost/x", "//xeno canto.org/1"] {
+            assert!(matches!(normalize_file_url(url), Err(XcError::Other(_))), "{url:?}");
+        }
+    }
+
+    #[test]
+    fn test_parsed_recording_url_is_normalized() {
+        let rec = serde_json::json!({ "id": "928094", "file": "//xeno-canto.org/928094/download" });
+        assert_eq!(parse_recording(&rec).unwrap().file_url, CANONICAL);
+    }
+
+    #[test]
+    fn test_image_url_takes_largest_size() {
+        let sono = serde_json::json!({
+            "small": "//xeno-canto.org/sounds/uploaded/ABC/ffts/XC1-small.png",
+            "full": "//xeno-canto.org/sounds/uploaded/ABC/ffts/XC1-full.png",
+        });
+        assert_eq!(image_url(&sono), "https://xeno-canto.org/sounds/uploaded/ABC/ffts/XC1-full.png");
+        let osci = serde_json::json!({ "med": "", "small": "https://xeno-canto.org/o-small.png" });
+        assert_eq!(image_url(&osci), "https://xeno-canto.org/o-small.png");
+        // Sidecars store the chosen URL directly
+        assert_eq!(image_url(&serde_json::json!("https://xeno-canto.org/s.png")), "https://xeno-canto.org/s.png");
+        assert_eq!(image_url(&serde_json::Value::Null), "");
+    }
+}
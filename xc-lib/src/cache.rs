@@ -166,24 +166,51 @@ pub fn save_taxonomy(root: &Path, group: &str, country: Option<&str>, data: &XcG
     Ok(())
 }
 
-/// Check how old the taxonomy cache is (returns human-readable string).
-pub fn taxonomy_age_string(root: &Path, group: &str, country: Option<&str>) -> Option<String> {
+/// Cached taxonomy older than this many days is considered stale.
+pub const TAXONOMY_STALE_DAYS: i64 = 30;
+
+/// How old a group's cached taxonomy is.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct TaxonomyAge {
+    /// Human-readable age, e.g. "3 days ago".
+    pub label: String,
+    /// Older than [`TAXONOMY_STALE_DAYS`].
+    pub stale: bool,
+    /// [`TAXONOMY_STALE_DAYS`], so the UI can explain the flag.
+    pub stale_after_days: i64,
+}
+
+/// Check how old the taxonomy cache is. None if there is no cache.
+pub fn taxonomy_age(root: &Path, group: &str, country: Option<&str>) -> Option<TaxonomyAge> {
     let taxonomy = load_taxonomy(root, group, country).ok()??;
     let updated = chrono::DateTime::parse_from_rfc3339(&taxonomy.last_updated).ok()?;
     let now = chrono::Utc::now();
-    let duration = now.signed_duration_since(updated);
+    Some(age_from_duration(now.signed_duration_since(updated)))
+}
 
+/// Label and stale flag for a cache last updated `duration` ago.
+fn age_from_duration(duration: chrono::Duration) -> TaxonomyAge {
     let hours = duration.num_hours();
-    if hours < 1 {
-        Some("just now".to_string())
+    let label = if hours < 1 {
+        "just now".to_string()
     } else if hours < 24 {
-        Some(format!("{hours} hour{} ago", if hours == 1 { "" } else { "s" }))
+        format!("{hours} hour{} ago", if hours == 1 { "" } else { "s" })
     } else {
         let days = duration.num_days();
-        Some(format!("{days} day{} ago", if days == 1 { "" } else { "s" }))
+        format!("{days} day{} ago", if days == 1 { "" } else { "s" })
+    };
+    TaxonomyAge {
+        label,
+        stale: duration.num_days() >= TAXONOMY_STALE_DAYS,
+        stale_after_days: TAXONOMY_STALE_DAYS,
     }
 }
 
+/// Check how old the taxonomy cache is (returns human-readable string).
+pub fn taxonomy_age_string(root: &Path, group: &str, country: Option<&str>) -> Option<String> {
+    taxonomy_age(root, group, country).map(|age| age.label)
+}
+
 /// Check if a recording's audio is already cached.
 pub fn is_recording_cached(root: &Path, id: u64) -> bool {
     let sounds_dir = root.join("sounds");
@@ -482,6 +509,8 @@ pub fn build_metadata_json(rec: &XcRecording) -> serde_json::Value {
         "q": &rec.q,
         "length": &rec.length,
         "smp": rec.smp.parse::<u64>().ok(),
+        "sono": &rec.sono_url,
+        "osci": &rec.osci_url,
         "lic": &rec.lic,
         "attribution": attribution,
         "retrieved": now,
@@ -605,6 +634,133 @@ pub fn delete_recording(root: &Path, id: u64) -> Result<Vec<String>, String> {
     Ok(deleted)
 }
 
+/// A cached recording: its audio file and `.xc.json` sidecar.
+#[derive(Clone, Debug)]
+pub struct CachedRecording {
+    pub stem: String,
+    pub audio_path: PathBuf,
+    pub sidecar_path: PathBuf,
+}
+
+/// List every recording in `sounds/` that has both a sidecar and an audio
+/// file, sorted by stem.
+pub fn list_cached_recordings(root: &Path) -> Vec<CachedRecording> {
+    let sounds_dir = root.join("sounds");
+    let Ok(entries) = fs::read_dir(&sounds_dir) else {
+        return Vec::new();
+    };
+    let names: Vec<String> = entries
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+
+    let mut recordings: Vec<CachedRecording> = names
+        .iter()
+        .filter_map(|name| {
+            let stem = name.strip_suffix(".xc.json")?;
+            let audio = names.iter().find(|n| {
+                !n.ends_with(".xc.json")
+                    && n.rsplit_once('.').is_some_and(|(s, _)| s == stem)
+            })?;
+            Some(CachedRecording {
+                stem: stem.to_string(),
+                audio_path: sounds_dir.join(audio),
+                sidecar_path: sounds_dir.join(name),
+            })
+        })
+        .collect();
+    recordings.sort_by(|a, b| a.stem.cmp(&b.stem));
+    recordings
+}
+
+/// Result of converting one recording in [`convert_cached_recordings`].
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConvertOutcome {
+    Converted,
+    /// Output and sidecar already exist in the target directory.
+    Skipped,
+    Failed(String),
+}
+
+/// Totals from [`convert_cached_recordings`].
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize)]
+pub struct ConvertSummary {
+    pub converted: u32,
+    pub skipped: u32,
+    pub failed: u32,
+}
+
+/// Convert every cached recording into `out_dir` as `<stem>.<out_ext>`,
+/// copying its `.xc.json` sidecar alongside.
+///
+/// `convert` maps the original file's bytes to the output file's bytes, so
+/// the codec lives with the caller. Recordings whose output and sidecar
+/// already exist are skipped unless `force` is set. Audio is written to a
+/// `.part` file and renamed, so an interrupted run never leaves a truncated
+/// output that a later run would skip. `progress` is called after each
+/// recording with its 1-based index and the total. `out_dir` must not be the
+/// cache's own sounds directory, where outputs would replace their inputs.
+pub fn convert_cached_recordings(
+    root: &Path,
+    out_dir: &Path,
+    out_ext: &str,
+    force: bool,
+    mut convert: impl FnMut(&[u8]) -> Result<Vec<u8>, String>,
+    mut progress: impl FnMut(usize, usize, &CachedRecording, &ConvertOutcome),
+) -> Result<ConvertSummary, String> {
+    fs::create_dir_all(out_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", out_dir.display()))?;
+    let sounds_dir = root.join("sounds");
+    if let (Ok(out), Ok(sounds)) = (fs::canonicalize(out_dir), fs::canonicalize(&sounds_dir)) {
+        if out == sounds {
+            return Err(format!(
+                "Output directory {} is the cache's sounds directory; choose another",
+                out_dir.display()
+            ));
+        }
+    }
+
+    let recordings = list_cached_recordings(root);
+    let total = recordings.len();
+    let mut summary = ConvertSummary::default();
+
+    for (i, rec) in recordings.iter().enumerate() {
+        let out_path = out_dir.join(format!("{}.{out_ext}", rec.stem));
+        let out_sidecar = out_dir.join(format!("{}.xc.json", rec.stem));
+
+        let outcome = if !force && out_path.exists() && out_sidecar.exists() {
+            ConvertOutcome::Skipped
+        } else {
+            let result = fs::read(&rec.audio_path)
+                .map_err(|e| format!("Failed to read audio: {e}"))
+                .and_then(|bytes| convert(&bytes))
+                .and_then(|converted| {
+                    let part_path = out_dir.join(format!("{}.{out_ext}.part", rec.stem));
+                    fs::write(&part_path, &converted)
+                        .map_err(|e| format!("Failed to write output: {e}"))?;
+                    fs::rename(&part_path, &out_path)
+                        .map_err(|e| format!("Failed to finalize output: {e}"))?;
+                    fs::copy(&rec.sidecar_path, &out_sidecar)
+                        .map_err(|e| format!("Failed to copy sidecar: {e}"))?;
+                    Ok(())
+                });
+            match result {
+                Ok(()) => ConvertOutcome::Converted,
+                Err(e) => ConvertOutcome::Failed(e),
+            }
+        };
+
+        match outcome {
+            ConvertOutcome::Converted => summary.converted += 1,
+            ConvertOutcome::Skipped => summary.skipped += 1,
+            ConvertOutcome::Failed(_) => summary.failed += 1,
+        }
+        progress(i + 1, total, rec, &outcome);
+    }
+
+    Ok(summary)
+}
+
 /// Remove a recording entry from index.json by XC ID.
 fn remove_from_index(root: &Path, id: u64) -> Result<(), String> {
     let index_path = root.join("index.json");
@@ -700,3 +856,23 @@ fn update_index(
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_taxonomy_age_labels_and_staleness() {
+        assert_eq!(age_from_duration(Duration::minutes(20)).label, "just now");
+        assert_eq!(age_from_duration(Duration::hours(1)).label, "1 hour ago");
+        assert_eq!(age_from_duration(Duration::hours(30)).label, "1 day ago");
+
+        let fresh = age_from_duration(Duration::days(TAXONOMY_STALE_DAYS) - Duration::hours(1));
+        assert!(!fresh.stale);
+        let stale = age_from_duration(Duration::days(TAXONOMY_STALE_DAYS));
+        assert!(stale.stale);
+        assert_eq!(stale.label, format!("{TAXONOMY_STALE_DAYS} days ago"));
+        assert_eq!(stale.stale_after_days, TAXONOMY_STALE_DAYS);
+    }
+}
@@ -0,0 +1,180 @@
+use serde::{Deserialize, Serialize};
+
+/// Why an xeno-canto request failed, classified so a UI can act on it
+/// (e.g. prompt for a new API key on `Auth`) instead of showing a raw string.
+///
+/// Serializes as `{ "kind": "auth", "message": "..." }`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "message", rename_all = "snake_case")]
+pub enum XcError {
+    /// The API key is missing, wrong or expired.
+    Auth(String),
+    /// Too many requests; retry after a pause.
+    RateLimited(String),
+    /// The recording or resource doesn't exist.
+    NotFound(String),
+    /// Couldn't reach the server, the request timed out, or the server failed.
+    Network(String),
+    /// Offline mode is on, so no request was made.
+    Offline(String),
+    /// Anything else (bad input, unexpected response, local I/O).
+    Other(String),
+}
+
+impl XcError {
+    /// Classify a non-success HTTP status. `what` names the request for the
+    /// fallback message, e.g. "Search" or "Download".
+    pub fn from_status(status: u16, what: &str) -> Self {
+        match status {
+            401 | 403 => XcError::Auth("Access denied \u{2014} check your API key".into()),
+            404 => XcError::NotFound("Not found on server".into()),
+            429 => XcError::RateLimited("Too many requests \u{2014} wait a moment and try again".into()),
+            500..=599 => XcError::Network(format!("Server error (HTTP {status}) \u{2014} try again later")),
+            _ => XcError::Other(format!("{what} failed (HTTP {status})")),
+        }
+    }
+
+    /// Classify a transport error (timeout, connection failure, ...).
+    pub fn from_reqwest(e: &reqwest::Error, what: &str) -> Self {
+        if e.is_timeout() {
+            XcError::Network(format!("{what} timed out \u{2014} try again"))
+        } else if e.is_connect() {
+            XcError::Network("Could not connect to server \u{2014} check your internet connection".into())
+        } else if let Some(status) = e.status() {
+            XcError::from_status(status.as_u16(), what)
+        } else {
+            XcError::Network(format!("{what} failed: {e}"))
+        }
+    }
+
+    /// Classify an API error response by its HTTP `status`, keeping the
+    /// server's explanation from `body` in the message. `body` is the parsed
+    /// JSON (the v3 API sends `error` and `message` fields) or the raw text
+    /// as a JSON string. A success status with an `error` object is `Other`.
+    pub fn from_api_error(status: u16, body: &serde_json::Value, what: &str) -> Self {
+        let kind = if (200..300).contains(&status) {
+            XcError::Other(format!("{what} failed"))
+        } else {
+            XcError::from_status(status, what)
+        };
+        match api_error_detail(body) {
+            Some(detail) => kind.map_message(|m| format!("{m} ({detail})")),
+            None => kind,
+        }
+    }
+
+    fn map_message(self, f: impl FnOnce(String) -> String) -> Self {
+        match self {
+            XcError::Auth(m) => XcError::Auth(f(m)),
+            XcError::RateLimited(m) => XcError::RateLimited(f(m)),
+            XcError::NotFound(m) => XcError::NotFound(f(m)),
+            XcError::Network(m) => XcError::Network(f(m)),
+            XcError::Offline(m) => XcError::Offline(f(m)),
+            XcError::Other(m) => XcError::Other(f(m)),
+        }
+    }
+
+    /// Same kind, with `context` (e.g. "XC12345") prefixed to the message.
+    pub fn context(self, context: &str) -> Self {
+        self.map_message(|m| format!("{context}: {m}"))
+    }
+
+    pub fn message(&self) -> &str {
+        match self {
+            XcError::Auth(m)
+            | XcError::RateLimited(m)
+            | XcError::NotFound(m)
+            | XcError::Network(m)
+            | XcError::Offline(m)
+            | XcError::Other(m) => m,
+        }
+    }
+}
+
+impl std::fmt::Display for XcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message())
+    }
+}
+
+impl std::error::Error for XcError {}
+
+/// Longest stretch of a non-JSON error body kept in a message.
+const MAX_BODY_DETAIL: usize = 200;
+
+/// The explanation in an error response body: `error` and `message` fields
+/// of a JSON object, or the start of a plain-text body.
+fn api_error_detail(body: &serde_json::Value) -> Option<String> {
+    let field = |key: &str| {
+        body.get(key)
+            .map(|v| v.as_str().map(str::to_string).unwrap_or_else(|| v.to_string()))
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+    };
+    let detail = match body {
+        serde_json::Value::Object(_) => match (field("error"), field("message")) {
+            (Some(error), Some(message)) => format!("{error}: {message}"),
+            (error, message) => error.or(message)?,
+        },
+        serde_json::Value::String(text) => {
+            let text = text.trim();
+            match text.char_indices().nth(MAX_BODY_DETAIL) {
+                Some((cut, _)) => format!("{}\u{2026}", &text[..cut]),
+                None => text.to_string(),
+            }
+        }
+        _ => return None,
+    };
+    (!detail.is_empty()).then_some(detail)
+}
+
+impl From<String> for XcError {
+    fn from(s: String) -> Self {
+        XcError::Other(s)
+    }
+}
+
+impl From<XcError> for String {
+    fn from(e: XcError) -> Self {
+        e.message().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_api_error_classified_by_status() {
+        let body = json!({ "error": "client_error", "message": "Missing or invalid 'key' parameter" });
+        let e = XcError::from_api_error(401, &body, "Search");
+        assert!(matches!(e, XcError::Auth(_)), "{e:?}");
+        assert!(e.message().contains("Missing or invalid 'key' parameter"), "{e}");
+
+        // Mentioning "key" doesn't make a bad query an auth failure
+        let body = json!({ "error": "client_error", "message": "Unknown search key 'sp'" });
+        let e = XcError::from_api_error(400, &body, "Search");
+        assert!(matches!(e, XcError::Other(_)), "{e:?}");
+        assert!(e.message().contains("Unknown search key 'sp'"), "{e}");
+
+        assert!(matches!(XcError::from_api_error(429, &body, "Search"), XcError::RateLimited(_)));
+        assert!(matches!(XcError::from_api_error(503, &body, "Search"), XcError::Network(_)));
+        assert!(matches!(XcError::from_api_error(200, &json!({ "error": "key" }), "Search"), XcError::Other(_)));
+    }
+
+    #[test]
+    fn test_api_error_keeps_body_detail() {
+        let e = XcError::from_api_error(404, &json!({ "message": "No such recording" }), "Search");
+        assert_eq!(e, XcError::NotFound("Not found on server (No such recording)".into()));
+
+        let html = serde_json::Value::String(format!("<html>{}</html>", "x".repeat(500)));
+        let e = XcError::from_api_error(502, &html, "Search");
+        assert!(e.message().contains("<html>xxx"), "{e}");
+        assert!(e.message().len() < 300, "{e}");
+
+        // Nothing useful in the body: the status message alone
+        assert_eq!(XcError::from_api_error(403, &json!({}), "Search"), XcError::from_status(403, "Search"));
+        assert_eq!(XcError::from_api_error(500, &json!(""), "Search"), XcError::from_status(500, "Search"));
+    }
+}
@@ -1,8 +1,19 @@
+use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
 /// The app identifier used by Tauri (must match tauri.conf.json).
 const APP_IDENTIFIER: &str = "com.oversample.app";
 const KEY_FILENAME: &str = "xc_api_key.txt";
+const DEFAULTS_FILENAME: &str = "xc_cli_defaults.json";
+
+/// Stored defaults for `xc-fetch browse`, used when the arguments are omitted.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct BrowseDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+}
 
 /// Resolve the directory where Tauri stores app config.
 ///
@@ -90,3 +101,30 @@ pub fn resolve_key(explicit: &Option<String>) -> Option<String> {
     }
     std::env::var("XC_API_KEY").ok().filter(|k| !k.is_empty())
 }
+
+/// Get the path to the stored browse defaults (next to the API key).
+pub fn defaults_path() -> Option<PathBuf> {
+    app_config_dir().map(|d| d.join(DEFAULTS_FILENAME))
+}
+
+/// Read the stored browse defaults. Missing or unreadable files give empty defaults.
+pub fn load_defaults() -> BrowseDefaults {
+    defaults_path()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|s| serde_json::from_str(&s).ok())
+        .unwrap_or_default()
+}
+
+/// Save browse defaults to the shared config location.
+pub fn save_defaults(defaults: &BrowseDefaults) -> Result<PathBuf, String> {
+    let path = defaults_path().ok_or("Could not determine config directory")?;
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create config dir: {e}"))?;
+    }
+    let json = serde_json::to_string_pretty(defaults)
+        .map_err(|e| format!("Failed to serialize defaults: {e}"))?;
+    std::fs::write(&path, format!("{json}\n"))
+        .map_err(|e| format!("Failed to write defaults: {e}"))?;
+    Ok(path)
+}
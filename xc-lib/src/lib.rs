@@ -1,7 +1,9 @@
 pub mod api;
 pub mod cache;
+pub mod error;
 pub mod key_store;
 pub mod taxonomy;
 pub mod types;
 
+pub use error::XcError;
 pub use types::*;
@@ -1,19 +1,34 @@
 use std::collections::HashMap;
+use serde::Serialize;
 use crate::api;
+use crate::error::XcError;
 use crate::types::{XcGroupTaxonomy, XcSpecies};
 
+/// Partial result reported after each page of [`build_species_list`].
+#[derive(Clone, Debug, Serialize)]
+pub struct TaxonomyProgress {
+    pub page: u32,
+    pub total_pages: u32,
+    /// Distinct species seen so far.
+    pub species_found: usize,
+    /// Species first seen on this page. `recording_count` only covers this
+    /// page; the final taxonomy has the full counts.
+    pub new_species: Vec<XcSpecies>,
+}
+
 /// Build a species list for a group by paginating through all API results.
 ///
-/// The `on_progress` callback receives `(pages_fetched, total_pages)`.
+/// The `on_progress` callback runs after every page so callers can show
+/// species as they are discovered rather than waiting for the last page.
 pub async fn build_species_list<F>(
     client: &reqwest::Client,
     api_key: &str,
     group: &str,
     country: Option<&str>,
     mut on_progress: F,
-) -> Result<XcGroupTaxonomy, String>
+) -> Result<XcGroupTaxonomy, XcError>
 where
-    F: FnMut(u32, u32),
+    F: FnMut(&TaxonomyProgress),
 {
     let mut query = format!("grp:{group}");
     if let Some(cnt) = country {
@@ -32,16 +47,31 @@ where
         let result = api::search(client, api_key, &query, page, per_page).await?;
         total_pages = result.num_pages;
         total_recordings = result.num_recordings;
-        on_progress(page, total_pages);
 
+        let mut new_keys = Vec::new();
         for rec in &result.recordings {
             let key = (rec.genus.clone(), rec.sp.clone());
-            let entry = species_map.entry(key).or_insert_with(|| {
+            let entry = species_map.entry(key.clone()).or_insert_with(|| {
+                new_keys.push(key);
                 (rec.en.clone(), 0)
             });
             entry.1 += 1;
         }
 
+        let new_species = new_keys
+            .into_iter()
+            .map(|(genus, sp)| {
+                let (en, count) = species_map[&(genus.clone(), sp.clone())].clone();
+                XcSpecies { genus, sp, en, fam: String::new(), recording_count: count }
+            })
+            .collect();
+        on_progress(&TaxonomyProgress {
+            page,
+            total_pages,
+            species_found: species_map.len(),
+            new_species,
+        });
+
         if page >= total_pages {
             break;
         }
@@ -75,6 +75,12 @@ pub struct XcRecording {
     pub mic: String,
     /// Sample rate (as string from API, e.g. "256000")
     pub smp: String,
+    /// Sonogram image URL (largest size offered by the API), empty if absent
+    #[serde(default)]
+    pub sono_url: String,
+    /// Oscillogram image URL (largest size offered by the API), empty if absent
+    #[serde(default)]
+    pub osci_url: String,
 }
 
 impl XcRecording {